@@ -20,6 +20,19 @@ pub struct Ingester2Config {
     )]
     pub wal_rotation_period_seconds: u64,
 
+    /// Abort startup if a WAL file cannot be read in full during replay.
+    ///
+    /// By default, a corrupt WAL file (such as one with a torn final write
+    /// after a crash) has all complete entries before the corruption replayed,
+    /// and is then moved into a "quarantine" directory within the WAL
+    /// directory.
+    #[clap(
+        long = "wal-replay-strict",
+        env = "INFLUXDB_IOX_WAL_REPLAY_STRICT",
+        action
+    )]
+    pub wal_replay_strict: bool,
+
     /// Sets how many queries the ingester will handle simultaneously before
     /// rejecting further incoming requests.
     #[clap(
@@ -60,4 +73,28 @@ pub struct Ingester2Config {
         action
     )]
     pub persist_hot_partition_cost: usize,
+
+    /// Persist a partition once it has not been written to for this number of
+    /// seconds, rather than waiting for the next WAL rotation.
+    ///
+    /// If not specified, idle partitions are persisted at WAL rotation.
+    #[clap(
+        long = "persist-idle-partition-seconds",
+        env = "INFLUXDB_IOX_PERSIST_IDLE_PARTITION_SECONDS",
+        action
+    )]
+    pub persist_idle_partition_seconds: Option<u64>,
+
+    /// The limit at which the sum of the estimated persistence cost of all
+    /// buffered partitions causes the largest partitions to be queued for
+    /// persistence, until the total is below this limit.
+    ///
+    /// If not specified, the total buffered data is not limited between WAL
+    /// rotations.
+    #[clap(
+        long = "persist-buffer-watermark",
+        env = "INFLUXDB_IOX_PERSIST_BUFFER_WATERMARK",
+        action
+    )]
+    pub persist_buffer_watermark: Option<usize>,
 }
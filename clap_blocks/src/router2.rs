@@ -130,6 +130,35 @@ pub struct Router2Config {
     /// write failure.
     #[clap(long = "rpc-write-replicas", env = "INFLUXDB_IOX_RPC_WRITE_REPLICAS")]
     pub rpc_write_replicas: Option<NonZeroUsize>,
+
+    /// Accept the valid lines of a line protocol write when some lines fail
+    /// to parse or validate.
+    ///
+    /// When enabled, the rejected lines are skipped, the rest of the write is
+    /// applied, and a 400 response listing each rejected line and the reason
+    /// it was rejected is returned. When disabled, the whole write is
+    /// rejected.
+    #[clap(
+        long = "partial-writes-enabled",
+        env = "INFLUXDB_IOX_PARTIAL_WRITES_ENABLED",
+        default_value = "false",
+        action
+    )]
+    pub partial_writes_enabled: bool,
+
+    /// The number of seconds a cached namespace schema is used for before it
+    /// is reloaded from the catalog.
+    ///
+    /// Changes made to a namespace through another router (such as deleting
+    /// or renaming tables and columns, or changing limits) are observed by
+    /// this router within this duration.
+    #[clap(
+        long = "namespace-cache-ttl-seconds",
+        env = "INFLUXDB_IOX_NAMESPACE_CACHE_TTL_SECONDS",
+        default_value = "60",
+        value_parser = parse_duration
+    )]
+    pub namespace_cache_ttl_seconds: Duration,
 }
 
 /// Map a string containing an integer number of seconds into a [`Duration`].
@@ -21,7 +21,7 @@ use crate::{partition_info::PartitionInfo, plan_ir::FileIR};
 pub struct QueryableParquetChunk {
     // Data of the parquet file
     data: Arc<ParquetChunk>,
-    // We do not yet support delete but we need this to work with the straight QueryChunkMeta
+    // Delete predicates of the tombstones not yet applied to the file
     delete_predicates: Vec<Arc<DeletePredicate>>,
     partition_id: PartitionId,
     sort_key: Option<SortKey>,
@@ -39,6 +39,7 @@ impl QueryableParquetChunk {
         sort_key: Option<SortKey>,
         partition_sort_key: Option<SortKey>,
         order: ChunkOrder,
+        delete_predicates: Vec<Arc<DeletePredicate>>,
     ) -> Self {
         let summary = Arc::new(create_basic_summary(
             data.rows() as u64,
@@ -47,7 +48,7 @@ impl QueryableParquetChunk {
         ));
         Self {
             data,
-            delete_predicates: vec![],
+            delete_predicates,
             partition_id,
             sort_key,
             partition_sort_key,
@@ -224,5 +225,6 @@ fn to_queryable_parquet_chunk(
         sort_key,
         partition_info.sort_key.clone(),
         file.order,
+        partition_info.delete_predicates_for(&file.file),
     )
 }
@@ -88,6 +88,7 @@ use super::{
         split_compact::SplitCompact,
     },
     tables_source::catalog::CatalogTablesSource,
+    tombstones_source::catalog::CatalogTombstonesSource,
     Components,
 };
 
@@ -259,6 +260,7 @@ fn make_partition_info_source(config: &Config) -> Arc<dyn PartitionInfoSource> {
         )),
         CatalogTablesSource::new(config.backoff_config.clone(), Arc::clone(&config.catalog)),
         CatalogNamespacesSource::new(config.backoff_config.clone(), Arc::clone(&config.catalog)),
+        CatalogTombstonesSource::new(config.backoff_config.clone(), Arc::clone(&config.catalog)),
     ))
 }
 
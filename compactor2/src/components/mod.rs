@@ -42,6 +42,7 @@ pub mod skipped_compactions_source;
 pub mod split_or_compact;
 pub mod tables_source;
 pub mod timeout;
+pub mod tombstones_source;
 
 /// Pluggable system to determine compactor behavior. Please see
 /// [Crate Level Documentation](crate) for more details on the
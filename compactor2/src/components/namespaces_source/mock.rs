@@ -148,6 +148,7 @@ mod tests {
                                 },
                             ),
                         ]),
+                        partition_template: None,
                     },
                 ),
                 (
@@ -177,6 +178,7 @@ mod tests {
                                 },
                             ),
                         ]),
+                        partition_template: None,
                     },
                 ),
             ]);
@@ -195,6 +197,9 @@ mod tests {
                         max_columns_per_table: 10,
                         retention_period_ns: None,
                         deleted_at: None,
+                        partition_template: None,
+                        max_lines_per_second: None,
+                        max_bytes_per_second: None,
                     },
                     schema: NamespaceSchema {
                         id,
@@ -204,6 +209,9 @@ mod tests {
                         max_columns_per_table: 10,
                         max_tables: 42,
                         retention_period_ns: None,
+                        partition_template: None,
+                        max_lines_per_second: None,
+                        max_bytes_per_second: None,
                     },
                 },
             }
@@ -84,6 +84,7 @@ impl ParquetFileSink for MockParquetFileSink {
             created_at: Timestamp::new(1),
             column_set: ColumnSet::new(vec![]),
             max_l0_created_at: max_l0_created_at.into(),
+            max_tombstone_sequence_number: partition.max_tombstone_sequence_number,
         });
         guard.push(StoredFile {
             batches,
@@ -181,6 +182,7 @@ mod tests {
                 created_at: Timestamp::new(1),
                 column_set: ColumnSet::new([]),
                 max_l0_created_at: max_l0_created_at.into(),
+                max_tombstone_sequence_number: SequenceNumber::new(0),
             }),
         );
 
@@ -245,6 +247,7 @@ mod tests {
                 created_at: Timestamp::new(1),
                 column_set: ColumnSet::new([]),
                 max_l0_created_at: max_l0_created_at.into(),
+                max_tombstone_sequence_number: SequenceNumber::new(0),
             }),
         );
 
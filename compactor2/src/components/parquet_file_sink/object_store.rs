@@ -88,15 +88,21 @@ impl ParquetFileSink for ObjectStoreParquetFileSink {
             }
         };
 
-        let parquet_file =
-            meta.to_parquet_file(partition.partition_id, file_size, &parquet_meta, |name| {
+        // The rows of the compacted files have all tombstones of the table applied.
+        let parquet_file = meta.to_parquet_file(
+            partition.partition_id,
+            file_size,
+            &parquet_meta,
+            partition.max_tombstone_sequence_number,
+            |name| {
                 partition
                     .table_schema
                     .columns
                     .get(name)
                     .expect("unknown column")
                     .id
-            });
+            },
+        );
 
         Ok(Some(parquet_file))
     }
@@ -1,12 +1,14 @@
 use std::{fmt::Display, sync::Arc};
 
 use async_trait::async_trait;
-use data_types::PartitionId;
+use data_types::{PartitionId, SequenceNumber};
+use observability_deps::tracing::warn;
+use predicate::delete_predicate::{delete_predicate_for_columns, parse_tombstone_predicate};
 
 use crate::{
     components::{
         namespaces_source::NamespacesSource, partition_source::PartitionSource,
-        tables_source::TablesSource,
+        tables_source::TablesSource, tombstones_source::TombstonesSource,
     },
     error::DynError,
     partition_info::PartitionInfo,
@@ -15,53 +17,67 @@ use crate::{
 use super::PartitionInfoSource;
 
 #[derive(Debug)]
-pub struct SubSourcePartitionInfoSource<P, T, N>
+pub struct SubSourcePartitionInfoSource<P, T, N, D>
 where
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
     partition_source: P,
     tables_source: T,
     namespaces_source: N,
+    tombstones_source: D,
 }
 
-impl<P, T, N> SubSourcePartitionInfoSource<P, T, N>
+impl<P, T, N, D> SubSourcePartitionInfoSource<P, T, N, D>
 where
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
-    pub fn new(partition_source: P, tables_source: T, namespaces_source: N) -> Self {
+    pub fn new(
+        partition_source: P,
+        tables_source: T,
+        namespaces_source: N,
+        tombstones_source: D,
+    ) -> Self {
         Self {
             partition_source,
             tables_source,
             namespaces_source,
+            tombstones_source,
         }
     }
 }
 
-impl<P, T, N> Display for SubSourcePartitionInfoSource<P, T, N>
+impl<P, T, N, D> Display for SubSourcePartitionInfoSource<P, T, N, D>
 where
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "sub_sources(partition={}, tables={}, namespaces={})",
-            self.partition_source, self.tables_source, self.namespaces_source
+            "sub_sources(partition={}, tables={}, namespaces={}, tombstones={})",
+            self.partition_source,
+            self.tables_source,
+            self.namespaces_source,
+            self.tombstones_source
         )
     }
 }
 
 #[async_trait]
-impl<P, T, N> PartitionInfoSource for SubSourcePartitionInfoSource<P, T, N>
+impl<P, T, N, D> PartitionInfoSource for SubSourcePartitionInfoSource<P, T, N, D>
 where
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
     async fn fetch(&self, partition_id: PartitionId) -> Result<Arc<PartitionInfo>, DynError> {
         // Get info for the partition
@@ -96,6 +112,40 @@ where
             .get(&table.name)
             .ok_or_else::<DynError, _>(|| String::from("Cannot find table schema").into())?;
 
+        // Rows matching a tombstone are removed from the compacted output, unless the tombstone
+        // was already applied to the file they are read from. Predicates that cannot be parsed
+        // are skipped, consistent with the querier.
+        //
+        // The tombstones are fetched after the files of the partition, so every file is written
+        // with tombstones up to at most the most recent one fetched here applied.
+        let tombstones = self.tombstones_source.fetch(table.id).await;
+        let max_tombstone_sequence_number = tombstones
+            .iter()
+            .map(|t| t.sequence_number)
+            .max()
+            .unwrap_or_else(|| SequenceNumber::new(0));
+        let delete_predicates = tombstones
+            .into_iter()
+            .filter_map(|tombstone| match parse_tombstone_predicate(&tombstone) {
+                Ok(predicate) => Some((tombstone.sequence_number, Arc::new(predicate))),
+                Err(e) => {
+                    warn!(
+                        tombstone_id=%tombstone.id,
+                        table_id=%tombstone.table_id,
+                        %e,
+                        "ignoring tombstone with invalid predicate",
+                    );
+                    None
+                }
+            })
+            .filter_map(|(sequence_number, predicate)| {
+                delete_predicate_for_columns(&predicate, |column| {
+                    table_schema.columns.contains_key(column)
+                })
+                .map(|predicate| (sequence_number, predicate))
+            })
+            .collect();
+
         Ok(Arc::new(PartitionInfo {
             partition_id,
             namespace_id: table.namespace_id,
@@ -104,6 +154,8 @@ where
             table_schema: Arc::new(table_schema.clone()),
             sort_key: partition.sort_key(),
             partition_key: partition.partition_key,
+            max_tombstone_sequence_number,
+            delete_predicates,
         }))
     }
 }
@@ -0,0 +1,46 @@
+use std::{fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig};
+use data_types::{TableId, Tombstone};
+use iox_catalog::interface::Catalog;
+
+use super::TombstonesSource;
+
+#[derive(Debug)]
+pub struct CatalogTombstonesSource {
+    backoff_config: BackoffConfig,
+    catalog: Arc<dyn Catalog>,
+}
+
+impl CatalogTombstonesSource {
+    pub fn new(backoff_config: BackoffConfig, catalog: Arc<dyn Catalog>) -> Self {
+        Self {
+            backoff_config,
+            catalog,
+        }
+    }
+}
+
+impl Display for CatalogTombstonesSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "catalog")
+    }
+}
+
+#[async_trait]
+impl TombstonesSource for CatalogTombstonesSource {
+    async fn fetch(&self, table: TableId) -> Vec<Tombstone> {
+        Backoff::new(&self.backoff_config)
+            .retry_all_errors("tombstones_of_given_table_id", || async {
+                self.catalog
+                    .repositories()
+                    .await
+                    .tombstones()
+                    .list_by_table(table)
+                    .await
+            })
+            .await
+            .expect("retry forever")
+    }
+}
@@ -190,6 +190,8 @@ async fn try_compact_partition(
     transmit_progress_signal: Sender<bool>,
 ) -> Result<(), DynError> {
     let mut files = components.partition_files_source.fetch(partition_id).await;
+    // NB: The partition info must be fetched after the files, see `max_tombstone_sequence_number`
+    //     of `PartitionInfo`.
     let partition_info = components.partition_info_source.fetch(partition_id).await?;
 
     // loop for each "Round", consider each file in the partition
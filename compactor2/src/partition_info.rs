@@ -2,7 +2,10 @@
 
 use std::sync::Arc;
 
-use data_types::{NamespaceId, PartitionId, PartitionKey, Table, TableSchema};
+use data_types::{
+    DeletePredicate, NamespaceId, ParquetFile, PartitionId, PartitionKey, SequenceNumber, Table,
+    TableSchema,
+};
 use schema::sort::SortKey;
 
 /// Information about the Partition being compacted
@@ -28,6 +31,15 @@ pub struct PartitionInfo {
 
     /// partition_key
     pub partition_key: PartitionKey,
+
+    /// Sequence number of the most recent tombstone of the table.
+    ///
+    /// The files created by a compaction contain no row deleted by any tombstone up to it.
+    pub max_tombstone_sequence_number: SequenceNumber,
+
+    /// Delete predicates, with the sequence number of their tombstone, of the tombstones of the
+    /// table that can match rows of the table
+    pub delete_predicates: Vec<(SequenceNumber, Arc<DeletePredicate>)>,
 }
 
 impl PartitionInfo {
@@ -35,4 +47,14 @@ impl PartitionInfo {
     pub fn column_count(&self) -> usize {
         self.table_schema.column_count()
     }
+
+    /// Returns the delete predicates to apply when compacting the parquet file `file`, i.e. those
+    /// of the tombstones not yet applied to it
+    pub fn delete_predicates_for(&self, file: &ParquetFile) -> Vec<Arc<DeletePredicate>> {
+        self.delete_predicates
+            .iter()
+            .filter(|(sequence_number, _)| *sequence_number > file.max_tombstone_sequence_number)
+            .map(|(_, predicate)| Arc::clone(predicate))
+            .collect()
+    }
 }
@@ -1,8 +1,8 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use data_types::{
-    ColumnId, ColumnSchema, ColumnType, NamespaceId, PartitionId, PartitionKey, Table, TableId,
-    TableSchema,
+    ColumnId, ColumnSchema, ColumnType, NamespaceId, PartitionId, PartitionKey, SequenceNumber,
+    Table, TableId, TableSchema,
 };
 
 use crate::PartitionInfo;
@@ -26,13 +26,17 @@ impl PartitionInfoBuilder {
                     id: TableId::new(3),
                     namespace_id,
                     name: String::from("table"),
+                    partition_template: None,
                 }),
                 table_schema: Arc::new(TableSchema {
                     id: table_id,
                     columns: BTreeMap::new(),
+                    partition_template: None,
                 }),
                 sort_key: None,
                 partition_key: PartitionKey::from("key"),
+                max_tombstone_sequence_number: SequenceNumber::new(0),
+                delete_predicates: vec![],
             },
         }
     }
@@ -55,6 +59,7 @@ impl PartitionInfoBuilder {
         let table_schema = Arc::new(TableSchema {
             id: self.inner.table.id,
             columns,
+            partition_template: None,
         });
         self.inner.table_schema = table_schema;
 
@@ -1,5 +1,8 @@
 use arrow_util::assert_batches_sorted_eq;
-use data_types::{CompactionLevel, ParquetFile, PartitionId};
+use data_types::{
+    CompactionLevel, ParquetFile, PartitionId, Timestamp, MAX_NANO_TIME, MIN_NANO_TIME,
+    TRANSITION_SHARD_NUMBER,
+};
 
 use compactor2_test_utils::{format_files, list_object_store, TestSetup};
 
@@ -182,6 +185,84 @@ async fn test_compact_target_level() {
     );
 }
 
+#[tokio::test]
+async fn test_compact_with_tombstone() {
+    test_helpers::maybe_start_logging();
+
+    // Create a test setup with 6 files
+    let setup = TestSetup::builder()
+        .await
+        .with_files()
+        .await
+        // Ensure we have enough resource to compact the files
+        .with_max_num_files_per_plan(10)
+        .with_min_num_l1_files_to_compact(2)
+        .build()
+        .await;
+
+    // delete all rows with tag1 = VT and the row at time 25
+    let shard = setup
+        .table
+        .namespace
+        .create_shard(TRANSITION_SHARD_NUMBER)
+        .await;
+    let mut repos = setup.catalog.catalog().repositories().await;
+    let t1 = repos
+        .tombstones()
+        .create(
+            setup.table.table.id,
+            shard.shard.id,
+            Timestamp::new(MIN_NANO_TIME),
+            Timestamp::new(MAX_NANO_TIME),
+            r#""tag1"='VT'"#,
+        )
+        .await
+        .unwrap();
+    let t2 = repos
+        .tombstones()
+        .create(
+            setup.table.table.id,
+            shard.shard.id,
+            Timestamp::new(25_000),
+            Timestamp::new(25_000),
+            "",
+        )
+        .await
+        .unwrap();
+    drop(repos);
+
+    // compact
+    setup.run_compact().await;
+
+    // verify the deleted rows are removed from the compacted files
+    let mut batches = vec![];
+    for file in setup.list_by_table_not_to_delete().await {
+        batches.extend(setup.read_parquet_file(file).await);
+    }
+    assert_batches_sorted_eq!(
+        [
+            "+-----------+------+------+------+-----------------------------+",
+            "| field_int | tag1 | tag2 | tag3 | time                        |",
+            "+-----------+------+------+------+-----------------------------+",
+            "| 1500      | WA   |      |      | 1970-01-01T00:00:00.000008Z |",
+            "| 1601      |      | PA   | 15   | 1970-01-01T00:00:00.000030Z |",
+            "| 210       |      | OH   | 21   | 1970-01-01T00:00:00.000136Z |",
+            "| 22        |      | OH   | 21   | 1970-01-01T00:00:00.000036Z |",
+            "| 70        | UT   |      |      | 1970-01-01T00:00:00.000020Z |",
+            "| 99        | OR   |      |      | 1970-01-01T00:00:00.000012Z |",
+            "+-----------+------+------+------+-----------------------------+",
+        ],
+        &batches
+    );
+
+    // both tombstones are recorded as applied to every compacted file
+    for file in setup.list_by_table_not_to_delete().await {
+        assert!(!t1.applies_to(&file));
+        assert!(!t2.applies_to(&file));
+        assert_eq!(file.max_tombstone_sequence_number, t2.sequence_number);
+    }
+}
+
 #[tokio::test]
 async fn test_compact_large_overlapes() {
     test_helpers::maybe_start_logging();
@@ -32,7 +32,9 @@ use std::{
 
 use async_trait::async_trait;
 use backoff::BackoffConfig;
-use data_types::{ColumnType, CompactionLevel, ParquetFile, TableId, TRANSITION_SHARD_NUMBER};
+use data_types::{
+    ColumnType, CompactionLevel, ParquetFile, SequenceNumber, TableId, TRANSITION_SHARD_NUMBER,
+};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion_util::config::register_iox_object_store;
 use futures::TryStreamExt;
@@ -576,6 +578,8 @@ impl<const WITH_FILES: bool> TestSetupBuilder<WITH_FILES> {
             table_schema: Arc::new(self.table.catalog_schema().await),
             sort_key: self.partition.partition.sort_key(),
             partition_key: self.partition.partition.partition_key.clone(),
+            max_tombstone_sequence_number: SequenceNumber::new(0),
+            delete_predicates: vec![],
         });
 
         TestSetup {
@@ -216,6 +216,7 @@ impl SimulatedFile {
             created_at: Timestamp::new(1),
             column_set,
             max_l0_created_at: max_l0_created_at.into(),
+            max_tombstone_sequence_number: partition_info.max_tombstone_sequence_number,
         }
     }
 }
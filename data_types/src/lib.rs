@@ -24,7 +24,7 @@ use schema::{
     builder::SchemaBuilder, sort::SortKey, InfluxColumnType, InfluxFieldType, Schema,
     TIME_COLUMN_NAME,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sqlx::postgres::PgHasArrayType;
 use std::{
     borrow::Borrow,
@@ -404,6 +404,27 @@ impl std::fmt::Display for ParquetFileId {
     }
 }
 
+/// Unique ID for a `Tombstone`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct TombstoneId(i64);
+
+#[allow(missing_docs)]
+impl TombstoneId {
+    pub fn new(v: i64) -> Self {
+        Self(v)
+    }
+    pub fn get(&self) -> i64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for TombstoneId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Data object for a topic. When Kafka is used as the write buffer, this is the Kafka topic name
 /// plus a catalog-assigned ID.
 #[derive(Debug, Clone, Eq, PartialEq, sqlx::FromRow)]
@@ -443,6 +464,19 @@ pub struct Namespace {
     pub max_columns_per_table: i32,
     /// When this file was marked for deletion.
     pub deleted_at: Option<Timestamp>,
+    #[sqlx(default)]
+    /// The partition template for tables in this namespace. None means the
+    /// router's default template is used.
+    pub partition_template: Option<PartitionTemplate>,
+    #[sqlx(default)]
+    /// The maximum number of line protocol lines per second that can be
+    /// written to this namespace. None means no limit.
+    pub max_lines_per_second: Option<i64>,
+    #[sqlx(default)]
+    /// The maximum number of bytes per second that can be written to this
+    /// namespace, measured as the in-memory size of the decoded write. None
+    /// means no limit.
+    pub max_bytes_per_second: Option<i64>,
 }
 
 /// Schema collection for a namespace. This is an in-memory object useful for a schema
@@ -464,6 +498,15 @@ pub struct NamespaceSchema {
     /// The retention period in ns.
     /// None represents infinite duration (i.e. never drop data).
     pub retention_period_ns: Option<i64>,
+    /// The partition template for tables in this namespace, if one is set.
+    pub partition_template: Option<PartitionTemplate>,
+    /// The maximum number of line protocol lines per second that can be
+    /// written to this namespace, if limited.
+    pub max_lines_per_second: Option<u64>,
+    /// The maximum number of bytes per second that can be written to this
+    /// namespace, measured as the in-memory size of the decoded write, if
+    /// limited.
+    pub max_bytes_per_second: Option<u64>,
 }
 
 impl NamespaceSchema {
@@ -484,6 +527,9 @@ impl NamespaceSchema {
             max_columns_per_table: max_columns_per_table as usize,
             max_tables: max_tables as usize,
             retention_period_ns,
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
         }
     }
 
@@ -507,6 +553,12 @@ pub struct Table {
     pub namespace_id: NamespaceId,
     /// The name of the table, which is unique within the associated namespace
     pub name: String,
+    #[sqlx(default)]
+    /// The partition template for this table. None means the namespace's
+    /// template is used.
+    pub partition_template: Option<PartitionTemplate>,
+    /// When this table was marked for deletion.
+    pub deleted_at: Option<Timestamp>,
 }
 
 /// Column definitions for a table
@@ -516,6 +568,8 @@ pub struct TableSchema {
     pub id: TableId,
     /// the table's columns by their name
     pub columns: BTreeMap<String, ColumnSchema>,
+    /// the partition template of this table, if one is set
+    pub partition_template: Option<PartitionTemplate>,
 }
 
 impl TableSchema {
@@ -524,6 +578,16 @@ impl TableSchema {
         Self {
             id,
             columns: BTreeMap::new(),
+            partition_template: None,
+        }
+    }
+
+    /// Initialize a new, empty `TableSchema` for the given catalog [`Table`].
+    pub fn new_for_table(table: &Table) -> Self {
+        Self {
+            id: table.id,
+            columns: BTreeMap::new(),
+            partition_template: table.partition_template.clone(),
         }
     }
 
@@ -581,6 +645,8 @@ pub struct Column {
     pub name: String,
     /// the logical type of the column
     pub column_type: ColumnType,
+    /// When this column was marked for deletion.
+    pub deleted_at: Option<Timestamp>,
 }
 
 impl Column {
@@ -980,6 +1046,54 @@ pub struct SkippedCompaction {
     pub limit_num_files_first_in_partition: i64,
 }
 
+/// Data object for a tombstone: a delete of all rows of a table that fall
+/// within a time range and match a predicate.
+///
+/// A tombstone only applies to rows written before it was created. Each parquet
+/// file records the [`max_tombstone_sequence_number`] of the tombstones of its
+/// table already applied to its rows, so a tombstone is applied to a parquet
+/// file only if its [`sequence_number`] is greater, and rows written after the
+/// delete stay visible.
+///
+/// Tombstones are kept forever: nothing tracks when every parquet file of the
+/// table has a tombstone applied, after which it could be removed.
+///
+/// [`max_tombstone_sequence_number`]: ParquetFile::max_tombstone_sequence_number
+/// [`sequence_number`]: Tombstone::sequence_number
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
+pub struct Tombstone {
+    /// the id of the tombstone
+    pub id: TombstoneId,
+    /// the table the tombstone is associated with
+    pub table_id: TableId,
+    /// the shard the tombstone was sent through
+    pub shard_id: ShardId,
+    /// the sequence number assigned to the tombstone, increasing per table
+    pub sequence_number: SequenceNumber,
+    /// the min time (inclusive) that the delete applies to
+    pub min_time: Timestamp,
+    /// the max time (inclusive) that the delete applies to
+    pub max_time: Timestamp,
+    /// the full delete predicate, as returned by
+    /// [`DeletePredicate::expr_sql_string`]
+    pub serialized_predicate: String,
+    /// the time the tombstone was created
+    pub created_at: Timestamp,
+}
+
+impl Tombstone {
+    /// Estimate the memory consumption of this object and its contents
+    pub fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.serialized_predicate.capacity()
+    }
+
+    /// Return true if this tombstone applies to the rows of `file`, which is
+    /// the case unless it was already applied to them.
+    pub fn applies_to(&self, file: &ParquetFile) -> bool {
+        self.sequence_number > file.max_tombstone_sequence_number
+    }
+}
+
 /// Set of columns.
 #[derive(Debug, Clone, PartialEq, Eq, sqlx::Type)]
 #[sqlx(transparent)]
@@ -1089,6 +1203,10 @@ pub struct ParquetFile {
     pub column_set: ColumnSet,
     /// the max of created_at of all L0 files needed for file/chunk ordering for deduplication
     pub max_l0_created_at: Timestamp,
+    /// the sequence number of the most recent tombstone of the table that was
+    /// applied to the rows of this file before it was written; tombstones up to
+    /// and including this sequence number are not applied to it
+    pub max_tombstone_sequence_number: SequenceNumber,
 }
 
 impl ParquetFile {
@@ -1113,6 +1231,7 @@ impl ParquetFile {
             created_at: params.created_at,
             column_set: params.column_set,
             max_l0_created_at: params.max_l0_created_at,
+            max_tombstone_sequence_number: params.max_tombstone_sequence_number,
         }
     }
 
@@ -1164,6 +1283,9 @@ pub struct ParquetFileParams {
     pub column_set: ColumnSet,
     /// the max of created_at of all L0 files
     pub max_l0_created_at: Timestamp,
+    /// the sequence number of the most recent tombstone of the table applied
+    /// to the rows of this file
+    pub max_tombstone_sequence_number: SequenceNumber,
 }
 
 impl From<ParquetFile> for ParquetFileParams {
@@ -1183,6 +1305,7 @@ impl From<ParquetFile> for ParquetFileParams {
             created_at: value.created_at,
             column_set: value.column_set,
             max_l0_created_at: value.max_l0_created_at,
+            max_tombstone_sequence_number: value.max_tombstone_sequence_number,
         }
     }
 }
@@ -1277,15 +1400,68 @@ impl ChunkOrder {
 ///
 /// The key is constructed in order of the template parts; thus ordering changes
 /// what partition key is generated.
-#[derive(Debug, Default, Eq, PartialEq, Clone)]
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct PartitionTemplate {
     pub parts: Vec<TemplatePart>,
 }
 
+// Partition templates are stored in the catalog as their JSON serialisation.
+
+impl sqlx::Type<sqlx::Postgres> for PartitionTemplate {
+    fn type_info() -> sqlx::postgres::PgTypeInfo {
+        // Store this type as TEXT
+        sqlx::postgres::PgTypeInfo::with_name("TEXT")
+    }
+}
+
+impl sqlx::Encode<'_, sqlx::Postgres> for PartitionTemplate {
+    fn encode_by_ref(
+        &self,
+        buf: &mut <sqlx::Postgres as sqlx::database::HasArguments<'_>>::ArgumentBuffer,
+    ) -> sqlx::encode::IsNull {
+        let json = serde_json::to_string(self).expect("partition template is serialisable");
+        <String as sqlx::Encode<sqlx::Postgres>>::encode(json, buf)
+    }
+}
+
+impl sqlx::Decode<'_, sqlx::Postgres> for PartitionTemplate {
+    fn decode(
+        value: <sqlx::Postgres as sqlx::database::HasValueRef<'_>>::ValueRef,
+    ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+        let json = <&str as sqlx::Decode<sqlx::Postgres>>::decode(value)?;
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+impl sqlx::Type<sqlx::Sqlite> for PartitionTemplate {
+    fn type_info() -> sqlx::sqlite::SqliteTypeInfo {
+        <String as sqlx::Type<sqlx::Sqlite>>::type_info()
+    }
+}
+
+impl sqlx::Encode<'_, sqlx::Sqlite> for PartitionTemplate {
+    fn encode_by_ref(
+        &self,
+        buf: &mut <sqlx::Sqlite as sqlx::database::HasArguments<'_>>::ArgumentBuffer,
+    ) -> sqlx::encode::IsNull {
+        let json = serde_json::to_string(self).expect("partition template is serialisable");
+        <String as sqlx::Encode<sqlx::Sqlite>>::encode(json, buf)
+    }
+}
+
+impl sqlx::Decode<'_, sqlx::Sqlite> for PartitionTemplate {
+    fn decode(
+        value: <sqlx::Sqlite as sqlx::database::HasValueRef<'_>>::ValueRef,
+    ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+        let json = <String as sqlx::Decode<sqlx::Sqlite>>::decode(value)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+}
+
 /// `TemplatePart` specifies what part of a row should be used to compute this
 /// part of a partition key.
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub enum TemplatePart {
     /// The name of a table
     Table,
@@ -1305,7 +1481,12 @@ pub enum TemplatePart {
 
 /// `RegexCapture` is for pulling parts of a string column into the partition
 /// key.
-#[derive(Debug, Eq, PartialEq, Clone)]
+///
+/// The value of the named column is matched against `regex`, and the first
+/// capture group (or the whole match if the regex has no capture groups) is
+/// rendered as `<column>_<match>`. Null and non-matching values render as
+/// just the column name.
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct RegexCapture {
     pub column: String,
@@ -1320,8 +1501,8 @@ pub struct RegexCapture {
 ///
 /// For example, a time format of "%Y-%m-%d %H:%M:%S" will produce
 /// partition key parts such as "2021-03-14 12:25:21" and
-/// "2021-04-14 12:24:21"
-#[derive(Debug, Eq, PartialEq, Clone)]
+/// "2021-04-14 12:24:21". Null values render as just the column name.
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct StrftimeColumn {
     pub column: String,
@@ -3133,6 +3314,7 @@ mod tests {
         let schema1 = TableSchema {
             id: TableId::new(1),
             columns: BTreeMap::from([]),
+            partition_template: None,
         };
         let schema2 = TableSchema {
             id: TableId::new(2),
@@ -3143,6 +3325,7 @@ mod tests {
                     column_type: ColumnType::Bool,
                 },
             )]),
+            partition_template: None,
         };
         assert!(schema1.size() < schema2.size());
     }
@@ -3157,6 +3340,9 @@ mod tests {
             max_columns_per_table: 4,
             max_tables: 42,
             retention_period_ns: None,
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
         };
         let schema2 = NamespaceSchema {
             id: NamespaceId::new(1),
@@ -3166,6 +3352,9 @@ mod tests {
             max_columns_per_table: 4,
             max_tables: 42,
             retention_period_ns: None,
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
         };
         assert!(schema1.size() < schema2.size());
     }
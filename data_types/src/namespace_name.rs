@@ -3,6 +3,16 @@ use std::{borrow::Cow, ops::RangeInclusive};
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use thiserror::Error;
 
+/// The separator between the database and retention policy of an InfluxDB
+/// 1.x `db` / `rp` pair, when mapped to a [`NamespaceName`].
+///
+/// When a retention policy is provided, it is appended to the database name,
+/// separated by a single `/`. The database of a namespace name without the
+/// separator uses the default (`autogen`) retention policy.
+///
+/// See <https://github.com/influxdata/idpe/issues/17265>.
+pub const V1_NAMESPACE_RP_SEPARATOR: char = '/';
+
 /// Length constraints for a [`NamespaceName`] name.
 ///
 /// A `RangeInclusive` is a closed interval, covering [1, 64]
@@ -16,7 +16,7 @@
 use std::time::Duration;
 
 use data_types::{
-    DeletePredicate, NamespaceId, NonEmptyString, PartitionKey, Sequence, StatValues, Statistics,
+    DeletePredicate, NamespaceId, PartitionKey, Sequence, SequenceNumber, StatValues, Statistics,
     TableId,
 };
 use hashbrown::HashMap;
@@ -300,11 +300,15 @@ impl DmlWrite {
 }
 
 /// A delete operation
+///
+/// A delete is recorded as a tombstone in the catalog before it is applied to
+/// buffered data, and carries the sequence number the catalog assigned to it.
 #[derive(Debug, Clone, PartialEq)]
 pub struct DmlDelete {
     namespace_id: NamespaceId,
+    table_id: TableId,
     predicate: DeletePredicate,
-    table_name: Option<NonEmptyString>,
+    tombstone_sequence_number: SequenceNumber,
     meta: DmlMeta,
 }
 
@@ -312,21 +316,29 @@ impl DmlDelete {
     /// Create a new [`DmlDelete`]
     pub fn new(
         namespace_id: NamespaceId,
+        table_id: TableId,
         predicate: DeletePredicate,
-        table_name: Option<NonEmptyString>,
+        tombstone_sequence_number: SequenceNumber,
         meta: DmlMeta,
     ) -> Self {
         Self {
             namespace_id,
+            table_id,
             predicate,
-            table_name,
+            tombstone_sequence_number,
             meta,
         }
     }
 
-    /// Returns the table_name for this delete
-    pub fn table_name(&self) -> Option<&str> {
-        self.table_name.as_deref()
+    /// Returns the [`TableId`] of the table this delete applies to
+    pub fn table_id(&self) -> TableId {
+        self.table_id
+    }
+
+    /// Returns the catalog sequence number of the tombstone recording this
+    /// delete
+    pub fn tombstone_sequence_number(&self) -> SequenceNumber {
+        self.tombstone_sequence_number
     }
 
     /// Returns the [`DeletePredicate`]
@@ -349,11 +361,6 @@ impl DmlDelete {
     /// This includes `Self`.
     pub fn size(&self) -> usize {
         std::mem::size_of::<Self>() + self.predicate.size() - std::mem::size_of::<DeletePredicate>()
-            + self
-                .table_name
-                .as_ref()
-                .map(|s| s.len())
-                .unwrap_or_default()
             + self.meta.size()
             - std::mem::size_of::<DmlMeta>()
     }
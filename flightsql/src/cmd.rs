@@ -1,31 +1,94 @@
 //! IOx FlightSQL Command structures
 
-use std::fmt::Display;
+use std::{fmt::Display, io::Cursor, str::FromStr};
 
+use arrow::{
+    ipc::{reader::StreamReader, writer::StreamWriter},
+    record_batch::RecordBatch,
+};
 use arrow_flight::sql::{
     ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest, Any,
     CommandGetCatalogs, CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
     CommandGetImportedKeys, CommandGetPrimaryKeys, CommandGetSqlInfo, CommandGetTableTypes,
-    CommandGetTables, CommandPreparedStatementQuery, CommandStatementQuery,
+    CommandGetTables, CommandGetXdbcTypeInfo, CommandPreparedStatementQuery, CommandStatementQuery,
 };
 use bytes::Bytes;
 use prost::Message;
-use snafu::ResultExt;
+use snafu::{ensure, ResultExt};
+use uuid::Uuid;
 
 use crate::error::*;
 
+/// The query language of a FlightSQL statement.
+///
+/// Statements are SQL unless the client requests otherwise, for
+/// example to run saved InfluxQL queries from a BI tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueryLanguage {
+    /// SQL, planned by DataFusion
+    #[default]
+    Sql,
+    /// InfluxQL, planned by the IOx InfluxQL planner
+    InfluxQL,
+}
+
+impl QueryLanguage {
+    /// return the name of the query language, as accepted by
+    /// [`QueryLanguage::from_str`]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sql => "sql",
+            Self::InfluxQL => "influxql",
+        }
+    }
+}
+
+impl Display for QueryLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for QueryLanguage {
+    type Err = Error;
+
+    /// Parse a query language name, ignoring case
+    fn from_str(s: &str) -> Result<Self> {
+        if s.eq_ignore_ascii_case("sql") {
+            Ok(Self::Sql)
+        } else if s.eq_ignore_ascii_case("influxql") {
+            Ok(Self::InfluxQL)
+        } else {
+            InvalidQueryLanguageSnafu { language: s }.fail()
+        }
+    }
+}
+
 /// Represents a prepared statement "handle". IOx passes all state
 /// required to run the prepared statement back and forth to the
 /// client, so any querier instance can run it
 #[derive(Debug, Clone, PartialEq)]
 pub struct PreparedStatementHandle {
-    /// The raw SQL query text
+    /// The raw query text
     query: String,
+    /// The language of `query`
+    language: QueryLanguage,
+    /// Identifies this prepared statement, so that parameter values
+    /// bound by one client are not visible to another client that
+    /// prepared the same query
+    id: String,
+    /// The bound parameter values, if any, as a single row
+    parameters: Option<RecordBatch>,
 }
 
 impl PreparedStatementHandle {
-    pub fn new(query: String) -> Self {
-        Self { query }
+    pub fn new(query: String, language: QueryLanguage) -> Self {
+        Self {
+            query,
+            language,
+            id: Uuid::new_v4().to_string(),
+            parameters: None,
+        }
     }
 
     /// return the query
@@ -33,16 +96,105 @@ impl PreparedStatementHandle {
         self.query.as_ref()
     }
 
+    /// return the language of the query
+    pub fn language(&self) -> QueryLanguage {
+        self.language
+    }
+
+    /// return the unique identifier of this prepared statement
+    pub fn id(&self) -> &str {
+        self.id.as_ref()
+    }
+
+    /// return the bound parameter values, if any
+    pub fn parameters(&self) -> Option<&RecordBatch> {
+        self.parameters.as_ref()
+    }
+
+    /// Bind the parameter values in `parameters` to this prepared
+    /// statement. `parameters` must contain exactly one row, with
+    /// one column per placeholder in the query.
+    ///
+    /// InfluxQL prepared statements do not support parameters.
+    pub fn with_parameters(self, parameters: RecordBatch) -> Result<Self> {
+        ensure!(
+            self.language == QueryLanguage::Sql,
+            InvalidParametersSnafu {
+                description: format!(
+                    "{} prepared statements do not support parameters",
+                    self.language
+                )
+            }
+        );
+        ensure!(
+            parameters.num_rows() == 1,
+            InvalidParametersSnafu {
+                description: format!(
+                    "expected exactly one row of parameter values, got {}",
+                    parameters.num_rows()
+                )
+            }
+        );
+
+        Ok(Self {
+            parameters: Some(parameters),
+            ..self
+        })
+    }
+
     fn try_decode(handle: Bytes) -> Result<Self> {
-        // Note: in IOx  handles are the entire decoded query
-        // It will likely need to get more sophisticated as part of
-        // https://github.com/influxdata/influxdb_iox/issues/6699
-        let query = String::from_utf8(handle.to_vec()).context(InvalidHandleSnafu)?;
-        Ok(Self { query })
+        let PreparedStatementHandleMessage {
+            query,
+            id,
+            parameters,
+            language,
+        } = Message::decode(handle).context(InvalidHandleSnafu)?;
+
+        // handles created before the language was recorded are SQL
+        let language = if language.is_empty() {
+            QueryLanguage::Sql
+        } else {
+            language.parse()?
+        };
+
+        let parameters = if parameters.is_empty() {
+            None
+        } else {
+            Some(decode_parameters(parameters)?)
+        };
+
+        Ok(Self {
+            query,
+            language,
+            id,
+            parameters,
+        })
+    }
+
+    /// Encode this handle as Bytes
+    pub fn try_encode(self) -> Result<Bytes> {
+        let parameters = match &self.parameters {
+            Some(parameters) => encode_parameters(parameters)?,
+            None => Bytes::new(),
+        };
+
+        let msg = PreparedStatementHandleMessage {
+            query: self.query,
+            id: self.id,
+            parameters,
+            language: self.language.to_string(),
+        };
+        Ok(msg.encode_to_vec().into())
     }
 
-    fn encode(self) -> Bytes {
-        Bytes::from(self.query.into_bytes())
+    /// Encode this handle as the `app_metadata` of the `PutResult`
+    /// returned by a `DoPut` that bound its parameters, so the client
+    /// can execute it with the bound values on any querier
+    pub fn try_encode_put_result(self) -> Result<Bytes> {
+        let msg = DoPutPreparedStatementResultMessage {
+            prepared_statement_handle: Some(self.try_encode()?),
+        };
+        Ok(msg.encode_to_vec().into())
     }
 }
 
@@ -52,10 +204,51 @@ impl Display for PreparedStatementHandle {
     }
 }
 
-/// Encode a PreparedStatementHandle as Bytes
-impl From<PreparedStatementHandle> for Bytes {
-    fn from(value: PreparedStatementHandle) -> Self {
-        Bytes::from(value.query.into_bytes())
+/// The wire format of a [`PreparedStatementHandle`]
+#[derive(Clone, PartialEq, Message)]
+struct PreparedStatementHandleMessage {
+    #[prost(string, tag = "1")]
+    query: String,
+    #[prost(string, tag = "2")]
+    id: String,
+    /// The bound parameter values, as an Arrow IPC stream. Empty if
+    /// no parameters have been bound.
+    #[prost(bytes = "bytes", tag = "3")]
+    parameters: Bytes,
+    /// The query language, as a [`QueryLanguage`] name
+    #[prost(string, tag = "4")]
+    language: String,
+}
+
+/// The wire format of the `DoPut` result for a prepared statement,
+/// matching the FlightSQL `DoPutPreparedStatementResult` message
+#[derive(Clone, PartialEq, Message)]
+struct DoPutPreparedStatementResultMessage {
+    /// The handle with the bound parameter values, to be used in
+    /// place of the original handle
+    #[prost(bytes = "bytes", optional, tag = "1")]
+    prepared_statement_handle: Option<Bytes>,
+}
+
+/// Encode the parameter values as an Arrow IPC stream
+fn encode_parameters(parameters: &RecordBatch) -> Result<Bytes> {
+    let mut buf = vec![];
+    let mut writer = StreamWriter::try_new(&mut buf, parameters.schema().as_ref())?;
+    writer.write(parameters)?;
+    writer.finish()?;
+    drop(writer);
+    Ok(buf.into())
+}
+
+/// Decode parameter values encoded with [`encode_parameters`]
+fn decode_parameters(parameters: Bytes) -> Result<RecordBatch> {
+    let mut reader = StreamReader::try_new(Cursor::new(parameters), None)?;
+    match reader.next() {
+        Some(batch) => Ok(batch?),
+        None => InvalidParametersSnafu {
+            description: "no parameter values in prepared statement handle",
+        }
+        .fail(),
     }
 }
 
@@ -96,6 +289,9 @@ pub enum FlightSQLCommand {
     CommandGetTables(CommandGetTables),
     /// Get a list of the available table tyypes
     CommandGetTableTypes(CommandGetTableTypes),
+    /// Get information about the data types supported. See
+    /// [`CommandGetXdbcTypeInfo`] for details.
+    CommandGetXdbcTypeInfo(CommandGetXdbcTypeInfo),
     /// Create a prepared statement
     ActionCreatePreparedStatementRequest(ActionCreatePreparedStatementRequest),
     /// Close a prepared statement
@@ -224,6 +420,16 @@ impl Display for FlightSQLCommand {
             Self::CommandGetTableTypes(CommandGetTableTypes {}) => {
                 write!(f, "CommandGetTableTypes")
             }
+            Self::CommandGetXdbcTypeInfo(CommandGetXdbcTypeInfo { data_type }) => {
+                write!(
+                    f,
+                    "CommandGetXdbcTypeInfo(data_type={})",
+                    data_type
+                        .as_ref()
+                        .map(|d| d.to_string())
+                        .unwrap_or_else(|| "<NONE>".to_string())
+                )
+            }
             Self::ActionCreatePreparedStatementRequest(ActionCreatePreparedStatementRequest {
                 query,
             }) => {
@@ -269,6 +475,8 @@ impl FlightSQLCommand {
             Ok(Self::CommandGetTables(decode_cmd))
         } else if let Some(decoded_cmd) = Any::unpack::<CommandGetTableTypes>(&msg)? {
             Ok(Self::CommandGetTableTypes(decoded_cmd))
+        } else if let Some(decoded_cmd) = Any::unpack::<CommandGetXdbcTypeInfo>(&msg)? {
+            Ok(Self::CommandGetXdbcTypeInfo(decoded_cmd))
         } else if let Some(decoded_cmd) = Any::unpack::<ActionCreatePreparedStatementRequest>(&msg)?
         {
             Ok(Self::ActionCreatePreparedStatementRequest(decoded_cmd))
@@ -293,7 +501,7 @@ impl FlightSQLCommand {
         let msg = match self {
             FlightSQLCommand::CommandStatementQuery(cmd) => Any::pack(&cmd),
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
-                let prepared_statement_handle = handle.encode();
+                let prepared_statement_handle = handle.try_encode()?;
                 let cmd = CommandPreparedStatementQuery {
                     prepared_statement_handle,
                 };
@@ -308,9 +516,10 @@ impl FlightSQLCommand {
             FlightSQLCommand::CommandGetPrimaryKeys(cmd) => Any::pack(&cmd),
             FlightSQLCommand::CommandGetTables(cmd) => Any::pack(&cmd),
             FlightSQLCommand::CommandGetTableTypes(cmd) => Any::pack(&cmd),
+            FlightSQLCommand::CommandGetXdbcTypeInfo(cmd) => Any::pack(&cmd),
             FlightSQLCommand::ActionCreatePreparedStatementRequest(cmd) => Any::pack(&cmd),
             FlightSQLCommand::ActionClosePreparedStatementRequest(handle) => {
-                let prepared_statement_handle = handle.encode();
+                let prepared_statement_handle = handle.try_encode()?;
                 Any::pack(&ActionClosePreparedStatementRequest {
                     prepared_statement_handle,
                 })
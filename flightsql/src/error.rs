@@ -1,6 +1,4 @@
 //! FlightSQL errors
-use std::string::FromUtf8Error;
-
 use arrow::error::ArrowError;
 use arrow_flight::error::FlightError;
 use datafusion::error::DataFusionError;
@@ -16,8 +14,14 @@ pub enum Error {
     #[snafu(context(false))]
     Decode { source: DecodeError },
 
-    #[snafu(display("Invalid PreparedStatement handle (invalid UTF-8:) {}", source))]
-    InvalidHandle { source: FromUtf8Error },
+    #[snafu(display("Invalid PreparedStatement handle: {}", source))]
+    InvalidHandle { source: DecodeError },
+
+    #[snafu(display("Invalid PreparedStatement parameters: {}", description))]
+    InvalidParameters { description: String },
+
+    #[snafu(display("Invalid query language '{}', expected 'sql' or 'influxql'", language))]
+    InvalidQueryLanguage { language: String },
 
     #[snafu(display("{}", source))]
     #[snafu(context(false))]
@@ -45,6 +49,10 @@ impl From<Error> for DataFusionError {
         match value {
             Error::DataFusion { source } => source,
             Error::Arrow { source } => DataFusionError::ArrowError(source),
+            // report invalid parameter values as user errors
+            value @ (Error::InvalidParameters { .. } | Error::InvalidQueryLanguage { .. }) => {
+                DataFusionError::Plan(value.to_string())
+            }
             value => DataFusionError::External(Box::new(value)),
         }
     }
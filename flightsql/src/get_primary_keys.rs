@@ -0,0 +1,196 @@
+//! Implementation of FlightSQL GetPrimaryKeys
+
+use std::sync::Arc;
+
+use crate::error::*;
+use arrow::{
+    array::{ArrayRef, Int32Builder, StringBuilder},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
+use datafusion::prelude::SessionContext;
+use once_cell::sync::Lazy;
+
+/// Return a RecordBatch for the GetPrimaryKeys
+///
+/// # Parameters
+///
+/// Definition from <https://github.com/apache/arrow/blob/2fe17338e2d1f85d0c2685d31d2dd51f138b6b80/format/FlightSql.proto#L1261-L1297>
+///
+/// catalog: Specifies the catalog to search for the table.
+/// An empty string retrieves those without a catalog.
+/// If omitted the catalog name should not be used to narrow the search.
+///
+/// db_schema: Specifies the schema to search for the table.
+/// An empty string retrieves those without a schema.
+/// If omitted the schema name should not be used to narrow the search.
+///
+/// table: Specifies the table to get the primary keys for.
+///
+/// # IOx primary keys
+///
+/// The primary key of an IOx table is its tag columns, in
+/// lexicographic order, followed by `time`. This is the key rows are
+/// deduplicated on, and the default sort key of its partitions.
+///
+/// Tables without an IOx schema (such as those in `system` and
+/// `information_schema`) have no primary key.
+pub(crate) async fn get_primary_keys(
+    ctx: &SessionContext,
+    catalog_filter: Option<String>,
+    db_schema_filter: Option<String>,
+    table_name: String,
+) -> Result<RecordBatch> {
+    // data needs to be ordered by
+    // ORDER BY catalog_name, db_schema_name, table_name, key_name, key_sequence
+
+    let mut builder = GetPrimaryKeysBuilder::new();
+
+    let catalog_list = ctx.state().catalog_list();
+
+    for catalog_name in sorted(catalog_list.catalog_names()) {
+        // we just got the catalog name from the catalog_list, so it
+        // should always be Some, but avoid unwrap to be safe
+        let Some(catalog) = catalog_list.catalog(&catalog_name) else {
+            continue
+        };
+
+        // if the catalog doesn't match request, skip
+        if let Some(catalog_filter) = catalog_filter.as_ref() {
+            if catalog_filter != &catalog_name {
+                continue;
+            }
+        }
+
+        for schema_name in sorted(catalog.schema_names()) {
+            // if the schema doesn't match request, skip
+            if let Some(db_schema_filter) = db_schema_filter.as_ref() {
+                if db_schema_filter != &schema_name {
+                    continue;
+                }
+            }
+
+            let Some(db_schema) = catalog.schema(&schema_name) else {
+                continue
+            };
+
+            let Some(table) = db_schema.table(&table_name).await else {
+                continue
+            };
+
+            // Only tables with an IOx schema have a primary key
+            let Ok(iox_schema) = schema::Schema::try_from(table.schema()) else {
+                continue
+            };
+
+            let key_name = format!("{table_name}_pkey");
+            for (i, column_name) in iox_schema.primary_key().into_iter().enumerate() {
+                builder.append(
+                    &catalog_name,
+                    &schema_name,
+                    &table_name,
+                    column_name,
+                    &key_name,
+                    i as i32 + 1,
+                );
+            }
+        }
+    }
+
+    builder.build()
+}
+
+fn sorted(mut v: Vec<String>) -> Vec<String> {
+    v.sort_unstable();
+    v
+}
+
+/// Return the schema of the RecordBatch that will be returned from
+/// [`get_primary_keys`].
+pub(crate) fn get_primary_keys_schema() -> SchemaRef {
+    Arc::clone(&GET_PRIMARY_KEYS_SCHEMA)
+}
+
+/// The schema for GetPrimaryKeys
+static GET_PRIMARY_KEYS_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("catalog_name", DataType::Utf8, false),
+        Field::new("db_schema_name", DataType::Utf8, false),
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("column_name", DataType::Utf8, false),
+        Field::new("key_name", DataType::Utf8, false),
+        Field::new("key_sequence", DataType::Int32, false),
+    ]))
+});
+
+/// Builds rows like this:
+///
+/// * catalog_name: utf8,
+/// * db_schema_name: utf8,
+/// * table_name: utf8 not null,
+/// * column_name: utf8 not null,
+/// * key_name: utf8,
+/// * key_sequence: int32 not null (1-based position of the column in the key)
+struct GetPrimaryKeysBuilder {
+    catalog_name: StringBuilder,
+    db_schema_name: StringBuilder,
+    table_name: StringBuilder,
+    column_name: StringBuilder,
+    key_name: StringBuilder,
+    key_sequence: Int32Builder,
+}
+
+impl GetPrimaryKeysBuilder {
+    fn new() -> Self {
+        Self {
+            catalog_name: StringBuilder::new(),
+            db_schema_name: StringBuilder::new(),
+            table_name: StringBuilder::new(),
+            column_name: StringBuilder::new(),
+            key_name: StringBuilder::new(),
+            key_sequence: Int32Builder::new(),
+        }
+    }
+
+    /// Append a row
+    fn append(
+        &mut self,
+        catalog_name: &str,
+        schema_name: &str,
+        table_name: &str,
+        column_name: &str,
+        key_name: &str,
+        key_sequence: i32,
+    ) {
+        self.catalog_name.append_value(catalog_name);
+        self.db_schema_name.append_value(schema_name);
+        self.table_name.append_value(table_name);
+        self.column_name.append_value(column_name);
+        self.key_name.append_value(key_name);
+        self.key_sequence.append_value(key_sequence);
+    }
+
+    /// builds the correct schema
+    fn build(self) -> Result<RecordBatch> {
+        let Self {
+            mut catalog_name,
+            mut db_schema_name,
+            mut table_name,
+            mut column_name,
+            mut key_name,
+            mut key_sequence,
+        } = self;
+
+        Ok(RecordBatch::try_new(
+            get_primary_keys_schema(),
+            vec![
+                Arc::new(catalog_name.finish()) as ArrayRef,
+                Arc::new(db_schema_name.finish()) as ArrayRef,
+                Arc::new(table_name.finish()) as ArrayRef,
+                Arc::new(column_name.finish()) as ArrayRef,
+                Arc::new(key_name.finish()) as ArrayRef,
+                Arc::new(key_sequence.finish()) as ArrayRef,
+            ],
+        )?)
+    }
+}
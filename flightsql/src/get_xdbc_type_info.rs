@@ -0,0 +1,323 @@
+//! Implementation of FlightSQL GetXdbcTypeInfo
+
+use std::sync::Arc;
+
+use crate::error::*;
+use arrow::{
+    array::{new_null_array, ArrayRef, BooleanArray, Int32Array, StringArray, UInt32Array},
+    compute::take,
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
+use once_cell::sync::Lazy;
+
+// XDBC type codes, from the `XdbcDataType` enum in FlightSql.proto
+const XDBC_BIT: i32 = -7;
+const XDBC_BIGINT: i32 = -5;
+const XDBC_DOUBLE: i32 = 8;
+const XDBC_VARCHAR: i32 = 12;
+const XDBC_DATETIME: i32 = 9;
+const XDBC_TIMESTAMP: i32 = 93;
+
+// From the `XdbcDatetimeSubcode` enum in FlightSql.proto
+const XDBC_SUBCODE_TIMESTAMP: i32 = 3;
+
+// From the `Nullable` enum in FlightSql.proto
+const NULLABILITY_NULLABLE: i32 = 1;
+
+// From the `Searchable` enum in FlightSql.proto
+const SEARCHABLE_BASIC: i32 = 2;
+const SEARCHABLE_FULL: i32 = 3;
+
+/// Description of a single type supported by IOx
+struct XdbcTypeInfo {
+    type_name: &'static str,
+    data_type: i32,
+    column_size: Option<i32>,
+    literal_prefix: Option<&'static str>,
+    literal_suffix: Option<&'static str>,
+    case_sensitive: bool,
+    searchable: i32,
+    unsigned_attribute: Option<bool>,
+    minimum_scale: Option<i32>,
+    maximum_scale: Option<i32>,
+    sql_data_type: i32,
+    datetime_subcode: Option<i32>,
+    num_prec_radix: Option<i32>,
+}
+
+/// The SQL types of IOx columns: tags are `VARCHAR`, `time` is a
+/// nanosecond precision `TIMESTAMP` and fields are one of `DOUBLE`,
+/// `BIGINT`, `BIGINT UNSIGNED`, `VARCHAR` or `BOOLEAN`.
+///
+/// Ordered by data_type, then type_name, as required by FlightSQL.
+const IOX_TYPES: &[XdbcTypeInfo] = &[
+    XdbcTypeInfo {
+        type_name: "BOOLEAN",
+        data_type: XDBC_BIT,
+        column_size: Some(1),
+        literal_prefix: None,
+        literal_suffix: None,
+        case_sensitive: false,
+        searchable: SEARCHABLE_BASIC,
+        unsigned_attribute: None,
+        minimum_scale: None,
+        maximum_scale: None,
+        sql_data_type: XDBC_BIT,
+        datetime_subcode: None,
+        num_prec_radix: None,
+    },
+    XdbcTypeInfo {
+        type_name: "BIGINT",
+        data_type: XDBC_BIGINT,
+        column_size: Some(19),
+        literal_prefix: None,
+        literal_suffix: None,
+        case_sensitive: false,
+        searchable: SEARCHABLE_BASIC,
+        unsigned_attribute: Some(false),
+        minimum_scale: Some(0),
+        maximum_scale: Some(0),
+        sql_data_type: XDBC_BIGINT,
+        datetime_subcode: None,
+        num_prec_radix: Some(10),
+    },
+    XdbcTypeInfo {
+        type_name: "BIGINT UNSIGNED",
+        data_type: XDBC_BIGINT,
+        column_size: Some(20),
+        literal_prefix: None,
+        literal_suffix: None,
+        case_sensitive: false,
+        searchable: SEARCHABLE_BASIC,
+        unsigned_attribute: Some(true),
+        minimum_scale: Some(0),
+        maximum_scale: Some(0),
+        sql_data_type: XDBC_BIGINT,
+        datetime_subcode: None,
+        num_prec_radix: Some(10),
+    },
+    XdbcTypeInfo {
+        type_name: "DOUBLE",
+        data_type: XDBC_DOUBLE,
+        column_size: Some(15),
+        literal_prefix: None,
+        literal_suffix: None,
+        case_sensitive: false,
+        searchable: SEARCHABLE_BASIC,
+        unsigned_attribute: Some(false),
+        minimum_scale: None,
+        maximum_scale: None,
+        sql_data_type: XDBC_DOUBLE,
+        datetime_subcode: None,
+        num_prec_radix: Some(2),
+    },
+    XdbcTypeInfo {
+        type_name: "VARCHAR",
+        data_type: XDBC_VARCHAR,
+        column_size: None,
+        literal_prefix: Some("'"),
+        literal_suffix: Some("'"),
+        case_sensitive: true,
+        searchable: SEARCHABLE_FULL,
+        unsigned_attribute: None,
+        minimum_scale: None,
+        maximum_scale: None,
+        sql_data_type: XDBC_VARCHAR,
+        datetime_subcode: None,
+        num_prec_radix: None,
+    },
+    XdbcTypeInfo {
+        type_name: "TIMESTAMP",
+        data_type: XDBC_TIMESTAMP,
+        // "YYYY-MM-DD HH:MM:SS.fffffffff"
+        column_size: Some(29),
+        literal_prefix: Some("'"),
+        literal_suffix: Some("'"),
+        case_sensitive: false,
+        searchable: SEARCHABLE_BASIC,
+        unsigned_attribute: None,
+        minimum_scale: Some(9),
+        maximum_scale: Some(9),
+        sql_data_type: XDBC_DATETIME,
+        datetime_subcode: Some(XDBC_SUBCODE_TIMESTAMP),
+        num_prec_radix: None,
+    },
+];
+
+/// Return a RecordBatch for the GetXdbcTypeInfo
+///
+/// # Parameters
+///
+/// Definition from <https://github.com/apache/arrow/blob/2fe17338e2d1f85d0c2685d31d2dd51f138b6b80/format/FlightSql.proto#L1036-L1062>
+///
+/// data_type: Specifies the data type to search for the info. If
+/// omitted, all types are returned.
+pub(crate) fn get_xdbc_type_info(data_type: Option<i32>) -> Result<RecordBatch> {
+    let batch = XDBC_TYPE_INFO_RECORD_BATCH.clone();
+
+    let Some(data_type) = data_type else {
+        return Ok(batch)
+    };
+
+    let indices: UInt32Array = IOX_TYPES
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.data_type == data_type)
+        .map(|(i, _)| i as u32)
+        .collect();
+
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|c| take(c.as_ref(), &indices, None))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(RecordBatch::try_new(get_xdbc_type_info_schema(), columns)?)
+}
+
+/// Return the schema of the RecordBatch that will be returned from
+/// [`get_xdbc_type_info`].
+pub(crate) fn get_xdbc_type_info_schema() -> SchemaRef {
+    Arc::clone(&GET_XDBC_TYPE_INFO_SCHEMA)
+}
+
+/// The schema for GetXdbcTypeInfo
+static GET_XDBC_TYPE_INFO_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("type_name", DataType::Utf8, false),
+        Field::new("data_type", DataType::Int32, false),
+        Field::new("column_size", DataType::Int32, true),
+        Field::new("literal_prefix", DataType::Utf8, true),
+        Field::new("literal_suffix", DataType::Utf8, true),
+        Field::new(
+            "create_params",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, false))),
+            true,
+        ),
+        Field::new("nullable", DataType::Int32, false),
+        Field::new("case_sensitive", DataType::Boolean, false),
+        Field::new("searchable", DataType::Int32, false),
+        Field::new("unsigned_attribute", DataType::Boolean, true),
+        Field::new("fixed_prec_scale", DataType::Boolean, false),
+        Field::new("auto_increment", DataType::Boolean, true),
+        Field::new("local_type_name", DataType::Utf8, true),
+        Field::new("minimum_scale", DataType::Int32, true),
+        Field::new("maximum_scale", DataType::Int32, true),
+        Field::new("sql_data_type", DataType::Int32, false),
+        Field::new("datetime_subcode", DataType::Int32, true),
+        Field::new("num_prec_radix", DataType::Int32, true),
+        Field::new("interval_precision", DataType::Int32, true),
+    ]))
+});
+
+static XDBC_TYPE_INFO_RECORD_BATCH: Lazy<RecordBatch> = Lazy::new(|| {
+    let n = IOX_TYPES.len();
+
+    // No IOx type takes parameters
+    let create_params = new_null_array(
+        &DataType::List(Arc::new(Field::new("item", DataType::Utf8, false))),
+        n,
+    );
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            IOX_TYPES.iter().map(|t| t.type_name),
+        )),
+        Arc::new(Int32Array::from_iter_values(
+            IOX_TYPES.iter().map(|t| t.data_type),
+        )),
+        Arc::new(Int32Array::from_iter(
+            IOX_TYPES.iter().map(|t| t.column_size),
+        )),
+        Arc::new(StringArray::from_iter(
+            IOX_TYPES.iter().map(|t| t.literal_prefix),
+        )),
+        Arc::new(StringArray::from_iter(
+            IOX_TYPES.iter().map(|t| t.literal_suffix),
+        )),
+        create_params,
+        Arc::new(Int32Array::from_iter_values(
+            IOX_TYPES.iter().map(|_| NULLABILITY_NULLABLE),
+        )),
+        Arc::new(BooleanArray::from_iter(
+            IOX_TYPES.iter().map(|t| Some(t.case_sensitive)),
+        )),
+        Arc::new(Int32Array::from_iter_values(
+            IOX_TYPES.iter().map(|t| t.searchable),
+        )),
+        Arc::new(BooleanArray::from_iter(
+            IOX_TYPES.iter().map(|t| t.unsigned_attribute),
+        )),
+        // no IOx type has a fixed precision and scale
+        Arc::new(BooleanArray::from(vec![false; n])),
+        // IOx has no auto increment columns
+        Arc::new(BooleanArray::from(vec![Some(false); n])),
+        // the local type name is the same as the type name
+        Arc::new(StringArray::from_iter(
+            IOX_TYPES.iter().map(|t| Some(t.type_name)),
+        )),
+        Arc::new(Int32Array::from_iter(
+            IOX_TYPES.iter().map(|t| t.minimum_scale),
+        )),
+        Arc::new(Int32Array::from_iter(
+            IOX_TYPES.iter().map(|t| t.maximum_scale),
+        )),
+        Arc::new(Int32Array::from_iter_values(
+            IOX_TYPES.iter().map(|t| t.sql_data_type),
+        )),
+        Arc::new(Int32Array::from_iter(
+            IOX_TYPES.iter().map(|t| t.datetime_subcode),
+        )),
+        Arc::new(Int32Array::from_iter(
+            IOX_TYPES.iter().map(|t| t.num_prec_radix),
+        )),
+        // IOx has no interval columns
+        Arc::new(Int32Array::from(vec![None; n])),
+    ];
+
+    RecordBatch::try_new(get_xdbc_type_info_schema(), columns)
+        .expect("IOX_TYPES columns match GET_XDBC_TYPE_INFO_SCHEMA")
+});
+
+#[cfg(test)]
+mod tests {
+    use arrow::util::pretty::pretty_format_batches;
+
+    use super::*;
+
+    #[test]
+    fn test_get_xdbc_type_info() {
+        let batch = get_xdbc_type_info(None).unwrap();
+        assert_eq!(batch.num_rows(), IOX_TYPES.len());
+
+        // ordered by data_type
+        let data_types = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert!(data_types.values().windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_get_xdbc_type_info_filter() {
+        let batch = get_xdbc_type_info(Some(XDBC_BIGINT)).unwrap();
+        let type_names = pretty_format_batches(&[batch.project(&[0, 1, 9]).unwrap()])
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            type_names,
+            "+-----------------+-----------+--------------------+\n\
+             | type_name       | data_type | unsigned_attribute |\n\
+             +-----------------+-----------+--------------------+\n\
+             | BIGINT          | -5        | false              |\n\
+             | BIGINT UNSIGNED | -5        | true               |\n\
+             +-----------------+-----------+--------------------+"
+        );
+
+        // types not used by IOx have no rows
+        let batch = get_xdbc_type_info(Some(XDBC_DATETIME)).unwrap();
+        assert_eq!(batch.num_rows(), 0);
+    }
+}
@@ -3,10 +3,13 @@ mod cmd;
 mod error;
 mod get_catalogs;
 mod get_db_schemas;
+mod get_primary_keys;
 mod get_tables;
+mod get_xdbc_type_info;
+mod parameters;
 mod planner;
 mod sql_info;
 
-pub use cmd::{FlightSQLCommand, PreparedStatementHandle};
+pub use cmd::{FlightSQLCommand, PreparedStatementHandle, QueryLanguage};
 pub use error::{Error, Result};
 pub use planner::FlightSQLPlanner;
@@ -0,0 +1,151 @@
+//! Support for prepared statement parameters ("bind parameters")
+use std::sync::Arc;
+
+use arrow::{
+    compute::cast,
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use datafusion::{logical_expr::LogicalPlan, scalar::ScalarValue};
+use snafu::{ensure, OptionExt};
+
+use crate::error::*;
+
+/// Rewrites the positional `?` placeholders used by JDBC / ODBC
+/// clients into the numbered `$1`, `$2`, ... form understood by
+/// DataFusion.
+///
+/// `?` characters within string literals, quoted identifiers and
+/// comments are left unchanged.
+pub(crate) fn rewrite_placeholders(query: &str) -> String {
+    let mut output = String::with_capacity(query.len());
+    let mut chars = query.chars().peekable();
+    let mut next_placeholder = 1;
+
+    while let Some(c) = chars.next() {
+        if c == '?' {
+            output.push_str(&format!("${next_placeholder}"));
+            next_placeholder += 1;
+            continue;
+        }
+
+        output.push(c);
+        match c {
+            // string literals and quoted identifiers. An escaped
+            // quote ('' or "") is handled as two adjacent quoted
+            // sections.
+            '\'' | '"' => {
+                for c2 in chars.by_ref() {
+                    output.push(c2);
+                    if c2 == c {
+                        break;
+                    }
+                }
+            }
+            // line comment
+            '-' if chars.peek() == Some(&'-') => {
+                for c2 in chars.by_ref() {
+                    output.push(c2);
+                    if c2 == '\n' {
+                        break;
+                    }
+                }
+            }
+            // block comment
+            '/' if chars.peek() == Some(&'*') => {
+                output.push(chars.next().unwrap());
+                let mut prev = None;
+                for c2 in chars.by_ref() {
+                    output.push(c2);
+                    if prev == Some('*') && c2 == '/' {
+                        break;
+                    }
+                    prev = Some(c2);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    output
+}
+
+/// Return the schema of the parameters of `plan`, with one field per
+/// placeholder, in order (the first field is `$1`, the second `$2`
+/// and so on).
+///
+/// Placeholders whose type could not be inferred by DataFusion have
+/// type [`DataType::Null`]. Values bound to them are used as is.
+pub(crate) fn parameter_schema(plan: &LogicalPlan) -> Result<Schema> {
+    let mut types = vec![];
+    for (name, data_type) in plan.get_parameter_types()? {
+        let index = name
+            .strip_prefix('$')
+            .and_then(|index| index.parse::<usize>().ok())
+            .filter(|index| *index > 0)
+            .context(InvalidParametersSnafu {
+                description: format!("invalid placeholder '{name}'"),
+            })?;
+
+        if types.len() < index {
+            types.resize(index, None);
+        }
+        types[index - 1] = data_type;
+    }
+
+    let fields = types
+        .into_iter()
+        .enumerate()
+        .map(|(i, data_type)| {
+            Field::new(
+                format!("${}", i + 1),
+                data_type.unwrap_or(DataType::Null),
+                true,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Schema::new(fields))
+}
+
+/// Replace the placeholders in `plan` with the values in the (single
+/// row of) `parameters`, casting each value to the inferred type of
+/// its placeholder.
+pub(crate) fn bind_parameters(plan: LogicalPlan, parameters: &RecordBatch) -> Result<LogicalPlan> {
+    let schema = parameter_schema(&plan)?;
+
+    ensure!(
+        parameters.num_rows() == 1,
+        InvalidParametersSnafu {
+            description: format!(
+                "expected exactly one row of parameter values, got {}",
+                parameters.num_rows()
+            )
+        }
+    );
+    ensure!(
+        parameters.num_columns() == schema.fields().len(),
+        InvalidParametersSnafu {
+            description: format!(
+                "expected {} parameter values, got {}",
+                schema.fields().len(),
+                parameters.num_columns()
+            )
+        }
+    );
+
+    let values = parameters
+        .columns()
+        .iter()
+        .zip(schema.fields())
+        .map(|(array, field)| {
+            let array = match field.data_type() {
+                DataType::Null => Arc::clone(array),
+                data_type => cast(array, data_type)?,
+            };
+            Ok(ScalarValue::try_from_array(&array, 0)?)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(plan.with_param_values(values)?)
+}
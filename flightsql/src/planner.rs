@@ -13,7 +13,7 @@ use arrow_flight::{
         ActionCreatePreparedStatementRequest, ActionCreatePreparedStatementResult, Any,
         CommandGetCatalogs, CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
         CommandGetImportedKeys, CommandGetPrimaryKeys, CommandGetSqlInfo, CommandGetTableTypes,
-        CommandGetTables, CommandStatementQuery,
+        CommandGetTables, CommandGetXdbcTypeInfo, CommandStatementQuery,
     },
     IpcMessage, SchemaAsIpc,
 };
@@ -21,6 +21,7 @@ use arrow_util::flight::prepare_schema_for_flight;
 use bytes::Bytes;
 use datafusion::{logical_expr::LogicalPlan, physical_plan::ExecutionPlan};
 use iox_query::{exec::IOxSessionContext, QueryNamespace};
+use iox_query_influxql::frontend::planner::InfluxQLQueryPlanner;
 use observability_deps::tracing::debug;
 use once_cell::sync::Lazy;
 use prost::Message;
@@ -29,10 +30,13 @@ use crate::{
     error::*,
     get_catalogs::{get_catalogs, get_catalogs_schema},
     get_db_schemas::{get_db_schemas, get_db_schemas_schema},
+    get_primary_keys::{get_primary_keys, get_primary_keys_schema},
     get_tables::{get_tables, get_tables_schema},
+    get_xdbc_type_info::{get_xdbc_type_info, get_xdbc_type_info_schema},
+    parameters::{bind_parameters, parameter_schema, rewrite_placeholders},
     sql_info::iox_sql_info_list,
 };
-use crate::{FlightSQLCommand, PreparedStatementHandle};
+use crate::{FlightSQLCommand, PreparedStatementHandle, QueryLanguage};
 
 /// Logic for creating plans for various Flight messages against a query database
 #[derive(Debug, Default)]
@@ -44,20 +48,24 @@ impl FlightSQLPlanner {
     }
 
     /// Returns the schema, in Arrow IPC encoded form, for the request in msg.
+    ///
+    /// A `CommandStatementQuery` is planned as `language`, while
+    /// prepared statements use the language they were created with.
     pub async fn get_flight_info(
         namespace_name: impl Into<String>,
         cmd: FlightSQLCommand,
+        language: QueryLanguage,
         ctx: &IOxSessionContext,
     ) -> Result<Bytes> {
         let namespace_name = namespace_name.into();
-        debug!(%namespace_name, %cmd, "Handling flightsql get_flight_info");
+        debug!(%namespace_name, %cmd, %language, "Handling flightsql get_flight_info");
 
         match cmd {
             FlightSQLCommand::CommandStatementQuery(CommandStatementQuery { query }) => {
-                get_schema_for_query(&query, ctx).await
+                get_schema_for_query(&query, language, ctx).await
             }
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
-                get_schema_for_query(handle.query(), ctx).await
+                get_schema_for_query(handle.query(), handle.language(), ctx).await
             }
             FlightSQLCommand::CommandGetSqlInfo(CommandGetSqlInfo { .. }) => {
                 encode_schema(iox_sql_info_list().schema())
@@ -78,7 +86,7 @@ impl FlightSQLPlanner {
                 encode_schema(&GET_IMPORTED_KEYS_SCHEMA)
             }
             FlightSQLCommand::CommandGetPrimaryKeys(CommandGetPrimaryKeys { .. }) => {
-                encode_schema(&GET_PRIMARY_KEYS_SCHEMA)
+                encode_schema(get_primary_keys_schema().as_ref())
             }
             FlightSQLCommand::CommandGetTables(CommandGetTables { include_schema, .. }) => {
                 encode_schema(get_tables_schema(include_schema).as_ref())
@@ -86,6 +94,9 @@ impl FlightSQLPlanner {
             FlightSQLCommand::CommandGetTableTypes(CommandGetTableTypes { .. }) => {
                 encode_schema(&GET_TABLE_TYPE_SCHEMA)
             }
+            FlightSQLCommand::CommandGetXdbcTypeInfo(CommandGetXdbcTypeInfo { .. }) => {
+                encode_schema(get_xdbc_type_info_schema().as_ref())
+            }
             FlightSQLCommand::ActionCreatePreparedStatementRequest(_)
             | FlightSQLCommand::ActionClosePreparedStatementRequest(_) => ProtocolSnafu {
                 cmd: format!("{cmd:?}"),
@@ -96,6 +107,9 @@ impl FlightSQLPlanner {
     }
 
     /// Returns a plan that computes results requested in msg
+    ///
+    /// A `CommandStatementQuery` is always planned as SQL - callers
+    /// are expected to run InfluxQL statements directly.
     pub async fn do_get(
         namespace_name: impl Into<String>,
         _database: Arc<dyn QueryNamespace>,
@@ -112,8 +126,21 @@ impl FlightSQLPlanner {
             }
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
                 let query = handle.query();
-                debug!(%query, "Planning FlightSQL prepared query");
-                Ok(ctx.sql_to_physical_plan(query).await?)
+                let language = handle.language();
+                debug!(%query, %language, "Planning FlightSQL prepared query");
+                match language {
+                    QueryLanguage::Sql => {
+                        let mut plan = ctx.sql_to_logical_plan(query).await?;
+                        if let Some(parameters) = handle.parameters() {
+                            plan = bind_parameters(plan, parameters)?;
+                        }
+                        Ok(ctx.create_physical_plan(&plan).await?)
+                    }
+                    // InfluxQL prepared statements have no parameters
+                    QueryLanguage::InfluxQL => {
+                        Ok(InfluxQLQueryPlanner::new().query(query, ctx).await?)
+                    }
+                }
             }
             FlightSQLCommand::CommandGetSqlInfo(CommandGetSqlInfo { info }) => {
                 debug!("Planning GetSqlInfo query");
@@ -239,6 +266,11 @@ impl FlightSQLPlanner {
                 let plan = plan_get_table_types(ctx).await?;
                 Ok(ctx.create_physical_plan(&plan).await?)
             }
+            FlightSQLCommand::CommandGetXdbcTypeInfo(CommandGetXdbcTypeInfo { data_type }) => {
+                debug!(?data_type, "Planning GetXdbcTypeInfo query");
+                let plan = plan_get_xdbc_type_info(ctx, data_type).await?;
+                Ok(ctx.create_physical_plan(&plan).await?)
+            }
             FlightSQLCommand::ActionClosePreparedStatementRequest(_)
             | FlightSQLCommand::ActionCreatePreparedStatementRequest(_) => ProtocolSnafu {
                 cmd: format!("{cmd:?}"),
@@ -251,32 +283,52 @@ impl FlightSQLPlanner {
     /// Handles the action specified in `msg` and returns bytes for
     /// the [`arrow_flight::Result`] (not the same as a rust
     /// [`Result`]!)
+    ///
+    /// Prepared statements are created for queries in `language`.
     pub async fn do_action(
         namespace_name: impl Into<String>,
         _database: Arc<dyn QueryNamespace>,
         cmd: FlightSQLCommand,
+        language: QueryLanguage,
         ctx: &IOxSessionContext,
     ) -> Result<Bytes> {
         let namespace_name = namespace_name.into();
-        debug!(%namespace_name, %cmd, "Handling flightsql do_action");
+        debug!(%namespace_name, %cmd, %language, "Handling flightsql do_action");
 
         match cmd {
             FlightSQLCommand::ActionCreatePreparedStatementRequest(
                 ActionCreatePreparedStatementRequest { query },
             ) => {
-                debug!(%query, "Creating prepared statement");
-
-                // todo run the planner here and actually figure out parameter schemas
-                // see https://github.com/apache/arrow-datafusion/pull/4701
-                let parameter_schema = vec![];
-
-                let dataset_schema = get_schema_for_query(&query, ctx).await?;
-                let handle = PreparedStatementHandle::new(query);
+                debug!(%query, %language, "Creating prepared statement");
+
+                let (query, dataset_schema, parameter_schema) = match language {
+                    QueryLanguage::Sql => {
+                        // DataFusion only understands numbered placeholders
+                        let query = rewrite_placeholders(&query);
+                        let plan = ctx.sql_to_logical_plan(&query).await?;
+
+                        // An empty parameter schema signals to the client
+                        // that there are no parameters
+                        let parameter_schema = parameter_schema(&plan)?;
+                        let parameter_schema = if parameter_schema.fields().is_empty() {
+                            Bytes::new()
+                        } else {
+                            encode_schema(&parameter_schema)?
+                        };
+
+                        (query, get_schema_for_plan(plan)?, parameter_schema)
+                    }
+                    QueryLanguage::InfluxQL => {
+                        let dataset_schema = get_schema_for_query(&query, language, ctx).await?;
+                        (query, dataset_schema, Bytes::new())
+                    }
+                };
+                let handle = PreparedStatementHandle::new(query, language);
 
                 let result = ActionCreatePreparedStatementResult {
-                    prepared_statement_handle: Bytes::from(handle),
+                    prepared_statement_handle: handle.try_encode()?,
                     dataset_schema,
-                    parameter_schema: Bytes::from(parameter_schema),
+                    parameter_schema,
                 };
 
                 let msg = Any::pack(&result)?;
@@ -298,11 +350,21 @@ impl FlightSQLPlanner {
     }
 }
 
-/// Return the schema for the specified query
+/// Return the schema for the specified query, in `language`
 ///
 /// returns: IPC encoded (schema_bytes) for this query
-async fn get_schema_for_query(query: &str, ctx: &IOxSessionContext) -> Result<Bytes> {
-    get_schema_for_plan(ctx.sql_to_logical_plan(query).await?)
+async fn get_schema_for_query(
+    query: &str,
+    language: QueryLanguage,
+    ctx: &IOxSessionContext,
+) -> Result<Bytes> {
+    match language {
+        QueryLanguage::Sql => get_schema_for_plan(ctx.sql_to_logical_plan(query).await?),
+        QueryLanguage::InfluxQL => {
+            let schema = InfluxQLQueryPlanner::new().query_schema(query, ctx).await?;
+            encode_schema(&prepare_schema_for_flight(schema))
+        }
+    }
 }
 
 /// Return the schema for the specified logical plan
@@ -383,11 +445,11 @@ async fn plan_get_imported_keys(
 
 async fn plan_get_primary_keys(
     ctx: &IOxSessionContext,
-    _catalog: Option<String>,
-    _db_schema: Option<String>,
-    _table: String,
+    catalog: Option<String>,
+    db_schema: Option<String>,
+    table: String,
 ) -> Result<LogicalPlan> {
-    let batch = RecordBatch::new_empty(Arc::clone(&GET_PRIMARY_KEYS_SCHEMA));
+    let batch = get_primary_keys(ctx.inner(), catalog, db_schema, table).await?;
     Ok(ctx.batch_to_logical_plan(batch)?)
 }
 
@@ -417,6 +479,14 @@ async fn plan_get_table_types(ctx: &IOxSessionContext) -> Result<LogicalPlan> {
     Ok(ctx.batch_to_logical_plan(TABLE_TYPES_RECORD_BATCH.clone())?)
 }
 
+/// Return a `LogicalPlan` for GetXdbcTypeInfo
+async fn plan_get_xdbc_type_info(
+    ctx: &IOxSessionContext,
+    data_type: Option<i32>,
+) -> Result<LogicalPlan> {
+    Ok(ctx.batch_to_logical_plan(get_xdbc_type_info(data_type)?)?)
+}
+
 /// The schema for GetTableTypes
 static GET_TABLE_TYPE_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
     Arc::new(Schema::new(vec![Field::new(
@@ -494,14 +564,3 @@ static GET_IMPORTED_KEYS_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
         Field::new("delete_rule", DataType::UInt8, false),
     ]))
 });
-
-static GET_PRIMARY_KEYS_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
-    Arc::new(Schema::new(vec![
-        Field::new("catalog_name", DataType::Utf8, false),
-        Field::new("db_schema_name", DataType::Utf8, false),
-        Field::new("table_name", DataType::Utf8, false),
-        Field::new("column_name", DataType::Utf8, false),
-        Field::new("key_name", DataType::Utf8, false),
-        Field::new("key_sequence", DataType::Int32, false),
-    ]))
-});
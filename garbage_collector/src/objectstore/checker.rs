@@ -193,6 +193,7 @@ mod tests {
             created_at: Timestamp::new(1),
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: Timestamp::new(1),
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         };
 
         let parquet_file = repos
@@ -20,7 +20,20 @@ pub(crate) async fn perform(
             .context(FlaggingSnafu)?;
         info!(flagged_count = %flagged.len(), "iox_catalog::flag_for_delete_by_retention()");
 
-        if flagged.is_empty() {
+        // Files of soft-deleted tables are removed regardless of retention.
+        let flagged_deleted = catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .flag_for_delete_by_deleted_tables()
+            .await
+            .context(FlaggingDeletedTablesSnafu)?;
+        info!(
+            flagged_count = %flagged_deleted.len(),
+            "iox_catalog::flag_for_delete_by_deleted_tables()"
+        );
+
+        if flagged.is_empty() && flagged_deleted.is_empty() {
             select! {
                 _ = shutdown.cancelled() => {
                     break
@@ -41,6 +54,11 @@ pub enum Error {
     Flagging {
         source: iox_catalog::interface::Error,
     },
+
+    #[snafu(display("Failed to flag parquet files of deleted tables for deletion"))]
+    FlaggingDeletedTables {
+        source: iox_catalog::interface::Error,
+    },
 }
 
 pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
@@ -25,10 +25,12 @@ fn main() -> Result<()> {
 /// - `influxdata.iox.ingester.v1.rs`
 /// - `influxdata.iox.namespace.v1.rs`
 /// - `influxdata.iox.object_store.v1.rs`
+/// - `influxdata.iox.partition_template.v1.rs`
 /// - `influxdata.iox.predicate.v1.rs`
 /// - `influxdata.iox.querier.v1.rs`
 /// - `influxdata.iox.schema.v1.rs`
 /// - `influxdata.iox.sharder.v1.rs`
+/// - `influxdata.iox.table.v1.rs`
 /// - `influxdata.iox.wal.v1.rs`
 /// - `influxdata.iox.write.v1.rs`
 /// - `influxdata.iox.write_buffer.v1.rs`
@@ -41,10 +43,12 @@ fn generate_grpc_types(root: &Path) -> Result<()> {
     let ingester_path = root.join("influxdata/iox/ingester/v1");
     let namespace_path = root.join("influxdata/iox/namespace/v1");
     let object_store_path = root.join("influxdata/iox/object_store/v1");
+    let partition_template_path = root.join("influxdata/iox/partition_template/v1");
     let predicate_path = root.join("influxdata/iox/predicate/v1");
     let querier_path = root.join("influxdata/iox/querier/v1");
     let schema_path = root.join("influxdata/iox/schema/v1");
     let sharder_path = root.join("influxdata/iox/sharder/v1");
+    let table_path = root.join("influxdata/iox/table/v1");
     let wal_path = root.join("influxdata/iox/wal/v1");
     let write_buffer_path = root.join("influxdata/iox/write_buffer/v1");
     let storage_path = root.join("influxdata/platform/storage");
@@ -63,6 +67,7 @@ fn generate_grpc_types(root: &Path) -> Result<()> {
         ingester_path.join("persist.proto"),
         namespace_path.join("service.proto"),
         object_store_path.join("service.proto"),
+        partition_template_path.join("template.proto"),
         predicate_path.join("predicate.proto"),
         querier_path.join("flight.proto"),
         root.join("google/longrunning/operations.proto"),
@@ -72,6 +77,7 @@ fn generate_grpc_types(root: &Path) -> Result<()> {
         root.join("influxdata/pbdata/v1/influxdb_pb_data_protocol.proto"),
         schema_path.join("service.proto"),
         sharder_path.join("sharder.proto"),
+        table_path.join("service.proto"),
         wal_path.join("wal.proto"),
         write_buffer_path.join("write_buffer.proto"),
         storage_path.join("predicate.proto"),
@@ -0,0 +1,192 @@
+use crate::google::{FieldViolation, FromRepeatedField, NonEmptyString, OptionalField};
+use crate::influxdata::iox::partition_template::v1 as proto;
+use chrono::format::{Item, StrftimeItems};
+use data_types::{PartitionTemplate, RegexCapture, StrftimeColumn, TemplatePart};
+
+impl From<PartitionTemplate> for proto::PartitionTemplate {
+    fn from(template: PartitionTemplate) -> Self {
+        Self {
+            parts: template.parts.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::PartitionTemplate> for PartitionTemplate {
+    type Error = FieldViolation;
+
+    fn try_from(value: proto::PartitionTemplate) -> Result<Self, Self::Error> {
+        if value.parts.is_empty() {
+            return Err(FieldViolation {
+                field: "parts".to_string(),
+                description: "partition template must have at least one part".to_string(),
+            });
+        }
+
+        Ok(Self {
+            parts: value.parts.repeated("parts")?,
+        })
+    }
+}
+
+impl From<TemplatePart> for proto::TemplatePart {
+    fn from(part: TemplatePart) -> Self {
+        let part = match part {
+            TemplatePart::Table => proto::template_part::Part::Table(Default::default()),
+            TemplatePart::Column(column) => proto::template_part::Part::Column(column),
+            TemplatePart::TimeFormat(format) => proto::template_part::Part::TimeFormat(format),
+            TemplatePart::RegexCapture(RegexCapture { column, regex }) => {
+                proto::template_part::Part::RegexCapture(proto::RegexCapture { column, regex })
+            }
+            TemplatePart::StrftimeColumn(StrftimeColumn { column, format }) => {
+                proto::template_part::Part::StrftimeColumn(proto::StrftimeColumn { column, format })
+            }
+        };
+
+        Self { part: Some(part) }
+    }
+}
+
+impl TryFrom<proto::TemplatePart> for TemplatePart {
+    type Error = FieldViolation;
+
+    fn try_from(value: proto::TemplatePart) -> Result<Self, Self::Error> {
+        Ok(match value.part.unwrap_field("part")? {
+            proto::template_part::Part::Table(_) => Self::Table,
+            proto::template_part::Part::Column(column) => Self::Column(column.non_empty("column")?),
+            proto::template_part::Part::TimeFormat(format) => {
+                Self::TimeFormat(strftime_format(format, "time_format")?)
+            }
+            proto::template_part::Part::RegexCapture(proto::RegexCapture { column, regex }) => {
+                let regex = regex.non_empty("regex_capture.regex")?;
+                if let Err(e) = regex::Regex::new(&regex) {
+                    return Err(FieldViolation {
+                        field: "regex_capture.regex".to_string(),
+                        description: format!("invalid regex: {e}"),
+                    });
+                }
+
+                Self::RegexCapture(RegexCapture {
+                    column: column.non_empty("regex_capture.column")?,
+                    regex,
+                })
+            }
+            proto::template_part::Part::StrftimeColumn(proto::StrftimeColumn {
+                column,
+                format,
+            }) => Self::StrftimeColumn(StrftimeColumn {
+                column: column.non_empty("strftime_column.column")?,
+                format: strftime_format(format, "strftime_column.format")?,
+            }),
+        })
+    }
+}
+
+/// Returns `format` if it is a non-empty, valid strftime format string
+fn strftime_format(format: String, field: &'static str) -> Result<String, FieldViolation> {
+    let format = format.non_empty(field)?;
+    if StrftimeItems::new(&format).any(|item| matches!(item, Item::Error)) {
+        return Err(FieldViolation {
+            field: field.to_string(),
+            description: format!("invalid strftime format: {format}"),
+        });
+    }
+    Ok(format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let template = PartitionTemplate {
+            parts: vec![
+                TemplatePart::Table,
+                TemplatePart::Column("region".to_string()),
+                TemplatePart::TimeFormat("%Y-%m-%d %H".to_string()),
+                TemplatePart::RegexCapture(RegexCapture {
+                    column: "host".to_string(),
+                    regex: "^(\\w+)-".to_string(),
+                }),
+                TemplatePart::StrftimeColumn(StrftimeColumn {
+                    column: "other_time".to_string(),
+                    format: "%Y".to_string(),
+                }),
+            ],
+        };
+
+        let encoded = proto::PartitionTemplate::from(template.clone());
+        let decoded = PartitionTemplate::try_from(encoded).unwrap();
+        assert_eq!(template, decoded);
+    }
+
+    #[test]
+    fn test_empty_template() {
+        let err = PartitionTemplate::try_from(proto::PartitionTemplate { parts: vec![] })
+            .expect_err("empty template should be rejected");
+        assert_eq!(err.field, "parts");
+    }
+
+    #[test]
+    fn test_missing_part() {
+        let err = PartitionTemplate::try_from(proto::PartitionTemplate {
+            parts: vec![proto::TemplatePart { part: None }],
+        })
+        .expect_err("missing part should be rejected");
+        assert_eq!(err.field, "parts.0.part");
+    }
+
+    #[test]
+    fn test_empty_column() {
+        let err = PartitionTemplate::try_from(proto::PartitionTemplate {
+            parts: vec![proto::TemplatePart {
+                part: Some(proto::template_part::Part::Column(String::new())),
+            }],
+        })
+        .expect_err("empty column name should be rejected");
+        assert_eq!(err.field, "parts.0.column");
+    }
+
+    #[test]
+    fn test_invalid_time_format() {
+        let err = PartitionTemplate::try_from(proto::PartitionTemplate {
+            parts: vec![proto::TemplatePart {
+                part: Some(proto::template_part::Part::TimeFormat("%Y-%".to_string())),
+            }],
+        })
+        .expect_err("invalid time format should be rejected");
+        assert_eq!(err.field, "parts.0.time_format");
+    }
+
+    #[test]
+    fn test_invalid_strftime_column_format() {
+        let err = PartitionTemplate::try_from(proto::PartitionTemplate {
+            parts: vec![proto::TemplatePart {
+                part: Some(proto::template_part::Part::StrftimeColumn(
+                    proto::StrftimeColumn {
+                        column: "other_time".to_string(),
+                        format: "%Q".to_string(),
+                    },
+                )),
+            }],
+        })
+        .expect_err("invalid strftime format should be rejected");
+        assert_eq!(err.field, "parts.0.strftime_column.format");
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let err = PartitionTemplate::try_from(proto::PartitionTemplate {
+            parts: vec![proto::TemplatePart {
+                part: Some(proto::template_part::Part::RegexCapture(
+                    proto::RegexCapture {
+                        column: "host".to_string(),
+                        regex: "(".to_string(),
+                    },
+                )),
+            }],
+        })
+        .expect_err("invalid regex should be rejected");
+        assert_eq!(err.field, "parts.0.regex_capture.regex");
+    }
+}
@@ -14,7 +14,7 @@ use std::fmt::{Display, Formatter};
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DropMeasurementStatement {
     /// The name of the measurement to delete.
-    name: Identifier,
+    pub name: Identifier,
 }
 
 impl Display for DropMeasurementStatement {
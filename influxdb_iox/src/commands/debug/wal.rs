@@ -0,0 +1,475 @@
+//! This module implements the `debug wal` CLI command
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use comfy_table::{Cell, Table};
+use futures::Future;
+use generated_types::influxdata::{
+    iox::wal::v1::{sequenced_wal_op::Op, SequencedWalOp as ProtoSequencedWalOp},
+    pbdata::v1::DatabaseBatch,
+};
+use influxdb_iox_client::connection::Connection;
+use mutable_batch_pb::decode::decode_database_batch;
+use observability_deps::tracing::{info, warn};
+use schema::Projection;
+use thiserror::Error;
+use wal::{ClosedSegmentFileReader, SequencedWalOp};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Error reading WAL segment: {0}")]
+    Wal(#[from] wal::Error),
+
+    #[error("Error decoding write: {0}")]
+    Decode(#[from] mutable_batch_pb::decode::Error),
+
+    #[error("Error converting write to line protocol: {0}")]
+    Conversion(String),
+
+    #[error("Cannot {operation} output file '{path:?}': {source}")]
+    File {
+        operation: &'static str,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("JSON Serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+
+    #[error("Client error: {0}")]
+    Client(#[from] influxdb_iox_client::error::Error),
+
+    #[error(
+        "Namespace with ID {0} not found in the catalog, \
+        use --skip-name-lookup to export it by ID"
+    )]
+    NamespaceNotFound(i64),
+
+    #[error(
+        "Table with ID {table_id} not found in namespace {namespace}, \
+        use --skip-name-lookup to export it by ID"
+    )]
+    TableNotFound { namespace: String, table_id: i64 },
+}
+
+/// Inspect and export the contents of ingester WAL segment files
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+/// All possible subcommands for WAL inspection
+#[derive(Debug, clap::Parser)]
+enum Command {
+    /// List the entries and sequence number ranges of WAL segment files
+    Inspect {
+        /// The WAL segment files to inspect
+        #[clap(value_parser, required = true)]
+        files: Vec<PathBuf>,
+    },
+
+    /// Print the operations in a WAL segment file as JSON, one per line
+    Dump {
+        /// The WAL segment file to dump
+        #[clap(value_parser)]
+        file: PathBuf,
+
+        /// Only print the operations with this sequence number
+        #[clap(long)]
+        sequence_number: Option<u64>,
+    },
+
+    /// Convert the writes in WAL segment files back into line protocol, so
+    /// they can be written through a router.
+    ///
+    /// One line protocol file is written per namespace into the output
+    /// directory.
+    RegenerateLp {
+        /// The WAL segment files to convert, in the order they should be
+        /// replayed
+        #[clap(value_parser, required = true)]
+        files: Vec<PathBuf>,
+
+        /// The directory to write the line protocol files into
+        #[clap(long, short)]
+        output_directory: PathBuf,
+
+        /// Do not resolve namespace and table names through the catalog, and
+        /// name them by their IDs instead
+        #[clap(long)]
+        skip_name_lookup: bool,
+    },
+}
+
+pub async fn command<C, CFut>(connection: C, config: Config) -> Result<(), Error>
+where
+    C: Send + FnOnce() -> CFut,
+    CFut: Send + Future<Output = Connection>,
+{
+    match config.command {
+        Command::Inspect { files } => {
+            let summaries = files
+                .iter()
+                .map(|path| SegmentSummary::read(path))
+                .collect::<Result<Vec<_>, _>>()?;
+            println!("{}", create_table(&summaries));
+        }
+        Command::Dump {
+            file,
+            sequence_number,
+        } => {
+            let mut reader = ClosedSegmentFileReader::from_path(&file)?;
+            let mut stdout = std::io::stdout().lock();
+            while let Some(ops) = reader.next_batch()? {
+                for op in ops {
+                    if sequence_number.map_or(false, |want| want != op.sequence_number) {
+                        continue;
+                    }
+                    serde_json::to_writer(&mut stdout, &ProtoSequencedWalOp::from(op))?;
+                    writeln!(stdout).map_err(|source| Error::File {
+                        operation: "write",
+                        path: "<stdout>".into(),
+                        source,
+                    })?;
+                }
+            }
+        }
+        Command::RegenerateLp {
+            files,
+            output_directory,
+            skip_name_lookup,
+        } => {
+            let names = match skip_name_lookup {
+                true => NameLookup::Ids,
+                false => NameLookup::catalog(connection().await).await?,
+            };
+            regenerate_lp(&files, &output_directory, &names)?;
+        } // Deliberately not adding _ => so the compiler will direct people here to impl new
+          // commands
+    }
+
+    Ok(())
+}
+
+/// Statistics about the contents of a single WAL segment file.
+#[derive(Debug, Default)]
+struct SegmentSummary {
+    path: PathBuf,
+    id: Option<wal::SegmentId>,
+    entries: usize,
+    writes: usize,
+    deletes: usize,
+    persists: usize,
+    min_sequence_number: Option<u64>,
+    max_sequence_number: Option<u64>,
+    /// The error that stopped the file from being read to the end, if any.
+    error: Option<wal::Error>,
+}
+
+impl SegmentSummary {
+    fn read(path: &Path) -> Result<Self, Error> {
+        let mut summary = Self {
+            path: path.to_owned(),
+            ..Default::default()
+        };
+
+        let mut reader = ClosedSegmentFileReader::from_path(path)?;
+        summary.id = Some(reader.id());
+
+        loop {
+            let ops = match reader.next_batch() {
+                Ok(Some(v)) => v,
+                Ok(None) => break,
+                Err(e) => {
+                    // Report what could be read from a corrupt file.
+                    summary.error = Some(e);
+                    break;
+                }
+            };
+
+            summary.entries += 1;
+            for op in ops {
+                match op.op {
+                    Op::Write(_) => summary.writes += 1,
+                    Op::Delete(_) => summary.deletes += 1,
+                    Op::Persist(_) => summary.persists += 1,
+                }
+                let seq = op.sequence_number;
+                summary.min_sequence_number =
+                    Some(summary.min_sequence_number.map_or(seq, |v| v.min(seq)));
+                summary.max_sequence_number = summary.max_sequence_number.max(Some(seq));
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+/// Turn segment summaries into a table
+fn create_table(summaries: &[SegmentSummary]) -> Table {
+    let mut table = Table::new();
+    table.load_preset("||--+-++|    ++++++");
+
+    let headers: Vec<_> = [
+        "segment_id",
+        "path",
+        "entries",
+        "writes",
+        "deletes",
+        "persists",
+        "min_sequence_number",
+        "max_sequence_number",
+        "error",
+    ]
+    .into_iter()
+    .map(Cell::new)
+    .collect();
+    table.set_header(headers);
+
+    let optional = |v: Option<u64>| v.map(|v| v.to_string()).unwrap_or_default();
+
+    for summary in summaries {
+        table.add_row(vec![
+            Cell::new(summary.id.map(|v| v.to_string()).unwrap_or_default()),
+            Cell::new(summary.path.display()),
+            Cell::new(summary.entries),
+            Cell::new(summary.writes),
+            Cell::new(summary.deletes),
+            Cell::new(summary.persists),
+            Cell::new(optional(summary.min_sequence_number)),
+            Cell::new(optional(summary.max_sequence_number)),
+            Cell::new(
+                summary
+                    .error
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+            ),
+        ]);
+    }
+
+    table
+}
+
+/// Resolves the namespace and table IDs in WAL entries to the names used in
+/// the regenerated line protocol.
+#[derive(Debug)]
+enum NameLookup {
+    /// Name namespaces and tables by their IDs.
+    Ids,
+    /// Use the names recorded in the catalog, keyed by namespace ID.
+    Catalog(HashMap<i64, (String, HashMap<i64, String>)>),
+}
+
+impl NameLookup {
+    /// Load the names of all namespaces and their tables from the catalog.
+    async fn catalog(connection: Connection) -> Result<Self, Error> {
+        let mut namespace_client = influxdb_iox_client::namespace::Client::new(connection.clone());
+        let mut schema_client = influxdb_iox_client::schema::Client::new(connection);
+
+        let mut names = HashMap::new();
+        for namespace in namespace_client.get_namespaces().await? {
+            let schema = schema_client.get_schema(&namespace.name).await?;
+            let tables = schema
+                .tables
+                .into_iter()
+                .map(|(name, table)| (table.id, name))
+                .collect();
+            names.insert(namespace.id, (namespace.name, tables));
+        }
+
+        Ok(Self::Catalog(names))
+    }
+
+    fn namespace(&self, namespace_id: i64) -> Result<String, Error> {
+        match self {
+            Self::Ids => Ok(format!("namespace_{namespace_id}")),
+            Self::Catalog(names) => names
+                .get(&namespace_id)
+                .map(|(name, _)| name.clone())
+                .ok_or(Error::NamespaceNotFound(namespace_id)),
+        }
+    }
+
+    fn table(&self, namespace_id: i64, table_id: i64) -> Result<String, Error> {
+        match self {
+            Self::Ids => Ok(format!("table_{table_id}")),
+            Self::Catalog(names) => {
+                let (namespace, tables) = names
+                    .get(&namespace_id)
+                    .ok_or(Error::NamespaceNotFound(namespace_id))?;
+                tables
+                    .get(&table_id)
+                    .cloned()
+                    .ok_or_else(|| Error::TableNotFound {
+                        namespace: namespace.clone(),
+                        table_id,
+                    })
+            }
+        }
+    }
+}
+
+/// Convert the writes in `files` to line protocol, writing one file per
+/// namespace into `output_directory`.
+fn regenerate_lp(
+    files: &[PathBuf],
+    output_directory: &Path,
+    names: &NameLookup,
+) -> Result<(), Error> {
+    std::fs::create_dir_all(output_directory).map_err(|source| Error::File {
+        operation: "create",
+        path: output_directory.to_owned(),
+        source,
+    })?;
+
+    // Output files, keyed by namespace ID.
+    let mut outputs: BTreeMap<i64, (PathBuf, BufWriter<File>)> = BTreeMap::new();
+
+    for file in files {
+        info!(?file, "regenerating line protocol from wal segment");
+
+        let mut reader = ClosedSegmentFileReader::from_path(file)?;
+        while let Some(ops) = reader.next_batch()? {
+            for SequencedWalOp {
+                sequence_number,
+                op,
+            } in ops
+            {
+                let batch = match op {
+                    Op::Write(w) => w,
+                    Op::Delete(_) => {
+                        warn!(sequence_number, "skipping delete operation");
+                        continue;
+                    }
+                    Op::Persist(_) => continue,
+                };
+
+                let (path, writer) = match outputs.entry(batch.database_id) {
+                    std::collections::btree_map::Entry::Occupied(v) => v.into_mut(),
+                    std::collections::btree_map::Entry::Vacant(v) => {
+                        let path = output_directory
+                            .join(format!("{}.lp", names.namespace(batch.database_id)?));
+                        let f = File::create(&path).map_err(|source| Error::File {
+                            operation: "create",
+                            path: path.clone(),
+                            source,
+                        })?;
+                        v.insert((path, BufWriter::new(f)))
+                    }
+                };
+
+                let lp = write_to_lp(&batch, names)?;
+                writer.write_all(&lp).map_err(|source| Error::File {
+                    operation: "write",
+                    path: path.clone(),
+                    source,
+                })?;
+            }
+        }
+    }
+
+    for (_, (path, mut writer)) in outputs {
+        writer.flush().map_err(|source| Error::File {
+            operation: "flush",
+            path: path.clone(),
+            source,
+        })?;
+        println!("{}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Convert a single WAL write into line protocol.
+fn write_to_lp(batch: &DatabaseBatch, names: &NameLookup) -> Result<Vec<u8>, Error> {
+    // Emit tables in a stable order.
+    let tables: BTreeMap<_, _> = decode_database_batch(batch)?.into_iter().collect();
+
+    let mut lp = Vec::new();
+    for (table_id, table) in tables {
+        let measurement = names.table(batch.database_id, table_id)?;
+        let schema = table
+            .schema(Projection::All)
+            .map_err(|e| Error::Conversion(e.to_string()))?;
+        let record_batch = table
+            .to_arrow(Projection::All)
+            .map_err(|e| Error::Conversion(e.to_string()))?;
+
+        lp.extend(
+            parquet_to_line_protocol::convert_to_lines(&measurement, &schema, &record_batch)
+                .map_err(Error::Conversion)?,
+        );
+    }
+
+    Ok(lp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_write(namespace_id: i64, lp: &str) -> DatabaseBatch {
+        let tables = mutable_batch_lp::lines_to_batches(lp, 0).unwrap();
+        DatabaseBatch {
+            database_id: namespace_id,
+            partition_key: "1970-01-01".to_string(),
+            table_batches: tables
+                .iter()
+                .map(|(name, batch)| {
+                    let id = if name == "bananas" { 1 } else { 2 };
+                    mutable_batch_pb::encode::encode_batch(id, batch)
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_write_to_lp_ids() {
+        let write = test_write(
+            42,
+            "bananas,region=Madrid temp=35 4242\nplatanos count=1i 4243",
+        );
+
+        let lp = write_to_lp(&write, &NameLookup::Ids).unwrap();
+        assert_eq!(
+            String::from_utf8(lp).unwrap(),
+            "table_1,region=Madrid temp=35 4242\ntable_2 count=1i 4243\n"
+        );
+    }
+
+    #[test]
+    fn test_write_to_lp_names() {
+        let write = test_write(
+            42,
+            "bananas,region=Madrid temp=35 4242\nplatanos count=1i 4243",
+        );
+        let names = NameLookup::Catalog(HashMap::from([(
+            42,
+            (
+                "fruit".to_string(),
+                HashMap::from([(1, "bananas".to_string()), (2, "platanos".to_string())]),
+            ),
+        )]));
+
+        assert_eq!(names.namespace(42).unwrap(), "fruit");
+        let lp = write_to_lp(&write, &names).unwrap();
+        assert_eq!(
+            String::from_utf8(lp).unwrap(),
+            "bananas,region=Madrid temp=35 4242\nplatanos count=1i 4243\n"
+        );
+
+        // Unknown IDs are reported
+        let write = test_write(24, "bananas temp=35 4242");
+        assert!(matches!(
+            write_to_lp(&write, &names),
+            Err(Error::NamespaceNotFound(24))
+        ));
+    }
+}
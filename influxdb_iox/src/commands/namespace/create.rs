@@ -1,6 +1,6 @@
-use influxdb_iox_client::connection::Connection;
+use influxdb_iox_client::{connection::Connection, namespace::generated_types::PartitionTemplate};
 
-use crate::commands::namespace::Result;
+use crate::commands::namespace::{parse_partition_template, Result};
 
 /// Write data into the specified database
 #[derive(Debug, clap::Parser)]
@@ -19,12 +19,28 @@ pub struct Config {
         default_value = "0"
     )]
     retention_hours: u32,
+
+    /// The partition template used for the tables of this namespace, as JSON.
+    /// If not specified, the default partition template of the router will be
+    /// used.
+    ///
+    /// For example, to partition by hour and the value of the "region" tag:
+    ///
+    /// '{"parts": [{"timeFormat": "%Y-%m-%d %H"}, {"column": "region"}]}'
+    #[clap(
+        action,
+        long = "partition-template",
+        env = "INFLUXDB_IOX_NAMESPACE_PARTITION_TEMPLATE",
+        value_parser = parse_partition_template
+    )]
+    partition_template: Option<PartitionTemplate>,
 }
 
 pub async fn command(connection: Connection, config: Config) -> Result<()> {
     let Config {
         namespace,
         retention_hours,
+        partition_template,
     } = config;
 
     let mut client = influxdb_iox_client::namespace::Client::new(connection);
@@ -37,7 +53,9 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         // internally
         Some(retention_hours as i64 * 60 * 60 * 1_000_000_000)
     };
-    let namespace = client.create_namespace(&namespace, retention).await?;
+    let namespace = client
+        .create_namespace(&namespace, retention, partition_template)
+        .await?;
     println!("{}", serde_json::to_string_pretty(&namespace)?);
 
     Ok(())
@@ -1,6 +1,9 @@
 //! This module implements the `namespace` CLI command
 
-use influxdb_iox_client::{connection::Connection, namespace};
+use influxdb_iox_client::{
+    connection::Connection,
+    namespace::{self, generated_types::PartitionTemplate},
+};
 use thiserror::Error;
 
 mod create;
@@ -20,6 +23,12 @@ pub enum Error {
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Parse the JSON representation of a [`PartitionTemplate`] given on the
+/// command line.
+pub(crate) fn parse_partition_template(s: &str) -> Result<PartitionTemplate, String> {
+    serde_json::from_str(s).map_err(|e| format!("invalid partition template: {e}"))
+}
+
 /// Various commands for namespace inspection
 #[derive(Debug, clap::Parser)]
 pub struct Config {
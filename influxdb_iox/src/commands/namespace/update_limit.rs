@@ -16,14 +16,19 @@ pub struct Config {
 #[derive(Debug, clap::Args)]
 #[clap(group(
             // This arg group "limit" links the members of the below struct 
-            // named "max_tables" and "max_columns_per_table" together as 
-            // mutually exclusive flags. As we specify all flags & commands
+            // named "max_tables", "max_columns_per_table" and the write rate
+            // limits together as mutually exclusive flags. As we specify all flags & commands
             // using clap-derive rather than the imperative builder, v3 only
             // properly supports this kind of behaviour in a macro code block.
             // NOTE: It takes the variable names and not the flag long names.
             clap::ArgGroup::new("limit")
                 .required(true)
-                .args(&["max_tables", "max_columns_per_table"])
+                .args(&[
+                    "max_tables",
+                    "max_columns_per_table",
+                    "max_lines_per_second",
+                    "max_bytes_per_second",
+                ])
         ))]
 struct Args {
     /// The maximum number of tables to allow for this namespace
@@ -33,6 +38,17 @@ struct Args {
     /// The maximum number of columns to allow per table for this namespace
     #[clap(action, long = "max-columns-per-table", short = 'c', group = "limit")]
     max_columns_per_table: Option<i32>,
+
+    /// The maximum number of lines per second the router accepts for this
+    /// namespace (0 removes the limit)
+    #[clap(action, long = "max-lines-per-second", group = "limit")]
+    max_lines_per_second: Option<i64>,
+
+    /// The maximum number of bytes per second the router accepts for this
+    /// namespace, measured as the in-memory size of the decoded write rather
+    /// than the request size (0 removes the limit)
+    #[clap(action, long = "max-bytes-per-second", group = "limit")]
+    max_bytes_per_second: Option<i64>,
 }
 
 impl From<Args> for LimitUpdate {
@@ -40,6 +56,8 @@ impl From<Args> for LimitUpdate {
         let Args {
             max_tables,
             max_columns_per_table,
+            max_lines_per_second,
+            max_bytes_per_second,
         } = args;
 
         if let Some(n) = max_tables {
@@ -48,6 +66,12 @@ impl From<Args> for LimitUpdate {
         if let Some(n) = max_columns_per_table {
             return Self::MaxColumnsPerTable(n);
         }
+        if let Some(n) = max_lines_per_second {
+            return Self::MaxLinesPerSecond(n);
+        }
+        if let Some(n) = max_bytes_per_second {
+            return Self::MaxBytesPerSecond(n);
+        }
         unreachable!();
     }
 }
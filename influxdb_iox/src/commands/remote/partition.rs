@@ -368,6 +368,7 @@ async fn load_parquet_files(
                     created_at: Timestamp::new(p.created_at),
                     column_set: ColumnSet::new(p.column_set.into_iter().map(ColumnId::new)),
                     max_l0_created_at: Timestamp::new(p.max_l0_created_at),
+                    max_tombstone_sequence_number: SequenceNumber::new(0),
                 };
 
                 repos.parquet_files().create(params).await?
@@ -416,8 +417,12 @@ mod tests {
                             column_type: 1,
                         },
                     )]),
+                    partition_template: None,
                 },
             )]),
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
         };
         let res = load_schema(&catalog, "foo", &schema).await.unwrap();
 
@@ -449,8 +454,12 @@ mod tests {
                             column_type: 1,
                         },
                     )]),
+                    partition_template: None,
                 },
             )]),
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
         };
         assert!(load_schema(&catalog, "foo", &schema).await.is_ok());
 
@@ -470,6 +479,7 @@ mod tests {
                                 column_type: 1,
                             },
                         )]),
+                        partition_template: None,
                     },
                 ),
                 (
@@ -492,9 +502,13 @@ mod tests {
                                 },
                             ),
                         ]),
+                        partition_template: None,
                     },
                 ),
             ]),
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
         };
 
         let res = load_schema(&catalog, "foo", &schema).await.unwrap();
@@ -582,6 +596,7 @@ mod tests {
                 created_at: created_at.get(),
                 column_set: vec![1, 2],
                 max_l0_created_at: created_at.get(),
+                max_tombstone_sequence_number: SequenceNumber::new(0),
             }],
         )
         .await
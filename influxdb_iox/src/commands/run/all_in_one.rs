@@ -452,10 +452,13 @@ impl Config {
         let ingester_config = Ingester2Config {
             wal_directory,
             wal_rotation_period_seconds,
+            wal_replay_strict: false,
             concurrent_query_limit,
             persist_max_parallelism,
             persist_queue_depth,
             persist_hot_partition_cost,
+            persist_idle_partition_seconds: None,
+            persist_buffer_watermark: None,
         };
 
         let router_config = Router2Config {
@@ -469,6 +472,8 @@ impl Config {
             rpc_write_timeout_seconds: Duration::new(3, 0),
             rpc_write_replicas: None,
             single_tenant_deployment: false,
+            partial_writes_enabled: false,
+            namespace_cache_ttl_seconds: Duration::from_secs(60),
         };
 
         // create a CompactorConfig for the all in one server based on
@@ -1,4 +1,7 @@
-use std::{borrow::Cow, convert::TryInto, path::PathBuf, sync::Arc, time::Instant};
+use std::{
+    borrow::Cow, collections::BTreeSet, convert::TryInto, path::PathBuf, str::FromStr, sync::Arc,
+    time::Instant,
+};
 
 use arrow::{
     array::{ArrayRef, Int64Array, StringArray},
@@ -11,7 +14,14 @@ use snafu::{ResultExt, Snafu};
 
 use super::repl_command::ReplCommand;
 
-use influxdb_iox_client::{connection::Connection, format::QueryOutputFormat};
+use influxdb_iox_client::{
+    connection::Connection,
+    format::{
+        influxql::{write_columnar, Options},
+        QueryOutputFormat,
+    },
+    schema::generated_types::{column_schema::ColumnType, NamespaceSchema},
+};
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -28,15 +38,29 @@ pub enum Error {
         source: influxdb_iox_client::format::Error,
     },
 
+    #[snafu(display("Error formatting InfluxQL results: {}", source))]
+    FormattingInfluxQLResults {
+        source: influxdb_iox_client::format::influxql::Error,
+    },
+
     #[snafu(display("Error setting format to '{}': {}", requested_format, source))]
     SettingFormat {
         requested_format: String,
         source: influxdb_iox_client::format::Error,
     },
 
+    #[snafu(display(
+        "Unknown query language '{}', expected 'sql' or 'influxql'",
+        requested_language
+    ))]
+    SettingLanguage { requested_language: String },
+
     #[snafu(display("Error parsing command: {}", message))]
     ParsingCommand { message: String },
 
+    #[snafu(display("Table '{}' not found in namespace '{}'", table_name, db_name))]
+    TableNotFound { table_name: String, db_name: String },
+
     #[snafu(display("Error running remote query: {}", source))]
     RunningRemoteQuery {
         source: influxdb_iox_client::flight::Error,
@@ -53,9 +77,44 @@ enum QueryEngine {
     Remote(String),
 }
 
+/// The language queries are interpreted as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QueryLanguage {
+    Sql,
+    InfluxQL,
+}
+
+impl FromStr for QueryLanguage {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sql" => Ok(Self::Sql),
+            "influxql" => Ok(Self::InfluxQL),
+            _ => SettingLanguageSnafu {
+                requested_language: s,
+            }
+            .fail(),
+        }
+    }
+}
+
+impl std::fmt::Display for QueryLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sql => write!(f, "SQL"),
+            Self::InfluxQL => write!(f, "InfluxQL"),
+        }
+    }
+}
+
 struct RustylineHelper {
     hinter: rustyline::hint::HistoryHinter,
     highlighter: rustyline::highlight::MatchingBracketHighlighter,
+
+    /// Table and column names of the current namespace, offered as
+    /// completions
+    completions: BTreeSet<String>,
 }
 
 impl Default for RustylineHelper {
@@ -63,6 +122,7 @@ impl Default for RustylineHelper {
         Self {
             hinter: rustyline::hint::HistoryHinter {},
             highlighter: rustyline::highlight::MatchingBracketHighlighter::default(),
+            completions: BTreeSet::new(),
         }
     }
 }
@@ -76,7 +136,8 @@ impl rustyline::validate::Validator for RustylineHelper {
     ) -> rustyline::Result<rustyline::validate::ValidationResult> {
         let input = ctx.input();
 
-        if input.trim_end().ends_with(';') {
+        // backslash commands do not need to be terminated by ';'
+        if input.trim_end().ends_with(';') || input.trim_start().starts_with('\\') {
             match ReplCommand::try_from(input) {
                 Ok(_) => Ok(rustyline::validate::ValidationResult::Valid(None)),
                 Err(err) => Ok(rustyline::validate::ValidationResult::Invalid(Some(err))),
@@ -142,6 +203,21 @@ impl rustyline::completion::Completer for RustylineHelper {
         pos: usize,
         ctx: &rustyline::Context<'_>,
     ) -> rustyline::Result<(usize, Vec<Self::Candidate>)> {
+        // Complete the word being typed with matching table and column names
+        let start = word_start(line, pos);
+        let prefix = &line[start..pos];
+        if !prefix.is_empty() {
+            let candidates: Vec<_> = self
+                .completions
+                .iter()
+                .filter(|name| name.starts_with(prefix))
+                .cloned()
+                .collect();
+            if !candidates.is_empty() {
+                return Ok((start, candidates));
+            }
+        }
+
         // If there is a hint, use that as the auto-complete when user hits `tab`
         if let Some(hint) = self.hinter.hint(line, pos, ctx) {
             let start_pos = pos;
@@ -164,6 +240,9 @@ pub struct Repl {
     /// Client for interacting with IOx namespace API
     namespace_client: influxdb_iox_client::namespace::Client,
 
+    /// Client for fetching the schema of namespaces
+    schema_client: influxdb_iox_client::schema::Client,
+
     /// Client for running sql
     flight_client: influxdb_iox_client::flight::Client,
 
@@ -172,6 +251,12 @@ pub struct Repl {
 
     /// Formatter to use to format query results
     output_format: QueryOutputFormat,
+
+    /// Language queries are run as
+    query_language: QueryLanguage,
+
+    /// Print the execution time of each query
+    timing: bool,
 }
 
 impl Repl {
@@ -182,6 +267,7 @@ impl Repl {
     /// Create a new Repl instance, connected to the specified URL
     pub fn new(connection: Connection) -> Result<Self> {
         let namespace_client = influxdb_iox_client::namespace::Client::new(connection.clone());
+        let schema_client = influxdb_iox_client::schema::Client::new(connection.clone());
         let flight_client = influxdb_iox_client::flight::Client::new(connection);
 
         let mut rl = Editor::new().context(ReplCreationSnafu)?;
@@ -200,9 +286,12 @@ impl Repl {
             rl,
             prompt,
             namespace_client,
+            schema_client,
             flight_client,
             query_engine: None,
             output_format,
+            query_language: QueryLanguage::Sql,
+            timing: true,
         })
     }
 
@@ -222,11 +311,29 @@ impl Repl {
                         .map_err(|e| println!("{e}"))
                         .ok();
                 }
+                ReplCommand::ShowTables => match self.query_language {
+                    // SHOW TABLES is valid SQL, so let the server answer it
+                    QueryLanguage::Sql => {
+                        self.run_query("SHOW TABLES".to_string())
+                            .await
+                            .map_err(|e| println!("{e}"))
+                            .ok();
+                    }
+                    QueryLanguage::InfluxQL => {
+                        self.list_tables().await.map_err(|e| println!("{e}")).ok();
+                    }
+                },
+                ReplCommand::Describe { table_name } => {
+                    self.describe_table(table_name)
+                        .await
+                        .map_err(|e| println!("{e}"))
+                        .ok();
+                }
                 ReplCommand::UseNamespace { db_name } => {
-                    self.use_namespace(db_name);
+                    self.use_namespace(db_name).await;
                 }
                 ReplCommand::SqlCommand { sql } => {
-                    self.run_sql(sql).await.map_err(|e| println!("{e}")).ok();
+                    self.run_query(sql).await.map_err(|e| println!("{e}")).ok();
                 }
                 ReplCommand::Exit => {
                     info!("exiting at user request");
@@ -235,6 +342,16 @@ impl Repl {
                 ReplCommand::SetFormat { format } => {
                     self.set_output_format(format)?;
                 }
+                ReplCommand::SetLanguage { language } => {
+                    self.set_query_language(language)
+                        .map_err(|e| println!("{e}"))
+                        .ok();
+                }
+                ReplCommand::Timing { enabled } => {
+                    self.timing = enabled.unwrap_or(!self.timing);
+                    let state = if self.timing { "on" } else { "off" };
+                    println!("Timing is {state}");
+                }
             }
         }
     }
@@ -287,23 +404,119 @@ impl Repl {
         self.print_results(&[record_batch])
     }
 
-    // Run a command against the currently selected remote namespace
-    async fn run_sql(&mut self, sql: String) -> Result<()> {
+    // print all tables in the current namespace to the output
+    async fn list_tables(&mut self) -> Result<()> {
+        let Some(schema) = self.load_schema().await? else {
+            return Ok(());
+        };
+
+        let mut tables: Vec<_> = schema.tables.into_iter().collect();
+        tables.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        let table_id: Int64Array = tables.iter().map(|(_, t)| Some(t.id)).collect();
+        let name: StringArray = tables.iter().map(|(name, _)| Some(name)).collect();
+
+        let record_batch = RecordBatch::try_from_iter(vec![
+            ("table_id", Arc::new(table_id) as ArrayRef),
+            ("name", Arc::new(name) as ArrayRef),
+        ])
+        .expect("creating record batch successfully");
+
+        self.print_results(&[record_batch])
+    }
+
+    // print the columns of a table in the current namespace to the output
+    async fn describe_table(&mut self, table_name: String) -> Result<()> {
+        let Some(mut schema) = self.load_schema().await? else {
+            return Ok(());
+        };
+
+        let Some(table) = schema.tables.remove(&table_name) else {
+            return TableNotFoundSnafu {
+                table_name,
+                db_name: self.namespace().unwrap_or_default(),
+            }
+            .fail();
+        };
+
+        let mut columns: Vec<_> = table.columns.into_iter().collect();
+        columns.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        let column_id: Int64Array = columns.iter().map(|(_, c)| Some(c.id)).collect();
+        let name: StringArray = columns.iter().map(|(name, _)| Some(name)).collect();
+        let column_type: StringArray = columns
+            .iter()
+            .map(|(_, c)| Some(column_type_name(c.column_type())))
+            .collect();
+
+        let record_batch = RecordBatch::try_from_iter(vec![
+            ("column_id", Arc::new(column_id) as ArrayRef),
+            ("name", Arc::new(name) as ArrayRef),
+            ("column_type", Arc::new(column_type) as ArrayRef),
+        ])
+        .expect("creating record batch successfully");
+
+        self.print_results(&[record_batch])
+    }
+
+    /// Fetch the schema of the current namespace, refreshing the
+    /// completions with its table and column names.
+    ///
+    /// Returns `None` if no namespace is selected.
+    async fn load_schema(&mut self) -> Result<Option<NamespaceSchema>> {
+        let Some(db_name) = self.namespace() else {
+            print_no_namespace();
+            return Ok(None);
+        };
+
+        let schema = self
+            .schema_client
+            .get_schema(&db_name)
+            .await
+            .map_err(|e| Box::new(e) as _)
+            .context(LoadingRemoteStateSnafu)?;
+
+        self.set_completions(&schema);
+        Ok(Some(schema))
+    }
+
+    /// Set the completions to the table and column names of `schema`
+    fn set_completions(&mut self, schema: &NamespaceSchema) {
+        if let Some(helper) = self.rl.helper_mut() {
+            helper.completions = schema
+                .tables
+                .iter()
+                .flat_map(|(name, table)| std::iter::once(name).chain(table.columns.keys()))
+                .cloned()
+                .collect();
+        }
+    }
+
+    // Run a query against the currently selected remote namespace, in
+    // the current query language
+    async fn run_query(&mut self, query: String) -> Result<()> {
         let start = Instant::now();
 
         let batches: Vec<_> = match &self.query_engine {
             None => {
-                println!("Error: no namespace selected.");
-                println!("Hint: Run USE NAMESPACE <dbname> to select namespace");
+                print_no_namespace();
                 return Ok(());
             }
             Some(QueryEngine::Remote(db_name)) => {
-                info!(%db_name, %sql, "Running sql on remote namespace");
+                let language = self.query_language;
+                info!(%db_name, %query, %language, "Running query on remote namespace");
+
+                let stream = match language {
+                    QueryLanguage::Sql => self.flight_client.sql(db_name.to_string(), query).await,
+                    QueryLanguage::InfluxQL => {
+                        self.flight_client
+                            .influxql(db_name.to_string(), query)
+                            .await
+                    }
+                }
+                .context(RunningRemoteQuerySnafu)?;
 
-                self.flight_client
-                    .sql(db_name.to_string(), sql)
-                    .await
-                    .context(RunningRemoteQuerySnafu)?
+                stream
                     .try_collect()
                     .await
                     .context(RunningRemoteQuerySnafu)?
@@ -311,13 +524,26 @@ impl Repl {
         };
 
         let end = Instant::now();
-        self.print_results(&batches)?;
+        if is_explain(&batches) && matches!(self.output_format, QueryOutputFormat::Pretty) {
+            print_explain(&batches);
+        } else if self.query_language == QueryLanguage::InfluxQL
+            && matches!(self.output_format, QueryOutputFormat::Pretty)
+        {
+            write_columnar(std::io::stdout(), &batches, Options::default())
+                .context(FormattingInfluxQLResultsSnafu)?;
+        } else {
+            self.print_results(&batches)?;
+        }
 
-        println!(
-            "Returned {} in {:?}",
-            Self::row_summary(&batches),
-            end - start
-        );
+        if self.timing {
+            println!(
+                "Returned {} in {:?}",
+                Self::row_summary(&batches),
+                end - start
+            );
+        } else {
+            println!("Returned {}", Self::row_summary(&batches));
+        }
         Ok(())
     }
 
@@ -333,10 +559,26 @@ impl Repl {
         }
     }
 
-    fn use_namespace(&mut self, db_name: String) {
+    async fn use_namespace(&mut self, db_name: String) {
         info!(%db_name, "setting current namespace");
         println!("You are now in remote mode, querying namespace {db_name}");
         self.set_query_engine(QueryEngine::Remote(db_name));
+
+        // Completions are best effort, as the namespace may not exist yet
+        if let Err(e) = self.load_schema().await {
+            debug!(%e, "error loading schema for completions");
+            if let Some(helper) = self.rl.helper_mut() {
+                helper.completions.clear();
+            }
+        }
+    }
+
+    /// Return the name of the currently selected namespace, if any
+    fn namespace(&self) -> Option<String> {
+        match &self.query_engine {
+            Some(QueryEngine::Remote(db_name)) => Some(db_name.clone()),
+            None => None,
+        }
     }
 
     fn set_query_engine(&mut self, query_engine: QueryEngine) {
@@ -359,6 +601,13 @@ impl Repl {
         Ok(())
     }
 
+    /// Sets the language queries are run as
+    fn set_query_language(&mut self, requested_language: String) -> Result<()> {
+        self.query_language = requested_language.parse()?;
+        println!("Set query language to {}", self.query_language);
+        Ok(())
+    }
+
     /// Prints to the specified output format
     fn print_results(&self, batches: &[RecordBatch]) -> Result<()> {
         let formatted_results = self
@@ -380,6 +629,67 @@ impl Drop for Repl {
     }
 }
 
+fn print_no_namespace() {
+    println!("Error: no namespace selected.");
+    println!("Hint: Run USE NAMESPACE <dbname> to select namespace");
+}
+
+/// Return the display name of a column type
+fn column_type_name(column_type: ColumnType) -> &'static str {
+    match column_type {
+        ColumnType::Unspecified => "unknown",
+        ColumnType::I64 => "i64",
+        ColumnType::U64 => "u64",
+        ColumnType::F64 => "f64",
+        ColumnType::Bool => "bool",
+        ColumnType::String => "string",
+        ColumnType::Time => "time",
+        ColumnType::Tag => "tag",
+    }
+}
+
+/// Returns true if `batches` are the output of `EXPLAIN` or `EXPLAIN
+/// ANALYZE`, which have `plan_type` and `plan` columns
+fn is_explain(batches: &[RecordBatch]) -> bool {
+    batches.first().map_or(false, |batch| {
+        let schema = batch.schema();
+        schema.column_with_name("plan_type").is_some() && schema.column_with_name("plan").is_some()
+    })
+}
+
+/// Print the output of `EXPLAIN` as the plan text under a heading
+/// for each plan type, rather than as a table with multi-line cells
+fn print_explain(batches: &[RecordBatch]) {
+    for batch in batches {
+        let column = |name| {
+            batch
+                .column_by_name(name)
+                .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+        };
+        let (Some(plan_type), Some(plan)) = (column("plan_type"), column("plan")) else {
+            continue;
+        };
+
+        for (plan_type, plan) in plan_type.iter().zip(plan.iter()) {
+            println!("{}:", plan_type.unwrap_or_default());
+            for line in plan.unwrap_or_default().lines() {
+                println!("  {line}");
+            }
+            println!();
+        }
+    }
+}
+
+/// Return the start of the (table or column name) word ending at `pos`
+fn word_start(line: &str, pos: usize) -> usize {
+    line[..pos]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphanumeric() || *c == '_' || *c == '-')
+        .last()
+        .map_or(pos, |(i, _)| i)
+}
+
 fn is_exit_command(line: &str) -> bool {
     let line = line.trim_end().to_lowercase();
     line == "quit" || line == "exit"
@@ -5,7 +5,11 @@ use observability_deps::tracing::{debug, warn};
 pub enum ReplCommand {
     Help,
     ShowNamespaces,
+    ShowTables,
+    Describe { table_name: String },
     SetFormat { format: String },
+    SetLanguage { language: String },
+    Timing { enabled: Option<bool> },
     UseNamespace { db_name: String },
     SqlCommand { sql: String },
     Exit,
@@ -78,11 +82,28 @@ impl TryFrom<&str> for ReplCommand {
                 })
             }
             ["show", "namespaces"] => Ok(Self::ShowNamespaces),
+            ["show", "tables"] => Ok(Self::ShowTables),
+            ["describe"] => Err("table not specified. Usage: DESCRIBE <table>".to_string()),
+            ["describe", _table_name] => Ok(Self::Describe {
+                table_name: raw_commands[1].to_string(),
+            }),
             ["set", "format", _format] => Ok(Self::SetFormat {
                 format: raw_commands[2].to_string(),
             }),
+            ["set", "lang" | "language", _language] => Ok(Self::SetLanguage {
+                language: raw_commands[2].to_string(),
+            }),
+            // toggle, or explicitly set, the display of query timing
+            ["\\timing"] => Ok(Self::Timing { enabled: None }),
+            ["\\timing", "on"] => Ok(Self::Timing {
+                enabled: Some(true),
+            }),
+            ["\\timing", "off"] => Ok(Self::Timing {
+                enabled: Some(false),
+            }),
             _ => {
-                // By default, treat the entire string as SQL
+                // By default, treat the entire string as a query in
+                // the current language
                 Ok(Self::SqlCommand { sql: input.into() })
             }
         }
@@ -100,10 +121,20 @@ SHOW NAMESPACES: List namespaces available on the server
 
 USE NAMESPACE <name>: Set the current remote namespace to name
 
+SHOW TABLES: List tables in the current namespace (run by the server in SQL mode)
+
+DESCRIBE <table>: List the columns of a table in the current namespace
+
 SET FORMAT <format>: Set the output format to Pretty, csv or json
 
+SET LANG <language>: Set the query language to sql or influxql
+
+\TIMING [ON | OFF]: Toggle the display of query execution time (default on)
+
 [EXIT | QUIT]: Quit this session and exit the program
 
+Table and column names of the current namespace are completed with <tab>
+
 # Examples: use remote namespace foo
 SHOW NAMESPACES;
 USE foo;
@@ -112,7 +143,13 @@ USE foo;
 
 ;; Explore Schema:
 SHOW TABLES; ;; Show available tables
-SHOW COLUMNS FROM my_table; ;; Show columns in the table
+DESCRIBE my_table; ;; Show columns in the table
+EXPLAIN ANALYZE SELECT * FROM my_table; ;; Show the plan, with metrics
+
+# Basic InfluxQL Primer
+SET LANG influxql;
+SHOW MEASUREMENTS;
+SELECT * FROM my_measurement WHERE time > now() - 1h;
 
 "#
     }
@@ -213,6 +250,68 @@ mod tests {
         assert_eq!("set format Hmm".try_into(), expected);
     }
 
+    #[test]
+    fn show_tables() {
+        let expected = Ok(ReplCommand::ShowTables);
+        assert_eq!("show tables".try_into(), expected);
+        assert_eq!("SHOW TABLES;".try_into(), expected);
+        assert_eq!(" show  Tables ; ".try_into(), expected);
+
+        assert_eq!(
+            "SHOW TABLES FROM foo".try_into(),
+            sql_cmd("SHOW TABLES FROM foo")
+        );
+    }
+
+    #[test]
+    fn describe() {
+        let expected = Ok(ReplCommand::Describe {
+            table_name: "Foo".to_string(),
+        });
+        assert_eq!("describe Foo".try_into(), expected);
+        assert_eq!("DESCRIBE Foo;".try_into(), expected);
+        assert_eq!("  describe  Foo ; ".try_into(), expected);
+
+        let expected: Result<ReplCommand, String> =
+            Err("table not specified. Usage: DESCRIBE <table>".to_string());
+        assert_eq!("describe".try_into(), expected);
+        assert_eq!("DESCRIBE;".try_into(), expected);
+    }
+
+    #[test]
+    fn set_language() {
+        let expected = Ok(ReplCommand::SetLanguage {
+            language: "influxql".to_string(),
+        });
+        assert_eq!("set lang influxql".try_into(), expected);
+        assert_eq!("SET LANG influxql;".try_into(), expected);
+        assert_eq!("set language influxql".try_into(), expected);
+
+        let expected = Ok(ReplCommand::SetLanguage {
+            language: "SQL".to_string(),
+        });
+        assert_eq!("set lang SQL".try_into(), expected);
+    }
+
+    #[test]
+    fn timing() {
+        let expected = Ok(ReplCommand::Timing { enabled: None });
+        assert_eq!("\\timing".try_into(), expected);
+        assert_eq!("\\timing;".try_into(), expected);
+        assert_eq!("\\TIMING".try_into(), expected);
+
+        let expected = Ok(ReplCommand::Timing {
+            enabled: Some(true),
+        });
+        assert_eq!("\\timing on".try_into(), expected);
+        assert_eq!("\\timing ON;".try_into(), expected);
+
+        let expected = Ok(ReplCommand::Timing {
+            enabled: Some(false),
+        });
+        assert_eq!("\\timing off".try_into(), expected);
+    }
+
     #[test]
     fn sql_command() {
         let expected = sql_cmd("SELECT * from foo");
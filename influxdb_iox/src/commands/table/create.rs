@@ -0,0 +1,42 @@
+use influxdb_iox_client::{connection::Connection, table::generated_types::PartitionTemplate};
+
+use crate::commands::{namespace::parse_partition_template, table::Result};
+
+/// Create a table in the specified namespace
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The namespace the table is created in
+    #[clap(action)]
+    namespace: String,
+
+    /// The name of the table to be created
+    #[clap(action)]
+    table: String,
+
+    /// The partition template used for this table, as JSON.
+    /// If not specified, the partition template of the namespace will be used.
+    #[clap(
+        action,
+        long = "partition-template",
+        env = "INFLUXDB_IOX_TABLE_PARTITION_TEMPLATE",
+        value_parser = parse_partition_template
+    )]
+    partition_template: Option<PartitionTemplate>,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let Config {
+        namespace,
+        table,
+        partition_template,
+    } = config;
+
+    let mut client = influxdb_iox_client::table::Client::new(connection);
+
+    let table = client
+        .create_table(&namespace, &table, partition_template)
+        .await?;
+    println!("{}", serde_json::to_string_pretty(&table)?);
+
+    Ok(())
+}
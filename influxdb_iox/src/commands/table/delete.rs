@@ -0,0 +1,26 @@
+use influxdb_iox_client::connection::Connection;
+
+use crate::commands::table::Result;
+
+/// Soft-delete a table in the specified namespace
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The namespace the table belongs to
+    #[clap(action)]
+    namespace: String,
+
+    /// The name of the table to be deleted
+    #[clap(action)]
+    table: String,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let Config { namespace, table } = config;
+
+    let mut client = influxdb_iox_client::table::Client::new(connection);
+
+    client.delete_table(&namespace, &table).await?;
+    println!("Deleted table {table:?} in namespace {namespace:?}");
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use influxdb_iox_client::connection::Connection;
+
+use crate::commands::table::Result;
+
+/// Soft-delete a column of a table in the specified namespace
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The namespace the table belongs to
+    #[clap(action)]
+    namespace: String,
+
+    /// The name of the table the column belongs to
+    #[clap(action)]
+    table: String,
+
+    /// The name of the column to be deleted
+    #[clap(action)]
+    column: String,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let Config {
+        namespace,
+        table,
+        column,
+    } = config;
+
+    let mut client = influxdb_iox_client::table::Client::new(connection);
+
+    client.delete_column(&namespace, &table, &column).await?;
+    println!("Deleted column {column:?} of table {table:?} in namespace {namespace:?}");
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! This module implements the `table` CLI command
+
+use influxdb_iox_client::connection::Connection;
+use thiserror::Error;
+
+mod create;
+mod delete;
+mod delete_column;
+mod rename;
+mod rename_column;
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("JSON Serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+
+    #[error("Client error: {0}")]
+    ClientError(#[from] influxdb_iox_client::error::Error),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Various commands for table manipulation
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+/// All possible subcommands for table
+#[derive(Debug, clap::Parser)]
+enum Command {
+    /// Create a new table
+    Create(create::Config),
+
+    /// Soft-delete a table, along with all of its data
+    Delete(delete::Config),
+
+    /// Soft-delete a column of a table
+    DeleteColumn(delete_column::Config),
+
+    /// Rename a table
+    Rename(rename::Config),
+
+    /// Rename a column of a table
+    RenameColumn(rename_column::Config),
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    match config.command {
+        Command::Create(config) => {
+            create::command(connection, config).await?;
+        }
+        Command::Delete(config) => {
+            delete::command(connection, config).await?;
+        }
+        Command::DeleteColumn(config) => {
+            delete_column::command(connection, config).await?;
+        }
+        Command::Rename(config) => {
+            rename::command(connection, config).await?;
+        }
+        Command::RenameColumn(config) => {
+            rename_column::command(connection, config).await?;
+        } // Deliberately not adding _ => so the compiler will direct people here to impl new
+          // commands
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use influxdb_iox_client::connection::Connection;
+
+use crate::commands::table::Result;
+
+/// Rename a table in the specified namespace
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The namespace the table belongs to
+    #[clap(action)]
+    namespace: String,
+
+    /// The current name of the table
+    #[clap(action)]
+    table: String,
+
+    /// The new name of the table
+    #[clap(action)]
+    new_name: String,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let Config {
+        namespace,
+        table,
+        new_name,
+    } = config;
+
+    let mut client = influxdb_iox_client::schema::Client::new(connection);
+
+    client.rename_table(&namespace, &table, &new_name).await?;
+    println!("Renamed table {table:?} to {new_name:?} in namespace {namespace:?}");
+
+    Ok(())
+}
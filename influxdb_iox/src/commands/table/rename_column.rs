@@ -0,0 +1,45 @@
+use influxdb_iox_client::connection::Connection;
+
+use crate::commands::table::Result;
+
+/// Rename a column of a table in the specified namespace
+///
+/// Columns that have already been persisted cannot be renamed.
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The namespace the table belongs to
+    #[clap(action)]
+    namespace: String,
+
+    /// The name of the table the column belongs to
+    #[clap(action)]
+    table: String,
+
+    /// The current name of the column
+    #[clap(action)]
+    column: String,
+
+    /// The new name of the column
+    #[clap(action)]
+    new_name: String,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let Config {
+        namespace,
+        table,
+        column,
+        new_name,
+    } = config;
+
+    let mut client = influxdb_iox_client::schema::Client::new(connection);
+
+    client
+        .rename_column(&namespace, &table, &column, &new_name)
+        .await?;
+    println!(
+        "Renamed column {column:?} of table {table:?} to {new_name:?} in namespace {namespace:?}"
+    );
+
+    Ok(())
+}
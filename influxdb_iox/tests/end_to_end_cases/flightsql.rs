@@ -1,7 +1,7 @@
 use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use arrow::{
-    array::as_generic_binary_array,
+    array::{as_generic_binary_array, ArrayRef, Int64Array, StringArray},
     datatypes::{DataType, Fields, Schema, SchemaRef, TimeUnit},
     record_batch::RecordBatch,
 };
@@ -9,8 +9,9 @@ use arrow_flight::{
     decode::FlightRecordBatchStream,
     error::FlightError,
     sql::{
-        Any, CommandGetCatalogs, CommandGetDbSchemas, CommandGetSqlInfo, CommandGetTableTypes,
-        CommandGetTables, CommandStatementQuery, ProstMessageExt, SqlInfo,
+        Any, CommandGetCatalogs, CommandGetDbSchemas, CommandGetPrimaryKeys, CommandGetSqlInfo,
+        CommandGetTableTypes, CommandGetTables, CommandGetXdbcTypeInfo, CommandStatementQuery,
+        ProstMessageExt, SqlInfo,
     },
     FlightClient, FlightDescriptor, IpcMessage,
 };
@@ -157,6 +158,282 @@ async fn flightsql_prepared_query() {
     .await
 }
 
+#[tokio::test]
+async fn flightsql_prepared_query_with_parameters() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared2(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457\n\
+                 {table_name},tag1=A,tag2=C val=44i 123458"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    // positional (JDBC style) placeholders
+                    let sql = format!("select * from {table_name} where tag2 = ? and val > ?");
+                    let mut client = flightsql_client(state.cluster());
+
+                    let mut handle = client.prepare(sql).await.unwrap();
+
+                    // one parameter per placeholder, with the type inferred from the query
+                    let parameter_schema = handle.get_parameter_schema();
+                    let names: Vec<_> = parameter_schema
+                        .fields()
+                        .iter()
+                        .map(|f| f.name().as_str())
+                        .collect();
+                    assert_eq!(names, ["$1", "$2"]);
+                    assert_eq!(parameter_schema.field(1).data_type(), &DataType::Int64);
+
+                    handle.set_parameters(parameters("C", 42)).unwrap();
+                    let stream = client.execute(handle.clone()).await.unwrap();
+
+                    let batches = collect_stream(stream).await;
+                    insta::assert_yaml_snapshot!(
+                        batches_to_sorted_lines(&batches),
+                        @r###"
+                    ---
+                    - +------+------+--------------------------------+-----+
+                    - "| tag1 | tag2 | time                           | val |"
+                    - +------+------+--------------------------------+-----+
+                    - "| A    | C    | 1970-01-01T00:00:00.000123457Z | 43  |"
+                    - "| A    | C    | 1970-01-01T00:00:00.000123458Z | 44  |"
+                    - +------+------+--------------------------------+-----+
+                    "###
+                    );
+
+                    // the same statement can be rebound with different values
+                    handle.set_parameters(parameters("C", 43)).unwrap();
+                    let stream = client.execute(handle).await.unwrap();
+
+                    let batches = collect_stream(stream).await;
+                    insta::assert_yaml_snapshot!(
+                        batches_to_sorted_lines(&batches),
+                        @r###"
+                    ---
+                    - +------+------+--------------------------------+-----+
+                    - "| tag1 | tag2 | time                           | val |"
+                    - +------+------+--------------------------------+-----+
+                    - "| A    | C    | 1970-01-01T00:00:00.000123458Z | 44  |"
+                    - +------+------+--------------------------------+-----+
+                    "###
+                    );
+
+                    // numbered placeholders
+                    let sql = format!("select * from {table_name} where val > $2 and tag2 = $1");
+                    let mut handle = client.prepare(sql).await.unwrap();
+                    assert_eq!(handle.get_parameter_schema().fields().len(), 2);
+
+                    handle.set_parameters(parameters("B", 0)).unwrap();
+                    let stream = client.execute(handle).await.unwrap();
+
+                    let batches = collect_stream(stream).await;
+                    insta::assert_yaml_snapshot!(
+                        batches_to_sorted_lines(&batches),
+                        @r###"
+                    ---
+                    - +------+------+--------------------------------+-----+
+                    - "| tag1 | tag2 | time                           | val |"
+                    - +------+------+--------------------------------+-----+
+                    - "| A    | B    | 1970-01-01T00:00:00.000123456Z | 42  |"
+                    - +------+------+--------------------------------+-----+
+                    "###
+                    );
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
+#[tokio::test]
+async fn flightsql_prepared_query_invalid_parameters() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared2(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let sql = format!("select * from {table_name} where tag2 = ? and val > ?");
+                    let mut client = flightsql_client(state.cluster());
+
+                    // too few parameter values
+                    let mut handle = client.prepare(sql.clone()).await.unwrap();
+                    let array = Arc::new(StringArray::from(vec!["C"])) as ArrayRef;
+                    handle
+                        .set_parameters(RecordBatch::try_from_iter([("$1", array)]).unwrap())
+                        .unwrap();
+
+                    let err = client.execute(handle).await.unwrap_err();
+                    let status = match err {
+                        FlightError::Tonic(status) => status,
+                        e => panic!("Expected tonic error, got {e}"),
+                    };
+                    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+                    assert_contains!(status.message(), "expected 2 parameter values, got 1");
+
+                    // parameter values larger than the DoPut limit
+                    let mut handle = client.prepare(sql).await.unwrap();
+                    let tag2 = "C".repeat(2 * 1024 * 1024);
+                    handle.set_parameters(parameters(&tag2, 42)).unwrap();
+
+                    let err = client.execute(handle).await.unwrap_err();
+                    let status = match err {
+                        FlightError::Tonic(status) => status,
+                        e => panic!("Expected tonic error, got {e}"),
+                    };
+                    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+                    assert_contains!(status.message(), "parameter values exceed the maximum");
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
+/// Return a single row of prepared statement parameters, with a string
+/// value for `$1` and an integer value for `$2`
+fn parameters(tag2: &str, val: i64) -> RecordBatch {
+    RecordBatch::try_from_iter([
+        ("$1", Arc::new(StringArray::from(vec![tag2])) as ArrayRef),
+        ("$2", Arc::new(Int64Array::from(vec![val])) as ArrayRef),
+    ])
+    .unwrap()
+}
+
+#[tokio::test]
+async fn flightsql_influxql_adhoc_query() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared2(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let query = format!("select tag1, val from {table_name}");
+                    let mut client = flightsql_client(state.cluster());
+                    client.add_header("query-language", "influxql").unwrap();
+
+                    let stream = client.query(query).await.unwrap();
+                    let batches = collect_stream(stream).await;
+                    insta::assert_yaml_snapshot!(
+                        batches_to_sorted_lines(&batches),
+                        @r###"
+                    ---
+                    - +------------------+--------------------------------+------+-----+
+                    - "| iox::measurement | time                           | tag1 | val |"
+                    - +------------------+--------------------------------+------+-----+
+                    - "| the_table        | 1970-01-01T00:00:00.000123456Z | A    | 42  |"
+                    - "| the_table        | 1970-01-01T00:00:00.000123457Z | A    | 43  |"
+                    - +------------------+--------------------------------+------+-----+
+                    "###
+                    );
+
+                    // an unknown query language is rejected
+                    let mut client = flightsql_client(state.cluster());
+                    client.add_header("query-language", "flux").unwrap();
+
+                    let err = client.query("select 1".to_string()).await.unwrap_err();
+                    let status = match err {
+                        FlightError::Tonic(status) => status,
+                        e => panic!("Expected tonic error, got {e}"),
+                    };
+                    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+                    assert_contains!(status.message(), "Invalid query language 'flux'");
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
+#[tokio::test]
+async fn flightsql_influxql_prepared_query() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared2(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let query = format!("select tag1, val from {table_name} where tag2 = 'C'");
+                    let mut client = flightsql_client(state.cluster());
+                    client.add_header("query-language", "influxql").unwrap();
+
+                    let handle = client.prepare(query).await.unwrap();
+
+                    // the statement is executed as InfluxQL, regardless of
+                    // the header on the execute request
+                    let mut client = flightsql_client(state.cluster());
+                    let stream = client.execute(handle).await.unwrap();
+
+                    let batches = collect_stream(stream).await;
+                    insta::assert_yaml_snapshot!(
+                        batches_to_sorted_lines(&batches),
+                        @r###"
+                    ---
+                    - +------------------+--------------------------------+------+-----+
+                    - "| iox::measurement | time                           | tag1 | val |"
+                    - +------------------+--------------------------------+------+-----+
+                    - "| the_table        | 1970-01-01T00:00:00.000123457Z | A    | 43  |"
+                    - +------------------+--------------------------------+------+-----+
+                    "###
+                    );
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 async fn flightsql_get_sql_infos() {
     test_helpers::maybe_start_logging();
@@ -1117,6 +1394,32 @@ async fn flightsql_get_primary_keys() {
                         .unwrap();
                     let batches = collect_stream(stream).await;
 
+                    // the tag columns, followed by time
+                    insta::assert_yaml_snapshot!(
+                        batches_to_sorted_lines(&batches),
+                        @r###"
+                    ---
+                    - +--------------+----------------+------------+-------------+----------------+--------------+
+                    - "| catalog_name | db_schema_name | table_name | column_name | key_name       | key_sequence |"
+                    - +--------------+----------------+------------+-------------+----------------+--------------+
+                    - "| public       | iox            | the_table  | tag1        | the_table_pkey | 1            |"
+                    - "| public       | iox            | the_table  | tag2        | the_table_pkey | 2            |"
+                    - "| public       | iox            | the_table  | time        | the_table_pkey | 3            |"
+                    - +--------------+----------------+------------+-------------+----------------+--------------+
+                    "###
+                    );
+
+                    // system tables have no primary key
+                    let stream = client
+                        .get_primary_keys(
+                            Some("public"),
+                            Some("system"),
+                            "queries".to_string(),
+                        )
+                        .await
+                        .unwrap();
+                    let batches = collect_stream(stream).await;
+
                     insta::assert_yaml_snapshot!(
                         batches_to_sorted_lines(&batches),
                         @r###"
@@ -1134,6 +1437,80 @@ async fn flightsql_get_primary_keys() {
     .await
 }
 
+#[tokio::test]
+async fn flightsql_get_xdbc_type_info() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared2(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let mut client = flightsql_client(state.cluster());
+
+                    let stream = client.get_xdbc_type_info(None).await.unwrap();
+                    let batches = collect_stream(stream).await;
+                    let batches: Vec<_> = batches
+                        .iter()
+                        // type_name, data_type, unsigned_attribute
+                        .map(|batch| batch.project(&[0, 1, 9]).unwrap())
+                        .collect();
+
+                    insta::assert_yaml_snapshot!(
+                        batches_to_sorted_lines(&batches),
+                        @r###"
+                    ---
+                    - +-----------------+-----------+--------------------+
+                    - "| type_name       | data_type | unsigned_attribute |"
+                    - +-----------------+-----------+--------------------+
+                    - "| BIGINT          | -5        | false              |"
+                    - "| BIGINT UNSIGNED | -5        | true               |"
+                    - "| BOOLEAN         | -7        |                    |"
+                    - "| DOUBLE          | 8         | false              |"
+                    - "| TIMESTAMP       | 93        |                    |"
+                    - "| VARCHAR         | 12        |                    |"
+                    - +-----------------+-----------+--------------------+
+                    "###
+                    );
+
+                    // filtered by data type (XDBC_VARCHAR)
+                    let stream = client.get_xdbc_type_info(Some(12)).await.unwrap();
+                    let batches = collect_stream(stream).await;
+                    let batches: Vec<_> = batches
+                        .iter()
+                        .map(|batch| batch.project(&[0, 1]).unwrap())
+                        .collect();
+
+                    insta::assert_yaml_snapshot!(
+                        batches_to_sorted_lines(&batches),
+                        @r###"
+                    ---
+                    - +-----------+-----------+
+                    - "| type_name | data_type |"
+                    - +-----------+-----------+
+                    - "| VARCHAR   | 12        |"
+                    - +-----------+-----------+
+                    "###
+                    );
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 /// Runs  the `jdbc_client` program against IOx to verify JDBC via FlightSQL is working
 ///
@@ -1343,6 +1720,13 @@ async fn flightsql_schema_matches() {
                         }
                         .as_any(),
                         CommandGetTableTypes {}.as_any(),
+                        CommandGetPrimaryKeys {
+                            catalog: None,
+                            db_schema: None,
+                            table: String::from("the_table"),
+                        }
+                        .as_any(),
+                        CommandGetXdbcTypeInfo { data_type: None }.as_any(),
                     ];
 
                     for cmd in cases {
@@ -1,4 +1,5 @@
 use futures::FutureExt;
+use influxdb_iox_client::format::influxql::{write_columnar, Options};
 use test_helpers_end_to_end::{
     check_flight_error, maybe_skip_integration, try_run_influxql, Authorizer, MiniCluster, Step,
     StepTest, StepTestState,
@@ -75,6 +76,63 @@ async fn influxql_select_returns_results() {
     .await
 }
 
+#[tokio::test]
+async fn influxql_multiple_statements() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared2(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let cluster = state.cluster();
+                    // The failure of the second statement must not fail the others
+                    let (batches, _) = try_run_influxql(
+                        format!(
+                            "SELECT val FROM {table_name}; SHOW TAG KEYS ON foo; SHOW MEASUREMENTS"
+                        ),
+                        cluster.namespace(),
+                        cluster.querier().querier_grpc_connection(),
+                        None,
+                    )
+                    .await
+                    .unwrap();
+
+                    let mut out = Vec::<u8>::new();
+                    write_columnar(&mut out, &batches, Options::default()).unwrap();
+                    let out = String::from_utf8(out).unwrap();
+                    let lines = out.lines().collect::<Vec<_>>();
+
+                    let find = |s: &str| {
+                        lines
+                            .iter()
+                            .position(|l| *l == s)
+                            .unwrap_or_else(|| panic!("expected line {s:?} in:\n{out}"))
+                    };
+                    let select = find(&format!("name: {table_name}"));
+                    let error =
+                        find("ERR: This feature is not implemented: SHOW TAG KEYS ON <database>");
+                    let show_measurements = find("name: measurements");
+                    assert!(select < error && error < show_measurements, "{out}");
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 async fn authz() {
     test_helpers::maybe_start_logging();
@@ -64,7 +64,10 @@ async fn soft_deletion() {
                         state.cluster().router().router_grpc_connection(),
                     );
                     let namespace_name = state.cluster().namespace();
-                    client.create_namespace(namespace_name, None).await.unwrap();
+                    client
+                        .create_namespace(namespace_name, None, None)
+                        .await
+                        .unwrap();
                     let namespaces = client.get_namespaces().await.unwrap();
                     let created_namespace = namespaces
                         .iter()
@@ -192,7 +195,7 @@ async fn soft_deletion() {
                     let namespace_name = state.cluster().namespace();
 
                     let error = client
-                        .create_namespace(namespace_name, None)
+                        .create_namespace(namespace_name, None, None)
                         .await
                         .unwrap_err();
                     assert_eq!(
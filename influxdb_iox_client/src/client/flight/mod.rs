@@ -102,6 +102,12 @@ impl From<tonic::Status> for Error {
 /// For SQL queries, this client yields a stream of [`RecordBatch`]es
 /// with the same schema.
 ///
+/// For InfluxQL queries, which may contain several statements, the
+/// results of each statement are yielded in turn, and the schema of the
+/// [`RecordBatch`]es identifies the statement in the `statement_id` of its
+/// `InfluxQlMetadata`. A statement that fails yields a single
+/// [`RecordBatch`] describing the error, rather than failing the stream.
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -23,17 +23,22 @@
 
 use std::sync::Arc;
 
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::{
+    datatypes::{Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
 use arrow_flight::{
     decode::FlightRecordBatchStream,
+    encode::FlightDataEncoderBuilder,
     error::{FlightError, Result},
     sql::{
         ActionCreatePreparedStatementRequest, ActionCreatePreparedStatementResult, Any,
         CommandGetCatalogs, CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
         CommandGetImportedKeys, CommandGetPrimaryKeys, CommandGetSqlInfo, CommandGetTableTypes,
-        CommandGetTables, CommandPreparedStatementQuery, CommandStatementQuery, ProstMessageExt,
+        CommandGetTables, CommandGetXdbcTypeInfo, CommandPreparedStatementQuery,
+        CommandStatementQuery, ProstMessageExt,
     },
-    Action, FlightClient, FlightDescriptor, FlightInfo, IpcMessage, Ticket,
+    Action, FlightClient, FlightData, FlightDescriptor, FlightInfo, IpcMessage, PutResult, Ticket,
 };
 use bytes::Bytes;
 use futures_util::TryStreamExt;
@@ -361,6 +366,25 @@ impl FlightSqlClient {
         self.do_get_with_cmd(msg.as_any()).await
     }
 
+    /// List information about the data types supported by this server
+    /// using a [`CommandGetXdbcTypeInfo`] message.
+    ///
+    /// # Parameters
+    ///
+    /// Definition from <https://github.com/apache/arrow/blob/2fe17338e2d1f85d0c2685d31d2dd51f138b6b80/format/FlightSql.proto#L1036-L1062>
+    ///
+    /// data_type: Specifies the data type to search for the info.
+    /// If omitted, all types are returned.
+    ///
+    /// This implementation does not support alternate endpoints
+    pub async fn get_xdbc_type_info(
+        &mut self,
+        data_type: Option<i32>,
+    ) -> Result<FlightRecordBatchStream> {
+        let msg = CommandGetXdbcTypeInfo { data_type };
+        self.do_get_with_cmd(msg.as_any()).await
+    }
+
     /// Implements the canonical interaction for most FlightSQL messages:
     ///
     /// 1. Call `GetFlightInfo` with the provided message, and get a
@@ -464,15 +488,15 @@ impl FlightSqlClient {
         ))
     }
 
-    /// Execute a SQL query on the server using [`CommandStatementQuery`]
+    /// Execute a previously prepared statement on the server using
+    /// [`CommandPreparedStatementQuery`]
     ///
-    /// This involves two round trips
+    /// If parameters have been set with
+    /// [`PreparedStatement::set_parameters`], they are first bound by
+    /// sending them to the `DoPut` endpoint of the FlightSQL server,
+    /// and the statement is run with the handle it returns.
     ///
-    /// Step 1: send a [`CommandStatementQuery`] message to the
-    /// `GetFlightInfo` endpoint of the FlightSQL server to receive a
-    /// FlightInfo descriptor.
-    ///
-    /// Step 2: Fetch the results described in the [`FlightInfo`]
+    /// See [`Self::query`] for the remaining steps.
     ///
     /// This implementation does not support alternate endpoints
     pub async fn execute(
@@ -483,15 +507,71 @@ impl FlightSqlClient {
             prepared_statement_handle,
             dataset_schema: _,
             parameter_schema: _,
+            parameter_binding,
         } = statement;
-        // TODO handle parameters (via DoPut)
 
-        let cmd = CommandPreparedStatementQuery {
+        let mut cmd = CommandPreparedStatementQuery {
             prepared_statement_handle,
         };
 
+        if let Some(parameters) = parameter_binding {
+            if let Some(handle) = self.bind_parameters(&cmd, parameters).await? {
+                cmd.prepared_statement_handle = handle;
+            }
+        }
+
         self.do_get_with_cmd(cmd.as_any()).await
     }
+
+    /// Bind the values in `parameters` to the prepared statement in
+    /// `cmd` by sending them to the `DoPut` endpoint
+    ///
+    /// Returns the prepared statement handle to use in place of the
+    /// original, if the server returned one
+    async fn bind_parameters(
+        &mut self,
+        cmd: &CommandPreparedStatementQuery,
+        parameters: RecordBatch,
+    ) -> Result<Option<Bytes>> {
+        let descriptor = FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec());
+
+        let mut flight_data: Vec<FlightData> = FlightDataEncoderBuilder::new()
+            .build(futures_util::stream::iter([Ok(parameters)]))
+            .try_collect()
+            .await?;
+
+        // The server expects the descriptor in the first message
+        if let Some(first) = flight_data.first_mut() {
+            first.flight_descriptor = Some(descriptor);
+        }
+
+        let results: Vec<PutResult> = self
+            .inner
+            .do_put(futures_util::stream::iter(flight_data))
+            .await?
+            .try_collect()
+            .await?;
+
+        let handle = match results.last() {
+            Some(result) if !result.app_metadata.is_empty() => {
+                DoPutPreparedStatementResult::decode(result.app_metadata.clone())
+                    .map_err(|e| FlightError::ExternalError(Box::new(e)))?
+                    .prepared_statement_handle
+            }
+            _ => None,
+        };
+
+        Ok(handle)
+    }
+}
+
+/// The `app_metadata` of the `PutResult` returned when binding
+/// parameters to a prepared statement, as defined by the FlightSQL
+/// `DoPutPreparedStatementResult` message
+#[derive(Clone, PartialEq, Message)]
+struct DoPutPreparedStatementResult {
+    #[prost(bytes = "bytes", optional, tag = "1")]
+    prepared_statement_handle: Option<Bytes>,
 }
 
 fn schema_bytes_to_schema(schema: Bytes) -> Result<SchemaRef> {
@@ -515,6 +595,9 @@ pub struct PreparedStatement {
 
     /// Schema of parameters, if any
     parameter_schema: SchemaRef,
+
+    /// The parameter values to bind when executing, if any
+    parameter_binding: Option<RecordBatch>,
 }
 
 impl PreparedStatement {
@@ -530,6 +613,7 @@ impl PreparedStatement {
             prepared_statement_handle,
             dataset_schema,
             parameter_schema,
+            parameter_binding: None,
         }
     }
 
@@ -542,4 +626,19 @@ impl PreparedStatement {
     pub fn get_parameter_schema(&self) -> SchemaRef {
         Arc::clone(&self.parameter_schema)
     }
+
+    /// Set the parameter values to bind when this statement is
+    /// executed. `parameters` must contain a single row, with one
+    /// column per field in [`Self::get_parameter_schema`].
+    pub fn set_parameters(&mut self, parameters: RecordBatch) -> Result<()> {
+        if parameters.num_rows() != 1 {
+            return Err(FlightError::protocol(format!(
+                "Expected a single row of parameters, got {}",
+                parameters.num_rows()
+            )));
+        }
+
+        self.parameter_binding = Some(parameters);
+        Ok(())
+    }
 }
@@ -10,6 +10,9 @@ pub mod generated_types {
     pub use generated_types::influxdata::iox::namespace::v1::{
         update_namespace_service_protection_limit_request::LimitUpdate, *,
     };
+    pub use generated_types::influxdata::iox::partition_template::v1::{
+        template_part, PartitionTemplate, TemplatePart,
+    };
 }
 
 /// A basic client for working with Namespaces.
@@ -40,16 +43,22 @@ impl Client {
     /// drop data), and 0 is also mapped to `None` on the server side.
     ///
     /// Negative retention periods are rejected, returning an error.
+    ///
+    /// `partition_template` is the template used to partition the data of
+    /// the tables in the namespace. `None` uses the default template of the
+    /// router.
     pub async fn create_namespace(
         &mut self,
         namespace: &str,
         retention_period_ns: Option<i64>,
+        partition_template: Option<PartitionTemplate>,
     ) -> Result<Namespace, Error> {
         let response = self
             .inner
             .create_namespace(CreateNamespaceRequest {
                 name: namespace.to_string(),
                 retention_period_ns,
+                partition_template,
             })
             .await?;
 
@@ -35,4 +35,46 @@ impl Client {
 
         Ok(response.into_inner().schema.unwrap_field("schema")?)
     }
+
+    /// Rename the table `table` in `namespace` to `new_name`, returning the
+    /// ID of the renamed table.
+    pub async fn rename_table(
+        &mut self,
+        namespace: &str,
+        table: &str,
+        new_name: &str,
+    ) -> Result<i64, Error> {
+        let response = self
+            .inner
+            .rename_table(RenameTableRequest {
+                namespace: namespace.to_string(),
+                table: table.to_string(),
+                new_name: new_name.to_string(),
+            })
+            .await?;
+
+        Ok(response.into_inner().id)
+    }
+
+    /// Rename the column `column` of `table` in `namespace` to `new_name`,
+    /// returning the ID of the renamed column.
+    pub async fn rename_column(
+        &mut self,
+        namespace: &str,
+        table: &str,
+        column: &str,
+        new_name: &str,
+    ) -> Result<i64, Error> {
+        let response = self
+            .inner
+            .rename_column(RenameColumnRequest {
+                namespace: namespace.to_string(),
+                table: table.to_string(),
+                column: column.to_string(),
+                new_name: new_name.to_string(),
+            })
+            .await?;
+
+        Ok(response.into_inner().id)
+    }
 }
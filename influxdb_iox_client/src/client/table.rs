@@ -0,0 +1,81 @@
+use client_util::connection::GrpcConnection;
+
+use self::generated_types::{table_service_client::TableServiceClient, *};
+use crate::connection::Connection;
+use crate::error::Error;
+use ::generated_types::google::OptionalField;
+
+/// Re-export generated_types
+pub mod generated_types {
+    pub use generated_types::influxdata::iox::partition_template::v1::{
+        template_part, PartitionTemplate, TemplatePart,
+    };
+    pub use generated_types::influxdata::iox::table::v1::*;
+}
+
+/// A basic client for working with Tables.
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: TableServiceClient<GrpcConnection>,
+}
+
+impl Client {
+    /// Creates a new client with the provided connection
+    pub fn new(connection: Connection) -> Self {
+        Self {
+            inner: TableServiceClient::new(connection.into_grpc_connection()),
+        }
+    }
+
+    /// Create a table in `namespace`
+    ///
+    /// `partition_template` is the template used to partition the data of
+    /// the table. `None` uses the template of the namespace.
+    pub async fn create_table(
+        &mut self,
+        namespace: &str,
+        table: &str,
+        partition_template: Option<PartitionTemplate>,
+    ) -> Result<Table, Error> {
+        let response = self
+            .inner
+            .create_table(CreateTableRequest {
+                namespace: namespace.to_string(),
+                name: table.to_string(),
+                partition_template,
+            })
+            .await?;
+
+        Ok(response.into_inner().table.unwrap_field("table")?)
+    }
+
+    /// Soft-delete `table` in `namespace`, along with all of its data
+    pub async fn delete_table(&mut self, namespace: &str, table: &str) -> Result<(), Error> {
+        self.inner
+            .delete_table(DeleteTableRequest {
+                namespace: namespace.to_string(),
+                name: table.to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Soft-delete `column` of `table` in `namespace`
+    pub async fn delete_column(
+        &mut self,
+        namespace: &str,
+        table: &str,
+        column: &str,
+    ) -> Result<(), Error> {
+        self.inner
+            .delete_column(DeleteColumnRequest {
+                namespace: namespace.to_string(),
+                table: table.to_string(),
+                name: column.to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
+}
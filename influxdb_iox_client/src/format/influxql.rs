@@ -60,16 +60,54 @@ impl Options {
 }
 
 /// Write the record batches in a columnar format.
+///
+/// The batches of a query with multiple statements are written in the order of
+/// the statements, and a statement that failed is written as its error message.
 pub fn write_columnar(mut w: impl Write, batches: &[RecordBatch], options: Options) -> Result<()> {
-    let arrow_opts = arrow::util::display::FormatOptions::default().with_display_error(true);
+    let metadata = batches
+        .iter()
+        .map(|b| {
+            let md = b
+                .schema()
+                .metadata()
+                .get(schema::INFLUXQL_METADATA_KEY)
+                .ok_or(Error::MissingMetadata)?
+                .clone();
+            Ok(serde_json::from_str::<InfluxQlMetadata>(&md)?)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // The batches of each statement are contiguous
+    let mut start = 0;
+    while start < batches.len() {
+        let statement_id = metadata[start].statement_id;
+        let end = metadata[start..]
+            .iter()
+            .position(|md| md.statement_id != statement_id)
+            .map_or(batches.len(), |n| start + n);
+
+        match &metadata[start].error {
+            Some(error) => writeln!(w, "ERR: {error}")?,
+            None => write_statement(&mut w, &batches[start..end], &metadata[start], &options)?,
+        }
+
+        start = end;
+    }
 
-    let Some(schema) = batches.first().map(|b|b.schema()) else { return Ok(()) };
-    let md = schema
-        .metadata()
-        .get(schema::INFLUXQL_METADATA_KEY)
-        .ok_or(Error::MissingMetadata)?;
+    Ok(())
+}
 
-    let v: InfluxQlMetadata = serde_json::from_str(md)?;
+/// Write the non-empty record batches of a single statement, described by `v`,
+/// in a columnar format.
+fn write_statement(
+    mut w: impl Write,
+    batches: &[RecordBatch],
+    v: &InfluxQlMetadata,
+    options: &Options,
+) -> Result<()> {
+    let arrow_opts = arrow::util::display::FormatOptions::default().with_display_error(true);
+
+    let schema = batches[0].schema();
 
     let measurement_idx = v.measurement_column_index as usize;
     let (tag_keys, tag_key_indexes): (Vec<_>, Vec<_>) = v
@@ -275,6 +313,7 @@ mod test {
         let rb = batches(InfluxQlMetadata {
             measurement_column_index: 0,
             tag_key_columns: vec![],
+            ..Default::default()
         });
         let mut s = Vec::<u8>::new();
         write_columnar(&mut s, &rb, Options::default()).unwrap();
@@ -305,6 +344,7 @@ mod test {
                 column_index: 2,
                 is_projected: false,
             }],
+            ..Default::default()
         });
         let mut s = Vec::<u8>::new();
         write_columnar(&mut s, &rb, Options::default()).unwrap();
@@ -343,6 +383,7 @@ mod test {
                 column_index: 2,
                 is_projected: true,
             }],
+            ..Default::default()
         });
         let mut s = Vec::<u8>::new();
         write_columnar(&mut s, &rb, Options::default()).unwrap();
@@ -388,6 +429,7 @@ mod test {
                     is_projected: false,
                 },
             ],
+            ..Default::default()
         });
         let mut s = Vec::<u8>::new();
         write_columnar(&mut s, &rb, Options::default()).unwrap();
@@ -424,4 +466,49 @@ mod test {
         +---------------------+------------+------+
         "###);
     }
+
+    #[test]
+    fn test_write_columnar_statements() {
+        let mut rb = batches(InfluxQlMetadata {
+            measurement_column_index: 0,
+            ..Default::default()
+        });
+        let meta = InfluxQlMetadata {
+            statement_id: 1,
+            error: Some("database not found: foo".to_owned()),
+            ..Default::default()
+        };
+        let schema = Arc::new(Schema::new_with_metadata(
+            vec![Field::new("error", DataType::Utf8, false)],
+            HashMap::from([(
+                "iox::influxql::group_key::metadata".to_owned(),
+                serde_json::to_string(&meta).unwrap(),
+            )]),
+        ));
+        rb.push(
+            RecordBatch::try_new(schema, vec![strs(&[Some("database not found: foo")])]).unwrap(),
+        );
+
+        let mut s = Vec::<u8>::new();
+        write_columnar(&mut s, &rb, Options::default()).unwrap();
+        let res = String::from_utf8(s).unwrap();
+        insta::assert_snapshot!(res, @r###"
+        name: cpu
+        +---------------------+------+--------+------------+------+
+        | time                | cpu  | device | usage_idle | free |
+        +---------------------+------+--------+------------+------+
+        | 2006-09-01T03:45:00 | cpu0 |        | 99.1       |      |
+        | 2006-09-01T03:45:10 | cpu0 |        | 99.8       |      |
+        | 2006-09-01T03:45:00 | cpu1 |        | 99.2       |      |
+        +---------------------+------+--------+------------+------+
+        name: disk
+        +---------------------+-----+---------+------------+------+
+        | time                | cpu | device  | usage_idle | free |
+        +---------------------+-----+---------+------------+------+
+        | 2006-09-01T03:45:00 |     | disk1s1 |            | 2133 |
+        | 2006-09-01T03:45:00 |     | disk1s2 |            | 4110 |
+        +---------------------+-----+---------+------------+------+
+        ERR: database not found: foo
+        "###);
+    }
 }
@@ -327,6 +327,25 @@ impl Client {
         Ok(responses)
     }
 
+    /// Make a request to query::read_series_cardinality and do the
+    /// required async dance to flatten the resulting stream to counts
+    pub async fn read_series_cardinality(
+        &mut self,
+        request: ReadSeriesCardinalityRequest,
+    ) -> Result<Vec<i64>, tonic::Status> {
+        let request = request.log_trace("read_series_cardinality request");
+        let responses: Vec<Int64ValuesResponse> = self
+            .inner
+            .read_series_cardinality(request)
+            .await
+            .log_trace("read_series_cardinality response")?
+            .into_inner()
+            .try_collect()
+            .await?;
+
+        Ok(responses.into_iter().flat_map(|r| r.values).collect())
+    }
+
     /// Extract the data frames from the list of ReadResponse
     fn collect_data(responses: Vec<ReadResponse>) -> Vec<read_response::frame::Data> {
         responses
@@ -8,13 +8,16 @@ use async_trait::async_trait;
 use data_types::{NamespaceId, ShardId, TableId};
 use dml::DmlOperation;
 use metric::U64Counter;
-use observability_deps::tracing::warn;
+use predicate::Predicate;
 use trace::span::Span;
 
 use super::{
     partition::resolver::PartitionProvider,
     post_write::PostWriteObserver,
-    table::{name_resolver::TableNameProvider, TableData},
+    table::{
+        column_resolver::ColumnIdProvider, name_resolver::TableNameProvider,
+        tombstone_resolver::TombstoneSequenceProvider, TableData,
+    },
 };
 use crate::{
     arcmap::ArcMap,
@@ -67,6 +70,12 @@ pub(crate) struct NamespaceData<O> {
     /// [`TableName`]: crate::buffer_tree::table::TableName
     tables: ArcMap<TableId, TableData<O>>,
     table_name_resolver: Arc<dyn TableNameProvider>,
+    /// The resolver of the catalog column IDs of buffered writes, shared by
+    /// all [`TableData`].
+    column_id_provider: Arc<dyn ColumnIdProvider>,
+    /// The resolver of the most recent tombstone of a table, shared by all
+    /// [`TableData`].
+    tombstone_sequence_provider: Arc<dyn TombstoneSequenceProvider>,
     /// The count of tables initialised in this Ingester so far, across all
     /// namespaces.
     table_count: U64Counter,
@@ -87,6 +96,8 @@ impl<O> NamespaceData<O> {
         namespace_id: NamespaceId,
         namespace_name: DeferredLoad<NamespaceName>,
         table_name_resolver: Arc<dyn TableNameProvider>,
+        column_id_provider: Arc<dyn ColumnIdProvider>,
+        tombstone_sequence_provider: Arc<dyn TombstoneSequenceProvider>,
         partition_provider: Arc<dyn PartitionProvider>,
         post_write_observer: Arc<O>,
         metrics: &metric::Registry,
@@ -104,6 +115,8 @@ impl<O> NamespaceData<O> {
             namespace_name: Arc::new(namespace_name),
             tables: Default::default(),
             table_name_resolver,
+            column_id_provider,
+            tombstone_sequence_provider,
             table_count,
             partition_provider,
             post_write_observer,
@@ -134,6 +147,25 @@ impl<O> NamespaceData<O> {
     pub(super) fn tables(&self) -> Vec<Arc<TableData<O>>> {
         self.tables.values()
     }
+
+    /// Grab a reference to the table data, or insert a new [`TableData`] for
+    /// it.
+    fn get_or_insert_table(&self, table_id: TableId) -> Arc<TableData<O>> {
+        self.tables.get_or_insert_with(&table_id, || {
+            self.table_count.inc(1);
+            Arc::new(TableData::new(
+                table_id,
+                self.table_name_resolver.for_table(table_id),
+                self.namespace_id,
+                Arc::clone(&self.namespace_name),
+                Arc::clone(&self.partition_provider),
+                Arc::clone(&self.column_id_provider),
+                Arc::clone(&self.tombstone_sequence_provider),
+                Arc::clone(&self.post_write_observer),
+                self.transition_shard_id,
+            ))
+        })
+    }
 }
 
 #[async_trait]
@@ -156,36 +188,18 @@ where
                 let partition_key = write.partition_key().clone();
 
                 for (table_id, b) in write.into_tables() {
-                    // Grab a reference to the table data, or insert a new
-                    // TableData for it.
-                    let table_data = self.tables.get_or_insert_with(&table_id, || {
-                        self.table_count.inc(1);
-                        Arc::new(TableData::new(
-                            table_id,
-                            self.table_name_resolver.for_table(table_id),
-                            self.namespace_id,
-                            Arc::clone(&self.namespace_name),
-                            Arc::clone(&self.partition_provider),
-                            Arc::clone(&self.post_write_observer),
-                            self.transition_shard_id,
-                        ))
-                    });
-
-                    table_data
+                    self.get_or_insert_table(table_id)
                         .buffer_table_write(sequence_number, b, partition_key.clone())
                         .await?;
                 }
             }
             DmlOperation::Delete(delete) => {
-                // Deprecated delete support:
-                // https://github.com/influxdata/influxdb_iox/issues/5825
-                warn!(
-                    namespace_name=%self.namespace_name,
-                    namespace_id=%self.namespace_id,
-                    table_name=?delete.table_name(),
-                    sequence_number=?delete.meta().sequence(),
-                    "discarding unsupported delete op"
-                );
+                // The table is initialised even if it has no buffered data, so
+                // that writes buffered after the delete are known not to be
+                // affected by it.
+                self.get_or_insert_table(delete.table_id())
+                    .apply_delete(delete.predicate(), delete.tombstone_sequence_number())
+                    .await;
             }
         }
 
@@ -206,6 +220,7 @@ where
         table_id: TableId,
         columns: Vec<String>,
         span: Option<Span>,
+        predicate: Option<Predicate>,
     ) -> Result<Self::Response, QueryError> {
         assert_eq!(
             self.namespace_id, namespace_id,
@@ -221,7 +236,7 @@ where
         // a tracing delegate to emit a child span.
         Ok(QueryResponse::new(
             QueryExecTracing::new(inner, "table")
-                .query_exec(namespace_id, table_id, columns, span)
+                .query_exec(namespace_id, table_id, columns, span, predicate)
                 .await?,
         ))
     }
@@ -240,7 +255,11 @@ mod tests {
             namespace::NamespaceData,
             partition::{resolver::mock::MockPartitionProvider, PartitionData, SortKeyState},
             post_write::mock::MockPostWriteObserver,
-            table::{name_resolver::mock::MockTableNameProvider, TableName},
+            table::{
+                column_resolver::mock::MockColumnIdProvider,
+                name_resolver::mock::MockTableNameProvider,
+                tombstone_resolver::mock::MockTombstoneSequenceProvider, TableName,
+            },
         },
         deferred_load::{self, DeferredLoad},
         test_util::make_write_op,
@@ -279,6 +298,8 @@ mod tests {
             NAMESPACE_ID,
             DeferredLoad::new(Duration::from_millis(1), async { NAMESPACE_NAME.into() }),
             Arc::new(MockTableNameProvider::new(TABLE_NAME)),
+            Arc::new(MockColumnIdProvider::default()),
+            Arc::new(MockTombstoneSequenceProvider::default()),
             partition_provider,
             Arc::new(MockPostWriteObserver::default()),
             &metrics,
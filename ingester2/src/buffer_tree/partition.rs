@@ -1,14 +1,18 @@
 //! Partition level data buffer structures.
 
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
 
 use data_types::{
-    sequence_number_set::SequenceNumberSet, NamespaceId, PartitionId, PartitionKey, SequenceNumber,
-    ShardId, TableId,
+    sequence_number_set::SequenceNumberSet, ColumnId, DeletePredicate, NamespaceId, PartitionId,
+    PartitionKey, SequenceNumber, ShardId, TableId,
 };
 use mutable_batch::MutableBatch;
 use observability_deps::tracing::*;
 use schema::sort::SortKey;
+use tokio::time::Instant;
 
 use self::{
     buffer::{traits::Queryable, BufferState, DataBuffer, Persisting},
@@ -93,6 +97,22 @@ pub struct PartitionData {
     /// [`PartitionData`].
     completed_persistence_count: u64,
 
+    /// The time at which the most recent write was buffered in this
+    /// [`PartitionData`], if any.
+    last_write_at: Option<Instant>,
+
+    /// The sequence number of the most recent tombstone received for the
+    /// table before the data in the current [`DataBuffer`] was buffered.
+    ///
+    /// Tombstones with a lower or equal sequence number do not apply to the
+    /// buffered data, and a newer tombstone causes the buffer to be persisted
+    /// so that later writes are not affected by it.
+    max_tombstone_sequence_number: SequenceNumber,
+
+    /// The catalog [`ColumnId`] of each column in the current [`DataBuffer`],
+    /// as resolved when the writes were buffered.
+    column_ids: HashMap<String, ColumnId>,
+
     transition_shard_id: ShardId,
 }
 
@@ -121,6 +141,9 @@ impl PartitionData {
             persisting: VecDeque::with_capacity(1),
             started_persistence_count: BatchIdent::default(),
             completed_persistence_count: 0,
+            last_write_at: None,
+            max_tombstone_sequence_number: SequenceNumber::new(0),
+            column_ids: Default::default(),
             transition_shard_id,
         }
     }
@@ -133,6 +156,7 @@ impl PartitionData {
     ) -> Result<(), mutable_batch::Error> {
         // Buffer the write.
         self.buffer.buffer_write(mb, sequence_number)?;
+        self.last_write_at = Some(Instant::now());
 
         trace!(
             namespace_id = %self.namespace_id,
@@ -146,12 +170,83 @@ impl PartitionData {
         Ok(())
     }
 
+    /// Record the catalog [`ColumnId`] of the named columns of the most
+    /// recently buffered write.
+    ///
+    /// The IDs are handed to the persist job of the buffered data, so that the
+    /// data is persisted to the right columns even if they are renamed or
+    /// deleted in the meantime.
+    pub(crate) fn record_column_ids(&mut self, ids: impl IntoIterator<Item = (String, ColumnId)>) {
+        self.column_ids.extend(ids);
+    }
+
+    /// Set the sequence number of the most recent tombstone received for the
+    /// table, which MUST NOT be newer than any tombstone applied to the data
+    /// currently buffered.
+    pub(crate) fn set_max_tombstone_sequence_number(&mut self, sequence_number: SequenceNumber) {
+        self.max_tombstone_sequence_number = sequence_number;
+    }
+
+    /// Apply the delete `predicate` of the tombstone with the specified
+    /// `tombstone_sequence_number` to the data in this partition.
+    ///
+    /// The rows matching `predicate` are removed from the data returned by
+    /// queries, but not from the data being persisted - instead the buffered
+    /// data is marked as persisting and returned (if any) so it is persisted
+    /// before any later write, into a file the tombstone applies to.
+    pub(crate) fn apply_delete(
+        &mut self,
+        predicate: &DeletePredicate,
+        tombstone_sequence_number: SequenceNumber,
+    ) -> Option<PersistingData> {
+        let data = self.mark_persisting();
+
+        for (batch_ident, fsm) in self.persisting.iter_mut() {
+            let query_data = fsm.get_query_data();
+            if query_data.is_empty() {
+                continue;
+            }
+
+            match QueryAdaptor::new(self.partition_id, query_data).delete(predicate) {
+                Ok(v) => {
+                    fsm.set_query_data(v.map(|v| v.record_batches().to_vec()).unwrap_or_default())
+                }
+                Err(error) => warn!(
+                    %error,
+                    partition_id = %self.partition_id,
+                    %batch_ident,
+                    "cannot apply delete to persisting data"
+                ),
+            }
+        }
+
+        self.max_tombstone_sequence_number = tombstone_sequence_number;
+
+        debug!(
+            namespace_id = %self.namespace_id,
+            table_id = %self.table_id,
+            table_name = %self.table_name,
+            partition_id = %self.partition_id,
+            partition_key = %self.partition_key,
+            tombstone_sequence_number = tombstone_sequence_number.get(),
+            "applied delete"
+        );
+
+        data
+    }
+
     /// Return an estimated cost of persisting the data buffered in this
     /// [`PartitionData`].
     pub(crate) fn persist_cost_estimate(&self) -> usize {
         self.buffer.persist_cost_estimate()
     }
 
+    /// Return the time at which the most recent write was buffered in this
+    /// [`PartitionData`], or [`None`] if no write has ever been buffered.
+    pub(crate) fn last_write_at(&self) -> Option<Instant> {
+        self.last_write_at
+    }
+
     /// Return all data for this partition, ordered by the calls to
     /// [`PartitionData::buffer_write()`].
     pub(crate) fn get_query_data(&mut self) -> Option<QueryAdaptor> {
@@ -216,6 +311,8 @@ impl PartitionData {
         // mark_persisted() call.
         let batch_ident = self.started_persistence_count.next();
 
+        let column_ids = std::mem::take(&mut self.column_ids);
+
         debug!(
             namespace_id = %self.namespace_id,
             table_id = %self.table_id,
@@ -230,6 +327,8 @@ impl PartitionData {
         let data = PersistingData::new(
             QueryAdaptor::new(self.partition_id, fsm.get_query_data()),
             batch_ident,
+            self.max_tombstone_sequence_number,
+            column_ids,
         );
 
         // Push the new buffer to the back of the persisting queue, so that
@@ -347,7 +446,7 @@ mod tests {
     use arrow_util::assert_batches_eq;
     use assert_matches::assert_matches;
     use backoff::BackoffConfig;
-    use data_types::ShardIndex;
+    use data_types::{DeleteExpr, Scalar, ShardIndex, TimestampRange};
     use datafusion::{
         physical_expr::PhysicalSortExpr,
         physical_plan::{expressions::col, memory::MemoryExec, ExecutionPlan},
@@ -976,6 +1075,101 @@ mod tests {
         );
     }
 
+    // Apply a delete to a partition with buffered and persisting data,
+    // ensuring the deleted rows are no longer queryable and the buffered data
+    // is persisted separately from later writes.
+    #[tokio::test]
+    async fn test_apply_delete() {
+        let mut p = PartitionData::new(
+            PARTITION_ID,
+            PARTITION_KEY.clone(),
+            NamespaceId::new(3),
+            Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                NAMESPACE_NAME.clone()
+            })),
+            TableId::new(4),
+            Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                TABLE_NAME.clone()
+            })),
+            SortKeyState::Provided(None),
+            TRANSITION_SHARD_ID,
+        );
+
+        let mb = lp_to_mutable_batch(r#"bananas,city=London people=2 10"#).1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+        let persisting = p.mark_persisting().expect("must contain existing data");
+
+        let mb = lp_to_mutable_batch(r#"bananas,city=Madrid people=4 20"#).1;
+        p.buffer_write(mb, SequenceNumber::new(2))
+            .expect("write should succeed");
+        let mb = lp_to_mutable_batch(r#"bananas,city=London people=6 30"#).1;
+        p.buffer_write(mb, SequenceNumber::new(3))
+            .expect("write should succeed");
+
+        // Delete all the London rows.
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(0, 100),
+            exprs: vec![DeleteExpr::new(
+                "city".to_string(),
+                data_types::Op::Eq,
+                Scalar::String("London".to_string()),
+            )],
+        };
+        let deleted = p
+            .apply_delete(&predicate, SequenceNumber::new(5))
+            .expect("buffered data should be marked as persisting");
+
+        // The data being persisted is unchanged, and the tombstone applies to
+        // it.
+        assert_eq!(
+            persisting.max_tombstone_sequence_number(),
+            SequenceNumber::new(0)
+        );
+        assert_eq!(
+            deleted.max_tombstone_sequence_number(),
+            SequenceNumber::new(0)
+        );
+        assert_eq!(
+            deleted
+                .record_batches()
+                .iter()
+                .map(|b| b.num_rows())
+                .sum::<usize>(),
+            2
+        );
+
+        // A later write is not affected by the delete.
+        let mb = lp_to_mutable_batch(r#"bananas,city=London people=8 40"#).1;
+        p.buffer_write(mb, SequenceNumber::new(4))
+            .expect("write should succeed");
+
+        let data = p.get_query_data().expect("should contain data");
+        let expected = [
+            "+--------+--------+--------------------------------+",
+            "| city   | people | time                           |",
+            "+--------+--------+--------------------------------+",
+            "| Madrid | 4.0    | 1970-01-01T00:00:00.000000020Z |",
+            "| London | 8.0    | 1970-01-01T00:00:00.000000040Z |",
+            "+--------+--------+--------------------------------+",
+        ];
+        assert_batches_eq!(
+            expected,
+            &*data
+                .record_batches()
+                .iter()
+                .map(Deref::deref)
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+
+        let later = p.mark_persisting().expect("must contain existing data");
+        assert_eq!(
+            later.max_tombstone_sequence_number(),
+            SequenceNumber::new(5)
+        );
+    }
+
     // Ensure an updated sort key is returned.
     #[tokio::test]
     async fn test_update_provided_sort_key() {
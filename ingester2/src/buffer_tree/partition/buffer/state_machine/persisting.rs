@@ -11,9 +11,10 @@ use crate::buffer_tree::partition::buffer::traits::Queryable;
 /// An immutable set of [`RecordBatch`] in the process of being persisted.
 #[derive(Debug)]
 pub(crate) struct Persisting {
-    /// Snapshots generated from previous buffer contents to be persisted.
+    /// Snapshots generated from previous buffer contents to be persisted, as
+    /// visible to queries.
     ///
-    /// INVARIANT: this array is always non-empty.
+    /// This array is empty if all the rows were deleted while persisting.
     snapshots: Vec<Arc<RecordBatch>>,
 }
 
@@ -30,6 +31,14 @@ impl Queryable for Persisting {
 }
 
 impl BufferState<Persisting> {
+    /// Replace the data returned by queries against this buffer with `data`,
+    /// such as after removing deleted rows from it.
+    ///
+    /// The data being persisted is unaffected.
+    pub(crate) fn set_query_data(&mut self, data: Vec<Arc<RecordBatch>>) {
+        self.state.snapshots = data;
+    }
+
     /// Consume `self` and all references to the buffered data, returning the owned
     /// [`SequenceNumberSet`] within it.
     pub(crate) fn into_sequence_number_set(self) -> SequenceNumberSet {
@@ -1,4 +1,6 @@
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display, sync::Arc};
+
+use data_types::{ColumnId, SequenceNumber};
 
 use crate::query_adaptor::QueryAdaptor;
 
@@ -41,17 +43,46 @@ impl Display for BatchIdent {
 pub struct PersistingData {
     data: QueryAdaptor,
     batch_ident: BatchIdent,
+    max_tombstone_sequence_number: SequenceNumber,
+    column_ids: Arc<HashMap<String, ColumnId>>,
 }
 
 impl PersistingData {
-    pub(super) fn new(data: QueryAdaptor, batch_ident: BatchIdent) -> Self {
-        Self { data, batch_ident }
+    pub(super) fn new(
+        data: QueryAdaptor,
+        batch_ident: BatchIdent,
+        max_tombstone_sequence_number: SequenceNumber,
+        column_ids: HashMap<String, ColumnId>,
+    ) -> Self {
+        Self {
+            data,
+            batch_ident,
+            max_tombstone_sequence_number,
+            column_ids: Arc::new(column_ids),
+        }
     }
 
     pub(super) fn batch_ident(&self) -> BatchIdent {
         self.batch_ident
     }
 
+    /// The sequence number of the most recent tombstone received before this
+    /// data was buffered.
+    ///
+    /// Tombstones with a lower or equal sequence number do not apply to this
+    /// data.
+    pub(crate) fn max_tombstone_sequence_number(&self) -> SequenceNumber {
+        self.max_tombstone_sequence_number
+    }
+
+    /// The catalog [`ColumnId`] of the columns in this data, as resolved when
+    /// the data was buffered.
+    ///
+    /// Columns that did not exist in the catalog when buffered are absent.
+    pub(crate) fn column_ids(&self) -> &HashMap<String, ColumnId> {
+        &self.column_ids
+    }
+
     pub(crate) fn query_adaptor(&self) -> QueryAdaptor {
         self.data.clone()
     }
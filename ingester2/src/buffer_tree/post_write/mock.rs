@@ -2,13 +2,21 @@ use std::sync::Arc;
 
 use parking_lot::Mutex;
 
-use crate::buffer_tree::partition::PartitionData;
+use crate::buffer_tree::partition::{persisting::PersistingData, PartitionData};
 
 use super::PostWriteObserver;
 
 #[derive(Debug, Default)]
 pub(crate) struct MockPostWriteObserver {
     saw: Mutex<Vec<Arc<Mutex<PartitionData>>>>,
+    persisted: Mutex<Vec<PersistingData>>,
+}
+
+impl MockPostWriteObserver {
+    /// Return the data handed to [`PostWriteObserver::observe_delete()`].
+    pub(crate) fn persisted(&self) -> Vec<PersistingData> {
+        self.persisted.lock().clone()
+    }
 }
 
 impl PostWriteObserver for MockPostWriteObserver {
@@ -19,4 +27,8 @@ impl PostWriteObserver for MockPostWriteObserver {
     ) {
         self.saw.lock().push(partition);
     }
+
+    fn observe_delete(&self, _partition: Arc<Mutex<PartitionData>>, data: PersistingData) {
+        self.persisted.lock().push(data);
+    }
 }
@@ -2,8 +2,12 @@ use std::{fmt::Debug, sync::Arc};
 
 use parking_lot::{Mutex, MutexGuard};
 
-use crate::buffer_tree::partition::PartitionData;
+use crate::buffer_tree::partition::{persisting::PersistingData, PartitionData};
 
 pub(crate) trait PostWriteObserver: Send + Sync + Debug {
     fn observe(&self, partition: Arc<Mutex<PartitionData>>, guard: MutexGuard<'_, PartitionData>);
+
+    /// Persist the `data` of `partition` that was marked as persisting when a
+    /// delete was applied to it.
+    fn observe_delete(&self, partition: Arc<Mutex<PartitionData>>, data: PersistingData);
 }
@@ -5,16 +5,21 @@ use data_types::{NamespaceId, ShardId, TableId};
 use dml::DmlOperation;
 use metric::U64Counter;
 use parking_lot::Mutex;
+use predicate::Predicate;
 use trace::span::Span;
 
 use super::{
     namespace::{name_resolver::NamespaceNameProvider, NamespaceData},
     partition::{resolver::PartitionProvider, PartitionData},
     post_write::PostWriteObserver,
-    table::name_resolver::TableNameProvider,
+    table::{
+        column_resolver::ColumnIdProvider, name_resolver::TableNameProvider,
+        tombstone_resolver::TombstoneSequenceProvider,
+    },
 };
 use crate::{
     arcmap::ArcMap,
+    column_id_cache::ColumnIdCache,
     dml_sink::DmlSink,
     partition_iter::PartitionIter,
     query::{response::QueryResponse, tracing::QueryExecTracing, QueryError, QueryExec},
@@ -98,6 +103,10 @@ pub(crate) struct BufferTree<O> {
     /// [`TableName`]: crate::buffer_tree::table::TableName
     /// [`TableData`]: crate::buffer_tree::table::TableData
     table_name_resolver: Arc<dyn TableNameProvider>,
+    /// The resolver of the catalog column IDs of buffered writes.
+    column_id_provider: Arc<dyn ColumnIdProvider>,
+    /// The resolver of the most recent tombstone of a table.
+    tombstone_sequence_provider: Arc<dyn TombstoneSequenceProvider>,
 
     metrics: Arc<metric::Registry>,
     namespace_count: U64Counter,
@@ -114,6 +123,8 @@ where
     pub(crate) fn new(
         namespace_name_resolver: Arc<dyn NamespaceNameProvider>,
         table_name_resolver: Arc<dyn TableNameProvider>,
+        column_id_provider: Arc<dyn ColumnIdProvider>,
+        tombstone_sequence_provider: Arc<dyn TombstoneSequenceProvider>,
         partition_provider: Arc<dyn PartitionProvider>,
         post_write_observer: Arc<O>,
         metrics: Arc<metric::Registry>,
@@ -130,6 +141,8 @@ where
             namespaces: Default::default(),
             namespace_name_resolver,
             table_name_resolver,
+            column_id_provider,
+            tombstone_sequence_provider,
             metrics,
             partition_provider,
             post_write_observer,
@@ -182,6 +195,8 @@ where
                 namespace_id,
                 self.namespace_name_resolver.for_namespace(namespace_id),
                 Arc::clone(&self.table_name_resolver),
+                Arc::clone(&self.column_id_provider),
+                Arc::clone(&self.tombstone_sequence_provider),
                 Arc::clone(&self.partition_provider),
                 Arc::clone(&self.post_write_observer),
                 &self.metrics,
@@ -206,6 +221,7 @@ where
         table_id: TableId,
         columns: Vec<String>,
         span: Option<Span>,
+        predicate: Option<Predicate>,
     ) -> Result<Self::Response, QueryError> {
         // Extract the namespace if it exists.
         let inner = self
@@ -215,7 +231,7 @@ where
         // Delegate query execution to the namespace, wrapping the execution in
         // a tracing delegate to emit a child span.
         QueryExecTracing::new(inner, "namespace")
-            .query_exec(namespace_id, table_id, columns, span)
+            .query_exec(namespace_id, table_id, columns, span, predicate)
             .await
     }
 }
@@ -229,13 +245,28 @@ where
     }
 }
 
+impl<O> ColumnIdCache for BufferTree<O>
+where
+    O: Send + Sync + Debug,
+{
+    fn invalidate_column_ids(&self, namespace_id: NamespaceId, table_id: TableId) {
+        // A table that has not been buffered has no cached column IDs.
+        if let Some(table) = self.namespace(namespace_id).and_then(|n| n.table(table_id)) {
+            table.invalidate_column_ids();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{sync::Arc, time::Duration};
 
     use assert_matches::assert_matches;
     use data_types::{PartitionId, PartitionKey};
-    use datafusion::{assert_batches_eq, assert_batches_sorted_eq};
+    use datafusion::{
+        assert_batches_eq, assert_batches_sorted_eq,
+        prelude::{col, lit},
+    };
     use futures::{StreamExt, TryStreamExt};
     use metric::{Attributes, Metric};
 
@@ -247,7 +278,11 @@ mod tests {
             },
             partition::{resolver::mock::MockPartitionProvider, PartitionData, SortKeyState},
             post_write::mock::MockPostWriteObserver,
-            table::{name_resolver::mock::MockTableNameProvider, TableName},
+            table::{
+                column_resolver::mock::MockColumnIdProvider,
+                name_resolver::mock::MockTableNameProvider,
+                tombstone_resolver::mock::MockTombstoneSequenceProvider, TableName,
+            },
         },
         deferred_load::{self, DeferredLoad},
         query::partition_response::PartitionResponse,
@@ -288,6 +323,8 @@ mod tests {
             NAMESPACE_ID,
             DeferredLoad::new(Duration::from_millis(1), async { NAMESPACE_NAME.into() }),
             Arc::new(MockTableNameProvider::new(TABLE_NAME)),
+            Arc::new(MockColumnIdProvider::default()),
+            Arc::new(MockTombstoneSequenceProvider::default()),
             partition_provider,
             Arc::new(MockPostWriteObserver::default()),
             &metrics,
@@ -342,6 +379,21 @@ mod tests {
             partitions = [$($partition:expr), +], // The set of PartitionData for the mock partition provider
             writes = [$($write:expr), *],         // The set of DmlWrite to apply()
             want = $want:expr                     // The expected results of querying NAMESPACE_ID and TABLE_ID
+        ) => {
+            test_write_query!(
+                $name,
+                partitions = [$($partition), +],
+                writes = [$($write), *],
+                predicate = None,
+                want = $want
+            );
+        };
+        (
+            $name:ident,
+            partitions = [$($partition:expr), +], // The set of PartitionData for the mock partition provider
+            writes = [$($write:expr), *],         // The set of DmlWrite to apply()
+            predicate = $predicate:expr,          // An optional predicate to push down into the query
+            want = $want:expr                     // The expected results of querying NAMESPACE_ID and TABLE_ID
         ) => {
             paste::paste! {
                 #[tokio::test]
@@ -358,6 +410,8 @@ mod tests {
                     let buf = BufferTree::new(
                         Arc::new(MockNamespaceNameProvider::default()),
                         Arc::new(MockTableNameProvider::new(TABLE_NAME)),
+                        Arc::new(MockColumnIdProvider::default()),
+                        Arc::new(MockTombstoneSequenceProvider::default()),
                         partition_provider,
                         Arc::new(MockPostWriteObserver::default()),
                         Arc::new(metric::Registry::default()),
@@ -373,7 +427,7 @@ mod tests {
 
                     // Execute the query against NAMESPACE_ID and TABLE_ID
                     let batches = buf
-                        .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None)
+                        .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None, $predicate)
                         .await
                         .expect("query should succeed")
                         .into_record_batches()
@@ -487,6 +541,116 @@ mod tests {
         ]
     );
 
+    // A query with a tag predicate that prunes an entire partition using its
+    // statistics.
+    test_write_query!(
+        predicate_prunes_partitions,
+        partitions = [
+            PartitionData::new(
+                PartitionId::new(0),
+                PartitionKey::from("p1"),
+                NAMESPACE_ID,
+                Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                    NamespaceName::from(NAMESPACE_NAME)
+                })),
+                TABLE_ID,
+                Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                    TableName::from(TABLE_NAME)
+                })),
+                SortKeyState::Provided(None),
+                TRANSITION_SHARD_ID,
+            ),
+            PartitionData::new(
+                PartitionId::new(1),
+                PartitionKey::from("p2"),
+                NAMESPACE_ID,
+                Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                    NamespaceName::from(NAMESPACE_NAME)
+                })),
+                TABLE_ID,
+                Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                    TableName::from(TABLE_NAME)
+                })),
+                SortKeyState::Provided(None),
+                TRANSITION_SHARD_ID,
+            )
+        ],
+        writes = [
+            make_write_op(
+                &PartitionKey::from("p1"),
+                NAMESPACE_ID,
+                TABLE_NAME,
+                TABLE_ID,
+                0,
+                r#"bananas,region=Madrid temp=35 4242424242"#,
+            ),
+            make_write_op(
+                &PartitionKey::from("p2"),
+                NAMESPACE_ID,
+                TABLE_NAME,
+                TABLE_ID,
+                0,
+                r#"bananas,region=Asturias temp=25 4242424242"#,
+            )
+        ],
+        predicate = Some(Predicate::new().with_expr(col("region").eq(lit("Madrid")))),
+        want = [
+            "+--------+------+-------------------------------+",
+            "| region | temp | time                          |",
+            "+--------+------+-------------------------------+",
+            "| Madrid | 35.0 | 1970-01-01T00:00:04.242424242 |",
+            "+--------+------+-------------------------------+",
+        ]
+    );
+
+    // A query with a predicate that filters the rows within a partition.
+    //
+    // Only the time range and the tag expression are applied by the buffer -
+    // the field expression cannot be evaluated before deduplication and is left
+    // for the querier to apply.
+    test_write_query!(
+        predicate_filters_rows,
+        partitions = [PartitionData::new(
+            PartitionId::new(0),
+            PartitionKey::from("p1"),
+            NAMESPACE_ID,
+            Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                NamespaceName::from(NAMESPACE_NAME)
+            })),
+            TABLE_ID,
+            Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                TableName::from(TABLE_NAME)
+            })),
+            SortKeyState::Provided(None),
+            TRANSITION_SHARD_ID,
+        )],
+        writes = [make_write_op(
+            &PartitionKey::from("p1"),
+            NAMESPACE_ID,
+            TABLE_NAME,
+            TABLE_ID,
+            0,
+            "bananas,region=Madrid temp=35 10\n\
+            bananas,region=Asturias temp=25 20\n\
+            bananas,region=Madrid temp=20 30\n\
+            bananas,region=Madrid temp=15 200",
+        )],
+        predicate = Some(
+            Predicate::new()
+                .with_range(0, 100)
+                .with_expr(col("region").eq(lit("Madrid")))
+                .with_expr(col("temp").gt(lit(30.0)))
+        ),
+        want = [
+            "+--------+------+-------------------------------+",
+            "| region | temp | time                          |",
+            "+--------+------+-------------------------------+",
+            "| Madrid | 35.0 | 1970-01-01T00:00:00.000000010 |",
+            "| Madrid | 20.0 | 1970-01-01T00:00:00.000000030 |",
+            "+--------+------+-------------------------------+",
+        ]
+    );
+
     // A query that ensures the data across multiple namespaces is correctly
     // filtered to return only the queried table.
     test_write_query!(
@@ -701,6 +865,8 @@ mod tests {
         let buf = BufferTree::new(
             Arc::new(MockNamespaceNameProvider::default()),
             Arc::new(MockTableNameProvider::new(TABLE_NAME)),
+            Arc::new(MockColumnIdProvider::default()),
+            Arc::new(MockTombstoneSequenceProvider::default()),
             partition_provider,
             Arc::new(MockPostWriteObserver::default()),
             Arc::clone(&metrics),
@@ -810,6 +976,8 @@ mod tests {
         let buf = BufferTree::new(
             Arc::new(MockNamespaceNameProvider::default()),
             Arc::new(MockTableNameProvider::new(TABLE_NAME)),
+            Arc::new(MockColumnIdProvider::default()),
+            Arc::new(MockTombstoneSequenceProvider::default()),
             partition_provider,
             Arc::new(MockPostWriteObserver::default()),
             Arc::clone(&Arc::new(metric::Registry::default())),
@@ -895,6 +1063,8 @@ mod tests {
         let buf = BufferTree::new(
             Arc::new(MockNamespaceNameProvider::default()),
             Arc::new(MockTableNameProvider::new(TABLE_NAME)),
+            Arc::new(MockColumnIdProvider::default()),
+            Arc::new(MockTombstoneSequenceProvider::default()),
             partition_provider,
             Arc::new(MockPostWriteObserver::default()),
             Arc::new(metric::Registry::default()),
@@ -903,7 +1073,7 @@ mod tests {
 
         // Query the empty tree
         let err = buf
-            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None)
+            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None, None)
             .await
             .expect_err("query should fail");
         assert_matches!(err, QueryError::NamespaceNotFound(ns) => {
@@ -924,7 +1094,7 @@ mod tests {
 
         // Ensure an unknown table errors
         let err = buf
-            .query_exec(NAMESPACE_ID, TableId::new(1234), vec![], None)
+            .query_exec(NAMESPACE_ID, TableId::new(1234), vec![], None, None)
             .await
             .expect_err("query should fail");
         assert_matches!(err, QueryError::TableNotFound(ns, t) => {
@@ -933,7 +1103,7 @@ mod tests {
         });
 
         // Ensure a valid namespace / table does not error
-        buf.query_exec(NAMESPACE_ID, TABLE_ID, vec![], None)
+        buf.query_exec(NAMESPACE_ID, TABLE_ID, vec![], None, None)
             .await
             .expect("namespace / table should exist");
     }
@@ -994,6 +1164,8 @@ mod tests {
         let buf = BufferTree::new(
             Arc::new(MockNamespaceNameProvider::default()),
             Arc::new(MockTableNameProvider::new(TABLE_NAME)),
+            Arc::new(MockColumnIdProvider::default()),
+            Arc::new(MockTombstoneSequenceProvider::default()),
             partition_provider,
             Arc::new(MockPostWriteObserver::default()),
             Arc::new(metric::Registry::default()),
@@ -1015,7 +1187,7 @@ mod tests {
         // Execute a query of the buffer tree, generating the result stream, but
         // DO NOT consume it.
         let stream = buf
-            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None)
+            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None, None)
             .await
             .expect("query should succeed")
             .into_partition_stream();
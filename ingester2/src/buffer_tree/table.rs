@@ -1,17 +1,25 @@
 //! Table level data buffer structures.
 
+pub(crate) mod column_resolver;
 pub(crate) mod name_resolver;
+pub(crate) mod tombstone_resolver;
 
-use std::{fmt::Debug, sync::Arc};
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
-use data_types::{NamespaceId, PartitionKey, SequenceNumber, ShardId, TableId};
+use data_types::{
+    ColumnId, DeletePredicate, NamespaceId, PartitionKey, SequenceNumber, ShardId, TableId,
+};
 use datafusion_util::MemoryStream;
 use mutable_batch::MutableBatch;
+use observability_deps::tracing::debug;
 use parking_lot::Mutex;
+use predicate::Predicate;
 use schema::Projection;
+use tokio::sync::{OnceCell, RwLock};
 use trace::span::{Span, SpanRecorder};
 
+use self::{column_resolver::ColumnIdProvider, tombstone_resolver::TombstoneSequenceProvider};
 use super::{
     namespace::NamespaceName,
     partition::{resolver::PartitionProvider, PartitionData},
@@ -80,6 +88,23 @@ pub(crate) struct TableData<O> {
     /// `(key, table)` tuple.
     partition_provider: Arc<dyn PartitionProvider>,
 
+    /// The catalog [`ColumnId`] of each column name of the table, as of the
+    /// most recent fetch from the [`ColumnIdProvider`].
+    ///
+    /// Cleared when a column of the table is renamed, as the previous name may
+    /// then refer to a new column.
+    column_ids: Mutex<HashMap<String, ColumnId>>,
+    column_id_provider: Arc<dyn ColumnIdProvider>,
+
+    /// The sequence number of the most recent tombstone received for this
+    /// table, lazily initialised from the [`TombstoneSequenceProvider`].
+    ///
+    /// Writes hold the read lock while they are buffered, and deletes hold
+    /// the write lock while they are applied, so that every write is either
+    /// affected by a delete, or buffered after it.
+    max_tombstone_sequence_number: OnceCell<RwLock<SequenceNumber>>,
+    tombstone_sequence_provider: Arc<dyn TombstoneSequenceProvider>,
+
     // Map of partition key to its data
     partition_data: ArcMap<PartitionKey, Mutex<PartitionData>>,
 
@@ -99,6 +124,8 @@ impl<O> TableData<O> {
         namespace_id: NamespaceId,
         namespace_name: Arc<DeferredLoad<NamespaceName>>,
         partition_provider: Arc<dyn PartitionProvider>,
+        column_id_provider: Arc<dyn ColumnIdProvider>,
+        tombstone_sequence_provider: Arc<dyn TombstoneSequenceProvider>,
         post_write_observer: Arc<O>,
         transition_shard_id: ShardId,
     ) -> Self {
@@ -109,6 +136,10 @@ impl<O> TableData<O> {
             namespace_name,
             partition_data: Default::default(),
             partition_provider,
+            column_ids: Default::default(),
+            column_id_provider,
+            max_tombstone_sequence_number: Default::default(),
+            tombstone_sequence_provider,
             post_write_observer,
             transition_shard_id,
         }
@@ -145,6 +176,26 @@ impl<O> TableData<O> {
     pub(crate) fn namespace_id(&self) -> NamespaceId {
         self.namespace_id
     }
+
+    /// Discard the cached column IDs, causing them to be fetched from the
+    /// catalog for the next write.
+    pub(crate) fn invalidate_column_ids(&self) {
+        self.column_ids.lock().clear();
+    }
+
+    /// Return the lock over the sequence number of the most recent tombstone
+    /// received for this table, fetching it on first use.
+    async fn max_tombstone_sequence_number(&self) -> &RwLock<SequenceNumber> {
+        self.max_tombstone_sequence_number
+            .get_or_init(|| async {
+                RwLock::new(
+                    self.tombstone_sequence_provider
+                        .max_tombstone_sequence_number(self.table_id)
+                        .await,
+                )
+            })
+            .await
+    }
 }
 
 impl<O> TableData<O>
@@ -182,11 +233,21 @@ where
             }
         };
 
+        // Resolve the column IDs of the write while the column names are
+        // current, so the data is persisted to the right columns should they
+        // be renamed or deleted before the data is persisted.
+        let column_ids = self.column_ids(&batch).await;
+
+        // Prevent a delete from being applied concurrently with this write.
+        let max_tombstone_sequence_number = self.max_tombstone_sequence_number().await.read().await;
+
         // Obtain the partition lock.
         let mut p = partition_data.lock();
 
         // Enqueue the write, returning any error.
+        p.set_max_tombstone_sequence_number(*max_tombstone_sequence_number);
         p.buffer_write(batch, sequence_number)?;
+        p.record_column_ids(column_ids);
 
         // If successful, allow the observer to inspect the partition.
         self.post_write_observer
@@ -194,6 +255,63 @@ where
 
         Ok(())
     }
+
+    /// Apply the delete `predicate` of the tombstone with the specified
+    /// `tombstone_sequence_number` to the data buffered for this table.
+    ///
+    /// Deletes for tombstones that were already received are ignored.
+    pub(super) async fn apply_delete(
+        &self,
+        predicate: &DeletePredicate,
+        tombstone_sequence_number: SequenceNumber,
+    ) {
+        // Block writes to this table until the delete is applied.
+        let mut max_tombstone_sequence_number =
+            self.max_tombstone_sequence_number().await.write().await;
+
+        if tombstone_sequence_number <= *max_tombstone_sequence_number {
+            debug!(
+                table_id = %self.table_id,
+                tombstone_sequence_number = tombstone_sequence_number.get(),
+                "ignoring delete of already received tombstone"
+            );
+            return;
+        }
+
+        for partition in self.partitions() {
+            let data = partition
+                .lock()
+                .apply_delete(predicate, tombstone_sequence_number);
+
+            if let Some(data) = data {
+                self.post_write_observer.observe_delete(partition, data);
+            }
+        }
+
+        *max_tombstone_sequence_number = tombstone_sequence_number;
+    }
+
+    /// Return the catalog [`ColumnId`] of each column in `batch`, refreshing
+    /// the cached IDs if any column is unknown.
+    ///
+    /// Columns that do not exist in the catalog (because they were deleted
+    /// after the write was validated) are omitted.
+    async fn column_ids(&self, batch: &MutableBatch) -> Vec<(String, ColumnId)> {
+        let missing = {
+            let ids = self.column_ids.lock();
+            batch.columns().any(|(name, _)| !ids.contains_key(name))
+        };
+        if missing {
+            let fetched = self.column_id_provider.column_ids(self.table_id).await;
+            *self.column_ids.lock() = fetched;
+        }
+
+        let ids = self.column_ids.lock();
+        batch
+            .columns()
+            .filter_map(|(name, _)| ids.get(name).map(|id| (name.clone(), *id)))
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -209,6 +327,7 @@ where
         table_id: TableId,
         columns: Vec<String>,
         span: Option<Span>,
+        predicate: Option<Predicate>,
     ) -> Result<Self::Response, QueryError> {
         assert_eq!(self.table_id, table_id, "buffer tree index inconsistency");
         assert_eq!(
@@ -230,6 +349,17 @@ where
                 )
             };
 
+            // Prune the partition and filter the buffered rows using the
+            // (optional) query predicate.
+            //
+            // The partition is still included in the response when all of its
+            // data is filtered out, so the querier observes its persistence
+            // count.
+            let data = match &predicate {
+                Some(predicate) => data.and_then(|data| data.filter(predicate)),
+                None => data,
+            };
+
             let ret = match data {
                 Some(data) => {
                     assert_eq!(id, data.partition_id());
@@ -262,13 +392,18 @@ where
 mod tests {
     use std::{sync::Arc, time::Duration};
 
-    use data_types::PartitionId;
+    use assert_matches::assert_matches;
+    use data_types::{PartitionId, TimestampRange};
     use mutable_batch_lp::lines_to_batches;
 
     use super::*;
     use crate::buffer_tree::{
         partition::{resolver::mock::MockPartitionProvider, PartitionData, SortKeyState},
         post_write::mock::MockPostWriteObserver,
+        table::{
+            column_resolver::mock::MockColumnIdProvider,
+            tombstone_resolver::mock::MockTombstoneSequenceProvider,
+        },
     };
 
     const TABLE_NAME: &str = "bananas";
@@ -309,6 +444,8 @@ mod tests {
                 NamespaceName::from("platanos")
             })),
             partition_provider,
+            Arc::new(MockColumnIdProvider::default()),
+            Arc::new(MockTombstoneSequenceProvider::default()),
             Arc::new(MockPostWriteObserver::default()),
             TRANSITION_SHARD_ID,
         );
@@ -330,4 +467,215 @@ mod tests {
         // Referencing the partition should succeed
         assert!(table.partition_data.get(&PARTITION_KEY.into()).is_some());
     }
+
+    #[tokio::test]
+    async fn test_column_ids() {
+        let partition_provider = Arc::new(MockPartitionProvider::default().with_partition(
+            PartitionData::new(
+                PARTITION_ID,
+                PARTITION_KEY.into(),
+                NAMESPACE_ID,
+                Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                    NamespaceName::from("platanos")
+                })),
+                TABLE_ID,
+                Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                    TableName::from(TABLE_NAME)
+                })),
+                SortKeyState::Provided(None),
+                TRANSITION_SHARD_ID,
+            ),
+        ));
+
+        // The "value" column does not exist in the catalog.
+        let table = TableData::new(
+            TABLE_ID,
+            DeferredLoad::new(Duration::from_secs(1), async {
+                TableName::from(TABLE_NAME)
+            }),
+            NAMESPACE_ID,
+            Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                NamespaceName::from("platanos")
+            })),
+            partition_provider,
+            Arc::new(MockColumnIdProvider::new([
+                ("bat", ColumnId::new(1)),
+                ("time", ColumnId::new(2)),
+            ])),
+            Arc::new(MockTombstoneSequenceProvider::default()),
+            Arc::new(MockPostWriteObserver::default()),
+            TRANSITION_SHARD_ID,
+        );
+
+        let batch = lines_to_batches(r#"bananas,bat=man value=24 42"#, 0)
+            .unwrap()
+            .remove(TABLE_NAME)
+            .unwrap();
+        table
+            .buffer_table_write(SequenceNumber::new(42), batch, PARTITION_KEY.into())
+            .await
+            .expect("buffer op should succeed");
+
+        // The IDs of the known columns are recorded with the buffered data.
+        let data = table
+            .partition_data
+            .get(&PARTITION_KEY.into())
+            .unwrap()
+            .lock()
+            .mark_persisting()
+            .unwrap();
+        assert_eq!(
+            *data.column_ids(),
+            HashMap::from([
+                ("bat".to_string(), ColumnId::new(1)),
+                ("time".to_string(), ColumnId::new(2)),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_column_ids() {
+        let partition_provider = Arc::new(MockPartitionProvider::default());
+        let column_id_provider = Arc::new(MockColumnIdProvider::new([
+            ("bat", ColumnId::new(1)),
+            ("value", ColumnId::new(2)),
+            ("time", ColumnId::new(3)),
+        ]));
+
+        let table = TableData::new(
+            TABLE_ID,
+            DeferredLoad::new(Duration::from_secs(1), async {
+                TableName::from(TABLE_NAME)
+            }),
+            NAMESPACE_ID,
+            Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                NamespaceName::from("platanos")
+            })),
+            partition_provider,
+            Arc::clone(&column_id_provider),
+            Arc::new(MockTombstoneSequenceProvider::default()),
+            Arc::new(MockPostWriteObserver::default()),
+            TRANSITION_SHARD_ID,
+        );
+
+        let batch = lines_to_batches(r#"bananas,bat=man value=24 42"#, 0)
+            .unwrap()
+            .remove(TABLE_NAME)
+            .unwrap();
+        let bat_id = |ids: Vec<(String, ColumnId)>| {
+            ids.into_iter()
+                .find(|(name, _)| name == "bat")
+                .map(|(_, id)| id)
+        };
+
+        assert_eq!(
+            bat_id(table.column_ids(&batch).await),
+            Some(ColumnId::new(1))
+        );
+
+        // "bat" is renamed, and a new "bat" column is created.
+        column_id_provider.set_ids([
+            ("bat", ColumnId::new(4)),
+            ("value", ColumnId::new(2)),
+            ("time", ColumnId::new(3)),
+            ("cricket_bat", ColumnId::new(1)),
+        ]);
+
+        // The cached IDs are used until invalidated.
+        assert_eq!(
+            bat_id(table.column_ids(&batch).await),
+            Some(ColumnId::new(1))
+        );
+        table.invalidate_column_ids();
+        assert_eq!(
+            bat_id(table.column_ids(&batch).await),
+            Some(ColumnId::new(4))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_delete() {
+        let partition_provider = Arc::new(MockPartitionProvider::default().with_partition(
+            PartitionData::new(
+                PARTITION_ID,
+                PARTITION_KEY.into(),
+                NAMESPACE_ID,
+                Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                    NamespaceName::from("platanos")
+                })),
+                TABLE_ID,
+                Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                    TableName::from(TABLE_NAME)
+                })),
+                SortKeyState::Provided(None),
+                TRANSITION_SHARD_ID,
+            ),
+        ));
+        let observer = Arc::new(MockPostWriteObserver::default());
+
+        // The most recent tombstone of the table in the catalog.
+        let table = TableData::new(
+            TABLE_ID,
+            DeferredLoad::new(Duration::from_secs(1), async {
+                TableName::from(TABLE_NAME)
+            }),
+            NAMESPACE_ID,
+            Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                NamespaceName::from("platanos")
+            })),
+            partition_provider,
+            Arc::new(MockColumnIdProvider::default()),
+            Arc::new(MockTombstoneSequenceProvider::new(3)),
+            Arc::clone(&observer),
+            TRANSITION_SHARD_ID,
+        );
+
+        let batch = lines_to_batches(r#"bananas,bat=man value=24 42"#, 0)
+            .unwrap()
+            .remove(TABLE_NAME)
+            .unwrap();
+        table
+            .buffer_table_write(SequenceNumber::new(42), batch, PARTITION_KEY.into())
+            .await
+            .expect("buffer op should succeed");
+
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(0, 100),
+            exprs: vec![],
+        };
+
+        // A delete of a tombstone that was already received is ignored.
+        table.apply_delete(&predicate, SequenceNumber::new(3)).await;
+        assert!(observer.persisted().is_empty());
+
+        // A newer delete causes the buffered data to be persisted, to a file
+        // the tombstone applies to.
+        table.apply_delete(&predicate, SequenceNumber::new(4)).await;
+        assert_matches!(&*observer.persisted(), [data] => {
+            assert_eq!(data.max_tombstone_sequence_number(), SequenceNumber::new(3));
+        });
+
+        // And later writes are not affected by it.
+        let batch = lines_to_batches(r#"bananas,bat=man value=42 43"#, 0)
+            .unwrap()
+            .remove(TABLE_NAME)
+            .unwrap();
+        table
+            .buffer_table_write(SequenceNumber::new(43), batch, PARTITION_KEY.into())
+            .await
+            .expect("buffer op should succeed");
+
+        let partition = table.partition_data.get(&PARTITION_KEY.into()).unwrap();
+        let mut partition = partition.lock();
+        let data = partition.get_query_data().expect("should contain data");
+        assert_eq!(
+            data.record_batches()
+                .iter()
+                .map(|b| b.num_rows())
+                .sum::<usize>(),
+            1
+        );
+        let data = partition.mark_persisting().unwrap();
+        assert_eq!(data.max_tombstone_sequence_number(), SequenceNumber::new(4));
+    }
 }
@@ -0,0 +1,133 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig};
+use data_types::{ColumnId, TableId};
+use iox_catalog::interface::Catalog;
+
+/// An abstract provider of the catalog [`ColumnId`] of each column of a table,
+/// keyed by column name.
+#[async_trait]
+pub(crate) trait ColumnIdProvider: Send + Sync + std::fmt::Debug {
+    /// Return the name -> ID mapping of the columns of `table_id` that are not
+    /// soft-deleted.
+    async fn column_ids(&self, table_id: TableId) -> HashMap<String, ColumnId>;
+}
+
+#[derive(Debug)]
+pub(crate) struct ColumnIdResolver {
+    catalog: Arc<dyn Catalog>,
+    backoff_config: BackoffConfig,
+}
+
+impl ColumnIdResolver {
+    pub(crate) fn new(catalog: Arc<dyn Catalog>, backoff_config: BackoffConfig) -> Self {
+        Self {
+            catalog,
+            backoff_config,
+        }
+    }
+}
+
+#[async_trait]
+impl ColumnIdProvider for ColumnIdResolver {
+    /// Fetch the columns of `table_id` from the [`Catalog`], retrying endlessly
+    /// when errors occur.
+    async fn column_ids(&self, table_id: TableId) -> HashMap<String, ColumnId> {
+        Backoff::new(&self.backoff_config)
+            .retry_all_errors("fetch table column ids", || async {
+                let columns = self
+                    .catalog
+                    .repositories()
+                    .await
+                    .columns()
+                    .list_by_table_id(table_id)
+                    .await?;
+
+                Result::<_, iox_catalog::interface::Error>::Ok(
+                    columns.into_iter().map(|c| (c.name, c.id)).collect(),
+                )
+            })
+            .await
+            .expect("retry forever")
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    /// A [`ColumnIdProvider`] returning the same mapping for all tables.
+    #[derive(Debug, Default)]
+    pub(crate) struct MockColumnIdProvider {
+        ids: Mutex<HashMap<String, ColumnId>>,
+    }
+
+    impl MockColumnIdProvider {
+        pub(crate) fn new(ids: impl IntoIterator<Item = (&'static str, ColumnId)>) -> Self {
+            let provider = Self::default();
+            provider.set_ids(ids);
+            provider
+        }
+
+        /// Replace the mapping returned by this provider.
+        pub(crate) fn set_ids(&self, ids: impl IntoIterator<Item = (&'static str, ColumnId)>) {
+            *self.ids.lock() = ids
+                .into_iter()
+                .map(|(name, id)| (name.to_string(), id))
+                .collect();
+        }
+    }
+
+    #[async_trait]
+    impl ColumnIdProvider for MockColumnIdProvider {
+        async fn column_ids(&self, _table_id: TableId) -> HashMap<String, ColumnId> {
+            self.ids.lock().clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{ColumnType, ShardIndex};
+    use iox_catalog::mem::MemCatalog;
+
+    use super::*;
+    use crate::test_util::populate_catalog;
+
+    const SHARD_INDEX: ShardIndex = ShardIndex::new(24);
+    const TABLE_NAME: &str = "bananas";
+    const NAMESPACE_NAME: &str = "platanos";
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let metrics = Arc::new(metric::Registry::default());
+        let backoff_config = BackoffConfig::default();
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        // Populate the catalog with the shard / namespace / table
+        let (_shard_id, _ns_id, table_id) =
+            populate_catalog(&*catalog, SHARD_INDEX, NAMESPACE_NAME, TABLE_NAME).await;
+
+        let mut repos = catalog.repositories().await;
+        let kept = repos
+            .columns()
+            .create_or_get("kept", table_id, ColumnType::Tag)
+            .await
+            .unwrap();
+        let deleted = repos
+            .columns()
+            .create_or_get("deleted", table_id, ColumnType::F64)
+            .await
+            .unwrap();
+        repos.columns().soft_delete(deleted.id).await.unwrap();
+        drop(repos);
+
+        let got = ColumnIdResolver::new(catalog, backoff_config)
+            .column_ids(table_id)
+            .await;
+        assert_eq!(got, HashMap::from([("kept".to_string(), kept.id)]));
+    }
+}
@@ -0,0 +1,197 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig};
+use data_types::{SequenceNumber, TableId};
+use iox_catalog::interface::Catalog;
+
+/// An abstract provider of the sequence number of the most recent tombstone
+/// recorded in the catalog for a table.
+#[async_trait]
+pub(crate) trait TombstoneSequenceProvider: Send + Sync + std::fmt::Debug {
+    /// Return the sequence number of the most recent tombstone of `table_id`,
+    /// or 0 if the table has no tombstones.
+    async fn max_tombstone_sequence_number(&self, table_id: TableId) -> SequenceNumber;
+}
+
+#[derive(Debug)]
+pub(crate) struct TombstoneSequenceResolver {
+    catalog: Arc<dyn Catalog>,
+    backoff_config: BackoffConfig,
+}
+
+impl TombstoneSequenceResolver {
+    pub(crate) fn new(catalog: Arc<dyn Catalog>, backoff_config: BackoffConfig) -> Self {
+        Self {
+            catalog,
+            backoff_config,
+        }
+    }
+}
+
+#[async_trait]
+impl TombstoneSequenceProvider for TombstoneSequenceResolver {
+    /// Fetch the tombstones of `table_id` from the [`Catalog`], retrying
+    /// endlessly when errors occur.
+    async fn max_tombstone_sequence_number(&self, table_id: TableId) -> SequenceNumber {
+        Backoff::new(&self.backoff_config)
+            .retry_all_errors("fetch table tombstones", || async {
+                let tombstones = self
+                    .catalog
+                    .repositories()
+                    .await
+                    .tombstones()
+                    .list_by_table(table_id)
+                    .await?;
+
+                Result::<_, iox_catalog::interface::Error>::Ok(
+                    tombstones
+                        .into_iter()
+                        .map(|t| t.sequence_number)
+                        .max()
+                        .unwrap_or_else(|| SequenceNumber::new(0)),
+                )
+            })
+            .await
+            .expect("retry forever")
+    }
+}
+
+/// A [`TombstoneSequenceProvider`] decorator for replaying the write-ahead log.
+///
+/// The catalog reports the most recent tombstone of a table as of now, but a
+/// write replayed from the WAL was buffered before any tombstone that follows
+/// it in the WAL. For each table with a delete in the WAL, this provider
+/// returns the sequence number preceding the oldest such delete so that the
+/// replayed deletes are applied to the replayed writes.
+#[derive(Debug)]
+pub(crate) struct ReplayTombstoneSequenceProvider<T> {
+    inner: T,
+    replayed: HashMap<TableId, SequenceNumber>,
+}
+
+impl<T> ReplayTombstoneSequenceProvider<T> {
+    /// Initialise a provider that returns the sequence number preceding the
+    /// oldest `replayed` tombstone of a table, or the value from `inner` if
+    /// there is none.
+    pub(crate) fn new(inner: T, replayed: HashMap<TableId, SequenceNumber>) -> Self {
+        Self { inner, replayed }
+    }
+}
+
+#[async_trait]
+impl<T> TombstoneSequenceProvider for ReplayTombstoneSequenceProvider<T>
+where
+    T: TombstoneSequenceProvider,
+{
+    async fn max_tombstone_sequence_number(&self, table_id: TableId) -> SequenceNumber {
+        match self.replayed.get(&table_id) {
+            Some(v) => SequenceNumber::new(v.get() - 1),
+            None => self.inner.max_tombstone_sequence_number(table_id).await,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+
+    /// A [`TombstoneSequenceProvider`] returning a fixed sequence number for
+    /// all tables.
+    #[derive(Debug)]
+    pub(crate) struct MockTombstoneSequenceProvider {
+        sequence_number: SequenceNumber,
+    }
+
+    impl MockTombstoneSequenceProvider {
+        pub(crate) fn new(sequence_number: i64) -> Self {
+            Self {
+                sequence_number: SequenceNumber::new(sequence_number),
+            }
+        }
+    }
+
+    impl Default for MockTombstoneSequenceProvider {
+        fn default() -> Self {
+            Self::new(0)
+        }
+    }
+
+    #[async_trait]
+    impl TombstoneSequenceProvider for MockTombstoneSequenceProvider {
+        async fn max_tombstone_sequence_number(&self, _table_id: TableId) -> SequenceNumber {
+            self.sequence_number
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{ShardIndex, Timestamp, TRANSITION_SHARD_ID};
+    use iox_catalog::mem::MemCatalog;
+
+    use super::*;
+    use crate::test_util::populate_catalog;
+
+    const SHARD_INDEX: ShardIndex = ShardIndex::new(24);
+    const TABLE_NAME: &str = "bananas";
+    const NAMESPACE_NAME: &str = "platanos";
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let metrics = Arc::new(metric::Registry::default());
+        let backoff_config = BackoffConfig::default();
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        // Populate the catalog with the shard / namespace / table
+        let (_shard_id, _ns_id, table_id) =
+            populate_catalog(&*catalog, SHARD_INDEX, NAMESPACE_NAME, TABLE_NAME).await;
+
+        let resolver = TombstoneSequenceResolver::new(Arc::clone(&catalog), backoff_config);
+        assert_eq!(
+            resolver.max_tombstone_sequence_number(table_id).await,
+            SequenceNumber::new(0)
+        );
+
+        let mut repos = catalog.repositories().await;
+        for _ in 0..2 {
+            repos
+                .tombstones()
+                .create(
+                    table_id,
+                    TRANSITION_SHARD_ID,
+                    Timestamp::new(1),
+                    Timestamp::new(10),
+                    "",
+                )
+                .await
+                .unwrap();
+        }
+        drop(repos);
+
+        assert_eq!(
+            resolver.max_tombstone_sequence_number(table_id).await,
+            SequenceNumber::new(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay() {
+        let other = TableId::new(1);
+        let replayed = TableId::new(2);
+
+        let provider = ReplayTombstoneSequenceProvider::new(
+            mock::MockTombstoneSequenceProvider::new(42),
+            [(replayed, SequenceNumber::new(24))].into_iter().collect(),
+        );
+
+        assert_eq!(
+            provider.max_tombstone_sequence_number(other).await,
+            SequenceNumber::new(42)
+        );
+        assert_eq!(
+            provider.max_tombstone_sequence_number(replayed).await,
+            SequenceNumber::new(23)
+        );
+    }
+}
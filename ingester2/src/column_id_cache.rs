@@ -0,0 +1,54 @@
+//! An abstraction over a cache of catalog [`ColumnId`] values.
+//!
+//! The IDs of the columns of a buffered write are cached by name, and must be
+//! discarded when a column is renamed, as a new column may then be created
+//! with the previous name.
+//!
+//! [`ColumnId`]: data_types::ColumnId
+
+use std::{fmt::Debug, sync::Arc};
+
+use data_types::{NamespaceId, TableId};
+
+/// An abstraction over any type that caches the catalog IDs of the columns of
+/// the tables it buffers.
+pub(crate) trait ColumnIdCache: Send + Sync + Debug {
+    /// Discard the cached column IDs of `table_id` in `namespace_id`, causing
+    /// them to be fetched from the catalog for the next write.
+    fn invalidate_column_ids(&self, namespace_id: NamespaceId, table_id: TableId);
+}
+
+impl<T> ColumnIdCache for Arc<T>
+where
+    T: ColumnIdCache,
+{
+    fn invalidate_column_ids(&self, namespace_id: NamespaceId, table_id: TableId) {
+        (**self).invalidate_column_ids(namespace_id, table_id)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    /// A [`ColumnIdCache`] that records the tables it was asked to invalidate.
+    #[derive(Debug, Default)]
+    pub(crate) struct MockColumnIdCache {
+        calls: Mutex<Vec<(NamespaceId, TableId)>>,
+    }
+
+    impl MockColumnIdCache {
+        /// Return the tables passed to [`ColumnIdCache::invalidate_column_ids()`].
+        pub(crate) fn calls(&self) -> Vec<(NamespaceId, TableId)> {
+            self.calls.lock().clone()
+        }
+    }
+
+    impl ColumnIdCache for MockColumnIdCache {
+        fn invalidate_column_ids(&self, namespace_id: NamespaceId, table_id: TableId) {
+            self.calls.lock().push((namespace_id, table_id));
+        }
+    }
+}
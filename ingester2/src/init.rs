@@ -29,15 +29,24 @@ use crate::{
         partition::resolver::{
             CatalogPartitionResolver, CoalescePartitionResolver, PartitionCache, PartitionProvider,
         },
-        table::name_resolver::{TableNameProvider, TableNameResolver},
+        table::{
+            column_resolver::{ColumnIdProvider, ColumnIdResolver},
+            name_resolver::{TableNameProvider, TableNameResolver},
+            tombstone_resolver::{
+                ReplayTombstoneSequenceProvider, TombstoneSequenceProvider,
+                TombstoneSequenceResolver,
+            },
+        },
         BufferTree,
     },
     dml_sink::{instrumentation::DmlSinkInstrumentation, tracing::DmlSinkTracing},
     ingest_state::IngestState,
     ingester_id::IngesterId,
     persist::{
-        completion_observer::NopObserver, handle::PersistHandle,
+        completion_observer::NopObserver,
+        handle::PersistHandle,
         hot_partitions::HotPartitionPersister,
+        triggers::{PersistTriggers, PERSIST_TRIGGER_INTERVAL},
     },
     query::{
         exec_instrumentation::QueryExecInstrumentation,
@@ -99,6 +108,12 @@ pub struct IngesterGuard<T> {
     /// Aborted on drop.
     rotation_task: tokio::task::JoinHandle<()>,
 
+    /// The handle of the periodic idle / memory watermark persist task, if
+    /// enabled.
+    ///
+    /// Aborted on drop.
+    persist_trigger_task: Option<tokio::task::JoinHandle<()>>,
+
     /// The task handle executing the graceful shutdown once triggered.
     graceful_shutdown_handler: tokio::task::JoinHandle<()>,
     shutdown_complete: Shared<oneshot::Receiver<()>>,
@@ -125,6 +140,9 @@ where
 impl<T> Drop for IngesterGuard<T> {
     fn drop(&mut self) {
         self.rotation_task.abort();
+        if let Some(t) = &self.persist_trigger_task {
+            t.abort();
+        }
         self.graceful_shutdown_handler.abort();
     }
 }
@@ -159,7 +177,11 @@ pub enum InitError {
 ///
 /// These files are read and replayed fully before this function returns.
 ///
-/// Any error during replay is fatal.
+/// If `wal_replay_strict` is false, a WAL segment that cannot be read in full
+/// (for example, one with a torn final write after a crash) has all the
+/// complete entries before the corruption replayed and is then moved to a
+/// `quarantine` directory within `wal_directory`. If `wal_replay_strict` is
+/// true, or any other error occurs, replay is fatal.
 ///
 /// ## Graceful Shutdown
 ///
@@ -217,6 +239,22 @@ pub enum InitError {
 /// Decreasing this value increases the frequency of persist operations, and
 /// usually decreases the size of the resulting parquet files.
 ///
+/// ## Idle & Memory Watermark Persistence
+///
+/// In addition to persisting all buffered data at WAL rotation, individual
+/// partitions can be persisted between rotations by two optional triggers:
+///
+///   * If `persist_idle_partition_age` is set, any partition that has not been
+///     written to for at least this duration of time is persisted.
+///
+///   * If `persist_buffer_watermark` is set and the sum of the estimated
+///     persist cost of all buffered partitions exceeds it, the partitions with
+///     the largest cost are persisted first until the total drops to at most
+///     the watermark.
+///
+/// Both triggers are evaluated periodically, spreading persist operations out
+/// over the rotation period and bounding the amount of buffered data.
+///
 /// [`MutableBatch::size_data()`]: mutable_batch::MutableBatch::size_data
 #[allow(clippy::too_many_arguments)]
 pub async fn new<F>(
@@ -225,10 +263,13 @@ pub async fn new<F>(
     persist_background_fetch_time: Duration,
     wal_directory: PathBuf,
     wal_rotation_period: Duration,
+    wal_replay_strict: bool,
     persist_executor: Arc<Executor>,
     persist_workers: usize,
     persist_queue_depth: usize,
     persist_hot_partition_cost: usize,
+    persist_idle_partition_age: Option<Duration>,
+    persist_buffer_watermark: Option<usize>,
     object_store: ParquetStorage,
     shutdown: F,
 ) -> Result<IngesterGuard<impl IngesterRpcInterface>, InitError>
@@ -270,6 +311,23 @@ where
         BackoffConfig::default(),
     ));
 
+    // Initialise the resolver of the catalog column IDs of buffered writes.
+    let column_id_provider: Arc<dyn ColumnIdProvider> = Arc::new(ColumnIdResolver::new(
+        Arc::clone(&catalog),
+        BackoffConfig::default(),
+    ));
+
+    // Initialise the WAL
+    let wal = Wal::new(wal_directory).await.map_err(InitError::WalInit)?;
+
+    // Initialise the resolver of the most recent tombstone of a table, taking
+    // into account the deletes that are yet to be replayed from the WAL.
+    let tombstone_sequence_provider: Arc<dyn TombstoneSequenceProvider> =
+        Arc::new(ReplayTombstoneSequenceProvider::new(
+            TombstoneSequenceResolver::new(Arc::clone(&catalog), BackoffConfig::default()),
+            wal_replay::replayed_tombstones(&wal),
+        ));
+
     // Read the most recently created partitions.
     //
     // By caching these hot partitions overall catalog load after an ingester
@@ -333,20 +391,24 @@ where
     let buffer = Arc::new(BufferTree::new(
         namespace_name_provider,
         table_name_provider,
+        column_id_provider,
+        tombstone_sequence_provider,
         partition_provider,
         Arc::new(hot_partition_persister),
         Arc::clone(&metrics),
         transition_shard.id,
     ));
 
-    // Initialise the WAL
-    let wal = Wal::new(wal_directory).await.map_err(InitError::WalInit)?;
-
     // Replay the WAL log files, if any.
-    let max_sequence_number =
-        wal_replay::replay(&wal, &buffer, Arc::clone(&persist_handle), &metrics)
-            .await
-            .map_err(|e| InitError::WalReplay(e.into()))?;
+    let max_sequence_number = wal_replay::replay(
+        &wal,
+        &buffer,
+        Arc::clone(&persist_handle),
+        wal_replay_strict,
+        &metrics,
+    )
+    .await
+    .map_err(|e| InitError::WalReplay(e.into()))?;
 
     // Build the chain of DmlSink that forms the write path.
     let write_path = DmlSinkInstrumentation::new(
@@ -384,6 +446,22 @@ where
         Arc::clone(&persist_handle),
     ));
 
+    // Spawn a background thread to periodically persist idle partitions, and
+    // the largest partitions once the buffer exceeds the watermark.
+    let persist_trigger_task =
+        if persist_idle_partition_age.is_some() || persist_buffer_watermark.is_some() {
+            let triggers = PersistTriggers::new(
+                Arc::clone(&buffer),
+                Arc::clone(&persist_handle),
+                persist_idle_partition_age,
+                persist_buffer_watermark,
+                &metrics,
+            );
+            Some(tokio::spawn(triggers.run(PERSIST_TRIGGER_INTERVAL)))
+        } else {
+            None
+        };
+
     // Restore the highest sequence number from the WAL files, and default to 0
     // if there were no files to replay.
     //
@@ -419,6 +497,7 @@ where
             persist_handle,
         ),
         rotation_task,
+        persist_trigger_task,
         graceful_shutdown_handler: shutdown_task,
         shutdown_complete: shutdown_rx.shared(),
     })
@@ -1,12 +1,15 @@
 use data_types::{NamespaceId, PartitionKey, Sequence, SequenceNumber, TableId};
-use dml::{DmlMeta, DmlOperation, DmlWrite};
-use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op;
+use dml::{DmlDelete, DmlMeta, DmlOperation, DmlWrite};
+use generated_types::{
+    google::{FieldViolation, FromOptionalField},
+    influxdata::iox::wal::v1::sequenced_wal_op::Op,
+};
 use metric::U64Counter;
 use mutable_batch_pb::decode::decode_database_batch;
 use observability_deps::tracing::*;
-use std::time::Instant;
+use std::{collections::HashMap, time::Instant};
 use thiserror::Error;
-use wal::{SequencedWalOp, Wal};
+use wal::{ClosedSegment, SequencedWalOp, Wal};
 
 use crate::{
     dml_sink::{DmlError, DmlSink},
@@ -30,6 +33,10 @@ pub enum WalReplayError {
     #[error("failed converting wal entry to dml operation: {0}")]
     MapToDml(#[from] mutable_batch_pb::decode::Error),
 
+    /// An error converting a WAL delete entry into a [`DmlDelete`].
+    #[error("failed converting wal delete entry to dml operation: {0}")]
+    MapDelete(FieldViolation),
+
     /// A failure to apply a [`DmlOperation`] from the WAL to the in-memory
     /// [`BufferTree`].
     ///
@@ -38,16 +45,20 @@ pub enum WalReplayError {
     Apply(#[from] DmlError),
 }
 
-// TODO: tolerate WAL replay errors
-//
-// https://github.com/influxdata/influxdb_iox/issues/6283
-
 /// Replay all the entries in `wal` to `sink`, returning the maximum observed
 /// [`SequenceNumber`].
+///
+/// Each WAL entry is validated against its checksum and length as it is read.
+/// If `strict` is false, a segment that cannot be read in full (such as one
+/// containing a torn write left behind by a crash) has every complete entry
+/// before the corruption replayed, and is then moved out of the WAL into
+/// quarantine for later inspection instead of failing the replay. If `strict`
+/// is true, any read error aborts the replay.
 pub async fn replay<T, P>(
     wal: &Wal,
     sink: &T,
     persist: P,
+    strict: bool,
     metrics: &metric::Registry,
 ) -> Result<Option<SequenceNumber>, WalReplayError>
 where
@@ -81,9 +92,21 @@ where
             "Number of operations successfully replayed from the WAL",
         )
         .recorder(&[]);
+    let corrupt_file_metric = metrics
+        .register_metric::<U64Counter>(
+            "ingester_wal_replay_corrupt_files",
+            "Number of corrupt WAL files that were quarantined during replay",
+        )
+        .recorder(&[]);
+    let skipped_bytes_metric = metrics
+        .register_metric::<U64Counter>(
+            "ingester_wal_replay_skipped_bytes",
+            "Number of bytes of corrupt WAL files that could not be replayed",
+        )
+        .recorder(&[]);
 
     let n_files = files.len();
-    info!(n_files, "found wal files for replay");
+    info!(n_files, strict, "found wal files for replay");
 
     // Replay each file, keeping track of the last observed sequence number.
     //
@@ -97,9 +120,27 @@ where
         file_count_metric.inc(1);
 
         // Read the segment
-        let reader = wal
-            .reader_for_segment(file.id())
-            .map_err(WalReplayError::OpenSegment)?;
+        let reader = match wal.reader_for_segment(file.id()) {
+            Ok(v) => v,
+            Err(error) if !strict => {
+                // The segment header is unreadable, so none of the segment
+                // can be replayed.
+                error!(
+                    file_number,
+                    n_files,
+                    file_id = %file.id(),
+                    size = file.size(),
+                    skipped_bytes = file.size(),
+                    %error,
+                    "failed to open wal segment for replay, skipping",
+                );
+                corrupt_file_metric.inc(1);
+                skipped_bytes_metric.inc(file.size());
+                quarantine(wal, &file).await;
+                continue;
+            }
+            Err(e) => return Err(WalReplayError::OpenSegment(e)),
+        };
 
         // Emit a log entry so progress can be tracked (and a problematic file
         // be identified should an explosion happen during replay).
@@ -112,8 +153,31 @@ where
         );
 
         // Replay this segment file
-        match replay_file(reader, sink, &op_count_metric).await? {
+        let replayed = replay_file(reader, sink, strict, &op_count_metric).await?;
+
+        if let Some(error) = &replayed.corruption {
+            let skipped_bytes = file.size().saturating_sub(replayed.valid_bytes);
+            error!(
+                file_number,
+                n_files,
+                file_id = %file.id(),
+                size = file.size(),
+                skipped_bytes,
+                %error,
+                "wal segment is corrupt, replayed all complete entries before the corruption",
+            );
+            corrupt_file_metric.inc(1);
+            skipped_bytes_metric.inc(skipped_bytes);
+        }
+
+        match replayed.max_sequence {
             v @ Some(_) => max_sequence = max_sequence.max(v),
+            None if replayed.corruption.is_some() => {
+                // Nothing was recovered from this file, but it must be kept
+                // for inspection.
+                quarantine(wal, &file).await;
+                continue;
+            }
             None => {
                 // This file was empty and should be deleted.
                 warn!(
@@ -154,6 +218,13 @@ where
         // Persist all the data that was replayed from the WAL segment.
         persist_partitions(sink.partition_iter(), &persist).await;
 
+        // The recovered data of a corrupt segment is now durable, but the
+        // segment itself is kept aside rather than dropped.
+        if replayed.corruption.is_some() {
+            quarantine(wal, &file).await;
+            continue;
+        }
+
         // Drop the newly persisted data - it should not be replayed.
         wal.delete(file.id())
             .await
@@ -176,13 +247,79 @@ where
     Ok(max_sequence)
 }
 
-/// Replay the entries in `file`, applying them to `buffer`. Returns the highest
-/// sequence number observed in the file, or [`None`] if the file was empty.
+/// Return the sequence number of the oldest tombstone of each table that is
+/// deleted by an entry in `wal`, reading the entries that precede any
+/// corruption in a segment.
+///
+/// This allows the writes replayed before the deletes to be identified as
+/// being subject to them.
+pub(crate) fn replayed_tombstones(wal: &Wal) -> HashMap<TableId, SequenceNumber> {
+    let mut tombstones = HashMap::new();
+
+    for file in wal.closed_segments() {
+        let mut reader = match wal.reader_for_segment(file.id()) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        while let Ok(Some(ops)) = reader.next_batch() {
+            for op in ops {
+                if let Op::Delete(d) = op.op {
+                    let sequence_number = SequenceNumber::new(d.tombstone_sequence_number);
+                    tombstones
+                        .entry(TableId::new(d.table_id))
+                        .and_modify(|v: &mut SequenceNumber| *v = (*v).min(sequence_number))
+                        .or_insert(sequence_number);
+                }
+            }
+        }
+    }
+
+    tombstones
+}
+
+/// Move the corrupt segment `file` out of the WAL so it is not replayed again.
+async fn quarantine(wal: &Wal, file: &ClosedSegment) {
+    match wal.quarantine(file.id()).await {
+        Ok(path) => warn!(
+            file_id = %file.id(),
+            path = %path.display(),
+            "quarantined corrupt wal segment"
+        ),
+        // A failure to quarantine the file means it will be replayed again
+        // on the next startup, which is safe as replay is idempotent.
+        Err(error) => error!(
+            file_id = %file.id(),
+            %error,
+            "error quarantining corrupt wal segment"
+        ),
+    }
+}
+
+/// The outcome of replaying a single WAL segment file.
+#[derive(Debug)]
+struct ReplayedFile {
+    /// The highest sequence number observed in the file, or [`None`] if no
+    /// entries were replayed.
+    max_sequence: Option<SequenceNumber>,
+
+    /// The number of bytes of the file that were read without error.
+    valid_bytes: u64,
+
+    /// The error that stopped the replay of the file before its end, if any.
+    corruption: Option<wal::Error>,
+}
+
+/// Replay the entries in `file`, applying them to `buffer`.
+///
+/// If `strict` is false, replay stops at the first entry that cannot be read,
+/// and the error is returned in [`ReplayedFile::corruption`].
 async fn replay_file<T>(
     mut file: wal::ClosedSegmentFileReader,
     sink: &T,
+    strict: bool,
     op_count_metric: &U64Counter,
-) -> Result<Option<SequenceNumber>, WalReplayError>
+) -> Result<ReplayedFile, WalReplayError>
 where
     T: DmlSink,
 {
@@ -196,7 +333,20 @@ where
                 // This file is complete, return the last observed sequence
                 // number.
                 debug!("wal file replayed in {:?}", start.elapsed());
-                return Ok(max_sequence);
+                return Ok(ReplayedFile {
+                    max_sequence,
+                    valid_bytes: file.bytes_read(),
+                    corruption: None,
+                });
+            }
+            Err(e) if !strict => {
+                // Entries following a corrupt entry cannot be located
+                // reliably, so stop reading this file.
+                return Ok(ReplayedFile {
+                    max_sequence,
+                    valid_bytes: file.bytes_read(),
+                    corruption: Some(e),
+                });
             }
             Err(e) => return Err(WalReplayError::ReadEntry(e)),
         };
@@ -213,43 +363,56 @@ where
 
             max_sequence = max_sequence.max(Some(sequence_number));
 
-            let op = match op {
-                Op::Write(w) => w,
-                Op::Delete(_) => unreachable!(),
-                Op::Persist(_) => unreachable!(),
-            };
+            // The tracing context should be propagated over the RPC boundary.
+            let meta = DmlMeta::sequenced(
+                Sequence {
+                    shard_index: TRANSITION_SHARD_INDEX, // TODO: remove this from DmlMeta
+                    sequence_number,
+                },
+                iox_time::Time::MAX, // TODO: remove this from DmlMeta
+                // TODO: A tracing context should be added for WAL replay.
+                None,
+                42, // TODO: remove this from DmlMeta
+            );
 
             debug!(?op, sequence_number = sequence_number.get(), "apply wal op");
 
             // Reconstruct the DML operation
-            let batches = decode_database_batch(&op)?;
-            let namespace_id = NamespaceId::new(op.database_id);
-            let partition_key = PartitionKey::from(op.partition_key);
-
-            let op = DmlWrite::new(
-                namespace_id,
-                batches
-                    .into_iter()
-                    .map(|(k, v)| (TableId::new(k), v))
-                    .collect(),
-                partition_key,
-                // The tracing context should be propagated over the RPC boundary.
-                DmlMeta::sequenced(
-                    Sequence {
-                        shard_index: TRANSITION_SHARD_INDEX, // TODO: remove this from DmlMeta
-                        sequence_number,
-                    },
-                    iox_time::Time::MAX, // TODO: remove this from DmlMeta
-                    // TODO: A tracing context should be added for WAL replay.
-                    None,
-                    42, // TODO: remove this from DmlMeta
-                ),
-            );
+            let op = match op {
+                Op::Write(op) => {
+                    let batches = decode_database_batch(&op)?;
+                    let namespace_id = NamespaceId::new(op.database_id);
+                    let partition_key = PartitionKey::from(op.partition_key);
+
+                    DmlOperation::Write(DmlWrite::new(
+                        namespace_id,
+                        batches
+                            .into_iter()
+                            .map(|(k, v)| (TableId::new(k), v))
+                            .collect(),
+                        partition_key,
+                        meta,
+                    ))
+                }
+                Op::Delete(op) => {
+                    let predicate = op
+                        .predicate
+                        .required("predicate")
+                        .map_err(WalReplayError::MapDelete)?;
+
+                    DmlOperation::Delete(DmlDelete::new(
+                        NamespaceId::new(op.database_id),
+                        TableId::new(op.table_id),
+                        predicate,
+                        SequenceNumber::new(op.tombstone_sequence_number),
+                        meta,
+                    ))
+                }
+                Op::Persist(_) => unreachable!(),
+            };
 
             // Apply the operation to the provided DML sink
-            sink.apply(DmlOperation::Write(op))
-                .await
-                .map_err(Into::<DmlError>::into)?;
+            sink.apply(op).await.map_err(Into::<DmlError>::into)?;
 
             op_count_metric.inc(1);
         }
@@ -263,6 +426,7 @@ mod tests {
     use assert_matches::assert_matches;
     use async_trait::async_trait;
     use data_types::{NamespaceId, PartitionId, PartitionKey, ShardId, TableId};
+    use generated_types::influxdata::iox::delete::v1::DeletePayload;
     use metric::{Attributes, Metric};
     use parking_lot::Mutex;
     use wal::Wal;
@@ -411,7 +575,7 @@ mod tests {
         };
 
         let metrics = metric::Registry::default();
-        let max_sequence_number = replay(&wal, &mock_iter, Arc::clone(&persist), &metrics)
+        let max_sequence_number = replay(&wal, &mock_iter, Arc::clone(&persist), false, &metrics)
             .await
             .expect("failed to replay WAL");
 
@@ -460,4 +624,190 @@ mod tests {
             .fetch();
         assert_eq!(ops, 3);
     }
+
+    /// Write `op1` and `op2` into the first segment and `op3` into the second,
+    /// then tear the final write of the first segment.
+    async fn write_torn_wal(dir: &std::path::Path, ops: [&DmlWrite; 3]) {
+        let inner =
+            Arc::new(MockDmlSink::default().with_apply_return(vec![Ok(()), Ok(()), Ok(())]));
+        let wal = Wal::new(dir).await.expect("failed to initialise WAL");
+        let wal_sink = WalSink::new(Arc::clone(&inner), Arc::clone(&wal));
+
+        wal_sink
+            .apply(DmlOperation::Write(ops[0].clone()))
+            .await
+            .expect("wal should not error");
+        wal_sink
+            .apply(DmlOperation::Write(ops[1].clone()))
+            .await
+            .expect("wal should not error");
+        let (torn, _) = wal.rotate().expect("failed to rotate WAL file");
+        wal_sink
+            .apply(DmlOperation::Write(ops[2].clone()))
+            .await
+            .expect("wal should not error");
+
+        let path = dir.join(format!("{}.dat", torn.id()));
+        let data = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &data[..data.len() - 3]).unwrap();
+    }
+
+    fn test_ops() -> [DmlWrite; 3] {
+        [
+            make_write_op(
+                &PartitionKey::from("p1"),
+                NAMESPACE_ID,
+                TABLE_NAME,
+                TABLE_ID,
+                24,
+                r#"bananas,region=Madrid temp=35 4242424242"#,
+            ),
+            make_write_op(
+                &PartitionKey::from("p1"),
+                NAMESPACE_ID,
+                TABLE_NAME,
+                TABLE_ID,
+                25,
+                r#"bananas,region=Asturias temp=25 4242424242"#,
+            ),
+            make_write_op(
+                &PartitionKey::from("p1"),
+                NAMESPACE_ID,
+                TABLE_NAME,
+                TABLE_ID,
+                42,
+                r#"bananas,region=Asturias temp=15 4242424242"#,
+            ),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_replayed_tombstones() {
+        let dir = tempfile::tempdir().unwrap();
+        let other_table = TableId::new(45);
+
+        {
+            let wal = Wal::new(dir.path())
+                .await
+                .expect("failed to initialise WAL");
+
+            for (sequence_number, table_id, tombstone) in
+                [(1, TABLE_ID, 3), (2, other_table, 7), (3, TABLE_ID, 5)]
+            {
+                let mut res = wal.write_op(SequencedWalOp {
+                    sequence_number,
+                    op: Op::Delete(DeletePayload {
+                        database_id: NAMESPACE_ID.get(),
+                        table_id: table_id.get(),
+                        tombstone_sequence_number: tombstone,
+                        ..Default::default()
+                    }),
+                });
+                res.changed().await.expect("wal write should complete");
+            }
+
+            wal.rotate().expect("failed to rotate WAL file");
+        }
+
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+
+        let got = replayed_tombstones(&wal);
+        assert_eq!(got.len(), 2);
+        assert_eq!(got[&TABLE_ID], SequenceNumber::new(3));
+        assert_eq!(got[&other_table], SequenceNumber::new(7));
+    }
+
+    #[tokio::test]
+    async fn test_replay_torn_segment() {
+        let dir = tempfile::tempdir().unwrap();
+        let [op1, op2, op3] = test_ops();
+        write_torn_wal(dir.path(), [&op1, &op2, &op3]).await;
+
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+        assert_eq!(wal.closed_segments().len(), 2);
+
+        let persist = Arc::new(MockPersistQueue::default());
+        let mock_iter = MockIter {
+            sink: MockDmlSink::default().with_apply_return(vec![Ok(()), Ok(())]),
+            partitions: vec![],
+        };
+
+        let metrics = metric::Registry::default();
+        let max_sequence_number = replay(&wal, &mock_iter, Arc::clone(&persist), false, &metrics)
+            .await
+            .expect("failed to replay WAL");
+
+        assert_eq!(max_sequence_number, Some(SequenceNumber::new(42)));
+
+        // The complete entry before the torn write, and the entry in the
+        // following segment were replayed.
+        let ops = mock_iter.sink.get_calls();
+        assert_matches!(&*ops, &[DmlOperation::Write(ref w1), DmlOperation::Write(ref w3)] => {
+            assert_dml_writes_eq(w1.clone(), op1);
+            assert_dml_writes_eq(w3.clone(), op3);
+        });
+
+        // The torn segment was moved to quarantine, and the replayed segments
+        // are no longer part of the WAL.
+        assert_eq!(
+            std::fs::read_dir(dir.path().join("quarantine"))
+                .unwrap()
+                .count(),
+            1
+        );
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+        assert_eq!(wal.closed_segments().len(), 1);
+
+        let corrupt = metrics
+            .get_instrument::<Metric<U64Counter>>("ingester_wal_replay_corrupt_files")
+            .expect("corrupt file counter not found")
+            .get_observer(&Attributes::from([]))
+            .expect("attributes not found")
+            .fetch();
+        assert_eq!(corrupt, 1);
+        let skipped = metrics
+            .get_instrument::<Metric<U64Counter>>("ingester_wal_replay_skipped_bytes")
+            .expect("skipped bytes counter not found")
+            .get_observer(&Attributes::from([]))
+            .expect("attributes not found")
+            .fetch();
+        assert!(skipped > 0);
+    }
+
+    #[tokio::test]
+    async fn test_replay_torn_segment_strict() {
+        let dir = tempfile::tempdir().unwrap();
+        let [op1, op2, op3] = test_ops();
+        write_torn_wal(dir.path(), [&op1, &op2, &op3]).await;
+
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+
+        let mock_iter = MockIter {
+            sink: MockDmlSink::default().with_apply_return(vec![Ok(())]),
+            partitions: vec![],
+        };
+
+        let metrics = metric::Registry::default();
+        let err = replay(
+            &wal,
+            &mock_iter,
+            Arc::new(MockPersistQueue::default()),
+            true,
+            &metrics,
+        )
+        .await
+        .expect_err("strict replay should fail");
+        assert_matches!(err, WalReplayError::ReadEntry(_));
+
+        // Nothing was quarantined.
+        assert!(!dir.path().join("quarantine").exists());
+    }
 }
@@ -252,6 +252,7 @@ maybe_pub!(mod partition_iter);
 maybe_pub!(mod wal);
 mod arcmap;
 mod cancellation_safe;
+mod column_id_cache;
 mod deferred_load;
 mod ingest_state;
 mod ingester_id;
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use arrow::error::ArrowError;
 use data_types::{NamespaceId, PartitionId, PartitionKey, ShardId, TableId};
 use observability_deps::tracing::*;
 use parking_lot::Mutex;
@@ -30,6 +31,16 @@ pub(super) enum PersistError {
     /// aborted. The newly observed sort key is returned.
     #[error("detected concurrent sort key update")]
     ConcurrentSortKeyUpdate(SortKey),
+
+    /// A column of the parquet file was renamed or deleted before the file
+    /// was added to the catalog, and the file must be regenerated.
+    #[error("detected concurrent column rename")]
+    ConcurrentColumnRename,
+
+    /// The buffered columns could not be matched to the columns of the
+    /// table in the catalog.
+    #[error("failed to resolve persisting columns: {0}")]
+    ResolveColumns(#[from] ArrowError),
 }
 
 /// An internal type that contains all necessary information to run a persist
@@ -489,7 +489,11 @@ mod tests {
             namespace::{name_resolver::mock::MockNamespaceNameProvider, NamespaceName},
             partition::resolver::mock::MockPartitionProvider,
             post_write::mock::MockPostWriteObserver,
-            table::{name_resolver::mock::MockTableNameProvider, TableName},
+            table::{
+                column_resolver::mock::MockColumnIdProvider,
+                name_resolver::mock::MockTableNameProvider,
+                tombstone_resolver::mock::MockTombstoneSequenceProvider, TableName,
+            },
             BufferTree,
         },
         deferred_load::DeferredLoad,
@@ -531,6 +535,8 @@ mod tests {
         let buffer_tree = BufferTree::new(
             Arc::new(MockNamespaceNameProvider::new(NAMESPACE_NAME)),
             Arc::new(MockTableNameProvider::new(TABLE_NAME)),
+            Arc::new(MockColumnIdProvider::default()),
+            Arc::new(MockTombstoneSequenceProvider::default()),
             Arc::new(
                 MockPartitionProvider::default().with_partition(PartitionData::new(
                     partition_id,
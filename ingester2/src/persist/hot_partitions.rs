@@ -3,7 +3,10 @@ use std::{fmt::Debug, sync::Arc};
 use observability_deps::tracing::info;
 use parking_lot::{Mutex, MutexGuard};
 
-use crate::buffer_tree::{partition::PartitionData, post_write::PostWriteObserver};
+use crate::buffer_tree::{
+    partition::{persisting::PersistingData, PartitionData},
+    post_write::PostWriteObserver,
+};
 
 use super::queue::PersistQueue;
 
@@ -57,6 +60,13 @@ where
             .mark_persisting()
             .expect("failed to transition buffer fsm to persisting state");
 
+        self.enqueue(partition, data);
+
+        // Update any exported metrics.
+        self.persist_count.inc(1);
+    }
+
+    fn enqueue(&self, partition: Arc<Mutex<PartitionData>>, data: PersistingData) {
         // Perform the enqueue in a separate task, to avoid blocking this
         // writer if the persist system is saturated.
         let persist_handle = self.persist_handle.clone();
@@ -64,8 +74,6 @@ where
             // There is no need to await on the completion handle.
             persist_handle.enqueue(partition, data).await;
         });
-        // Update any exported metrics.
-        self.persist_count.inc(1);
     }
 }
 
@@ -97,6 +105,10 @@ where
             self.persist(cost_estimate, partition, guard)
         }
     }
+
+    fn observe_delete(&self, partition: Arc<Mutex<PartitionData>>, data: PersistingData) {
+        self.enqueue(partition, data);
+    }
 }
 
 #[cfg(test)]
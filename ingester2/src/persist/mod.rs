@@ -8,6 +8,7 @@ pub(crate) mod drain_buffer;
 pub(crate) mod handle;
 pub(crate) mod hot_partitions;
 pub mod queue;
+pub(crate) mod triggers;
 mod worker;
 
 #[cfg(test)]
@@ -15,17 +16,21 @@ mod tests {
     use std::{sync::Arc, time::Duration};
 
     use assert_matches::assert_matches;
-    use data_types::{CompactionLevel, ParquetFile, PartitionKey, SequenceNumber, ShardId};
+    use data_types::{
+        ColumnType, CompactionLevel, DeletePredicate, ParquetFile, PartitionKey, SequenceNumber,
+        ShardId, Timestamp, TimestampRange,
+    };
     use dml::DmlOperation;
     use futures::TryStreamExt;
     use iox_catalog::{
-        interface::{get_schema_by_id, Catalog, SoftDeletedRows},
+        interface::{get_schema_by_id, get_table_schema_by_id, Catalog, SoftDeletedRows},
         mem::MemCatalog,
         validate_or_insert_schema,
     };
     use iox_query::exec::Executor;
     use lazy_static::lazy_static;
     use metric::{Attributes, DurationHistogram, Metric, U64Counter, U64Gauge};
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
     use object_store::{memory::InMemory, ObjectMeta, ObjectStore};
     use parking_lot::Mutex;
     use parquet_file::{
@@ -39,7 +44,10 @@ mod tests {
             namespace::name_resolver::mock::MockNamespaceNameProvider,
             partition::{resolver::CatalogPartitionResolver, PartitionData, SortKeyState},
             post_write::mock::MockPostWriteObserver,
-            table::name_resolver::mock::MockTableNameProvider,
+            table::{
+                column_resolver::ColumnIdResolver, name_resolver::mock::MockTableNameProvider,
+                tombstone_resolver::TombstoneSequenceResolver,
+            },
             BufferTree,
         },
         dml_sink::DmlSink,
@@ -77,6 +85,14 @@ mod tests {
         let buf = BufferTree::new(
             Arc::new(MockNamespaceNameProvider::default()),
             Arc::new(MockTableNameProvider::new(TABLE_NAME)),
+            Arc::new(ColumnIdResolver::new(
+                Arc::clone(&catalog),
+                Default::default(),
+            )),
+            Arc::new(TombstoneSequenceResolver::new(
+                Arc::clone(&catalog),
+                Default::default(),
+            )),
             Arc::new(CatalogPartitionResolver::new(Arc::clone(&catalog))),
             Arc::new(MockPostWriteObserver::default()),
             Arc::new(metric::Registry::default()),
@@ -303,6 +319,223 @@ mod tests {
         )
     }
 
+    /// Parquet files record the most recent tombstone received before their
+    /// data was buffered, so that only the tombstones received afterwards are
+    /// applied to them.
+    #[tokio::test]
+    async fn test_persist_records_max_tombstone_sequence_number() {
+        maybe_start_logging();
+
+        let object_storage: Arc<dyn ObjectStore> = Arc::new(InMemory::default());
+        let storage = ParquetStorage::new(Arc::clone(&object_storage), StorageId::from("iox"));
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+        let ingest_state = Arc::new(IngestState::default());
+        let completion_observer = Arc::new(MockCompletionObserver::default());
+
+        let handle = PersistHandle::new(
+            1,
+            2,
+            Arc::clone(&ingest_state),
+            Arc::clone(&EXEC),
+            storage,
+            Arc::clone(&catalog),
+            Arc::clone(&completion_observer),
+            &metrics,
+        );
+
+        // Generate a partition with data, and record a tombstone after the
+        // write was buffered.
+        let partition = partition_with_write(Arc::clone(&catalog)).await;
+        let table_id = partition.lock().table_id();
+        let partition_id = partition.lock().partition_id();
+        let tombstone = catalog
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table_id,
+                TRANSITION_SHARD_ID,
+                Timestamp::new(0),
+                Timestamp::new(i64::MAX),
+                "",
+            )
+            .await
+            .expect("failed to create tombstone");
+
+        // Applying the delete causes the buffered data to be persisted into a
+        // file the tombstone applies to.
+        let data = partition
+            .lock()
+            .apply_delete(
+                &DeletePredicate {
+                    range: TimestampRange::new(0, i64::MAX),
+                    exprs: vec![],
+                },
+                tombstone.sequence_number,
+            )
+            .expect("partition with write should transition to persisting");
+        handle
+            .enqueue(Arc::clone(&partition), data)
+            .await
+            .with_timeout(Duration::from_secs(10))
+            .await
+            .expect("timeout waiting for completion notification")
+            .expect("worker task failed");
+
+        let files = catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .list_by_partition_not_to_delete(partition_id)
+            .await
+            .expect("query for parquet files failed");
+        let first_file = assert_matches!(&*files, [f] => {
+            assert_eq!(f.max_tombstone_sequence_number, SequenceNumber::new(0));
+            assert!(tombstone.applies_to(f));
+            f.id
+        });
+
+        // A write buffered after the delete is not affected by the tombstone.
+        let mb = lp_to_mutable_batch(r#"bananas,region=Madrid temp=30 4242424243"#).1;
+        partition
+            .lock()
+            .buffer_write(mb, SequenceNumber::new(1))
+            .expect("failed to buffer write");
+        let data = partition
+            .lock()
+            .mark_persisting()
+            .expect("partition with write should transition to persisting");
+        handle
+            .enqueue(Arc::clone(&partition), data)
+            .await
+            .with_timeout(Duration::from_secs(10))
+            .await
+            .expect("timeout waiting for completion notification")
+            .expect("worker task failed");
+
+        let files = catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .list_by_partition_not_to_delete(partition_id)
+            .await
+            .expect("query for parquet files failed");
+        let second_file = assert_matches!(&*files, [a, b] => {
+            if a.id == first_file { b } else { a }
+        });
+        assert_eq!(
+            second_file.max_tombstone_sequence_number,
+            tombstone.sequence_number
+        );
+        assert!(!tombstone.applies_to(second_file));
+    }
+
+    /// Buffered data of columns renamed or deleted before it is persisted is
+    /// persisted to the renamed column or dropped, without recreating any
+    /// catalog columns, even if a new column was created with the previous
+    /// name.
+    #[tokio::test]
+    async fn test_persist_renamed_and_deleted_columns() {
+        maybe_start_logging();
+
+        let object_storage: Arc<dyn ObjectStore> = Arc::new(InMemory::default());
+        let storage = ParquetStorage::new(Arc::clone(&object_storage), StorageId::from("iox"));
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+        let ingest_state = Arc::new(IngestState::default());
+        let completion_observer = Arc::new(MockCompletionObserver::default());
+
+        let handle = PersistHandle::new(
+            1,
+            2,
+            Arc::clone(&ingest_state),
+            Arc::clone(&EXEC),
+            storage,
+            Arc::clone(&catalog),
+            Arc::clone(&completion_observer),
+            &metrics,
+        );
+
+        // Buffer a write to the "region", "temp" and "time" columns.
+        let partition = partition_with_write(Arc::clone(&catalog)).await;
+        let table_id = partition.lock().table_id();
+        let partition_id = partition.lock().partition_id();
+
+        // Rename "temp" and delete "region" before the data is persisted, and
+        // create a new "temp" column.
+        let mut repos = catalog.repositories().await;
+        let schema = get_table_schema_by_id(table_id, &mut *repos)
+            .await
+            .expect("failed to read table schema");
+        let temp_id = schema.columns["temp"].id;
+        let time_id = schema.columns["time"].id;
+        repos
+            .columns()
+            .rename(temp_id, "temperature")
+            .await
+            .expect("failed to rename column");
+        let new_temp_id = repos
+            .columns()
+            .create_or_get("temp", table_id, ColumnType::F64)
+            .await
+            .expect("failed to create column")
+            .id;
+        assert_ne!(new_temp_id, temp_id);
+        repos
+            .columns()
+            .soft_delete(schema.columns["region"].id)
+            .await
+            .expect("failed to delete column");
+        drop(repos);
+
+        // Buffer a write using the new column name.
+        let mb = lp_to_mutable_batch(r#"bananas temperature=30 4242424243"#).1;
+        {
+            let mut p = partition.lock();
+            p.buffer_write(mb, SequenceNumber::new(1))
+                .expect("failed to buffer write");
+            p.record_column_ids([("temperature".to_string(), temp_id)]);
+        }
+
+        let data = partition
+            .lock()
+            .mark_persisting()
+            .expect("partition with write should transition to persisting");
+        handle
+            .enqueue(Arc::clone(&partition), data)
+            .await
+            .with_timeout(Duration::from_secs(10))
+            .await
+            .expect("timeout waiting for completion notification")
+            .expect("worker task failed");
+
+        // No columns were recreated.
+        let mut repos = catalog.repositories().await;
+        let schema = get_table_schema_by_id(table_id, &mut *repos)
+            .await
+            .expect("failed to read table schema");
+        let mut columns = schema.columns.keys().collect::<Vec<_>>();
+        columns.sort();
+        assert_eq!(columns, ["temp", "temperature", "time"]);
+
+        // Both writes were persisted to the renamed column, not the new one.
+        let files = repos
+            .parquet_files()
+            .list_by_partition_not_to_delete(partition_id)
+            .await
+            .expect("query for parquet files failed");
+        assert_matches!(&*files, [f] => {
+            assert_eq!(f.row_count, 2);
+            let mut column_set = f.column_set.iter().copied().collect::<Vec<_>>();
+            column_set.sort();
+            assert_eq!(column_set, [temp_id, time_id]);
+        });
+        assert_matches!(partition.lock().sort_key(), SortKeyState::Provided(Some(p)) => {
+            assert_eq!(p.to_columns().collect::<Vec<_>>(), &["time"]);
+        });
+    }
+
     /// An integration test covering concurrent catalog sort key updates,
     /// discovered at persist time.
     #[tokio::test]
@@ -0,0 +1,350 @@
+use std::{sync::Arc, time::Duration};
+
+use metric::U64Counter;
+use observability_deps::tracing::*;
+use parking_lot::Mutex;
+use tokio::time::{Instant, MissedTickBehavior};
+
+use crate::{buffer_tree::partition::PartitionData, partition_iter::PartitionIter};
+
+use super::queue::PersistQueue;
+
+/// The default interval between evaluations of the [`PersistTriggers`].
+pub(crate) const PERSIST_TRIGGER_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The reason a partition was selected for persistence by [`PersistTriggers`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trigger {
+    /// The partition has not been written to for longer than the configured
+    /// idle age.
+    Idle,
+    /// The total buffered data exceeded the configured watermark, and this
+    /// partition was one of the largest.
+    Watermark,
+}
+
+/// A background task that periodically inspects the buffered partitions and
+/// enqueues individual partitions for persistence between WAL rotations.
+///
+/// Two triggers are evaluated on each tick:
+///
+///   * Idle partitions: any partition that has not been written to for at
+///     least `idle_age` is persisted.
+///
+///   * Buffer watermark: if the sum of the estimated persist cost of all
+///     buffered partitions exceeds `buffer_watermark`, the partitions with the
+///     largest persist cost are persisted first, until the estimated total is
+///     at or below the watermark.
+///
+/// Either trigger can be disabled by setting it to [`None`].
+///
+/// Partitions are persisted in the same way as hot partitions - there is no
+/// need to wait for the persist operation to complete, and any writes
+/// subsequently applied to the partition are buffered in a new buffer.
+#[derive(Debug)]
+pub(crate) struct PersistTriggers<T, P> {
+    buffer: T,
+    persist_handle: P,
+
+    idle_age: Option<Duration>,
+    buffer_watermark: Option<usize>,
+
+    /// The number of partitions persisted because they were idle.
+    idle_persist_count: U64Counter,
+    /// The number of partitions persisted because the buffer watermark was
+    /// exceeded.
+    watermark_persist_count: U64Counter,
+}
+
+impl<T, P> PersistTriggers<T, P>
+where
+    T: PartitionIter + Sync,
+    P: PersistQueue,
+{
+    pub(crate) fn new(
+        buffer: T,
+        persist_handle: P,
+        idle_age: Option<Duration>,
+        buffer_watermark: Option<usize>,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let persist_count = metrics.register_metric::<U64Counter>(
+            "ingester_persist_trigger_enqueue_count",
+            "number of times persistence of a partition has been triggered \
+            between wal rotations, by the trigger that caused it",
+        );
+
+        Self {
+            buffer,
+            persist_handle,
+            idle_age,
+            buffer_watermark,
+            idle_persist_count: persist_count.recorder(&[("trigger", "idle")]),
+            watermark_persist_count: persist_count.recorder(&[("trigger", "watermark")]),
+        }
+    }
+
+    /// Evaluate the persistence triggers every `period` duration of time,
+    /// forever.
+    pub(crate) async fn run(self, period: Duration) {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            self.check().await;
+        }
+    }
+
+    /// Evaluate the persistence triggers once, enqueuing any selected
+    /// partitions for persistence.
+    pub(crate) async fn check(&self) {
+        let now = Instant::now();
+
+        // Snapshot the persist cost of all partitions with buffered data,
+        // separating out the idle partitions.
+        let mut idle = Vec::new();
+        let mut buffered = Vec::new();
+        let mut buffered_total = 0_usize;
+        for p in self.buffer.partition_iter() {
+            let (cost, last_write_at) = {
+                let guard = p.lock();
+                (guard.persist_cost_estimate(), guard.last_write_at())
+            };
+
+            // Skip this partition if there is no data to persist
+            if cost == 0 {
+                continue;
+            }
+
+            let is_idle = match (self.idle_age, last_write_at) {
+                (Some(age), Some(t)) => now.saturating_duration_since(t) >= age,
+                _ => false,
+            };
+
+            if is_idle {
+                idle.push(p);
+            } else {
+                buffered_total += cost;
+                buffered.push((cost, p));
+            }
+        }
+
+        for p in idle {
+            self.persist(Trigger::Idle, p).await;
+        }
+
+        let watermark = match self.buffer_watermark {
+            Some(v) if buffered_total > v => v,
+            _ => return,
+        };
+
+        debug!(
+            buffered_total,
+            watermark, "buffered data exceeds persist watermark"
+        );
+
+        // Persist the largest partitions first, until the remaining buffered
+        // data is at or below the watermark.
+        buffered.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+        for (cost, p) in buffered {
+            if buffered_total <= watermark {
+                break;
+            }
+            self.persist(Trigger::Watermark, p).await;
+            buffered_total -= cost;
+        }
+    }
+
+    async fn persist(&self, trigger: Trigger, partition: Arc<Mutex<PartitionData>>) {
+        // The partition may have been persisted concurrently since the
+        // snapshot was taken, in which case there is nothing to do.
+        let data = partition.lock().mark_persisting();
+        let data = match data {
+            Some(v) => v,
+            None => return,
+        };
+
+        info!(
+            partition_id = data.partition_id().get(),
+            ?trigger,
+            "marking partition for persistence"
+        );
+
+        // There is no need to await on the completion handle.
+        let _ = self
+            .persist_handle
+            .enqueue(Arc::clone(&partition), data)
+            .await;
+
+        match trigger {
+            Trigger::Idle => self.idle_persist_count.inc(1),
+            Trigger::Watermark => self.watermark_persist_count.inc(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use data_types::{NamespaceId, PartitionId, PartitionKey, SequenceNumber, ShardId, TableId};
+    use lazy_static::lazy_static;
+    use metric::{Attributes, Metric};
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::{
+        buffer_tree::{
+            namespace::NamespaceName, partition::PartitionData, partition::SortKeyState,
+            table::TableName,
+        },
+        deferred_load::DeferredLoad,
+        persist::queue::mock::MockPersistQueue,
+    };
+
+    const TRANSITION_SHARD_ID: ShardId = ShardId::new(84);
+
+    lazy_static! {
+        static ref PARTITION_KEY: PartitionKey = PartitionKey::from("pohtaytoes");
+        static ref TABLE_NAME: TableName = TableName::from("potatoes");
+        static ref NAMESPACE_NAME: NamespaceName = NamespaceName::from("namespace-potatoes");
+    }
+
+    /// Construct a [`PartitionData`] with the given ID containing the writes
+    /// in `lp`.
+    fn new_partition(id: i64, lp: &str) -> Arc<Mutex<PartitionData>> {
+        let mut p = PartitionData::new(
+            PartitionId::new(id),
+            PARTITION_KEY.clone(),
+            NamespaceId::new(3),
+            Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                NAMESPACE_NAME.clone()
+            })),
+            TableId::new(4),
+            Arc::new(DeferredLoad::new(Duration::from_secs(1), async {
+                TABLE_NAME.clone()
+            })),
+            SortKeyState::Provided(None),
+            TRANSITION_SHARD_ID,
+        );
+
+        p.buffer_write(lp_to_mutable_batch(lp).1, SequenceNumber::new(1))
+            .expect("write should succeed");
+
+        Arc::new(Mutex::new(p))
+    }
+
+    fn persisted_ids(persist_handle: &MockPersistQueue) -> Vec<PartitionId> {
+        let mut ids = persist_handle
+            .calls()
+            .iter()
+            .map(|p| p.lock().partition_id())
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids
+    }
+
+    fn assert_trigger_count(metrics: &metric::Registry, trigger: &'static str, want: u64) {
+        let got = metrics
+            .get_instrument::<Metric<U64Counter>>("ingester_persist_trigger_enqueue_count")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[("trigger", trigger)]))
+            .expect("failed to get observer")
+            .fetch();
+        assert_eq!(got, want, "unexpected {trigger} trigger count");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_partition_persist() {
+        let idle = new_partition(1, r#"potatoes,city=Hereford people=1 10"#);
+        let active = new_partition(2, r#"potatoes,city=Worcester people=2 10"#);
+
+        let metrics = metric::Registry::default();
+        let persist_handle = Arc::new(MockPersistQueue::default());
+        let triggers = PersistTriggers::new(
+            vec![Arc::clone(&idle), Arc::clone(&active)],
+            Arc::clone(&persist_handle),
+            Some(Duration::from_secs(60)),
+            None,
+            &metrics,
+        );
+
+        // Neither partition has been idle long enough to be persisted.
+        triggers.check().await;
+        assert!(persist_handle.calls().is_empty());
+
+        // Advance time past the idle age, writing to one of the partitions
+        // shortly before evaluating the triggers again.
+        tokio::time::advance(Duration::from_secs(59)).await;
+        active
+            .lock()
+            .buffer_write(
+                lp_to_mutable_batch(r#"potatoes,city=Worcester people=3 20"#).1,
+                SequenceNumber::new(2),
+            )
+            .expect("write should succeed");
+        tokio::time::advance(Duration::from_secs(1)).await;
+
+        triggers.check().await;
+        assert_eq!(persisted_ids(&persist_handle), [PartitionId::new(1)]);
+        assert_trigger_count(&metrics, "idle", 1);
+        assert_trigger_count(&metrics, "watermark", 0);
+
+        // The persisted partition no longer has any buffered data, and is not
+        // persisted again.
+        triggers.check().await;
+        assert_eq!(persisted_ids(&persist_handle), [PartitionId::new(1)]);
+        assert_trigger_count(&metrics, "idle", 1);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_watermark_persist() {
+        let small = new_partition(1, r#"potatoes,city=Hereford people=1 10"#);
+        let large = new_partition(
+            2,
+            "potatoes,city=Worcester people=2,crisps=\"good\" 10\n\
+            potatoes,city=Worcester people=3,crisps=\"fine\" 20\n\
+            potatoes,city=Worcester people=4,crisps=\"bad\" 30",
+        );
+
+        let small_cost = small.lock().persist_cost_estimate();
+        let large_cost = large.lock().persist_cost_estimate();
+        assert!(large_cost > small_cost);
+
+        let metrics = metric::Registry::default();
+        let persist_handle = Arc::new(MockPersistQueue::default());
+
+        // The watermark is exceeded by the combined buffered data, but not by
+        // the small partition alone.
+        let triggers = PersistTriggers::new(
+            vec![Arc::clone(&small), Arc::clone(&large)],
+            Arc::clone(&persist_handle),
+            None,
+            Some(small_cost + large_cost - 1),
+            &metrics,
+        );
+
+        triggers.check().await;
+
+        // Only the largest partition is persisted.
+        assert_eq!(persisted_ids(&persist_handle), [PartitionId::new(2)]);
+        assert_trigger_count(&metrics, "idle", 0);
+        assert_trigger_count(&metrics, "watermark", 1);
+
+        // The buffered data is now below the watermark.
+        triggers.check().await;
+        assert_eq!(persisted_ids(&persist_handle), [PartitionId::new(2)]);
+        assert_trigger_count(&metrics, "watermark", 1);
+
+        // Check persist completion.
+        drop(triggers);
+        Arc::try_unwrap(persist_handle)
+            .expect("should be no more refs")
+            .join()
+            .await;
+        assert_eq!(small.lock().completed_persistence_count(), 0);
+        assert_eq!(large.lock().completed_persistence_count(), 1);
+    }
+}
@@ -1,8 +1,15 @@
-use std::{ops::ControlFlow, sync::Arc};
-
+use std::{collections::HashMap, ops::ControlFlow, sync::Arc};
+
+use arrow::{
+    array::ArrayRef,
+    compute::{is_not_null, kernels::zip::zip},
+    datatypes::{Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
 use async_channel::RecvError;
 use backoff::Backoff;
-use data_types::{CompactionLevel, ParquetFileParams, SequenceNumber};
+use data_types::{ColumnId, CompactionLevel, ParquetFileParams, SequenceNumber, TableSchema};
 use iox_catalog::interface::{get_table_schema_by_id, CasFailure, Catalog};
 use iox_query::exec::Executor;
 use iox_time::{SystemProvider, TimeProvider};
@@ -13,7 +20,7 @@ use schema::sort::SortKey;
 use tokio::{sync::mpsc, time::Instant};
 use uuid::Uuid;
 
-use crate::persist::compact::compact_persisting_batch;
+use crate::{persist::compact::compact_persisting_batch, query_adaptor::QueryAdaptor};
 
 use super::{
     compact::CompactedStream,
@@ -68,6 +75,10 @@ pub(super) struct SharedWorkerState<O> {
 ///             └──────────────┘
 /// ```
 ///
+/// If a column of the parquet file is renamed (or deleted) before the file is
+/// added to the catalog, the persist is restarted, persisting the data under
+/// the new column name.
+///
 /// [`PersistingData`]:
 ///     crate::buffer_tree::partition::persisting::PersistingData
 /// [`PartitionData`]: crate::buffer_tree::partition::PartitionData
@@ -127,16 +138,31 @@ pub(super) async fn run_task<O>(
         // operation; if this update fails due to a concurrent sort key update,
         // the compaction must be redone with the new sort key and uploaded
         // before continuing.
-        let parquet_table_data = loop {
-            match compact_and_upload(&mut ctx, &worker_state).await {
+        //
+        // Then make the newly uploaded parquet file visible to other nodes,
+        // unless one of its columns was concurrently renamed, in which case
+        // the data must be persisted again under the new name.
+        let object_store_id = loop {
+            let res = async {
+                let (parquet_table_data, table_schema) =
+                    compact_and_upload(&mut ctx, &worker_state).await?;
+                update_catalog_parquet(&ctx, &worker_state, parquet_table_data, &table_schema).await
+            }
+            .await;
+
+            match res {
                 Ok(v) => break v,
-                Err(PersistError::ConcurrentSortKeyUpdate(_)) => continue,
+                Err(
+                    PersistError::ConcurrentSortKeyUpdate(_) | PersistError::ConcurrentColumnRename,
+                ) => continue,
+                // As with a compaction failure, the buffered data cannot be
+                // persisted.
+                Err(e @ PersistError::ResolveColumns(_)) => {
+                    panic!("unable to persist partition: {e}")
+                }
             };
         };
 
-        // Make the newly uploaded parquet file visible to other nodes.
-        let object_store_id = update_catalog_parquet(&ctx, &worker_state, parquet_table_data).await;
-
         // And finally mark the persist job as complete and notify any
         // observers.
         ctx.mark_complete(object_store_id, &worker_state.completion_observer)
@@ -149,7 +175,8 @@ pub(super) async fn run_task<O>(
 }
 
 /// Run a compaction on the [`PersistingData`], generate a parquet file and
-/// upload it to object storage.
+/// upload it to object storage, returning its catalog metadata and the table
+/// schema used to resolve its column IDs.
 ///
 /// This function composes functionality from the smaller [`compact()`],
 /// [`upload()`], and [`update_catalog_sort_key()`] functions.
@@ -165,12 +192,14 @@ pub(super) async fn run_task<O>(
 async fn compact_and_upload<O>(
     ctx: &mut Context,
     worker_state: &SharedWorkerState<O>,
-) -> Result<ParquetFileParams, PersistError>
+) -> Result<(ParquetFileParams, TableSchema), PersistError>
 where
     O: Send + Sync,
 {
-    let compacted = compact(ctx, worker_state).await;
-    let (sort_key_update, parquet_table_data) = upload(ctx, worker_state, compacted).await;
+    let (data, table_schema) = resolve_columns(ctx, worker_state).await?;
+    let compacted = compact(ctx, worker_state, data).await;
+    let (sort_key_update, parquet_table_data) =
+        upload(ctx, worker_state, compacted, &table_schema).await;
 
     if let Some(update) = sort_key_update {
         update_catalog_sort_key(
@@ -182,12 +211,138 @@ where
         .await?
     }
 
-    Ok(parquet_table_data)
+    Ok((parquet_table_data, table_schema))
+}
+
+/// Read the table schema from the catalog to act as a map of column name ->
+/// column IDs, and return the data in `ctx` with its columns matching the
+/// catalog.
+///
+/// Each buffered column is resolved by the [`ColumnId`] recorded when its data
+/// was buffered, as the column may have been renamed or soft-deleted since,
+/// and its name may now refer to a different column. The data of a renamed
+/// column is persisted under its current name, and the data of a deleted
+/// column is dropped. A column without a recorded ID (as it did not exist in
+/// the catalog when buffered) is kept only if a column of that name exists.
+/// Columns are never (re-)created in the catalog.
+async fn resolve_columns<O>(
+    ctx: &Context,
+    worker_state: &SharedWorkerState<O>,
+) -> Result<(QueryAdaptor, TableSchema), ArrowError>
+where
+    O: Send + Sync,
+{
+    let table_schema = Backoff::new(&Default::default())
+        .retry_all_errors("get table schema", || async {
+            let mut repos = worker_state.catalog.repositories().await;
+            get_table_schema_by_id(ctx.table_id(), repos.as_mut()).await
+        })
+        .await
+        .expect("retry forever");
+
+    let names_by_id = table_schema
+        .columns
+        .iter()
+        .map(|(name, c)| (c.id, name))
+        .collect::<HashMap<ColumnId, _>>();
+
+    // Map each buffered column whose current name differs to that name, or to
+    // None if it was deleted.
+    let data = ctx.data().query_adaptor();
+    let column_ids = ctx.data().column_ids();
+    let renames = data
+        .record_batches()
+        .iter()
+        .flat_map(|b| {
+            b.schema()
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .collect::<Vec<_>>()
+        })
+        .filter_map(|name| {
+            let current = match column_ids.get(&name) {
+                Some(id) => names_by_id.get(id).map(|v| v.to_string()),
+                None => table_schema
+                    .columns
+                    .contains_key(&name)
+                    .then(|| name.clone()),
+            };
+            (current.as_ref() != Some(&name)).then_some((name, current))
+        })
+        .collect::<HashMap<_, _>>();
+
+    if renames.is_empty() {
+        return Ok((data, table_schema));
+    }
+
+    warn!(
+        table_id = %ctx.table_id(),
+        partition_id = %ctx.partition_id(),
+        ?renames,
+        "persisting columns renamed or deleted since they were buffered"
+    );
+
+    let batches = data
+        .record_batches()
+        .iter()
+        .map(|b| rename_columns(b, &renames).map(Arc::new))
+        .collect::<Result<_, _>>()?;
+
+    Ok((QueryAdaptor::new(ctx.partition_id(), batches), table_schema))
+}
+
+/// Rename the columns of `batch` to the names in `renames`, dropping those
+/// mapped to [`None`].
+///
+/// A column renamed to the name of another column in `batch` (because writes
+/// using both the previous and the current name were buffered) is merged into
+/// it, which fails if the two columns are of different types.
+fn rename_columns(
+    batch: &RecordBatch,
+    renames: &HashMap<String, Option<String>>,
+) -> Result<RecordBatch, ArrowError> {
+    let schema = batch.schema();
+    let mut fields: Vec<Field> = Vec::with_capacity(schema.fields().len());
+    let mut columns: Vec<ArrayRef> = Vec::with_capacity(schema.fields().len());
+
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        let name = match renames.get(field.name()) {
+            None => field.name().clone(),
+            Some(None) => continue,
+            Some(Some(name)) => name.clone(),
+        };
+
+        match fields.iter().position(|f| *f.name() == name) {
+            Some(idx) => {
+                // Each row was written using one of the names, so take the
+                // non-null value.
+                let mask = is_not_null(column.as_ref())?;
+                columns[idx] = zip(&mask, column.as_ref(), columns[idx].as_ref())?;
+            }
+            None => {
+                fields.push(
+                    Field::new(name, field.data_type().clone(), field.is_nullable())
+                        .with_metadata(field.metadata().clone()),
+                );
+                columns.push(Arc::clone(column));
+            }
+        }
+    }
+
+    RecordBatch::try_new(
+        Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone())),
+        columns,
+    )
 }
 
-/// Compact the data in `ctx` using sorted by the sort key returned from
+/// Compact `data` from `ctx` using sorted by the sort key returned from
 /// [`Context::sort_key()`].
-async fn compact<O>(ctx: &Context, worker_state: &SharedWorkerState<O>) -> CompactedStream
+async fn compact<O>(
+    ctx: &Context,
+    worker_state: &SharedWorkerState<O>,
+    data: QueryAdaptor,
+) -> CompactedStream
 where
     O: Send + Sync,
 {
@@ -204,7 +359,7 @@ where
         "compacting partition"
     );
 
-    assert!(!ctx.data().record_batches().is_empty());
+    assert!(!data.record_batches().is_empty());
 
     // Run a compaction sort the data and resolve any duplicate values.
     //
@@ -214,7 +369,7 @@ where
         &worker_state.exec,
         sort_key,
         ctx.table_name().get().await,
-        ctx.data().query_adaptor(),
+        data,
     )
     .await
     .expect("unable to compact persisting batch")
@@ -226,6 +381,7 @@ async fn upload<O>(
     ctx: &Context,
     worker_state: &SharedWorkerState<O>,
     compacted: CompactedStream,
+    table_schema: &TableSchema,
 ) -> (Option<SortKey>, ParquetFileParams)
 where
     O: Send + Sync,
@@ -291,20 +447,14 @@ where
         "partition parquet uploaded"
     );
 
-    // Read the table schema from the catalog to act as a map of column name
-    // -> column IDs.
-    let table_schema = Backoff::new(&Default::default())
-        .retry_all_errors("get table schema", || async {
-            let mut repos = worker_state.catalog.repositories().await;
-            get_table_schema_by_id(ctx.table_id(), repos.as_mut()).await
-        })
-        .await
-        .expect("retry forever");
-
     // Build the data that must be inserted into the parquet_files catalog
     // table in order to make the file visible to queriers.
-    let parquet_table_data =
-        iox_metadata.to_parquet_file(ctx.partition_id(), file_size, &md, |name| {
+    let parquet_table_data = iox_metadata.to_parquet_file(
+        ctx.partition_id(),
+        file_size,
+        &md,
+        ctx.data().max_tombstone_sequence_number(),
+        |name| {
             table_schema
                 .columns
                 .get(name)
@@ -315,7 +465,8 @@ where
                     )
                 })
                 .id
-        });
+        },
+    );
 
     (catalog_sort_key_update, parquet_table_data)
 }
@@ -460,11 +611,20 @@ where
     Ok(())
 }
 
+/// Add the uploaded parquet file described by `parquet_table_data` to the
+/// catalog, returning its object store ID.
+///
+/// The file refers to its columns by the names in `table_schema`, so it is
+/// added in a transaction that locks the columns of the table and checks they
+/// still have those names, returning [`PersistError::ConcurrentColumnRename`]
+/// if not. This prevents a column from being renamed between this check and
+/// the file becoming visible, and so from being renamed once persisted.
 async fn update_catalog_parquet<O>(
     ctx: &Context,
     worker_state: &SharedWorkerState<O>,
     parquet_table_data: ParquetFileParams,
-) -> Uuid
+    table_schema: &TableSchema,
+) -> Result<Uuid, PersistError>
 where
     O: Send + Sync,
 {
@@ -485,17 +645,38 @@ where
         "updating catalog parquet table"
     );
 
+    let names_by_id = table_schema
+        .columns
+        .iter()
+        .map(|(name, c)| (c.id, name.as_str()))
+        .collect::<HashMap<_, _>>();
+
     // Add the parquet file to the catalog.
     //
     // This has the effect of allowing the queriers to "discover" the
     // parquet file by polling / querying the catalog.
-    Backoff::new(&Default::default())
+    let added = Backoff::new(&Default::default())
         .retry_all_errors("add parquet file to catalog", || async {
-            let mut repos = worker_state.catalog.repositories().await;
-            let parquet_file = repos
+            let mut txn = worker_state.catalog.start_transaction().await?;
+
+            let columns = txn.columns().lock_by_table_id(ctx.table_id()).await?;
+            let renamed = parquet_table_data.column_set.iter().any(|id| {
+                columns
+                    .iter()
+                    .find(|c| c.id == *id)
+                    .map(|c| c.name.as_str())
+                    != names_by_id.get(id).copied()
+            });
+            if renamed {
+                txn.abort().await?;
+                return Ok(false);
+            }
+
+            let parquet_file = txn
                 .parquet_files()
                 .create(parquet_table_data.clone())
                 .await?;
+            txn.commit().await?;
 
             debug!(
                 namespace_id = %ctx.namespace_id(),
@@ -511,10 +692,24 @@ where
             );
 
             // compiler insisted on getting told the type of the error :shrug:
-            Ok(()) as Result<(), iox_catalog::interface::Error>
+            Ok(true) as Result<bool, iox_catalog::interface::Error>
         })
         .await
         .expect("retry forever");
 
-    object_store_id
+    if !added {
+        warn!(
+            namespace_id = %ctx.namespace_id(),
+            namespace_name = %ctx.namespace_name(),
+            table_id = %ctx.table_id(),
+            table_name = %ctx.table_name(),
+            partition_id = %ctx.partition_id(),
+            partition_key = %ctx.partition_key(),
+            %object_store_id,
+            "detected concurrent column rename, regenerating parquet"
+        );
+        return Err(PersistError::ConcurrentColumnRename);
+    }
+
+    Ok(object_store_id)
 }
@@ -3,17 +3,22 @@
 
 use std::{any::Any, sync::Arc};
 
-use arrow::record_batch::RecordBatch;
+use arrow::{array::BooleanArray, compute::filter_record_batch, record_batch::RecordBatch};
 use arrow_util::util::ensure_schema;
 use data_types::{ChunkId, ChunkOrder, DeletePredicate, PartitionId, TableSummary};
-use datafusion::error::DataFusionError;
+use datafusion::{
+    error::DataFusionError, logical_expr::Expr, physical_expr::execution_props::ExecutionProps,
+};
+use datafusion_util::create_physical_expr_from_schema;
 use iox_query::{
     exec::{stringset::StringSet, IOxSessionContext},
+    pruning::prune_summaries,
     util::{compute_timenanosecond_min_max, create_basic_summary},
     QueryChunk, QueryChunkData, QueryChunkMeta,
 };
+use observability_deps::tracing::{debug, warn};
 use once_cell::sync::OnceCell;
-use predicate::Predicate;
+use predicate::{delete_predicate::delete_predicate_for_columns, Predicate};
 use schema::{merge::merge_record_batch_schemas, sort::SortKey, Projection, Schema};
 
 /// A queryable wrapper over a set of ordered [`RecordBatch`] snapshot from a
@@ -97,6 +102,118 @@ impl QueryAdaptor {
             .collect()
     }
 
+    /// Apply `predicate` to the data in this [`QueryAdaptor`], returning a new
+    /// [`QueryAdaptor`] containing only the rows that may match it, or [`None`]
+    /// if no rows match.
+    ///
+    /// Only the parts of `predicate` that are safe to evaluate before
+    /// deduplication (the time range and expressions over tag / time columns)
+    /// are applied - the caller remains responsible for applying the full
+    /// predicate to the result.
+    ///
+    /// The column statistics of this [`QueryAdaptor`] are first used to
+    /// discard the data entirely if no row can match, before the remaining
+    /// rows are filtered. If the predicate cannot be evaluated against the
+    /// data, the unfiltered data is returned.
+    pub(crate) fn filter(self, predicate: &Predicate) -> Option<Self> {
+        let predicate = predicate.clone().push_through_dedup(&self.schema);
+        let filter_expr = match predicate.filter_expr() {
+            Some(v) => v,
+            None => return Some(self),
+        };
+
+        // Attempt to prune the whole partition using the statistics of the
+        // buffered data.
+        match prune_summaries(&self.schema, &[self.summary()], &predicate) {
+            Ok(v) if v == [false] => {
+                debug!(partition_id=%self.partition_id, %predicate, "pruned partition data");
+                return None;
+            }
+            Ok(_) => {}
+            Err(reason) => {
+                debug!(partition_id=%self.partition_id, %reason, "cannot prune partition data");
+            }
+        }
+
+        let data = match self.filter_rows(&filter_expr) {
+            Ok(v) => v,
+            Err(e) => {
+                warn!(error=%e, %filter_expr, "cannot apply query predicate to buffered data");
+                return Some(self);
+            }
+        };
+
+        if data.is_empty() {
+            return None;
+        }
+
+        Some(Self::new(self.partition_id, data))
+    }
+
+    /// Remove the rows matching the delete `predicate` from the data in this
+    /// [`QueryAdaptor`], returning the remaining rows, or [`None`] if no rows
+    /// remain.
+    pub(crate) fn delete(
+        self,
+        predicate: &DeletePredicate,
+    ) -> Result<Option<Self>, DataFusionError> {
+        // An equality on a column missing from the data never matches.
+        let predicate = match delete_predicate_for_columns(&Arc::new(predicate.clone()), |c| {
+            self.schema.find_index_of(c).is_some()
+        }) {
+            Some(v) => Predicate::from(v.as_ref().clone()),
+            None => return Ok(Some(self)),
+        };
+        let keep_expr = match Predicate::negated_expr(&[Arc::new(predicate)]) {
+            Some(v) => v,
+            None => return Ok(Some(self)),
+        };
+
+        let data = self.filter_rows(&keep_expr)?;
+        if data.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self::new(self.partition_id, data)))
+    }
+
+    /// Return the non-empty batches of the rows for which `expr` evaluates to
+    /// true.
+    fn filter_rows(&self, expr: &Expr) -> Result<Vec<Arc<RecordBatch>>, DataFusionError> {
+        // Evaluate the filter against the merged schema, ensuring columns
+        // missing from an individual batch are treated as NULL.
+        let schema = self.schema.as_arrow();
+        let physical_expr =
+            create_physical_expr_from_schema(&ExecutionProps::new(), expr, &schema)?;
+
+        let filtered = self
+            .data
+            .iter()
+            .map(|batch| {
+                let mask = physical_expr
+                    .evaluate(&ensure_schema(&schema, batch)?)?
+                    .into_array(batch.num_rows());
+                let mask = mask
+                    .as_any()
+                    .downcast_ref::<BooleanArray>()
+                    .ok_or_else(|| {
+                        DataFusionError::Internal(
+                            "filter predicate evaluated to non-boolean value".to_string(),
+                        )
+                    })?;
+
+                // Apply the mask to the original batch to preserve its schema.
+                Ok(filter_record_batch(batch, mask)?)
+            })
+            .collect::<Result<Vec<_>, DataFusionError>>()?;
+
+        Ok(filtered
+            .into_iter()
+            .filter(|b| b.num_rows() > 0)
+            .map(Arc::new)
+            .collect())
+    }
+
     /// Returns the [`RecordBatch`] instances in this [`QueryAdaptor`].
     pub(crate) fn record_batches(&self) -> &[Arc<RecordBatch>] {
         self.data.as_ref()
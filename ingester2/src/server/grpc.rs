@@ -10,6 +10,7 @@ use iox_catalog::interface::Catalog;
 use service_grpc_catalog::CatalogService;
 
 use crate::{
+    column_id_cache::ColumnIdCache,
     dml_sink::DmlSink,
     ingest_state::IngestState,
     ingester_id::IngesterId,
@@ -44,7 +45,7 @@ impl<D, Q, T, P> GrpcDelegate<D, Q, T, P>
 where
     D: DmlSink + 'static,
     Q: QueryExec<Response = QueryResponse> + 'static,
-    T: PartitionIter + Sync + 'static,
+    T: PartitionIter + ColumnIdCache + Sync + 'static,
     P: PersistQueue + Sync + 'static,
 {
     /// Initialise a new [`GrpcDelegate`].
@@ -80,11 +81,11 @@ impl<D, Q, T, P> IngesterRpcInterface for GrpcDelegate<D, Q, T, P>
 where
     D: DmlSink + 'static,
     Q: QueryExec<Response = QueryResponse> + 'static,
-    T: PartitionIter + Sync + 'static,
+    T: PartitionIter + ColumnIdCache + Sync + 'static,
     P: PersistQueue + Sync + 'static,
 {
     type CatalogHandler = CatalogService;
-    type WriteHandler = RpcWrite<Arc<D>>;
+    type WriteHandler = RpcWrite<Arc<D>, Arc<T>>;
     type PersistHandler = PersistHandler<Arc<T>, Arc<P>>;
     type FlightHandler = query::FlightService<Arc<Q>>;
 
@@ -101,6 +102,7 @@ where
     fn write_service(&self) -> Self::WriteHandler {
         RpcWrite::new(
             Arc::clone(&self.dml_sink),
+            Arc::clone(&self.buffer),
             Arc::clone(&self.timestamp),
             Arc::clone(&self.ingest_state),
         )
@@ -9,9 +9,13 @@ use arrow_flight::{
 use data_types::{NamespaceId, PartitionId, TableId};
 use flatbuffers::FlatBufferBuilder;
 use futures::{Stream, StreamExt, TryStreamExt};
-use generated_types::influxdata::iox::ingester::v1::{self as proto, PartitionStatus};
+use generated_types::{
+    google::FieldViolation,
+    influxdata::iox::ingester::v1::{self as proto, PartitionStatus},
+};
 use metric::U64Counter;
 use observability_deps::tracing::*;
+use predicate::Predicate;
 use prost::Message;
 use thiserror::Error;
 use tokio::sync::{Semaphore, TryAcquireError};
@@ -39,6 +43,11 @@ enum Error {
     #[error("invalid flight ticket: {0}")]
     InvalidTicket(#[from] prost::DecodeError),
 
+    /// The query predicate within the [`proto::IngesterQueryRequest`] cannot
+    /// be deserialised into a [`Predicate`].
+    #[error("invalid query predicate: {0}")]
+    InvalidPredicate(FieldViolation),
+
     /// The number of simultaneous queries being executed has been reached.
     #[error("simultaneous query limit exceeded")]
     RequestLimit,
@@ -67,6 +76,10 @@ impl From<Error> for tonic::Status {
                 debug!(error=%e, "invalid flight query ticket");
                 Code::InvalidArgument
             }
+            Error::InvalidPredicate(_) => {
+                debug!(error=%e, "invalid flight query predicate");
+                Code::InvalidArgument
+            }
             Error::RequestLimit => {
                 warn!("simultaneous query limit exceeded");
                 Code::ResourceExhausted
@@ -172,14 +185,16 @@ where
         let namespace_id = NamespaceId::new(request.namespace_id);
         let table_id = TableId::new(request.table_id);
 
-        // Predicate pushdown is part of the API, but not implemented.
-        if let Some(p) = request.predicate {
-            warn!(predicate=?p, "ignoring query predicate (unsupported)");
-        }
+        // Decode the (optional) predicate to be pushed down into the buffer.
+        let predicate = request
+            .predicate
+            .map(Predicate::try_from)
+            .transpose()
+            .map_err(Error::InvalidPredicate)?;
 
         let response = match self
             .query_handler
-            .query_exec(namespace_id, table_id, request.columns, span)
+            .query_exec(namespace_id, table_id, request.columns, span, predicate)
             .await
         {
             Ok(v) => v,
@@ -375,4 +390,36 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn rejects_invalid_predicate() {
+        let flight = FlightService::new(
+            MockQueryExec::default(),
+            IngesterId::new(),
+            100,
+            &metric::Registry::default(),
+        );
+
+        let request = proto::IngesterQueryRequest {
+            namespace_id: 42,
+            table_id: 24,
+            columns: vec![],
+            predicate: Some(proto::Predicate {
+                field_columns: vec![],
+                range: None,
+                exprs: vec![vec![0xDE, 0xAD, 0xBE, 0xEF]],
+                value_expr: vec![],
+            }),
+        };
+
+        let req = tonic::Request::new(Ticket {
+            ticket: request.encode_to_vec().into(),
+        });
+        match flight.do_get(req).await {
+            Ok(_) => panic!("expected error because of invalid predicate"),
+            Err(s) => {
+                assert_eq!(s.code(), Code::InvalidArgument);
+            }
+        }
+    }
 }
@@ -1,9 +1,10 @@
 use std::sync::Arc;
 
-use data_types::{NamespaceId, PartitionKey, Sequence, TableId};
-use dml::{DmlMeta, DmlOperation, DmlWrite};
-use generated_types::influxdata::iox::ingester::v1::{
-    self as proto, write_service_server::WriteService,
+use data_types::{NamespaceId, PartitionKey, Sequence, SequenceNumber, TableId};
+use dml::{DmlDelete, DmlMeta, DmlOperation, DmlWrite};
+use generated_types::{
+    google::{FieldViolation, FromOptionalField},
+    influxdata::iox::ingester::v1::{self as proto, write_service_server::WriteService},
 };
 use mutable_batch::writer;
 use mutable_batch_pb::decode::decode_database_batch;
@@ -12,6 +13,7 @@ use thiserror::Error;
 use tonic::{Code, Request, Response};
 
 use crate::{
+    column_id_cache::ColumnIdCache,
     dml_sink::{DmlError, DmlSink},
     ingest_state::{IngestState, IngestStateError},
     timestamp_oracle::TimestampOracle,
@@ -38,6 +40,10 @@ enum RpcError {
     #[error(transparent)]
     Decode(mutable_batch_pb::decode::Error),
 
+    /// The delete payload does not contain a valid predicate.
+    #[error(transparent)]
+    InvalidDelete(FieldViolation),
+
     /// The ingester's [`IngestState`] returns [`IngestStateError`] instances if
     /// set by a subsystem. See [`IngestState`] for documentation.
     #[error(transparent)]
@@ -47,7 +53,10 @@ enum RpcError {
 impl From<RpcError> for tonic::Status {
     fn from(e: RpcError) -> Self {
         let code = match e {
-            RpcError::Decode(_) | RpcError::NoPayload | RpcError::NoTables => Code::InvalidArgument,
+            RpcError::Decode(_)
+            | RpcError::InvalidDelete(_)
+            | RpcError::NoPayload
+            | RpcError::NoTables => Code::InvalidArgument,
             RpcError::SystemState(IngestStateError::PersistSaturated) => Code::ResourceExhausted,
             RpcError::SystemState(IngestStateError::GracefulStop) => Code::FailedPrecondition,
         };
@@ -99,22 +108,26 @@ fn map_write_error(e: mutable_batch::Error) -> tonic::Status {
 /// This handler accepts writes from an upstream, and applies them to the
 /// provided [`DmlSink`].
 #[derive(Debug)]
-pub(crate) struct RpcWrite<T> {
+pub(crate) struct RpcWrite<T, C> {
     sink: T,
+    column_ids: C,
     timestamp: Arc<TimestampOracle>,
     ingest_state: Arc<IngestState>,
 }
 
-impl<T> RpcWrite<T> {
+impl<T, C> RpcWrite<T, C> {
     /// Instantiate a new [`RpcWrite`] that pushes [`DmlOperation`] instances
-    /// into `sink`.
+    /// into `sink`, and discards the column IDs cached by `column_ids` when
+    /// requested.
     pub(crate) fn new(
         sink: T,
+        column_ids: C,
         timestamp: Arc<TimestampOracle>,
         ingest_state: Arc<IngestState>,
     ) -> Self {
         Self {
             sink,
+            column_ids,
             timestamp,
             ingest_state,
         }
@@ -122,9 +135,10 @@ impl<T> RpcWrite<T> {
 }
 
 #[tonic::async_trait]
-impl<T> WriteService for RpcWrite<T>
+impl<T, C> WriteService for RpcWrite<T, C>
 where
     T: DmlSink + 'static,
+    C: ColumnIdCache + 'static,
 {
     /// Handle an RPC write request.
     async fn write(
@@ -212,6 +226,77 @@ where
 
         Ok(Response::new(proto::WriteResponse {}))
     }
+
+    /// Handle an RPC delete request.
+    ///
+    /// The delete is applied in order with the writes handled by this
+    /// ingester: rows buffered before the delete are removed, while rows
+    /// written afterwards are not.
+    async fn delete(
+        &self,
+        request: Request<proto::DeleteRequest>,
+    ) -> Result<Response<proto::DeleteResponse>, tonic::Status> {
+        self.ingest_state.read().map_err(RpcError::SystemState)?;
+
+        let payload = request.into_inner().payload.ok_or(RpcError::NoPayload)?;
+        let predicate = payload
+            .predicate
+            .required("predicate")
+            .map_err(RpcError::InvalidDelete)?;
+        let namespace_id = NamespaceId::new(payload.database_id);
+        let table_id = TableId::new(payload.table_id);
+        let tombstone_sequence_number = SequenceNumber::new(payload.tombstone_sequence_number);
+
+        trace!(
+            %namespace_id,
+            %table_id,
+            tombstone_sequence_number = tombstone_sequence_number.get(),
+            "received rpc delete"
+        );
+
+        let op = DmlDelete::new(
+            namespace_id,
+            table_id,
+            predicate,
+            tombstone_sequence_number,
+            DmlMeta::sequenced(
+                Sequence {
+                    shard_index: TRANSITION_SHARD_INDEX, // TODO: remove this from DmlMeta
+                    sequence_number: self.timestamp.next(),
+                },
+                iox_time::Time::MAX, // TODO: remove this from DmlMeta
+                None,
+                42, // TODO: remove this from DmlMeta
+            ),
+        );
+
+        if let Err(e) = self.sink.apply(DmlOperation::Delete(op)).await {
+            error!(error=%e, "failed to apply DML op");
+            return Err(e.into());
+        }
+
+        Ok(Response::new(proto::DeleteResponse {}))
+    }
+
+    /// Handle an RPC request to discard the cached column IDs of a table.
+    ///
+    /// This is accepted regardless of the ingest state, as it does not buffer
+    /// any data.
+    async fn invalidate_columns(
+        &self,
+        request: Request<proto::InvalidateColumnsRequest>,
+    ) -> Result<Response<proto::InvalidateColumnsResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let namespace_id = NamespaceId::new(request.namespace_id);
+        let table_id = TableId::new(request.table_id);
+
+        debug!(%namespace_id, %table_id, "invalidating cached column ids");
+
+        self.column_ids
+            .invalidate_column_ids(namespace_id, table_id);
+
+        Ok(Response::new(proto::InvalidateColumnsResponse {}))
+    }
 }
 
 #[cfg(test)]
@@ -219,13 +304,17 @@ mod tests {
     use std::sync::Arc;
 
     use assert_matches::assert_matches;
-    use generated_types::influxdata::pbdata::v1::{
-        column::{SemanticType, Values},
-        Column, DatabaseBatch, TableBatch,
+    use data_types::{DeletePredicate, TimestampRange};
+    use generated_types::influxdata::{
+        iox::delete::v1::DeletePayload,
+        pbdata::v1::{
+            column::{SemanticType, Values},
+            Column, DatabaseBatch, TableBatch,
+        },
     };
 
     use super::*;
-    use crate::dml_sink::mock_sink::MockDmlSink;
+    use crate::{column_id_cache::mock::MockColumnIdCache, dml_sink::mock_sink::MockDmlSink};
 
     const NAMESPACE_ID: NamespaceId = NamespaceId::new(42);
     const PARTITION_KEY: &str = "bananas";
@@ -249,7 +338,12 @@ mod tests {
 
                     let ingest_state = Arc::new(IngestState::default());
 
-                    let handler = RpcWrite::new(Arc::clone(&mock), timestamp, ingest_state);
+                    let handler = RpcWrite::new(
+                        Arc::clone(&mock),
+                        Arc::new(MockColumnIdCache::default()),
+                        timestamp,
+                        ingest_state,
+                    );
 
                     let ret = handler
                         .write(Request::new($request))
@@ -363,7 +457,12 @@ mod tests {
 
         let ingest_state = Arc::new(IngestState::default());
 
-        let handler = RpcWrite::new(Arc::clone(&mock), timestamp, ingest_state);
+        let handler = RpcWrite::new(
+            Arc::clone(&mock),
+            Arc::new(MockColumnIdCache::default()),
+            timestamp,
+            ingest_state,
+        );
 
         let req = proto::WriteRequest {
             payload: Some(DatabaseBatch {
@@ -411,6 +510,112 @@ mod tests {
         );
     }
 
+    /// Deletes are applied to the sink as a sequenced [`DmlDelete`].
+    #[tokio::test]
+    async fn test_rpc_delete() {
+        let mock = Arc::new(MockDmlSink::default().with_apply_return(vec![Ok(())]));
+        let timestamp = Arc::new(TimestampOracle::new(0));
+
+        let ingest_state = Arc::new(IngestState::default());
+
+        let handler = RpcWrite::new(
+            Arc::clone(&mock),
+            Arc::new(MockColumnIdCache::default()),
+            timestamp,
+            ingest_state,
+        );
+
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(1, 10),
+            exprs: vec![],
+        };
+        handler
+            .delete(Request::new(proto::DeleteRequest {
+                payload: Some(DeletePayload {
+                    database_id: NAMESPACE_ID.get(),
+                    table_name: String::new(),
+                    predicate: Some(predicate.clone().into()),
+                    table_id: 24,
+                    tombstone_sequence_number: 3,
+                }),
+            }))
+            .await
+            .expect("delete should succeed");
+
+        assert_matches!(
+            mock.get_calls().as_slice(),
+            [DmlOperation::Delete(d)] => {
+                assert_eq!(d.namespace_id(), NAMESPACE_ID);
+                assert_eq!(d.table_id(), TableId::new(24));
+                assert_eq!(d.predicate(), &predicate);
+                assert_eq!(d.tombstone_sequence_number(), SequenceNumber::new(3));
+                assert_eq!(d.meta().sequence().unwrap().sequence_number.get(), 1);
+            }
+        );
+    }
+
+    /// Column invalidation requests are passed to the column ID cache, even
+    /// when writes are rejected.
+    #[tokio::test]
+    async fn test_rpc_invalidate_columns() {
+        let mock = Arc::new(MockDmlSink::default());
+        let column_ids = Arc::new(MockColumnIdCache::default());
+        let timestamp = Arc::new(TimestampOracle::new(0));
+
+        let ingest_state = Arc::new(IngestState::default());
+        ingest_state.set(IngestStateError::PersistSaturated);
+
+        let handler = RpcWrite::new(
+            Arc::clone(&mock),
+            Arc::clone(&column_ids),
+            timestamp,
+            ingest_state,
+        );
+
+        handler
+            .invalidate_columns(Request::new(proto::InvalidateColumnsRequest {
+                namespace_id: NAMESPACE_ID.get(),
+                table_id: 24,
+            }))
+            .await
+            .expect("invalidation should succeed");
+
+        assert_eq!(column_ids.calls(), [(NAMESPACE_ID, TableId::new(24))]);
+        assert!(mock.get_calls().is_empty());
+    }
+
+    /// A delete without a predicate is rejected.
+    #[tokio::test]
+    async fn test_rpc_delete_no_predicate() {
+        let mock = Arc::new(MockDmlSink::default());
+        let timestamp = Arc::new(TimestampOracle::new(0));
+
+        let ingest_state = Arc::new(IngestState::default());
+
+        let handler = RpcWrite::new(
+            Arc::clone(&mock),
+            Arc::new(MockColumnIdCache::default()),
+            timestamp,
+            ingest_state,
+        );
+
+        let err = handler
+            .delete(Request::new(proto::DeleteRequest {
+                payload: Some(DeletePayload {
+                    database_id: NAMESPACE_ID.get(),
+                    table_name: String::new(),
+                    predicate: None,
+                    table_id: 24,
+                    tombstone_sequence_number: 3,
+                }),
+            }))
+            .await
+            .expect_err("delete without a predicate should fail");
+
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(mock.get_calls().is_empty());
+    }
+
     /// Validate that the persist system being marked as saturated prevents the
     /// ingester from accepting new writes.
     #[tokio::test]
@@ -420,7 +625,12 @@ mod tests {
 
         let ingest_state = Arc::new(IngestState::default());
 
-        let handler = RpcWrite::new(Arc::clone(&mock), timestamp, Arc::clone(&ingest_state));
+        let handler = RpcWrite::new(
+            Arc::clone(&mock),
+            Arc::new(MockColumnIdCache::default()),
+            timestamp,
+            Arc::clone(&ingest_state),
+        );
 
         let req = proto::WriteRequest {
             payload: Some(DatabaseBatch {
@@ -476,7 +686,12 @@ mod tests {
 
         let ingest_state = Arc::new(IngestState::default());
 
-        let handler = RpcWrite::new(Arc::clone(&mock), timestamp, Arc::clone(&ingest_state));
+        let handler = RpcWrite::new(
+            Arc::clone(&mock),
+            Arc::new(MockColumnIdCache::default()),
+            timestamp,
+            Arc::clone(&ingest_state),
+        );
 
         let req = proto::WriteRequest {
             payload: Some(DatabaseBatch {
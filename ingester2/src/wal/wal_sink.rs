@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use dml::DmlOperation;
-use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op;
+use generated_types::influxdata::iox::{delete::v1::DeletePayload, wal::v1::sequenced_wal_op::Op};
 use mutable_batch_pb::encode::encode_write;
 use std::sync::Arc;
 use tokio::sync::watch::Receiver;
@@ -89,7 +89,13 @@ impl WalAppender for Arc<wal::Wal> {
 
         let wal_op = match op {
             DmlOperation::Write(w) => Op::Write(encode_write(namespace_id.get(), w)),
-            DmlOperation::Delete(_) => unreachable!(),
+            DmlOperation::Delete(d) => Op::Delete(DeletePayload {
+                database_id: namespace_id.get(),
+                table_name: String::new(),
+                predicate: Some(d.predicate().clone().into()),
+                table_id: d.table_id().get(),
+                tombstone_sequence_number: d.tombstone_sequence_number().get(),
+            }),
         };
 
         self.write_op(SequencedWalOp {
@@ -0,0 +1,12 @@
+-- Add an optional partition template, serialised as JSON, to the "namespace"
+-- and "table_name" tables. NULL means the template of the parent (or the
+-- router's default template) is used.
+ALTER TABLE
+    namespace
+ADD
+    COLUMN partition_template TEXT DEFAULT NULL;
+
+ALTER TABLE
+    table_name
+ADD
+    COLUMN partition_template TEXT DEFAULT NULL;
@@ -0,0 +1,11 @@
+-- Add optional per-namespace write rate limits, enforced by the router. NULL
+-- means the namespace writes are not rate limited.
+ALTER TABLE
+    namespace
+ADD
+    COLUMN max_lines_per_second BIGINT DEFAULT NULL;
+
+ALTER TABLE
+    namespace
+ADD
+    COLUMN max_bytes_per_second BIGINT DEFAULT NULL;
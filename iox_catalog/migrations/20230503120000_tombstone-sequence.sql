@@ -0,0 +1,24 @@
+-- Allocate tombstone sequence numbers from a per-table counter.
+--
+-- Computing the next sequence number from the maximum of the existing
+-- tombstones races with concurrent deletes of the same table. Incrementing a
+-- counter row is atomic, so every tombstone gets a distinct sequence number.
+CREATE TABLE IF NOT EXISTS tombstone_sequence (
+    table_id BIGINT NOT NULL PRIMARY KEY,
+    sequence_number BIGINT NOT NULL
+);
+
+ALTER TABLE
+    IF EXISTS tombstone_sequence
+ADD
+    CONSTRAINT tombstone_sequence_table_id_fkey FOREIGN KEY (table_id) REFERENCES table_name (id) ON DELETE CASCADE;
+
+INSERT INTO
+    tombstone_sequence (table_id, sequence_number)
+SELECT
+    table_id,
+    MAX(sequence_number)
+FROM
+    tombstone
+GROUP BY
+    table_id;
@@ -0,0 +1,14 @@
+-- Only enforce unique column names within a table for columns that are not
+-- soft-deleted.
+--
+-- A column created with the name of a soft-deleted column gets a new record
+-- (and ID) rather than reviving the deleted one, so it may also have a
+-- different type.
+ALTER TABLE
+    column_name
+DROP
+    CONSTRAINT IF EXISTS column_name_unique;
+
+CREATE UNIQUE INDEX IF NOT EXISTS column_name_unique ON column_name (table_id, name)
+WHERE
+    deleted_at IS NULL;
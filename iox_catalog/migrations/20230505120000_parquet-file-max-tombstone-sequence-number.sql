@@ -0,0 +1,9 @@
+-- Record the sequence number of the most recent tombstone of the table applied
+-- to the rows of each parquet file.
+--
+-- Tombstones with a greater sequence number are applied to the file. Existing
+-- files default to 0, so every tombstone of their table applies to them.
+ALTER TABLE
+    parquet_file
+ADD
+    COLUMN max_tombstone_sequence_number BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,13 @@
+-- Add soft-deletion timestamps to the "table_name" and "column_name" tables.
+--
+-- Soft-deleted tables and columns are hidden from the catalog and do not count
+-- against the namespace's table and column limits.
+ALTER TABLE
+    table_name
+ADD
+    COLUMN deleted_at numeric DEFAULT NULL;
+
+ALTER TABLE
+    column_name
+ADD
+    COLUMN deleted_at numeric DEFAULT NULL;
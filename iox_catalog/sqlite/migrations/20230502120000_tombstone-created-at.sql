@@ -0,0 +1,9 @@
+-- Record when each tombstone was created.
+--
+-- A tombstone only applies to rows written before it was created. Existing
+-- tombstones default to 0, and keep applying to the parquet files they have
+-- not been processed for.
+ALTER TABLE
+    tombstone
+ADD
+    COLUMN created_at numeric NOT NULL DEFAULT 0;
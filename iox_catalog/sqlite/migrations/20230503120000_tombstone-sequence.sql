@@ -0,0 +1,19 @@
+-- Allocate tombstone sequence numbers from a per-table counter.
+--
+-- Computing the next sequence number from the maximum of the existing
+-- tombstones races with concurrent deletes of the same table. Incrementing a
+-- counter row is atomic, so every tombstone gets a distinct sequence number.
+create table if not exists tombstone_sequence
+(
+    table_id        numeric not null
+        constraint tombstone_sequence_pkey
+            primary key
+        references table_name
+            on delete cascade,
+    sequence_number numeric not null
+);
+
+insert into tombstone_sequence (table_id, sequence_number)
+select table_id, max(sequence_number)
+from tombstone
+group by table_id;
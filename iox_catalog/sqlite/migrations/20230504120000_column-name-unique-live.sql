@@ -0,0 +1,35 @@
+-- Only enforce unique column names within a table for columns that are not
+-- soft-deleted.
+--
+-- A column created with the name of a soft-deleted column gets a new record
+-- (and ID) rather than reviving the deleted one, so it may also have a
+-- different type.
+--
+-- SQLite cannot drop a table constraint, so the table is rebuilt without it.
+create table if not exists column_name_new
+(
+    id          INTEGER
+        constraint column_name_pkey
+            primary key autoincrement,
+    table_id    numeric  not null
+        references table_name
+            on delete cascade,
+    name        varchar  not null,
+    column_type smallint not null,
+    deleted_at  numeric default null
+);
+
+insert into column_name_new (id, table_id, name, column_type, deleted_at)
+select id, table_id, name, column_type, deleted_at
+from column_name;
+
+drop table column_name;
+
+alter table column_name_new rename to column_name;
+
+create index if not exists column_name_table_idx
+    on column_name (table_id);
+
+create unique index if not exists column_name_unique
+    on column_name (table_id, name)
+    where deleted_at is null;
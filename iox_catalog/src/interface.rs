@@ -2,10 +2,11 @@
 
 use async_trait::async_trait;
 use data_types::{
-    Column, ColumnSchema, ColumnType, CompactionLevel, Namespace, NamespaceId, NamespaceSchema,
-    ParquetFile, ParquetFileId, ParquetFileParams, Partition, PartitionId, PartitionKey,
-    PartitionParam, QueryPool, QueryPoolId, SequenceNumber, Shard, ShardId, ShardIndex,
-    SkippedCompaction, Table, TableId, TableSchema, Timestamp, TopicId, TopicMetadata,
+    Column, ColumnId, ColumnSchema, ColumnType, CompactionLevel, Namespace, NamespaceId,
+    NamespaceSchema, ParquetFile, ParquetFileId, ParquetFileParams, Partition, PartitionId,
+    PartitionKey, PartitionParam, PartitionTemplate, QueryPool, QueryPoolId, SequenceNumber, Shard,
+    ShardId, ShardIndex, SkippedCompaction, Table, TableId, TableSchema, Timestamp, Tombstone,
+    TopicId, TopicMetadata,
 };
 use iox_time::TimeProvider;
 use snafu::{OptionExt, Snafu};
@@ -64,6 +65,9 @@ pub enum Error {
     #[snafu(display("table {} not found", id))]
     TableNotFound { id: TableId },
 
+    #[snafu(display("column {} not found", id.get()))]
+    ColumnNotFound { id: ColumnId },
+
     #[snafu(display("partition {} not found", id))]
     PartitionNotFound { id: PartitionId },
 
@@ -130,6 +134,12 @@ pub enum Error {
 
     #[snafu(display("could not delete namespace: {source}"))]
     CouldNotDeleteNamespace { source: sqlx::Error },
+
+    #[snafu(display("could not delete table: {source}"))]
+    CouldNotDeleteTable { source: sqlx::Error },
+
+    #[snafu(display("could not delete column: {source}"))]
+    CouldNotDeleteColumn { source: sqlx::Error },
 }
 
 /// A specialized `Error` for Catalog errors
@@ -305,6 +315,9 @@ pub trait RepoCollection: Send + Sync + Debug {
 
     /// Repository for [Parquet files](data_types::ParquetFile).
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo;
+
+    /// Repository for [tombstones](data_types::Tombstone).
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo;
 }
 
 /// Functions for working with topics in the catalog.
@@ -345,6 +358,14 @@ pub trait NamespaceRepo: Send + Sync {
         retention_period_ns: Option<i64>,
     ) -> Result<Namespace>;
 
+    /// Update the partition template used for the tables of a namespace.
+    /// Specify `None` to use the default partition template.
+    async fn update_partition_template(
+        &mut self,
+        name: &str,
+        partition_template: Option<PartitionTemplate>,
+    ) -> Result<Namespace>;
+
     /// List all namespaces.
     async fn list(&mut self, deleted: SoftDeletedRows) -> Result<Vec<Namespace>>;
 
@@ -370,6 +391,22 @@ pub trait NamespaceRepo: Send + Sync {
 
     /// Update the limit on the number of columns that can exist per table in a given namespace.
     async fn update_column_limit(&mut self, name: &str, new_max: i32) -> Result<Namespace>;
+
+    /// Update the limit on the number of lines per second that can be written to a namespace.
+    /// Specify `None` to remove the limit.
+    async fn update_lines_per_second_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace>;
+
+    /// Update the limit on the number of bytes per second that can be written to a namespace.
+    /// Specify `None` to remove the limit.
+    async fn update_bytes_per_second_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace>;
 }
 
 /// Functions for working with tables in the catalog
@@ -378,21 +415,47 @@ pub trait TableRepo: Send + Sync {
     /// Creates the table in the catalog or get the existing record by name.
     async fn create_or_get(&mut self, name: &str, namespace_id: NamespaceId) -> Result<Table>;
 
-    /// get table by ID
+    /// Creates the table in the catalog with the given partition template. If one by the same
+    /// name already exists in the namespace, an error is returned.
+    /// Specify `None` for `partition_template` to use the template of the namespace.
+    async fn create(
+        &mut self,
+        name: &str,
+        partition_template: Option<PartitionTemplate>,
+        namespace_id: NamespaceId,
+    ) -> Result<Table>;
+
+    /// get table by ID, including soft-deleted tables.
     async fn get_by_id(&mut self, table_id: TableId) -> Result<Option<Table>>;
 
-    /// get table by namespace ID and name
+    /// get table by namespace ID and name, ignoring soft-deleted tables.
     async fn get_by_namespace_and_name(
         &mut self,
         namespace_id: NamespaceId,
         name: &str,
     ) -> Result<Option<Table>>;
 
-    /// Lists all tables in the catalog for the given namespace id.
+    /// Lists all tables in the catalog for the given namespace id, ignoring soft-deleted
+    /// tables.
     async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Table>>;
 
-    /// List all tables.
+    /// List all tables, ignoring soft-deleted tables.
     async fn list(&mut self) -> Result<Vec<Table>>;
+
+    /// Soft-delete a table by ID.
+    ///
+    /// A soft-deleted table no longer counts towards the namespace table limit, and is hidden
+    /// from all lookups other than [`TableRepo::get_by_id`]. All parquet files of the table are
+    /// flagged for deletion, so creating a table with the same name afterwards revives the
+    /// existing record without its data.
+    async fn soft_delete(&mut self, table_id: TableId) -> Result<()>;
+
+    /// Rename the table with the given ID to `new_name`, returning the updated record.
+    ///
+    /// Returns `Error::NameExists` if another table in the namespace already uses `new_name`
+    /// (including a soft-deleted table), and `Error::TableNotFound` if the table does not exist
+    /// or is soft-deleted. The previous name is free to be used by a new table afterwards.
+    async fn rename(&mut self, table_id: TableId, new_name: &str) -> Result<Table>;
 }
 
 /// Functions for working with columns in the catalog
@@ -423,14 +486,39 @@ pub trait ColumnRepo: Send + Sync {
         columns: HashMap<&str, ColumnType>,
     ) -> Result<Vec<Column>>;
 
-    /// Lists all columns in the passed in namespace id.
+    /// Lists all columns in the passed in namespace id, ignoring soft-deleted columns and the
+    /// columns of soft-deleted tables.
     async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Column>>;
 
-    /// List all columns for the given table ID.
+    /// List all columns for the given table ID, ignoring soft-deleted columns.
     async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Column>>;
 
-    /// List all columns.
+    /// List all columns for the given table ID, ignoring soft-deleted columns, and prevent them
+    /// from being renamed or soft-deleted by another transaction until this transaction ends.
+    ///
+    /// Used to check that the columns have the expected names when committing data that refers
+    /// to them by name.
+    async fn lock_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Column>>;
+
+    /// List all columns, ignoring soft-deleted columns and the columns of soft-deleted tables.
     async fn list(&mut self) -> Result<Vec<Column>>;
+
+    /// Soft-delete a column by ID.
+    ///
+    /// A soft-deleted column no longer counts towards the per-table column limit and is hidden
+    /// from all lookups. Creating a column with the same name afterwards creates a new record
+    /// with a new ID, of any type.
+    async fn soft_delete(&mut self, column_id: ColumnId) -> Result<()>;
+
+    /// Rename the column with the given ID to `new_name`, returning the updated record.
+    ///
+    /// Returns `Error::NameExists` if another column in the table already uses `new_name`
+    /// (ignoring soft-deleted columns), and `Error::ColumnNotFound` if the column does not
+    /// exist or is soft-deleted. The previous name is free to be used by a new column afterwards.
+    ///
+    /// Only the catalog record is changed - parquet files and partition sort keys that refer to
+    /// the column by its previous name are NOT rewritten.
+    async fn rename(&mut self, column_id: ColumnId, new_name: &str) -> Result<Column>;
 }
 
 /// Functions for working with shards in the catalog
@@ -566,6 +654,9 @@ pub trait ParquetFileRepo: Send + Sync {
     /// Flag all parquet files for deletion that are older than their namespace's retention period.
     async fn flag_for_delete_by_retention(&mut self) -> Result<Vec<ParquetFileId>>;
 
+    /// Flag all parquet files for deletion that belong to a soft-deleted table.
+    async fn flag_for_delete_by_deleted_tables(&mut self) -> Result<Vec<ParquetFileId>>;
+
     /// List all parquet files within a given namespace that are NOT marked as
     /// [`to_delete`](ParquetFile::to_delete).
     async fn list_by_namespace_not_to_delete(
@@ -618,6 +709,27 @@ pub trait ParquetFileRepo: Send + Sync {
     ) -> Result<Option<ParquetFile>>;
 }
 
+/// Functions for working with tombstones (predicate deletes) in the catalog
+#[async_trait]
+pub trait TombstoneRepo: Send + Sync {
+    /// Record a delete of all rows in the table that fall within `[min_time, max_time]` and match
+    /// the serialized `predicate`.
+    ///
+    /// The catalog assigns the tombstone the next sequence number for the table, and records the
+    /// time it was created.
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        shard_id: ShardId,
+        min_time: Timestamp,
+        max_time: Timestamp,
+        predicate: &str,
+    ) -> Result<Tombstone>;
+
+    /// List all tombstones for the given table, ordered by sequence number.
+    async fn list_by_table(&mut self, table_id: TableId) -> Result<Vec<Tombstone>>;
+}
+
 /// Gets the namespace schema including all tables and columns.
 pub async fn get_schema_by_id<R>(
     id: NamespaceId,
@@ -662,7 +774,7 @@ where
     let columns = repos.columns().list_by_namespace_id(namespace.id).await?;
     let tables = repos.tables().list_by_namespace_id(namespace.id).await?;
 
-    let mut namespace = NamespaceSchema::new(
+    let mut namespace_schema = NamespaceSchema::new(
         namespace.id,
         namespace.topic_id,
         namespace.query_pool_id,
@@ -670,14 +782,22 @@ where
         namespace.max_tables,
         namespace.retention_period_ns,
     );
+    namespace_schema.partition_template = namespace.partition_template;
+    namespace_schema.max_lines_per_second = namespace.max_lines_per_second.map(|v| v as u64);
+    namespace_schema.max_bytes_per_second = namespace.max_bytes_per_second.map(|v| v as u64);
 
     let mut table_id_to_schema = BTreeMap::new();
     for t in tables {
-        table_id_to_schema.insert(t.id, (t.name, TableSchema::new(t.id)));
+        let schema = TableSchema::new_for_table(&t);
+        table_id_to_schema.insert(t.id, (t.name, schema));
     }
 
     for c in columns {
-        let (_, t) = table_id_to_schema.get_mut(&c.table_id).unwrap();
+        // Skip columns of any table soft-deleted after the columns were read.
+        let t = match table_id_to_schema.get_mut(&c.table_id) {
+            Some((_, t)) => t,
+            None => continue,
+        };
         t.columns.insert(
             c.name,
             ColumnSchema {
@@ -688,10 +808,10 @@ where
     }
 
     for (_, (table_name, schema)) in table_id_to_schema {
-        namespace.tables.insert(table_name, schema);
+        namespace_schema.tables.insert(table_name, schema);
     }
 
-    Ok(namespace)
+    Ok(namespace_schema)
 }
 
 /// Gets the table schema including all columns.
@@ -739,8 +859,8 @@ pub async fn list_schemas(
     // retrieved columns (ignoring any newly added tables/namespaces since the
     // column snapshot was taken).
     //
-    // This approach also tolerates concurrently deleted namespaces, which are
-    // simply ignored at the end when joining to the namespace query result.
+    // This approach also tolerates concurrently deleted namespaces and tables,
+    // which are simply ignored when joining to the respective query result.
 
     // First fetch all the columns - this is the state snapshot of the catalog
     // schemas.
@@ -785,8 +905,12 @@ pub async fn list_schemas(
 
     let mut joined = HashMap::<NamespaceId, NamespaceTables>::default();
     for column in columns {
-        // Resolve the table this column references
-        let table = tables.get(&column.table_id).expect("no table for column");
+        // Resolve the table this column references, ignoring columns of
+        // tables that were soft-deleted after the column snapshot was taken.
+        let table = match tables.get(&column.table_id) {
+            Some(v) => v,
+            None => continue,
+        };
 
         let table_schema = joined
             // Find or create a record in the joined <NamespaceId, Tables> map
@@ -795,7 +919,7 @@ pub async fn list_schemas(
             .or_default()
             // Fetch the schema record for this table, or create an empty one.
             .entry(table.name.clone())
-            .or_insert_with(|| TableSchema::new(column.table_id));
+            .or_insert_with(|| TableSchema::new_for_table(table));
 
         table_schema.add_column(&column);
     }
@@ -823,6 +947,9 @@ pub async fn list_schemas(
                 v.max_tables,
                 v.retention_period_ns,
             );
+            ns.partition_template = v.partition_template.clone();
+            ns.max_lines_per_second = v.max_lines_per_second.map(|v| v as u64);
+            ns.max_bytes_per_second = v.max_bytes_per_second.map(|v| v as u64);
             ns.tables = joined.remove(&v.id)?;
             Some((v, ns))
         });
@@ -841,7 +968,8 @@ pub(crate) mod test_helpers {
     use ::test_helpers::{assert_contains, tracing::TracingCapture};
     use assert_matches::assert_matches;
     use data_types::{
-        ColumnId, ColumnSet, CompactionLevel, TRANSITION_SHARD_ID, TRANSITION_SHARD_INDEX,
+        ColumnId, ColumnSet, CompactionLevel, TemplatePart, TRANSITION_SHARD_ID,
+        TRANSITION_SHARD_INDEX,
     };
     use futures::Future;
     use metric::{Attributes, DurationHistogram, Metric};
@@ -854,6 +982,10 @@ pub(crate) mod test_helpers {
     {
         test_setup(clean_state().await).await;
         test_namespace_soft_deletion(clean_state().await).await;
+        test_table_soft_deletion(clean_state().await).await;
+        test_column_soft_deletion(clean_state().await).await;
+        test_table_rename(clean_state().await).await;
+        test_column_rename(clean_state().await).await;
         test_partitions_with_recent_created_files(clean_state().await).await;
         test_query_pool(clean_state().await).await;
         test_column(clean_state().await).await;
@@ -895,6 +1027,10 @@ pub(crate) mod test_helpers {
         let catalog = clean_state().await;
         test_parquet_file(Arc::clone(&catalog)).await;
         assert_metric_hit(&catalog.metrics(), "parquet_create");
+
+        let catalog = clean_state().await;
+        test_tombstone(Arc::clone(&catalog)).await;
+        assert_metric_hit(&catalog.metrics(), "tombstone_create");
     }
 
     async fn test_setup(catalog: Arc<dyn Catalog>) {
@@ -1037,6 +1173,45 @@ pub(crate) mod test_helpers {
             .expect("namespace should be updateable");
         assert_eq!(NEW_COLUMN_LIMIT, modified.max_columns_per_table);
 
+        assert_eq!(modified.max_lines_per_second, None);
+        assert_eq!(modified.max_bytes_per_second, None);
+        let modified = repos
+            .namespaces()
+            .update_lines_per_second_limit(namespace_name, Some(1000))
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(modified.max_lines_per_second, Some(1000));
+        let modified = repos
+            .namespaces()
+            .update_bytes_per_second_limit(namespace_name, Some(42_000))
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(modified.max_lines_per_second, Some(1000));
+        assert_eq!(modified.max_bytes_per_second, Some(42_000));
+        let schema = get_schema_by_name(namespace_name, repos.as_mut(), SoftDeletedRows::AllRows)
+            .await
+            .unwrap();
+        assert_eq!(schema.max_lines_per_second, Some(1000));
+        assert_eq!(schema.max_bytes_per_second, Some(42_000));
+        let modified = repos
+            .namespaces()
+            .update_lines_per_second_limit(namespace_name, None)
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(modified.max_lines_per_second, None);
+        let modified = repos
+            .namespaces()
+            .update_bytes_per_second_limit(namespace_name, None)
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(modified.max_bytes_per_second, None);
+        let err = repos
+            .namespaces()
+            .update_lines_per_second_limit("does_not_exist", Some(1))
+            .await
+            .expect_err("unknown namespace should error");
+        assert_matches!(err, Error::NamespaceNotFoundByName { .. });
+
         const NEW_RETENTION_PERIOD_NS: i64 = 5 * 60 * 60 * 1000 * 1000 * 1000;
         let modified = repos
             .namespaces()
@@ -1055,6 +1230,40 @@ pub(crate) mod test_helpers {
             .expect("namespace should be updateable");
         assert!(modified.retention_period_ns.is_none());
 
+        let template = PartitionTemplate {
+            parts: vec![
+                TemplatePart::TimeFormat("%Y-%m-%d %H".to_string()),
+                TemplatePart::Column("region".to_string()),
+            ],
+        };
+        let modified = repos
+            .namespaces()
+            .update_partition_template(namespace_name, Some(template.clone()))
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(modified.partition_template.as_ref(), Some(&template));
+        let found = repos
+            .namespaces()
+            .get_by_name(namespace_name, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .unwrap()
+            .expect("namespace should be there");
+        assert_eq!(found.partition_template, Some(template));
+
+        let modified = repos
+            .namespaces()
+            .update_partition_template(namespace_name, None)
+            .await
+            .expect("namespace should be updateable");
+        assert!(modified.partition_template.is_none());
+
+        let err = repos
+            .namespaces()
+            .update_partition_template("does_not_exist", None)
+            .await
+            .expect_err("unknown namespace should error");
+        assert_matches!(err, Error::NamespaceNotFoundByName { .. });
+
         // create namespace with retention period NULL
         let namespace3_name = "test_namespace3";
         let namespace3 = repos
@@ -1289,137 +1498,686 @@ pub(crate) mod test_helpers {
         let pool = repos.query_pools().create_or_get("foo").await.unwrap();
         let namespace = repos
             .namespaces()
-            .create("namespace_table_test", None, topic.id, pool.id)
+            .create("namespace_table_test", None, topic.id, pool.id)
+            .await
+            .unwrap();
+
+        // test we can create or get a table
+        let t = repos
+            .tables()
+            .create_or_get("test_table", namespace.id)
+            .await
+            .unwrap();
+        let tt = repos
+            .tables()
+            .create_or_get("test_table", namespace.id)
+            .await
+            .unwrap();
+        assert!(t.id > TableId::new(0));
+        assert_eq!(t, tt);
+
+        // get by id
+        assert_eq!(t, repos.tables().get_by_id(t.id).await.unwrap().unwrap());
+        assert!(repos
+            .tables()
+            .get_by_id(TableId::new(i64::MAX))
+            .await
+            .unwrap()
+            .is_none());
+
+        let tables = repos
+            .tables()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(vec![t.clone()], tables);
+
+        // test we can create a table of the same name in a different namespace
+        let namespace2 = repos
+            .namespaces()
+            .create("two", None, topic.id, pool.id)
+            .await
+            .unwrap();
+        assert_ne!(namespace, namespace2);
+        let test_table = repos
+            .tables()
+            .create_or_get("test_table", namespace2.id)
+            .await
+            .unwrap();
+        assert_ne!(tt, test_table);
+        assert_eq!(test_table.namespace_id, namespace2.id);
+
+        // test get by namespace and name
+        let foo_table = repos
+            .tables()
+            .create_or_get("foo", namespace2.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            repos
+                .tables()
+                .get_by_namespace_and_name(NamespaceId::new(i64::MAX), "test_table")
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            repos
+                .tables()
+                .get_by_namespace_and_name(namespace.id, "not_existing")
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            repos
+                .tables()
+                .get_by_namespace_and_name(namespace.id, "test_table")
+                .await
+                .unwrap(),
+            Some(t.clone())
+        );
+        assert_eq!(
+            repos
+                .tables()
+                .get_by_namespace_and_name(namespace2.id, "test_table")
+                .await
+                .unwrap()
+                .as_ref(),
+            Some(&test_table)
+        );
+        assert_eq!(
+            repos
+                .tables()
+                .get_by_namespace_and_name(namespace2.id, "foo")
+                .await
+                .unwrap()
+                .as_ref(),
+            Some(&foo_table)
+        );
+
+        // All tables should be returned by list(), regardless of namespace
+        let list = repos.tables().list().await.unwrap();
+        assert_eq!(list.as_slice(), [tt, test_table, foo_table]);
+
+        // test we can create a table with a partition template
+        let template = PartitionTemplate {
+            parts: vec![
+                TemplatePart::Table,
+                TemplatePart::Column("tag1".to_string()),
+            ],
+        };
+        let templated = repos
+            .tables()
+            .create("templated", Some(template.clone()), namespace2.id)
+            .await
+            .unwrap();
+        assert_eq!(templated.partition_template.as_ref(), Some(&template));
+        assert_eq!(
+            repos
+                .tables()
+                .get_by_id(templated.id)
+                .await
+                .unwrap()
+                .unwrap(),
+            templated
+        );
+        let schema = get_schema_by_id(namespace2.id, repos.as_mut(), SoftDeletedRows::AllRows)
+            .await
+            .unwrap();
+        assert_eq!(
+            schema.tables["templated"].partition_template,
+            Some(template)
+        );
+
+        // creating a table that already exists is an error
+        let err = repos
+            .tables()
+            .create("templated", None, namespace2.id)
+            .await
+            .expect_err("table should already exist");
+        assert_matches!(err, Error::NameExists { .. });
+
+        // test per-namespace table limits
+        let latest = repos
+            .namespaces()
+            .update_table_limit("namespace_table_test", 1)
+            .await
+            .expect("namespace should be updateable");
+        let err = repos
+            .tables()
+            .create_or_get("definitely_unique", latest.id)
+            .await
+            .expect_err("should error with table create limit error");
+        assert!(matches!(
+            err,
+            Error::TableCreateLimitError {
+                table_name: _,
+                namespace_id: _
+            }
+        ));
+        let err = repos
+            .tables()
+            .create("definitely_unique", None, latest.id)
+            .await
+            .expect_err("should error with table create limit error");
+        assert_matches!(err, Error::TableCreateLimitError { .. });
+
+        repos
+            .namespaces()
+            .soft_delete("namespace_table_test")
+            .await
+            .expect("delete namespace should succeed");
+        repos
+            .namespaces()
+            .soft_delete("two")
+            .await
+            .expect("delete namespace should succeed");
+    }
+
+    async fn test_table_soft_deletion(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let topic = repos.topics().create_or_get("foo").await.unwrap();
+        let pool = repos.query_pools().create_or_get("foo").await.unwrap();
+        let namespace = repos
+            .namespaces()
+            .create("namespace_table_soft_delete", None, topic.id, pool.id)
+            .await
+            .unwrap();
+        repos
+            .namespaces()
+            .update_table_limit("namespace_table_soft_delete", 2)
+            .await
+            .unwrap();
+
+        let deleted = repos
+            .tables()
+            .create_or_get("typo", namespace.id)
+            .await
+            .unwrap();
+        let active = repos
+            .tables()
+            .create_or_get("active", namespace.id)
+            .await
+            .unwrap();
+        let column = repos
+            .columns()
+            .create_or_get("tag", deleted.id, ColumnType::Tag)
+            .await
+            .unwrap();
+        let shard = repos
+            .shards()
+            .create_or_get(&topic, ShardIndex::new(1))
+            .await
+            .unwrap();
+        let partition = repos
+            .partitions()
+            .create_or_get("one".into(), shard.id, deleted.id)
+            .await
+            .unwrap();
+        let active_partition = repos
+            .partitions()
+            .create_or_get("one".into(), shard.id, active.id)
+            .await
+            .unwrap();
+        let file_params = ParquetFileParams {
+            shard_id: shard.id,
+            namespace_id: namespace.id,
+            table_id: deleted.id,
+            partition_id: partition.id,
+            object_store_id: Uuid::new_v4(),
+            max_sequence_number: SequenceNumber::new(140),
+            min_time: Timestamp::new(1),
+            max_time: Timestamp::new(10),
+            file_size_bytes: 1337,
+            row_count: 0,
+            compaction_level: CompactionLevel::Initial,
+            created_at: Timestamp::new(1),
+            column_set: ColumnSet::new([column.id]),
+            max_l0_created_at: Timestamp::new(1),
+            max_tombstone_sequence_number: SequenceNumber::new(0),
+        };
+        let deleted_file = repos
+            .parquet_files()
+            .create(file_params.clone())
+            .await
+            .unwrap();
+        let active_file = repos
+            .parquet_files()
+            .create(ParquetFileParams {
+                table_id: active.id,
+                partition_id: active_partition.id,
+                object_store_id: Uuid::new_v4(),
+                ..file_params.clone()
+            })
+            .await
+            .unwrap();
+
+        // The namespace is at its table limit.
+        let err = repos
+            .tables()
+            .create_or_get("another", namespace.id)
+            .await
+            .expect_err("should hit table limit");
+        assert_matches!(err, Error::TableCreateLimitError { .. });
+
+        // Soft-delete the "typo" table, which should be idempotent.
+        repos.tables().soft_delete(deleted.id).await.unwrap();
+        repos.tables().soft_delete(deleted.id).await.unwrap();
+
+        // The deleted table is only visible when fetched by ID.
+        let got = repos.tables().get_by_id(deleted.id).await.unwrap().unwrap();
+        assert_matches!(got.deleted_at, Some(_));
+        assert!(repos
+            .tables()
+            .get_by_namespace_and_name(namespace.id, "typo")
+            .await
+            .unwrap()
+            .is_none());
+        let got = repos
+            .tables()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|v| v.name);
+        assert_string_set_eq(got, ["active"]);
+        let got = repos
+            .tables()
+            .list()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|v| v.name);
+        assert_string_set_eq(got, ["active"]);
+
+        // The columns of the deleted table are hidden from namespace-wide
+        // listings, and the schema.
+        assert!(repos
+            .columns()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repos.columns().list().await.unwrap().is_empty());
+        let schema = get_schema_by_id(
+            namespace.id,
+            repos.as_mut(),
+            SoftDeletedRows::ExcludeDeleted,
+        )
+        .await
+        .unwrap();
+        assert_string_set_eq(schema.tables.keys().cloned(), ["active"]);
+
+        // Only the files of the deleted table are flagged for deletion, as
+        // part of the soft-delete.
+        let got = repos
+            .parquet_files()
+            .get_by_object_store_id(deleted_file.object_store_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_matches!(got.to_delete, Some(_));
+        let got = repos
+            .parquet_files()
+            .get_by_object_store_id(active_file.object_store_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_matches!(got.to_delete, None);
+
+        // Files persisted for the table after it was deleted are flagged by
+        // the garbage collector, once.
+        let late_file = repos
+            .parquet_files()
+            .create(ParquetFileParams {
+                object_store_id: Uuid::new_v4(),
+                ..file_params
+            })
+            .await
+            .unwrap();
+        let ids = repos
+            .parquet_files()
+            .flag_for_delete_by_deleted_tables()
+            .await
+            .unwrap();
+        assert_eq!(ids, [late_file.id]);
+        assert!(repos
+            .parquet_files()
+            .flag_for_delete_by_deleted_tables()
+            .await
+            .unwrap()
+            .is_empty());
+
+        // The deleted table no longer counts towards the table limit.
+        let another = repos
+            .tables()
+            .create_or_get("another", namespace.id)
+            .await
+            .unwrap();
+        repos.tables().soft_delete(another.id).await.unwrap();
+
+        // Creating a table with the same name revives the deleted record.
+        let revived = repos
+            .tables()
+            .create_or_get("typo", namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(revived.id, deleted.id);
+        assert_matches!(revived.deleted_at, None);
+
+        // The data of the deleted table is not revived with it.
+        assert!(repos
+            .parquet_files()
+            .list_by_table_not_to_delete(revived.id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // And so does an explicit create, while creating a live table is
+        // still an error.
+        let revived = repos.tables().create("another", None, namespace.id).await;
+        assert_matches!(revived, Err(Error::TableCreateLimitError { .. }));
+        repos.tables().soft_delete(deleted.id).await.unwrap();
+        let revived = repos
+            .tables()
+            .create("another", None, namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(revived.id, another.id);
+        assert_matches!(revived.deleted_at, None);
+        let err = repos
+            .tables()
+            .create("another", None, namespace.id)
+            .await
+            .expect_err("table should already exist");
+        assert_matches!(err, Error::NameExists { .. });
+    }
+
+    async fn test_column_soft_deletion(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let topic = repos.topics().create_or_get("foo").await.unwrap();
+        let pool = repos.query_pools().create_or_get("foo").await.unwrap();
+        let namespace = repos
+            .namespaces()
+            .create("namespace_column_soft_delete", None, topic.id, pool.id)
+            .await
+            .unwrap();
+        repos
+            .namespaces()
+            .update_column_limit("namespace_column_soft_delete", 2)
+            .await
+            .unwrap();
+        let table = repos
+            .tables()
+            .create_or_get("test_table", namespace.id)
+            .await
+            .unwrap();
+
+        let deleted = repos
+            .columns()
+            .create_or_get("typo", table.id, ColumnType::Tag)
+            .await
+            .unwrap();
+        let active = repos
+            .columns()
+            .create_or_get("active", table.id, ColumnType::Tag)
+            .await
+            .unwrap();
+
+        // The table is at its column limit.
+        let err = repos
+            .columns()
+            .create_or_get("another", table.id, ColumnType::Tag)
+            .await
+            .expect_err("should hit column limit");
+        assert_matches!(err, Error::ColumnCreateLimitError { .. });
+
+        // Soft-delete the "typo" column, which should be idempotent.
+        repos.columns().soft_delete(deleted.id).await.unwrap();
+        repos.columns().soft_delete(deleted.id).await.unwrap();
+
+        // The deleted column is hidden from all listings.
+        assert_eq!(
+            repos.columns().list_by_table_id(table.id).await.unwrap(),
+            [active.clone()]
+        );
+        assert_eq!(
+            repos
+                .columns()
+                .list_by_namespace_id(namespace.id)
+                .await
+                .unwrap(),
+            [active.clone()]
+        );
+        assert_eq!(repos.columns().list().await.unwrap(), [active.clone()]);
+
+        // The deleted column no longer counts towards the column limit.
+        let another = repos
+            .columns()
+            .create_or_get("another", table.id, ColumnType::Tag)
+            .await
+            .unwrap();
+        repos.columns().soft_delete(another.id).await.unwrap();
+
+        // Re-creating a deleted column creates a new record rather than
+        // reviving the deleted one, and it may have a different type.
+        let recreated = repos
+            .columns()
+            .create_or_get("typo", table.id, ColumnType::U64)
+            .await
+            .unwrap();
+        assert_ne!(recreated.id, deleted.id);
+        assert_eq!(recreated.column_type, ColumnType::U64);
+        assert_matches!(recreated.deleted_at, None);
+        let mut columns = HashMap::new();
+        columns.insert("another", ColumnType::Tag);
+        let recreated_many = repos
+            .columns()
+            .create_or_get_many_unchecked(table.id, columns)
+            .await
+            .unwrap();
+        assert_eq!(recreated_many.len(), 1);
+        assert_ne!(recreated_many[0].id, another.id);
+        assert_eq!(recreated_many[0].column_type, ColumnType::Tag);
+
+        // Getting the re-created column returns the live record.
+        let mut columns = HashMap::new();
+        columns.insert("typo", ColumnType::U64);
+        let got = repos
+            .columns()
+            .create_or_get_many_unchecked(table.id, columns)
+            .await
+            .unwrap();
+        assert_eq!(got, [recreated.clone()]);
+
+        let mut got = repos.columns().list_by_table_id(table.id).await.unwrap();
+        got.sort_by_key(|c| c.id);
+        assert_eq!(got, [active, recreated, recreated_many[0].clone()]);
+    }
+
+    async fn test_table_rename(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let topic = repos.topics().create_or_get("foo").await.unwrap();
+        let pool = repos.query_pools().create_or_get("foo").await.unwrap();
+        let namespace = repos
+            .namespaces()
+            .create("namespace_table_rename", None, topic.id, pool.id)
             .await
             .unwrap();
 
-        // test we can create or get a table
-        let t = repos
+        let table = repos
             .tables()
-            .create_or_get("test_table", namespace.id)
+            .create_or_get("bananas", namespace.id)
             .await
             .unwrap();
-        let tt = repos
+        let other = repos
             .tables()
-            .create_or_get("test_table", namespace.id)
+            .create_or_get("platanos", namespace.id)
             .await
             .unwrap();
-        assert!(t.id > TableId::new(0));
-        assert_eq!(t, tt);
 
-        // get by id
-        assert_eq!(t, repos.tables().get_by_id(t.id).await.unwrap().unwrap());
-        assert!(repos
+        // Renaming to a name already in use is an error, and changes nothing.
+        let err = repos
             .tables()
-            .get_by_id(TableId::new(i64::MAX))
+            .rename(table.id, "platanos")
             .await
-            .unwrap()
-            .is_none());
+            .expect_err("should not rename over an existing table");
+        assert_matches!(err, Error::NameExists { name } if name == "platanos");
+        assert_eq!(
+            repos.tables().get_by_id(table.id).await.unwrap().unwrap(),
+            table
+        );
 
-        let tables = repos
+        // A soft-deleted table still holds on to its name.
+        repos.tables().soft_delete(other.id).await.unwrap();
+        let err = repos
             .tables()
-            .list_by_namespace_id(namespace.id)
+            .rename(table.id, "platanos")
             .await
-            .unwrap();
-        assert_eq!(vec![t.clone()], tables);
+            .expect_err("should not rename over a soft-deleted table");
+        assert_matches!(err, Error::NameExists { .. });
 
-        // test we can create a table of the same name in a different namespace
-        let namespace2 = repos
-            .namespaces()
-            .create("two", None, topic.id, pool.id)
-            .await
-            .unwrap();
-        assert_ne!(namespace, namespace2);
-        let test_table = repos
+        // A soft-deleted table cannot be renamed.
+        let err = repos
             .tables()
-            .create_or_get("test_table", namespace2.id)
+            .rename(other.id, "plantains")
             .await
-            .unwrap();
-        assert_ne!(tt, test_table);
-        assert_eq!(test_table.namespace_id, namespace2.id);
+            .expect_err("should not rename a soft-deleted table");
+        assert_matches!(err, Error::TableNotFound { id } if id == other.id);
 
-        // test get by namespace and name
-        let foo_table = repos
-            .tables()
-            .create_or_get("foo", namespace2.id)
-            .await
-            .unwrap();
+        let renamed = repos.tables().rename(table.id, "bananes").await.unwrap();
+        assert_eq!(renamed.id, table.id);
+        assert_eq!(renamed.name, "bananes");
+
+        // The table is only visible by its new name.
         assert_eq!(
             repos
                 .tables()
-                .get_by_namespace_and_name(NamespaceId::new(i64::MAX), "test_table")
+                .get_by_namespace_and_name(namespace.id, "bananes")
                 .await
                 .unwrap(),
-            None
+            Some(renamed.clone())
         );
         assert_eq!(
             repos
                 .tables()
-                .get_by_namespace_and_name(namespace.id, "not_existing")
+                .get_by_namespace_and_name(namespace.id, "bananas")
                 .await
                 .unwrap(),
             None
         );
-        assert_eq!(
-            repos
-                .tables()
-                .get_by_namespace_and_name(namespace.id, "test_table")
-                .await
-                .unwrap(),
-            Some(t.clone())
-        );
-        assert_eq!(
-            repos
-                .tables()
-                .get_by_namespace_and_name(namespace2.id, "test_table")
-                .await
-                .unwrap()
-                .as_ref(),
-            Some(&test_table)
-        );
-        assert_eq!(
-            repos
-                .tables()
-                .get_by_namespace_and_name(namespace2.id, "foo")
-                .await
-                .unwrap()
-                .as_ref(),
-            Some(&foo_table)
-        );
 
-        // All tables should be returned by list(), regardless of namespace
-        let list = repos.tables().list().await.unwrap();
-        assert_eq!(list.as_slice(), [tt, test_table, foo_table]);
+        // The old name is free to be used by a new table.
+        let new = repos
+            .tables()
+            .create_or_get("bananas", namespace.id)
+            .await
+            .unwrap();
+        assert_ne!(new.id, table.id);
 
-        // test per-namespace table limits
-        let latest = repos
+        let mut got = repos
+            .tables()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .unwrap();
+        got.sort_by_key(|t| t.id);
+        assert_eq!(got, [renamed, new]);
+    }
+
+    async fn test_column_rename(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let topic = repos.topics().create_or_get("foo").await.unwrap();
+        let pool = repos.query_pools().create_or_get("foo").await.unwrap();
+        let namespace = repos
             .namespaces()
-            .update_table_limit("namespace_table_test", 1)
+            .create("namespace_column_rename", None, topic.id, pool.id)
             .await
-            .expect("namespace should be updateable");
-        let err = repos
+            .unwrap();
+        let table = repos
             .tables()
-            .create_or_get("definitely_unique", latest.id)
+            .create_or_get("test_table", namespace.id)
             .await
-            .expect_err("should error with table create limit error");
-        assert!(matches!(
-            err,
-            Error::TableCreateLimitError {
-                table_name: _,
-                namespace_id: _
-            }
-        ));
+            .unwrap();
+        let other_table = repos
+            .tables()
+            .create_or_get("other_table", namespace.id)
+            .await
+            .unwrap();
 
-        repos
-            .namespaces()
-            .soft_delete("namespace_table_test")
+        let column = repos
+            .columns()
+            .create_or_get("tmep", table.id, ColumnType::F64)
             .await
-            .expect("delete namespace should succeed");
-        repos
-            .namespaces()
-            .soft_delete("two")
+            .unwrap();
+        let other = repos
+            .columns()
+            .create_or_get("temp", table.id, ColumnType::F64)
             .await
-            .expect("delete namespace should succeed");
+            .unwrap();
+
+        // Renaming to a name already in use in the same table is an error.
+        let err = repos
+            .columns()
+            .rename(column.id, "temp")
+            .await
+            .expect_err("should not rename over an existing column");
+        assert_matches!(err, Error::NameExists { name } if name == "temp");
+
+        // A soft-deleted column releases its name.
+        repos.columns().soft_delete(other.id).await.unwrap();
+        let renamed = repos.columns().rename(column.id, "temp").await.unwrap();
+        assert_eq!(renamed.id, column.id);
+        assert_eq!(renamed.name, "temp");
+
+        // A soft-deleted column cannot be renamed.
+        let err = repos
+            .columns()
+            .rename(other.id, "temperature")
+            .await
+            .expect_err("should not rename a soft-deleted column");
+        assert_matches!(err, Error::ColumnNotFound { id } if id == other.id);
+
+        // Column names only need to be unique within a table.
+        let other_table_column = repos
+            .columns()
+            .create_or_get("temperature", other_table.id, ColumnType::F64)
+            .await
+            .unwrap();
+
+        let renamed = repos
+            .columns()
+            .rename(column.id, "temperature")
+            .await
+            .unwrap();
+        assert_eq!(renamed.id, column.id);
+        assert_eq!(renamed.name, "temperature");
+        assert_eq!(renamed.column_type, column.column_type);
+
+        assert_eq!(
+            repos.columns().list_by_table_id(table.id).await.unwrap(),
+            [renamed.clone()]
+        );
+        assert_eq!(
+            repos
+                .columns()
+                .list_by_table_id(other_table.id)
+                .await
+                .unwrap(),
+            [other_table_column]
+        );
+
+        // The old name is free to be used by a new column, of any type.
+        let new = repos
+            .columns()
+            .create_or_get("tmep", table.id, ColumnType::Tag)
+            .await
+            .unwrap();
+        assert_ne!(new.id, column.id);
+
+        let mut got = repos.columns().list_by_table_id(table.id).await.unwrap();
+        got.sort_by_key(|c| c.id);
+        assert_eq!(got, [renamed, new]);
     }
 
     async fn test_column(catalog: Arc<dyn Catalog>) {
@@ -1488,6 +2246,14 @@ pub(crate) mod test_helpers {
         let want2 = vec![c];
         assert_eq!(want2, columns);
 
+        // Locking lists the same columns within a transaction
+        drop(repos);
+        let mut txn = catalog.start_transaction().await.unwrap();
+        let columns = txn.columns().lock_by_table_id(table.id).await.unwrap();
+        assert_eq!(want2, columns);
+        txn.abort().await.unwrap();
+        let mut repos = catalog.repositories().await;
+
         // Add another tag column into table2
         let c3 = repos
             .columns()
@@ -1963,6 +2729,7 @@ pub(crate) mod test_helpers {
             created_at: Timestamp::new(1),
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: Timestamp::new(1),
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         };
         let parquet_file = repos
             .parquet_files()
@@ -2354,6 +3121,7 @@ pub(crate) mod test_helpers {
             created_at: Timestamp::new(1),
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: Timestamp::new(1),
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         };
         let parquet_file_params_2 = ParquetFileParams {
             shard_id: shard.id,
@@ -2370,6 +3138,7 @@ pub(crate) mod test_helpers {
             created_at: Timestamp::new(1),
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: Timestamp::new(1),
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         };
         let _parquet_file_1 = repos
             .parquet_files()
@@ -2474,6 +3243,7 @@ pub(crate) mod test_helpers {
             created_at: time_three_hour_ago,
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: time_now,
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         };
 
         // create a deleted L0 file that was created 3 hours ago
@@ -2956,6 +3726,7 @@ pub(crate) mod test_helpers {
             created_at: Timestamp::new(1),
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: Timestamp::new(1),
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         };
 
         let parquet_file = repos
@@ -3078,6 +3849,7 @@ pub(crate) mod test_helpers {
             created_at: Timestamp::new(1),
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: Timestamp::new(1),
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         };
         let parquet_file = repos
             .parquet_files()
@@ -3174,6 +3946,7 @@ pub(crate) mod test_helpers {
             created_at: Timestamp::new(1),
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: Timestamp::new(1),
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         };
         let p1_n1 = repos
             .parquet_files()
@@ -3237,6 +4010,7 @@ pub(crate) mod test_helpers {
             created_at: Timestamp::new(1),
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: Timestamp::new(1),
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         };
         let p1_n2 = repos
             .parquet_files()
@@ -3381,6 +4155,183 @@ pub(crate) mod test_helpers {
             .expect("parquet file exists check should succeed"));
     }
 
+    async fn test_tombstone(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let topic = repos.topics().create_or_get("foo").await.unwrap();
+        let pool = repos.query_pools().create_or_get("foo").await.unwrap();
+        let namespace = repos
+            .namespaces()
+            .create("namespace_tombstone_test", None, topic.id, pool.id)
+            .await
+            .unwrap();
+        let table = repos
+            .tables()
+            .create_or_get("test_table", namespace.id)
+            .await
+            .unwrap();
+        let other_table = repos
+            .tables()
+            .create_or_get("other", namespace.id)
+            .await
+            .unwrap();
+        let shard = repos
+            .shards()
+            .create_or_get(&topic, ShardIndex::new(1))
+            .await
+            .unwrap();
+
+        let t1 = repos
+            .tombstones()
+            .create(
+                table.id,
+                shard.id,
+                Timestamp::new(1),
+                Timestamp::new(10),
+                r#""host"='a'"#,
+            )
+            .await
+            .unwrap();
+        assert_eq!(t1.table_id, table.id);
+        assert_eq!(t1.shard_id, shard.id);
+        assert_eq!(t1.sequence_number, SequenceNumber::new(1));
+        assert_eq!(t1.min_time, Timestamp::new(1));
+        assert_eq!(t1.max_time, Timestamp::new(10));
+        assert_eq!(t1.serialized_predicate, r#""host"='a'"#);
+        assert!(t1.created_at.get() > 0);
+
+        // sequence numbers are assigned per table
+        let t2 = repos
+            .tombstones()
+            .create(
+                other_table.id,
+                shard.id,
+                Timestamp::new(1),
+                Timestamp::new(10),
+                "",
+            )
+            .await
+            .unwrap();
+        assert_eq!(t2.sequence_number, SequenceNumber::new(1));
+        let t3 = repos
+            .tombstones()
+            .create(
+                table.id,
+                shard.id,
+                Timestamp::new(5),
+                Timestamp::new(20),
+                "",
+            )
+            .await
+            .unwrap();
+        assert_eq!(t3.sequence_number, SequenceNumber::new(2));
+        assert_ne!(t1.id, t3.id);
+
+        let listed = repos.tombstones().list_by_table(table.id).await.unwrap();
+        assert_eq!(listed, vec![t1.clone(), t3.clone()]);
+        let listed = repos
+            .tombstones()
+            .list_by_table(other_table.id)
+            .await
+            .unwrap();
+        assert_eq!(listed, vec![t2]);
+        let listed = repos
+            .tombstones()
+            .list_by_table(TableId::new(i64::MAX))
+            .await
+            .unwrap();
+        assert!(listed.is_empty());
+
+        // tombstones for unknown tables are rejected
+        let err = repos
+            .tombstones()
+            .create(
+                TableId::new(i64::MAX),
+                shard.id,
+                Timestamp::new(1),
+                Timestamp::new(10),
+                "",
+            )
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            Error::ForeignKeyViolation { .. } | Error::TableNotFound { .. }
+        );
+
+        // parquet files record the tombstones applied to their rows
+        let partition = repos
+            .partitions()
+            .create_or_get("one".into(), shard.id, table.id)
+            .await
+            .unwrap();
+        let parquet_file_params = ParquetFileParams {
+            shard_id: shard.id,
+            namespace_id: namespace.id,
+            table_id: table.id,
+            partition_id: partition.id,
+            object_store_id: Uuid::new_v4(),
+            max_sequence_number: SequenceNumber::new(1),
+            min_time: Timestamp::new(1),
+            max_time: Timestamp::new(10),
+            file_size_bytes: 1337,
+            row_count: 1,
+            compaction_level: CompactionLevel::Initial,
+            created_at: Timestamp::new(1),
+            column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
+            max_l0_created_at: Timestamp::new(1),
+            max_tombstone_sequence_number: t1.sequence_number,
+        };
+        let f1 = repos
+            .parquet_files()
+            .create(parquet_file_params.clone())
+            .await
+            .unwrap();
+        assert_eq!(f1.max_tombstone_sequence_number, t1.sequence_number);
+        assert!(!t1.applies_to(&f1));
+        assert!(t3.applies_to(&f1));
+
+        let listed = repos
+            .parquet_files()
+            .list_by_table_not_to_delete(table.id)
+            .await
+            .unwrap();
+        assert_eq!(listed, vec![f1]);
+        drop(repos);
+
+        // concurrent deletes of the same table are assigned distinct sequence numbers
+        let (table_id, shard_id) = (other_table.id, shard.id);
+        let tasks = (0..10)
+            .map(|_| {
+                let catalog = Arc::clone(&catalog);
+                tokio::spawn(async move {
+                    catalog
+                        .repositories()
+                        .await
+                        .tombstones()
+                        .create(
+                            table_id,
+                            shard_id,
+                            Timestamp::new(1),
+                            Timestamp::new(10),
+                            "",
+                        )
+                        .await
+                        .unwrap()
+                        .sequence_number
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut sequence_numbers = vec![];
+        for task in tasks {
+            sequence_numbers.push(task.await.unwrap());
+        }
+        sequence_numbers.sort();
+        assert_eq!(
+            sequence_numbers,
+            (2..12).map(SequenceNumber::new).collect::<Vec<_>>()
+        );
+    }
+
     async fn test_txn_isolation(catalog: Arc<dyn Catalog>) {
         let barrier = Arc::new(tokio::sync::Barrier::new(2));
 
@@ -127,7 +127,7 @@ where
                 .tables()
                 .create_or_get(table_name, schema.id)
                 .await
-                .map(|t| TableSchema::new(t.id))?;
+                .map(|t| TableSchema::new_for_table(&t))?;
 
             // Always add a time column to all new tables.
             let time_col = repos
@@ -5,7 +5,8 @@ use crate::{
     interface::{
         sealed::TransactionFinalize, CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu,
         Error, NamespaceRepo, ParquetFileRepo, PartitionRepo, QueryPoolRepo, RepoCollection,
-        Result, ShardRepo, SoftDeletedRows, TableRepo, TopicMetadataRepo, Transaction,
+        Result, ShardRepo, SoftDeletedRows, TableRepo, TombstoneRepo, TopicMetadataRepo,
+        Transaction,
     },
     metrics::MetricDecorator,
     DEFAULT_MAX_COLUMNS_PER_TABLE, DEFAULT_MAX_TABLES,
@@ -14,8 +15,8 @@ use async_trait::async_trait;
 use data_types::{
     Column, ColumnId, ColumnType, CompactionLevel, Namespace, NamespaceId, ParquetFile,
     ParquetFileId, ParquetFileParams, Partition, PartitionId, PartitionKey, PartitionParam,
-    QueryPool, QueryPoolId, SequenceNumber, Shard, ShardId, ShardIndex, SkippedCompaction, Table,
-    TableId, Timestamp, TopicId, TopicMetadata,
+    PartitionTemplate, QueryPool, QueryPoolId, SequenceNumber, Shard, ShardId, ShardIndex,
+    SkippedCompaction, Table, TableId, Timestamp, Tombstone, TombstoneId, TopicId, TopicMetadata,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use observability_deps::tracing::warn;
@@ -72,6 +73,7 @@ struct MemCollections {
     partitions: Vec<Partition>,
     skipped_compactions: Vec<SkippedCompaction>,
     parquet_files: Vec<ParquetFile>,
+    tombstones: Vec<Tombstone>,
 }
 
 #[derive(Debug)]
@@ -230,6 +232,10 @@ impl RepoCollection for MemTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
 }
 
 #[async_trait]
@@ -307,6 +313,9 @@ impl NamespaceRepo for MemTxn {
             max_columns_per_table: DEFAULT_MAX_COLUMNS_PER_TABLE,
             retention_period_ns,
             deleted_at: None,
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
         };
         stage.namespaces.push(namespace);
         Ok(stage.namespaces.last().unwrap().clone())
@@ -387,6 +396,40 @@ impl NamespaceRepo for MemTxn {
         }
     }
 
+    async fn update_lines_per_second_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.max_lines_per_second = new_max;
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    async fn update_bytes_per_second_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.max_bytes_per_second = new_max;
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
     async fn update_retention_period(
         &mut self,
         name: &str,
@@ -403,6 +446,23 @@ impl NamespaceRepo for MemTxn {
             }),
         }
     }
+
+    async fn update_partition_template(
+        &mut self,
+        name: &str,
+        partition_template: Option<PartitionTemplate>,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.partition_template = partition_template;
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
 }
 
 #[async_trait]
@@ -427,7 +487,7 @@ impl TableRepo for MemTxn {
                 let tables_count = stage
                     .tables
                     .iter()
-                    .filter(|t| t.namespace_id == namespace_id)
+                    .filter(|t| t.namespace_id == namespace_id && t.deleted_at.is_none())
                     .count();
                 if tables_count >= max_tables.try_into().unwrap() {
                     return Err(Error::TableCreateLimitError {
@@ -440,15 +500,21 @@ impl TableRepo for MemTxn {
 
         let table = match stage
             .tables
-            .iter()
+            .iter_mut()
             .find(|t| t.name == name && t.namespace_id == namespace_id)
         {
-            Some(t) => t,
+            Some(t) => {
+                // Revive the table if it was soft-deleted
+                t.deleted_at = None;
+                &*t
+            }
             None => {
                 let table = Table {
                     id: TableId::new(stage.tables.len() as i64 + 1),
                     namespace_id,
                     name: name.to_string(),
+                    partition_template: None,
+                    deleted_at: None,
                 };
                 stage.tables.push(table);
                 stage.tables.last().unwrap()
@@ -458,6 +524,64 @@ impl TableRepo for MemTxn {
         Ok(table.clone())
     }
 
+    async fn create(
+        &mut self,
+        name: &str,
+        partition_template: Option<PartitionTemplate>,
+        namespace_id: NamespaceId,
+    ) -> Result<Table> {
+        let stage = self.stage();
+
+        if stage
+            .tables
+            .iter()
+            .any(|t| t.name == name && t.namespace_id == namespace_id && t.deleted_at.is_none())
+        {
+            return Err(Error::NameExists {
+                name: name.to_string(),
+            });
+        }
+
+        let namespace = stage
+            .namespaces
+            .iter()
+            .find(|n| n.id == namespace_id)
+            .ok_or_else(|| Error::NamespaceNotFoundById { id: namespace_id })?;
+        let tables_count = stage
+            .tables
+            .iter()
+            .filter(|t| t.namespace_id == namespace_id && t.deleted_at.is_none())
+            .count();
+        if tables_count >= namespace.max_tables.try_into().unwrap() {
+            return Err(Error::TableCreateLimitError {
+                table_name: name.to_string(),
+                namespace_id,
+            });
+        }
+
+        // Revive a soft-deleted table of the same name, if any
+        if let Some(t) = stage
+            .tables
+            .iter_mut()
+            .find(|t| t.name == name && t.namespace_id == namespace_id)
+        {
+            t.deleted_at = None;
+            t.partition_template = partition_template;
+            return Ok(t.clone());
+        }
+
+        let table = Table {
+            id: TableId::new(stage.tables.len() as i64 + 1),
+            namespace_id,
+            name: name.to_string(),
+            partition_template,
+            deleted_at: None,
+        };
+        stage.tables.push(table.clone());
+
+        Ok(table)
+    }
+
     async fn get_by_id(&mut self, table_id: TableId) -> Result<Option<Table>> {
         let stage = self.stage();
 
@@ -474,7 +598,7 @@ impl TableRepo for MemTxn {
         Ok(stage
             .tables
             .iter()
-            .find(|t| t.namespace_id == namespace_id && t.name == name)
+            .find(|t| t.namespace_id == namespace_id && t.name == name && t.deleted_at.is_none())
             .cloned())
     }
 
@@ -484,7 +608,7 @@ impl TableRepo for MemTxn {
         let tables: Vec<_> = stage
             .tables
             .iter()
-            .filter(|t| t.namespace_id == namespace_id)
+            .filter(|t| t.namespace_id == namespace_id && t.deleted_at.is_none())
             .cloned()
             .collect();
         Ok(tables)
@@ -492,7 +616,70 @@ impl TableRepo for MemTxn {
 
     async fn list(&mut self) -> Result<Vec<Table>> {
         let stage = self.stage();
-        Ok(stage.tables.clone())
+        Ok(stage
+            .tables
+            .iter()
+            .filter(|t| t.deleted_at.is_none())
+            .cloned()
+            .collect())
+    }
+
+    async fn soft_delete(&mut self, table_id: TableId) -> Result<()> {
+        let timestamp = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        if let Some(t) = stage
+            .tables
+            .iter_mut()
+            .find(|t| t.id == table_id && t.deleted_at.is_none())
+        {
+            t.deleted_at = Some(timestamp);
+
+            // Flag the files of the table, so that reviving the table afterwards does not
+            // revive its data.
+            for f in stage
+                .parquet_files
+                .iter_mut()
+                .filter(|f| f.table_id == table_id && f.to_delete.is_none())
+            {
+                f.to_delete = Some(timestamp);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn rename(&mut self, table_id: TableId, new_name: &str) -> Result<Table> {
+        let stage = self.stage();
+
+        let namespace_id = match stage
+            .tables
+            .iter()
+            .find(|t| t.id == table_id && t.deleted_at.is_none())
+        {
+            Some(t) => t.namespace_id,
+            None => return Err(Error::TableNotFound { id: table_id }),
+        };
+
+        // The name uniqueness constraint includes soft-deleted tables.
+        if stage
+            .tables
+            .iter()
+            .any(|t| t.namespace_id == namespace_id && t.name == new_name && t.id != table_id)
+        {
+            return Err(Error::NameExists {
+                name: new_name.to_string(),
+            });
+        }
+
+        let t = stage
+            .tables
+            .iter_mut()
+            .find(|t| t.id == table_id)
+            .expect("table exists");
+        t.name = new_name.to_string();
+
+        Ok(t.clone())
     }
 }
 
@@ -530,7 +717,7 @@ impl ColumnRepo for MemTxn {
                         let columns_count = stage
                             .columns
                             .iter()
-                            .filter(|t| t.table_id == table_id)
+                            .filter(|t| t.table_id == table_id && t.deleted_at.is_none())
                             .count();
                         if columns_count >= max_columns_per_table.try_into().unwrap() {
                             return Err(Error::ColumnCreateLimitError {
@@ -546,7 +733,7 @@ impl ColumnRepo for MemTxn {
         let column = match stage
             .columns
             .iter()
-            .find(|t| t.name == name && t.table_id == table_id)
+            .find(|t| t.name == name && t.table_id == table_id && t.deleted_at.is_none())
         {
             Some(c) => {
                 ensure!(
@@ -565,6 +752,7 @@ impl ColumnRepo for MemTxn {
                     table_id,
                     name: name.to_string(),
                     column_type,
+                    deleted_at: None,
                 };
                 stage.columns.push(column);
                 stage.columns.last().unwrap()
@@ -588,11 +776,9 @@ impl ColumnRepo for MemTxn {
         let out: Vec<_> = columns
             .iter()
             .map(|(&column_name, &column_type)| {
-                match stage
-                    .columns
-                    .iter()
-                    .find(|t| t.name == column_name && t.table_id == table_id)
-                {
+                match stage.columns.iter().find(|t| {
+                    t.name == column_name && t.table_id == table_id && t.deleted_at.is_none()
+                }) {
                     Some(c) => {
                         ensure!(
                             column_type == c.column_type,
@@ -610,6 +796,7 @@ impl ColumnRepo for MemTxn {
                             table_id,
                             name: column_name.to_string(),
                             column_type,
+                            deleted_at: None,
                         };
                         stage.columns.push(new_column);
                         Ok(stage.columns.last().unwrap().clone())
@@ -627,13 +814,13 @@ impl ColumnRepo for MemTxn {
         let table_ids: Vec<_> = stage
             .tables
             .iter()
-            .filter(|t| t.namespace_id == namespace_id)
+            .filter(|t| t.namespace_id == namespace_id && t.deleted_at.is_none())
             .map(|t| t.id)
             .collect();
         let columns: Vec<_> = stage
             .columns
             .iter()
-            .filter(|c| table_ids.contains(&c.table_id))
+            .filter(|c| table_ids.contains(&c.table_id) && c.deleted_at.is_none())
             .cloned()
             .collect();
 
@@ -646,16 +833,82 @@ impl ColumnRepo for MemTxn {
         let columns: Vec<_> = stage
             .columns
             .iter()
-            .filter(|c| c.table_id == table_id)
+            .filter(|c| c.table_id == table_id && c.deleted_at.is_none())
             .cloned()
             .collect();
 
         Ok(columns)
     }
 
+    async fn lock_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Column>> {
+        // Transactions hold the lock on the whole catalog.
+        self.list_by_table_id(table_id).await
+    }
+
     async fn list(&mut self) -> Result<Vec<Column>> {
         let stage = self.stage();
-        Ok(stage.columns.clone())
+
+        let deleted_tables: HashSet<_> = stage
+            .tables
+            .iter()
+            .filter(|t| t.deleted_at.is_some())
+            .map(|t| t.id)
+            .collect();
+        Ok(stage
+            .columns
+            .iter()
+            .filter(|c| c.deleted_at.is_none() && !deleted_tables.contains(&c.table_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn soft_delete(&mut self, column_id: ColumnId) -> Result<()> {
+        let timestamp = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        if let Some(c) = stage
+            .columns
+            .iter_mut()
+            .find(|c| c.id == column_id && c.deleted_at.is_none())
+        {
+            c.deleted_at = Some(timestamp);
+        }
+
+        Ok(())
+    }
+
+    async fn rename(&mut self, column_id: ColumnId, new_name: &str) -> Result<Column> {
+        let stage = self.stage();
+
+        let table_id = match stage
+            .columns
+            .iter()
+            .find(|c| c.id == column_id && c.deleted_at.is_none())
+        {
+            Some(c) => c.table_id,
+            None => return Err(Error::ColumnNotFound { id: column_id }),
+        };
+
+        // The name uniqueness constraint ignores soft-deleted columns.
+        if stage.columns.iter().any(|c| {
+            c.table_id == table_id
+                && c.name == new_name
+                && c.id != column_id
+                && c.deleted_at.is_none()
+        }) {
+            return Err(Error::NameExists {
+                name: new_name.to_string(),
+            });
+        }
+
+        let c = stage
+            .columns
+            .iter_mut()
+            .find(|c| c.id == column_id)
+            .expect("column exists");
+        c.name = new_name.to_string();
+
+        Ok(c.clone())
     }
 }
 
@@ -1040,6 +1293,29 @@ impl ParquetFileRepo for MemTxn {
             .collect())
     }
 
+    async fn flag_for_delete_by_deleted_tables(&mut self) -> Result<Vec<ParquetFileId>> {
+        let now = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        let deleted_tables: HashSet<_> = stage
+            .tables
+            .iter()
+            .filter(|t| t.deleted_at.is_some())
+            .map(|t| t.id)
+            .collect();
+
+        Ok(stage
+            .parquet_files
+            .iter_mut()
+            // don't flag if already flagged for deletion
+            .filter(|f| f.to_delete.is_none() && deleted_tables.contains(&f.table_id))
+            .map(|f| {
+                f.to_delete = Some(now);
+                f.id
+            })
+            .collect())
+    }
+
     async fn list_by_namespace_not_to_delete(
         &mut self,
         namespace_id: NamespaceId,
@@ -1163,6 +1439,63 @@ impl ParquetFileRepo for MemTxn {
     }
 }
 
+#[async_trait]
+impl TombstoneRepo for MemTxn {
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        shard_id: ShardId,
+        min_time: Timestamp,
+        max_time: Timestamp,
+        predicate: &str,
+    ) -> Result<Tombstone> {
+        let created_at = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        if !stage.tables.iter().any(|t| t.id == table_id) {
+            return Err(Error::TableNotFound { id: table_id });
+        }
+
+        // The catalog lock is held for the lifetime of this transaction, so no concurrent
+        // delete can allocate the same sequence number.
+        let sequence_number = stage
+            .tombstones
+            .iter()
+            .filter(|t| t.table_id == table_id)
+            .map(|t| t.sequence_number.get())
+            .max()
+            .unwrap_or_default()
+            + 1;
+
+        let tombstone = Tombstone {
+            id: TombstoneId::new(stage.tombstones.len() as i64 + 1),
+            table_id,
+            shard_id,
+            sequence_number: SequenceNumber::new(sequence_number),
+            min_time,
+            max_time,
+            serialized_predicate: predicate.to_string(),
+            created_at,
+        };
+        stage.tombstones.push(tombstone.clone());
+
+        Ok(tombstone)
+    }
+
+    async fn list_by_table(&mut self, table_id: TableId) -> Result<Vec<Tombstone>> {
+        let stage = self.stage();
+
+        let mut tombstones: Vec<_> = stage
+            .tombstones
+            .iter()
+            .filter(|t| t.table_id == table_id)
+            .cloned()
+            .collect();
+        tombstones.sort_by_key(|t| t.sequence_number);
+        Ok(tombstones)
+    }
+}
+
 fn filter_namespace_soft_delete<'a>(
     v: impl IntoIterator<Item = &'a Namespace>,
     deleted: SoftDeletedRows,
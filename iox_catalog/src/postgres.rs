@@ -5,17 +5,18 @@ use crate::{
         self, sealed::TransactionFinalize, CasFailure, Catalog, ColumnRepo,
         ColumnTypeMismatchSnafu, Error, NamespaceRepo, ParquetFileRepo, PartitionRepo,
         QueryPoolRepo, RepoCollection, Result, ShardRepo, SoftDeletedRows, TableRepo,
-        TopicMetadataRepo, Transaction,
+        TombstoneRepo, TopicMetadataRepo, Transaction,
     },
     metrics::MetricDecorator,
     DEFAULT_MAX_COLUMNS_PER_TABLE, DEFAULT_MAX_TABLES, SHARED_TOPIC_ID, SHARED_TOPIC_NAME,
 };
 use async_trait::async_trait;
 use data_types::{
-    Column, ColumnType, CompactionLevel, Namespace, NamespaceId, ParquetFile, ParquetFileId,
-    ParquetFileParams, Partition, PartitionId, PartitionKey, PartitionParam, QueryPool,
-    QueryPoolId, SequenceNumber, Shard, ShardId, ShardIndex, SkippedCompaction, Table, TableId,
-    Timestamp, TopicId, TopicMetadata, TRANSITION_SHARD_ID, TRANSITION_SHARD_INDEX,
+    Column, ColumnId, ColumnType, CompactionLevel, Namespace, NamespaceId, ParquetFile,
+    ParquetFileId, ParquetFileParams, Partition, PartitionId, PartitionKey, PartitionParam,
+    PartitionTemplate, QueryPool, QueryPoolId, SequenceNumber, Shard, ShardId, ShardIndex,
+    SkippedCompaction, Table, TableId, Timestamp, Tombstone, TopicId, TopicMetadata,
+    TRANSITION_SHARD_ID, TRANSITION_SHARD_INDEX,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use observability_deps::tracing::{debug, info, warn};
@@ -563,6 +564,10 @@ impl RepoCollection for PostgresTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
 }
 
 #[async_trait]
@@ -795,6 +800,52 @@ RETURNING *;
         Ok(namespace)
     }
 
+    async fn update_lines_per_second_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"UPDATE namespace SET max_lines_per_second = $1 WHERE name = $2 RETURNING *;"#,
+        )
+        .bind(new_max) // $1
+        .bind(name) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_bytes_per_second_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"UPDATE namespace SET max_bytes_per_second = $1 WHERE name = $2 RETURNING *;"#,
+        )
+        .bind(new_max) // $1
+        .bind(name) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
     async fn update_retention_period(
         &mut self,
         name: &str,
@@ -817,6 +868,29 @@ RETURNING *;
 
         Ok(namespace)
     }
+
+    async fn update_partition_template(
+        &mut self,
+        name: &str,
+        partition_template: Option<PartitionTemplate>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"UPDATE namespace SET partition_template = $1 WHERE name = $2 RETURNING *;"#,
+        )
+        .bind(partition_template) // $1
+        .bind(name) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
 }
 
 #[async_trait]
@@ -831,17 +905,20 @@ impl TableRepo for PostgresTxn {
         // By using SELECT rather than VALUES it will insert zero rows if it finds a null in the
         // subquery, i.e. if count >= max_tables. fetch_one() will return a RowNotFound error if
         // nothing was inserted. Not pretty!
+        //
+        // Soft-deleted tables do not count towards the limit, and are revived on conflict.
         let rec = sqlx::query_as::<_, Table>(
             r#"
 INSERT INTO table_name ( name, namespace_id )
 SELECT $1, id FROM (
     SELECT namespace.id AS id, max_tables, COUNT(table_name.*) AS count
-    FROM namespace LEFT JOIN table_name ON namespace.id = table_name.namespace_id
+    FROM namespace LEFT JOIN table_name
+        ON namespace.id = table_name.namespace_id AND table_name.deleted_at IS NULL
     WHERE namespace.id = $2
     GROUP BY namespace.max_tables, table_name.namespace_id, namespace.id
 ) AS get_count WHERE count < max_tables
 ON CONFLICT ON CONSTRAINT table_name_unique
-DO UPDATE SET name = table_name.name
+DO UPDATE SET name = table_name.name, deleted_at = NULL
 RETURNING *;
         "#,
         )
@@ -866,6 +943,62 @@ RETURNING *;
         Ok(rec)
     }
 
+    async fn create(
+        &mut self,
+        name: &str,
+        partition_template: Option<PartitionTemplate>,
+        namespace_id: NamespaceId,
+    ) -> Result<Table> {
+        // See `create_or_get` for why the table limit is checked in a subquery. A soft-deleted
+        // table of the same name is revived with the new partition template, while a conflict
+        // with a live table updates (and returns) no rows.
+        let rec = sqlx::query_as::<_, Table>(
+            r#"
+INSERT INTO table_name ( name, namespace_id, partition_template )
+SELECT $1, id, $3 FROM (
+    SELECT namespace.id AS id, max_tables, COUNT(table_name.*) AS count
+    FROM namespace LEFT JOIN table_name
+        ON namespace.id = table_name.namespace_id AND table_name.deleted_at IS NULL
+    WHERE namespace.id = $2
+    GROUP BY namespace.max_tables, table_name.namespace_id, namespace.id
+) AS get_count WHERE count < max_tables
+ON CONFLICT ON CONSTRAINT table_name_unique
+DO UPDATE SET deleted_at = NULL, partition_template = EXCLUDED.partition_template
+WHERE table_name.deleted_at IS NOT NULL
+RETURNING *;
+        "#,
+        )
+        .bind(name) // $1
+        .bind(namespace_id) // $2
+        .bind(partition_template) // $3
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let rec = match rec {
+            Ok(v) => v,
+            Err(sqlx::Error::RowNotFound) => {
+                // Either the table limit was reached, or a live table already exists.
+                if self
+                    .get_by_namespace_and_name(namespace_id, name)
+                    .await?
+                    .is_some()
+                {
+                    return Err(Error::NameExists {
+                        name: name.to_string(),
+                    });
+                }
+                return Err(Error::TableCreateLimitError {
+                    table_name: name.to_string(),
+                    namespace_id,
+                });
+            }
+            Err(e) if is_fk_violation(&e) => return Err(Error::ForeignKeyViolation { source: e }),
+            Err(e) => return Err(Error::SqlxError { source: e }),
+        };
+
+        Ok(rec)
+    }
+
     async fn get_by_id(&mut self, table_id: TableId) -> Result<Option<Table>> {
         let rec = sqlx::query_as::<_, Table>(
             r#"
@@ -896,7 +1029,7 @@ WHERE id = $1;
             r#"
 SELECT *
 FROM table_name
-WHERE namespace_id = $1 AND name = $2;
+WHERE namespace_id = $1 AND name = $2 AND deleted_at IS NULL;
             "#,
         )
         .bind(namespace_id) // $1
@@ -918,7 +1051,7 @@ WHERE namespace_id = $1 AND name = $2;
             r#"
 SELECT *
 FROM table_name
-WHERE namespace_id = $1;
+WHERE namespace_id = $1 AND deleted_at IS NULL;
             "#,
         )
         .bind(namespace_id)
@@ -930,13 +1063,63 @@ WHERE namespace_id = $1;
     }
 
     async fn list(&mut self) -> Result<Vec<Table>> {
-        let rec = sqlx::query_as::<_, Table>("SELECT * FROM table_name;")
+        let rec = sqlx::query_as::<_, Table>("SELECT * FROM table_name WHERE deleted_at IS NULL;")
             .fetch_all(&mut self.inner)
             .await
             .map_err(|e| Error::SqlxError { source: e })?;
 
         Ok(rec)
     }
+
+    async fn soft_delete(&mut self, table_id: TableId) -> Result<()> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+
+        // Flag the files of the table in the same statement, so that reviving the table
+        // afterwards does not revive its data.
+        sqlx::query(
+            r#"
+WITH deleted AS (
+    UPDATE table_name SET deleted_at=$1 WHERE id = $2 AND deleted_at IS NULL RETURNING id
+)
+UPDATE parquet_file
+SET to_delete = $1
+FROM deleted
+WHERE parquet_file.table_id = deleted.id
+  AND parquet_file.to_delete IS NULL;
+        "#,
+        )
+        .bind(flagged_at) // $1
+        .bind(table_id) // $2
+        .execute(&mut self.inner)
+        .await
+        .context(interface::CouldNotDeleteTableSnafu)
+        .map(|_| ())
+    }
+
+    async fn rename(&mut self, table_id: TableId, new_name: &str) -> Result<Table> {
+        let rec = sqlx::query_as::<_, Table>(
+            r#"
+UPDATE table_name
+SET name = $1
+WHERE id = $2 AND deleted_at IS NULL
+RETURNING *;
+        "#,
+        )
+        .bind(new_name) // $1
+        .bind(table_id) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let table = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::TableNotFound { id: table_id },
+            _ if is_unique_violation(&e) => Error::NameExists {
+                name: new_name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(table)
+    }
 }
 
 #[async_trait]
@@ -953,11 +1136,12 @@ INSERT INTO column_name ( name, table_id, column_type )
 SELECT $1, table_id, $3 FROM (
     SELECT max_columns_per_table, namespace.id, table_name.id as table_id, COUNT(column_name.*) AS count
     FROM namespace LEFT JOIN table_name ON namespace.id = table_name.namespace_id
-                   LEFT JOIN column_name ON table_name.id = column_name.table_id
+                   LEFT JOIN column_name
+                       ON table_name.id = column_name.table_id AND column_name.deleted_at IS NULL
     WHERE table_name.id = $2
     GROUP BY namespace.max_columns_per_table, namespace.id, table_name.id
 ) AS get_count WHERE count < max_columns_per_table
-ON CONFLICT ON CONSTRAINT column_name_unique
+ON CONFLICT (table_id, name) WHERE deleted_at IS NULL
 DO UPDATE SET name = column_name.name
 RETURNING *;
         "#,
@@ -997,7 +1181,9 @@ RETURNING *;
             r#"
 SELECT column_name.* FROM table_name
 INNER JOIN column_name on column_name.table_id = table_name.id
-WHERE table_name.namespace_id = $1;
+WHERE table_name.namespace_id = $1
+AND table_name.deleted_at IS NULL
+AND column_name.deleted_at IS NULL;
             "#,
         )
         .bind(namespace_id)
@@ -1012,7 +1198,23 @@ WHERE table_name.namespace_id = $1;
         let rec = sqlx::query_as::<_, Column>(
             r#"
 SELECT * FROM column_name
-WHERE table_id = $1;
+WHERE table_id = $1 AND deleted_at IS NULL;
+            "#,
+        )
+        .bind(table_id)
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        Ok(rec)
+    }
+
+    async fn lock_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Column>> {
+        let rec = sqlx::query_as::<_, Column>(
+            r#"
+SELECT * FROM column_name
+WHERE table_id = $1 AND deleted_at IS NULL
+FOR SHARE;
             "#,
         )
         .bind(table_id)
@@ -1024,10 +1226,16 @@ WHERE table_id = $1;
     }
 
     async fn list(&mut self) -> Result<Vec<Column>> {
-        let rec = sqlx::query_as::<_, Column>("SELECT * FROM column_name;")
-            .fetch_all(&mut self.inner)
-            .await
-            .map_err(|e| Error::SqlxError { source: e })?;
+        let rec = sqlx::query_as::<_, Column>(
+            r#"
+SELECT column_name.* FROM column_name
+INNER JOIN table_name on column_name.table_id = table_name.id
+WHERE table_name.deleted_at IS NULL AND column_name.deleted_at IS NULL;
+            "#,
+        )
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
 
         Ok(rec)
     }
@@ -1055,7 +1263,7 @@ INSERT INTO column_name ( name, table_id, column_type )
 SELECT name, $1, column_type
 FROM UNNEST($2, $3) as a(name, column_type)
 ORDER BY name
-ON CONFLICT ON CONSTRAINT column_name_unique
+ON CONFLICT (table_id, name) WHERE deleted_at IS NULL
 DO UPDATE SET name = column_name.name
 RETURNING *;
             "#,
@@ -1089,6 +1297,43 @@ RETURNING *;
 
         Ok(out)
     }
+
+    async fn soft_delete(&mut self, column_id: ColumnId) -> Result<()> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+
+        sqlx::query(r#"UPDATE column_name SET deleted_at=$1 WHERE id = $2 AND deleted_at IS NULL;"#)
+            .bind(flagged_at) // $1
+            .bind(column_id) // $2
+            .execute(&mut self.inner)
+            .await
+            .context(interface::CouldNotDeleteColumnSnafu)
+            .map(|_| ())
+    }
+
+    async fn rename(&mut self, column_id: ColumnId, new_name: &str) -> Result<Column> {
+        let rec = sqlx::query_as::<_, Column>(
+            r#"
+UPDATE column_name
+SET name = $1
+WHERE id = $2 AND deleted_at IS NULL
+RETURNING *;
+        "#,
+        )
+        .bind(new_name) // $1
+        .bind(column_id) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let column = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::ColumnNotFound { id: column_id },
+            _ if is_unique_violation(&e) => Error::NameExists {
+                name: new_name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(column)
+    }
 }
 
 #[async_trait]
@@ -1501,6 +1746,7 @@ impl ParquetFileRepo for PostgresTxn {
             created_at,
             column_set,
             max_l0_created_at,
+            max_tombstone_sequence_number,
         } = parquet_file_params;
 
         let rec = sqlx::query_as::<_, ParquetFile>(
@@ -1508,8 +1754,9 @@ impl ParquetFileRepo for PostgresTxn {
 INSERT INTO parquet_file (
     shard_id, table_id, partition_id, object_store_id,
     max_sequence_number, min_time, max_time, file_size_bytes,
-    row_count, compaction_level, created_at, namespace_id, column_set, max_l0_created_at )
-VALUES ( $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14 )
+    row_count, compaction_level, created_at, namespace_id, column_set, max_l0_created_at,
+    max_tombstone_sequence_number )
+VALUES ( $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15 )
 RETURNING *;
         "#,
         )
@@ -1527,6 +1774,7 @@ RETURNING *;
         .bind(namespace_id) // $12
         .bind(column_set) // $13
         .bind(max_l0_created_at) // $14
+        .bind(max_tombstone_sequence_number) // $15
         .fetch_one(&mut self.inner)
         .await
         .map_err(|e| {
@@ -1579,6 +1827,28 @@ RETURNING *;
         Ok(flagged)
     }
 
+    async fn flag_for_delete_by_deleted_tables(&mut self) -> Result<Vec<ParquetFileId>> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+        let flagged = sqlx::query(
+            r#"
+                UPDATE parquet_file
+                SET to_delete = $1
+                FROM table_name
+                WHERE table_name.deleted_at IS NOT NULL
+                AND parquet_file.to_delete IS NULL
+                AND table_name.id = parquet_file.table_id
+                RETURNING parquet_file.id;
+            "#,
+        )
+        .bind(flagged_at) // $1
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        let flagged = flagged.into_iter().map(|row| row.get("id")).collect();
+        Ok(flagged)
+    }
+
     async fn list_by_namespace_not_to_delete(
         &mut self,
         namespace_id: NamespaceId,
@@ -1592,7 +1862,7 @@ SELECT parquet_file.id, parquet_file.shard_id, parquet_file.namespace_id,
        parquet_file.max_sequence_number, parquet_file.min_time,
        parquet_file.max_time, parquet_file.to_delete, parquet_file.file_size_bytes,
        parquet_file.row_count, parquet_file.compaction_level, parquet_file.created_at, parquet_file.column_set,
-       parquet_file.max_l0_created_at
+       parquet_file.max_l0_created_at, parquet_file.max_tombstone_sequence_number
 FROM parquet_file
 INNER JOIN table_name on table_name.id = parquet_file.table_id
 WHERE table_name.namespace_id = $1
@@ -1612,7 +1882,8 @@ WHERE table_name.namespace_id = $1
             r#"
 SELECT id, shard_id, namespace_id, table_id, partition_id, object_store_id,
        max_sequence_number, min_time, max_time, to_delete, file_size_bytes,
-       row_count, compaction_level, created_at, column_set, max_l0_created_at
+       row_count, compaction_level, created_at, column_set, max_l0_created_at,
+       max_tombstone_sequence_number
 FROM parquet_file
 WHERE table_id = $1 AND to_delete IS NULL;
              "#,
@@ -1630,7 +1901,8 @@ WHERE table_id = $1 AND to_delete IS NULL;
             r#"
 SELECT id, shard_id, namespace_id, table_id, partition_id, object_store_id,
        max_sequence_number, min_time, max_time, to_delete, file_size_bytes,
-       row_count, compaction_level, created_at, column_set, max_l0_created_at
+       row_count, compaction_level, created_at, column_set, max_l0_created_at,
+       max_tombstone_sequence_number
 FROM parquet_file
 WHERE table_id = $1;
              "#,
@@ -1676,7 +1948,8 @@ RETURNING id;
             r#"
 SELECT id, shard_id, namespace_id, table_id, partition_id, object_store_id,
        max_sequence_number, min_time, max_time, to_delete, file_size_bytes,
-       row_count, compaction_level, created_at, column_set, max_l0_created_at
+       row_count, compaction_level, created_at, column_set, max_l0_created_at,
+       max_tombstone_sequence_number
 FROM parquet_file
 WHERE parquet_file.partition_id = $1
   AND parquet_file.to_delete IS NULL;
@@ -1746,7 +2019,8 @@ RETURNING id;
             r#"
 SELECT id, shard_id, namespace_id, table_id, partition_id, object_store_id,
        max_sequence_number, min_time, max_time, to_delete, file_size_bytes,
-       row_count, compaction_level, created_at, column_set, max_l0_created_at
+       row_count, compaction_level, created_at, column_set, max_l0_created_at,
+       max_tombstone_sequence_number
 FROM parquet_file
 WHERE object_store_id = $1;
              "#,
@@ -1765,6 +2039,68 @@ WHERE object_store_id = $1;
     }
 }
 
+#[async_trait]
+impl TombstoneRepo for PostgresTxn {
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        shard_id: ShardId,
+        min_time: Timestamp,
+        max_time: Timestamp,
+        predicate: &str,
+    ) -> Result<Tombstone> {
+        let created_at = Timestamp::from(self.time_provider.now());
+        // The sequence number is allocated by atomically incrementing the table's counter row,
+        // so concurrent deletes of the same table never allocate the same sequence number.
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+WITH seq AS (
+    INSERT INTO tombstone_sequence ( table_id, sequence_number )
+    VALUES ( $1, 1 )
+    ON CONFLICT (table_id)
+    DO UPDATE SET sequence_number = tombstone_sequence.sequence_number + 1
+    RETURNING sequence_number
+)
+INSERT INTO tombstone
+    ( table_id, shard_id, sequence_number, min_time, max_time, serialized_predicate, created_at )
+SELECT $1, $2, seq.sequence_number, $3, $4, $5, $6
+FROM seq
+RETURNING *;
+        "#,
+        )
+        .bind(table_id) // $1
+        .bind(shard_id) // $2
+        .bind(min_time) // $3
+        .bind(max_time) // $4
+        .bind(predicate) // $5
+        .bind(created_at) // $6
+        .fetch_one(&mut self.inner)
+        .await
+        .map_err(|e| {
+            if is_fk_violation(&e) {
+                Error::ForeignKeyViolation { source: e }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })
+    }
+
+    async fn list_by_table(&mut self, table_id: TableId) -> Result<Vec<Tombstone>> {
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+SELECT *
+FROM tombstone
+WHERE table_id = $1
+ORDER BY sequence_number;
+        "#,
+        )
+        .bind(table_id) // $1
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+}
+
 /// The error code returned by Postgres for a unique constraint violation.
 ///
 /// See <https://www.postgresql.org/docs/9.2/errcodes-appendix.html>
@@ -2455,6 +2791,7 @@ mod tests {
             created_at: time_now,
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: time_now,
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         };
         let f1 = postgres
             .repositories()
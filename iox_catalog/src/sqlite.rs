@@ -5,7 +5,7 @@ use crate::{
         self, sealed::TransactionFinalize, CasFailure, Catalog, ColumnRepo,
         ColumnTypeMismatchSnafu, Error, NamespaceRepo, ParquetFileRepo, PartitionRepo,
         QueryPoolRepo, RepoCollection, Result, ShardRepo, SoftDeletedRows, TableRepo,
-        TopicMetadataRepo, Transaction,
+        TombstoneRepo, TopicMetadataRepo, Transaction,
     },
     metrics::MetricDecorator,
     DEFAULT_MAX_COLUMNS_PER_TABLE, DEFAULT_MAX_TABLES, SHARED_TOPIC_ID, SHARED_TOPIC_NAME,
@@ -14,8 +14,9 @@ use async_trait::async_trait;
 use data_types::{
     Column, ColumnId, ColumnSet, ColumnType, CompactionLevel, Namespace, NamespaceId, ParquetFile,
     ParquetFileId, ParquetFileParams, Partition, PartitionId, PartitionKey, PartitionParam,
-    QueryPool, QueryPoolId, SequenceNumber, Shard, ShardId, ShardIndex, SkippedCompaction, Table,
-    TableId, Timestamp, TopicId, TopicMetadata, TRANSITION_SHARD_ID, TRANSITION_SHARD_INDEX,
+    PartitionTemplate, QueryPool, QueryPoolId, SequenceNumber, Shard, ShardId, ShardIndex,
+    SkippedCompaction, Table, TableId, Timestamp, Tombstone, TopicId, TopicMetadata,
+    TRANSITION_SHARD_ID, TRANSITION_SHARD_INDEX,
 };
 use serde::{Deserialize, Serialize};
 use std::ops::Deref;
@@ -334,6 +335,10 @@ impl RepoCollection for SqliteTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
 }
 
 #[async_trait]
@@ -566,6 +571,52 @@ RETURNING *;
         Ok(namespace)
     }
 
+    async fn update_lines_per_second_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"UPDATE namespace SET max_lines_per_second = $1 WHERE name = $2 RETURNING *;"#,
+        )
+        .bind(new_max) // $1
+        .bind(name) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_bytes_per_second_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"UPDATE namespace SET max_bytes_per_second = $1 WHERE name = $2 RETURNING *;"#,
+        )
+        .bind(new_max) // $1
+        .bind(name) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
     async fn update_retention_period(
         &mut self,
         name: &str,
@@ -588,6 +639,29 @@ RETURNING *;
 
         Ok(namespace)
     }
+
+    async fn update_partition_template(
+        &mut self,
+        name: &str,
+        partition_template: Option<PartitionTemplate>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"UPDATE namespace SET partition_template = $1 WHERE name = $2 RETURNING *;"#,
+        )
+        .bind(partition_template) // $1
+        .bind(name) // $2
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
 }
 
 #[async_trait]
@@ -602,17 +676,20 @@ impl TableRepo for SqliteTxn {
         // By using SELECT rather than VALUES it will insert zero rows if it finds a null in the
         // subquery, i.e. if count >= max_tables. fetch_one() will return a RowNotFound error if
         // nothing was inserted. Not pretty!
+        //
+        // Soft-deleted tables do not count towards the limit, and are revived on conflict.
         let rec = sqlx::query_as::<_, Table>(
             r#"
 INSERT INTO table_name ( name, namespace_id )
 SELECT $1, id FROM (
     SELECT namespace.id AS id, max_tables, COUNT(table_name.id) AS count
-    FROM namespace LEFT JOIN table_name ON namespace.id = table_name.namespace_id
+    FROM namespace LEFT JOIN table_name
+        ON namespace.id = table_name.namespace_id AND table_name.deleted_at IS NULL
     WHERE namespace.id = $2
     GROUP BY namespace.max_tables, table_name.namespace_id, namespace.id
 ) AS get_count WHERE count < max_tables
 ON CONFLICT (namespace_id, name)
-DO UPDATE SET name = table_name.name
+DO UPDATE SET name = table_name.name, deleted_at = NULL
 RETURNING *;
         "#,
         )
@@ -637,6 +714,62 @@ RETURNING *;
         Ok(rec)
     }
 
+    async fn create(
+        &mut self,
+        name: &str,
+        partition_template: Option<PartitionTemplate>,
+        namespace_id: NamespaceId,
+    ) -> Result<Table> {
+        // See `create_or_get` for why the table limit is checked in a subquery. A soft-deleted
+        // table of the same name is revived with the new partition template, while a conflict
+        // with a live table updates (and returns) no rows.
+        let rec = sqlx::query_as::<_, Table>(
+            r#"
+INSERT INTO table_name ( name, namespace_id, partition_template )
+SELECT $1, id, $3 FROM (
+    SELECT namespace.id AS id, max_tables, COUNT(table_name.id) AS count
+    FROM namespace LEFT JOIN table_name
+        ON namespace.id = table_name.namespace_id AND table_name.deleted_at IS NULL
+    WHERE namespace.id = $2
+    GROUP BY namespace.max_tables, table_name.namespace_id, namespace.id
+) AS get_count WHERE count < max_tables
+ON CONFLICT (namespace_id, name)
+DO UPDATE SET deleted_at = NULL, partition_template = excluded.partition_template
+WHERE table_name.deleted_at IS NOT NULL
+RETURNING *;
+        "#,
+        )
+        .bind(name) // $1
+        .bind(namespace_id) // $2
+        .bind(partition_template) // $3
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        let rec = match rec {
+            Ok(v) => v,
+            Err(sqlx::Error::RowNotFound) => {
+                // Either the table limit was reached, or a live table already exists.
+                if self
+                    .get_by_namespace_and_name(namespace_id, name)
+                    .await?
+                    .is_some()
+                {
+                    return Err(Error::NameExists {
+                        name: name.to_string(),
+                    });
+                }
+                return Err(Error::TableCreateLimitError {
+                    table_name: name.to_string(),
+                    namespace_id,
+                });
+            }
+            Err(e) if is_fk_violation(&e) => return Err(Error::ForeignKeyViolation { source: e }),
+            Err(e) => return Err(Error::SqlxError { source: e }),
+        };
+
+        Ok(rec)
+    }
+
     async fn get_by_id(&mut self, table_id: TableId) -> Result<Option<Table>> {
         let rec = sqlx::query_as::<_, Table>(
             r#"
@@ -667,7 +800,7 @@ WHERE id = $1;
             r#"
 SELECT *
 FROM table_name
-WHERE namespace_id = $1 AND name = $2;
+WHERE namespace_id = $1 AND name = $2 AND deleted_at IS NULL;
             "#,
         )
         .bind(namespace_id) // $1
@@ -689,7 +822,7 @@ WHERE namespace_id = $1 AND name = $2;
             r#"
 SELECT *
 FROM table_name
-WHERE namespace_id = $1;
+WHERE namespace_id = $1 AND deleted_at IS NULL;
             "#,
         )
         .bind(namespace_id)
@@ -701,13 +834,67 @@ WHERE namespace_id = $1;
     }
 
     async fn list(&mut self) -> Result<Vec<Table>> {
-        let rec = sqlx::query_as::<_, Table>("SELECT * FROM table_name;")
+        let rec = sqlx::query_as::<_, Table>("SELECT * FROM table_name WHERE deleted_at IS NULL;")
             .fetch_all(self.inner.get_mut())
             .await
             .map_err(|e| Error::SqlxError { source: e })?;
 
         Ok(rec)
     }
+
+    async fn soft_delete(&mut self, table_id: TableId) -> Result<()> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+
+        let deleted = sqlx::query(
+            r#"UPDATE table_name SET deleted_at=$1 WHERE id = $2 AND deleted_at IS NULL;"#,
+        )
+        .bind(flagged_at) // $1
+        .bind(table_id) // $2
+        .execute(self.inner.get_mut())
+        .await
+        .context(interface::CouldNotDeleteTableSnafu)?;
+
+        if deleted.rows_affected() == 0 {
+            return Ok(());
+        }
+
+        // Flag the files of the table, so that reviving the table afterwards does not revive
+        // its data.
+        sqlx::query(
+            r#"UPDATE parquet_file SET to_delete = $1 WHERE table_id = $2 AND to_delete IS NULL;"#,
+        )
+        .bind(flagged_at) // $1
+        .bind(table_id) // $2
+        .execute(self.inner.get_mut())
+        .await
+        .context(interface::CouldNotDeleteTableSnafu)
+        .map(|_| ())
+    }
+
+    async fn rename(&mut self, table_id: TableId, new_name: &str) -> Result<Table> {
+        let rec = sqlx::query_as::<_, Table>(
+            r#"
+UPDATE table_name
+SET name = $1
+WHERE id = $2 AND deleted_at IS NULL
+RETURNING *;
+        "#,
+        )
+        .bind(new_name) // $1
+        .bind(table_id) // $2
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        let table = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::TableNotFound { id: table_id },
+            _ if is_unique_violation(&e) => Error::NameExists {
+                name: new_name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(table)
+    }
 }
 
 #[async_trait]
@@ -724,11 +911,12 @@ INSERT INTO column_name ( name, table_id, column_type )
 SELECT $1, table_id, $3 FROM (
     SELECT max_columns_per_table, namespace.id, table_name.id as table_id, COUNT(column_name.id) AS count
     FROM namespace LEFT JOIN table_name ON namespace.id = table_name.namespace_id
-                   LEFT JOIN column_name ON table_name.id = column_name.table_id
+                   LEFT JOIN column_name
+                       ON table_name.id = column_name.table_id AND column_name.deleted_at IS NULL
     WHERE table_name.id = $2
     GROUP BY namespace.max_columns_per_table, namespace.id, table_name.id
 ) AS get_count WHERE count < max_columns_per_table
-ON CONFLICT (table_id, name)
+ON CONFLICT (table_id, name) WHERE deleted_at IS NULL
 DO UPDATE SET name = column_name.name
 RETURNING *;
         "#,
@@ -768,7 +956,9 @@ RETURNING *;
             r#"
 SELECT column_name.* FROM table_name
 INNER JOIN column_name on column_name.table_id = table_name.id
-WHERE table_name.namespace_id = $1;
+WHERE table_name.namespace_id = $1
+AND table_name.deleted_at IS NULL
+AND column_name.deleted_at IS NULL;
             "#,
         )
         .bind(namespace_id)
@@ -783,7 +973,7 @@ WHERE table_name.namespace_id = $1;
         let rec = sqlx::query_as::<_, Column>(
             r#"
 SELECT * FROM column_name
-WHERE table_id = $1;
+WHERE table_id = $1 AND deleted_at IS NULL;
             "#,
         )
         .bind(table_id)
@@ -794,11 +984,22 @@ WHERE table_id = $1;
         Ok(rec)
     }
 
+    async fn lock_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Column>> {
+        // SQLite serialises transactions that write, so no row lock is needed.
+        self.list_by_table_id(table_id).await
+    }
+
     async fn list(&mut self) -> Result<Vec<Column>> {
-        let rec = sqlx::query_as::<_, Column>("SELECT * FROM column_name;")
-            .fetch_all(self.inner.get_mut())
-            .await
-            .map_err(|e| Error::SqlxError { source: e })?;
+        let rec = sqlx::query_as::<_, Column>(
+            r#"
+SELECT column_name.* FROM column_name
+INNER JOIN table_name on column_name.table_id = table_name.id
+WHERE table_name.deleted_at IS NULL AND column_name.deleted_at IS NULL;
+            "#,
+        )
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
 
         Ok(rec)
     }
@@ -836,7 +1037,7 @@ INSERT INTO column_name ( name, table_id, column_type )
 SELECT a.value ->> 'name' AS name, $1, a.value ->> 'column_type' AS column_type
 FROM json_each($2) as a
 ORDER BY name
-ON CONFLICT (table_id, name)
+ON CONFLICT (table_id, name) WHERE deleted_at IS NULL
 DO UPDATE SET name = column_name.name
 RETURNING *;
             "#,
@@ -869,6 +1070,43 @@ RETURNING *;
 
         Ok(out)
     }
+
+    async fn soft_delete(&mut self, column_id: ColumnId) -> Result<()> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+
+        sqlx::query(r#"UPDATE column_name SET deleted_at=$1 WHERE id = $2 AND deleted_at IS NULL;"#)
+            .bind(flagged_at) // $1
+            .bind(column_id) // $2
+            .execute(self.inner.get_mut())
+            .await
+            .context(interface::CouldNotDeleteColumnSnafu)
+            .map(|_| ())
+    }
+
+    async fn rename(&mut self, column_id: ColumnId, new_name: &str) -> Result<Column> {
+        let rec = sqlx::query_as::<_, Column>(
+            r#"
+UPDATE column_name
+SET name = $1
+WHERE id = $2 AND deleted_at IS NULL
+RETURNING *;
+        "#,
+        )
+        .bind(new_name) // $1
+        .bind(column_id) // $2
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        let column = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::ColumnNotFound { id: column_id },
+            _ if is_unique_violation(&e) => Error::NameExists {
+                name: new_name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(column)
+    }
 }
 
 #[async_trait]
@@ -1326,6 +1564,7 @@ struct ParquetFilePod {
     created_at: Timestamp,
     column_set: Json<Vec<i64>>,
     max_l0_created_at: Timestamp,
+    max_tombstone_sequence_number: SequenceNumber,
 }
 
 impl From<ParquetFilePod> for ParquetFile {
@@ -1347,6 +1586,7 @@ impl From<ParquetFilePod> for ParquetFile {
             created_at: value.created_at,
             column_set: to_column_set(&value.column_set),
             max_l0_created_at: value.max_l0_created_at,
+            max_tombstone_sequence_number: value.max_tombstone_sequence_number,
         }
     }
 }
@@ -1369,6 +1609,7 @@ impl ParquetFileRepo for SqliteTxn {
             created_at,
             column_set,
             max_l0_created_at,
+            max_tombstone_sequence_number,
         } = parquet_file_params;
 
         let rec = sqlx::query_as::<_, ParquetFilePod>(
@@ -1376,8 +1617,9 @@ impl ParquetFileRepo for SqliteTxn {
 INSERT INTO parquet_file (
     shard_id, table_id, partition_id, object_store_id,
     max_sequence_number, min_time, max_time, file_size_bytes,
-    row_count, compaction_level, created_at, namespace_id, column_set, max_l0_created_at )
-VALUES ( $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14 )
+    row_count, compaction_level, created_at, namespace_id, column_set, max_l0_created_at,
+    max_tombstone_sequence_number )
+VALUES ( $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15 )
 RETURNING *;
         "#,
         )
@@ -1395,6 +1637,7 @@ RETURNING *;
         .bind(namespace_id) // $12
         .bind(from_column_set(&column_set)) // $13
         .bind(max_l0_created_at) // $14
+        .bind(max_tombstone_sequence_number) // $15
         .fetch_one(self.inner.get_mut())
         .await
         .map_err(|e| {
@@ -1447,6 +1690,28 @@ RETURNING *;
         Ok(flagged)
     }
 
+    async fn flag_for_delete_by_deleted_tables(&mut self) -> Result<Vec<ParquetFileId>> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+        let flagged = sqlx::query(
+            r#"
+                UPDATE parquet_file
+                SET to_delete = $1
+                FROM table_name
+                WHERE table_name.deleted_at IS NOT NULL
+                AND parquet_file.to_delete IS NULL
+                AND table_name.id = parquet_file.table_id
+                RETURNING parquet_file.id;
+            "#,
+        )
+        .bind(flagged_at) // $1
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        let flagged = flagged.into_iter().map(|row| row.get("id")).collect();
+        Ok(flagged)
+    }
+
     async fn list_by_namespace_not_to_delete(
         &mut self,
         namespace_id: NamespaceId,
@@ -1460,7 +1725,7 @@ SELECT parquet_file.id, parquet_file.shard_id, parquet_file.namespace_id,
        parquet_file.max_sequence_number, parquet_file.min_time,
        parquet_file.max_time, parquet_file.to_delete, parquet_file.file_size_bytes,
        parquet_file.row_count, parquet_file.compaction_level, parquet_file.created_at, parquet_file.column_set,
-       parquet_file.max_l0_created_at
+       parquet_file.max_l0_created_at, parquet_file.max_tombstone_sequence_number
 FROM parquet_file
 INNER JOIN table_name on table_name.id = parquet_file.table_id
 WHERE table_name.namespace_id = $1
@@ -1483,7 +1748,8 @@ WHERE table_name.namespace_id = $1
             r#"
 SELECT id, shard_id, namespace_id, table_id, partition_id, object_store_id,
        max_sequence_number, min_time, max_time, to_delete, file_size_bytes,
-       row_count, compaction_level, created_at, column_set, max_l0_created_at
+       row_count, compaction_level, created_at, column_set, max_l0_created_at,
+       max_tombstone_sequence_number
 FROM parquet_file
 WHERE table_id = $1 AND to_delete IS NULL;
              "#,
@@ -1504,7 +1770,8 @@ WHERE table_id = $1 AND to_delete IS NULL;
             r#"
 SELECT id, shard_id, namespace_id, table_id, partition_id, object_store_id,
        max_sequence_number, min_time, max_time, to_delete, file_size_bytes,
-       row_count, compaction_level, created_at, column_set, max_l0_created_at
+       row_count, compaction_level, created_at, column_set, max_l0_created_at,
+       max_tombstone_sequence_number
 FROM parquet_file
 WHERE table_id = $1;
              "#,
@@ -1553,7 +1820,8 @@ RETURNING id;
             r#"
 SELECT id, shard_id, namespace_id, table_id, partition_id, object_store_id,
        max_sequence_number, min_time, max_time, to_delete, file_size_bytes,
-       row_count, compaction_level, created_at, column_set, max_l0_created_at
+       row_count, compaction_level, created_at, column_set, max_l0_created_at,
+       max_tombstone_sequence_number
 FROM parquet_file
 WHERE parquet_file.partition_id = $1
   AND parquet_file.to_delete IS NULL;
@@ -1626,7 +1894,8 @@ RETURNING id;
             r#"
 SELECT id, shard_id, namespace_id, table_id, partition_id, object_store_id,
        max_sequence_number, min_time, max_time, to_delete, file_size_bytes,
-       row_count, compaction_level, created_at, column_set, max_l0_created_at
+       row_count, compaction_level, created_at, column_set, max_l0_created_at,
+       max_tombstone_sequence_number
 FROM parquet_file
 WHERE object_store_id = $1;
              "#,
@@ -1645,6 +1914,82 @@ WHERE object_store_id = $1;
     }
 }
 
+#[async_trait]
+impl TombstoneRepo for SqliteTxn {
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        shard_id: ShardId,
+        min_time: Timestamp,
+        max_time: Timestamp,
+        predicate: &str,
+    ) -> Result<Tombstone> {
+        let created_at = Timestamp::from(self.time_provider.now());
+        // The sequence number is allocated by atomically incrementing the table's counter row,
+        // so concurrent deletes of the same table never allocate the same sequence number.
+        let rec = sqlx::query(
+            r#"
+INSERT INTO tombstone_sequence ( table_id, sequence_number )
+VALUES ( $1, 1 )
+ON CONFLICT (table_id)
+DO UPDATE SET sequence_number = tombstone_sequence.sequence_number + 1
+RETURNING sequence_number;
+        "#,
+        )
+        .bind(table_id) // $1
+        .fetch_one(self.inner.get_mut())
+        .await
+        .map_err(|e| {
+            if is_fk_violation(&e) {
+                Error::ForeignKeyViolation { source: e }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })?;
+        let sequence_number = rec.get::<i64, _>("sequence_number");
+
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+INSERT INTO tombstone
+    ( table_id, shard_id, sequence_number, min_time, max_time, serialized_predicate, created_at )
+VALUES ( $1, $2, $3, $4, $5, $6, $7 )
+RETURNING *;
+        "#,
+        )
+        .bind(table_id) // $1
+        .bind(shard_id) // $2
+        .bind(sequence_number) // $3
+        .bind(min_time) // $4
+        .bind(max_time) // $5
+        .bind(predicate) // $6
+        .bind(created_at) // $7
+        .fetch_one(self.inner.get_mut())
+        .await
+        .map_err(|e| {
+            if is_fk_violation(&e) {
+                Error::ForeignKeyViolation { source: e }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })
+    }
+
+    async fn list_by_table(&mut self, table_id: TableId) -> Result<Vec<Tombstone>> {
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+SELECT *
+FROM tombstone
+WHERE table_id = $1
+ORDER BY sequence_number;
+        "#,
+        )
+        .bind(table_id) // $1
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+}
+
 /// The error code returned by SQLite for a unique constraint violation.
 ///
 /// See <https://sqlite.org/rescode.html#constraint_unique>
@@ -2073,6 +2418,7 @@ mod tests {
             created_at: time_now,
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: time_now,
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         };
         let f1 = sqlite
             .repositories()
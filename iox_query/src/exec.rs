@@ -33,6 +33,7 @@ pub use context::{IOxSessionConfig, IOxSessionContext, SessionContextIOxExt};
 use schema_pivot::SchemaPivotNode;
 
 use self::{non_null_checker::NonNullCheckerNode, split::StreamSplitNode};
+use crate::QueryNamespaceFilter;
 
 /// Configuration for an Executor
 #[derive(Debug, Clone)]
@@ -343,6 +344,14 @@ pub fn make_stream_split(input: LogicalPlan, split_exprs: Vec<Expr>) -> LogicalP
 pub trait ExecutionContextProvider {
     /// Returns a new execution context suitable for running queries
     fn new_query_context(&self, span_ctx: Option<trace::ctx::SpanContext>) -> IOxSessionContext;
+
+    /// Returns a new execution context like [`new_query_context`](Self::new_query_context),
+    /// whose queries discover the namespaces accepted by `filter`.
+    fn new_query_context_with_filter(
+        &self,
+        span_ctx: Option<trace::ctx::SpanContext>,
+        filter: Arc<dyn QueryNamespaceFilter>,
+    ) -> IOxSessionContext;
 }
 
 #[cfg(test)]
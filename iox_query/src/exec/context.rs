@@ -29,6 +29,7 @@ use crate::{
         seriesset::{SeriesSetPlan, SeriesSetPlans},
         stringset::StringSetPlan,
     },
+    QueryNamespaceDeleter, QueryNamespaceFilter,
 };
 use arrow::record_batch::RecordBatch;
 use async_trait::async_trait;
@@ -179,8 +180,22 @@ pub struct IOxSessionConfig {
 
     /// Span context from which to create spans for this query
     span_ctx: Option<SpanContext>,
+
+    /// Deleter used to execute delete statements
+    deleter: Option<Arc<dyn QueryNamespaceDeleter>>,
+
+    /// Name of the namespace the query is run against, if any
+    namespace_name: Option<QueryNamespaceName>,
+
+    /// Filter of the namespaces the query may discover
+    namespace_filter: Option<Arc<dyn QueryNamespaceFilter>>,
 }
 
+/// The name of the namespace a query is run against, as registered with the
+/// DataFusion session.
+#[derive(Debug, Clone)]
+struct QueryNamespaceName(String);
+
 impl fmt::Debug for IOxSessionConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "IOxSessionConfig ...")
@@ -201,6 +216,9 @@ impl IOxSessionConfig {
             runtime,
             default_catalog: None,
             span_ctx: None,
+            deleter: None,
+            namespace_name: None,
+            namespace_filter: None,
         }
     }
 
@@ -225,6 +243,31 @@ impl IOxSessionConfig {
         Self { span_ctx, ..self }
     }
 
+    /// Set the deleter used to execute delete statements, such as the InfluxQL `DELETE`.
+    pub fn with_deleter(self, deleter: Arc<dyn QueryNamespaceDeleter>) -> Self {
+        Self {
+            deleter: Some(deleter),
+            ..self
+        }
+    }
+
+    /// Set the name of the namespace the query is run against.
+    pub fn with_namespace_name(self, namespace_name: impl Into<String>) -> Self {
+        Self {
+            namespace_name: Some(QueryNamespaceName(namespace_name.into())),
+            ..self
+        }
+    }
+
+    /// Set the filter of the namespaces the query may discover, such as with
+    /// the InfluxQL `SHOW DATABASES`.
+    pub fn with_namespace_filter(self, namespace_filter: Arc<dyn QueryNamespaceFilter>) -> Self {
+        Self {
+            namespace_filter: Some(namespace_filter),
+            ..self
+        }
+    }
+
     /// Set DataFusion [config option].
     ///
     /// May be used to set [IOx-specific] option as well.
@@ -251,9 +294,18 @@ impl IOxSessionConfig {
         let recorder = SpanRecorder::new(maybe_span);
 
         // attach span to DataFusion session
-        let session_config = self
+        let mut session_config = self
             .session_config
             .with_extension(Arc::new(recorder.span().cloned()));
+        if let Some(deleter) = self.deleter {
+            session_config = session_config.with_extension(Arc::new(deleter));
+        }
+        if let Some(namespace_name) = self.namespace_name {
+            session_config = session_config.with_extension(Arc::new(namespace_name));
+        }
+        if let Some(namespace_filter) = self.namespace_filter {
+            session_config = session_config.with_extension(Arc::new(namespace_filter));
+        }
 
         let state = SessionState::with_config_rt(session_config, self.runtime)
             .with_query_planner(Arc::new(IOxQueryPlanner {}));
@@ -688,6 +740,30 @@ impl IOxSessionContext {
         )
     }
 
+    /// Returns the deleter registered with this context, if any.
+    pub fn deleter(&self) -> Option<Arc<dyn QueryNamespaceDeleter>> {
+        self.inner
+            .copied_config()
+            .get_extension::<Arc<dyn QueryNamespaceDeleter>>()
+            .map(|deleter| Arc::clone(deleter.as_ref()))
+    }
+
+    /// Returns the name of the namespace the query is run against, if any.
+    pub fn namespace_name(&self) -> Option<String> {
+        self.inner
+            .copied_config()
+            .get_extension::<QueryNamespaceName>()
+            .map(|name| name.0.clone())
+    }
+
+    /// Returns the filter of the namespaces the query may discover, if any.
+    pub fn namespace_filter(&self) -> Option<Arc<dyn QueryNamespaceFilter>> {
+        self.inner
+            .copied_config()
+            .get_extension::<Arc<dyn QueryNamespaceFilter>>()
+            .map(|filter| Arc::clone(filter.as_ref()))
+    }
+
     /// Record an event on the span recorder
     pub fn record_event(&mut self, name: &'static str) {
         self.recorder.event(name);
@@ -721,6 +797,9 @@ pub trait SessionContextIOxExt {
 
     /// Get span context
     fn span_ctx(&self) -> Option<SpanContext>;
+
+    /// Get the filter of the namespaces the query may discover, if any.
+    fn namespace_filter(&self) -> Option<Arc<dyn QueryNamespaceFilter>>;
 }
 
 impl SessionContextIOxExt for SessionState {
@@ -735,4 +814,10 @@ impl SessionContextIOxExt for SessionState {
             .get_extension::<Option<Span>>()
             .and_then(|span| span.as_ref().as_ref().map(|span| span.ctx.clone()))
     }
+
+    fn namespace_filter(&self) -> Option<Arc<dyn QueryNamespaceFilter>> {
+        self.config()
+            .get_extension::<Arc<dyn QueryNamespaceFilter>>()
+            .map(|filter| Arc::clone(filter.as_ref()))
+    }
 }
@@ -190,6 +190,36 @@ pub trait QueryNamespace: QueryNamespaceMeta + Debug + Send + Sync {
     fn as_meta(&self) -> &dyn QueryNamespaceMeta;
 }
 
+/// Records predicate deletes against the tables of a namespace.
+///
+/// A deleter is registered with the [`IOxSessionContext`] of a query (see
+/// [`IOxSessionContext::deleter`]) so that statements such as the InfluxQL
+/// `DELETE` can be executed.
+#[async_trait]
+pub trait QueryNamespaceDeleter: Debug + Send + Sync {
+    /// Delete all rows of the tables `table_names` that match `predicate`.
+    ///
+    /// Tables that do not exist are ignored.
+    async fn delete(
+        &self,
+        table_names: &[String],
+        predicate: &DeletePredicate,
+    ) -> Result<(), DataFusionError>;
+}
+
+/// Decides which namespaces of the catalog a query may discover, such as with
+/// the InfluxQL `SHOW DATABASES`.
+///
+/// A filter is registered with the [`IOxSessionContext`] of a query (see
+/// [`IOxSessionContext::namespace_filter`]), typically to restrict the
+/// namespaces to those the client is authorized to access. Queries without a
+/// filter only discover the namespace they are run against.
+#[async_trait]
+pub trait QueryNamespaceFilter: Debug + Send + Sync {
+    /// Return the names in `namespaces` that are visible to the query.
+    async fn filter(&self, namespaces: Vec<String>) -> Result<Vec<String>, DataFusionError>;
+}
+
 /// Raw data of a [`QueryChunk`].
 #[derive(Debug, Clone)]
 pub enum QueryChunkData {
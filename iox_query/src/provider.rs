@@ -2,8 +2,10 @@
 
 use async_trait::async_trait;
 use data_types::DeletePredicate;
-use hashbrown::HashMap;
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
 
 use arrow::{
     datatypes::{Fields, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef},
@@ -17,7 +19,7 @@ use datafusion::{
     optimizer::utils::{conjunction, split_conjunction},
     physical_plan::{
         expressions::col as physical_col, filter::FilterExec, projection::ProjectionExec,
-        sorts::sort::SortExec, ExecutionPlan,
+        sorts::sort::SortExec, union::UnionExec, ExecutionPlan,
     },
     prelude::Expr,
 };
@@ -227,8 +229,12 @@ impl TableProvider for ChunkTableProvider {
         let pk = self.iox_schema().primary_key();
         let dedup_sort_key = SortKey::from_columns(pk.iter().copied());
 
+        // Chunks usually share the same delete predicates, which are then applied after
+        // de-duplication. Chunks with differing delete predicates (e.g. tombstones that were
+        // already resolved for some of the files of a table) each get their own predicates
+        // applied before de-duplication instead.
         let mut chunks_by_delete_predicates =
-            HashMap::<&[Arc<DeletePredicate>], Vec<Arc<dyn QueryChunk>>>::new();
+            BTreeMap::<&[Arc<DeletePredicate>], Vec<Arc<dyn QueryChunk>>>::new();
         for chunk in &self.chunks {
             chunks_by_delete_predicates
                 .entry(chunk.delete_predicates())
@@ -236,28 +242,40 @@ impl TableProvider for ChunkTableProvider {
                 .push(Arc::clone(chunk));
         }
 
-        let (delete_predicates, chunks) = match chunks_by_delete_predicates.len() {
-            0 => (&[] as _, vec![]),
-            1 => chunks_by_delete_predicates
+        let (delete_predicates, plan) = if chunks_by_delete_predicates.len() <= 1 {
+            let (delete_predicates, chunks) = chunks_by_delete_predicates
                 .into_iter()
                 .next()
-                .expect("checked len"),
-            n => {
-                return Err(DataFusionError::External(
-                    format!("expected at most 1 delete predicate set, got {n}").into(),
-                ));
-            }
+                .unwrap_or((&[] as _, vec![]));
+
+            // Create data stream from chunk data. This is the most simple data stream possible and contains duplicates and
+            // has no filters at all.
+            let plan = chunks_to_physical_nodes(
+                &schema_with_chunk_order,
+                None,
+                chunks,
+                ctx.config().target_partitions(),
+            );
+            (delete_predicates, plan)
+        } else {
+            let inputs = chunks_by_delete_predicates
+                .into_iter()
+                .map(|(delete_predicates, chunks)| {
+                    let plan = chunks_to_physical_nodes(
+                        &schema_with_chunk_order,
+                        None,
+                        chunks,
+                        ctx.config().target_partitions(),
+                    );
+                    filter_deleted_rows(plan, delete_predicates)
+                })
+                .collect::<DataFusionResult<Vec<_>>>()?;
+            (
+                &[] as _,
+                Arc::new(UnionExec::new(inputs)) as Arc<dyn ExecutionPlan>,
+            )
         };
 
-        // Create data stream from chunk data. This is the most simple data stream possible and contains duplicates and
-        // has no filters at all.
-        let plan = chunks_to_physical_nodes(
-            &schema_with_chunk_order,
-            None,
-            chunks,
-            ctx.config().target_partitions(),
-        );
-
         // De-dup before doing anything else, because all logical expressions act on de-duplicated data.
         let plan = if self.deduplication {
             let sort_exprs = arrow_sort_key_exprs(&dedup_sort_key, &plan.schema());
@@ -359,6 +377,25 @@ impl TableProvider for ChunkTableProvider {
     }
 }
 
+/// Remove the rows matching any of the `delete_predicates` from the output of `plan`.
+fn filter_deleted_rows(
+    plan: Arc<dyn ExecutionPlan>,
+    delete_predicates: &[Arc<DeletePredicate>],
+) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+    let del_preds: Vec<Arc<Predicate>> = delete_predicates
+        .iter()
+        .map(|pred| Arc::new(pred.as_ref().clone().into()))
+        .collect();
+
+    match Predicate::negated_expr(&del_preds[..]) {
+        Some(expr) => Ok(Arc::new(FilterExec::try_new(
+            df_physical_expr(plan.as_ref(), expr)?,
+            plan,
+        )?)),
+        None => Ok(plan),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -811,10 +848,21 @@ mod test {
             .build()
             .unwrap();
 
-        let err = provider.scan(&state, None, &[], None).await.unwrap_err();
-        assert_eq!(
-            err.to_string(),
-            "External error: expected at most 1 delete predicate set, got 2"
+        let plan = provider.scan(&state, None, &[], None).await.unwrap();
+        insta::assert_yaml_snapshot!(
+            format_execution_plan(&plan),
+            @r###"
+        ---
+        - " ProjectionExec: expr=[field@0 as field, tag1@1 as tag1, tag2@2 as tag2, time@3 as time]"
+        - "   DeduplicateExec: [tag1@1 ASC,tag2@2 ASC,time@3 ASC]"
+        - "     UnionExec"
+        - "       FilterExec: time@3 < -9223372036854775808 OR time@3 > 100"
+        - "         UnionExec"
+        - "           RecordBatchesExec: batches_groups=1 batches=0 total_rows=0"
+        - "       FilterExec: time@3 < -9223372036854775808 OR time@3 > 200"
+        - "         UnionExec"
+        - "           ParquetExec: limit=None, partitions={1 group: [[2.parquet]]}, output_ordering=[__chunk_order@4 ASC], projection=[field, tag1, tag2, time, __chunk_order]"
+        "###
         );
     }
 }
@@ -5,10 +5,10 @@
 use crate::{
     exec::{
         stringset::{StringSet, StringSetRef},
-        ExecutionContextProvider, Executor, ExecutorType, IOxSessionContext,
+        ExecutionContextProvider, Executor, ExecutorType, IOxSessionConfig, IOxSessionContext,
     },
     Predicate, PredicateMatch, QueryChunk, QueryChunkData, QueryChunkMeta, QueryCompletedToken,
-    QueryNamespace, QueryText,
+    QueryNamespace, QueryNamespaceFilter, QueryText,
 };
 use arrow::array::{BooleanArray, Float64Array};
 use arrow::datatypes::SchemaRef;
@@ -187,8 +187,8 @@ impl QueryNamespaceMeta for TestDatabase {
     }
 }
 
-impl ExecutionContextProvider for TestDatabase {
-    fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
+impl TestDatabase {
+    fn query_config(&self, span_ctx: Option<SpanContext>) -> IOxSessionConfig {
         // Note: unlike Db this does not register a catalog provider
         self.executor
             .new_execution_config(ExecutorType::Query)
@@ -196,6 +196,21 @@ impl ExecutionContextProvider for TestDatabase {
                 self,
             )))
             .with_span_context(span_ctx)
+    }
+}
+
+impl ExecutionContextProvider for TestDatabase {
+    fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
+        self.query_config(span_ctx).build()
+    }
+
+    fn new_query_context_with_filter(
+        &self,
+        span_ctx: Option<SpanContext>,
+        filter: Arc<dyn QueryNamespaceFilter>,
+    ) -> IOxSessionContext {
+        self.query_config(span_ctx)
+            .with_namespace_filter(filter)
             .build()
     }
 }
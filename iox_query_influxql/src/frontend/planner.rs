@@ -1,4 +1,7 @@
+use arrow::array::{Int64Array, StringArray};
 use arrow::datatypes::SchemaRef;
+use influxdb_influxql_parser::delete::DeleteStatement;
+use influxdb_influxql_parser::drop::DropMeasurementStatement;
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
 use influxdb_influxql_parser::show_measurements::ShowMeasurementsStatement;
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
@@ -10,28 +13,36 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
 
-use crate::plan::{parse_regex, InfluxQLToLogicalPlan, SchemaProvider};
+use crate::plan::{
+    parse_regex, DeleteRequest, InfluxQLToLogicalPlan, NamespaceInfo, SchemaProvider,
+};
 use datafusion::common::Statistics;
 use datafusion::datasource::provider_as_source;
 use datafusion::execution::context::{SessionState, TaskContext};
 use datafusion::logical_expr::{AggregateUDF, LogicalPlan, ScalarUDF, TableSource};
 use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
 use datafusion::physical_plan::{Partitioning, SendableRecordBatchStream};
 use datafusion::{
     error::{DataFusionError, Result},
     physical_plan::ExecutionPlan,
 };
+use futures::{stream, TryStreamExt};
+use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
 use influxdb_influxql_parser::common::MeasurementName;
 use influxdb_influxql_parser::parse_statements;
 use influxdb_influxql_parser::statement::Statement;
 use influxdb_influxql_parser::visit::{Visitable, Visitor};
 use iox_query::exec::IOxSessionContext;
+use iox_query::QueryNamespaceDeleter;
 use observability_deps::tracing::debug;
-use schema::Schema;
+use schema::{Schema, INFLUXQL_METADATA_KEY};
 
 struct ContextSchemaProvider<'a> {
     state: &'a SessionState,
     tables: HashMap<String, (Arc<dyn TableSource>, Schema)>,
+    namespaces: Vec<NamespaceInfo>,
+    namespace_name: Option<String>,
 }
 
 impl<'a> SchemaProvider for ContextSchemaProvider<'a> {
@@ -61,6 +72,14 @@ impl<'a> SchemaProvider for ContextSchemaProvider<'a> {
     fn table_schema(&self, name: &str) -> Option<Schema> {
         self.tables.get(name).map(|(_, s)| s.clone())
     }
+
+    fn namespaces(&self) -> Vec<NamespaceInfo> {
+        self.namespaces.clone()
+    }
+
+    fn namespace_name(&self) -> Option<&str> {
+        self.namespace_name.as_deref()
+    }
 }
 
 /// A physical operator that overrides the `schema` API,
@@ -118,6 +137,102 @@ impl ExecutionPlan for SchemaExec {
     }
 }
 
+/// A physical operator that deletes the rows described by a [`DeleteRequest`]
+/// when it is executed, before streaming the results of its input.
+///
+/// Deferring the delete to execution ensures planning a statement, for example
+/// to determine its schema or permissions, never changes the catalog.
+struct DeleteExec {
+    input: Arc<dyn ExecutionPlan>,
+    deleter: Arc<dyn QueryNamespaceDeleter>,
+    request: DeleteRequest,
+}
+
+impl Debug for DeleteExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DeleteExec")
+    }
+}
+
+impl ExecutionPlan for DeleteExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        // The delete must only be executed once
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![Arc::clone(&self.input)]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match children.as_slice() {
+            [input] => Ok(Arc::new(Self {
+                input: Arc::clone(input),
+                deleter: Arc::clone(&self.deleter),
+                request: self.request.clone(),
+            })),
+            _ => Err(DataFusionError::Internal(format!(
+                "DeleteExec expects exactly one child, got {}",
+                children.len()
+            ))),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "DeleteExec invalid partition {partition}"
+            )));
+        }
+
+        let input = Arc::clone(&self.input);
+        let deleter = Arc::clone(&self.deleter);
+        let DeleteRequest {
+            table_names,
+            predicate,
+        } = self.request.clone();
+
+        let stream = stream::once(async move {
+            debug!(
+                tables=?table_names,
+                predicate=?predicate,
+                "executing InfluxQL delete"
+            );
+            deleter.delete(&table_names, &predicate).await?;
+            input.execute(0, context)
+        })
+        .try_flatten();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.input.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
 /// Create plans for running InfluxQL queries against databases
 #[derive(Debug, Default)]
 pub struct InfluxQLQueryPlanner {}
@@ -137,9 +252,57 @@ impl InfluxQLQueryPlanner {
         debug!(text=%query, "planning InfluxQL query");
 
         let statement = self.query_to_statement(query)?;
-        let logical_plan = self.statement_to_plan(statement, ctx).await?;
+        self.statement_to_physical_plan(statement, 0, ctx).await
+    }
 
-        let input = ctx.create_physical_plan(&logical_plan).await?;
+    /// Plan each statement of an InfluxQL query, which may contain multiple
+    /// statements separated by `;`, against the catalogs registered with `ctx`.
+    ///
+    /// The plans are returned in the order of the statements, such that the
+    /// index of each plan is the `statement_id` recorded in its
+    /// [`InfluxQlMetadata`]. A statement that fails to plan does not prevent
+    /// the remaining statements from being planned.
+    pub async fn query_statements(
+        &self,
+        query: &str,
+        ctx: &IOxSessionContext,
+    ) -> Result<Vec<Result<Arc<dyn ExecutionPlan>>>> {
+        debug!(text=%query, "planning InfluxQL query");
+
+        let statements =
+            parse_statements(query).map_err(|e| DataFusionError::Plan(e.to_string()))?;
+
+        let mut plans = Vec::with_capacity(statements.len());
+        for (statement_id, statement) in statements.into_iter().enumerate() {
+            plans.push(
+                self.statement_to_physical_plan(statement, statement_id, ctx)
+                    .await,
+            );
+        }
+        Ok(plans)
+    }
+
+    async fn statement_to_physical_plan(
+        &self,
+        statement: Statement,
+        statement_id: usize,
+        ctx: &IOxSessionContext,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let (logical_plan, delete_request) = self.statement_to_plan(statement, ctx).await?;
+
+        let mut input = ctx.create_physical_plan(&logical_plan).await?;
+        if let Some(request) = delete_request {
+            let deleter = ctx.deleter().ok_or_else(|| {
+                DataFusionError::NotImplemented(
+                    "deleting data is not supported by this service".to_string(),
+                )
+            })?;
+            input = Arc::new(DeleteExec {
+                input,
+                deleter,
+                request,
+            });
+        }
 
         // Merge schema-level metadata from the logical plan with the
         // schema from the physical plan, as it is not propagated through the
@@ -147,6 +310,7 @@ impl InfluxQLQueryPlanner {
         let input_schema = input.schema();
         let mut md = input_schema.metadata().clone();
         md.extend(logical_plan.schema().metadata().clone());
+        set_statement_id(&mut md, statement_id)?;
         let schema = Arc::new(arrow::datatypes::Schema::new_with_metadata(
             input_schema.fields().clone(),
             md,
@@ -155,11 +319,66 @@ impl InfluxQLQueryPlanner {
         Ok(Arc::new(SchemaExec { input, schema }))
     }
 
+    /// Plan an InfluxQL query against the catalogs registered with `ctx`, and
+    /// return the schema of its results.
+    ///
+    /// Statements that delete data, such as `DELETE` and `DROP MEASUREMENT`,
+    /// are planned without being executed.
+    pub async fn query_schema(&self, query: &str, ctx: &IOxSessionContext) -> Result<SchemaRef> {
+        debug!(text=%query, "planning InfluxQL query schema");
+
+        let statement = self.query_to_statement(query)?;
+        let (logical_plan, _) = self.statement_to_plan(statement, ctx).await?;
+        Ok(Arc::new(logical_plan.schema().as_ref().into()))
+    }
+
+    /// Returns `true` if any statement of the InfluxQL `query` deletes data,
+    /// such as `DELETE` or `DROP MEASUREMENT`.
+    ///
+    /// Callers use this to require write, rather than read, permission to run
+    /// the query. A query that fails to parse deletes no data, as it is
+    /// rejected when it is planned.
+    pub fn query_deletes_data(query: &str) -> bool {
+        parse_statements(query)
+            .map(|statements| {
+                statements.iter().any(|statement| {
+                    matches!(
+                        statement,
+                        Statement::Delete(_) | Statement::DropMeasurement(_)
+                    )
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    /// Returns `true` if any statement of the InfluxQL `query` must be run
+    /// against a database.
+    ///
+    /// Only `SHOW DATABASES` and `SHOW RETENTION POLICIES ON <database>` can
+    /// be run without one. A query that fails to parse does not require a
+    /// database, so that the parse error is reported when it is planned.
+    pub fn query_requires_database(query: &str) -> bool {
+        parse_statements(query)
+            .map(|statements| {
+                statements.iter().any(|statement| match statement {
+                    Statement::ShowDatabases(_) => false,
+                    Statement::ShowRetentionPolicies(s) => s.database.is_none(),
+                    _ => true,
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    /// Plan `statement` against the catalogs registered with `ctx`.
+    ///
+    /// Statements that delete data are not executed as part of planning; the
+    /// returned [`DeleteRequest`] describes the rows to delete when the plan
+    /// is executed.
     async fn statement_to_plan(
         &self,
         statement: Statement,
         ctx: &IOxSessionContext,
-    ) -> Result<LogicalPlan> {
+    ) -> Result<(LogicalPlan, Option<DeleteRequest>)> {
         use std::collections::hash_map::Entry;
 
         let session_cfg = ctx.inner().copied_config();
@@ -183,9 +402,20 @@ impl InfluxQLQueryPlanner {
         let names = schema.table_names();
         let query_tables = find_all_measurements(&statement, &names)?;
 
+        // Only statements that report databases or retention policies require the
+        // namespaces of the catalog, so avoid reading them otherwise.
+        let namespaces = match &statement {
+            Statement::ShowDatabases(_) | Statement::ShowRetentionPolicies(_) => {
+                find_namespaces(ctx).await?
+            }
+            _ => vec![],
+        };
+
         let mut sp = ContextSchemaProvider {
             state: &ctx.inner().state(),
             tables: HashMap::with_capacity(query_tables.len()),
+            namespaces,
+            namespace_name: ctx.namespace_name(),
         };
 
         for table_name in &query_tables {
@@ -201,9 +431,13 @@ impl InfluxQLQueryPlanner {
         }
 
         let planner = InfluxQLToLogicalPlan::new(&sp, ctx);
+        if let Some(request) = planner.statement_to_delete_request(&statement)? {
+            return Ok((planner.delete_result_plan()?, Some(request)));
+        }
+
         let logical_plan = planner.statement_to_plan(statement)?;
         debug!(plan=%logical_plan.display_graphviz(), "logical plan");
-        Ok(logical_plan)
+        Ok((logical_plan, None))
     }
 
     fn query_to_statement(&self, query: &str) -> Result<Statement> {
@@ -220,6 +454,62 @@ impl InfluxQLQueryPlanner {
     }
 }
 
+/// Record the `statement_id` in the [`InfluxQlMetadata`] of the schema metadata, `md`,
+/// if present.
+fn set_statement_id(md: &mut HashMap<String, String>, statement_id: usize) -> Result<()> {
+    let Some(data) = md.get_mut(INFLUXQL_METADATA_KEY) else { return Ok(()) };
+
+    let mut metadata: InfluxQlMetadata = serde_json::from_str(data).map_err(|err| {
+        DataFusionError::Internal(format!("error deserializing InfluxQL metadata: {err}"))
+    })?;
+    metadata.statement_id = u32::try_from(statement_id)
+        .map_err(|_| DataFusionError::Plan(format!("too many statements: {statement_id}")))?;
+    *data = serde_json::to_string(&metadata).map_err(|err| {
+        DataFusionError::Internal(format!("error serializing InfluxQL metadata: {err}"))
+    })?;
+
+    Ok(())
+}
+
+/// The system table that lists the namespaces visible to the query.
+const NAMESPACES_TABLE: &str = "system.namespaces";
+
+/// Read the namespaces visible to the query from the [`NAMESPACES_TABLE`] system table.
+async fn find_namespaces(ctx: &IOxSessionContext) -> Result<Vec<NamespaceInfo>> {
+    let batches = ctx.inner().table(NAMESPACES_TABLE).await?.collect().await?;
+
+    let mut namespaces = vec![];
+    for batch in batches {
+        let names = batch
+            .column_by_name("name")
+            .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+            .ok_or_else(|| {
+                DataFusionError::Internal(format!(
+                    "expected Utf8 name column in {NAMESPACES_TABLE}"
+                ))
+            })?;
+        let retention_periods = batch
+            .column_by_name("retention_period_ns")
+            .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+            .ok_or_else(|| {
+                DataFusionError::Internal(format!(
+                    "expected Int64 retention_period_ns column in {NAMESPACES_TABLE}"
+                ))
+            })?;
+
+        namespaces.extend(names.iter().zip(retention_periods.iter()).filter_map(
+            |(name, retention_period_ns)| {
+                Some(NamespaceInfo {
+                    name: name?.to_owned(),
+                    retention_period_ns,
+                })
+            },
+        ));
+    }
+
+    Ok(namespaces)
+}
+
 fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<String>> {
     struct Matcher<'a>(&'a mut HashSet<String>, &'a [String]);
     impl<'a> Visitor for Matcher<'a> {
@@ -251,6 +541,26 @@ fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<
             Ok(self)
         }
 
+        fn post_visit_delete_statement(self, ds: &DeleteStatement) -> Result<Self, Self::Error> {
+            if let DeleteStatement::Where(_) = ds {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
+
+        fn post_visit_drop_measurement_statement(
+            self,
+            dm: &DropMeasurementStatement,
+        ) -> Result<Self, Self::Error> {
+            let name = dm.name.deref();
+            if self.1.contains(name) {
+                self.0.insert(name.to_string());
+            }
+
+            Ok(self)
+        }
+
         fn post_visit_show_measurements_statement(
             self,
             sm: &ShowMeasurementsStatement,
@@ -324,6 +634,58 @@ mod test {
         );
     }
 
+    #[derive(Debug)]
+    struct NoopDeleter;
+
+    #[async_trait::async_trait]
+    impl QueryNamespaceDeleter for NoopDeleter {
+        async fn delete(
+            &self,
+            _table_names: &[String],
+            _predicate: &data_types::DeletePredicate,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_delete_exec_children() {
+        let schema = Arc::new(arrow::datatypes::Schema::empty());
+        let input: Arc<dyn ExecutionPlan> = Arc::new(
+            datafusion::physical_plan::empty::EmptyExec::new(false, Arc::clone(&schema)),
+        );
+        let exec: Arc<dyn ExecutionPlan> = Arc::new(DeleteExec {
+            input: Arc::clone(&input),
+            deleter: Arc::new(NoopDeleter),
+            request: DeleteRequest {
+                table_names: vec!["cpu".to_string()],
+                predicate: data_types::DeletePredicate {
+                    range: data_types::TimestampRange::new(0, 10),
+                    exprs: vec![],
+                },
+            },
+        });
+
+        let children = exec.children();
+        assert_eq!(children.len(), 1);
+        assert!(Arc::ptr_eq(&children[0], &input));
+
+        // rebuilt from the new child
+        let new_input: Arc<dyn ExecutionPlan> = Arc::new(
+            datafusion::physical_plan::empty::EmptyExec::new(true, schema),
+        );
+        let rebuilt = Arc::clone(&exec)
+            .with_new_children(vec![Arc::clone(&new_input)])
+            .unwrap();
+        assert!(rebuilt.as_any().is::<DeleteExec>());
+        assert!(Arc::ptr_eq(&rebuilt.children()[0], &new_input));
+
+        assert_error!(
+            exec.with_new_children(vec![]),
+            DataFusionError::Internal(ref s) if s == "DeleteExec expects exactly one child, got 0"
+        );
+    }
+
     #[test]
     fn test_find_all_measurements() {
         fn find(q: &str) -> Vec<String> {
@@ -381,10 +743,82 @@ mod test {
         assert_eq!(find("SHOW TAG KEYS"), vec!["bar", "foo", "foobar"]);
         assert_eq!(find("SHOW TAG KEYS FROM /^foo/"), vec!["foo", "foobar"]);
 
+        // Find all measurements in `DELETE` and `DROP MEASUREMENT`
+        assert_eq!(find("DELETE FROM /^foo/"), vec!["foo", "foobar"]);
+        assert_eq!(find("DELETE WHERE time < 10"), vec!["bar", "foo", "foobar"]);
+        assert_eq!(find("DROP MEASUREMENT foo"), vec!["foo"]);
+        assert!(find("DROP MEASUREMENT none").is_empty());
+
         // Finds no measurements
         assert!(find("SELECT * FROM none").is_empty());
         assert!(find("SELECT * FROM (SELECT * FROM none)").is_empty());
         assert!(find("SELECT * FROM /^l/").is_empty());
         assert!(find("SELECT * FROM (SELECT * FROM /^l/)").is_empty());
     }
+
+    #[test]
+    fn test_query_deletes_data() {
+        assert!(InfluxQLQueryPlanner::query_deletes_data("DELETE FROM foo"));
+        assert!(InfluxQLQueryPlanner::query_deletes_data(
+            "DELETE WHERE time < 10"
+        ));
+        assert!(InfluxQLQueryPlanner::query_deletes_data(
+            "DROP MEASUREMENT foo"
+        ));
+        assert!(InfluxQLQueryPlanner::query_deletes_data(
+            "SELECT foo FROM bar; DROP MEASUREMENT foo"
+        ));
+
+        assert!(!InfluxQLQueryPlanner::query_deletes_data(
+            "SELECT foo FROM bar"
+        ));
+        assert!(!InfluxQLQueryPlanner::query_deletes_data(
+            "SHOW MEASUREMENTS; SHOW DATABASES"
+        ));
+        // Invalid queries are rejected when they are planned
+        assert!(!InfluxQLQueryPlanner::query_deletes_data("DELETE"));
+    }
+
+    #[test]
+    fn test_query_requires_database() {
+        assert!(InfluxQLQueryPlanner::query_requires_database(
+            "SELECT foo FROM bar"
+        ));
+        assert!(InfluxQLQueryPlanner::query_requires_database(
+            "SHOW RETENTION POLICIES"
+        ));
+        assert!(InfluxQLQueryPlanner::query_requires_database(
+            "SHOW DATABASES; SHOW MEASUREMENTS"
+        ));
+
+        assert!(!InfluxQLQueryPlanner::query_requires_database(
+            "SHOW DATABASES"
+        ));
+        assert!(!InfluxQLQueryPlanner::query_requires_database(
+            "SHOW DATABASES; SHOW RETENTION POLICIES ON foo"
+        ));
+        // Invalid queries are rejected when they are planned
+        assert!(!InfluxQLQueryPlanner::query_requires_database("SHOW"));
+    }
+
+    #[test]
+    fn test_set_statement_id() {
+        let meta = InfluxQlMetadata {
+            measurement_column_index: 0,
+            ..Default::default()
+        };
+        let mut md = HashMap::from([(
+            INFLUXQL_METADATA_KEY.to_owned(),
+            serde_json::to_string(&meta).unwrap(),
+        )]);
+        set_statement_id(&mut md, 2).unwrap();
+        let got: InfluxQlMetadata = serde_json::from_str(&md[INFLUXQL_METADATA_KEY]).unwrap();
+        assert_eq!(got.statement_id, 2);
+        assert_eq!(got.measurement_column_index, 0);
+
+        // Schemas without InfluxQL metadata, such as EXPLAIN, are unchanged
+        let mut md = HashMap::new();
+        set_statement_id(&mut md, 1).unwrap();
+        assert!(md.is_empty());
+    }
 }
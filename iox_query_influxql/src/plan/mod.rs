@@ -12,6 +12,8 @@ mod util;
 mod util_copy;
 mod var_ref;
 
+pub use planner::DeleteRequest;
 pub use planner::InfluxQLToLogicalPlan;
+pub use planner::NamespaceInfo;
 pub use planner::SchemaProvider;
 pub(crate) use util::parse_regex;
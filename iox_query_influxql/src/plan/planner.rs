@@ -1,6 +1,7 @@
 mod select;
 
 use crate::plan::error;
+use crate::plan::field::field_name;
 use crate::plan::planner::select::{
     check_exprs_satisfy_columns, fields_to_exprs_no_nulls, make_tag_key_column_meta,
     plan_with_sort, ToSortExpr,
@@ -10,33 +11,44 @@ use crate::plan::planner_time_range_expression::{
     duration_expr_to_nanoseconds, expr_to_df_interval_dt, time_range_to_df_expr,
 };
 use crate::plan::rewriter::{
-    rewrite_statement, select_statement_info, ProjectionType, SelectStatementInfo,
+    rewrite_field_list_aliases, rewrite_statement, select_statement_info, ProjectionType,
+    SelectStatementInfo,
 };
 use crate::plan::util::{binary_operator_to_df_operator, rebase_expr, Schemas};
+use crate::plan::util_copy::clone_with_replacement;
 use crate::plan::var_ref::{column_type_to_var_ref_data_type, var_ref_data_type_to_data_type};
-use arrow::array::{StringBuilder, StringDictionaryBuilder};
+use arrow::array::{BooleanBuilder, Int64Builder, StringBuilder, StringDictionaryBuilder};
 use arrow::datatypes::{DataType, Field as ArrowField, Int32Type, Schema as ArrowSchema};
 use arrow::record_batch::RecordBatch;
 use chrono_tz::Tz;
+use data_types::{
+    DeleteExpr, DeletePredicate, Op, Scalar, TimestampRange, MAX_NANO_TIME, MIN_NANO_TIME,
+    V1_NAMESPACE_RP_SEPARATOR,
+};
 use datafusion::catalog::TableReference;
 use datafusion::common::tree_node::{TreeNode, TreeNodeRewriter};
 use datafusion::common::{DFSchema, DFSchemaRef, Result, ScalarValue, ToDFSchema};
 use datafusion::datasource::{provider_as_source, MemTable};
+use datafusion::execution::context::ExecutionProps;
 use datafusion::logical_expr::expr_rewriter::normalize_col;
 use datafusion::logical_expr::logical_plan::builder::project;
 use datafusion::logical_expr::logical_plan::Analyze;
-use datafusion::logical_expr::utils::{expr_as_column_expr, find_aggregate_exprs};
+use datafusion::logical_expr::utils::{
+    expr_as_column_expr, find_aggregate_exprs, find_window_exprs,
+};
 use datafusion::logical_expr::{
-    binary_expr, col, date_bin, expr, expr::WindowFunction, lit, lit_timestamp_nano, now,
+    binary_expr, col, date_bin, expr, expr::WindowFunction, lit, lit_timestamp_nano, now, random,
     window_function, Aggregate, AggregateFunction, AggregateUDF, Between, BinaryExpr,
     BuiltInWindowFunction, BuiltinScalarFunction, EmptyRelation, Explain, Expr, ExprSchemable,
     Extension, GetIndexedField, LogicalPlan, LogicalPlanBuilder, Operator, PlanType, ScalarUDF,
     TableSource, ToStringifiedPlan, WindowFrame, WindowFrameBound, WindowFrameUnits,
 };
+use datafusion::optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext};
 use datafusion::prelude::Column;
 use datafusion_util::{lit_dict, AsExpr};
 use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
-use influxdb_influxql_parser::common::{LimitClause, OffsetClause};
+use influxdb_influxql_parser::common::{LimitClause, OffsetClause, OrderByClause};
+use influxdb_influxql_parser::delete::DeleteStatement;
 use influxdb_influxql_parser::explain::{ExplainOption, ExplainStatement};
 use influxdb_influxql_parser::expression::walk::walk_expr;
 use influxdb_influxql_parser::expression::{
@@ -44,12 +56,14 @@ use influxdb_influxql_parser::expression::{
     VarRefDataType,
 };
 use influxdb_influxql_parser::select::{
-    FillClause, GroupByClause, SLimitClause, SOffsetClause, TimeZoneClause,
+    Dimension, FillClause, GroupByClause, SLimitClause, SOffsetClause, TimeDimension,
+    TimeZoneClause,
 };
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
 use influxdb_influxql_parser::show_measurements::{
     ShowMeasurementsStatement, WithMeasurementClause,
 };
+use influxdb_influxql_parser::show_retention_policies::ShowRetentionPoliciesStatement;
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
 use influxdb_influxql_parser::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
 use influxdb_influxql_parser::simple_from_clause::ShowFromClause;
@@ -68,19 +82,21 @@ use iox_query::logical_optimizer::range_predicate::find_time_range;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 use query_functions::selectors::{
-    selector_first, selector_last, selector_max, selector_min, SelectorOutput,
+    selector_first, selector_last, selector_max, selector_min, selector_percentile, SelectorOutput,
 };
 use query_functions::{
-    clean_non_meta_escapes,
+    clean_non_meta_escapes, registry,
     selectors::{
         struct_selector_first, struct_selector_last, struct_selector_max, struct_selector_min,
+        struct_selector_percentile,
     },
+    transformations::{DERIVATIVE_UDAF_NAME, NON_NEGATIVE_DERIVATIVE_UDAF_NAME},
 };
 use schema::{
-    InfluxColumnType, InfluxFieldType, Schema, INFLUXQL_MEASUREMENT_COLUMN_NAME,
+    InfluxColumnType, InfluxFieldType, Schema, SchemaBuilder, INFLUXQL_MEASUREMENT_COLUMN_NAME,
     INFLUXQL_METADATA_KEY,
 };
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::fmt::Debug;
 use std::iter;
 use std::ops::{Bound, ControlFlow, Deref, Range};
@@ -92,6 +108,21 @@ use super::parse_regex;
 /// The column index of the measurement column.
 const MEASUREMENT_COLUMN_INDEX: u32 = 0;
 
+/// The name of the `ROW_NUMBER` window expression, used to limit
+/// the number of rows of each group.
+const IOX_ROW_ALIAS: &str = "iox::row";
+
+/// A request to delete data, produced by planning a `DELETE` or
+/// `DROP MEASUREMENT` statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeleteRequest {
+    /// The names of the tables to delete from.
+    pub table_names: Vec<String>,
+
+    /// Rows of the tables matching this predicate are deleted.
+    pub predicate: DeletePredicate,
+}
+
 /// The `SchemaProvider` trait allows the InfluxQL query planner to obtain
 /// meta-data about tables referenced in InfluxQL statements.
 pub trait SchemaProvider {
@@ -114,6 +145,24 @@ pub trait SchemaProvider {
 
     /// Get the schema for the specified `table`.
     fn table_schema(&self, name: &str) -> Option<Schema>;
+
+    /// The namespaces of the catalog, which are mapped to InfluxQL
+    /// databases and retention policies.
+    fn namespaces(&self) -> Vec<NamespaceInfo>;
+
+    /// The name of the namespace the query is run against, if any.
+    fn namespace_name(&self) -> Option<&str>;
+}
+
+/// Describes a namespace of the catalog, for the purpose of answering
+/// `SHOW DATABASES` and `SHOW RETENTION POLICIES`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceInfo {
+    /// The name of the namespace.
+    pub name: String,
+    /// The retention period of the namespace, in nanoseconds, or `None`
+    /// for an infinite retention period.
+    pub retention_period_ns: Option<i64>,
 }
 
 /// Informs the planner which rules should be applied when transforming
@@ -180,7 +229,23 @@ impl<'a> Context<'a> {
     fn is_aggregate(&self) -> bool {
         matches!(
             self.info.projection_type,
-            ProjectionType::Aggregate | ProjectionType::Selector { .. }
+            ProjectionType::Aggregate
+                | ProjectionType::WindowAggregate
+                | ProjectionType::Selector { .. }
+        )
+    }
+
+    fn is_window(&self) -> bool {
+        matches!(
+            self.info.projection_type,
+            ProjectionType::Window | ProjectionType::WindowAggregate
+        )
+    }
+
+    fn is_multi_row_selector(&self) -> bool {
+        matches!(
+            self.info.projection_type,
+            ProjectionType::TopBottomSelector | ProjectionType::Sample
         )
     }
 
@@ -204,18 +269,21 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     pub fn statement_to_plan(&self, statement: Statement) -> Result<LogicalPlan> {
         match statement {
             Statement::CreateDatabase(_) => error::not_implemented("CREATE DATABASE"),
-            Statement::Delete(_) => error::not_implemented("DELETE"),
-            Statement::DropMeasurement(_) => error::not_implemented("DROP MEASUREMENT"),
+            Statement::Delete(_) | Statement::DropMeasurement(_) => {
+                // Deletes have side effects, so are executed by the caller via
+                // `statement_to_delete_request`, rather than planned.
+                error::internal(format!("unexpected statement: {statement}"))
+            }
             Statement::Explain(explain) => self.explain_statement_to_plan(*explain),
             Statement::Select(select) => {
                 self.select_statement_to_plan(&self.rewrite_select_statement(*select)?)
             }
-            Statement::ShowDatabases(_) => error::not_implemented("SHOW DATABASES"),
+            Statement::ShowDatabases(_) => self.show_databases_to_plan(),
             Statement::ShowMeasurements(show_measurements) => {
                 self.show_measurements_to_plan(*show_measurements)
             }
-            Statement::ShowRetentionPolicies(_) => {
-                error::not_implemented("SHOW RETENTION POLICIES")
+            Statement::ShowRetentionPolicies(show_retention_policies) => {
+                self.show_retention_policies_to_plan(*show_retention_policies)
             }
             Statement::ShowTagKeys(show_tag_keys) => self.show_tag_keys_to_plan(*show_tag_keys),
             Statement::ShowTagValues(show_tag_values) => {
@@ -227,6 +295,94 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
+    /// Returns the [`DeleteRequest`] of a `DELETE` or `DROP MEASUREMENT` `statement`,
+    /// or `None` for any other statement.
+    ///
+    /// Deleting data has side effects, so it is the responsibility of the caller
+    /// to execute the request, using [`Self::delete_result_plan`] as the result.
+    pub fn statement_to_delete_request(
+        &self,
+        statement: &Statement,
+    ) -> Result<Option<DeleteRequest>> {
+        match statement {
+            Statement::Delete(delete) => self.delete_statement_to_request(delete).map(Some),
+            Statement::DropMeasurement(drop) => {
+                let name = drop.name.as_str();
+                let table_names = if self.s.table_exists(name) {
+                    vec![name.to_owned()]
+                } else {
+                    vec![]
+                };
+                Ok(Some(DeleteRequest {
+                    table_names,
+                    predicate: DeletePredicate {
+                        range: TimestampRange::new(MIN_NANO_TIME, MAX_NANO_TIME),
+                        exprs: vec![],
+                    },
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn delete_statement_to_request(&self, delete: &DeleteStatement) -> Result<DeleteRequest> {
+        let (table_names, condition) = match delete {
+            DeleteStatement::FromWhere { from, condition } => (
+                self.expand_measurement_names(from.iter())?,
+                condition.as_deref(),
+            ),
+            DeleteStatement::Where(condition) => {
+                let mut table_names = self
+                    .s
+                    .table_names()
+                    .into_iter()
+                    .map(|s| s.to_owned())
+                    .collect::<Vec<_>>();
+                table_names.sort();
+                (table_names, Some(condition.deref()))
+            }
+        };
+
+        let mut predicate = DeletePredicate {
+            range: TimestampRange::new(MIN_NANO_TIME, MAX_NANO_TIME),
+            exprs: vec![],
+        };
+        if let Some(condition) = condition {
+            let (mut start, mut end) = (MIN_NANO_TIME, MAX_NANO_TIME);
+            add_delete_condition(condition, &mut start, &mut end, &mut predicate.exprs)?;
+            if start > end {
+                return error::query("DELETE time range is empty");
+            }
+            predicate.range = TimestampRange::new(start, end);
+        }
+
+        Ok(DeleteRequest {
+            table_names,
+            predicate,
+        })
+    }
+
+    /// Returns the plan for the result of a `DELETE` or `DROP MEASUREMENT`
+    /// statement, which produces no rows.
+    pub fn delete_result_plan(&self) -> Result<LogicalPlan> {
+        let schema = ArrowSchema::new(vec![ArrowField::new(
+            INFLUXQL_MEASUREMENT_COLUMN_NAME,
+            (&InfluxColumnType::Tag).into(),
+            false,
+        )]);
+        plan_with_metadata(
+            LogicalPlan::EmptyRelation(EmptyRelation {
+                produce_one_row: false,
+                schema: schema.to_dfschema_ref()?,
+            }),
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+                ..Default::default()
+            },
+        )
+    }
+
     fn explain_statement_to_plan(&self, explain: ExplainStatement) -> Result<LogicalPlan> {
         let plan =
             self.select_statement_to_plan(&self.rewrite_select_statement(*explain.select)?)?;
@@ -270,6 +426,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &InfluxQlMetadata {
                 measurement_column_index,
                 tag_key_columns: vec![],
+                ..Default::default()
             },
         )
     }
@@ -280,7 +437,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
     /// Create a [`LogicalPlan`] from the specified InfluxQL `SELECT` statement.
     fn select_statement_to_plan(&self, select: &SelectStatement) -> Result<LogicalPlan> {
-        let mut plans = self.plan_from_tables(&select.from)?;
+        let mut plans = self.plan_from_tables(select)?;
 
         let ctx = Context::new(select_statement_info(select)?)
             .with_timezone(select.timezone)
@@ -354,14 +511,37 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
         fields.extend(select.fields.iter().cloned());
 
+        // The tags specified as arguments to the `top` or `bottom` functions are
+        // projected as additional columns, following the selector.
+        if let Some(call) = find_multi_row_selector(&select.fields) {
+            let tag_fields = call.args[1..call.args.len() - 1]
+                .iter()
+                .filter_map(|arg| match arg {
+                    IQLExpr::VarRef(var_ref)
+                        if !fields
+                            .iter()
+                            .any(|f| field_name(f) == var_ref.name.as_str()) =>
+                    {
+                        Some(Field {
+                            expr: arg.clone(),
+                            alias: Some(var_ref.name.clone()),
+                        })
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            fields.extend(tag_fields);
+        }
+
         // Build the first non-empty plan
         let plan = {
             loop {
                 match plans.pop_front() {
-                    Some((plan, proj)) => match self.project_select(
+                    Some((plan, proj, schemas)) => match self.project_select(
                         &ctx,
                         plan,
                         proj,
+                        &schemas,
                         select,
                         &fields,
                         &group_by_tag_set,
@@ -380,20 +560,30 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         };
 
         // UNION the remaining plans
-        let plan = plans.into_iter().try_fold(plan, |prev, (next, proj)| {
-            let next = self.project_select(&ctx, next, proj, select, &fields, &group_by_tag_set)?;
-            if let LogicalPlan::EmptyRelation(EmptyRelation {
-                produce_one_row: false,
-                ..
-            }) = next
-            {
-                // Exclude any plans that produce no data, which is
-                // consistent with InfluxQL.
-                Ok(prev)
-            } else {
-                LogicalPlanBuilder::from(prev).union(next)?.build()
-            }
-        })?;
+        let plan = plans
+            .into_iter()
+            .try_fold(plan, |prev, (next, proj, schemas)| {
+                let next = self.project_select(
+                    &ctx,
+                    next,
+                    proj,
+                    &schemas,
+                    select,
+                    &fields,
+                    &group_by_tag_set,
+                )?;
+                if let LogicalPlan::EmptyRelation(EmptyRelation {
+                    produce_one_row: false,
+                    ..
+                }) = next
+                {
+                    // Exclude any plans that produce no data, which is
+                    // consistent with InfluxQL.
+                    Ok(prev)
+                } else {
+                    LogicalPlanBuilder::from(prev).union(next)?.build()
+                }
+            })?;
 
         let plan = plan_with_metadata(
             plan,
@@ -404,11 +594,17 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     &group_by_tag_set,
                     &is_projected,
                 ),
+                ..Default::default()
             },
         )?;
 
-        // The UNION operator indicates the result set produces multiple tables or measurements.
-        let is_multiple_measurements = matches!(plan, LogicalPlan::Union(_));
+        // The UNION operator indicates the result set produces multiple tables or measurements,
+        // as does a subquery, which may select from more than one measurement.
+        let is_multiple_measurements = matches!(plan, LogicalPlan::Union(_))
+            || select
+                .from
+                .iter()
+                .any(|ms| matches!(ms, MeasurementSelection::Subquery(_)));
 
         let plan = plan_with_sort(
             plan,
@@ -428,22 +624,30 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &projection_tag_set,
         )?;
 
-        let plan = self.slimit(plan, select.series_offset, select.series_limit)?;
+        let plan = self.slimit(
+            plan,
+            select.series_offset,
+            select.series_limit,
+            vec![select.order_by.to_sort_expr()],
+            is_multiple_measurements,
+            &group_by_tag_set,
+            &projection_tag_set,
+        )?;
 
         Ok(plan)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn project_select(
         &self,
         ctx: &Context<'_>,
         input: LogicalPlan,
         proj: Vec<Expr>,
+        schemas: &Schemas,
         select: &SelectStatement,
         fields: &[Field],
         group_by_tag_set: &[&str],
     ) -> Result<LogicalPlan> {
-        let schemas = Schemas::new(input.schema())?;
-
         // To be consistent with InfluxQL, exclude measurements
         // when the projection has no matching fields.
         if !fields.iter().any(|f| {
@@ -463,10 +667,10 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             return LogicalPlanBuilder::empty(false).build();
         }
 
-        let plan = self.plan_where_clause(ctx, &select.condition, input, &schemas)?;
+        let plan = self.plan_where_clause(ctx, &select.condition, input, schemas)?;
 
         // Transform InfluxQL AST field expressions to a list of DataFusion expressions.
-        let mut select_exprs = self.field_list_to_exprs(ctx, &plan, fields, &schemas)?;
+        let mut select_exprs = self.field_list_to_exprs(ctx, &plan, fields, schemas)?;
 
         if ctx.is_raw_distinct() {
             // This is a special case, where exactly one column can be projected with a `DISTINCT`
@@ -492,16 +696,131 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             return LogicalPlanBuilder::from(plan).distinct()?.build();
         }
 
+        let plan = self.select_multi_row_selector(ctx, plan, fields, schemas, group_by_tag_set)?;
+
         let (plan, select_exprs_post_aggr) =
-            self.select_aggregate(ctx, plan, fields, select_exprs, group_by_tag_set, &schemas)?;
+            self.select_aggregate(ctx, plan, fields, select_exprs, group_by_tag_set, schemas)?;
+
+        let (plan, select_exprs_post_window) = self.select_window(
+            ctx,
+            plan,
+            fields,
+            select_exprs_post_aggr,
+            select.order_by,
+            group_by_tag_set,
+        )?;
 
         // Wrap the plan in a `LogicalPlan::Projection` from the select expressions
         project(
             plan,
-            proj.into_iter().chain(select_exprs_post_aggr.into_iter()),
+            proj.into_iter().chain(select_exprs_post_window.into_iter()),
         )
     }
 
+    /// Generate a plan that selects up to `N` rows of the `input` plan for each series,
+    /// for the `top`, `bottom` or `sample` selector functions.
+    ///
+    /// Unlike other selector functions, which produce a single row for each group, these
+    /// functions produce multiple rows. The rows are ranked using the `ROW_NUMBER` window
+    /// function, partitioned by the measurement, the tags of the `GROUP BY` clause and the
+    /// `GROUP BY time` interval, if present. As the output rows are those of the `input`
+    /// plan, the `time` column and any additional fields or tags of the projection are
+    /// the values of the selected row.
+    ///
+    /// When tags are specified as arguments to `top` or `bottom`, only the first ranked
+    /// row for each unique combination of the tag values is considered.
+    fn select_multi_row_selector(
+        &self,
+        ctx: &Context<'_>,
+        input: LogicalPlan,
+        fields: &[Field],
+        schemas: &Schemas,
+        group_by_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        if !ctx.is_multi_row_selector() {
+            return Ok(input);
+        }
+
+        let Some(call) = find_multi_row_selector(fields) else {
+            return error::internal("unable to find top, bottom or sample function")
+        };
+
+        let (limit, args) = match call.args.split_last() {
+            Some((IQLExpr::Literal(Literal::Integer(limit)), args)) if !args.is_empty() => {
+                (*limit, args)
+            }
+            _ => {
+                // Should have been validated by `select_statement_info`
+                return error::internal(format!(
+                    "expected integer as last argument for {}",
+                    call.name
+                ));
+            }
+        };
+
+        // The value of the selector, which is `NULL` if the field does not exist
+        // in the current table, and therefore no rows are selected.
+        let value = self.field_to_df_expr(
+            ctx,
+            &Field {
+                expr: args[0].clone(),
+                alias: None,
+            },
+            &input,
+            schemas,
+        )?;
+
+        let mut partition_by =
+            fields_to_exprs_no_nulls(input.schema(), group_by_tag_set).collect::<Vec<_>>();
+        if let Some(dim) = ctx.group_by.and_then(|gb| gb.time_dimension()) {
+            partition_by.push(time_dimension_to_date_bin(dim)?);
+        }
+        if input
+            .schema()
+            .has_column_with_unqualified_name(INFLUXQL_MEASUREMENT_COLUMN_NAME)
+        {
+            partition_by.push(INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr());
+        }
+
+        // Rows with equal values are ranked by ascending time, per InfluxQL.
+        let order_by = match call.name.as_str() {
+            "top" => vec![
+                value.clone().sort(false, false),
+                "time".as_expr().sort(true, false),
+            ],
+            "bottom" => vec![
+                value.clone().sort(true, false),
+                "time".as_expr().sort(true, false),
+            ],
+            "sample" => vec![random().sort(true, false)],
+            name => return error::internal(format!("unexpected selector function {name}")),
+        };
+
+        let plan = LogicalPlanBuilder::from(input)
+            .filter(value.is_not_null())?
+            .build()?;
+
+        let tag_names = args[1..]
+            .iter()
+            .filter_map(|arg| match arg {
+                IQLExpr::VarRef(VarRef { name, .. }) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let plan = if tag_names.is_empty() {
+            plan
+        } else {
+            let partition_by = partition_by
+                .iter()
+                .cloned()
+                .chain(fields_to_exprs_no_nulls(plan.schema(), &tag_names))
+                .collect::<Vec<_>>();
+            filter_by_row_number(plan, partition_by, order_by.clone(), 1)?
+        };
+
+        filter_by_row_number(plan, partition_by, order_by, limit)
+    }
+
     fn select_aggregate(
         &self,
         ctx: &Context<'_>,
@@ -552,18 +871,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             // 2. is a single-selector query, project the `time` field of the selector aggregate,
             // 3. otherwise, project the Unix epoch (0)
             select_exprs[time_column_index] = if let Some(dim) = ctx.group_by.and_then(|gb| gb.time_dimension()) {
-                let stride = expr_to_df_interval_dt(&dim.interval)?;
-                let offset = if let Some(offset) = &dim.offset {
-                    duration_expr_to_nanoseconds(offset)?
-                } else {
-                    0
-                };
-
-                date_bin(
-                    stride,
-                    "time".as_expr(),
-                    lit(ScalarValue::TimestampNanosecond(Some(offset), None)),
-                )
+                time_dimension_to_date_bin(dim)?
             } else if let ProjectionType::Selector { has_fields } =
                 ctx.info.projection_type
             {
@@ -591,7 +899,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             time_column_index
         };
 
-        let aggr_group_by_exprs = if let Some(group_by) = ctx.group_by {
+        let mut aggr_group_by_exprs = if let Some(group_by) = ctx.group_by {
             let mut group_by_exprs = Vec::new();
 
             if group_by.time_dimension().is_some() {
@@ -617,6 +925,18 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             vec![]
         };
 
+        // A subquery may produce rows for more than one measurement, which must be
+        // aggregated independently.
+        //
+        // NOTE: The measurement column is added after the `time` column, as the
+        // GapFill operator expects the `DATE_BIN` expression as the first group expression.
+        if schemas
+            .df_schema
+            .has_column_with_unqualified_name(INFLUXQL_MEASUREMENT_COLUMN_NAME)
+        {
+            aggr_group_by_exprs.push(INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr());
+        }
+
         if aggr_exprs.is_empty() && aggr_group_by_exprs.is_empty() {
             // If there are no aggregate expressions in the projection, because
             // they all referred to non-existent columns in the table, and there
@@ -729,6 +1049,125 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         Ok((plan, select_exprs_post_aggr))
     }
 
+    /// Generate a plan that evaluates the window-like functions of the projection, such as
+    /// `difference` or `derivative`, for each series of the `input` plan.
+    ///
+    /// The window functions are partitioned by the measurement, if the `input` produces
+    /// multiple measurements, and the tags of the `GROUP BY` clause, and ordered by the `time`
+    /// column of the projection, which is the `DATE_BIN` expression when the query includes a
+    /// `GROUP BY time` clause.
+    ///
+    /// Rows for which every window function produces a `NULL` value are omitted when all
+    /// the aggregate fields of the projection are window-like functions, as InfluxQL does not
+    /// produce a row for the first point of a series when calculating a `difference`.
+    fn select_window(
+        &self,
+        ctx: &Context<'_>,
+        input: LogicalPlan,
+        fields: &[Field],
+        select_exprs: Vec<Expr>,
+        order_by: Option<OrderByClause>,
+        group_by_tag_set: &[&str],
+    ) -> Result<(LogicalPlan, Vec<Expr>)> {
+        if !ctx.is_window() {
+            return Ok((input, select_exprs));
+        }
+
+        let Some(time_column_index) = find_time_column_index(fields) else {
+            return error::internal("unable to find time column")
+        };
+        let time_expr = select_exprs[time_column_index].clone().unalias();
+
+        // The partition columns are qualified, so the names of the window expressions match
+        // the output columns of the window plan, which are used to rebase the projection.
+        let partition_by = fields_to_exprs_no_nulls(input.schema(), group_by_tag_set)
+            .chain(
+                input
+                    .schema()
+                    .has_column_with_unqualified_name(INFLUXQL_MEASUREMENT_COLUMN_NAME)
+                    .then(|| INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr()),
+            )
+            .map(|expr| normalize_col(expr, &input))
+            .collect::<Result<Vec<_>>>()?;
+        let order_by = vec![time_expr
+            .clone()
+            .sort(!matches!(order_by, Some(OrderByClause::Descending)), false)];
+
+        // Complete the window expressions, which were planned without a partition or order,
+        // as they are not known until the input plan is created.
+        let select_exprs = select_exprs
+            .iter()
+            .map(|expr| {
+                clone_with_replacement(expr, &|nested_expr| {
+                    Ok(match nested_expr {
+                        Expr::WindowFunction(WindowFunction {
+                            fun,
+                            args,
+                            window_frame,
+                            ..
+                        }) => {
+                            let mut args = args.clone();
+                            if let window_function::WindowFunction::AggregateUDF(udaf) = fun {
+                                if udaf.name == DERIVATIVE_UDAF_NAME
+                                    || udaf.name == NON_NEGATIVE_DERIVATIVE_UDAF_NAME
+                                {
+                                    args.push(time_expr.clone());
+                                }
+                            }
+                            Some(Expr::WindowFunction(WindowFunction {
+                                fun: fun.clone(),
+                                args,
+                                partition_by: partition_by.clone(),
+                                order_by: order_by.clone(),
+                                window_frame: window_frame.clone(),
+                            }))
+                        }
+                        _ => None,
+                    })
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let window_exprs = find_window_exprs(&select_exprs);
+        if window_exprs.is_empty() {
+            // All the window functions refer to columns that do not exist in the input.
+            return Ok((input, select_exprs));
+        }
+
+        let plan = LogicalPlanBuilder::from(input)
+            .window(window_exprs.clone())?
+            .build()?;
+
+        // Rewrite the window expressions of the projection, so that the expressions refer
+        // to the output columns of the window plan.
+        let select_exprs = select_exprs
+            .iter()
+            .map(|expr| rebase_expr(expr, &window_exprs, &None, &plan))
+            .collect::<Result<Vec<_>>>()?;
+
+        let plan = if fields
+            .iter()
+            .filter(|f| is_aggregate_field(f))
+            .all(is_window_field)
+        {
+            let filter_expr = window_exprs
+                .iter()
+                .map(|expr| Ok(expr_as_column_expr(expr, &plan)?.is_not_null()))
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .reduce(|acc, expr| acc.or(expr))
+                .expect("at least one window expression");
+
+            LogicalPlanBuilder::from(plan)
+                .filter(filter_expr)?
+                .build()?
+        } else {
+            plan
+        };
+
+        Ok((plan, select_exprs))
+    }
+
     /// Generate a plan that partitions the input data into groups, first omitting a specified
     /// number of rows, followed by restricting the quantity of rows within each group.
     ///
@@ -771,9 +1210,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             // are applied to each unique group. To accomplish this, construct a plan which uses
             // the ROW_NUMBER windowing function.
 
-            // The name of the ROW_NUMBER window expression
-            const IOX_ROW_ALIAS: &str = "iox::row";
-
             // Construct a ROW_NUMBER window expression:
             //
             // ROW_NUMBER() OVER (
@@ -826,8 +1262,12 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             let row_filter_expr = match (limit, offset) {
                 // WHERE "iox::row" BETWEEN OFFSET + 1 AND OFFSET + LIMIT
                 (Some(limit), Some(offset)) => {
-                    let low = offset + 1;
-                    let high = offset + limit;
+                    let low = offset
+                        .checked_add(1)
+                        .ok_or_else(|| error::map::query("offset out of range"))?;
+                    let high = offset
+                        .checked_add(limit)
+                        .ok_or_else(|| error::map::query("limit and offset out of range"))?;
 
                     Expr::Between(Between {
                         expr: Box::new(row_alias),
@@ -864,25 +1304,147 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
-    /// Verifies the `SLIMIT` and `SOFFSET` clauses are `None`; otherwise, return a
-    /// `NotImplemented` error.
+    /// Generate a plan that omits a specified number of series, followed by restricting the
+    /// quantity of series, within each measurement.
+    ///
+    /// A series is a unique combination of the tag values from the `GROUP BY` clause within
+    /// a measurement. When a query does not group by any tags, each measurement consists of
+    /// a single series.
     ///
-    /// ## Why?
-    /// * `SLIMIT` and `SOFFSET` don't work as expected per issue [#7571]
-    /// * This issue [is noted](https://docs.influxdata.com/influxdb/v1.8/query_language/explore-data/#the-slimit-clause) in our official documentation
+    /// ## Arguments
     ///
-    /// [#7571]: https://github.com/influxdata/influxdb/issues/7571
+    /// - `input`: The plan to apply the series limit to.
+    /// - `offset`: The number of series to skip within each measurement.
+    /// - `limit`: The maximum number of series to return per measurement.
+    /// - `sort_exprs`: An `Expr::Sort` referring to the `time` column of the input.
+    /// - `is_multiple_measurements`: `true` if the `input` produces multiple measurements.
+    /// - `group_by_tag_set`: Tag columns from the `input` plan that identify a series.
+    /// - `projection_tag_set`: Additional tag columns that should be used to sort the `output`
+    ///   plan.
+    #[allow(clippy::too_many_arguments)]
     fn slimit(
         &self,
         input: LogicalPlan,
         offset: Option<SOffsetClause>,
         limit: Option<SLimitClause>,
+        sort_exprs: Vec<Expr>,
+        is_multiple_measurements: bool,
+        group_by_tag_set: &[&str],
+        projection_tag_set: &[&str],
     ) -> Result<LogicalPlan> {
         if offset.is_none() && limit.is_none() {
             return Ok(input);
         }
 
-        error::not_implemented("SLIMIT or SOFFSET")
+        let limit = limit
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("slimit out of range"))?;
+        let offset = offset
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("soffset out of range"))?;
+
+        let series_tag_exprs = fields_to_exprs_no_nulls(input.schema(), group_by_tag_set)
+            .map(|expr| expr.sort(true, false))
+            .collect::<Vec<_>>();
+
+        if series_tag_exprs.is_empty() {
+            // Without any tags in the GROUP BY clause, each measurement is a single
+            // series, so the result is either the entire input or nothing at all.
+            return if offset.unwrap_or_default() > 0 || limit == Some(0) {
+                Ok(LogicalPlan::EmptyRelation(EmptyRelation {
+                    produce_one_row: false,
+                    schema: Arc::clone(input.schema()),
+                }))
+            } else {
+                Ok(input)
+            };
+        }
+
+        // The name of the DENSE_RANK window expression
+        const IOX_SERIES_ALIAS: &str = "iox::series";
+
+        // Construct a DENSE_RANK window expression, which assigns the same number to every
+        // row of a series, numbering the series of each measurement from 1:
+        //
+        // DENSE_RANK() OVER (
+        //   PARTITION BY [iox::measurement]
+        //   ORDER BY [group_by_tag_set]
+        //   ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+        // ) AS iox::series
+        let window_func_exprs = vec![Expr::WindowFunction(WindowFunction {
+            fun: window_function::WindowFunction::BuiltInWindowFunction(
+                BuiltInWindowFunction::DenseRank,
+            ),
+            args: vec![],
+            partition_by: vec![INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr()],
+            order_by: series_tag_exprs,
+            window_frame: WindowFrame {
+                units: WindowFrameUnits::Rows,
+                start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                end_bound: WindowFrameBound::CurrentRow,
+            },
+        })
+        .alias(IOX_SERIES_ALIAS)];
+
+        // Prepare new projection.
+        let proj_exprs = input
+            .schema()
+            .fields()
+            .iter()
+            .map(|expr| Expr::Column(expr.unqualified_column()))
+            .collect::<Vec<_>>();
+
+        let plan = LogicalPlanBuilder::from(input)
+            .window(window_func_exprs)?
+            .build()?;
+
+        // a reference to the DENSE_RANK column.
+        let series_alias = IOX_SERIES_ALIAS.as_expr();
+
+        let series_filter_expr = match (limit, offset) {
+            // WHERE "iox::series" BETWEEN SOFFSET + 1 AND SOFFSET + SLIMIT
+            (Some(limit), Some(offset)) => {
+                let low = offset
+                    .checked_add(1)
+                    .ok_or_else(|| error::map::query("soffset out of range"))?;
+                let high = offset
+                    .checked_add(limit)
+                    .ok_or_else(|| error::map::query("slimit and soffset out of range"))?;
+
+                Expr::Between(Between {
+                    expr: Box::new(series_alias),
+                    negated: false,
+                    low: Box::new(lit(low)),
+                    high: Box::new(lit(high)),
+                })
+            }
+
+            // WHERE "iox::series" <= SLIMIT
+            (Some(limit), None) => series_alias.lt_eq(lit(limit)),
+
+            // WHERE "iox::series" > SOFFSET
+            (None, Some(offset)) => series_alias.gt(lit(offset)),
+            (None, None) => unreachable!("slimit and soffset cannot not be None"),
+        };
+
+        let plan = LogicalPlanBuilder::from(plan)
+            // Filter by the SLIMIT and SOFFSET clause
+            .filter(series_filter_expr)?
+            // Project the output without the IOX_SERIES_ALIAS column
+            .project(proj_exprs)?
+            .build()?;
+
+        // For consistency with InfluxQL, the final results must be sorted by
+        // the tag set from the GROUP BY
+        plan_with_sort(
+            plan,
+            sort_exprs,
+            is_multiple_measurements,
+            group_by_tag_set,
+            projection_tag_set,
+        )
     }
 
     /// Map the InfluxQL `SELECT` projection list into a list of DataFusion expressions.
@@ -1121,6 +1683,11 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             // query, so the planner only needs to project the single column
             // argument.
             "distinct" => self.expr_to_df_expr(ctx, &args[0], schemas),
+            // The `top`, `bottom` and `sample` functions are handled as a
+            // `ProjectionType::TopBottomSelector` or `ProjectionType::Sample`
+            // query, which selects rows from the input, so the planner only needs
+            // to project the value argument.
+            "top" | "bottom" | "sample" => self.expr_to_df_expr(ctx, &args[0], schemas),
             "count" => {
                 let (expr, distinct) = match &args[0] {
                     IQLExpr::Call(c) if c.name == "distinct" => {
@@ -1154,12 +1721,28 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     None,
                 )))
             }
-            name @ ("first" | "last" | "min" | "max") => {
+            name @ ("first" | "last" | "min" | "max" | "percentile") => {
                 let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
                 if let Expr::Literal(ScalarValue::Null) = expr {
                     return Ok(expr);
                 }
 
+                let data_type = &expr.get_type(&schemas.df_schema)?;
+                let mut selector_args = vec![expr, "time".as_expr()];
+                if name == "percentile" {
+                    check_arg_count(name, args, 2)?;
+                    selector_args.push(lit(match &args[1] {
+                        IQLExpr::Literal(Literal::Integer(v)) => *v as f64,
+                        IQLExpr::Literal(Literal::Float(v)) => *v,
+                        _ => {
+                            return error::query(format!(
+                                "expected number for percentile(), got {:?}",
+                                &args[1]
+                            ))
+                        }
+                    }));
+                }
+
                 Ok(
                     if let ProjectionType::Selector { .. } = ctx.info.projection_type {
                         // Selector queries use the `struct_selector_<name>`, as they
@@ -1171,26 +1754,65 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                                     "last" => struct_selector_last(),
                                     "max" => struct_selector_max(),
                                     "min" => struct_selector_min(),
+                                    "percentile" => struct_selector_percentile(),
                                     _ => unreachable!(),
                                 }
-                                .call(vec![expr, "time".as_expr()]),
+                                .call(selector_args),
                             ),
                             key: ScalarValue::Utf8(Some("value".to_owned())),
                         })
                     } else {
                         // All other queries only require the value of the selector
-                        let data_type = &expr.get_type(&schemas.df_schema)?;
                         match name {
                             "first" => selector_first(data_type, SelectorOutput::Value),
                             "last" => selector_last(data_type, SelectorOutput::Value),
                             "max" => selector_max(data_type, SelectorOutput::Value),
                             "min" => selector_min(data_type, SelectorOutput::Value),
+                            "percentile" => selector_percentile(data_type, SelectorOutput::Value),
                             _ => unreachable!(),
                         }
-                        .call(vec![expr, "time".as_expr()])
+                        .call(selector_args)
                     },
                 )
             }
+            name @ ("derivative" | "non_negative_derivative") => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                // The unit defaults to the GROUP BY interval, if specified, or 1s.
+                let unit = if let Some(unit) = args.get(1) {
+                    duration_expr_to_nanoseconds(unit)?
+                } else if let Some(dim) = ctx.group_by.and_then(|gb| gb.time_dimension()) {
+                    duration_expr_to_nanoseconds(&dim.interval)?
+                } else {
+                    1_000_000_000
+                };
+
+                // The `time` argument is added when the window function is planned,
+                // as it depends on the input plan.
+                window_udaf_to_df_expr(name, vec![expr, lit(unit)])
+            }
+            name @ ("difference" | "non_negative_difference" | "cumulative_sum") => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count(name, args, 1)?;
+                window_udaf_to_df_expr(name, vec![expr])
+            }
+            "moving_average" => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count(name, args, 2)?;
+                let n = self.expr_to_df_expr(ctx, &args[1], schemas)?;
+                window_udaf_to_df_expr(name, vec![expr, n])
+            }
             _ => error::query(format!("Invalid function '{name}'")),
         }
     }
@@ -1263,34 +1885,128 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
-    /// Generate a list of logical plans for each of the tables references in the `FROM`
-    /// clause.
+    /// Generate a list of logical plans for each of the tables or subqueries referenced
+    /// in the `FROM` clause of `select`.
     fn plan_from_tables(
         &self,
-        from: &FromMeasurementClause,
-    ) -> Result<VecDeque<(LogicalPlan, Vec<Expr>)>> {
+        select: &SelectStatement,
+    ) -> Result<VecDeque<(LogicalPlan, Vec<Expr>, Schemas)>> {
         // A list of scans and their initial projections
         let mut table_projs = VecDeque::new();
-        for ms in from.iter() {
+        for ms in select.from.iter() {
             let Some(table_proj) = match ms {
                 MeasurementSelection::Name(qn) => match qn.name {
-                    MeasurementName::Name(ref ident) => {
-                        self.create_table_ref(normalize_identifier(ident))
-                    }
+                    MeasurementName::Name(ref ident) => self
+                        .create_table_ref(normalize_identifier(ident))?
+                        .map(|(plan, proj)| {
+                            Schemas::new(plan.schema()).map(|schemas| (plan, proj, schemas))
+                        })
+                        .transpose(),
                     // rewriter is expected to expand the regular expression
                     MeasurementName::Regex(_) => error::internal(
                         "unexpected regular expression in FROM clause",
                     ),
                 },
-                MeasurementSelection::Subquery(_) => error::not_implemented(
-                    "subquery in FROM clause",
-                ),
+                MeasurementSelection::Subquery(subquery) => {
+                    self.subquery_to_plan(select, subquery)
+                }
             }? else { continue };
             table_projs.push_back(table_proj);
         }
         Ok(table_projs)
     }
 
+    /// Create a [`LogicalPlan`] for the `subquery` found in the `FROM` clause of the
+    /// `outer` statement.
+    ///
+    /// The subquery is combined with the outer query per the rules of InfluxQL, such that:
+    ///
+    /// * the time range of the subquery is narrowed by the time range of the outer query,
+    /// * an aggregate subquery without a `GROUP BY time()` inherits the interval of the
+    ///   outer query, and
+    /// * an aggregate subquery using the default `FILL(null)` is changed to `FILL(none)`,
+    ///   as `NULL` values are ignored by the outer query.
+    ///
+    /// Returns `None` if the subquery produces no data.
+    ///
+    /// See: <https://github.com/influxdata/influxdb/blob/98361e207349a3643bcc332d54b009818fe7585f/query/iterator.go#L757>
+    fn subquery_to_plan(
+        &self,
+        outer: &SelectStatement,
+        subquery: &SelectStatement,
+    ) -> Result<Option<(LogicalPlan, Vec<Expr>, Schemas)>> {
+        let mut select = subquery.clone();
+
+        // The columns of the subquery are referenced by name in the outer query.
+        rewrite_field_list_aliases(&mut select.fields)?;
+
+        if let Some(time_range) = outer
+            .condition
+            .as_ref()
+            .and_then(|cond| find_time_range_conditions(cond))
+        {
+            select.condition = Some(WhereClause::new(match select.condition.take() {
+                Some(cond) => ConditionalExpression::Binary(ConditionalBinary {
+                    lhs: Box::new(ConditionalExpression::Grouped(Box::new(
+                        cond.deref().clone(),
+                    ))),
+                    op: ConditionalOperator::And,
+                    rhs: Box::new(time_range),
+                }),
+                None => time_range,
+            }));
+        }
+
+        let is_aggregate = !matches!(
+            select_statement_info(&select)?.projection_type,
+            ProjectionType::Raw | ProjectionType::RawDistinct | ProjectionType::Window
+        );
+
+        if is_aggregate {
+            let has_time_dimension = select
+                .group_by
+                .as_ref()
+                .and_then(|gb| gb.time_dimension())
+                .is_some();
+
+            if !has_time_dimension {
+                if let Some(dim) = outer.group_by.as_ref().and_then(|gb| gb.time_dimension()) {
+                    let mut dimensions = select
+                        .group_by
+                        .take()
+                        .map(|gb| gb.to_vec())
+                        .unwrap_or_default();
+                    dimensions.push(Dimension::Time(dim.clone()));
+                    select.group_by = Some(GroupByClause::new(dimensions));
+                }
+            }
+
+            if matches!(select.fill, None | Some(FillClause::Null)) {
+                select.fill = Some(FillClause::None);
+            }
+        }
+
+        let plan = match self.select_statement_to_plan(&select)? {
+            LogicalPlan::EmptyRelation(EmptyRelation {
+                produce_one_row: false,
+                ..
+            }) => return Ok(None),
+            plan => plan,
+        };
+
+        let schemas = Schemas {
+            df_schema: Arc::clone(plan.schema()),
+            iox_schema: subquery_schema(&select, plan.schema())?,
+        };
+
+        // The measurement column of the subquery is projected by the outer query.
+        Ok(Some((
+            plan,
+            vec![INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr()],
+            schemas,
+        )))
+    }
+
     /// Create a [LogicalPlan] that refers to the specified `table_name`.
     ///
     /// Normally, this functions will not return a `None`, as tables have been matched]
@@ -1321,8 +2037,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 Ok(tables)
             }
             Some(from) => {
-                let all_tables = self.s.table_names().into_iter().collect::<HashSet<_>>();
-                let mut out = HashSet::new();
                 for qualified_name in from.iter() {
                     if qualified_name.database.is_some() {
                         return error::not_implemented("database name in from clause");
@@ -1330,29 +2044,42 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     if qualified_name.retention_policy.is_some() {
                         return error::not_implemented("retention policy in from clause");
                     }
-                    match &qualified_name.name {
-                        MeasurementName::Name(name) => {
-                            let name = name.as_str();
-                            if all_tables.contains(name) {
-                                out.insert(name);
-                            }
-                        }
-                        MeasurementName::Regex(regex) => {
-                            let regex = parse_regex(regex)?;
-                            for name in &all_tables {
-                                if regex.is_match(name) {
-                                    out.insert(name);
-                                }
-                            }
+                }
+                self.expand_measurement_names(from.iter().map(|qn| &qn.name))
+            }
+        }
+    }
+
+    /// Expand the measurement names and regular expressions of `names` to
+    /// the sorted list of matching tables.
+    fn expand_measurement_names<'n>(
+        &self,
+        names: impl IntoIterator<Item = &'n MeasurementName>,
+    ) -> Result<Vec<String>> {
+        let all_tables = self.s.table_names().into_iter().collect::<HashSet<_>>();
+        let mut out = HashSet::new();
+        for name in names {
+            match name {
+                MeasurementName::Name(name) => {
+                    let name = name.as_str();
+                    if all_tables.contains(name) {
+                        out.insert(name);
+                    }
+                }
+                MeasurementName::Regex(regex) => {
+                    let regex = parse_regex(regex)?;
+                    for name in &all_tables {
+                        if regex.is_match(name) {
+                            out.insert(name);
                         }
                     }
                 }
-
-                let mut out = out.into_iter().map(|s| s.to_owned()).collect::<Vec<_>>();
-                out.sort();
-                Ok(out)
             }
         }
+
+        let mut out = out.into_iter().map(|s| s.to_owned()).collect::<Vec<_>>();
+        out.sort();
+        Ok(out)
     }
 
     fn show_tag_keys_to_plan(&self, show_tag_keys: ShowTagKeysStatement) -> Result<LogicalPlan> {
@@ -1411,6 +2138,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &InfluxQlMetadata {
                 measurement_column_index: MEASUREMENT_COLUMN_INDEX,
                 tag_key_columns: vec![],
+                ..Default::default()
             },
         )?;
 
@@ -1490,6 +2218,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &InfluxQlMetadata {
                 measurement_column_index: MEASUREMENT_COLUMN_INDEX,
                 tag_key_columns: vec![],
+                ..Default::default()
             },
         )?;
         let plan = self.limit(
@@ -1586,6 +2315,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &InfluxQlMetadata {
                 measurement_column_index: MEASUREMENT_COLUMN_INDEX,
                 tag_key_columns: vec![],
+                ..Default::default()
             },
         )?;
         let plan = self.limit(
@@ -1708,6 +2438,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &InfluxQlMetadata {
                 measurement_column_index: MEASUREMENT_COLUMN_INDEX,
                 tag_key_columns: vec![],
+                ..Default::default()
             },
         )?;
         let plan = self.limit(
@@ -1723,6 +2454,136 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         Ok(plan)
     }
 
+    fn show_databases_to_plan(&self) -> Result<LogicalPlan> {
+        let databases = self
+            .s
+            .namespaces()
+            .iter()
+            .map(|ns| namespace_to_database_rp(&ns.name).0.to_owned())
+            .collect::<BTreeSet<_>>();
+
+        let output_schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new(
+                INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                (&InfluxColumnType::Tag).into(),
+                false,
+            ),
+            ArrowField::new("name", (&InfluxColumnType::Tag).into(), false),
+        ]));
+
+        let mut dummy_measurement_names_builder = StringDictionaryBuilder::<Int32Type>::new();
+        let mut name_builder = StringDictionaryBuilder::<Int32Type>::new();
+        for database in databases {
+            dummy_measurement_names_builder.append_value("databases");
+            name_builder.append_value(database);
+        }
+        let plan = LogicalPlanBuilder::scan(
+            "databases",
+            provider_as_source(Arc::new(MemTable::try_new(
+                Arc::clone(&output_schema),
+                vec![vec![RecordBatch::try_new(
+                    Arc::clone(&output_schema),
+                    vec![
+                        Arc::new(dummy_measurement_names_builder.finish()),
+                        Arc::new(name_builder.finish()),
+                    ],
+                )?]],
+            )?)),
+            None,
+        )?
+        .build()?;
+
+        plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Plan `SHOW RETENTION POLICIES`.
+    ///
+    /// The retention policies are those of the database of the `ON` clause,
+    /// or of the database of the namespace being queried if it is omitted,
+    /// and are reported with the name of the database as the measurement.
+    fn show_retention_policies_to_plan(
+        &self,
+        show_retention_policies: ShowRetentionPoliciesStatement,
+    ) -> Result<LogicalPlan> {
+        let database = match (
+            show_retention_policies.database.as_ref(),
+            self.s.namespace_name(),
+        ) {
+            (Some(db), _) => db.as_str().to_owned(),
+            (None, Some(namespace_name)) => namespace_to_database_rp(namespace_name).0.to_owned(),
+            (None, None) => return error::query("database name required"),
+        };
+
+        let policies = database_retention_policies(&self.s.namespaces(), &database);
+        if policies.is_empty() {
+            return error::query(format!("database not found: {database}"));
+        }
+
+        let output_schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new(
+                INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                (&InfluxColumnType::Tag).into(),
+                false,
+            ),
+            ArrowField::new("name", (&InfluxColumnType::Tag).into(), false),
+            ArrowField::new("duration", DataType::Utf8, false),
+            ArrowField::new("shardGroupDuration", DataType::Utf8, false),
+            ArrowField::new("replicaN", DataType::Int64, false),
+            ArrowField::new("default", DataType::Boolean, false),
+        ]));
+
+        let mut measurement_names_builder = StringDictionaryBuilder::<Int32Type>::new();
+        let mut name_builder = StringDictionaryBuilder::<Int32Type>::new();
+        let mut duration_builder = StringBuilder::new();
+        let mut shard_group_duration_builder = StringBuilder::new();
+        let mut replica_n_builder = Int64Builder::new();
+        let mut default_builder = BooleanBuilder::new();
+        for (rp, retention_period_ns, default) in policies {
+            measurement_names_builder.append_value(&database);
+            name_builder.append_value(rp);
+            duration_builder.append_value(format_duration(retention_period_ns));
+            shard_group_duration_builder
+                .append_value(format_duration(shard_group_duration(retention_period_ns)));
+            replica_n_builder.append_value(1);
+            default_builder.append_value(default);
+        }
+        let plan = LogicalPlanBuilder::scan(
+            "retention_policies",
+            provider_as_source(Arc::new(MemTable::try_new(
+                Arc::clone(&output_schema),
+                vec![vec![RecordBatch::try_new(
+                    Arc::clone(&output_schema),
+                    vec![
+                        Arc::new(measurement_names_builder.finish()),
+                        Arc::new(name_builder.finish()),
+                        Arc::new(duration_builder.finish()),
+                        Arc::new(shard_group_duration_builder.finish()),
+                        Arc::new(replica_n_builder.finish()),
+                        Arc::new(default_builder.finish()),
+                    ],
+                )?]],
+            )?)),
+            None,
+        )?
+        .build()?;
+
+        plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+                ..Default::default()
+            },
+        )
+    }
+
     fn metadata_cutoff(&self) -> MetadataCutoff {
         self.iox_ctx
             .inner()
@@ -1939,17 +2800,142 @@ fn plan_with_metadata(plan: LogicalPlan, metadata: &InfluxQlMetadata) -> Result<
     set_schema(&plan, metadata)
 }
 
+/// Returns the `DATE_BIN` expression for the `GROUP BY time` dimension `dim`.
+fn time_dimension_to_date_bin(dim: &TimeDimension) -> Result<Expr> {
+    let stride = expr_to_df_interval_dt(&dim.interval)?;
+    let offset = if let Some(offset) = &dim.offset {
+        duration_expr_to_nanoseconds(offset)?
+    } else {
+        0
+    };
+
+    Ok(date_bin(
+        stride,
+        "time".as_expr(),
+        lit(ScalarValue::TimestampNanosecond(Some(offset), None)),
+    ))
+}
+
+/// Construct a plan that restricts the `input` plan to at most `limit` rows for
+/// each partition, using the `ROW_NUMBER` window function:
+///
+/// ```text
+/// ROW_NUMBER() OVER (
+///   PARTITION BY [partition_by]
+///   ORDER BY [order_by]
+///   ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+/// ) AS iox::row
+/// ```
+///
+/// The output plan projects the same columns as the `input` plan.
+fn filter_by_row_number(
+    input: LogicalPlan,
+    partition_by: Vec<Expr>,
+    order_by: Vec<Expr>,
+    limit: i64,
+) -> Result<LogicalPlan> {
+    let proj_exprs = input
+        .schema()
+        .fields()
+        .iter()
+        .map(|expr| Expr::Column(expr.unqualified_column()))
+        .collect::<Vec<_>>();
+
+    let window_func_exprs = vec![Expr::WindowFunction(WindowFunction {
+        fun: window_function::WindowFunction::BuiltInWindowFunction(
+            BuiltInWindowFunction::RowNumber,
+        ),
+        args: vec![],
+        partition_by,
+        order_by,
+        window_frame: WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+            end_bound: WindowFrameBound::CurrentRow,
+        },
+    })
+    .alias(IOX_ROW_ALIAS)];
+
+    LogicalPlanBuilder::from(input)
+        .window(window_func_exprs)?
+        .filter(IOX_ROW_ALIAS.as_expr().lt_eq(lit(limit)))?
+        .project(proj_exprs)?
+        .build()
+}
+
+/// Find the `top`, `bottom` or `sample` function call of the projection, which
+/// selects multiple rows for each series.
+fn find_multi_row_selector(fields: &[Field]) -> Option<&Call> {
+    fn find(expr: &IQLExpr) -> Option<&Call> {
+        match expr {
+            IQLExpr::Call(call) if matches!(call.name.as_str(), "top" | "bottom" | "sample") => {
+                Some(call)
+            }
+            IQLExpr::Call(Call { args, .. }) => args.iter().find_map(find),
+            IQLExpr::Binary(Binary { lhs, rhs, .. }) => find(lhs).or_else(|| find(rhs)),
+            IQLExpr::Nested(expr) => find(expr),
+            _ => None,
+        }
+    }
+
+    fields.iter().find_map(|f| find(&f.expr))
+}
+
 /// A utility function that checks whether `f` is an
 /// aggregate field or not. An aggregate field is one that contains at least one
 /// call to an aggregate function.
 fn is_aggregate_field(f: &Field) -> bool {
     walk_expr(&f.expr, &mut |e| match e {
-        IQLExpr::Call(Call { name, .. }) if is_aggregate_function(name) => ControlFlow::Break(()),
+        IQLExpr::Call(Call { name, .. }) if is_aggregate_function(name) => ControlFlow::Break(()),
+        _ => ControlFlow::Continue(()),
+    })
+    .is_break()
+}
+
+/// A utility function that checks whether `f` is a field that contains at
+/// least one call to a window-like function, such as `difference`.
+fn is_window_field(f: &Field) -> bool {
+    walk_expr(&f.expr, &mut |e| match e {
+        IQLExpr::Call(Call { name, .. }) if is_window_function(name) => ControlFlow::Break(()),
         _ => ControlFlow::Continue(()),
     })
     .is_break()
 }
 
+/// Returns `true` if `name` is a window-like function that is
+/// evaluated as a DataFusion window function.
+fn is_window_function(name: &str) -> bool {
+    matches!(
+        name,
+        "derivative"
+            | "non_negative_derivative"
+            | "difference"
+            | "non_negative_difference"
+            | "moving_average"
+            | "cumulative_sum"
+    )
+}
+
+/// Create a DataFusion window function expression that invokes the
+/// window-like user defined aggregate function `name`.
+///
+/// The window is evaluated for each row, using all the preceding rows
+/// of the partition. The partition and order of the window is determined
+/// once the input plan is known.
+fn window_udaf_to_df_expr(name: &str, args: Vec<Expr>) -> Result<Expr> {
+    Ok(Expr::WindowFunction(WindowFunction {
+        fun: window_function::WindowFunction::AggregateUDF(registry().udaf(name)?),
+        args,
+        partition_by: vec![],
+        order_by: vec![],
+        window_frame: WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+            end_bound: WindowFrameBound::CurrentRow,
+        },
+    }))
+}
+
 /// Find all the columns where the resolved data type
 /// is a tag or is [`None`], which is unknown.
 fn find_tag_and_unknown_columns(fields: &FieldList) -> impl Iterator<Item = &str> {
@@ -2052,6 +3038,119 @@ fn normalize_identifier(ident: &Identifier) -> String {
     ident.deref().clone()
 }
 
+/// The name of the retention policy reported for namespaces that do not
+/// specify one.
+const DEFAULT_RETENTION_POLICY_NAME: &str = "autogen";
+
+/// Split the namespace `name` into an InfluxQL database and retention policy,
+/// using the same `db/rp` convention as the v1 write API of the router.
+///
+/// The retention policy is `None` if the name does not contain a separator.
+fn namespace_to_database_rp(name: &str) -> (&str, Option<&str>) {
+    match name.split_once(V1_NAMESPACE_RP_SEPARATOR) {
+        Some((db, rp)) => (db, Some(rp)),
+        None => (name, None),
+    }
+}
+
+/// Returns the retention policies of `database` as tuples of the name, duration
+/// in nanoseconds and whether it is the default policy, ordered by name.
+///
+/// Like in InfluxDB 1.x, a database has exactly one default retention
+/// policy, which is the policy of the namespace without a retention policy in
+/// its name, as this is where writes that omit the retention policy go. If
+/// there is no such namespace, the first policy by name is the default.
+fn database_retention_policies(
+    namespaces: &[NamespaceInfo],
+    database: &str,
+) -> Vec<(String, i64, bool)> {
+    let mut policies = namespaces
+        .iter()
+        .filter_map(|ns| match namespace_to_database_rp(&ns.name) {
+            (db, rp) if db == database => Some((rp, ns.retention_period_ns.unwrap_or_default())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    policies.sort_by_key(|(rp, _)| rp.unwrap_or(DEFAULT_RETENTION_POLICY_NAME));
+
+    let default_idx = policies
+        .iter()
+        .position(|(rp, _)| rp.is_none())
+        .unwrap_or(0);
+    policies
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (rp, retention_period_ns))| {
+            (
+                rp.unwrap_or(DEFAULT_RETENTION_POLICY_NAME).to_owned(),
+                retention_period_ns,
+                idx == default_idx,
+            )
+        })
+        .collect()
+}
+
+/// Returns the shard group duration InfluxDB 1.x would assign to a retention
+/// policy with a duration of `ns` nanoseconds, where `0` is infinite.
+///
+/// See: <https://github.com/influxdata/influxdb/blob/1.8/services/meta/data.go>
+fn shard_group_duration(ns: i64) -> i64 {
+    const HOUR: i64 = 3_600_000_000_000;
+    const DAY: i64 = 24 * HOUR;
+
+    if ns <= 0 || ns >= 180 * DAY {
+        7 * DAY
+    } else if ns >= 2 * DAY {
+        DAY
+    } else {
+        HOUR
+    }
+}
+
+/// Format the duration of `ns` nanoseconds in the same form as the
+/// `String` method of the Go `time.Duration` type, such as `168h0m0s`.
+fn format_duration(ns: i64) -> String {
+    const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+    let ns = ns.max(0) as u64;
+    if ns == 0 {
+        return "0s".to_owned();
+    }
+
+    if ns < NANOS_PER_SEC {
+        let (unit, divisor) = if ns < 1_000 {
+            ("ns", 1)
+        } else if ns < 1_000_000 {
+            ("µs", 1_000)
+        } else {
+            ("ms", 1_000_000)
+        };
+        return format!("{}{unit}", format_fraction(ns, divisor));
+    }
+
+    let hours = ns / (3600 * NANOS_PER_SEC);
+    let minutes = ns / (60 * NANOS_PER_SEC) % 60;
+    let seconds = format_fraction(ns % (60 * NANOS_PER_SEC), NANOS_PER_SEC);
+    if hours > 0 {
+        format!("{hours}h{minutes}m{seconds}s")
+    } else if minutes > 0 {
+        format!("{minutes}m{seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+/// Format `v / divisor` as a decimal, without trailing zeros.
+fn format_fraction(v: u64, divisor: u64) -> String {
+    let (whole, fraction) = (v / divisor, v % divisor);
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let width = divisor.ilog10() as usize;
+    let fraction = format!("{fraction:0width$}");
+    format!("{whole}.{}", fraction.trim_end_matches('0'))
+}
+
 /// Find the index of the time column in the fields list.
 ///
 /// > **Note**
@@ -2152,6 +3251,202 @@ fn find_expr(cond: &ConditionalExpression) -> Result<&IQLExpr> {
         .ok_or_else(|| error::map::internal("incomplete conditional expression"))
 }
 
+/// Adds the conjunction `cond` of the `WHERE` clause of a `DELETE` statement to
+/// the inclusive time range, `start` and `end`, and the tag comparisons, `exprs`.
+///
+/// Only comparisons of the `time` column to a timestamp and comparisons of a tag
+/// to a string literal using `=` or `!=` are supported, which is consistent with
+/// the predicates supported by the `/api/v2/delete` API.
+fn add_delete_condition(
+    cond: &ConditionalExpression,
+    start: &mut i64,
+    end: &mut i64,
+    exprs: &mut Vec<DeleteExpr>,
+) -> Result<()> {
+    let operand = |cond: &ConditionalExpression| -> Result<IQLExpr> {
+        cond.expr()
+            .cloned()
+            .ok_or_else(|| error::map::query(format!("unsupported DELETE condition: {cond}")))
+    };
+
+    match cond {
+        ConditionalExpression::Grouped(e) => add_delete_condition(e, start, end, exprs),
+        ConditionalExpression::Expr(_) => {
+            error::query(format!("unsupported DELETE condition: {cond}"))
+        }
+        ConditionalExpression::Binary(ConditionalBinary { lhs, op, rhs }) => {
+            use ConditionalOperator::*;
+            if *op == And {
+                add_delete_condition(lhs, start, end, exprs)?;
+                return add_delete_condition(rhs, start, end, exprs);
+            }
+
+            // Normalise the comparison such that `time` is on the left-hand side.
+            let time_cmp = if is_time_field(lhs) {
+                Some((*op, operand(rhs)?))
+            } else if is_time_field(rhs) {
+                let op = match op {
+                    Lt => Gt,
+                    LtEq => GtEq,
+                    Gt => Lt,
+                    GtEq => LtEq,
+                    op => *op,
+                };
+                Some((op, operand(lhs)?))
+            } else {
+                None
+            };
+
+            if let Some((op, expr)) = time_cmp {
+                let ts = delete_timestamp(&expr)?;
+                match op {
+                    Eq => {
+                        *start = (*start).max(ts);
+                        *end = (*end).min(ts);
+                    }
+                    Gt => *start = (*start).max(ts.saturating_add(1)),
+                    GtEq => *start = (*start).max(ts),
+                    Lt => *end = (*end).min(ts.saturating_sub(1)),
+                    LtEq => *end = (*end).min(ts),
+                    _ => {
+                        return error::query(format!(
+                            "unsupported operator for time in DELETE condition: {op}"
+                        ))
+                    }
+                }
+                return Ok(());
+            }
+
+            let op = match op {
+                Eq => Op::Eq,
+                NotEq => Op::Ne,
+                _ => {
+                    return error::query(format!("unsupported operator in DELETE condition: {op}"))
+                }
+            };
+            match (operand(lhs)?, operand(rhs)?) {
+                (
+                    IQLExpr::VarRef(VarRef { name, .. }),
+                    IQLExpr::Literal(Literal::String(value)),
+                )
+                | (
+                    IQLExpr::Literal(Literal::String(value)),
+                    IQLExpr::VarRef(VarRef { name, .. }),
+                ) => {
+                    exprs.push(DeleteExpr::new(
+                        name.deref().to_owned(),
+                        op,
+                        Scalar::String(value),
+                    ));
+                    Ok(())
+                }
+                _ => error::query(format!(
+                    "DELETE condition must compare a tag to a string: {cond}"
+                )),
+            }
+        }
+    }
+}
+
+/// Evaluates the time expression `expr` of a `DELETE` condition to a timestamp.
+fn delete_timestamp(expr: &IQLExpr) -> Result<i64> {
+    let df_expr = time_range_to_df_expr(expr, None)?;
+
+    let props = ExecutionProps::new();
+    let simplifier =
+        ExprSimplifier::new(SimplifyContext::new(&props).with_schema(Arc::new(DFSchema::empty())));
+    match simplifier.simplify(df_expr)? {
+        Expr::Literal(ScalarValue::TimestampNanosecond(Some(ts), _)) => Ok(ts),
+        _ => error::query(format!("invalid time in DELETE condition: {expr}")),
+    }
+}
+
+/// Returns the conjunction of all the top-level conditional expressions of `cond`
+/// that compare the `time` column, or `None` if there are none.
+///
+/// The result is used to narrow the time range of a subquery to that of the outer
+/// query. Conditions on `time` nested within an `OR` are ignored, as they do not
+/// restrict the time range of the outer query.
+fn find_time_range_conditions(cond: &ConditionalExpression) -> Option<ConditionalExpression> {
+    match cond {
+        ConditionalExpression::Grouped(e) => find_time_range_conditions(e),
+        ConditionalExpression::Binary(ConditionalBinary {
+            lhs,
+            op: ConditionalOperator::And,
+            rhs,
+        }) => match (
+            find_time_range_conditions(lhs),
+            find_time_range_conditions(rhs),
+        ) {
+            (Some(lhs), Some(rhs)) => Some(ConditionalExpression::Binary(ConditionalBinary {
+                lhs: Box::new(lhs),
+                op: ConditionalOperator::And,
+                rhs: Box::new(rhs),
+            })),
+            (Some(expr), None) | (None, Some(expr)) => Some(expr),
+            (None, None) => None,
+        },
+        ConditionalExpression::Binary(ConditionalBinary {
+            lhs,
+            op:
+                ConditionalOperator::Eq
+                | ConditionalOperator::Lt
+                | ConditionalOperator::LtEq
+                | ConditionalOperator::Gt
+                | ConditionalOperator::GtEq,
+            rhs,
+        }) if is_time_field(lhs) ^ is_time_field(rhs) => Some(cond.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the IOx schema of the columns produced by the plan of the `select` subquery,
+/// described by `df_schema`.
+///
+/// Tags are those columns that are specified in the `GROUP BY` clause or those that project
+/// a tag. All remaining columns, except the measurement and `time` columns, are fields.
+fn subquery_schema(select: &SelectStatement, df_schema: &DFSchemaRef) -> Result<Schema> {
+    let tags = select
+        .group_by
+        .iter()
+        .flat_map(|gb| gb.tags().map(|t| t.deref().as_str()))
+        .chain(
+            select
+                .fields
+                .iter()
+                .filter_map(|f| match (&f.expr, &f.alias) {
+                    (
+                        IQLExpr::VarRef(VarRef {
+                            data_type: Some(VarRefDataType::Tag),
+                            ..
+                        }),
+                        Some(alias),
+                    ) => Some(alias.deref().as_str()),
+                    _ => None,
+                }),
+        )
+        .collect::<HashSet<_>>();
+
+    let mut builder = SchemaBuilder::new();
+    for field in df_schema.fields() {
+        match field.name().as_str() {
+            INFLUXQL_MEASUREMENT_COLUMN_NAME => continue,
+            "time" => builder.timestamp(),
+            name if tags.contains(name) => builder.tag(name),
+            name => match InfluxFieldType::try_from(field.data_type().clone()) {
+                Ok(field_type) => builder.influx_field(name, field_type),
+                // Columns without a corresponding InfluxDB type, such as those that
+                // are always NULL, are treated as missing by the outer query.
+                Err(_) => continue,
+            },
+        };
+    }
+
+    builder
+        .build()
+        .map_err(|err| error::map::internal(format!("unable to create schema for subquery: {err}")))
+}
+
 /// Evaluate [`WithKeyClause`] on the given list of keys.
 ///
 /// This may fail if the clause contains an invalid regex.
@@ -2231,8 +3526,7 @@ mod test {
     use insta::assert_snapshot;
     use schema::SchemaBuilder;
 
-    fn logical_plan(sql: &str) -> Result<LogicalPlan> {
-        let mut statements = parse_statements(sql).unwrap();
+    fn schema_provider() -> MockSchemaProvider {
         let mut sp = MockSchemaProvider::default();
         sp.add_schemas(vec![
             SchemaBuilder::new()
@@ -2264,7 +3558,12 @@ mod test {
                 .build()
                 .unwrap(),
         ]);
+        sp
+    }
 
+    fn logical_plan(sql: &str) -> Result<LogicalPlan> {
+        let mut statements = parse_statements(sql).unwrap();
+        let sp = schema_provider();
         let iox_ctx = IOxSessionContext::with_testing();
         let planner = InfluxQLToLogicalPlan::new(&sp, &iox_ctx);
 
@@ -2294,10 +3593,124 @@ mod test {
     #[test]
     fn test_unsupported_statements() {
         assert_snapshot!(plan("CREATE DATABASE foo"), @"This feature is not implemented: CREATE DATABASE");
-        assert_snapshot!(plan("DELETE FROM foo"), @"This feature is not implemented: DELETE");
-        assert_snapshot!(plan("DROP MEASUREMENT foo"), @"This feature is not implemented: DROP MEASUREMENT");
-        assert_snapshot!(plan("SHOW DATABASES"), @"This feature is not implemented: SHOW DATABASES");
-        assert_snapshot!(plan("SHOW RETENTION POLICIES"), @"This feature is not implemented: SHOW RETENTION POLICIES");
+    }
+
+    mod delete {
+        use super::*;
+
+        fn delete_request(sql: &str) -> Result<Option<DeleteRequest>> {
+            let mut statements = parse_statements(sql).unwrap();
+            let sp = schema_provider();
+            let iox_ctx = IOxSessionContext::with_testing();
+            let planner = InfluxQLToLogicalPlan::new(&sp, &iox_ctx);
+
+            planner.statement_to_delete_request(&statements.pop().unwrap())
+        }
+
+        fn tag_expr(column: &str, op: Op, value: &str) -> DeleteExpr {
+            DeleteExpr::new(column.to_owned(), op, Scalar::String(value.to_owned()))
+        }
+
+        #[test]
+        fn test_not_a_delete() {
+            assert_eq!(delete_request("SELECT f64_field FROM data").unwrap(), None);
+        }
+
+        #[test]
+        fn test_delete_from() {
+            assert_eq!(
+                delete_request("DELETE FROM data").unwrap().unwrap(),
+                DeleteRequest {
+                    table_names: vec!["data".into()],
+                    predicate: DeletePredicate {
+                        range: TimestampRange::new(MIN_NANO_TIME, MAX_NANO_TIME),
+                        exprs: vec![],
+                    },
+                }
+            );
+
+            // regular expressions are expanded, and unknown measurements are ignored
+            assert_eq!(
+                delete_request("DELETE FROM /^(all_|d)/, non_existent")
+                    .unwrap()
+                    .unwrap()
+                    .table_names,
+                vec!["all_types", "data"]
+            );
+        }
+
+        #[test]
+        fn test_delete_where() {
+            assert_eq!(
+                delete_request(
+                    "DELETE WHERE foo = 'a' AND (bar != 'b' AND time >= 10) AND 20 > time"
+                )
+                .unwrap()
+                .unwrap(),
+                DeleteRequest {
+                    table_names: vec!["all_types".into(), "data".into()],
+                    predicate: DeletePredicate {
+                        range: TimestampRange::new(10, 19),
+                        exprs: vec![tag_expr("foo", Op::Eq, "a"), tag_expr("bar", Op::Ne, "b")],
+                    },
+                }
+            );
+
+            let req = delete_request(
+                "DELETE FROM data WHERE time > '2004-04-09T02:33:45Z' AND time <= '2004-04-09T02:33:46Z'",
+            )
+            .unwrap()
+            .unwrap();
+            assert_eq!(
+                req.predicate.range,
+                TimestampRange::new(1081478025000000001, 1081478026000000000)
+            );
+
+            let req = delete_request("DELETE FROM data WHERE time = 5")
+                .unwrap()
+                .unwrap();
+            assert_eq!(req.predicate.range, TimestampRange::new(5, 5));
+        }
+
+        #[test]
+        fn test_delete_where_unsupported() {
+            assert_snapshot!(delete_request("DELETE FROM data WHERE foo = 'a' OR bar = 'b'").unwrap_err(), @"Error during planning: unsupported operator in DELETE condition: OR");
+            assert_snapshot!(delete_request("DELETE FROM data WHERE foo =~ /a/").unwrap_err(), @"Error during planning: unsupported operator in DELETE condition: =~");
+            assert_snapshot!(delete_request("DELETE FROM data WHERE f64_field = 1").unwrap_err(), @"Error during planning: DELETE condition must compare a tag to a string: f64_field = 1");
+            assert_snapshot!(delete_request("DELETE FROM data WHERE time != 1").unwrap_err(), @"Error during planning: unsupported operator for time in DELETE condition: !=");
+            assert_snapshot!(delete_request("DELETE FROM data WHERE time > 10 AND time < 5").unwrap_err(), @"Error during planning: DELETE time range is empty");
+        }
+
+        #[test]
+        fn test_drop_measurement() {
+            assert_eq!(
+                delete_request("DROP MEASUREMENT data").unwrap().unwrap(),
+                DeleteRequest {
+                    table_names: vec!["data".into()],
+                    predicate: DeletePredicate {
+                        range: TimestampRange::new(MIN_NANO_TIME, MAX_NANO_TIME),
+                        exprs: vec![],
+                    },
+                }
+            );
+
+            assert_eq!(
+                delete_request("DROP MEASUREMENT non_existent")
+                    .unwrap()
+                    .unwrap()
+                    .table_names,
+                Vec::<String>::new()
+            );
+        }
+
+        #[test]
+        fn test_delete_result_plan() {
+            let sp = schema_provider();
+            let iox_ctx = IOxSessionContext::with_testing();
+            let planner = InfluxQLToLogicalPlan::new(&sp, &iox_ctx);
+            let plan = planner.delete_result_plan().unwrap();
+            assert_snapshot!(plan.display_indent_schema(), @"EmptyRelation [iox::measurement:Dictionary(Int32, Utf8)]");
+        }
     }
 
     mod metadata_queries {
@@ -2315,6 +3728,20 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_show_databases() {
+            assert_snapshot!(plan("SHOW DATABASES"), @"TableScan: databases [iox::measurement:Dictionary(Int32, Utf8), name:Dictionary(Int32, Utf8)]");
+        }
+
+        #[test]
+        fn test_show_retention_policies() {
+            assert_snapshot!(plan("SHOW RETENTION POLICIES"), @"TableScan: retention_policies [iox::measurement:Dictionary(Int32, Utf8), name:Dictionary(Int32, Utf8), duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]");
+            assert_snapshot!(plan("SHOW RETENTION POLICIES ON foo"), @"TableScan: retention_policies [iox::measurement:Dictionary(Int32, Utf8), name:Dictionary(Int32, Utf8), duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]");
+
+            // Fallible cases
+            assert_snapshot!(plan("SHOW RETENTION POLICIES ON baz"), @"Error during planning: database not found: baz");
+        }
+
         #[test]
         fn test_snow_measurements() {
             assert_snapshot!(plan("SHOW MEASUREMENTS"), @"TableScan: measurements [iox::measurement:Dictionary(Int32, Utf8), name:Dictionary(Int32, Utf8)]");
@@ -2381,6 +3808,72 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_namespace_to_database_rp() {
+        assert_eq!(namespace_to_database_rp("foo"), ("foo", None));
+        assert_eq!(namespace_to_database_rp("foo/bar"), ("foo", Some("bar")));
+        assert_eq!(
+            namespace_to_database_rp("foo/bar/baz"),
+            ("foo", Some("bar/baz"))
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(1), "1ns");
+        assert_eq!(format_duration(1_500), "1.5µs");
+        assert_eq!(format_duration(2_000_000), "2ms");
+        assert_eq!(format_duration(1_500_000_000), "1.5s");
+        assert_eq!(format_duration(90_000_000_000), "1m30s");
+        assert_eq!(format_duration(3_600_000_000_000), "1h0m0s");
+        assert_eq!(format_duration(7 * 24 * 3_600_000_000_000), "168h0m0s");
+    }
+
+    #[test]
+    fn test_shard_group_duration() {
+        const HOUR: i64 = 3_600_000_000_000;
+        assert_eq!(shard_group_duration(0), 168 * HOUR);
+        assert_eq!(shard_group_duration(HOUR), HOUR);
+        assert_eq!(shard_group_duration(7 * 24 * HOUR), 24 * HOUR);
+        assert_eq!(shard_group_duration(365 * 24 * HOUR), 168 * HOUR);
+    }
+
+    #[test]
+    fn test_database_retention_policies() {
+        const HOUR: i64 = 3_600_000_000_000;
+        let namespaces = [
+            ("foo/one_week", Some(7 * 24 * HOUR)),
+            ("foo", None),
+            ("bar/one_hour", Some(HOUR)),
+            ("bar/a_day", Some(24 * HOUR)),
+        ]
+        .into_iter()
+        .map(|(name, retention_period_ns)| NamespaceInfo {
+            name: name.to_owned(),
+            retention_period_ns,
+        })
+        .collect::<Vec<_>>();
+
+        // The namespace without a retention policy is the default.
+        assert_eq!(
+            database_retention_policies(&namespaces, "foo"),
+            vec![
+                ("autogen".to_owned(), 0, true),
+                ("one_week".to_owned(), 7 * 24 * HOUR, false),
+            ]
+        );
+        // Otherwise the first policy is the default.
+        assert_eq!(
+            database_retention_policies(&namespaces, "bar"),
+            vec![
+                ("a_day".to_owned(), 24 * HOUR, true),
+                ("one_hour".to_owned(), HOUR, false),
+            ]
+        );
+        assert!(database_retention_policies(&namespaces, "baz").is_empty());
+    }
+
     /// Tests to validate InfluxQL `SELECT` statements, where the projections do not matter,
     /// such as the WHERE clause.
     mod select {
@@ -2495,6 +3988,123 @@ mod test {
                       TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
                 "###);
             }
+
+            /// Window-like functions are planned as DataFusion window functions,
+            /// which are evaluated for each series.
+            #[test]
+            fn test_window_functions() {
+                // Evaluated using the raw values of each series, omitting the rows
+                // that do not produce a value.
+                assert_snapshot!(plan("SELECT difference(usage_idle), non_negative_difference(usage_idle) FROM cpu GROUP BY cpu"), @r###"
+                Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, difference:Float64;N, non_negative_difference:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, difference(cpu.usage_idle) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS difference, non_negative_difference(cpu.usage_idle) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS non_negative_difference [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, difference:Float64;N, non_negative_difference:Float64;N]
+                    Filter: difference(cpu.usage_idle) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL OR non_negative_difference(cpu.usage_idle) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, difference(cpu.usage_idle) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N, non_negative_difference(cpu.usage_idle) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                      WindowAggr: windowExpr=[[difference(cpu.usage_idle) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW, non_negative_difference(cpu.usage_idle) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, difference(cpu.usage_idle) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N, non_negative_difference(cpu.usage_idle) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // Evaluated using the aggregated values of each series, when grouping by time
+                assert_snapshot!(plan("SELECT derivative(mean(usage_idle)), non_negative_derivative(mean(usage_idle), 1m), moving_average(mean(usage_idle), 3), cumulative_sum(mean(usage_idle)) FROM cpu GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, derivative:Float64;N, non_negative_derivative:Float64;N, moving_average:Float64;N, cumulative_sum:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, derivative(AVG(cpu.usage_idle),Int64(10000000000),time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS derivative, non_negative_derivative(AVG(cpu.usage_idle),Int64(60000000000),time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS non_negative_derivative, moving_average(AVG(cpu.usage_idle),Int64(3)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS moving_average, cumulative_sum(AVG(cpu.usage_idle)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS cumulative_sum [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, derivative:Float64;N, non_negative_derivative:Float64;N, moving_average:Float64;N, cumulative_sum:Float64;N]
+                    Filter: derivative(AVG(cpu.usage_idle),Int64(10000000000),time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL OR non_negative_derivative(AVG(cpu.usage_idle),Int64(60000000000),time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL OR moving_average(AVG(cpu.usage_idle),Int64(3)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL OR cumulative_sum(AVG(cpu.usage_idle)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, derivative(AVG(cpu.usage_idle),Int64(10000000000),time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N, non_negative_derivative(AVG(cpu.usage_idle),Int64(60000000000),time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N, moving_average(AVG(cpu.usage_idle),Int64(3)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N, cumulative_sum(AVG(cpu.usage_idle)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                      WindowAggr: windowExpr=[[derivative(AVG(cpu.usage_idle), Int64(10000000000), time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW, non_negative_derivative(AVG(cpu.usage_idle), Int64(60000000000), time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW, moving_average(AVG(cpu.usage_idle), Int64(3)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW, cumulative_sum(AVG(cpu.usage_idle)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, derivative(AVG(cpu.usage_idle),Int64(10000000000),time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N, non_negative_derivative(AVG(cpu.usage_idle),Int64(60000000000),time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N, moving_average(AVG(cpu.usage_idle),Int64(3)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N, cumulative_sum(AVG(cpu.usage_idle)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                        GapFill: groupBy=[[time]], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Excluded(now()) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                          Aggregate: groupBy=[[datebin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                            TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // Rows are not omitted when the projection includes other aggregates
+                assert_snapshot!(plan("SELECT mean(usage_idle), difference(mean(usage_idle)) FROM cpu GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, mean:Float64;N, difference:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, AVG(cpu.usage_idle) AS mean, difference(AVG(cpu.usage_idle)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS difference [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, mean:Float64;N, difference:Float64;N]
+                    WindowAggr: windowExpr=[[difference(AVG(cpu.usage_idle)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, difference(AVG(cpu.usage_idle)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                      GapFill: groupBy=[[time]], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Excluded(now()) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                        Aggregate: groupBy=[[datebin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // Fallible
+
+                assert_snapshot!(plan("SELECT difference(usage_idle), mean(usage_idle) FROM cpu"), @"This feature is not implemented: mixing window-like functions with aggregate or selector functions without a GROUP BY interval");
+                assert_snapshot!(plan("SELECT difference(usage_idle) FROM cpu GROUP BY TIME(10s)"), @"Error during planning: aggregate function required inside the call to difference");
+            }
+
+            #[test]
+            fn test_percentile() {
+                // single-selector query, projecting the time of the selected row
+                assert_snapshot!(plan("SELECT percentile(usage_idle, 90) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, percentile:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, (selector_percentile(cpu.usage_idle,cpu.time,Float64(90)))[time] AS time, (selector_percentile(cpu.usage_idle,cpu.time,Float64(90)))[value] AS percentile [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, percentile:Float64;N]
+                    Aggregate: groupBy=[[]], aggr=[[selector_percentile(cpu.usage_idle, cpu.time, Float64(90))]] [selector_percentile(cpu.usage_idle,cpu.time,Float64(90)):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // aggregate query, as we're grouping by time
+                assert_snapshot!(plan("SELECT percentile(usage_idle, 99.5), mean(usage_idle) FROM cpu GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, percentile:Float64;N, mean:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, selector_percentile_value(cpu.usage_idle,cpu.time,Float64(99.5)) AS percentile, AVG(cpu.usage_idle) AS mean [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, percentile:Float64;N, mean:Float64;N]
+                    GapFill: groupBy=[[time]], aggr=[[selector_percentile_value(cpu.usage_idle,cpu.time,Float64(99.5)), AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Excluded(now()) [time:Timestamp(Nanosecond, None);N, selector_percentile_value(cpu.usage_idle,cpu.time,Float64(99.5)):Float64;N, AVG(cpu.usage_idle):Float64;N]
+                      Aggregate: groupBy=[[datebin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[selector_percentile_value(cpu.usage_idle, cpu.time, Float64(99.5)), AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, selector_percentile_value(cpu.usage_idle,cpu.time,Float64(99.5)):Float64;N, AVG(cpu.usage_idle):Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+            }
+
+            /// The `top`, `bottom` and `sample` functions select multiple rows for
+            /// each series, using the `ROW_NUMBER` window function.
+            #[test]
+            fn test_multi_row_selectors() {
+                // The time column is the time of the selected row
+                assert_snapshot!(plan("SELECT top(usage_idle, 3) FROM cpu GROUP BY cpu"), @r###"
+                Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, top:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS top [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, top:Float64;N]
+                    Projection: cpu.cpu, cpu.host, cpu.region, cpu.time, cpu.usage_idle, cpu.usage_system, cpu.usage_user [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      Filter: iox::row <= Int64(3) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, iox::row:UInt64;N]
+                        WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [cpu.cpu] ORDER BY [cpu.usage_idle DESC NULLS LAST, cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::row]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, iox::row:UInt64;N]
+                          Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                            TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // Rows are ranked for each GROUP BY time interval
+                assert_snapshot!(plan("SELECT bottom(usage_idle, 3), usage_system FROM cpu GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), bottom:Float64;N, usage_system:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS bottom, cpu.usage_system AS usage_system [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), bottom:Float64;N, usage_system:Float64;N]
+                    Projection: cpu.cpu, cpu.host, cpu.region, cpu.time, cpu.usage_idle, cpu.usage_system, cpu.usage_user [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      Filter: iox::row <= Int64(3) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, iox::row:UInt64;N]
+                        WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [datebin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None))] ORDER BY [cpu.usage_idle ASC NULLS LAST, cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::row]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, iox::row:UInt64;N]
+                          Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                            TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // Tags of the function call are projected as columns, and only the first
+                // ranked row of each unique combination of the tag values is considered
+                assert_snapshot!(plan("SELECT top(usage_idle, host, region, 2) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), top:Float64;N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS top, cpu.host AS host, cpu.region AS region [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), top:Float64;N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N]
+                    Projection: cpu.cpu, cpu.host, cpu.region, cpu.time, cpu.usage_idle, cpu.usage_system, cpu.usage_user [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      Filter: iox::row <= Int64(2) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, iox::row:UInt64;N]
+                        WindowAggr: windowExpr=[[ROW_NUMBER() ORDER BY [cpu.usage_idle DESC NULLS LAST, cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::row]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, iox::row:UInt64;N]
+                          Projection: cpu.cpu, cpu.host, cpu.region, cpu.time, cpu.usage_idle, cpu.usage_system, cpu.usage_user [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                            Filter: iox::row <= Int64(1) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, iox::row:UInt64;N]
+                              WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [cpu.host, cpu.region] ORDER BY [cpu.usage_idle DESC NULLS LAST, cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::row]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, iox::row:UInt64;N]
+                                Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                assert_snapshot!(plan("SELECT sample(usage_idle, 2) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), sample:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS sample [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), sample:Float64;N]
+                    Projection: cpu.cpu, cpu.host, cpu.region, cpu.time, cpu.usage_idle, cpu.usage_system, cpu.usage_user [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      Filter: iox::row <= Int64(2) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, iox::row:UInt64;N]
+                        WindowAggr: windowExpr=[[ROW_NUMBER() ORDER BY [random() ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::row]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, iox::row:UInt64;N]
+                          Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                            TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // Fallible
+
+                assert_snapshot!(plan("SELECT sample(usage_idle, 2), mean(usage_idle) FROM cpu"), @"This feature is not implemented: selector function sample combined with other functions");
+            }
         }
 
         /// Test InfluxQL-specific behaviour of scalar functions that differ
@@ -3166,6 +4776,11 @@ mod test {
                 let max = (i64::MAX as u64) + 1;
                 assert_snapshot!(plan(format!("SELECT COUNT(f64_field) FROM data GROUP BY foo LIMIT {max}")), @"Error during planning: limit out of range");
                 assert_snapshot!(plan(format!("SELECT COUNT(f64_field) FROM data GROUP BY foo OFFSET {max}")), @"Error during planning: offset out of range");
+
+                // returns an error if OFFSET + LIMIT exceeds i64::MAX
+                let max = i64::MAX;
+                assert_snapshot!(plan(format!("SELECT COUNT(f64_field) FROM data GROUP BY foo LIMIT 1 OFFSET {max}")), @"Error during planning: offset out of range");
+                assert_snapshot!(plan(format!("SELECT COUNT(f64_field) FROM data GROUP BY foo LIMIT {max} OFFSET 1")), @"Error during planning: limit and offset out of range");
             }
 
             /// These tests validate the planner returns an error when using features that
@@ -3426,6 +5041,57 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_select_group_by_slimit_soffset() {
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() PARTITION BY [iox::measurement] ORDER BY [cpu ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SOFFSET 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series > Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() PARTITION BY [iox::measurement] ORDER BY [cpu ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 2 SOFFSET 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series BETWEEN Int64(2) AND Int64(3) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() PARTITION BY [iox::measurement] ORDER BY [cpu ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // Without a GROUP BY tag, the measurement is a single series
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu SLIMIT 1"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu SOFFSET 1"), @"EmptyRelation [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]");
+
+            // Fallible
+
+            // returns an error if SLIMIT or SOFFSET values exceed i64::MAX
+            let max = (i64::MAX as u64) + 1;
+            assert_snapshot!(plan(format!("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT {max}")), @"Error during planning: slimit out of range");
+            assert_snapshot!(plan(format!("SELECT usage_idle FROM cpu GROUP BY cpu SOFFSET {max}")), @"Error during planning: soffset out of range");
+
+            // returns an error if SOFFSET + SLIMIT exceeds i64::MAX
+            let max = i64::MAX;
+            assert_snapshot!(plan(format!("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 1 SOFFSET {max}")), @"Error during planning: soffset out of range");
+            assert_snapshot!(plan(format!("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT {max} SOFFSET 1")), @"Error during planning: slimit and soffset out of range");
+        }
+
         // The following is an outline of additional scenarios to develop
         // as the planner learns more features.
         // This is not an exhaustive list and is expected to grow as the
@@ -3518,6 +5184,87 @@ mod test {
         // * regular expression matching
     }
 
+    /// Tests to validate InfluxQL `SELECT` statements that specify a subquery in the
+    /// `FROM` clause.
+    mod select_subquery {
+        use super::*;
+
+        #[test]
+        fn test_raw() {
+            assert_snapshot!(plan("SELECT usage_idle FROM (SELECT usage_idle FROM cpu)"), @r###"
+            Sort: iox::measurement ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Projection: iox::measurement, time AS time, usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // The subquery does not match any measurements
+            assert_snapshot!(plan("SELECT usage_idle FROM (SELECT usage_idle FROM non_existent)"), @"EmptyRelation []");
+        }
+
+        /// The time range of the outer query narrows the time range of the subquery.
+        #[test]
+        fn test_time_range() {
+            assert_snapshot!(plan("SELECT usage_idle FROM (SELECT usage_idle FROM cpu) WHERE time > '2004-04-09T02:33:45Z'"), @r###"
+            Sort: iox::measurement ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Projection: iox::measurement, time AS time, usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                Filter: time > TimestampNanosecond(1081478025000000000, None) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                  Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                    Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                      Filter: cpu.time > TimestampNanosecond(1081478025000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        /// The outer query aggregates each measurement of the subquery independently.
+        #[test]
+        fn test_aggregate() {
+            assert_snapshot!(plan("SELECT count(usage_idle) FROM (SELECT usage_idle FROM cpu)"), @r###"
+            Sort: iox::measurement ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), count:Int64;N]
+              Projection: iox::measurement, TimestampNanosecond(0, None) AS time, COUNT(usage_idle) AS count [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), count:Int64;N]
+                Aggregate: groupBy=[[iox::measurement]], aggr=[[COUNT(usage_idle)]] [iox::measurement:Dictionary(Int32, Utf8), COUNT(usage_idle):Int64;N]
+                  Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                    Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        /// The outer query aggregates a subquery grouped by time and tag, where the
+        /// time range of the outer query bounds both the subquery and the gap filling
+        /// of the outer `GROUP BY time` interval.
+        #[test]
+        fn test_aggregate_group_by_time_and_tag() {
+            // Default is FILL(null) for the outer query, and the subquery is FILL(none)
+            assert_snapshot!(plan("SELECT count(usage_idle) FROM (SELECT mean(usage_idle) AS usage_idle FROM cpu GROUP BY time(10s), cpu) WHERE time >= '2022-10-31T02:00:00Z' AND time < '2022-10-31T02:02:00Z' GROUP BY time(1m)"), @r###"
+            Sort: iox::measurement ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64;N]
+              Projection: iox::measurement, time, COUNT(usage_idle) AS count [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count:Int64;N]
+                GapFill: groupBy=[[time, iox::measurement]], aggr=[[COUNT(usage_idle)]], time_column=time, stride=IntervalMonthDayNano("60000000000"), range=Included(TimestampNanosecond(1667181600000000000, None))..Excluded(TimestampNanosecond(1667181720000000000, None)) [time:Timestamp(Nanosecond, None);N, iox::measurement:Dictionary(Int32, Utf8), COUNT(usage_idle):Int64;N]
+                  Aggregate: groupBy=[[datebin(IntervalMonthDayNano("60000000000"), time, TimestampNanosecond(0, None)) AS time, iox::measurement]], aggr=[[COUNT(usage_idle)]] [time:Timestamp(Nanosecond, None);N, iox::measurement:Dictionary(Int32, Utf8), COUNT(usage_idle):Int64;N]
+                    Filter: time >= TimestampNanosecond(1667181600000000000, None) AND time < TimestampNanosecond(1667181720000000000, None) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, cpu.cpu AS cpu, AVG(cpu.usage_idle) AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                          Aggregate: groupBy=[[datebin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time, cpu.cpu]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, AVG(cpu.usage_idle):Float64;N]
+                            Filter: cpu.time >= TimestampNanosecond(1667181600000000000, None) AND cpu.time < TimestampNanosecond(1667181720000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                              TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // The outer query groups by the tag of the subquery and fills with the previous value
+            assert_snapshot!(plan("SELECT count(usage_idle) FROM (SELECT mean(usage_idle) AS usage_idle FROM cpu GROUP BY time(10s), cpu) WHERE time >= '2022-10-31T02:00:00Z' AND time < '2022-10-31T02:02:00Z' GROUP BY time(1m), cpu FILL(previous)"), @r###"
+            Sort: iox::measurement ASC NULLS LAST, cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, count:Int64;N]
+              Projection: iox::measurement, time, cpu AS cpu, COUNT(usage_idle) AS count [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, count:Int64;N]
+                GapFill: groupBy=[[time, cpu, iox::measurement]], aggr=[[LOCF(COUNT(usage_idle))]], time_column=time, stride=IntervalMonthDayNano("60000000000"), range=Included(TimestampNanosecond(1667181600000000000, None))..Excluded(TimestampNanosecond(1667181720000000000, None)) [time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, iox::measurement:Dictionary(Int32, Utf8), COUNT(usage_idle):Int64;N]
+                  Aggregate: groupBy=[[datebin(IntervalMonthDayNano("60000000000"), time, TimestampNanosecond(0, None)) AS time, cpu, iox::measurement]], aggr=[[COUNT(usage_idle)]] [time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, iox::measurement:Dictionary(Int32, Utf8), COUNT(usage_idle):Int64;N]
+                    Filter: time >= TimestampNanosecond(1667181600000000000, None) AND time < TimestampNanosecond(1667181720000000000, None) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, cpu.cpu AS cpu, AVG(cpu.usage_idle) AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                          Aggregate: groupBy=[[datebin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time, cpu.cpu]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, AVG(cpu.usage_idle):Float64;N]
+                            Filter: cpu.time >= TimestampNanosecond(1667181600000000000, None) AND cpu.time < TimestampNanosecond(1667181720000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                              TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+    }
+
     /// This module contains esoteric features of InfluxQL that are identified during
     /// the development of other features, and require additional work to implement or resolve.
     ///
@@ -454,7 +454,7 @@ fn rewrite_field_list(s: &dyn SchemaProvider, stmt: &mut SelectStatement) -> Res
 /// [original implementation]. The names are assigned to the `alias` field of the [`Field`] struct.
 ///
 /// [original implementation]: https://github.com/influxdata/influxql/blob/1ba470371ec093d57a726b143fe6ccbacf1b452b/ast.go#L1651
-fn rewrite_field_list_aliases(field_list: &mut FieldList) -> Result<()> {
+pub(crate) fn rewrite_field_list_aliases(field_list: &mut FieldList) -> Result<()> {
     let names = field_list.iter().map(field_name).collect::<Vec<_>>();
     let mut column_aliases = HashMap::<&str, _>::from_iter(names.iter().map(|f| (f.as_str(), 0)));
     names
@@ -573,6 +573,9 @@ struct FieldChecker {
     /// `true` if the projection contains an invocation of the `TOP` or `BOTTOM` function.
     has_top_bottom: bool,
 
+    /// `true` if the projection contains an invocation of the `SAMPLE` function.
+    has_sample: bool,
+
     /// `true` when one or more projections do not contain an aggregate expression.
     has_non_aggregate_fields: bool,
 
@@ -584,6 +587,12 @@ struct FieldChecker {
 
     /// Accumulator for the number of selector expressions for the statement.
     selector_count: usize,
+
+    /// Accumulator for the number of window-like expressions, such as `difference`,
+    /// for the statement.
+    ///
+    /// Window-like expressions are also included in `aggregate_count`.
+    window_count: usize,
 }
 
 impl FieldChecker {
@@ -623,6 +632,11 @@ impl FieldChecker {
                     "selector functions top and bottom cannot be combined with other functions",
                 )
             }
+            2.. if self.has_sample => {
+                return error::not_implemented(
+                    "selector function sample combined with other functions",
+                )
+            }
             _ => {}
         }
 
@@ -651,10 +665,23 @@ impl FieldChecker {
 
         if self.has_top_bottom {
             Ok(ProjectionType::TopBottomSelector)
+        } else if self.has_sample {
+            Ok(ProjectionType::Sample)
         } else if self.has_group_by_time {
-            Ok(ProjectionType::Aggregate)
+            if self.window_count > 0 {
+                Ok(ProjectionType::WindowAggregate)
+            } else {
+                Ok(ProjectionType::Aggregate)
+            }
         } else if self.has_distinct {
             Ok(ProjectionType::RawDistinct)
+        } else if self.window_count > 0 {
+            if self.function_count() > self.window_count {
+                return error::not_implemented(
+                    "mixing window-like functions with aggregate or selector functions without a GROUP BY interval",
+                );
+            }
+            Ok(ProjectionType::Window)
         } else if self.selector_count == 1 && self.aggregate_count == 0 {
             Ok(ProjectionType::Selector {
                 has_fields: self.has_non_aggregate_fields,
@@ -806,6 +833,7 @@ impl FieldChecker {
 
     fn check_sample(&mut self, args: &[Expr]) -> Result<()> {
         self.inc_selector_count();
+        self.has_sample = true;
 
         check_exp_args!("sample", 2, args);
         let v = lit_integer!("sample", args, 1);
@@ -888,7 +916,7 @@ impl FieldChecker {
     }
 
     fn check_derivative(&mut self, name: &str, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
 
         check_exp_args!(name, 1, 2, args);
         match args.get(1) {
@@ -926,21 +954,21 @@ impl FieldChecker {
     }
 
     fn check_difference(&mut self, name: &str, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!(name, 1, args);
 
         self.check_nested_symbol(name, &args[0])
     }
 
     fn check_cumulative_sum(&mut self, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!("cumulative_sum", 1, args);
 
         self.check_nested_symbol("cumulative_sum", &args[0])
     }
 
     fn check_moving_average(&mut self, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!("moving_average", 2, args);
 
         let v = lit_integer!("moving_average", args, 1);
@@ -1103,6 +1131,13 @@ impl FieldChecker {
         self.selector_count += 1
     }
 
+    /// Increments the window function call count, which is also
+    /// counted as an aggregate function.
+    fn inc_window_count(&mut self) {
+        self.inc_aggregate_count();
+        self.window_count += 1
+    }
+
     fn check_nested_expr(&mut self, expr: &Expr) -> Result<()> {
         match expr {
             Expr::Call(c) if c.name == "distinct" => self.check_distinct(&c.args, true),
@@ -1154,6 +1189,14 @@ pub(crate) enum ProjectionType {
     },
     /// A query that projects the `top` or `bottom` selector function.
     TopBottomSelector,
+    /// A query that projects the `sample` selector function.
+    Sample,
+    /// A query that projects one or more window-like functions, such as `difference`,
+    /// without a `GROUP BY time` clause.
+    Window,
+    /// A query that projects one or more window-like functions, such as
+    /// `difference(mean(foo))`, with a `GROUP BY time` clause.
+    WindowAggregate,
 }
 
 /// Holds high-level information as the result of analysing
@@ -1257,6 +1300,31 @@ mod test {
 
         let info = select_statement_info(&parse_select("SELECT top(foo, 3) FROM cpu")).unwrap();
         assert_matches!(info.projection_type, ProjectionType::TopBottomSelector);
+
+        let info = select_statement_info(&parse_select("SELECT sample(foo, 3) FROM cpu")).unwrap();
+        assert_matches!(info.projection_type, ProjectionType::Sample);
+
+        let sel = parse_select("SELECT sample(foo, 3), mean(bar) FROM cpu");
+        assert_error!(
+            select_statement_info(&sel),
+            DataFusionError::NotImplemented(_)
+        );
+
+        let info = select_statement_info(&parse_select("SELECT difference(foo) FROM cpu")).unwrap();
+        assert_matches!(info.projection_type, ProjectionType::Window);
+
+        let info = select_statement_info(&parse_select(
+            "SELECT derivative(mean(foo)), mean(foo) FROM cpu GROUP BY TIME(10s)",
+        ))
+        .unwrap();
+        assert_matches!(info.projection_type, ProjectionType::WindowAggregate);
+
+        // Mixing window-like and aggregate functions requires a GROUP BY interval
+        let sel = parse_select("SELECT difference(foo), mean(foo) FROM cpu");
+        assert_error!(
+            select_statement_info(&sel),
+            DataFusionError::NotImplemented(_)
+        );
     }
 
     /// Verify all the aggregate, window-like and selector functions are handled
@@ -1,7 +1,7 @@
 //! APIs for testing.
 #![cfg(test)]
 
-use crate::plan::{error, SchemaProvider};
+use crate::plan::{error, NamespaceInfo, SchemaProvider};
 use datafusion::common::Result as DataFusionResult;
 use datafusion::datasource::empty::EmptyTable;
 use datafusion::datasource::provider_as_source;
@@ -33,6 +33,24 @@ pub(crate) mod database {
     use super::*;
     use schema::InfluxFieldType;
 
+    /// Return the namespaces of the test catalog.
+    pub(crate) fn namespaces() -> Vec<NamespaceInfo> {
+        vec![
+            NamespaceInfo {
+                name: "foo".to_owned(),
+                retention_period_ns: None,
+            },
+            NamespaceInfo {
+                name: "foo/one_week".to_owned(),
+                retention_period_ns: Some(7 * 24 * 3_600_000_000_000),
+            },
+            NamespaceInfo {
+                name: "bar/one_hour".to_owned(),
+                retention_period_ns: Some(3_600_000_000_000),
+            },
+        ]
+    }
+
     /// Return a set of schemas that make up the test database.
     pub(crate) fn schemas() -> Vec<Schema> {
         vec![
@@ -178,4 +196,12 @@ impl SchemaProvider for MockSchemaProvider {
     fn table_schema(&self, name: &str) -> Option<Schema> {
         self.tables.get(name).map(|(_, s)| s.clone())
     }
+
+    fn namespaces(&self) -> Vec<NamespaceInfo> {
+        database::namespaces()
+    }
+
+    fn namespace_name(&self) -> Option<&str> {
+        Some("foo")
+    }
 }
@@ -6,7 +6,7 @@ use datafusion::{
     common::DFSchemaRef,
     error::DataFusionError,
     logical_expr::{utils::exprlist_to_columns, ExprSchemable, LogicalPlan, LogicalPlanBuilder},
-    prelude::{when, Column, Expr},
+    prelude::{cast, count, lit, sum, when, Column, Expr},
 };
 use datafusion_util::AsExpr;
 use futures::{Stream, StreamExt, TryStreamExt};
@@ -44,6 +44,10 @@ use std::{cmp::Reverse, collections::BTreeSet, sync::Arc};
 
 const CONCURRENT_TABLE_JOBS: usize = 10;
 
+/// The name of the column holding the number of series produced by the plans
+/// of [`InfluxRpcPlanner::read_series_cardinality`].
+pub const SERIES_CARDINALITY_COLUMN_NAME: &str = "series";
+
 #[derive(Debug, Snafu)]
 pub enum Error {
     #[snafu(display("gRPC planner got error finding column names: {}", source))]
@@ -777,6 +781,45 @@ impl InfluxRpcPlanner {
         Ok(SeriesSetPlans::new(plans))
     }
 
+    /// Returns plans that count the series with data that match the
+    /// predicate, one plan per table.
+    ///
+    /// Consistent with the InfluxDB storage engine, a series is a unique
+    /// combination of measurement, tag set and field key, and fields without
+    /// values do not form a series. Each plan produces a single row with the
+    /// number of series of its table in the [`SERIES_CARDINALITY_COLUMN_NAME`]
+    /// column, so that the series themselves are never materialized.
+    ///
+    /// Schematically, the plan of each table looks like:
+    ///
+    /// (aggregate -- sum((count(field1) > 0) + ... + (count(fieldN) > 0)))
+    ///   (aggregate by tags -- count(field1), ..., count(fieldN))
+    ///     (apply filters)
+    pub async fn read_series_cardinality(
+        &self,
+        namespace: Arc<dyn QueryNamespace>,
+        rpc_predicate: InfluxRpcPredicate,
+    ) -> Result<Vec<LogicalPlan>> {
+        let ctx = self.ctx.child_ctx("planning_read_series_cardinality");
+        debug!(?rpc_predicate, "planning read_series_cardinality");
+
+        let table_predicates = rpc_predicate
+            .table_predicates(namespace.as_meta())
+            .context(CreatingPredicatesSnafu)?;
+
+        let plans = create_plans(
+            namespace,
+            &table_predicates,
+            ctx,
+            |table_name, predicate, chunks, schema| {
+                Self::read_series_cardinality_plan(table_name, schema, predicate, chunks)
+            },
+        )
+        .await?;
+
+        Ok(plans.into_iter().flatten().collect())
+    }
+
     /// Creates one or more GroupedSeriesSet plans that produces an
     /// output table with rows grouped according to group_columns and
     /// an aggregate function which is applied to each *series* (aka
@@ -1140,6 +1183,65 @@ impl InfluxRpcPlanner {
         Ok(ss_plan)
     }
 
+    /// Creates a plan that counts the series of a table, as described on
+    /// [`Self::read_series_cardinality`].
+    ///
+    /// Returns `None` if no field of the table matches the predicate, as the
+    /// table then has no series.
+    fn read_series_cardinality_plan(
+        table_name: &str,
+        schema: &Schema,
+        predicate: &Predicate,
+        chunks: Vec<Arc<dyn QueryChunk>>,
+    ) -> Result<Option<LogicalPlan>> {
+        let scan_and_filter = ScanPlanBuilder::new(Arc::from(table_name), schema)
+            .with_predicate(predicate)
+            .with_chunks(chunks)
+            .build()?;
+
+        let schema = scan_and_filter.provider.iox_schema();
+
+        let fields: Vec<_> = filtered_fields_iter(schema, predicate).collect();
+        if fields.is_empty() {
+            return Ok(None);
+        }
+
+        let tags: Vec<Expr> = schema
+            .tags_iter()
+            .map(|field| field.name().as_expr())
+            .collect();
+        let tags_and_fields: Vec<Expr> = tags
+            .iter()
+            .cloned()
+            .chain(fields.iter().map(|f| f.expr.clone()))
+            .collect();
+        let field_counts: Vec<Expr> = fields
+            .iter()
+            .map(|f| count(f.name.as_expr()).alias(f.name))
+            .collect();
+        let series = fields
+            .iter()
+            .map(|f| cast(f.name.as_expr().gt(lit(0_i64)), DataType::Int64))
+            .reduce(|a, b| a + b)
+            .expect("at least one field");
+
+        let plan = scan_and_filter
+            .plan_builder
+            .project(tags_and_fields)
+            .context(BuildingPlanSnafu)?
+            .aggregate(tags, field_counts)
+            .context(BuildingPlanSnafu)?
+            .aggregate(
+                Vec::<Expr>::new(),
+                vec![sum(series).alias(SERIES_CARDINALITY_COLUMN_NAME)],
+            )
+            .context(BuildingPlanSnafu)?
+            .build()
+            .context(BuildingPlanSnafu)?;
+
+        Ok(Some(plan))
+    }
+
     /// Creates a GroupedSeriesSet plan that produces an output table
     /// with one row per tagset and the values aggregated using a
     /// specific function.
@@ -32,6 +32,7 @@ impl ParquetFileBuilder {
                 created_at: Timestamp::new(0),
                 column_set: ColumnSet::new(vec![]),
                 max_l0_created_at: Timestamp::new(0),
+                max_tombstone_sequence_number: SequenceNumber::new(0),
             },
         }
     }
@@ -97,6 +98,16 @@ impl ParquetFileBuilder {
         }
     }
 
+    /// Set max_tombstone_sequence_number
+    pub fn with_max_tombstone_sequence_number(self, max_tombstone_sequence_number: i64) -> Self {
+        Self {
+            file: ParquetFile {
+                max_tombstone_sequence_number: SequenceNumber::new(max_tombstone_sequence_number),
+                ..self.file
+            },
+        }
+    }
+
     /// Create the [`ParquetFile`]
     pub fn build(self) -> ParquetFile {
         self.file
@@ -123,6 +134,8 @@ impl TableBuilder {
                 id: TableId::new(id),
                 namespace_id: NamespaceId::new(0),
                 name: "table".to_string(),
+                partition_template: None,
+                deleted_at: None,
             },
         }
     }
@@ -624,6 +624,7 @@ impl TestPartition {
             object_store_id,
             row_count,
             max_l0_created_at,
+            max_tombstone_sequence_number,
             ..
         } = builder;
 
@@ -665,6 +666,7 @@ impl TestPartition {
             compaction_level,
             column_set,
             max_l0_created_at: Timestamp::new(max_l0_created_at),
+            max_tombstone_sequence_number,
         };
 
         let mut repos = self.catalog.catalog.repositories().await;
@@ -711,6 +713,7 @@ pub struct TestParquetFileBuilder {
     object_store_id: Option<Uuid>,
     row_count: Option<usize>,
     max_l0_created_at: i64,
+    max_tombstone_sequence_number: SequenceNumber,
 }
 
 impl Default for TestParquetFileBuilder {
@@ -730,6 +733,7 @@ impl Default for TestParquetFileBuilder {
             object_store_id: None,
             row_count: None,
             max_l0_created_at: 1,
+            max_tombstone_sequence_number: SequenceNumber::new(0),
         }
     }
 }
@@ -792,6 +796,12 @@ impl TestParquetFileBuilder {
         self
     }
 
+    /// Specify the sequence number of the last tombstone applied to this parquet file.
+    pub fn with_max_tombstone_seq(mut self, max_tombstone_seq: i64) -> Self {
+        self.max_tombstone_sequence_number = SequenceNumber::new(max_tombstone_seq);
+        self
+    }
+
     /// Specify the compaction level for the parquet file metadata.
     pub fn with_compaction_level(mut self, compaction_level: CompactionLevel) -> Self {
         self.compaction_level = compaction_level;
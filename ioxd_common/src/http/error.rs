@@ -1,5 +1,6 @@
 use hyper::{Body, Response, StatusCode};
 use observability_deps::tracing::warn;
+use std::time::Duration;
 
 /// Constants used in API error codes.
 ///
@@ -101,6 +102,10 @@ pub struct HttpApiError {
 
     /// Human-readable message.
     msg: String,
+
+    /// How long the client should wait before retrying the request, returned
+    /// in the `Retry-After` header.
+    retry_after: Option<Duration>,
 }
 
 impl HttpApiError {
@@ -109,9 +114,16 @@ impl HttpApiError {
         Self {
             code: code.into(),
             msg: msg.into(),
+            retry_after: None,
         }
     }
 
+    /// Ask the client to wait for `retry_after` before retrying the request.
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = Some(retry_after);
+        self
+    }
+
     /// Generate response body for this error.
     fn body(&self) -> Body {
         let json = serde_json::json!({
@@ -125,11 +137,18 @@ impl HttpApiError {
 
     /// Generate response for this error.
     pub fn response(&self) -> Response<Body> {
-        Response::builder()
+        let mut builder = Response::builder()
             .status(self.code.status_code())
-            .header("content-type", "application/json")
-            .body(self.body())
-            .unwrap()
+            .header("content-type", "application/json");
+
+        if let Some(retry_after) = self.retry_after {
+            // The header carries whole seconds, round up so the client does
+            // not retry too early.
+            let secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            builder = builder.header("retry-after", secs);
+        }
+
+        builder.body(self.body()).unwrap()
     }
 
     /// Check if the error is an internal server error.
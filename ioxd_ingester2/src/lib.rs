@@ -190,10 +190,15 @@ pub async fn create_ingester_server_type(
         PERSIST_BACKGROUND_FETCH_TIME,
         ingester_config.wal_directory.clone(),
         Duration::from_secs(ingester_config.wal_rotation_period_seconds),
+        ingester_config.wal_replay_strict,
         exec,
         ingester_config.persist_max_parallelism,
         ingester_config.persist_queue_depth,
         ingester_config.persist_hot_partition_cost,
+        ingester_config
+            .persist_idle_partition_seconds
+            .map(Duration::from_secs),
+        ingester_config.persist_buffer_watermark,
         object_store,
         shutdown_rx.map(|v| v.expect("shutdown sender dropped without calling shutdown")),
     )
@@ -0,0 +1,883 @@
+//! HTTP handlers for running InfluxQL queries through the [InfluxDB 1.x query
+//! API], and the `/api/v2/query` endpoint of the InfluxDB 2.x API.
+//!
+//! [InfluxDB 1.x query API]:
+//!     https://docs.influxdata.com/influxdb/v1.8/tools/api/#query-http-endpoint
+
+mod response;
+
+use std::{convert::Infallible, sync::Arc};
+
+use authz::{http::AuthorizationHeaderExtension, Action, Authorizer, Permission, Resource};
+use bytes::{Bytes, BytesMut};
+use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
+use futures::{stream::BoxStream, StreamExt};
+use hyper::{
+    header::{ACCEPT, CONTENT_TYPE},
+    Body, Method, Request, Response, StatusCode,
+};
+use iox_query::{
+    exec::{ExecutionContextProvider, IOxSessionContext},
+    QueryNamespace,
+};
+use ioxd_common::http::error::{HttpApiError, HttpApiErrorSource};
+use observability_deps::tracing::{debug, info};
+use serde::Deserialize;
+use service_common::{
+    planner::{influxql_deletes_data, influxql_requires_namespace, Planner},
+    AuthzNamespaceFilter, QueryNamespaceProvider,
+};
+use thiserror::Error;
+use trace::{ctx::SpanContext, span::SpanExt};
+
+use self::response::{
+    write_csv, Epoch, ErrorResponse, QueryResponse, SeriesBuilder, StatementResult,
+};
+
+/// The maximum size of a request body, which holds the query of a `POST`
+/// request.
+const MAX_REQUEST_BYTES: usize = 10 * 1024 * 1024;
+
+/// The number of rows in each chunk of a chunked response, unless set by the
+/// `chunk_size` parameter.
+const DEFAULT_CHUNK_SIZE: usize = 10_000;
+
+/// Errors returned by the query endpoints.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The requested path has no handler.
+    #[error("not found")]
+    NoHandler,
+
+    /// The request does not specify the database to query.
+    #[error("database name required")]
+    NoDatabase,
+
+    /// The request does not specify a query.
+    #[error("missing required parameter \"q\"")]
+    NoQuery,
+
+    /// The query parameters, or the form-encoded body, cannot be decoded.
+    #[error("invalid query parameters: {0}")]
+    InvalidParams(#[from] serde_urlencoded::de::Error),
+
+    /// The `epoch` parameter is not a supported precision.
+    #[error("invalid epoch: {0}")]
+    InvalidEpoch(String),
+
+    /// The JSON body of a 2.x query request cannot be decoded.
+    #[error("invalid request body: {0}")]
+    InvalidBody(serde_json::Error),
+
+    /// The 2.x query request is for a query language other than InfluxQL.
+    #[error("unsupported query type {0:?}, only InfluxQL queries are supported")]
+    UnsupportedQueryType(String),
+
+    /// The client disconnected while sending the request body.
+    #[error("failed to read request body: {0}")]
+    ClientHangup(hyper::Error),
+
+    /// The request body exceeds [`MAX_REQUEST_BYTES`].
+    #[error("max request size ({0} bytes) exceeded")]
+    RequestSizeExceeded(usize),
+
+    /// The query is not valid InfluxQL.
+    #[error("error parsing query: {0}")]
+    ParseQuery(DataFusionError),
+
+    /// The request has no authentication, but authorization is configured.
+    #[error("authentication required")]
+    Unauthenticated,
+
+    /// The provided authorization is not sufficient to perform the request.
+    #[error("access denied")]
+    Forbidden,
+
+    /// An error occurred verifying the authorization token.
+    #[error(transparent)]
+    Authorizer(authz::Error),
+}
+
+impl Error {
+    /// Convert the error into an appropriate [`StatusCode`] to be returned to
+    /// the end user.
+    pub fn as_status_code(&self) -> StatusCode {
+        match self {
+            Self::NoHandler => StatusCode::NOT_FOUND,
+            Self::NoDatabase
+            | Self::NoQuery
+            | Self::InvalidParams(_)
+            | Self::InvalidEpoch(_)
+            | Self::InvalidBody(_)
+            | Self::ClientHangup(_)
+            | Self::ParseQuery(_) => StatusCode::BAD_REQUEST,
+            Self::UnsupportedQueryType(_) => StatusCode::NOT_IMPLEMENTED,
+            Self::RequestSizeExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Unauthenticated => StatusCode::UNAUTHORIZED,
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::Authorizer(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// The response to a 1.x query request that failed, which reports the
+    /// error in the body in the shape used by the 1.x API.
+    fn into_v1_response(self) -> Response<Body> {
+        let body = serde_json::to_vec(&ErrorResponse {
+            error: &self.to_string(),
+        })
+        .expect("error response serialization is infallible");
+
+        Response::builder()
+            .status(self.as_status_code())
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+}
+
+impl From<authz::Error> for Error {
+    fn from(value: authz::Error) -> Self {
+        match value {
+            authz::Error::Forbidden => Self::Forbidden,
+            authz::Error::NoToken => Self::Unauthenticated,
+            e => Self::Authorizer(e),
+        }
+    }
+}
+
+impl HttpApiErrorSource for Error {
+    fn to_http_api_error(&self) -> HttpApiError {
+        HttpApiError::new(self.as_status_code(), self.to_string())
+    }
+}
+
+/// Parameters of a 1.x query request, read from the query string and, for
+/// `POST` requests, a form-encoded body.
+#[derive(Debug, Default, Deserialize)]
+struct QueryParamsV1 {
+    db: Option<String>,
+    rp: Option<String>,
+    q: Option<String>,
+    epoch: Option<String>,
+    chunked: Option<bool>,
+    chunk_size: Option<usize>,
+    /// The password of the 1.x API, which is used as the authorization token.
+    p: Option<String>,
+}
+
+impl QueryParamsV1 {
+    /// Merge `other` into these parameters, preferring the values of `other`.
+    fn merge(self, other: Self) -> Self {
+        Self {
+            db: other.db.or(self.db),
+            rp: other.rp.or(self.rp),
+            q: other.q.or(self.q),
+            epoch: other.epoch.or(self.epoch),
+            chunked: other.chunked.or(self.chunked),
+            chunk_size: other.chunk_size.or(self.chunk_size),
+            p: other.p.or(self.p),
+        }
+    }
+
+    /// The namespace addressed by the `db` and `rp` parameters.
+    ///
+    /// Like for writes, a retention policy other than the default one is
+    /// mapped to the namespace `db/rp`.
+    fn namespace(&self) -> Result<String, Error> {
+        let db = self
+            .db
+            .as_deref()
+            .filter(|db| !db.is_empty())
+            .ok_or(Error::NoDatabase)?;
+
+        Ok(match self.rp.as_deref() {
+            None | Some("") | Some("autogen") => db.to_string(),
+            Some(rp) => format!("{db}/{rp}"),
+        })
+    }
+}
+
+/// Query string parameters of a 2.x query request.
+#[derive(Debug, Deserialize)]
+struct QueryParamsV2 {
+    bucket: Option<String>,
+}
+
+/// The JSON body of a 2.x query request.
+#[derive(Debug, Deserialize)]
+struct QueryRequestV2 {
+    query: String,
+    #[serde(rename = "type", default)]
+    query_type: Option<String>,
+}
+
+/// The encoding of the results of a query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    fn from_request(req: &Request<Body>) -> Self {
+        match req.headers().get(ACCEPT).and_then(|v| v.to_str().ok()) {
+            Some(accept) if accept.contains("application/csv") || accept.contains("text/csv") => {
+                Self::Csv
+            }
+            _ => Self::Json,
+        }
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Csv => "application/csv",
+        }
+    }
+}
+
+/// The results of a query, in the order of its statements.
+type ResultStream = BoxStream<'static, StatementResult>;
+
+/// This type is responsible for servicing InfluxQL query requests to the
+/// `querier` HTTP endpoint.
+#[derive(Debug)]
+pub(crate) struct HttpDelegate<S> {
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+}
+
+impl<S> HttpDelegate<S>
+where
+    S: QueryNamespaceProvider,
+{
+    pub(crate) fn new(server: Arc<S>, authz: Option<Arc<dyn Authorizer>>) -> Self {
+        Self { server, authz }
+    }
+
+    /// Routes `req` to the appropriate handler, if any, returning the handler
+    /// response.
+    pub(crate) async fn route(&self, req: Request<Body>) -> Result<Response<Body>, Error> {
+        match (req.method(), req.uri().path()) {
+            (&Method::GET | &Method::POST, "/query") => Ok(self
+                .query_v1(req)
+                .await
+                .unwrap_or_else(Error::into_v1_response)),
+            (&Method::POST, "/api/v2/query") => self.query_v2(req).await,
+            _ => Err(Error::NoHandler),
+        }
+    }
+
+    async fn query_v1(&self, req: Request<Body>) -> Result<Response<Body>, Error> {
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+        let format = Format::from_request(&req);
+        let header_token = authorization_token(&req);
+
+        let mut params: QueryParamsV1 =
+            serde_urlencoded::from_str(req.uri().query().unwrap_or_default())?;
+
+        let is_form = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map_or(false, |v| {
+                v.starts_with("application/x-www-form-urlencoded")
+            });
+        if req.method() == Method::POST && is_form {
+            let body = read_body(req.into_body()).await?;
+            params = params.merge(serde_urlencoded::from_bytes(&body)?);
+        }
+
+        let query = params
+            .q
+            .as_deref()
+            .filter(|q| !q.is_empty())
+            .ok_or(Error::NoQuery)?;
+        // Clients list the databases when they connect, before choosing one.
+        let namespace = match params.namespace() {
+            Err(Error::NoDatabase) if !influxql_requires_namespace(query) => None,
+            namespace => Some(namespace?),
+        };
+        let epoch = params
+            .epoch
+            .as_deref()
+            .map(|e| Epoch::parse(e).ok_or_else(|| Error::InvalidEpoch(e.to_string())))
+            .transpose()?;
+        // Timestamps are always integers in CSV responses.
+        let epoch = match format {
+            Format::Json => epoch,
+            Format::Csv => epoch.or(Some(Epoch::Nanoseconds)),
+        };
+        let chunk_size = params.chunked.unwrap_or_default().then(|| {
+            params
+                .chunk_size
+                .filter(|n| *n > 0)
+                .unwrap_or(DEFAULT_CHUNK_SIZE)
+        });
+
+        let token = header_token.or_else(|| params.p.as_ref().map(|p| p.as_bytes().to_vec()));
+        match &namespace {
+            Some(namespace) => self.authorize(token.as_deref(), namespace, query).await?,
+            // The statements only list the namespaces the token grants access
+            // to, but still require a token if authorization is configured.
+            None if self.authz.is_some() && token.is_none() => return Err(Error::Unauthenticated),
+            None => {}
+        }
+
+        info!(?namespace, %query, "HTTP InfluxQL query request");
+
+        let results = self
+            .run_query(
+                span_ctx,
+                token,
+                namespace.as_deref(),
+                query,
+                epoch,
+                chunk_size,
+            )
+            .await?;
+
+        Ok(encode_response(results, format, chunk_size.is_some()).await)
+    }
+
+    async fn query_v2(&self, req: Request<Body>) -> Result<Response<Body>, Error> {
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+        let format = Format::from_request(&req);
+        let token = authorization_token(&req);
+
+        let params: QueryParamsV2 =
+            serde_urlencoded::from_str(req.uri().query().unwrap_or_default())?;
+        let namespace = params
+            .bucket
+            .filter(|b| !b.is_empty())
+            .ok_or(Error::NoDatabase)?;
+
+        let body = read_body(req.into_body()).await?;
+        let request: QueryRequestV2 = serde_json::from_slice(&body).map_err(Error::InvalidBody)?;
+        match request.query_type.as_deref() {
+            Some("influxql") => {}
+            t => return Err(Error::UnsupportedQueryType(t.unwrap_or("flux").to_string())),
+        }
+
+        self.authorize(token.as_deref(), &namespace, &request.query)
+            .await?;
+
+        info!(%namespace, query=%request.query, "HTTP InfluxQL query request");
+
+        let epoch = match format {
+            Format::Json => None,
+            Format::Csv => Some(Epoch::Nanoseconds),
+        };
+        let results = self
+            .run_query(
+                span_ctx,
+                token,
+                Some(&namespace),
+                &request.query,
+                epoch,
+                None,
+            )
+            .await?;
+
+        Ok(encode_response(results, format, false).await)
+    }
+
+    /// Require the request to carry a token that permits running `query`
+    /// against `namespace`.
+    ///
+    /// Queries that delete data, such as `DELETE` and `DROP MEASUREMENT`,
+    /// require write permission, all other queries require read permission.
+    async fn authorize(
+        &self,
+        token: Option<&[u8]>,
+        namespace: &str,
+        query: &str,
+    ) -> Result<(), Error> {
+        let action = if influxql_deletes_data(query) {
+            Action::Write
+        } else {
+            Action::Read
+        };
+        let perms = [Permission::ResourceAction(
+            Resource::Database(namespace.to_string()),
+            action,
+        )];
+        self.authz.require_any_permission(token, &perms).await?;
+
+        Ok(())
+    }
+
+    /// Plan the statements of the InfluxQL `query` against `namespace`,
+    /// returning a stream that executes them in turn.
+    ///
+    /// A statement that fails is reported by its [`StatementResult`], and
+    /// does not fail the statements that follow it. Statements that list
+    /// namespaces, such as `SHOW DATABASES`, only list those `token` grants
+    /// access to. Queries without a `namespace` can only list namespaces.
+    async fn run_query(
+        &self,
+        span_ctx: Option<SpanContext>,
+        token: Option<Vec<u8>>,
+        namespace: Option<&str>,
+        query: &str,
+        epoch: Option<Epoch>,
+        chunk_size: Option<usize>,
+    ) -> Result<ResultStream, Error> {
+        let permit = self
+            .server
+            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .await;
+
+        let filter = Arc::new(AuthzNamespaceFilter::new(self.authz.clone(), token));
+        let (ctx, mut query_completed_token) = match namespace {
+            Some(namespace) => {
+                let db = match self
+                    .server
+                    .db(namespace, span_ctx.child_span("get namespace"))
+                    .await
+                {
+                    Some(db) => db,
+                    None => return Ok(error_stream(0, format!("database not found: {namespace}"))),
+                };
+                let ctx = db.new_query_context_with_filter(span_ctx, filter);
+                let token = db.record_query(&ctx, "influxql", Box::new(query.to_string()));
+                (ctx, Some(token))
+            }
+            None => (
+                self.server
+                    .new_query_context_without_namespace(span_ctx, filter),
+                None,
+            ),
+        };
+        let plans = Planner::new(&ctx)
+            .influxql_statements(query)
+            .await
+            .map_err(Error::ParseQuery)?;
+
+        debug!(
+            ?namespace,
+            statements = plans.len(),
+            "planned InfluxQL query"
+        );
+
+        let done = futures::stream::once(async move {
+            if let Some(token) = query_completed_token.as_mut() {
+                token.set_success();
+            }
+            drop(permit);
+        })
+        .filter_map(|_| futures::future::ready(None::<StatementResult>));
+
+        Ok(futures::stream::iter(plans.into_iter().enumerate())
+            .flat_map(move |(statement_id, plan)| {
+                statement_results(
+                    ctx.child_ctx("influxql statement"),
+                    statement_id,
+                    plan,
+                    epoch,
+                    chunk_size,
+                )
+            })
+            .chain(done)
+            .boxed())
+    }
+}
+
+/// Execute the `plan` of the statement identified by `statement_id`.
+///
+/// If `chunk_size` is set, a partial [`StatementResult`] is returned for
+/// every `chunk_size` rows. Should the statement fail, the partial results
+/// that have already been returned are followed by an error result.
+fn statement_results(
+    ctx: IOxSessionContext,
+    statement_id: usize,
+    plan: Result<Arc<dyn ExecutionPlan>, DataFusionError>,
+    epoch: Option<Epoch>,
+    chunk_size: Option<usize>,
+) -> ResultStream {
+    let plan = match plan {
+        Ok(plan) => plan,
+        Err(e) => {
+            return futures::stream::once(async move { StatementResult::error(statement_id, e) })
+                .boxed()
+        }
+    };
+
+    futures::stream::once(async move {
+        let builder = match SeriesBuilder::try_new(&plan.schema(), epoch, chunk_size) {
+            Ok(builder) => builder,
+            Err(e) => return error_stream(statement_id, e),
+        };
+        let batches = match ctx.execute_stream(plan).await {
+            Ok(batches) => batches,
+            Err(e) => return error_stream(statement_id, e),
+        };
+
+        futures::stream::unfold(Some((batches, builder)), move |state| async move {
+            let (mut batches, mut builder) = state?;
+            loop {
+                if let Some(series) = builder.pop_chunk() {
+                    let result = StatementResult::partial(statement_id, series);
+                    return Some((result, Some((batches, builder))));
+                }
+
+                match batches.next().await {
+                    Some(Ok(batch)) => {
+                        if let Err(e) = builder.push_batch(&batch) {
+                            return Some((StatementResult::error(statement_id, e), None));
+                        }
+                    }
+                    Some(Err(e)) => return Some((StatementResult::error(statement_id, e), None)),
+                    None => {
+                        return Some((StatementResult::new(statement_id, builder.finish()), None))
+                    }
+                }
+            }
+        })
+        .boxed()
+    })
+    .flatten()
+    .boxed()
+}
+
+fn error_stream(statement_id: usize, error: impl ToString) -> ResultStream {
+    let result = StatementResult::error(statement_id, error);
+    futures::stream::once(async move { result }).boxed()
+}
+
+/// Encode the query `results` in the response body.
+///
+/// A chunked response is streamed, with a JSON object per line for each
+/// result, while other responses are encoded once all results are available.
+async fn encode_response(results: ResultStream, format: Format, chunked: bool) -> Response<Body> {
+    let body = if chunked {
+        let mut first = true;
+        Body::wrap_stream(results.map(move |result| {
+            let chunk = match format {
+                Format::Json => {
+                    let mut chunk = serde_json::to_vec(&QueryResponse { results: &[result] })
+                        .expect("response serialization is infallible");
+                    chunk.push(b'\n');
+                    chunk
+                }
+                Format::Csv => {
+                    let mut chunk = String::new();
+                    if !std::mem::take(&mut first) {
+                        chunk.push('\n');
+                    }
+                    write_csv(&mut chunk, &result);
+                    chunk.into_bytes()
+                }
+            };
+            Ok::<_, Infallible>(Bytes::from(chunk))
+        }))
+    } else {
+        let results: Vec<_> = results.collect().await;
+        match format {
+            Format::Json => Body::from(
+                serde_json::to_vec(&QueryResponse { results: &results })
+                    .expect("response serialization is infallible"),
+            ),
+            Format::Csv => {
+                let mut body = String::new();
+                for (i, result) in results.iter().enumerate() {
+                    // Statements are separated by an empty line
+                    if i > 0 {
+                        body.push('\n');
+                    }
+                    write_csv(&mut body, result);
+                }
+                Body::from(body)
+            }
+        }
+    };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, format.content_type())
+        .body(body)
+        .unwrap()
+}
+
+/// Returns the token of a `Token` authorization header, if any.
+fn authorization_token(req: &Request<Body>) -> Option<Vec<u8>> {
+    req.extensions()
+        .get::<AuthorizationHeaderExtension>()
+        .and_then(|v| v.as_ref())
+        .and_then(|v| v.as_bytes().strip_prefix(b"Token "))
+        .map(|token| token.to_vec())
+}
+
+async fn read_body(mut body: Body) -> Result<Bytes, Error> {
+    let mut buf = BytesMut::new();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(Error::ClientHangup)?;
+        // limit max size of in-memory payload
+        if (buf.len() + chunk.len()) > MAX_REQUEST_BYTES {
+            return Err(Error::RequestSizeExceeded(MAX_REQUEST_BYTES));
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    Ok(buf.freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use service_common::test_util::TestDatabaseStore;
+
+    #[derive(Debug)]
+    struct MockAuthorizer {}
+
+    #[async_trait]
+    impl Authorizer for MockAuthorizer {
+        async fn permissions(
+            &self,
+            token: Option<&[u8]>,
+            perms: &[Permission],
+        ) -> Result<Vec<Permission>, authz::Error> {
+            match token {
+                Some(b"GOOD") => Ok(perms.to_vec()),
+                Some(b"BAD") => Ok(vec![]),
+                Some(b"READ") => Ok(perms
+                    .iter()
+                    .filter(|p| matches!(p, Permission::ResourceAction(_, Action::Read)))
+                    .cloned()
+                    .collect()),
+                Some(_) => Err(authz::Error::verification("test", "test error")),
+                None => Err(authz::Error::NoToken),
+            }
+        }
+    }
+
+    async fn delegate(authz: Option<Arc<dyn Authorizer>>) -> HttpDelegate<TestDatabaseStore> {
+        let store = Arc::new(TestDatabaseStore::default());
+        store.db_or_create("bananas").await;
+        HttpDelegate::new(store, authz)
+    }
+
+    async fn body_string(response: Response<Body>) -> String {
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    async fn get(delegate: &HttpDelegate<TestDatabaseStore>, uri: &str) -> Response<Body> {
+        let req = Request::builder()
+            .uri(format!("https://querier{uri}"))
+            .method("GET")
+            .body(Body::empty())
+            .unwrap();
+        delegate.route(req).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_not_found() {
+        let delegate = delegate(None).await;
+        let req = Request::builder()
+            .uri("https://querier/api/v2/write")
+            .method("POST")
+            .body(Body::empty())
+            .unwrap();
+        let err = delegate.route(req).await.unwrap_err();
+        assert!(matches!(err, Error::NoHandler));
+    }
+
+    #[tokio::test]
+    async fn test_v1_meta_queries_without_database() {
+        let no_authz = delegate(None).await;
+
+        let response = get(&no_authz, "/query?q=SHOW+DATABASES").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = get(&no_authz, "/query?q=SHOW+RETENTION+POLICIES+ON+bananas").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Other statements still require a database.
+        let response = get(
+            &no_authz,
+            "/query?q=SHOW+DATABASES%3B+SHOW+RETENTION+POLICIES",
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_string(response).await,
+            r#"{"error":"database name required"}"#
+        );
+
+        // A token is required if authorization is configured.
+        let authz = delegate(Some(Arc::new(MockAuthorizer {}))).await;
+        let response = get(&authz, "/query?q=SHOW+DATABASES").await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = get(&authz, "/query?q=SHOW+DATABASES&p=BAD").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_v1_bad_requests() {
+        let delegate = delegate(None).await;
+
+        let response = get(&delegate, "/query?q=SHOW+MEASUREMENTS").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_string(response).await,
+            r#"{"error":"database name required"}"#
+        );
+
+        let response = get(&delegate, "/query?db=bananas").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_string(response).await,
+            r#"{"error":"missing required parameter \"q\""}"#
+        );
+
+        let response = get(&delegate, "/query?db=bananas&q=SHOW+MEASUREMENTS&epoch=d").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_string(response).await,
+            r#"{"error":"invalid epoch: d"}"#
+        );
+
+        let response = get(&delegate, "/query?db=bananas&q=SELEC+1").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(body_string(response)
+            .await
+            .starts_with(r#"{"error":"error parsing query: "#));
+    }
+
+    #[tokio::test]
+    async fn test_v1_database_not_found() {
+        let delegate = delegate(None).await;
+
+        let response = get(&delegate, "/query?db=platanos&q=SHOW+MEASUREMENTS").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_string(response).await,
+            r#"{"results":[{"statement_id":0,"error":"database not found: platanos"}]}"#
+        );
+
+        // A retention policy other than the default is part of the namespace
+        let response = get(&delegate, "/query?db=bananas&rp=weekly&q=SHOW+MEASUREMENTS").await;
+        assert_eq!(
+            body_string(response).await,
+            r#"{"results":[{"statement_id":0,"error":"database not found: bananas/weekly"}]}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_v1_form_body() {
+        let delegate = delegate(None).await;
+
+        let req = Request::builder()
+            .uri("https://querier/query?db=bananas")
+            .method("POST")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from("db=platanos&q=SHOW+MEASUREMENTS"))
+            .unwrap();
+        let response = delegate.route(req).await.unwrap();
+        assert_eq!(
+            body_string(response).await,
+            r#"{"results":[{"statement_id":0,"error":"database not found: platanos"}]}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_v1_authz() {
+        let delegate = delegate(Some(Arc::new(MockAuthorizer {}))).await;
+
+        async fn status(
+            delegate: &HttpDelegate<TestDatabaseStore>,
+            query: &str,
+            authorization: Option<&'static str>,
+        ) -> StatusCode {
+            let query = if query.contains("q=") {
+                query.to_string()
+            } else {
+                format!("&q=SHOW+MEASUREMENTS{query}")
+            };
+            let mut req = Request::builder()
+                .uri(format!("https://querier/query?db=platanos{query}"))
+                .method("GET")
+                .body(Body::empty())
+                .unwrap();
+            req.extensions_mut()
+                .insert(AuthorizationHeaderExtension::new(
+                    authorization.map(hyper::http::HeaderValue::from_static),
+                ));
+            delegate.route(req).await.unwrap().status()
+        }
+
+        assert_eq!(status(&delegate, "", None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status(&delegate, "", Some("Token GOOD")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(&delegate, "", Some("Token BAD")).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(&delegate, "", Some("Token UGLY")).await,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        // The password of the 1.x API is used as a token
+        assert_eq!(status(&delegate, "&p=GOOD", None).await, StatusCode::OK);
+        assert_eq!(
+            status(&delegate, "&p=BAD", None).await,
+            StatusCode::FORBIDDEN
+        );
+
+        // Deleting data requires write permission
+        assert_eq!(
+            status(&delegate, "", Some("Token READ")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(&delegate, "&q=DELETE+FROM+cpu", Some("Token READ")).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(&delegate, "&q=DROP+MEASUREMENT+cpu", Some("Token READ")).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(&delegate, "&q=DELETE+FROM+cpu", Some("Token GOOD")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_v2() {
+        let delegate = delegate(None).await;
+
+        async fn query(
+            delegate: &HttpDelegate<TestDatabaseStore>,
+            body: &'static str,
+        ) -> Result<Response<Body>, Error> {
+            let req = Request::builder()
+                .uri("https://querier/api/v2/query?bucket=platanos")
+                .method("POST")
+                .body(Body::from(body))
+                .unwrap();
+            delegate.route(req).await
+        }
+
+        let response = query(
+            &delegate,
+            r#"{"query":"SHOW MEASUREMENTS","type":"influxql"}"#,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            body_string(response).await,
+            r#"{"results":[{"statement_id":0,"error":"database not found: platanos"}]}"#
+        );
+
+        let err = query(&delegate, r#"{"query":"from(bucket: \"platanos\")"}"#)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::UnsupportedQueryType(t) if t == "flux"));
+
+        let err = query(&delegate, "SHOW MEASUREMENTS").await.unwrap_err();
+        assert!(matches!(err, Error::InvalidBody(_)));
+    }
+}
@@ -0,0 +1,663 @@
+//! Encoding of InfluxQL query results in the shapes used by the [InfluxDB 1.x
+//! query API].
+//!
+//! [InfluxDB 1.x query API]:
+//!     https://docs.influxdata.com/influxdb/v1.8/tools/api/#query-http-endpoint
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
+
+use arrow::{
+    array::{
+        Array, ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray,
+        TimestampNanosecondArray, UInt64Array,
+    },
+    compute::cast,
+    datatypes::{DataType, Schema, TimeUnit},
+    error::ArrowError,
+    record_batch::RecordBatch,
+    util::display::array_value_to_string,
+};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
+use schema::INFLUXQL_METADATA_KEY;
+use serde::Serialize;
+use serde_json::Value;
+
+/// The precision of the timestamps in a response, as selected by the `epoch`
+/// parameter.
+///
+/// Without an `epoch`, timestamps are rendered as RFC3339 strings in JSON
+/// responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Epoch {
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+    Seconds,
+    Minutes,
+    Hours,
+}
+
+impl Epoch {
+    /// Parse the value of an `epoch` parameter.
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "ns" => Self::Nanoseconds,
+            "u" | "µ" => Self::Microseconds,
+            "ms" => Self::Milliseconds,
+            "s" => Self::Seconds,
+            "m" => Self::Minutes,
+            "h" => Self::Hours,
+            _ => return None,
+        })
+    }
+
+    /// Convert the nanosecond timestamp `ts` to this precision.
+    fn convert(&self, ts: i64) -> i64 {
+        let divisor = match self {
+            Self::Nanoseconds => 1,
+            Self::Microseconds => 1_000,
+            Self::Milliseconds => 1_000_000,
+            Self::Seconds => 1_000_000_000,
+            Self::Minutes => 60 * 1_000_000_000,
+            Self::Hours => 60 * 60 * 1_000_000_000,
+        };
+        ts / divisor
+    }
+}
+
+/// A series of rows that share a measurement and the values of the tags in
+/// the group key.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Series {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub(crate) name: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) tags: BTreeMap<String, String>,
+    pub(crate) columns: Vec<String>,
+    pub(crate) values: Vec<Vec<Value>>,
+    /// Set if more rows of this series follow in the next chunk.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) partial: bool,
+}
+
+/// The result, or a chunk of the result, of a single statement.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct StatementResult {
+    pub(crate) statement_id: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) series: Vec<Series>,
+    /// Set if more chunks of the results of this statement follow.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) partial: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+impl StatementResult {
+    pub(crate) fn new(statement_id: usize, series: Vec<Series>) -> Self {
+        Self {
+            statement_id,
+            series,
+            partial: false,
+            error: None,
+        }
+    }
+
+    pub(crate) fn partial(statement_id: usize, series: Vec<Series>) -> Self {
+        Self {
+            partial: true,
+            ..Self::new(statement_id, series)
+        }
+    }
+
+    pub(crate) fn error(statement_id: usize, error: impl ToString) -> Self {
+        Self {
+            error: Some(error.to_string()),
+            ..Self::new(statement_id, vec![])
+        }
+    }
+}
+
+/// The body of a JSON response.
+#[derive(Debug, Serialize)]
+pub(crate) struct QueryResponse<'a> {
+    pub(crate) results: &'a [StatementResult],
+}
+
+/// The body of a JSON response to a request that failed as a whole.
+#[derive(Debug, Serialize)]
+pub(crate) struct ErrorResponse<'a> {
+    pub(crate) error: &'a str,
+}
+
+/// Groups the rows of the record batches of a statement into [`Series`],
+/// using the [`InfluxQlMetadata`] of their schema.
+#[derive(Debug)]
+pub(crate) struct SeriesBuilder {
+    /// The index of the measurement column, if the results have one.
+    measurement_idx: Option<usize>,
+    /// The tag keys of the group key and the indexes of their columns.
+    tag_keys: Vec<(String, usize)>,
+    /// The indexes of the columns that are rendered as values.
+    value_idxs: Vec<usize>,
+    /// The names of the columns that are rendered as values.
+    columns: Vec<String>,
+    epoch: Option<Epoch>,
+    /// The number of rows after which a chunk of series is ready, if the
+    /// results are chunked.
+    chunk_size: Option<usize>,
+
+    completed: Vec<Series>,
+    current: Option<Series>,
+    /// The number of rows in `completed` and `current`.
+    rows: usize,
+    ready: VecDeque<Vec<Series>>,
+}
+
+impl SeriesBuilder {
+    pub(crate) fn try_new(
+        schema: &Schema,
+        epoch: Option<Epoch>,
+        chunk_size: Option<usize>,
+    ) -> Result<Self, ArrowError> {
+        let metadata = schema
+            .metadata()
+            .get(INFLUXQL_METADATA_KEY)
+            .map(|md| serde_json::from_str::<InfluxQlMetadata>(md))
+            .transpose()
+            .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+
+        let (measurement_idx, tag_keys, hidden) = match &metadata {
+            Some(md) => (
+                Some(md.measurement_column_index as usize),
+                md.tag_key_columns
+                    .iter()
+                    .map(|tk| (tk.tag_key.clone(), tk.column_index as usize))
+                    .collect(),
+                // Tag key columns that only appear in the `GROUP BY` clause are
+                // only rendered as tags of the series.
+                md.tag_key_columns
+                    .iter()
+                    .filter(|tk| !tk.is_projected)
+                    .map(|tk| tk.column_index as usize)
+                    .collect(),
+            ),
+            None => (None, vec![], vec![]),
+        };
+
+        let value_idxs: Vec<_> = (0..schema.fields().len())
+            .filter(|i| Some(*i) != measurement_idx && !hidden.contains(i))
+            .collect();
+        let columns = value_idxs
+            .iter()
+            .map(|i| schema.field(*i).name().clone())
+            .collect();
+
+        Ok(Self {
+            measurement_idx,
+            tag_keys,
+            value_idxs,
+            columns,
+            epoch,
+            chunk_size,
+            completed: vec![],
+            current: None,
+            rows: 0,
+            ready: VecDeque::new(),
+        })
+    }
+
+    /// Add the rows of `batch` to the series.
+    pub(crate) fn push_batch(&mut self, batch: &RecordBatch) -> Result<(), ArrowError> {
+        let measurement = self
+            .measurement_idx
+            .map(|i| string_column(batch.column(i)))
+            .transpose()?;
+        let tags = self
+            .tag_keys
+            .iter()
+            .map(|(_, i)| string_column(batch.column(*i)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let values = self
+            .value_idxs
+            .iter()
+            .map(|i| match batch.column(*i).data_type() {
+                DataType::Dictionary(_, _) => string_column(batch.column(*i)),
+                _ => Ok(Arc::clone(batch.column(*i))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for row in 0..batch.num_rows() {
+            let name = measurement
+                .as_ref()
+                .map_or("", |m| string_value(m, row).unwrap_or_default());
+
+            let same_series = self.current.as_ref().map_or(false, |s| {
+                s.name == name
+                    && self.tag_keys.iter().zip(&tags).all(|((k, _), v)| {
+                        s.tags.get(k).map(String::as_str) == string_value(v, row).or(Some(""))
+                    })
+            });
+
+            if !same_series {
+                let series_tags = self
+                    .tag_keys
+                    .iter()
+                    .zip(&tags)
+                    .map(|((k, _), v)| {
+                        (
+                            k.clone(),
+                            string_value(v, row).unwrap_or_default().to_string(),
+                        )
+                    })
+                    .collect();
+                self.start_series(name.to_string(), series_tags);
+            }
+
+            let row = values
+                .iter()
+                .map(|array| json_value(array, row, self.epoch))
+                .collect::<Result<Vec<_>, _>>()?;
+            self.current
+                .as_mut()
+                .expect("series started")
+                .values
+                .push(row);
+            self.rows += 1;
+
+            if self.chunk_size.map_or(false, |n| self.rows >= n) {
+                self.flush_chunk();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the next chunk of series, if the results are chunked and
+    /// enough rows have been added.
+    pub(crate) fn pop_chunk(&mut self) -> Option<Vec<Series>> {
+        self.ready.pop_front()
+    }
+
+    /// Returns the remaining series.
+    pub(crate) fn finish(mut self) -> Vec<Series> {
+        let mut series: Vec<_> = self.ready.drain(..).flatten().collect();
+        series.append(&mut self.completed);
+        series.extend(self.current.filter(|s| !s.values.is_empty()));
+        series
+    }
+
+    fn start_series(&mut self, name: String, tags: BTreeMap<String, String>) {
+        // A series may be empty if its rows were all flushed in a chunk.
+        if let Some(series) = self.current.take().filter(|s| !s.values.is_empty()) {
+            self.completed.push(series);
+        }
+        self.current = Some(Series {
+            name,
+            tags,
+            columns: self.columns.clone(),
+            values: vec![],
+            partial: false,
+        });
+    }
+
+    fn flush_chunk(&mut self) {
+        let mut chunk = std::mem::take(&mut self.completed);
+        if let Some(current) = &mut self.current {
+            chunk.push(Series {
+                name: current.name.clone(),
+                tags: current.tags.clone(),
+                columns: current.columns.clone(),
+                values: std::mem::take(&mut current.values),
+                partial: true,
+            });
+        }
+        self.rows = 0;
+        self.ready.push_back(chunk);
+    }
+}
+
+/// Cast a tag or measurement column to a [`StringArray`].
+fn string_column(array: &ArrayRef) -> Result<ArrayRef, ArrowError> {
+    match array.data_type() {
+        DataType::Utf8 => Ok(Arc::clone(array)),
+        _ => cast(array, &DataType::Utf8),
+    }
+}
+
+/// Returns the value of a column returned by [`string_column`].
+fn string_value(array: &ArrayRef, row: usize) -> Option<&str> {
+    let array = array
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .expect("string column");
+    array.is_valid(row).then(|| array.value(row))
+}
+
+/// Render the value of `array` at `row` as JSON.
+fn json_value(array: &ArrayRef, row: usize, epoch: Option<Epoch>) -> Result<Value, ArrowError> {
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+
+    macro_rules! value {
+        ($t:ty) => {
+            array.as_any().downcast_ref::<$t>().unwrap().value(row)
+        };
+    }
+
+    Ok(match array.data_type() {
+        DataType::Float64 => {
+            serde_json::Number::from_f64(value!(Float64Array)).map_or(Value::Null, Value::Number)
+        }
+        DataType::Int64 => Value::from(value!(Int64Array)),
+        DataType::UInt64 => Value::from(value!(UInt64Array)),
+        DataType::Boolean => Value::from(value!(BooleanArray)),
+        DataType::Utf8 => Value::from(value!(StringArray)),
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            let ts = value!(TimestampNanosecondArray);
+            match epoch {
+                Some(epoch) => Value::from(epoch.convert(ts)),
+                None => Value::from(
+                    Utc.timestamp_nanos(ts)
+                        .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                ),
+            }
+        }
+        _ => Value::from(array_value_to_string(array, row)?),
+    })
+}
+
+/// Write `result` to `out` in the CSV format of the 1.x query API.
+///
+/// Each series is written as rows prefixed by the name and the tags of the
+/// series, and a header is written whenever the columns change. A statement
+/// that failed is written as an `error` column.
+pub(crate) fn write_csv(out: &mut String, result: &StatementResult) {
+    if let Some(error) = &result.error {
+        out.push_str("error\n");
+        push_csv_field(out, error);
+        out.push('\n');
+        return;
+    }
+
+    let mut columns: Option<&[String]> = None;
+    for series in &result.series {
+        if columns != Some(series.columns.as_slice()) {
+            out.push_str("name,tags");
+            for column in &series.columns {
+                out.push(',');
+                push_csv_field(out, column);
+            }
+            out.push('\n');
+            columns = Some(&series.columns);
+        }
+
+        let tags = series
+            .tags
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        for row in &series.values {
+            push_csv_field(out, &series.name);
+            out.push(',');
+            push_csv_field(out, &tags);
+            for value in row {
+                out.push(',');
+                match value {
+                    Value::Null => {}
+                    Value::String(s) => push_csv_field(out, s),
+                    v => out.push_str(&v.to_string()),
+                }
+            }
+            out.push('\n');
+        }
+    }
+}
+
+/// Append `field` to `out`, quoting it if necessary.
+fn push_csv_field(out: &mut String, field: &str) {
+    if field.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&field.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(field);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::{
+        array::DictionaryArray,
+        datatypes::{Field, Int32Type},
+    };
+    use generated_types::influxdata::iox::querier::v1::influx_ql_metadata::TagKeyColumn;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    /// Returns the batches of a `SELECT usage FROM cpu, mem GROUP BY host`
+    /// query, where `region` is projected too.
+    fn batches() -> Vec<RecordBatch> {
+        let metadata = InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![
+                TagKeyColumn {
+                    tag_key: "host".to_string(),
+                    column_index: 2,
+                    is_projected: false,
+                },
+                TagKeyColumn {
+                    tag_key: "region".to_string(),
+                    column_index: 3,
+                    is_projected: true,
+                },
+            ],
+            ..Default::default()
+        };
+        let schema = Arc::new(Schema::new_with_metadata(
+            vec![
+                Field::new(
+                    "iox::measurement",
+                    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                    false,
+                ),
+                Field::new(
+                    "time",
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    false,
+                ),
+                Field::new("host", DataType::Utf8, true),
+                Field::new("region", DataType::Utf8, true),
+                Field::new("usage", DataType::Float64, true),
+            ],
+            HashMap::from([(
+                INFLUXQL_METADATA_KEY.to_owned(),
+                serde_json::to_string(&metadata).unwrap(),
+            )]),
+        ));
+
+        let batch = |measurements: Vec<&str>,
+                     times: Vec<i64>,
+                     hosts: Vec<Option<&str>>,
+                     regions: Vec<Option<&str>>,
+                     usage: Vec<Option<f64>>| {
+            RecordBatch::try_new(
+                Arc::clone(&schema),
+                vec![
+                    Arc::new(
+                        measurements
+                            .into_iter()
+                            .collect::<DictionaryArray<Int32Type>>(),
+                    ),
+                    Arc::new(TimestampNanosecondArray::from(times)),
+                    Arc::new(StringArray::from(hosts)),
+                    Arc::new(StringArray::from(regions)),
+                    Arc::new(Float64Array::from(usage)),
+                ],
+            )
+            .unwrap()
+        };
+
+        vec![
+            batch(
+                vec!["cpu", "cpu", "cpu"],
+                vec![1_000_000_000, 2_000_000_000, 1_000_000_000],
+                vec![Some("a"), Some("a"), Some("b")],
+                vec![Some("west"), None, Some("east")],
+                vec![Some(1.5), None, Some(3.0)],
+            ),
+            // The series of host "b" continues in the next batch
+            batch(
+                vec!["cpu", "mem"],
+                vec![2_000_000_000, 1_000_000_000],
+                vec![Some("b"), None],
+                vec![Some("east"), None],
+                vec![Some(f64::NAN), Some(42.0)],
+            ),
+        ]
+    }
+
+    fn build(epoch: Option<Epoch>, chunk_size: Option<usize>) -> (Vec<Vec<Series>>, Vec<Series>) {
+        let batches = batches();
+        let mut builder = SeriesBuilder::try_new(&batches[0].schema(), epoch, chunk_size).unwrap();
+        let mut chunks = vec![];
+        for batch in &batches {
+            builder.push_batch(batch).unwrap();
+            while let Some(chunk) = builder.pop_chunk() {
+                chunks.push(chunk);
+            }
+        }
+        (chunks, builder.finish())
+    }
+
+    #[test]
+    fn test_series() {
+        let (chunks, series) = build(None, None);
+        assert!(chunks.is_empty());
+
+        let got = serde_json::to_value(StatementResult::new(0, series)).unwrap();
+        assert_eq!(
+            got,
+            json!({
+                "statement_id": 0,
+                "series": [
+                    {
+                        "name": "cpu",
+                        "tags": {"host": "a"},
+                        "columns": ["time", "region", "usage"],
+                        "values": [
+                            ["1970-01-01T00:00:01Z", "west", 1.5],
+                            ["1970-01-01T00:00:02Z", null, null],
+                        ],
+                    },
+                    {
+                        "name": "cpu",
+                        "tags": {"host": "b"},
+                        "columns": ["time", "region", "usage"],
+                        "values": [
+                            ["1970-01-01T00:00:01Z", "east", 3.0],
+                            ["1970-01-01T00:00:02Z", "east", null],
+                        ],
+                    },
+                    {
+                        "name": "mem",
+                        "tags": {"host": ""},
+                        "columns": ["time", "region", "usage"],
+                        "values": [
+                            ["1970-01-01T00:00:01Z", null, 42.0],
+                        ],
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_series_epoch() {
+        let (_, series) = build(Some(Epoch::Milliseconds), None);
+        let times: Vec<_> = series
+            .iter()
+            .flat_map(|s| s.values.iter().map(|row| row[0].clone()))
+            .collect();
+        assert_eq!(
+            times,
+            vec![
+                json!(1000),
+                json!(2000),
+                json!(1000),
+                json!(2000),
+                json!(1000)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_series_chunked() {
+        let (chunks, rest) = build(Some(Epoch::Seconds), Some(2));
+
+        let summary = |series: &[Series]| {
+            series
+                .iter()
+                .map(|s| (s.tags["host"].clone(), s.values.len(), s.partial))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            chunks.iter().map(|c| summary(c)).collect::<Vec<_>>(),
+            vec![
+                vec![("a".to_string(), 2, true)],
+                vec![("b".to_string(), 2, true)],
+            ]
+        );
+        assert_eq!(summary(&rest), vec![("".to_string(), 1, false)]);
+    }
+
+    #[test]
+    fn test_error_result() {
+        let got =
+            serde_json::to_value(StatementResult::error(1, "database not found: foo")).unwrap();
+        assert_eq!(
+            got,
+            json!({"statement_id": 1, "error": "database not found: foo"})
+        );
+
+        let got = serde_json::to_value(StatementResult::new(2, vec![])).unwrap();
+        assert_eq!(got, json!({"statement_id": 2}));
+    }
+
+    #[test]
+    fn test_csv() {
+        let (_, series) = build(Some(Epoch::Nanoseconds), None);
+
+        let mut out = String::new();
+        write_csv(&mut out, &StatementResult::new(0, series));
+        write_csv(&mut out, &StatementResult::error(1, "bad, \"query\""));
+
+        assert_eq!(
+            out,
+            "name,tags,time,region,usage\n\
+             cpu,host=a,1000000000,west,1.5\n\
+             cpu,host=a,2000000000,,\n\
+             cpu,host=b,1000000000,east,3.0\n\
+             cpu,host=b,2000000000,east,\n\
+             mem,host=,1000000000,,42.0\n\
+             error\n\
+             \"bad, \"\"query\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn test_epoch() {
+        assert_eq!(Epoch::parse("ms"), Some(Epoch::Milliseconds));
+        assert_eq!(Epoch::parse("µ"), Some(Epoch::Microseconds));
+        assert_eq!(Epoch::parse("d"), None);
+        assert_eq!(Epoch::Hours.convert(2 * 3_600_000_000_000 + 1), 2);
+    }
+}
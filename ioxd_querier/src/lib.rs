@@ -8,7 +8,7 @@ use iox_query::exec::{Executor, ExecutorType};
 use iox_time::TimeProvider;
 use ioxd_common::{
     add_service,
-    http::error::{HttpApiError, HttpApiErrorCode, HttpApiErrorSource},
+    http::error::HttpApiErrorSource,
     rpc::RpcBuilderInput,
     serve_builder,
     server_type::{CommonServerState, RpcError, ServerType},
@@ -20,20 +20,19 @@ use querier::{
     create_ingester_connections, QuerierCatalogCache, QuerierDatabase, QuerierHandler,
     QuerierHandlerImpl, QuerierServer,
 };
-use std::{
-    fmt::{Debug, Display},
-    sync::Arc,
-};
+use std::{fmt::Debug, sync::Arc};
 use thiserror::Error;
 use tokio::runtime::Handle;
 use tokio_util::sync::CancellationToken;
 use trace::TraceCollector;
 
+mod http;
 mod rpc;
 
 pub struct QuerierServerType<C: QuerierHandler> {
     database: Arc<QuerierDatabase>,
     server: QuerierServer<C>,
+    http: http::HttpDelegate<QuerierDatabase>,
     trace_collector: Option<Arc<dyn TraceCollector>>,
     authz: Option<Arc<dyn Authorizer>>,
 }
@@ -51,9 +50,11 @@ impl<C: QuerierHandler> QuerierServerType<C> {
         common_state: &CommonServerState,
         authz: Option<Arc<dyn Authorizer>>,
     ) -> Self {
+        let http = http::HttpDelegate::new(Arc::clone(&database), authz.as_ref().map(Arc::clone));
         Self {
             server,
             database,
+            http,
             trace_collector: common_state.trace_collector(),
             authz,
         }
@@ -77,12 +78,15 @@ impl<C: QuerierHandler + std::fmt::Debug + 'static> ServerType for QuerierServer
         self.trace_collector.as_ref().map(Arc::clone)
     }
 
-    /// Just return "not found".
+    /// Serve InfluxQL queries through the InfluxDB 1.x and 2.x query APIs.
     async fn route_http_request(
         &self,
-        _req: Request<Body>,
+        req: Request<Body>,
     ) -> Result<Response<Body>, Box<dyn HttpApiErrorSource>> {
-        Err(Box::new(IoxHttpError::NotFound))
+        self.http
+            .route(req)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn HttpApiErrorSource>)
     }
 
     /// Configure the gRPC services.
@@ -122,34 +126,6 @@ impl<C: QuerierHandler + std::fmt::Debug + 'static> ServerType for QuerierServer
     }
 }
 
-/// Simple error struct, we're not really providing an HTTP interface for the compactor.
-#[derive(Debug)]
-pub enum IoxHttpError {
-    NotFound,
-}
-
-impl IoxHttpError {
-    fn status_code(&self) -> HttpApiErrorCode {
-        match self {
-            IoxHttpError::NotFound => HttpApiErrorCode::NotFound,
-        }
-    }
-}
-
-impl Display for IoxHttpError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
-    }
-}
-
-impl std::error::Error for IoxHttpError {}
-
-impl HttpApiErrorSource for IoxHttpError {
-    fn to_http_api_error(&self) -> HttpApiError {
-        HttpApiError::new(self.status_code(), self.to_string())
-    }
-}
-
 /// Arguments required to create a [`ServerType`] for the querier.
 #[derive(Debug)]
 pub struct QuerierServerTypeArgs<'a> {
@@ -38,6 +38,9 @@ fn namespace_to_proto(namespace: Namespace) -> proto::Namespace {
         retention_period_ns: namespace.retention_period_ns,
         max_tables: namespace.max_tables,
         max_columns_per_table: namespace.max_columns_per_table,
+        partition_template: namespace.partition_template.map(Into::into),
+        max_lines_per_second: namespace.max_lines_per_second,
+        max_bytes_per_second: namespace.max_bytes_per_second,
     }
 }
 
@@ -192,6 +195,9 @@ mod tests {
                         retention_period_ns: TEST_RETENTION_PERIOD_NS,
                         max_tables: TEST_MAX_TABLES,
                         max_columns_per_table: TEST_MAX_COLUMNS_PER_TABLE,
+                        partition_template: None,
+                        max_lines_per_second: None,
+                        max_bytes_per_second: None,
                     },
                     proto::Namespace {
                         id: 2,
@@ -199,6 +205,9 @@ mod tests {
                         retention_period_ns: TEST_RETENTION_PERIOD_NS,
                         max_tables: TEST_MAX_TABLES,
                         max_columns_per_table: TEST_MAX_COLUMNS_PER_TABLE,
+                        partition_template: None,
+                        max_lines_per_second: None,
+                        max_bytes_per_second: None,
                     },
                 ]
             }
@@ -12,6 +12,7 @@ use ioxd_common::{
         generated_types::influxdata::iox::{
             catalog::v1::catalog_service_server, namespace::v1::namespace_service_server,
             object_store::v1::object_store_service_server, schema::v1::schema_service_server,
+            table::v1::table_service_server,
         },
         tonic::transport::Endpoint,
     },
@@ -24,19 +25,21 @@ use metric::Registry;
 use mutable_batch::MutableBatch;
 use object_store::DynObjectStore;
 use router::{
+    delete_handler::CatalogDeleteHandler,
     dml_handlers::{
         lazy_connector::LazyConnector, DmlHandler, DmlHandlerChainExt, FanOutAdaptor,
-        InstrumentationDecorator, Partitioner, RetentionValidator, RpcWrite, SchemaValidator,
+        InstrumentationDecorator, Partitioner, RateLimiter, RetentionValidator, RpcWrite,
+        SchemaValidator,
     },
     namespace_cache::{
         metrics::InstrumentedCache, MemoryNamespaceCache, NamespaceCache, ReadThroughCache,
-        ShardedCache,
+        ShardedCache, TtlCache,
     },
     namespace_resolver::{
         MissingNamespaceAction, NamespaceAutocreation, NamespaceResolver, NamespaceSchemaResolver,
     },
     server::{
-        grpc::RpcWriteGrpcDelegate,
+        grpc::{flight::FlightIngestService, RpcWriteGrpcDelegate},
         http::{
             write::{
                 multi_tenant::MultiTenantRequestParser, single_tenant::SingleTenantRequestParser,
@@ -69,14 +72,14 @@ pub enum Error {
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
-pub struct RpcWriteRouterServerType<D, N> {
-    server: RpcWriteRouterServer<D, N>,
+pub struct RpcWriteRouterServerType<D, N, C> {
+    server: RpcWriteRouterServer<D, N, C>,
     shutdown: CancellationToken,
     trace_collector: Option<Arc<dyn TraceCollector>>,
 }
 
-impl<D, N> RpcWriteRouterServerType<D, N> {
-    pub fn new(server: RpcWriteRouterServer<D, N>, common_state: &CommonServerState) -> Self {
+impl<D, N, C> RpcWriteRouterServerType<D, N, C> {
+    pub fn new(server: RpcWriteRouterServer<D, N, C>, common_state: &CommonServerState) -> Self {
         Self {
             server,
             shutdown: CancellationToken::new(),
@@ -85,17 +88,18 @@ impl<D, N> RpcWriteRouterServerType<D, N> {
     }
 }
 
-impl<D, N> std::fmt::Debug for RpcWriteRouterServerType<D, N> {
+impl<D, N, C> std::fmt::Debug for RpcWriteRouterServerType<D, N, C> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "RpcWriteRouter")
     }
 }
 
 #[async_trait]
-impl<D, N> ServerType for RpcWriteRouterServerType<D, N>
+impl<D, N, C> ServerType for RpcWriteRouterServerType<D, N, C>
 where
     D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()> + 'static,
     N: NamespaceResolver + 'static,
+    C: NamespaceCache + Clone + 'static,
 {
     fn name(&self) -> &str {
         "rpc_write_router"
@@ -126,7 +130,8 @@ where
             .map_err(|e| Box::new(e) as _)
     }
 
-    /// Registers the services exposed by the router [`RpcWriteGrpcDelegate`] delegate.
+    /// Registers the services exposed by the router [`RpcWriteGrpcDelegate`]
+    /// delegate, and the Flight bulk ingest service.
     ///
     /// [`RpcWriteGrpcDelegate`]: router::server::grpc::RpcWriteGrpcDelegate
     async fn server_grpc(self: Arc<Self>, builder_input: RpcBuilderInput) -> Result<(), RpcError> {
@@ -151,6 +156,11 @@ where
                 self.server.grpc().namespace_service()
             )
         );
+        add_service!(
+            builder,
+            table_service_server::TableServiceServer::new(self.server.grpc().table_service())
+        );
+        add_service!(builder, self.server.flight_service());
         serve_builder!(builder);
 
         Ok(())
@@ -182,7 +192,11 @@ impl std::error::Error for IoxHttpErrorAdaptor {}
 
 impl HttpApiErrorSource for IoxHttpErrorAdaptor {
     fn to_http_api_error(&self) -> HttpApiError {
-        HttpApiError::new(self.0.as_status_code(), self.to_string())
+        let err = HttpApiError::new(self.0.as_status_code(), self.to_string());
+        match self.0.retry_after() {
+            Some(retry_after) => err.with_retry_after(retry_after),
+            None => err,
+        }
     }
 }
 
@@ -195,19 +209,21 @@ pub async fn create_router2_server_type(
     authz: Option<Arc<dyn Authorizer>>,
     router_config: &Router2Config,
 ) -> Result<Arc<dyn ServerType>> {
-    let ingester_connections = router_config.ingester_addresses.iter().map(|addr| {
-        let addr = addr.to_string();
-        let endpoint = Endpoint::from_shared(hyper::body::Bytes::from(addr.clone()))
-            .expect("invalid ingester connection address");
-        (
-            LazyConnector::new(endpoint, router_config.rpc_write_timeout_seconds),
-            addr,
-        )
-    });
+    let ingester_connections = || {
+        router_config.ingester_addresses.iter().map(|addr| {
+            let addr = addr.to_string();
+            let endpoint = Endpoint::from_shared(hyper::body::Bytes::from(addr.clone()))
+                .expect("invalid ingester connection address");
+            (
+                LazyConnector::new(endpoint, router_config.rpc_write_timeout_seconds),
+                addr,
+            )
+        })
+    };
 
     // Initialise the DML handler that sends writes to the ingester using the RPC write path.
     let rpc_writer = RpcWrite::new(
-        ingester_connections,
+        ingester_connections(),
         router_config.rpc_write_replicas,
         &metrics,
     );
@@ -217,13 +233,18 @@ pub async fn create_router2_server_type(
     //
     // Initialise an instrumented namespace cache to be shared with the schema
     // validator, and namespace auto-creator that reports cache hit/miss/update
-    // metrics.
+    // metrics. Entries are reloaded from the catalog once they are older than
+    // the configured TTL, so that changes made through other routers are
+    // observed.
     let ns_cache = Arc::new(ReadThroughCache::new(
-        Arc::new(InstrumentedCache::new(
-            Arc::new(ShardedCache::new(
-                std::iter::repeat_with(|| Arc::new(MemoryNamespaceCache::default())).take(10),
+        Arc::new(TtlCache::new(
+            Arc::new(InstrumentedCache::new(
+                Arc::new(ShardedCache::new(
+                    std::iter::repeat_with(|| Arc::new(MemoryNamespaceCache::default())).take(10),
+                )),
+                &metrics,
             )),
-            &metrics,
+            router_config.namespace_cache_ttl_seconds,
         )),
         Arc::clone(&catalog),
     ));
@@ -240,6 +261,13 @@ pub async fn create_router2_server_type(
     let schema_validator =
         InstrumentationDecorator::new("schema_validator", &metrics, schema_validator);
 
+    // # Rate limiter
+    //
+    // Reject writes to namespaces that exceed their configured write rate
+    // limits before doing any further work.
+    let rate_limiter = RateLimiter::new(Arc::clone(&ns_cache), &metrics);
+    let rate_limiter = InstrumentationDecorator::new("rate_limiter", &metrics, rate_limiter);
+
     // # Retention validator
     //
     // Add a retention validator into handler stack to reject data outside the retention period
@@ -249,13 +277,17 @@ pub async fn create_router2_server_type(
 
     // # Write partitioner
     //
-    // Add a write partitioner into the handler stack that splits by the date
-    // portion of the write's timestamp.
-    let partitioner = Partitioner::new(PartitionTemplate {
-        parts: vec![TemplatePart::TimeFormat(
-            router_config.partition_key_pattern.clone(),
-        )],
-    });
+    // Add a write partitioner into the handler stack that splits writes by
+    // the partition template of the table or its namespace, defaulting to
+    // the date portion of the write's timestamp.
+    let partitioner = Partitioner::new(
+        Arc::clone(&ns_cache),
+        PartitionTemplate {
+            parts: vec![TemplatePart::TimeFormat(
+                router_config.partition_key_pattern.clone(),
+            )],
+        },
+    );
     let partitioner = InstrumentationDecorator::new("partitioner", &metrics, partitioner);
 
     // # Namespace resolver
@@ -325,7 +357,8 @@ pub async fn create_router2_server_type(
     // # Handler stack
     //
     // Build the chain of DML handlers that forms the request processing pipeline
-    let handler_stack = retention_validator
+    let handler_stack = rate_limiter
+        .and_then(retention_validator)
         .and_then(schema_validator)
         .and_then(partitioner)
         // Once writes have been partitioned, they are processed in parallel.
@@ -341,7 +374,15 @@ pub async fn create_router2_server_type(
         ));
 
     // Record the overall request handling latency
-    let handler_stack = InstrumentationDecorator::new("request", &metrics, handler_stack);
+    let handler_stack = Arc::new(InstrumentationDecorator::new(
+        "request",
+        &metrics,
+        handler_stack,
+    ));
+
+    // The handler stack and namespace resolver are shared by the HTTP write
+    // API and the Flight bulk ingest service.
+    let namespace_resolver = Arc::new(namespace_resolver);
 
     // Initialize the HTTP API delegate
     let write_param_extractor: Box<dyn WriteParamExtractor> =
@@ -352,20 +393,41 @@ pub async fn create_router2_server_type(
     let http = HttpDelegate::new(
         common_state.run_config().max_http_request_size,
         router_config.http_request_limit,
-        namespace_resolver,
-        handler_stack,
-        authz,
+        Arc::clone(&namespace_resolver),
+        Arc::clone(&handler_stack),
+        authz.as_ref().map(Arc::clone),
         &metrics,
         write_param_extractor,
-    );
+    )
+    .with_delete_handler(Arc::new(CatalogDeleteHandler::new(
+        Arc::clone(&catalog),
+        ingester_connections(),
+    )))
+    .with_partial_writes(router_config.partial_writes_enabled);
 
     // Initialize the gRPC API delegate that creates the services relevant to the RPC
     // write router path and use it to create the relevant `RpcWriteRouterServer` and
     // `RpcWriteRouterServerType`.
-    let grpc = RpcWriteGrpcDelegate::new(catalog, object_store, topic_id, query_id);
+    let grpc = RpcWriteGrpcDelegate::new(
+        catalog,
+        object_store,
+        ns_cache,
+        ingester_connections(),
+        topic_id,
+        query_id,
+    );
+
+    // Initialize the Flight bulk ingest service, bounded by the same request
+    // size limit as the HTTP write API.
+    let flight = FlightIngestService::new(
+        common_state.run_config().max_http_request_size,
+        namespace_resolver,
+        handler_stack,
+        authz,
+    );
 
     let router_server =
-        RpcWriteRouterServer::new(http, grpc, metrics, common_state.trace_collector());
+        RpcWriteRouterServer::new(http, grpc, flight, metrics, common_state.trace_collector());
     let server_type = Arc::new(RpcWriteRouterServerType::new(router_server, common_state));
     Ok(server_type)
 }
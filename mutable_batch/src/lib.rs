@@ -53,6 +53,9 @@ pub enum Error {
 
     #[snafu(context(false))]
     WriterError { source: writer::Error },
+
+    #[snafu(display("Invalid partition template: {}", reason))]
+    InvalidPartitionTemplate { reason: String },
 }
 
 /// A specialized `Error` for [`MutableBatch`] errors
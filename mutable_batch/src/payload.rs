@@ -98,16 +98,19 @@ impl<'a> PartitionWrite<'a> {
 
     /// Create a collection of [`PartitionWrite`] indexed by partition key
     /// from a [`MutableBatch`] and [`PartitionTemplate`]
+    ///
+    /// Returns an error if `partition_template` contains an invalid regex or
+    /// strftime format.
     pub fn partition(
         table_name: &str,
         batch: &'a MutableBatch,
         partition_template: &PartitionTemplate,
-    ) -> HashMap<PartitionKey, Self> {
+    ) -> Result<HashMap<PartitionKey, Self>> {
         use hashbrown::hash_map::Entry;
         let time = get_time_column(batch);
 
         let mut partition_ranges = HashMap::new();
-        for (partition, range) in partition::partition_batch(batch, table_name, partition_template)
+        for (partition, range) in partition::partition_batch(batch, table_name, partition_template)?
         {
             let row_count = NonZeroUsize::new(range.end - range.start).unwrap();
             let (min_timestamp, max_timestamp) = min_max_time(&time[range.clone()]);
@@ -131,7 +134,7 @@ impl<'a> PartitionWrite<'a> {
                 }
             }
         }
-        partition_ranges
+        Ok(partition_ranges)
     }
 }
 
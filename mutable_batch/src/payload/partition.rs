@@ -4,20 +4,73 @@
 
 use crate::{
     column::{Column, ColumnData},
-    MutableBatch,
+    InvalidPartitionTemplateSnafu, MutableBatch, Result,
 };
-use chrono::{format::StrftimeItems, TimeZone, Utc};
-use data_types::{PartitionTemplate, TemplatePart};
+use chrono::{
+    format::{Item, StrftimeItems},
+    TimeZone, Utc,
+};
+use data_types::{PartitionTemplate, RegexCapture, StrftimeColumn, TemplatePart};
+use hashbrown::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use regex::Regex;
 use schema::TIME_COLUMN_NAME;
 use std::ops::Range;
 
+/// The maximum number of compiled patterns held in [`COMPILED_REGEXES`].
+const MAX_COMPILED_REGEXES: usize = 1_000;
+
+/// Compiled [`RegexCapture`] patterns, keyed by their source.
+///
+/// Partition templates are validated when they are created and change rarely,
+/// so each distinct pattern is compiled once rather than for every batch. The
+/// patterns are user supplied, so at most [`MAX_COMPILED_REGEXES`] are held,
+/// discarding all of them when the limit is reached.
+static COMPILED_REGEXES: Lazy<RwLock<HashMap<String, Regex>>> = Lazy::new(Default::default);
+
+/// Returns the compiled form of `pattern`, compiling and caching it if necessary
+fn compiled_regex(pattern: &str) -> Result<Regex> {
+    if let Some(regex) = COMPILED_REGEXES.read().get(pattern) {
+        return Ok(regex.clone());
+    }
+
+    let regex = Regex::new(pattern).map_err(|e| {
+        InvalidPartitionTemplateSnafu {
+            reason: format!("invalid regex {pattern:?}: {e}"),
+        }
+        .build()
+    })?;
+
+    let mut cache = COMPILED_REGEXES.write();
+    if cache.len() >= MAX_COMPILED_REGEXES {
+        cache.clear();
+    }
+    cache.insert(pattern.to_string(), regex.clone());
+    Ok(regex)
+}
+
+/// Returns the parsed form of the strftime `format`
+fn strftime_items(format: &str) -> Result<StrftimeItems<'_>> {
+    let items = StrftimeItems::new(format);
+    if items.clone().any(|item| matches!(item, Item::Error)) {
+        return InvalidPartitionTemplateSnafu {
+            reason: format!("invalid strftime format {format:?}"),
+        }
+        .fail();
+    }
+    Ok(items)
+}
+
 /// Returns an iterator identifying consecutive ranges for a given partition key
+///
+/// Returns an error if `template` contains an invalid regex or strftime format.
 pub fn partition_batch<'a>(
     batch: &'a MutableBatch,
     table_name: &'a str,
     template: &'a PartitionTemplate,
-) -> impl Iterator<Item = (String, Range<usize>)> + 'a {
-    range_encode(partition_keys(batch, table_name, template))
+) -> Result<impl Iterator<Item = (String, Range<usize>)> + 'a> {
+    Ok(range_encode(partition_keys(batch, table_name, template)?))
 }
 
 /// A [`PartitionTemplate`] is made up of one of more [`TemplatePart`] that are rendered and
@@ -33,6 +86,11 @@ enum Template<'a> {
     Column(&'a Column, &'a str),
     MissingColumn(&'a str),
     TimeFormat(&'a [i64], StrftimeItems<'a>),
+    /// A string or tag column, the regex to apply to its values and the name of the column
+    RegexCapture(&'a Column, Regex, &'a str),
+    /// An integer or timestamp column, the format to apply to its values and the name of the
+    /// column
+    StrftimeColumn(&'a Column, StrftimeItems<'a>, &'a str),
 }
 
 impl<'a> Template<'a> {
@@ -68,6 +126,40 @@ impl<'a> Template<'a> {
                     .format_with_items(format.clone());
                 write!(out, "{formatted}")
             }
+            Template::RegexCapture(col, regex, col_name) => {
+                let value = match &col.data {
+                    ColumnData::String(col_data, _) if col.valid.get(idx) => col_data.get(idx),
+                    ColumnData::Tag(col_data, dictionary, _) if col.valid.get(idx) => {
+                        dictionary.lookup_id(col_data[idx])
+                    }
+                    _ => None,
+                };
+
+                // Use the first capture group if the regex has one, otherwise the whole match
+                let group = usize::from(regex.captures_len() > 1);
+                let matched = value
+                    .and_then(|value| regex.captures(value))
+                    .and_then(|c| c.get(group))
+                    .filter(|m| !m.as_str().is_empty());
+
+                out.write_str(col_name)?;
+                match matched {
+                    Some(m) => {
+                        out.write_char('_')?;
+                        out.write_str(m.as_str())
+                    }
+                    None => Ok(()),
+                }
+            }
+            Template::StrftimeColumn(col, format, _) if col.valid.get(idx) => {
+                let t = match &col.data {
+                    ColumnData::I64(col_data, _) => col_data[idx],
+                    x => unreachable!("expected i64 for strftime column got {}", x),
+                };
+                let formatted = Utc.timestamp_nanos(t).format_with_items(format.clone());
+                write!(out, "{formatted}")
+            }
+            Template::StrftimeColumn(_, _, col_name) => out.write_str(col_name),
         }
     }
 }
@@ -77,29 +169,46 @@ fn partition_keys<'a>(
     batch: &'a MutableBatch,
     table_name: &'a str,
     template: &'a PartitionTemplate,
-) -> impl Iterator<Item = String> + 'a {
+) -> Result<impl Iterator<Item = String> + 'a> {
     let time = batch.column(TIME_COLUMN_NAME).expect("time column");
     let time = match &time.data {
         ColumnData::I64(col_data, _) => col_data.as_slice(),
         x => unreachable!("expected i32 for time got {}", x),
     };
 
-    let cols: Vec<_> = template
+    let cols = template
         .parts
         .iter()
-        .map(|part| match part {
-            TemplatePart::Table => Template::Table(table_name),
-            TemplatePart::Column(name) => batch.column(name).map_or_else(
-                |_| Template::MissingColumn(name),
-                |col| Template::Column(col, name),
-            ),
-            TemplatePart::TimeFormat(fmt) => Template::TimeFormat(time, StrftimeItems::new(fmt)),
-            TemplatePart::RegexCapture(_) => unimplemented!(),
-            TemplatePart::StrftimeColumn(_) => unimplemented!(),
+        .map(|part| {
+            Ok(match part {
+                TemplatePart::Table => Template::Table(table_name),
+                TemplatePart::Column(name) => batch.column(name).map_or_else(
+                    |_| Template::MissingColumn(name),
+                    |col| Template::Column(col, name),
+                ),
+                TemplatePart::TimeFormat(fmt) => Template::TimeFormat(time, strftime_items(fmt)?),
+                TemplatePart::RegexCapture(RegexCapture { column, regex }) => {
+                    let regex = compiled_regex(regex)?;
+                    match batch.column(column) {
+                        Ok(col) => Template::RegexCapture(col, regex, column),
+                        Err(_) => Template::MissingColumn(column),
+                    }
+                }
+                TemplatePart::StrftimeColumn(StrftimeColumn { column, format }) => {
+                    let format = strftime_items(format)?;
+                    match batch.column(column) {
+                        Ok(col) if matches!(col.data, ColumnData::I64(_, _)) => {
+                            Template::StrftimeColumn(col, format, column)
+                        }
+                        // Columns that cannot hold a timestamp are treated as if they were null
+                        _ => Template::MissingColumn(column),
+                    }
+                }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<_>>>()?;
 
-    (0..batch.row_count).map(move |idx| {
+    Ok((0..batch.row_count).map(move |idx| {
         let mut string = String::new();
         for (col_idx, col) in cols.iter().enumerate() {
             col.fmt_row(&mut string, idx)
@@ -110,7 +219,7 @@ fn partition_keys<'a>(
             }
         }
         string
-    })
+    }))
 }
 
 /// Takes an iterator and merges consecutive elements together
@@ -221,7 +330,7 @@ mod tests {
 
         writer.commit();
 
-        let keys: Vec<_> = partition_keys(&batch, "foo", &template).collect();
+        let keys: Vec<_> = partition_keys(&batch, "foo", &template).unwrap().collect();
 
         assert_eq!(
             keys,
@@ -234,4 +343,174 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_partition_regex_capture() {
+        let mut batch = MutableBatch::new();
+        let mut writer = Writer::new(&mut batch, 5);
+
+        writer
+            .write_time("time", vec![1, 2, 3, 4, 5].into_iter())
+            .unwrap();
+
+        writer
+            .write_tag(
+                "host",
+                Some(&[0b00011101]),
+                vec!["eu1-host-a", "us2-host-b", "unknown", "us2-host-c"].into_iter(),
+            )
+            .unwrap();
+
+        writer
+            .write_string(
+                "path",
+                Some(&[0b00010111]),
+                vec!["/api/v1/write", "/api/v2/query", "/ping", "/api/v1/query"].into_iter(),
+            )
+            .unwrap();
+
+        writer
+            .write_f64("f64", None, vec![2., 4.5, 6., 3., 6.].into_iter())
+            .unwrap();
+
+        let template = PartitionTemplate {
+            parts: vec![
+                TemplatePart::RegexCapture(RegexCapture {
+                    column: "host".to_string(),
+                    regex: "^([a-z]+\\d)-".to_string(),
+                }),
+                TemplatePart::RegexCapture(RegexCapture {
+                    column: "path".to_string(),
+                    regex: "v\\d".to_string(),
+                }),
+                TemplatePart::RegexCapture(RegexCapture {
+                    column: "f64".to_string(),
+                    regex: ".*".to_string(),
+                }),
+                TemplatePart::RegexCapture(RegexCapture {
+                    column: "bananas".to_string(),
+                    regex: ".*".to_string(),
+                }),
+            ],
+        };
+
+        writer.commit();
+
+        let keys: Vec<_> = partition_keys(&batch, "foo", &template).unwrap().collect();
+
+        assert_eq!(
+            keys,
+            vec![
+                "host_eu1-path_v1-f64-bananas".to_string(),
+                "host-path_v2-f64-bananas".to_string(),
+                "host_us2-path-f64-bananas".to_string(),
+                "host-path-f64-bananas".to_string(),
+                "host_us2-path_v1-f64-bananas".to_string(),
+            ]
+        )
+    }
+
+    #[test]
+    fn test_partition_strftime_column() {
+        let mut batch = MutableBatch::new();
+        let mut writer = Writer::new(&mut batch, 4);
+
+        writer
+            .write_time("time", vec![1, 2, 3, 4].into_iter())
+            .unwrap();
+
+        writer
+            .write_i64(
+                "other_time",
+                Some(&[0b00001011]),
+                vec![
+                    1_650_000_000_000_000_000,
+                    1_660_000_000_000_000_000,
+                    1_670_000_000_000_000_000,
+                ]
+                .into_iter(),
+            )
+            .unwrap();
+
+        writer
+            .write_string(
+                "region",
+                None,
+                vec!["west", "east", "west", "east"].into_iter(),
+            )
+            .unwrap();
+
+        let template = PartitionTemplate {
+            parts: vec![
+                TemplatePart::TimeFormat("%Y".to_string()),
+                TemplatePart::StrftimeColumn(StrftimeColumn {
+                    column: "other_time".to_string(),
+                    format: "%Y-%m-%d".to_string(),
+                }),
+                TemplatePart::StrftimeColumn(StrftimeColumn {
+                    column: "region".to_string(),
+                    format: "%Y-%m-%d".to_string(),
+                }),
+                TemplatePart::StrftimeColumn(StrftimeColumn {
+                    column: "bananas".to_string(),
+                    format: "%Y-%m-%d".to_string(),
+                }),
+            ],
+        };
+
+        writer.commit();
+
+        let keys: Vec<_> = partition_keys(&batch, "foo", &template).unwrap().collect();
+
+        assert_eq!(
+            keys,
+            vec![
+                "1970-2022-04-15-region-bananas".to_string(),
+                "1970-2022-08-08-region-bananas".to_string(),
+                "1970-other_time-region-bananas".to_string(),
+                "1970-2022-12-02-region-bananas".to_string(),
+            ]
+        )
+    }
+
+    #[test]
+    fn test_compiled_regexes_bounded() {
+        for i in 0..=MAX_COMPILED_REGEXES {
+            let regex = compiled_regex(&format!("^host-{i}$")).unwrap();
+            assert!(regex.is_match(&format!("host-{i}")));
+            assert!(COMPILED_REGEXES.read().len() <= MAX_COMPILED_REGEXES);
+        }
+    }
+
+    #[test]
+    fn test_partition_invalid_template() {
+        let mut batch = MutableBatch::new();
+        let mut writer = Writer::new(&mut batch, 1);
+        writer.write_time("time", vec![1].into_iter()).unwrap();
+        writer
+            .write_tag("host", None, vec!["eu1-host-a"].into_iter())
+            .unwrap();
+        writer.commit();
+
+        for part in [
+            TemplatePart::TimeFormat("%Y-%".to_string()),
+            TemplatePart::RegexCapture(RegexCapture {
+                column: "host".to_string(),
+                regex: "(".to_string(),
+            }),
+            TemplatePart::StrftimeColumn(StrftimeColumn {
+                column: "bananas".to_string(),
+                format: "%Q".to_string(),
+            }),
+        ] {
+            let template = PartitionTemplate { parts: vec![part] };
+            let err = partition_keys(&batch, "foo", &template)
+                .err()
+                .expect("invalid template should be rejected");
+            assert!(
+                matches!(err, crate::Error::InvalidPartitionTemplate { .. }),
+                "{err}"
+            );
+        }
+    }
 }
@@ -438,7 +438,8 @@ fn test_partition_write() {
         &PartitionTemplate {
             parts: vec![TemplatePart::Column("b1".to_string())],
         },
-    );
+    )
+    .unwrap();
 
     for (_, write) in &partitioned {
         verify_write(write);
@@ -14,10 +14,11 @@
 )]
 
 use hashbrown::{hash_map::Entry, HashMap, HashSet};
-use influxdb_line_protocol::{parse_lines, FieldValue, ParsedLine};
+use influxdb_line_protocol::{parse_lines, split_lines, FieldValue, ParsedLine};
 use mutable_batch::writer::Writer;
 use mutable_batch::MutableBatch;
 use snafu::{ResultExt, Snafu};
+use std::str::Utf8Error;
 
 /// Error type for line protocol conversion
 #[derive(Debug, Snafu)]
@@ -32,6 +33,9 @@ pub enum Error {
     #[snafu(display("error writing line {}: {}", line, source))]
     Write { source: LineWriteError, line: usize },
 
+    #[snafu(display("error validating line {}: {}", line, reason))]
+    Validation { reason: String, line: usize },
+
     #[snafu(display("empty write payload"))]
     EmptyPayload,
 
@@ -60,6 +64,8 @@ pub struct LinesConverter {
     timestamp_base: i64,
     /// The statistics
     stats: PayloadStatistics,
+    /// The number of lines read so far, used to number lines across calls
+    lines_read: usize,
     /// The current batches
     batches: HashMap<String, MutableBatch>,
 }
@@ -71,6 +77,7 @@ impl LinesConverter {
             default_time,
             timestamp_base: 1,
             stats: Default::default(),
+            lines_read: 0,
             batches: Default::default(),
         }
     }
@@ -99,33 +106,96 @@ impl LinesConverter {
     ///   * same name for tag and field, different type :
     ///     [`mutable_batch::writer::Error::TypeMismatch`]
     ///
+    /// A payload may be written in several calls, as long as each call
+    /// contains only complete lines (see [`LineBuffer`]). Line numbers in
+    /// errors are relative to the start of the payload.
     pub fn write_lp(&mut self, lines: &str) -> Result<()> {
-        for (line_idx, maybe_line) in parse_lines(lines).enumerate() {
-            let mut line = maybe_line.context(LineProtocolSnafu { line: line_idx + 1 })?;
+        for maybe_line in parse_lines(lines) {
+            self.lines_read += 1;
+            self.write_parsed_line(self.lines_read, maybe_line, |_, _| Ok(()))?;
+        }
+        Ok(())
+    }
 
-            if let Some(t) = line.timestamp.as_mut() {
-                *t = t
-                    .checked_mul(self.timestamp_base)
-                    .ok_or(Error::TimestampOverflow)?;
-            }
+    /// Write some line protocol data, skipping any line that cannot be parsed
+    /// or written instead of failing the whole payload.
+    ///
+    /// Each parsed line is passed to `validate` along with its timestamp in
+    /// nanoseconds before it is written, and is skipped with an
+    /// [`Error::Validation`] if `validate` returns an error.
+    ///
+    /// The lines that were skipped are returned in the order they appear in
+    /// `lines`, and are not included in the [`PayloadStatistics`]. All other
+    /// lines are written with the same semantics as
+    /// [`LinesConverter::write_lp()`].
+    pub fn write_lp_partial<F>(&mut self, lines: &str, mut validate: F) -> Vec<SkippedLine>
+    where
+        F: FnMut(&ParsedLine<'_>, i64) -> Result<(), String>,
+    {
+        parse_lines(lines)
+            .filter_map(|maybe_line| {
+                self.lines_read += 1;
+                let line = self.lines_read;
+                self.write_parsed_line(line, maybe_line, &mut validate)
+                    .err()
+                    .map(|error| SkippedLine { line, error })
+            })
+            .collect()
+    }
 
-            self.stats.num_lines += 1;
-            self.stats.num_fields += line.field_set.len();
+    /// Write a single (1-based) `line_number` to the batch for its measurement,
+    /// if accepted by `validate`.
+    ///
+    /// If the line cannot be written, any partially written values are rolled
+    /// back and the batches are left as they were before the call.
+    fn write_parsed_line<F>(
+        &mut self,
+        line_number: usize,
+        maybe_line: Result<ParsedLine<'_>, influxdb_line_protocol::Error>,
+        mut validate: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&ParsedLine<'_>, i64) -> Result<(), String>,
+    {
+        let mut line = maybe_line.context(LineProtocolSnafu { line: line_number })?;
+
+        if let Some(t) = line.timestamp.as_mut() {
+            *t = t
+                .checked_mul(self.timestamp_base)
+                .ok_or(Error::TimestampOverflow)?;
+        }
 
-            let measurement = line.series.measurement.as_str();
+        validate(&line, line.timestamp.unwrap_or(self.default_time)).map_err(|reason| {
+            Error::Validation {
+                reason,
+                line: line_number,
+            }
+        })?;
+
+        let measurement = line.series.measurement.as_str();
+
+        let (_, batch) = self
+            .batches
+            .raw_entry_mut()
+            .from_key(measurement)
+            .or_insert_with(|| (measurement.to_string(), MutableBatch::new()));
+
+        // TODO: Reuse writer
+        let mut writer = Writer::new(batch, 1);
+        if let Err(e) = write_line(&mut writer, &line, self.default_time) {
+            // Dropping the uncommitted writer rolls back the partial row, but
+            // a batch created for this line would be left empty.
+            drop(writer);
+            if batch.rows() == 0 {
+                self.batches.remove(measurement);
+            }
+            return Err(e).context(WriteSnafu { line: line_number });
+        }
+        writer.commit();
 
-            let (_, batch) = self
-                .batches
-                .raw_entry_mut()
-                .from_key(measurement)
-                .or_insert_with(|| (measurement.to_string(), MutableBatch::new()));
+        self.stats.num_lines += 1;
+        self.stats.num_fields += line.field_set.len();
 
-            // TODO: Reuse writer
-            let mut writer = Writer::new(batch, 1);
-            write_line(&mut writer, &line, self.default_time)
-                .context(WriteSnafu { line: line_idx + 1 })?;
-            writer.commit();
-        }
         Ok(())
     }
 
@@ -139,6 +209,59 @@ impl LinesConverter {
     }
 }
 
+/// A line skipped by [`LinesConverter::write_lp_partial()`].
+#[derive(Debug)]
+pub struct SkippedLine {
+    /// The 1-based line number of the skipped line.
+    pub line: usize,
+    /// The reason the line was skipped.
+    pub error: Error,
+}
+
+/// Buffers a line protocol payload received in chunks split at arbitrary byte
+/// offsets, yielding only complete lines.
+///
+/// Lines are split following the same rules as [`parse_lines`], so a newline
+/// within a quoted string field does not end a line.
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    buf: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Append `chunk` to the buffer, returning (and removing) all the complete
+    /// lines it now contains.
+    ///
+    /// The returned string is empty if no line has been completed.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<String, Utf8Error> {
+        self.buf.extend_from_slice(chunk);
+
+        // A newline byte never occurs within a multi-byte UTF-8 sequence, so
+        // everything up to (and including) the last newline is complete text.
+        let end = match self.buf.iter().rposition(|&b| b == b'\n') {
+            Some(v) => v + 1,
+            None => return Ok(String::new()),
+        };
+        let text = std::str::from_utf8(&self.buf[..end])?;
+
+        // The last line may still be incomplete if the final newline is within
+        // a quoted string, so split before it.
+        let split = split_lines(text)
+            .last()
+            .map(|line| line.as_ptr() as usize - text.as_ptr() as usize)
+            .unwrap_or_default();
+
+        let lines = text[..split].to_string();
+        self.buf.drain(..split);
+        Ok(lines)
+    }
+
+    /// Consume the buffer, returning any remaining (unterminated) line.
+    pub fn finish(self) -> Result<String, Utf8Error> {
+        String::from_utf8(self.buf).map_err(|e| e.utf8_error())
+    }
+}
+
 /// Converts the provided lines of line protocol to a set of [`MutableBatch`]
 /// keyed by measurement name
 pub fn lines_to_batches(lines: &str, default_time: i64) -> Result<HashMap<String, MutableBatch>> {
@@ -445,6 +568,182 @@ m b=t 1639612800000000000
         assert!(!u.is_valid(2));
     }
 
+    #[test]
+    fn test_write_lp_partial() {
+        let lp = r#"cpu,tag1=v1 val=2i 1
+        cpu,tag1=v2 val=2.0 2
+        mem,tag1=v1,tag1=v2 ival=3i 3
+        bananas
+        mem,tag1=v3 ival=4i 4
+        "#;
+
+        let mut converter = LinesConverter::new(5);
+        let skipped = converter.write_lp_partial(lp, |_, _| Ok(()));
+
+        assert_matches!(
+            skipped.as_slice(),
+            [
+                SkippedLine {
+                    line: 2,
+                    error: Error::Write {
+                        source: LineWriteError::MutableBatch { .. },
+                        line: 2
+                    }
+                },
+                SkippedLine {
+                    line: 3,
+                    error: Error::Write {
+                        source: LineWriteError::DuplicateTag { .. },
+                        line: 3
+                    }
+                },
+                SkippedLine {
+                    line: 4,
+                    error: Error::LineProtocol { line: 4, .. }
+                },
+            ]
+        );
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_lines, 2);
+        assert_eq!(stats.num_fields, 2);
+
+        assert_batches_eq!(
+            &[
+                "+------+--------------------------------+-----+",
+                "| tag1 | time                           | val |",
+                "+------+--------------------------------+-----+",
+                "| v1   | 1970-01-01T00:00:00.000000001Z | 2   |",
+                "+------+--------------------------------+-----+",
+            ],
+            &[batches["cpu"].to_arrow(Projection::All).unwrap()]
+        );
+        assert_batches_eq!(
+            &[
+                "+------+------+--------------------------------+",
+                "| ival | tag1 | time                           |",
+                "+------+------+--------------------------------+",
+                "| 4    | v3   | 1970-01-01T00:00:00.000000004Z |",
+                "+------+------+--------------------------------+",
+            ],
+            &[batches["mem"].to_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_write_lp_partial_all_skipped() {
+        let mut converter = LinesConverter::new(5);
+        let skipped = converter.write_lp_partial("m1,tag=1,tag=2 val=1i 0", |_, _| Ok(()));
+        assert_eq!(skipped.len(), 1);
+
+        // The batch created for the skipped line is not retained.
+        assert_matches!(converter.finish(), Err(Error::EmptyPayload));
+    }
+
+    #[test]
+    fn test_write_lp_partial_validate() {
+        let mut converter = LinesConverter::new(5);
+        converter.set_timestamp_base(10);
+
+        let mut seen = vec![];
+        let skipped = converter.write_lp_partial(
+            "cpu val=1i 1
+mem val=2i
+cpu val=3i 3
+",
+            |line, time| {
+                seen.push((line.series.measurement.to_string(), time));
+                match time {
+                    30 => Err("bananas".to_string()),
+                    _ => Ok(()),
+                }
+            },
+        );
+        assert_eq!(
+            seen,
+            [
+                ("cpu".to_string(), 10),
+                ("mem".to_string(), 5),
+                ("cpu".to_string(), 30)
+            ]
+        );
+        assert_matches!(
+            skipped.as_slice(),
+            [SkippedLine {
+                line: 3,
+                error: Error::Validation { line: 3, reason }
+            }] if reason == "bananas"
+        );
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_lines, 2);
+        assert_eq!(batches["cpu"].rows(), 1);
+        assert_eq!(batches["mem"].rows(), 1);
+    }
+
+    #[test]
+    fn test_write_lp_chunks() {
+        let lp = "cpu,host=a val=1i 1\n\
+                  cpu,host=b desc=\"line\none ✓\" 2\n\
+                  # comment\n\
+                  mem,host=c val=3i 3";
+
+        let want = lines_to_batches(lp, 5).unwrap();
+
+        // Split the payload at every possible byte offset.
+        for split in 0..=lp.len() {
+            let (a, b) = lp.as_bytes().split_at(split);
+
+            let mut buffer = LineBuffer::default();
+            let mut converter = LinesConverter::new(5);
+            converter.write_lp(&buffer.push(a).unwrap()).unwrap();
+            converter.write_lp(&buffer.push(b).unwrap()).unwrap();
+            converter.write_lp(&buffer.finish().unwrap()).unwrap();
+
+            let (got, stats) = converter.finish().unwrap();
+            assert_eq!(stats.num_lines, 3);
+            assert_eq!(got.len(), want.len());
+            for (table, batch) in &want {
+                assert_eq!(
+                    got[table].to_arrow(Projection::All).unwrap(),
+                    batch.to_arrow(Projection::All).unwrap(),
+                    "split at {split}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_lp_line_numbers_across_calls() {
+        let mut converter = LinesConverter::new(5);
+        converter.write_lp("cpu val=1i 1\ncpu val=2i 2\n").unwrap();
+
+        let err = converter
+            .write_lp("cpu val=3i 3\nbananas\n")
+            .expect_err("invalid line should fail");
+        assert_matches!(err, Error::LineProtocol { line: 4, .. });
+
+        let skipped = converter.write_lp_partial("bananas\n", |_, _| Ok(()));
+        assert_matches!(skipped.as_slice(), [SkippedLine { line: 5, .. }]);
+    }
+
+    #[test]
+    fn test_line_buffer_non_utf8() {
+        let mut buffer = LineBuffer::default();
+        assert_eq!(buffer.push(&[b'a', 0xc3]).unwrap(), "");
+        assert_eq!(buffer.push(&[0xa9, b'\n', b'b']).unwrap(), "aé\n");
+        assert_eq!(buffer.finish().unwrap(), "b");
+
+        let mut buffer = LineBuffer::default();
+        buffer
+            .push(&[0xc3, 0x28, b'\n'])
+            .expect_err("invalid utf8 should fail");
+
+        let mut buffer = LineBuffer::default();
+        assert_eq!(buffer.push(&[0xc3, 0x28]).unwrap(), "");
+        buffer.finish().expect_err("invalid utf8 should fail");
+    }
+
     // https://github.com/influxdata/influxdb_iox/issues/4326
     mod issue4326 {
         use super::*;
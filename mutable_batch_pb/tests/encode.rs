@@ -126,7 +126,8 @@ fn test_encode_decode_null_columns_issue_4272() {
         &PartitionTemplate {
             parts: vec![TemplatePart::TimeFormat("%Y-%m-%d".to_owned())],
         },
-    );
+    )
+    .unwrap();
 
     // There should be two partitions, one with for the timestamp 160, and
     // one for the other timestamp.
@@ -437,6 +437,9 @@ impl IoxMetadata {
 
     /// Create a corresponding iox catalog's ParquetFile
     ///
+    /// `max_tombstone_sequence_number` is the sequence number of the last
+    /// tombstone already applied to the rows of this file.
+    ///
     /// # Panics
     ///
     /// This method panics if the [`IoxParquetMetaData`] structure does not
@@ -455,6 +458,7 @@ impl IoxMetadata {
         partition_id: PartitionId,
         file_size_bytes: usize,
         metadata: &IoxParquetMetaData,
+        max_tombstone_sequence_number: SequenceNumber,
         column_id_map: F,
     ) -> ParquetFileParams
     where
@@ -515,6 +519,7 @@ impl IoxMetadata {
             created_at: Timestamp::from(self.creation_timestamp),
             column_set: ColumnSet::new(columns),
             max_l0_created_at: Timestamp::from(self.max_l0_created_at),
+            max_tombstone_sequence_number,
         }
     }
 
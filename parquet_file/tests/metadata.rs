@@ -417,9 +417,13 @@ async fn test_derive_parquet_file_params() {
         ("some_field".into(), ColumnId::new(1)),
         ("time".into(), ColumnId::new(2)),
     ]);
-    let catalog_data = meta.to_parquet_file(partition_id, file_size, &iox_parquet_meta, |name| {
-        *column_id_map.get(name).unwrap()
-    });
+    let catalog_data = meta.to_parquet_file(
+        partition_id,
+        file_size,
+        &iox_parquet_meta,
+        SequenceNumber::new(3),
+        |name| *column_id_map.get(name).unwrap(),
+    );
 
     // And verify the resulting statistics used in the catalog.
     //
@@ -438,6 +442,10 @@ async fn test_derive_parquet_file_params() {
     assert_eq!(catalog_data.min_time, Timestamp::new(1646917692000000000));
     assert_eq!(catalog_data.max_time, Timestamp::new(1653311292000000000));
     assert_eq!(catalog_data.max_l0_created_at, Timestamp::new(1234));
+    assert_eq!(
+        catalog_data.max_tombstone_sequence_number,
+        SequenceNumber::new(3)
+    );
 }
 
 fn to_string_array(strs: &[&str]) -> ArrayRef {
@@ -1,6 +1,6 @@
 use crate::delete_expr::{df_to_expr, expr_to_df};
 use chrono::DateTime;
-use data_types::{DeleteExpr, DeletePredicate, TimestampRange};
+use data_types::{DeleteExpr, DeletePredicate, Op, TimestampRange, Tombstone};
 use datafusion::{
     logical_expr::Operator,
     prelude::{binary_expr, lit, Column, Expr},
@@ -11,6 +11,7 @@ use sqlparser::{
     dialect::GenericDialect,
     parser::Parser,
 };
+use std::sync::Arc;
 
 /// Parse Delete Predicates
 /// Parse Error
@@ -69,6 +70,44 @@ pub fn parse_delete_predicate(
     })
 }
 
+/// Reconstruct the [`DeletePredicate`] recorded in a catalog [`Tombstone`].
+pub fn parse_tombstone_predicate(tombstone: &Tombstone) -> Result<DeletePredicate> {
+    parse_delete_predicate(
+        &tombstone.min_time.get().to_string(),
+        &tombstone.max_time.get().to_string(),
+        &tombstone.serialized_predicate,
+    )
+}
+
+/// Adapt the delete predicate `pred` to the columns of a table, where
+/// `has_column` returns true for the columns that exist in the table.
+///
+/// A column that does not exist in the table is NULL for every row, so an
+/// equality on such a column can never match and `None` is returned, while an
+/// inequality always matches and is dropped from the predicate.
+pub fn delete_predicate_for_columns(
+    pred: &Arc<DeletePredicate>,
+    has_column: impl Fn(&str) -> bool,
+) -> Option<Arc<DeletePredicate>> {
+    if pred.exprs.iter().all(|expr| has_column(expr.column())) {
+        return Some(Arc::clone(pred));
+    }
+
+    let mut exprs = Vec::with_capacity(pred.exprs.len());
+    for expr in &pred.exprs {
+        match (has_column(expr.column()), expr.op()) {
+            (true, _) => exprs.push(expr.clone()),
+            (false, Op::Eq) => return None,
+            (false, Op::Ne) => {}
+        }
+    }
+
+    Some(Arc::new(DeletePredicate {
+        range: pred.range,
+        exprs,
+    }))
+}
+
 /// Parse the predicate and convert it into datafusion expression
 /// A delete predicate is a conjunctive expression of many
 /// binary expressions of 'colum = constant' or 'column != constant'
@@ -240,7 +279,7 @@ fn parse_time_range(start: &str, stop: &str) -> Result<(i64, i64)> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use data_types::{Op, Scalar};
+    use data_types::{Op, Scalar, SequenceNumber, ShardId, TableId, Timestamp, TombstoneId};
 
     #[test]
     fn test_time_range_valid() {
@@ -417,6 +456,65 @@ mod tests {
         assert_eq!(result.exprs, expected);
     }
 
+    #[test]
+    fn test_tombstone_pred_roundtrip() {
+        let pred = DeletePredicate {
+            range: TimestampRange::new(-10, 200),
+            exprs: vec![
+                DeleteExpr::new(
+                    "city".to_string(),
+                    Op::Eq,
+                    Scalar::String("Boston".to_string()),
+                ),
+                DeleteExpr::new("cost".to_string(), Op::Ne, Scalar::I64(100)),
+            ],
+        };
+
+        let tombstone = Tombstone {
+            id: TombstoneId::new(1),
+            table_id: TableId::new(2),
+            shard_id: ShardId::new(3),
+            sequence_number: SequenceNumber::new(1),
+            min_time: Timestamp::new(pred.range.start()),
+            max_time: Timestamp::new(pred.range.end()),
+            serialized_predicate: pred.expr_sql_string(),
+            created_at: Timestamp::new(1),
+        };
+
+        let result = parse_tombstone_predicate(&tombstone).unwrap();
+        assert_eq!(result, pred);
+    }
+
+    #[test]
+    fn test_delete_predicate_for_columns() {
+        let city = DeleteExpr::new(
+            "city".to_string(),
+            Op::Eq,
+            Scalar::String("Boston".to_string()),
+        );
+        let state = DeleteExpr::new(
+            "state".to_string(),
+            Op::Ne,
+            Scalar::String("MA".to_string()),
+        );
+        let pred = Arc::new(DeletePredicate {
+            range: TimestampRange::new(1, 10),
+            exprs: vec![city.clone(), state.clone()],
+        });
+
+        // all columns exist
+        let result = delete_predicate_for_columns(&pred, |_| true).unwrap();
+        assert!(Arc::ptr_eq(&result, &pred));
+
+        // an inequality on a missing column is dropped
+        let result = delete_predicate_for_columns(&pred, |c| c == "city").unwrap();
+        assert_eq!(result.range, pred.range);
+        assert_eq!(result.exprs, vec![city]);
+
+        // an equality on a missing column never matches
+        assert!(delete_predicate_for_columns(&pred, |c| c == "state").is_none());
+    }
+
     #[test]
     fn test_full_delete_pred_invalid_time_range() {
         let start = r#"100"#;
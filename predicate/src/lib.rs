@@ -206,10 +206,15 @@ impl Predicate {
             }
 
             // Exprs
+            //
+            // Use `IS NOT TRUE` rather than `NOT` so that rows where the column
+            // is NULL (e.g. a series without the tag) are retained rather than
+            // deleted.
             for exp in &pred.exprs {
+                let not_expr = Expr::IsNotTrue(Box::new(exp.clone()));
                 match expr {
-                    None => expr = Some(exp.clone().not()),
-                    Some(e) => expr = Some(e.or(exp.clone().not())),
+                    None => expr = Some(not_expr),
+                    Some(e) => expr = Some(e.or(not_expr)),
                 }
             }
 
@@ -739,6 +744,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_negated_expr_retains_null() {
+        let delete = Predicate::new()
+            .with_range(10, 20)
+            .with_expr(col("tag").eq(lit("A")));
+
+        let expr = Predicate::negated_expr(&[Arc::new(delete)]).unwrap();
+        assert_eq!(
+            expr,
+            col("time")
+                .lt(lit_timestamp_nano(10))
+                .or(col("time").gt(lit_timestamp_nano(20)))
+                .or(Expr::IsNotTrue(Box::new(col("tag").eq(lit("A")))))
+        );
+    }
+
     #[test]
     fn test_clear_timestamp_if_max_range_out_of_range() {
         let p = Predicate::new()
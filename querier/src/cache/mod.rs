@@ -11,6 +11,7 @@ use tokio::runtime::Handle;
 use self::{
     namespace::NamespaceCache, object_store::ObjectStoreCache, parquet_file::ParquetFileCache,
     partition::PartitionCache, projected_schema::ProjectedSchemaCache, ram::RamSize,
+    tombstone::TombstoneCache,
 };
 
 pub mod namespace;
@@ -19,6 +20,7 @@ pub mod parquet_file;
 pub mod partition;
 pub mod projected_schema;
 mod ram;
+pub mod tombstone;
 
 #[cfg(test)]
 mod test_util;
@@ -38,6 +40,9 @@ pub struct CatalogCache {
     /// Parquet file cache
     parquet_file_cache: ParquetFileCache,
 
+    /// Tombstone cache.
+    tombstone_cache: TombstoneCache,
+
     /// Projected schema cache.
     projected_schema_cache: ProjectedSchemaCache,
 
@@ -145,6 +150,14 @@ impl CatalogCache {
             Arc::clone(&ram_pool_metadata),
             testing,
         );
+        let tombstone_cache = TombstoneCache::new(
+            Arc::clone(&catalog),
+            backoff_config.clone(),
+            Arc::clone(&time_provider),
+            &metric_registry,
+            Arc::clone(&ram_pool_metadata),
+            testing,
+        );
         let projected_schema_cache = ProjectedSchemaCache::new(
             Arc::clone(&time_provider),
             &metric_registry,
@@ -165,6 +178,7 @@ impl CatalogCache {
             partition_cache,
             namespace_cache,
             parquet_file_cache,
+            tombstone_cache,
             projected_schema_cache,
             object_store_cache,
             metric_registry,
@@ -202,6 +216,11 @@ impl CatalogCache {
         &self.parquet_file_cache
     }
 
+    /// Tombstone cache.
+    pub(crate) fn tombstone(&self) -> &TombstoneCache {
+        &self.tombstone_cache
+    }
+
     /// Projected schema cache.
     pub(crate) fn projected_schema(&self) -> &ProjectedSchemaCache {
         &self.projected_schema_cache
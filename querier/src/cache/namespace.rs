@@ -16,6 +16,7 @@ use cache_system::{
 use data_types::{ColumnId, NamespaceId, NamespaceSchema, TableId, TableSchema};
 use iox_catalog::interface::{get_schema_by_name, Catalog, SoftDeletedRows};
 use iox_time::TimeProvider;
+use parking_lot::Mutex;
 use schema::Schema;
 use std::{
     collections::{HashMap, HashSet},
@@ -70,6 +71,12 @@ type CacheT = Box<
 pub struct NamespaceCache {
     cache: CacheT,
     remove_if_handle: RemoveIfHandle<Arc<str>, Option<Arc<CachedNamespace>>>,
+
+    /// Column IDs per namespace that are referenced by a query but were missing from a freshly
+    /// loaded schema, i.e. columns that were deleted from the catalog.
+    ///
+    /// These never cause a refresh again, since reloading the schema would not yield them.
+    deleted_columns: Mutex<HashMap<Arc<str>, HashSet<ColumnId>>>,
 }
 
 impl NamespaceCache {
@@ -169,6 +176,7 @@ impl NamespaceCache {
         Self {
             cache,
             remove_if_handle,
+            deleted_columns: Default::default(),
         }
     }
 
@@ -176,23 +184,30 @@ impl NamespaceCache {
     ///
     /// Expire namespace if the cached schema does NOT cover the given set of columns. The set is given as a list of
     /// pairs of table name and column set.
+    ///
+    /// Columns that are still not covered by the reloaded schema were deleted from the catalog. They are remembered
+    /// and do not cause further refreshes.
     pub async fn get(
         &self,
         name: Arc<str>,
         should_cover: &[(&str, &HashSet<ColumnId>)],
         span: Option<Span>,
     ) -> Option<Arc<CachedNamespace>> {
-        self.remove_if_handle
+        let namespace = self
+            .remove_if_handle
             .remove_if_and_get(
                 &self.cache,
-                name,
+                Arc::clone(&name),
                 |cached_namespace| {
                     if let Some(namespace) = cached_namespace.as_ref() {
+                        let deleted_columns = self.deleted_columns.lock();
+                        let deleted = deleted_columns.get(&name);
                         should_cover.iter().any(|(table_name, columns)| {
                             if let Some(table) = namespace.tables.get(*table_name) {
-                                columns
-                                    .iter()
-                                    .any(|col| !table.column_id_map.contains_key(col))
+                                columns.iter().any(|col| {
+                                    !table.column_id_map.contains_key(col)
+                                        && !deleted.map(|d| d.contains(col)).unwrap_or_default()
+                                })
                             } else {
                                 // table unknown => need to update
                                 true
@@ -205,7 +220,38 @@ impl NamespaceCache {
                 },
                 ((), span),
             )
-            .await
+            .await;
+
+        if let Some(namespace) = namespace.as_ref() {
+            let mut missing = should_cover
+                .iter()
+                .filter_map(|(table_name, columns)| {
+                    namespace.tables.get(*table_name).map(|table| {
+                        columns
+                            .iter()
+                            .filter(|col| !table.column_id_map.contains_key(col))
+                    })
+                })
+                .flatten()
+                .peekable();
+            if missing.peek().is_some() {
+                self.deleted_columns
+                    .lock()
+                    .entry(name)
+                    .or_default()
+                    .extend(missing);
+            }
+        }
+
+        namespace
+    }
+
+    /// Expire the cached schema of the namespace `name`, if any, so that it is
+    /// reloaded from the catalog on next access.
+    ///
+    /// Returns true if an entry was removed.
+    pub fn expire(&self, name: &str) -> bool {
+        self.remove_if_handle.remove_if(&Arc::from(name), |_| true)
     }
 }
 
@@ -569,4 +615,113 @@ mod tests {
             .is_some());
         assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 6);
     }
+
+    #[tokio::test]
+    async fn test_deleted_columns() {
+        let catalog = TestCatalog::new();
+
+        let cache = NamespaceCache::new(
+            catalog.catalog(),
+            BackoffConfig::default(),
+            catalog.time_provider(),
+            &catalog.metric_registry(),
+            test_ram_pool(),
+            &Handle::current(),
+            true,
+        );
+
+        let ns1 = catalog.create_namespace_1hr_retention("ns1").await;
+        let t1 = ns1.create_table("t1").await;
+        let c1 = t1.create_column("c1", ColumnType::Bool).await;
+        let c2 = t1.create_column("c2", ColumnType::Bool).await;
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .columns()
+            .soft_delete(c2.column.id)
+            .await
+            .unwrap();
+
+        let columns = HashSet::from([c1.column.id, c2.column.id]);
+        let cached = cache
+            .get(Arc::from("ns1"), &[("t1", &columns)], None)
+            .await
+            .unwrap();
+        let table = cached.tables.get("t1").unwrap();
+        assert!(table.column_id_map.contains_key(&c1.column.id));
+        assert!(!table.column_id_map.contains_key(&c2.column.id));
+        assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 1);
+
+        // The first reload did not yield the deleted column, so it does not
+        // cause another refresh.
+        cache
+            .get(Arc::from("ns1"), &[("t1", &columns)], None)
+            .await
+            .unwrap();
+        assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 1);
+
+        // Unknown columns still cause a refresh.
+        let c3 = t1.create_column("c3", ColumnType::Bool).await;
+        let cached = cache
+            .get(
+                Arc::from("ns1"),
+                &[("t1", &HashSet::from([c2.column.id, c3.column.id]))],
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(cached
+            .tables
+            .get("t1")
+            .unwrap()
+            .column_id_map
+            .contains_key(&c3.column.id));
+        assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 2);
+    }
+
+    #[tokio::test]
+    async fn test_expire() {
+        let catalog = TestCatalog::new();
+
+        let cache = NamespaceCache::new(
+            catalog.catalog(),
+            BackoffConfig::default(),
+            catalog.time_provider(),
+            &catalog.metric_registry(),
+            test_ram_pool(),
+            &Handle::current(),
+            true,
+        );
+
+        // Nothing to expire.
+        assert!(!cache.expire("ns1"));
+
+        let ns1 = catalog.create_namespace_1hr_retention("ns1").await;
+        let t1 = ns1.create_table("t1").await;
+
+        let cached = cache.get(Arc::from("ns1"), &[], None).await.unwrap();
+        assert!(cached.tables.contains_key("t1"));
+        assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 1);
+
+        // Rename the table in the catalog - the cached schema is stale.
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .tables()
+            .rename(t1.table.id, "t2")
+            .await
+            .unwrap();
+        let cached = cache.get(Arc::from("ns1"), &[], None).await.unwrap();
+        assert!(cached.tables.contains_key("t1"));
+        assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 1);
+
+        // Expiring the namespace causes it to be reloaded.
+        assert!(cache.expire("ns1"));
+        let cached = cache.get(Arc::from("ns1"), &[], None).await.unwrap();
+        assert!(!cached.tables.contains_key("t1"));
+        assert_eq!(cached.tables.get("t2").unwrap().id, t1.table.id);
+        assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 2);
+    }
 }
@@ -0,0 +1,429 @@
+//! Tombstone cache
+
+use backoff::{Backoff, BackoffConfig};
+use cache_system::{
+    backend::policy::{
+        lru::{LruPolicy, ResourcePool},
+        remove_if::{RemoveIfHandle, RemoveIfPolicy},
+        ttl::{TtlPolicy, TtlProvider},
+        PolicyBackend,
+    },
+    cache::{driver::CacheDriver, metrics::CacheWithMetrics, Cache},
+    loader::{metrics::MetricsLoader, FunctionLoader},
+    resource_consumption::FunctionEstimator,
+};
+use data_types::{DeletePredicate, SequenceNumber, TableId, Tombstone};
+use iox_catalog::interface::Catalog;
+use iox_time::TimeProvider;
+use observability_deps::tracing::warn;
+use predicate::delete_predicate::parse_tombstone_predicate;
+use snafu::{ResultExt, Snafu};
+use std::{mem, sync::Arc, time::Duration};
+use trace::span::Span;
+
+use super::ram::RamSize;
+
+/// Duration to keep the tombstones of a table.
+///
+/// Deletes are recorded by the router, so the querier has no way of knowing
+/// when a new tombstone is added unless a parquet file written after it is
+/// seen (see [`TombstoneCache::get`]). This bounds the time it takes for a
+/// delete to become visible to queries otherwise.
+pub const TTL: Duration = Duration::from_secs(60);
+
+const CACHE_ID: &str = "tombstone";
+
+#[derive(Debug, Snafu)]
+#[allow(missing_copy_implementations, missing_docs)]
+pub enum Error {
+    #[snafu(display("CatalogError refreshing tombstone cache: {}", source))]
+    Catalog {
+        source: iox_catalog::interface::Error,
+    },
+}
+
+/// Holds the delete predicates of all tombstones of a table.
+#[derive(Debug)]
+pub struct CachedTombstones {
+    /// Delete predicates with the sequence number of their tombstone, ordered by sequence number.
+    pub predicates: Arc<Vec<(SequenceNumber, Arc<DeletePredicate>)>>,
+
+    /// Sequence number of the most recent tombstone of the table, including
+    /// tombstones with an invalid predicate, or 0 if there are none.
+    max_sequence_number: SequenceNumber,
+}
+
+impl CachedTombstones {
+    fn new(mut tombstones: Vec<Tombstone>) -> Self {
+        tombstones.sort_by_key(|tombstone| tombstone.sequence_number);
+        let max_sequence_number = tombstones
+            .last()
+            .map(|tombstone| tombstone.sequence_number)
+            .unwrap_or_else(|| SequenceNumber::new(0));
+
+        let predicates: Vec<_> = tombstones
+            .into_iter()
+            .filter_map(|tombstone| match parse_tombstone_predicate(&tombstone) {
+                Ok(predicate) => Some((tombstone.sequence_number, Arc::new(predicate))),
+                Err(e) => {
+                    warn!(
+                        tombstone_id=%tombstone.id,
+                        table_id=%tombstone.table_id,
+                        %e,
+                        "ignoring tombstone with invalid predicate",
+                    );
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            predicates: Arc::new(predicates),
+            max_sequence_number,
+        }
+    }
+
+    /// Delete predicates that apply to a parquet file with the given
+    /// [`max_tombstone_sequence_number`](data_types::ParquetFile::max_tombstone_sequence_number).
+    ///
+    /// The tombstones the file was written after were already applied by the
+    /// ingester or compactor that wrote it.
+    pub fn predicates_for(
+        &self,
+        max_tombstone_sequence_number: SequenceNumber,
+    ) -> impl Iterator<Item = &Arc<DeletePredicate>> + '_ {
+        self.predicates
+            .iter()
+            .filter(move |(sequence_number, _)| *sequence_number > max_tombstone_sequence_number)
+            .map(|(_, predicate)| predicate)
+    }
+
+    /// Estimate the memory consumption of this object and its contents
+    fn size(&self) -> usize {
+        mem::size_of_val(self)
+            + mem::size_of_val(self.predicates.as_ref())
+            + self.predicates.capacity() * mem::size_of::<(SequenceNumber, Arc<DeletePredicate>)>()
+            + self.predicates.iter().map(|(_, p)| p.size()).sum::<usize>()
+    }
+}
+
+/// Expire every cached entry after [`TTL`].
+#[derive(Debug)]
+struct TombstoneTtlProvider;
+
+impl TtlProvider for TombstoneTtlProvider {
+    type K = TableId;
+    type V = Arc<CachedTombstones>;
+
+    fn expires_in(&self, _k: &Self::K, _v: &Self::V) -> Option<Duration> {
+        Some(TTL)
+    }
+}
+
+type CacheT = Box<
+    dyn Cache<
+        K = TableId,
+        V = Arc<CachedTombstones>,
+        GetExtra = ((), Option<Span>),
+        PeekExtra = ((), Option<Span>),
+    >,
+>;
+
+/// Cache for the tombstones (predicate deletes) of a table.
+#[derive(Debug)]
+pub struct TombstoneCache {
+    cache: CacheT,
+
+    /// Handle that allows clearing entries for existing cache entries
+    remove_if_handle: RemoveIfHandle<TableId, Arc<CachedTombstones>>,
+}
+
+impl TombstoneCache {
+    /// Create new empty cache.
+    pub fn new(
+        catalog: Arc<dyn Catalog>,
+        backoff_config: BackoffConfig,
+        time_provider: Arc<dyn TimeProvider>,
+        metric_registry: &metric::Registry,
+        ram_pool: Arc<ResourcePool<RamSize>>,
+        testing: bool,
+    ) -> Self {
+        let loader = FunctionLoader::new(move |table_id: TableId, _extra: ()| {
+            let catalog = Arc::clone(&catalog);
+            let backoff_config = backoff_config.clone();
+
+            async move {
+                Backoff::new(&backoff_config)
+                    .retry_all_errors("get tombstones", || async {
+                        let mut repos = catalog.repositories().await;
+                        let tombstones = repos
+                            .tombstones()
+                            .list_by_table(table_id)
+                            .await
+                            .context(CatalogSnafu)?;
+
+                        Ok(Arc::new(CachedTombstones::new(tombstones)))
+                            as std::result::Result<_, Error>
+                    })
+                    .await
+                    .expect("retry forever")
+            }
+        });
+        let loader = Arc::new(MetricsLoader::new(
+            loader,
+            CACHE_ID,
+            Arc::clone(&time_provider),
+            metric_registry,
+            testing,
+        ));
+
+        let mut backend = PolicyBackend::hashmap_backed(Arc::clone(&time_provider));
+        backend.add_policy(TtlPolicy::new(
+            Arc::new(TombstoneTtlProvider),
+            CACHE_ID,
+            metric_registry,
+        ));
+        let (policy_constructor, remove_if_handle) =
+            RemoveIfPolicy::create_constructor_and_handle(CACHE_ID, metric_registry);
+        backend.add_policy(policy_constructor);
+        backend.add_policy(LruPolicy::new(
+            Arc::clone(&ram_pool),
+            CACHE_ID,
+            Arc::new(FunctionEstimator::new(
+                |k: &TableId, v: &Arc<CachedTombstones>| {
+                    RamSize(mem::size_of_val(k) + mem::size_of_val(v) + v.size())
+                },
+            )),
+        ));
+
+        let cache = CacheDriver::new(loader, backend);
+        let cache = Box::new(CacheWithMetrics::new(
+            cache,
+            CACHE_ID,
+            time_provider,
+            metric_registry,
+        ));
+
+        Self {
+            cache,
+            remove_if_handle,
+        }
+    }
+
+    /// Get the delete predicates of all tombstones of the given table.
+    ///
+    /// `max_tombstone_sequence_number` is the highest
+    /// [`max_tombstone_sequence_number`](data_types::ParquetFile::max_tombstone_sequence_number)
+    /// of the parquet files of the table the caller knows about. A file can
+    /// only have been written after a tombstone that exists, so the cached
+    /// entry is refreshed if it does not contain a tombstone with at least
+    /// this sequence number.
+    pub async fn get(
+        &self,
+        table_id: TableId,
+        max_tombstone_sequence_number: Option<SequenceNumber>,
+        span: Option<Span>,
+    ) -> Arc<CachedTombstones> {
+        self.remove_if_handle
+            .remove_if_and_get(
+                &self.cache,
+                table_id,
+                |cached| {
+                    // If a file was written after a tombstone we don't know about, we need to
+                    // refresh.
+                    max_tombstone_sequence_number
+                        .map_or(false, |file_max| cached.max_sequence_number < file_max)
+                },
+                ((), span),
+            )
+            .await
+    }
+
+    /// Mark the entry for `table_id` as expired, so that newly recorded
+    /// tombstones are loaded on the next [`get`](Self::get).
+    pub fn expire(&self, table_id: TableId) {
+        self.remove_if_handle.remove_if(&table_id, |_| true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_types::{DeleteExpr, Op, Scalar, Timestamp, TimestampRange};
+    use iox_tests::{TestCatalog, TestTable};
+
+    use crate::cache::{ram::test_util::test_ram_pool, test_util::assert_histogram_metric_count};
+
+    const METRIC_NAME: &str = "tombstone_list_by_table";
+
+    #[tokio::test]
+    async fn test_tombstones() {
+        let (catalog, table) = make_catalog().await;
+        create_tombstone(&catalog, &table, 1, 10, r#""tag1"='A'"#).await;
+        create_tombstone(&catalog, &table, 20, 30, "").await;
+
+        let cache = make_cache(&catalog);
+        let cached = cache.get(table.table.id, None, None).await;
+        assert_eq!(
+            cached
+                .predicates_for(SequenceNumber::new(0))
+                .cloned()
+                .collect::<Vec<_>>(),
+            vec![
+                Arc::new(DeletePredicate {
+                    range: TimestampRange::new(1, 10),
+                    exprs: vec![DeleteExpr::new(
+                        "tag1".to_string(),
+                        Op::Eq,
+                        Scalar::String("A".to_string())
+                    )],
+                }),
+                Arc::new(DeletePredicate {
+                    range: TimestampRange::new(20, 30),
+                    exprs: vec![],
+                }),
+            ]
+        );
+
+        // validate a second request doesn't result in a catalog request
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+        cache.get(table.table.id, None, None).await;
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+    }
+
+    #[tokio::test]
+    async fn test_predicates_for_file() {
+        let (catalog, table) = make_catalog().await;
+        let t1 = create_tombstone(&catalog, &table, 1, 10, "").await;
+        let t2 = create_tombstone(&catalog, &table, 20, 30, "").await;
+
+        let cache = make_cache(&catalog);
+        let cached = cache.get(table.table.id, None, None).await;
+
+        let t2_predicate = Arc::new(DeletePredicate {
+            range: TimestampRange::new(20, 30),
+            exprs: vec![],
+        });
+        assert_eq!(cached.predicates_for(SequenceNumber::new(0)).count(), 2);
+        assert_eq!(
+            cached
+                .predicates_for(t1.sequence_number)
+                .cloned()
+                .collect::<Vec<_>>(),
+            vec![t2_predicate]
+        );
+        assert_eq!(cached.predicates_for(t2.sequence_number).count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_expire_on_newer_file() {
+        let (catalog, table) = make_catalog().await;
+        let cache = make_cache(&catalog);
+
+        let cached = cache.get(table.table.id, None, None).await;
+        assert!(cached.predicates.is_empty());
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        // files written before any tombstone don't cause a refresh
+        let cached = cache
+            .get(table.table.id, Some(SequenceNumber::new(0)), None)
+            .await;
+        assert!(cached.predicates.is_empty());
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        // a file written after a tombstone that isn't cached causes a refresh
+        let t1 = create_tombstone(&catalog, &table, 1, 10, "").await;
+        let cached = cache
+            .get(table.table.id, Some(t1.sequence_number), None)
+            .await;
+        assert_eq!(cached.predicates.len(), 1);
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 2);
+
+        // ... but only once
+        let cached = cache
+            .get(table.table.id, Some(t1.sequence_number), None)
+            .await;
+        assert_eq!(cached.predicates.len(), 1);
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_predicate_is_ignored() {
+        let (catalog, table) = make_catalog().await;
+        create_tombstone(&catalog, &table, 1, 10, "tag1 > 1").await;
+        create_tombstone(&catalog, &table, 1, 10, "").await;
+
+        let cache = make_cache(&catalog);
+        let cached = cache.get(table.table.id, None, None).await;
+        assert_eq!(cached.predicates.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expiration() {
+        let (catalog, table) = make_catalog().await;
+        let cache = make_cache(&catalog);
+
+        let cached = cache.get(table.table.id, None, None).await;
+        assert!(cached.predicates.is_empty());
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        // new tombstones are not visible until the entry expires
+        create_tombstone(&catalog, &table, 1, 10, "").await;
+        let cached = cache.get(table.table.id, None, None).await;
+        assert!(cached.predicates.is_empty());
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        cache.expire(table.table.id);
+        let cached = cache.get(table.table.id, None, None).await;
+        assert_eq!(cached.predicates.len(), 1);
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 2);
+
+        create_tombstone(&catalog, &table, 20, 30, "").await;
+        catalog.mock_time_provider().inc(TTL);
+        let cached = cache.get(table.table.id, None, None).await;
+        assert_eq!(cached.predicates.len(), 2);
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 3);
+    }
+
+    async fn make_catalog() -> (Arc<TestCatalog>, Arc<TestTable>) {
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let table = ns.create_table("table1").await;
+        (catalog, table)
+    }
+
+    async fn create_tombstone(
+        catalog: &TestCatalog,
+        table: &TestTable,
+        min_time: i64,
+        max_time: i64,
+        predicate: &str,
+    ) -> Tombstone {
+        let shard = table.namespace.create_shard(1).await;
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table.table.id,
+                shard.shard.id,
+                Timestamp::new(min_time),
+                Timestamp::new(max_time),
+                predicate,
+            )
+            .await
+            .unwrap()
+    }
+
+    fn make_cache(catalog: &TestCatalog) -> TombstoneCache {
+        TombstoneCache::new(
+            catalog.catalog(),
+            BackoffConfig::default(),
+            catalog.time_provider(),
+            &catalog.metric_registry(),
+            test_ram_pool(),
+            true,
+        )
+    }
+}
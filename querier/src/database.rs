@@ -1,18 +1,29 @@
 //! Database for the querier that contains all namespaces.
 
 use crate::{
-    cache::CatalogCache, ingester::IngesterConnection, namespace::QuerierNamespace,
-    parquet::ChunkAdapter, query_log::QueryLog, table::PruneMetrics,
+    cache::CatalogCache,
+    ingester::IngesterConnection,
+    namespace::{QuerierCatalogProvider, QuerierNamespace},
+    parquet::ChunkAdapter,
+    query_log::QueryLog,
+    table::PruneMetrics,
 };
 use async_trait::async_trait;
 use backoff::{Backoff, BackoffConfig};
 use data_types::Namespace;
 use iox_catalog::interface::SoftDeletedRows;
-use iox_query::exec::Executor;
+use iox_query::{
+    exec::{Executor, ExecutorType, IOxSessionContext},
+    QueryNamespaceFilter,
+};
+use observability_deps::tracing::debug;
 use service_common::QueryNamespaceProvider;
 use snafu::Snafu;
 use std::{collections::HashMap, sync::Arc};
-use trace::span::{Span, SpanRecorder};
+use trace::{
+    ctx::SpanContext,
+    span::{Span, SpanRecorder},
+};
 use tracker::{
     AsyncSemaphoreMetrics, InstrumentedAsyncOwnedSemaphorePermit, InstrumentedAsyncSemaphore,
 };
@@ -80,6 +91,29 @@ impl QueryNamespaceProvider for QuerierDatabase {
         self.namespace(name, span).await
     }
 
+    fn new_query_context_without_namespace(
+        &self,
+        span_ctx: Option<SpanContext>,
+        filter: Arc<dyn QueryNamespaceFilter>,
+    ) -> IOxSessionContext {
+        let catalog = QuerierCatalogProvider::without_namespace(
+            self.catalog_cache.catalog(),
+            Arc::clone(&self.query_log),
+        );
+        let mut cfg = self
+            .exec
+            .new_execution_config(ExecutorType::Query)
+            .with_default_catalog(Arc::new(catalog) as _)
+            .with_namespace_filter(filter)
+            .with_span_context(span_ctx);
+
+        for (k, v) in self.datafusion_config.as_ref() {
+            cfg = cfg.with_config_option(k, v);
+        }
+
+        cfg.build()
+    }
+
     async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit {
         Arc::clone(&self.query_execution_semaphore)
             .acquire_owned(span)
@@ -171,6 +205,14 @@ impl QuerierDatabase {
         )))
     }
 
+    /// Expire the cached schema of the namespace `name`, so that subsequent
+    /// queries observe the current catalog state.
+    pub fn expire_namespace(&self, name: &str) {
+        if self.catalog_cache.namespace().expire(name) {
+            debug!(namespace = name, "expired namespace schema from cache");
+        }
+    }
+
     /// Return all namespaces this querier knows about
     pub async fn namespaces(&self) -> Vec<Namespace> {
         let catalog = &self.catalog_cache.catalog();
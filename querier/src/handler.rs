@@ -22,7 +22,10 @@ use thiserror::Error;
 use tokio::task::{JoinError, JoinHandle};
 use tokio_util::sync::CancellationToken;
 
-use crate::{database::QuerierDatabase, poison::PoisonCabinet};
+use crate::{
+    database::QuerierDatabase, poison::PoisonCabinet,
+    schema_service::CacheInvalidatingSchemaService,
+};
 
 #[derive(Debug, Error)]
 #[allow(missing_copy_implementations, missing_docs)]
@@ -32,7 +35,7 @@ pub enum Error {}
 #[async_trait]
 pub trait QuerierHandler: Send + Sync {
     /// Acquire a [`SchemaServiceServer`] gRPC service implementation.
-    fn schema_service(&self) -> SchemaServiceServer<SchemaService>;
+    fn schema_service(&self) -> SchemaServiceServer<CacheInvalidatingSchemaService<SchemaService>>;
 
     /// Acquire a [`CatalogServiceServer`] gRPC service implementation.
     fn catalog_service(&self) -> CatalogServiceServer<CatalogService>;
@@ -105,8 +108,11 @@ impl QuerierHandlerImpl {
 
 #[async_trait]
 impl QuerierHandler for QuerierHandlerImpl {
-    fn schema_service(&self) -> SchemaServiceServer<SchemaService> {
-        SchemaServiceServer::new(SchemaService::new(Arc::clone(&self.catalog)))
+    fn schema_service(&self) -> SchemaServiceServer<CacheInvalidatingSchemaService<SchemaService>> {
+        SchemaServiceServer::new(CacheInvalidatingSchemaService::new(
+            SchemaService::new(Arc::clone(&self.catalog)),
+            Arc::clone(&self.database),
+        ))
     }
 
     fn catalog_service(&self) -> CatalogServiceServer<CatalogService> {
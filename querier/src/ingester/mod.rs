@@ -18,7 +18,13 @@ use data_types::{
 };
 use datafusion::error::DataFusionError;
 use futures::{stream::FuturesUnordered, TryStreamExt};
-use generated_types::ingester::{encode_proto_predicate_as_base64, IngesterQueryRequest};
+use generated_types::{
+    influxdata::iox::{
+        delete::v1::DeletePayload,
+        ingester::v1::{write_service_client::WriteServiceClient, DeleteRequest},
+    },
+    ingester::{encode_proto_predicate_as_base64, IngesterQueryRequest},
+};
 use influxdb_iox_client::flight::generated_types::IngesterQueryResponseMetadata;
 use iox_query::{
     exec::{stringset::StringSet, IOxSessionContext},
@@ -92,6 +98,12 @@ pub enum Error {
         source: connection::Error,
     },
 
+    #[snafu(display("Failed ingester delete '{}': {}", ingester_address, source))]
+    RemoteDelete {
+        ingester_address: String,
+        source: tonic::Status,
+    },
+
     #[snafu(display(
         "Error retrieving write info from '{}' for write token '{}': {}",
         ingester_address,
@@ -196,6 +208,10 @@ pub trait IngesterConnection: std::fmt::Debug + Send + Sync + 'static {
         span: Option<Span>,
     ) -> Result<Vec<IngesterPartition>>;
 
+    /// Apply deletes, already recorded in the catalog as tombstones, to the data buffered by all
+    /// ingester(s).
+    async fn delete(&self, payloads: Vec<DeletePayload>, span: Option<Span>) -> Result<()>;
+
     /// Return backend as [`Any`] which can be used to downcast to a specific implementation.
     fn as_any(&self) -> &dyn Any;
 }
@@ -860,6 +876,47 @@ impl IngesterConnection for IngesterConnectionImpl {
         Ok(ingester_partitions)
     }
 
+    /// Send the deletes to every ingester, one after the other so that each ingester applies
+    /// them in tombstone order.
+    async fn delete(&self, payloads: Vec<DeletePayload>, span: Option<Span>) -> Result<()> {
+        let mut span_recorder = SpanRecorder::new(span);
+
+        let res = self
+            .unique_ingester_addresses
+            .iter()
+            .map(|ingester_address| {
+                let payloads = payloads.clone();
+                async move {
+                    let ingester_address = ingester_address.as_ref();
+                    let connection = connection::Builder::new()
+                        .build(ingester_address)
+                        .await
+                        .context(ConnectingSnafu { ingester_address })?;
+                    let mut client = WriteServiceClient::new(connection.into_grpc_connection());
+
+                    for payload in payloads {
+                        client
+                            .delete(DeleteRequest {
+                                payload: Some(payload),
+                            })
+                            .await
+                            .context(RemoteDeleteSnafu { ingester_address })?;
+                    }
+
+                    Ok(())
+                }
+            })
+            .collect::<FuturesUnordered<_>>()
+            .try_collect::<Vec<()>>()
+            .await;
+
+        match &res {
+            Ok(_) => span_recorder.ok("done"),
+            Err(_) => span_recorder.error("failed"),
+        }
+        res.map(|_| ())
+    }
+
     fn as_any(&self) -> &dyn Any {
         self as &dyn Any
     }
@@ -2,6 +2,7 @@ use super::IngesterConnection;
 use crate::cache::namespace::CachedTable;
 use async_trait::async_trait;
 use data_types::NamespaceId;
+use generated_types::influxdata::iox::delete::v1::DeletePayload;
 use iox_query::util::create_basic_summary;
 use parking_lot::Mutex;
 use schema::{Projection, Schema as IOxSchema};
@@ -12,6 +13,7 @@ use trace::span::Span;
 #[derive(Debug, Default)]
 pub struct MockIngesterConnection {
     next_response: Mutex<Option<super::Result<Vec<super::IngesterPartition>>>>,
+    deletes: Mutex<Vec<DeletePayload>>,
 }
 
 impl MockIngesterConnection {
@@ -25,6 +27,12 @@ impl MockIngesterConnection {
     pub fn next_response(&self, response: super::Result<Vec<super::IngesterPartition>>) {
         *self.next_response.lock() = Some(response);
     }
+
+    /// Deletes sent to this connection.
+    #[allow(dead_code)]
+    pub fn deletes(&self) -> Vec<DeletePayload> {
+        self.deletes.lock().clone()
+    }
 }
 
 #[async_trait]
@@ -111,6 +119,11 @@ impl IngesterConnection for MockIngesterConnection {
         Ok(partitions)
     }
 
+    async fn delete(&self, payloads: Vec<DeletePayload>, _span: Option<Span>) -> super::Result<()> {
+        self.deletes.lock().extend(payloads);
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn Any {
         self as &dyn Any
     }
@@ -19,6 +19,7 @@ mod namespace;
 mod parquet;
 mod poison;
 mod query_log;
+mod schema_service;
 mod server;
 mod system_tables;
 mod table;
@@ -35,4 +36,5 @@ pub use ingester::{
     Error as IngesterError, IngesterConnection, IngesterConnectionImpl, IngesterPartition,
 };
 pub use namespace::QuerierNamespace;
+pub use schema_service::CacheInvalidatingSchemaService;
 pub use server::QuerierServer;
@@ -12,6 +12,7 @@ use iox_query::exec::Executor;
 use std::{collections::HashMap, sync::Arc};
 
 mod query_access;
+pub(crate) use query_access::QuerierCatalogProvider;
 
 #[cfg(test)]
 mod test_util;
@@ -42,6 +43,9 @@ pub struct QuerierNamespace {
     /// Catalog cache.
     catalog_cache: Arc<CatalogCache>,
 
+    /// Connection to ingester(s), used to apply deletes to unpersisted data.
+    ingester_connection: Option<Arc<dyn IngesterConnection>>,
+
     /// Query log.
     query_log: Arc<QueryLog>,
 
@@ -90,6 +94,7 @@ impl QuerierNamespace {
             tables: Arc::new(tables),
             exec,
             catalog_cache: Arc::clone(chunk_adapter.catalog_cache()),
+            ingester_connection,
             query_log,
             datafusion_config,
         }
@@ -1,22 +1,27 @@
 //! This module contains implementations of [`iox_query`] interfaces for [QuerierNamespace].
 
 use crate::{
+    cache::CatalogCache,
+    ingester::IngesterConnection,
     namespace::QuerierNamespace,
     query_log::QueryLog,
     system_tables::{SystemSchemaProvider, SYSTEM_SCHEMA},
     table::QuerierTable,
 };
 use async_trait::async_trait;
-use data_types::NamespaceId;
+use data_types::{DeletePredicate, NamespaceId, Timestamp, TRANSITION_SHARD_ID};
 use datafusion::{
     catalog::{catalog::CatalogProvider, schema::SchemaProvider},
     datasource::TableProvider,
     error::DataFusionError,
 };
 use datafusion_util::config::DEFAULT_SCHEMA;
+use generated_types::influxdata::iox::delete::v1::DeletePayload;
+use iox_catalog::interface::Catalog;
 use iox_query::{
-    exec::{ExecutionContextProvider, ExecutorType, IOxSessionContext},
-    QueryChunk, QueryCompletedToken, QueryNamespace, QueryText,
+    exec::{ExecutionContextProvider, ExecutorType, IOxSessionConfig, IOxSessionContext},
+    QueryChunk, QueryCompletedToken, QueryNamespace, QueryNamespaceDeleter, QueryNamespaceFilter,
+    QueryText,
 };
 use observability_deps::tracing::{debug, trace};
 use predicate::{rpc_predicate::QueryNamespaceMeta, Predicate};
@@ -100,8 +105,11 @@ impl QueryNamespace for QuerierNamespace {
 }
 
 pub struct QuerierCatalogProvider {
-    /// Namespace ID.
-    namespace_id: NamespaceId,
+    /// Namespace ID, or `None` for queries that are not run against a namespace.
+    namespace_id: Option<NamespaceId>,
+
+    /// The catalog, which is used to list the namespaces.
+    catalog: Arc<dyn Catalog>,
 
     /// A snapshot of all tables.
     tables: Arc<HashMap<Arc<str>, Arc<QuerierTable>>>,
@@ -113,11 +121,23 @@ pub struct QuerierCatalogProvider {
 impl QuerierCatalogProvider {
     fn from_namespace(namespace: &QuerierNamespace) -> Self {
         Self {
-            namespace_id: namespace.id,
+            namespace_id: Some(namespace.id),
+            catalog: namespace.catalog_cache.catalog(),
             tables: Arc::clone(&namespace.tables),
             query_log: Arc::clone(&namespace.query_log),
         }
     }
+
+    /// Create a provider for queries that are not run against a namespace,
+    /// which has no tables and only lists the namespaces of the catalog.
+    pub(crate) fn without_namespace(catalog: Arc<dyn Catalog>, query_log: Arc<QueryLog>) -> Self {
+        Self {
+            namespace_id: None,
+            catalog,
+            tables: Default::default(),
+            query_log,
+        }
+    }
 }
 
 impl CatalogProvider for QuerierCatalogProvider {
@@ -135,6 +155,7 @@ impl CatalogProvider for QuerierCatalogProvider {
                 tables: Arc::clone(&self.tables),
             })),
             SYSTEM_SCHEMA => Some(Arc::new(SystemSchemaProvider::new(
+                Arc::clone(&self.catalog),
                 Arc::clone(&self.query_log),
                 self.namespace_id,
             ))),
@@ -184,29 +205,148 @@ impl SchemaProvider for UserSchemaProvider {
     }
 }
 
-impl ExecutionContextProvider for QuerierNamespace {
-    fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
+/// Records the deletes of queries against a [`QuerierNamespace`] as tombstones in the catalog and
+/// applies them to the data buffered by the ingesters.
+#[derive(Debug)]
+struct QuerierNamespaceDeleter {
+    /// ID of the namespace.
+    namespace_id: NamespaceId,
+
+    /// Catalog cache, used to record tombstones and to expire the cached tombstones of a table.
+    catalog_cache: Arc<CatalogCache>,
+
+    /// Connection to ingester(s), used to apply the deletes to unpersisted data.
+    ingester_connection: Option<Arc<dyn IngesterConnection>>,
+
+    /// A snapshot of all tables.
+    tables: Arc<HashMap<Arc<str>, Arc<QuerierTable>>>,
+}
+
+impl QuerierNamespaceDeleter {
+    fn from_namespace(namespace: &QuerierNamespace) -> Self {
+        Self {
+            namespace_id: namespace.id,
+            catalog_cache: Arc::clone(&namespace.catalog_cache),
+            ingester_connection: namespace.ingester_connection.clone(),
+            tables: Arc::clone(&namespace.tables),
+        }
+    }
+}
+
+#[async_trait]
+impl QueryNamespaceDeleter for QuerierNamespaceDeleter {
+    async fn delete(
+        &self,
+        table_names: &[String],
+        predicate: &DeletePredicate,
+    ) -> Result<(), DataFusionError> {
+        let tables: Vec<_> = table_names
+            .iter()
+            .filter_map(|name| self.tables.get(name.as_str()))
+            .collect();
+        if tables.is_empty() {
+            return Ok(());
+        }
+
+        let serialized_predicate = predicate.expr_sql_string();
+        let catalog = self.catalog_cache.catalog();
+        let mut txn = catalog
+            .start_transaction()
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        let mut payloads = Vec::with_capacity(tables.len());
+        for table in &tables {
+            let tombstone = txn
+                .tombstones()
+                .create(
+                    table.id(),
+                    TRANSITION_SHARD_ID,
+                    Timestamp::new(predicate.range.start()),
+                    Timestamp::new(predicate.range.end()),
+                    &serialized_predicate,
+                )
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+            payloads.push(DeletePayload {
+                database_id: self.namespace_id.get(),
+                table_name: table.table_name().to_string(),
+                predicate: Some(predicate.clone().into()),
+                table_id: table.id().get(),
+                tombstone_sequence_number: tombstone.sequence_number.get(),
+            });
+        }
+        txn.commit()
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        // Apply the deletes to the data buffered by the ingesters, so that rows buffered from now
+        // on are not affected by them.
+        if let Some(ingester_connection) = &self.ingester_connection {
+            ingester_connection
+                .delete(payloads, None)
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        }
+
+        // make the deletes visible to subsequent queries of this querier
+        for table in tables {
+            debug!(
+                table_name=%table.table_name(),
+                predicate=%serialized_predicate,
+                "recorded delete"
+            );
+            self.catalog_cache.tombstone().expire(table.id());
+        }
+
+        Ok(())
+    }
+}
+
+impl QuerierNamespace {
+    fn query_config(&self, span_ctx: Option<SpanContext>) -> IOxSessionConfig {
         let mut cfg = self
             .exec
             .new_execution_config(ExecutorType::Query)
             .with_default_catalog(Arc::new(QuerierCatalogProvider::from_namespace(self)) as _)
+            .with_deleter(Arc::new(QuerierNamespaceDeleter::from_namespace(self)) as _)
+            .with_namespace_name(self.name.as_ref())
             .with_span_context(span_ctx);
 
         for (k, v) in self.datafusion_config.as_ref() {
             cfg = cfg.with_config_option(k, v);
         }
 
-        cfg.build()
+        cfg
+    }
+}
+
+impl ExecutionContextProvider for QuerierNamespace {
+    fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
+        self.query_config(span_ctx).build()
+    }
+
+    fn new_query_context_with_filter(
+        &self,
+        span_ctx: Option<SpanContext>,
+        filter: Arc<dyn QueryNamespaceFilter>,
+    ) -> IOxSessionContext {
+        self.query_config(span_ctx)
+            .with_namespace_filter(filter)
+            .build()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::namespace::test_util::{clear_parquet_cache, querier_namespace};
+    use crate::{
+        ingester::test_util::MockIngesterConnection,
+        namespace::test_util::{clear_parquet_cache, querier_namespace},
+    };
     use arrow::record_batch::RecordBatch;
     use arrow_util::test_util::{batches_to_sorted_lines, Normalizer};
-    use data_types::ColumnType;
+    use data_types::{ColumnType, TimestampRange};
     use datafusion::common::DataFusionError;
     use iox_query::frontend::sql::SqlQueryPlanner;
     use iox_tests::{TestCatalog, TestParquetFileBuilder};
@@ -577,6 +717,136 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_system_namespaces() {
+        test_helpers::maybe_start_logging();
+
+        let catalog = TestCatalog::new();
+
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+        catalog
+            .create_namespace_with_retention("db/rp", Some(3_600_000_000_000))
+            .await;
+
+        let querier_namespace = Arc::new(querier_namespace(&ns).await);
+
+        insta::assert_yaml_snapshot!(
+            format_query(&querier_namespace, "SELECT * FROM system.namespaces").await,
+            @r###"
+        ---
+        - +------+---------------------+
+        - "| name | retention_period_ns |"
+        - +------+---------------------+
+        - "| ns   |                     |"
+        - +------+---------------------+
+        "###
+        );
+
+        let ns = catalog
+            .create_namespace_with_retention("db/rp2", Some(3_600_000_000_000))
+            .await;
+        let querier_namespace = Arc::new(querier_namespace(&ns).await);
+
+        insta::assert_yaml_snapshot!(
+            format_query(&querier_namespace, "SELECT * FROM system.namespaces").await,
+            @r###"
+        ---
+        - +--------+---------------------+
+        - "| name   | retention_period_ns |"
+        - +--------+---------------------+
+        - "| db/rp2 | 3600000000000       |"
+        - +--------+---------------------+
+        "###
+        );
+
+        // a filter lists all namespaces it accepts
+        let ctx = querier_namespace
+            .new_query_context_with_filter(None, Arc::new(PrefixFilter("db/")) as _);
+        let physical_plan = SqlQueryPlanner::default()
+            .query("SELECT * FROM system.namespaces", &ctx)
+            .await
+            .unwrap();
+        let results = ctx.collect(physical_plan).await.unwrap();
+        insta::assert_yaml_snapshot!(
+            batches_to_sorted_lines(&results),
+            @r###"
+        ---
+        - +--------+---------------------+
+        - "| name   | retention_period_ns |"
+        - +--------+---------------------+
+        - "| db/rp  | 3600000000000       |"
+        - "| db/rp2 | 3600000000000       |"
+        - +--------+---------------------+
+        "###
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        test_helpers::maybe_start_logging();
+
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let table = ns.create_table("cpu").await;
+        table.create_column("host", ColumnType::Tag).await;
+        table.create_column("time", ColumnType::Time).await;
+
+        let querier_namespace = querier_namespace(&ns).await;
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(1, 10),
+            exprs: vec![],
+        };
+        QuerierNamespaceDeleter::from_namespace(&querier_namespace)
+            .delete(&["cpu".to_string(), "unknown".to_string()], &predicate)
+            .await
+            .unwrap();
+
+        // the delete is recorded as a tombstone
+        let tombstones = catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_table(table.table.id)
+            .await
+            .unwrap();
+        assert_eq!(tombstones.len(), 1);
+
+        // ... and applied to the data buffered by the ingesters
+        let deletes = querier_namespace
+            .ingester_connection
+            .as_ref()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<MockIngesterConnection>()
+            .unwrap()
+            .deletes();
+        assert_eq!(
+            deletes,
+            vec![DeletePayload {
+                database_id: ns.namespace.id.get(),
+                table_name: "cpu".to_string(),
+                predicate: Some(predicate.into()),
+                table_id: table.table.id.get(),
+                tombstone_sequence_number: tombstones[0].sequence_number.get(),
+            }]
+        );
+    }
+
+    /// Accepts the namespaces whose name starts with the prefix.
+    #[derive(Debug)]
+    struct PrefixFilter(&'static str);
+
+    #[async_trait]
+    impl QueryNamespaceFilter for PrefixFilter {
+        async fn filter(&self, namespaces: Vec<String>) -> Result<Vec<String>, DataFusionError> {
+            Ok(namespaces
+                .into_iter()
+                .filter(|ns| ns.starts_with(self.0))
+                .collect())
+        }
+    }
+
     async fn format_query(querier_namespace: &Arc<QuerierNamespace>, sql: &str) -> Vec<String> {
         format_query_with_span_ctx(querier_namespace, sql, None).await
     }
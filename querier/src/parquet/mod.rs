@@ -116,6 +116,14 @@ impl QuerierParquetChunk {
         self.meta.as_ref()
     }
 
+    /// The sequence number of the most recent tombstone already applied to the
+    /// parquet file of this chunk.
+    pub fn max_tombstone_sequence_number(&self) -> SequenceNumber {
+        self.parquet_chunk
+            .parquet_file()
+            .max_tombstone_sequence_number
+    }
+
     /// [`Arc`]ed version of the partition sort key.
     ///
     /// Note that this might NOT be the up-to-date sort key of the partition but the one that existed when the chunk was
@@ -0,0 +1,62 @@
+//! A [`SchemaService`] decorator expiring renamed namespaces from the
+//! querier's catalog cache.
+
+use std::sync::Arc;
+
+use generated_types::influxdata::iox::schema::v1::{schema_service_server::SchemaService, *};
+use tonic::{Request, Response, Status};
+
+use crate::database::QuerierDatabase;
+
+/// A [`SchemaService`] implementation that delegates all calls to `T`,
+/// expiring the cached schema of the affected namespace from the
+/// [`QuerierDatabase`] after a table or column is successfully renamed.
+///
+/// Only the cache of the querier that served the rename request is expired -
+/// other queriers observe the new name once their cached schema is refreshed.
+#[derive(Debug)]
+pub struct CacheInvalidatingSchemaService<T> {
+    inner: T,
+    database: Arc<QuerierDatabase>,
+}
+
+impl<T> CacheInvalidatingSchemaService<T> {
+    /// Decorate `inner`, expiring namespaces from the catalog cache of
+    /// `database` when one of their tables or columns is renamed.
+    pub fn new(inner: T, database: Arc<QuerierDatabase>) -> Self {
+        Self { inner, database }
+    }
+}
+
+#[tonic::async_trait]
+impl<T> SchemaService for CacheInvalidatingSchemaService<T>
+where
+    T: SchemaService,
+{
+    async fn get_schema(
+        &self,
+        request: Request<GetSchemaRequest>,
+    ) -> Result<Response<GetSchemaResponse>, Status> {
+        self.inner.get_schema(request).await
+    }
+
+    async fn rename_table(
+        &self,
+        request: Request<RenameTableRequest>,
+    ) -> Result<Response<RenameTableResponse>, Status> {
+        let namespace = request.get_ref().namespace.clone();
+        let resp = self.inner.rename_table(request).await?;
+        self.database.expire_namespace(&namespace);
+        Ok(resp)
+    }
+
+    async fn rename_column(
+        &self,
+        request: Request<RenameColumnRequest>,
+    ) -> Result<Response<RenameColumnResponse>, Status> {
+        let namespace = request.get_ref().namespace.clone();
+        let resp = self.inner.rename_column(request).await?;
+        self.database.expire_namespace(&namespace);
+        Ok(resp)
+    }
+}
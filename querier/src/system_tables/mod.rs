@@ -15,6 +15,7 @@ use datafusion::{
     },
     prelude::Expr,
 };
+use iox_catalog::interface::Catalog;
 use std::{
     any::Any,
     pin::Pin,
@@ -22,25 +23,44 @@ use std::{
     task::{Context, Poll},
 };
 
+mod namespaces;
 mod queries;
 
 pub const SYSTEM_SCHEMA: &str = "system";
 
+const NAMESPACES_TABLE: &str = "namespaces";
+
 const QUERIES_TABLE: &str = "queries";
 
-const ALL_SYSTEM_TABLES: &[&str] = &[QUERIES_TABLE];
+const ALL_SYSTEM_TABLES: &[&str] = &[NAMESPACES_TABLE, QUERIES_TABLE];
 
 pub struct SystemSchemaProvider {
-    queries: Arc<dyn TableProvider>,
+    namespaces: Arc<dyn TableProvider>,
+    queries: Option<Arc<dyn TableProvider>>,
 }
 
 impl SystemSchemaProvider {
-    pub fn new(query_log: Arc<QueryLog>, namespace_id: NamespaceId) -> Self {
-        let queries = Arc::new(SystemTableProvider {
-            table: Arc::new(queries::QueriesTable::new(query_log, Some(namespace_id))),
+    /// Create the system tables of queries against the namespace `namespace_id`.
+    ///
+    /// Queries that are not run against a namespace only have the
+    /// `namespaces` table, so that they cannot observe the queries of any
+    /// namespace.
+    pub fn new(
+        catalog: Arc<dyn Catalog>,
+        query_log: Arc<QueryLog>,
+        namespace_id: Option<NamespaceId>,
+    ) -> Self {
+        let namespaces = Arc::new(namespaces::NamespacesTable::new(catalog, namespace_id));
+        let queries = namespace_id.map(|namespace_id| {
+            Arc::new(SystemTableProvider {
+                table: Arc::new(queries::QueriesTable::new(query_log, Some(namespace_id))),
+            }) as _
         });
 
-        Self { queries }
+        Self {
+            namespaces,
+            queries,
+        }
     }
 }
 
@@ -53,21 +73,25 @@ impl SchemaProvider for SystemSchemaProvider {
     fn table_names(&self) -> Vec<String> {
         ALL_SYSTEM_TABLES
             .iter()
+            .filter(|&&name| self.table_exist(name))
             .map(|name| name.to_string())
             .collect()
     }
 
     async fn table(&self, name: &str) -> Option<Arc<dyn TableProvider>> {
         match name {
-            QUERIES_TABLE => Some(Arc::clone(&self.queries)),
+            NAMESPACES_TABLE => Some(Arc::clone(&self.namespaces)),
+            QUERIES_TABLE => self.queries.as_ref().map(Arc::clone),
             _ => None,
         }
     }
 
     fn table_exist(&self, name: &str) -> bool {
-        ALL_SYSTEM_TABLES
-            .iter()
-            .any(|&system_table| system_table == name)
+        match name {
+            NAMESPACES_TABLE => true,
+            QUERIES_TABLE => self.queries.is_some(),
+            _ => false,
+        }
     }
 }
 
@@ -0,0 +1,134 @@
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::{Namespace, NamespaceId};
+use datafusion::{
+    datasource::TableProvider,
+    error::{DataFusionError, Result as DataFusionResult},
+    execution::context::SessionState,
+    logical_expr::TableType,
+    physical_plan::{memory::MemoryExec, ExecutionPlan},
+    prelude::Expr,
+};
+use iox_catalog::interface::{Catalog, SoftDeletedRows};
+use iox_query::exec::SessionContextIOxExt;
+use std::{any::Any, collections::HashSet, sync::Arc};
+
+/// Implementation of system.namespaces table
+///
+/// Unlike the other system tables, the contents of this table are not
+/// local to the querier, and are read from the catalog each time the
+/// table is scanned.
+///
+/// The namespaces are listed if the [namespace filter] of the query
+/// accepts them. Queries without a filter only list the namespace being
+/// queried, so that they cannot discover the namespaces of other tenants.
+///
+/// [namespace filter]: iox_query::QueryNamespaceFilter
+pub(super) struct NamespacesTable {
+    schema: SchemaRef,
+    catalog: Arc<dyn Catalog>,
+    namespace_id: Option<NamespaceId>,
+}
+
+impl NamespacesTable {
+    pub(super) fn new(catalog: Arc<dyn Catalog>, namespace_id: Option<NamespaceId>) -> Self {
+        Self {
+            schema: namespaces_schema(),
+            catalog,
+            namespace_id,
+        }
+    }
+}
+
+#[async_trait]
+impl TableProvider for NamespacesTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(
+        &self,
+        ctx: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        let namespaces = match (ctx.namespace_filter(), self.namespace_id) {
+            (Some(filter), _) => {
+                let mut namespaces = self
+                    .catalog
+                    .repositories()
+                    .await
+                    .namespaces()
+                    .list(SoftDeletedRows::ExcludeDeleted)
+                    .await
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+                let visible = filter
+                    .filter(namespaces.iter().map(|ns| ns.name.clone()).collect())
+                    .await?
+                    .into_iter()
+                    .collect::<HashSet<_>>();
+                namespaces.retain(|ns| visible.contains(&ns.name));
+                namespaces.sort_by(|a, b| a.name.cmp(&b.name));
+                namespaces
+            }
+            (None, Some(namespace_id)) => self
+                .catalog
+                .repositories()
+                .await
+                .namespaces()
+                .get_by_id(namespace_id, SoftDeletedRows::ExcludeDeleted)
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?
+                .into_iter()
+                .collect(),
+            (None, None) => vec![],
+        };
+
+        let batch = from_namespaces(self.schema(), &namespaces)?;
+
+        Ok(Arc::new(MemoryExec::try_new(
+            &[vec![batch]],
+            self.schema(),
+            projection.cloned(),
+        )?))
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+}
+
+fn namespaces_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("name", DataType::Utf8, false),
+        Field::new("retention_period_ns", DataType::Int64, true),
+    ]))
+}
+
+fn from_namespaces(schema: SchemaRef, namespaces: &[Namespace]) -> DataFusionResult<RecordBatch> {
+    let name = namespaces
+        .iter()
+        .map(|ns| Some(ns.name.as_str()))
+        .collect::<StringArray>();
+    let retention_period_ns = namespaces
+        .iter()
+        .map(|ns| ns.retention_period_ns)
+        .collect::<Int64Array>();
+
+    Ok(RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(name) as ArrayRef,
+            Arc::new(retention_period_ns) as ArrayRef,
+        ],
+    )?)
+}
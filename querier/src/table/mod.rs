@@ -4,12 +4,12 @@ use crate::{
     parquet::ChunkAdapter,
     IngesterConnection,
 };
-use data_types::{ColumnId, DeletePredicate, NamespaceId, TableId};
+use data_types::{ColumnId, DeletePredicate, NamespaceId, SequenceNumber, TableId};
 use datafusion::error::DataFusionError;
 use futures::join;
 use iox_query::{provider, provider::ChunkPruner, QueryChunk};
 use observability_deps::tracing::{debug, trace};
-use predicate::Predicate;
+use predicate::{delete_predicate::delete_predicate_for_columns, Predicate};
 use schema::Schema;
 use snafu::{ResultExt, Snafu};
 use std::{
@@ -275,6 +275,41 @@ impl QuerierTable {
                 return Ok(vec![]);
             };
 
+        // Tombstones recorded for this table apply to the parquet files written before them, in
+        // addition to the retention delete predicate. The ingesters apply tombstones to the data
+        // they buffer, so only the retention delete predicate applies to ingester chunks.
+        //
+        // NB: Pass the max tombstone sequence number of the files to `get`
+        //     to ensure cache is refreshed if we learned about new tombstones.
+        let max_tombstone_sequence_number = parquet_files
+            .files
+            .iter()
+            .map(|file| file.max_tombstone_sequence_number)
+            .max();
+        let tombstones = catalog_cache
+            .tombstone()
+            .get(
+                self.id(),
+                max_tombstone_sequence_number,
+                span_recorder.child_span("cache GET tombstone"),
+            )
+            .await;
+        let delete_predicates_for = |max_tombstone_sequence_number: SequenceNumber| -> Vec<_> {
+            retention_delete_pred
+                .iter()
+                .cloned()
+                .chain(
+                    tombstones
+                        .predicates_for(max_tombstone_sequence_number)
+                        .filter_map(|pred| {
+                            delete_predicate_for_columns(pred, |column| {
+                                cached_table.schema.find_index_of(column).is_some()
+                            })
+                        }),
+                )
+                .collect()
+        };
+
         let reconciler = Reconciler::new(
             Arc::clone(&self.table_name),
             Arc::clone(&self.namespace_name),
@@ -290,12 +325,19 @@ impl QuerierTable {
                 MetricPruningObserver::new(Arc::clone(&self.prune_metrics)),
                 span_recorder.child_span("new_chunks"),
             )
-            .await;
+            .await
+            .into_iter()
+            .map(|chunk| {
+                let delete_predicates =
+                    delete_predicates_for(chunk.max_tombstone_sequence_number());
+                chunk.with_delete_predicates(delete_predicates)
+            })
+            .collect();
 
         let chunks = reconciler
             .reconcile(
                 partitions,
-                retention_delete_pred,
+                retention_delete_pred.into_iter().collect(),
                 parquet_files,
                 span_recorder.child_span("reconcile"),
             )
@@ -446,15 +488,18 @@ mod tests {
         table::test_util::{querier_table, IngesterPartitionBuilder},
     };
     use arrow_util::assert_batches_eq;
-    use data_types::{ChunkId, ColumnType, SequenceNumber};
+    use data_types::{
+        ChunkId, ColumnType, DeleteExpr, Op, Scalar, SequenceNumber, Timestamp, TimestampRange,
+    };
     use iox_query::exec::IOxSessionContext;
-    use iox_tests::{TestCatalog, TestParquetFileBuilder, TestTable};
+    use iox_tests::{TestCatalog, TestParquetFile, TestParquetFileBuilder, TestTable};
     use iox_time::TimeProvider;
     use predicate::Predicate;
     use schema::{builder::SchemaBuilder, InfluxFieldType};
     use std::sync::Arc;
     use test_helpers::maybe_start_logging;
     use trace::RingBufferTraceCollector;
+    use uuid::Uuid;
 
     #[test]
     fn sum_up_persisted_file_counts() {
@@ -822,6 +867,141 @@ mod tests {
         assert_eq!(chunks.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_tombstones() {
+        maybe_start_logging();
+        let catalog = TestCatalog::new();
+
+        // Namespace with infinite retention policy, so the only delete predicates are tombstones
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+        let table = ns.create_table("cpu").await;
+        let shard = ns.create_shard(1).await;
+        let partition = table.with_shard(&shard).create_partition("k").await;
+
+        table.create_column("host", ColumnType::Tag).await;
+        table.create_column("time", ColumnType::Time).await;
+        table.create_column("load", ColumnType::F64).await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=a load=1 11")
+            .with_max_seq(1)
+            .with_min_time(11)
+            .with_max_time(11);
+        partition.create_parquet_file(builder).await;
+
+        let mut repos = catalog.catalog().repositories().await;
+        for (min_time, max_time, predicate) in [
+            (1, 10, r#""host"='a'"#),
+            // equality on a column that does not exist in the table never matches
+            (1, 20, r#""region"='west'"#),
+            // inequality on a column that does not exist in the table always matches
+            (1, 30, r#""host"='a' AND "region"!='west'"#),
+        ] {
+            repos
+                .tombstones()
+                .create(
+                    table.table.id,
+                    shard.shard.id,
+                    Timestamp::new(min_time),
+                    Timestamp::new(max_time),
+                    predicate,
+                )
+                .await
+                .unwrap();
+        }
+        drop(repos);
+
+        let querier_table = TestQuerierTable::new(&catalog, &table).await;
+        let chunks = querier_table.chunks().await.unwrap();
+        assert_eq!(chunks.len(), 1);
+
+        let host_a = DeleteExpr::new("host".to_string(), Op::Eq, Scalar::String("a".to_string()));
+        assert_eq!(
+            chunks[0].delete_predicates(),
+            &[
+                Arc::new(DeletePredicate {
+                    range: TimestampRange::new(1, 10),
+                    exprs: vec![host_a.clone()],
+                }),
+                Arc::new(DeletePredicate {
+                    range: TimestampRange::new(1, 30),
+                    exprs: vec![host_a],
+                }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tombstones_applied_to_file() {
+        maybe_start_logging();
+        let catalog = TestCatalog::new();
+
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+        let table = ns.create_table("cpu").await;
+        let shard = ns.create_shard(1).await;
+        let partition = table.with_shard(&shard).create_partition("k").await;
+
+        table.create_column("host", ColumnType::Tag).await;
+        table.create_column("time", ColumnType::Time).await;
+        table.create_column("load", ColumnType::F64).await;
+
+        let tombstone = catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table.table.id,
+                shard.shard.id,
+                Timestamp::new(1),
+                Timestamp::new(20),
+                r#""host"='a'"#,
+            )
+            .await
+            .unwrap();
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=a load=1 11")
+            .with_max_seq(1)
+            .with_min_time(11)
+            .with_max_time(11);
+        // the first file was written after the tombstone, which was already applied to it
+        let file1 = partition
+            .create_parquet_file(
+                builder
+                    .clone()
+                    .with_max_tombstone_seq(tombstone.sequence_number.get()),
+            )
+            .await;
+        let file2 = partition.create_parquet_file(builder.with_max_seq(2)).await;
+
+        let querier_table = TestQuerierTable::new(&catalog, &table).await;
+        let chunks = querier_table.chunks().await.unwrap();
+        assert_eq!(chunks.len(), 2);
+
+        let delete_predicates = |file: &TestParquetFile| {
+            let chunk_id = ChunkId::from(Uuid::from_u128(file.parquet_file.id.get() as _));
+            chunks
+                .iter()
+                .find(|c| c.id() == chunk_id)
+                .unwrap()
+                .delete_predicates()
+                .to_vec()
+        };
+        assert_eq!(delete_predicates(&file1), vec![]);
+        assert_eq!(
+            delete_predicates(&file2),
+            vec![Arc::new(DeletePredicate {
+                range: TimestampRange::new(1, 20),
+                exprs: vec![DeleteExpr::new(
+                    "host".to_string(),
+                    Op::Eq,
+                    Scalar::String("a".to_string())
+                )],
+            })]
+        );
+    }
+
     /// Adds a "foo" column to the table and returns the created schema
     async fn make_schema(table: &Arc<TestTable>) -> Schema {
         table.create_column("foo", ColumnType::F64).await;
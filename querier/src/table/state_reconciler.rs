@@ -38,11 +38,15 @@ impl Reconciler {
     }
 
     /// Reconciles ingester state (ingester_partitions) and catalog state (parquet_files),
-    /// producing a list of chunks to query
+    /// producing a list of chunks to query.
+    ///
+    /// The `ingester_delete_predicates` are attached to every ingester chunk. The parquet chunks
+    /// carry their own delete predicates, as tombstones may already be applied to some of the
+    /// files.
     pub(crate) async fn reconcile(
         &self,
         ingester_partitions: Vec<IngesterPartition>,
-        retention_delete_pred: Option<Arc<DeletePredicate>>,
+        ingester_delete_predicates: Vec<Arc<DeletePredicate>>,
         parquet_files: Vec<QuerierParquetChunk>,
         span: Option<Span>,
     ) -> Result<Vec<Arc<dyn QueryChunk>>, ReconcileError> {
@@ -50,12 +54,11 @@ impl Reconciler {
         let mut chunks = self
             .build_chunks_from_parquet(
                 &ingester_partitions,
-                retention_delete_pred.clone(),
                 parquet_files,
                 span_recorder.child_span("build_chunks_from_parquet"),
             )
             .await?;
-        chunks.extend(self.build_ingester_chunks(ingester_partitions, retention_delete_pred));
+        chunks.extend(self.build_ingester_chunks(ingester_partitions, ingester_delete_predicates));
         debug!(num_chunks=%chunks.len(), "Final chunk count after reconcilation");
 
         let chunks = self.sync_partition_sort_keys(chunks);
@@ -71,7 +74,6 @@ impl Reconciler {
     async fn build_chunks_from_parquet(
         &self,
         ingester_partitions: &[IngesterPartition],
-        retention_delete_pred: Option<Arc<DeletePredicate>>,
         parquet_files: Vec<QuerierParquetChunk>,
         _span: Option<Span>,
     ) -> Result<Vec<Box<dyn UpdatableQuerierChunk>>, ReconcileError> {
@@ -87,16 +89,7 @@ impl Reconciler {
         let mut chunks: Vec<Box<dyn UpdatableQuerierChunk>> =
             Vec::with_capacity(parquet_files.len() + ingester_partitions.len());
 
-        let retention_expr_len = usize::from(retention_delete_pred.is_some());
         for chunk in parquet_files.into_iter() {
-            let mut delete_predicates = Vec::with_capacity(retention_expr_len);
-
-            if let Some(retention_delete_pred) = retention_delete_pred.clone() {
-                delete_predicates.push(retention_delete_pred);
-            }
-
-            let chunk = chunk.with_delete_predicates(delete_predicates);
-
             chunks.push(Box::new(chunk) as Box<dyn UpdatableQuerierChunk>);
         }
 
@@ -106,18 +99,16 @@ impl Reconciler {
     fn build_ingester_chunks(
         &self,
         ingester_partitions: Vec<IngesterPartition>,
-        retention_delete_pred: Option<Arc<DeletePredicate>>,
+        delete_predicates: Vec<Arc<DeletePredicate>>,
     ) -> impl Iterator<Item = Box<dyn UpdatableQuerierChunk>> {
         // Add ingester chunks to the overall chunk list.
         // - filter out chunks that don't have any record batches
         ingester_partitions
             .into_iter()
             .flat_map(move |c| {
-                let c = match &retention_delete_pred {
-                    Some(pred) => c.with_delete_predicates(vec![Arc::clone(pred)]),
-                    None => c,
-                };
-                c.into_chunks().into_iter()
+                c.with_delete_predicates(delete_predicates.clone())
+                    .into_chunks()
+                    .into_iter()
             })
             .map(|c| Box::new(c) as Box<dyn UpdatableQuerierChunk>)
     }
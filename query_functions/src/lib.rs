@@ -32,6 +32,9 @@ mod window;
 
 pub mod gapfill;
 
+/// InfluxQL transformation functions, evaluated as window functions
+pub mod transformations;
+
 /// Function registry
 mod registry;
 
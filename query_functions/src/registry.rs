@@ -7,7 +7,7 @@ use datafusion::{
 };
 use once_cell::sync::Lazy;
 
-use crate::{gapfill, regex, window};
+use crate::{gapfill, regex, transformations, window};
 
 static REGISTRY: Lazy<IOxFunctionRegistry> = Lazy::new(IOxFunctionRegistry::new);
 
@@ -51,9 +51,25 @@ impl FunctionRegistry for IOxFunctionRegistry {
     }
 
     fn udaf(&self, name: &str) -> DataFusionResult<Arc<AggregateUDF>> {
-        Err(DataFusionError::Plan(format!(
-            "IOx FunctionRegistry does not contain user defined aggregate function '{name}'"
-        )))
+        match name {
+            transformations::DERIVATIVE_UDAF_NAME => Ok(transformations::DERIVATIVE.clone()),
+            transformations::NON_NEGATIVE_DERIVATIVE_UDAF_NAME => {
+                Ok(transformations::NON_NEGATIVE_DERIVATIVE.clone())
+            }
+            transformations::DIFFERENCE_UDAF_NAME => Ok(transformations::DIFFERENCE.clone()),
+            transformations::NON_NEGATIVE_DIFFERENCE_UDAF_NAME => {
+                Ok(transformations::NON_NEGATIVE_DIFFERENCE.clone())
+            }
+            transformations::MOVING_AVERAGE_UDAF_NAME => {
+                Ok(transformations::MOVING_AVERAGE.clone())
+            }
+            transformations::CUMULATIVE_SUM_UDAF_NAME => {
+                Ok(transformations::CUMULATIVE_SUM.clone())
+            }
+            _ => Err(DataFusionError::Plan(format!(
+                "IOx FunctionRegistry does not contain user defined aggregate function '{name}'"
+            ))),
+        }
     }
 }
 
@@ -111,6 +111,7 @@ use datafusion::{
 
 /// Internal implementations of the selector functions
 mod internal;
+mod percentile;
 use internal::{
     BooleanFirstSelector, BooleanLastSelector, BooleanMaxSelector, BooleanMinSelector,
     F64FirstSelector, F64LastSelector, F64MaxSelector, F64MinSelector, I64FirstSelector,
@@ -118,6 +119,7 @@ use internal::{
     U64MaxSelector, U64MinSelector, Utf8FirstSelector, Utf8LastSelector, Utf8MaxSelector,
     Utf8MinSelector,
 };
+use percentile::PercentileAccumulator;
 use schema::TIME_DATA_TYPE;
 
 /// registers selector functions so they can be invoked via SQL
@@ -126,6 +128,7 @@ pub fn register_selector_aggregates(ctx: &SessionContext) {
     ctx.register_udaf(struct_selector_last());
     ctx.register_udaf(struct_selector_min());
     ctx.register_udaf(struct_selector_max());
+    ctx.register_udaf(struct_selector_percentile());
 }
 
 /// Returns a DataFusion user defined aggregate function for computing
@@ -308,6 +311,111 @@ pub fn selector_max(data_type: &DataType, output: SelectorOutput) -> AggregateUD
     )
 }
 
+/// Returns a DataFusion user defined aggregate function for computing
+/// the percentile(value, time, percentile) selector function, returning a struct:
+///
+/// percentile(value, time, percentile) -> struct { value, time }
+///
+/// ```text
+/// {
+///   value: value at the row nearest to the specified percentile of the value column
+///   time: value of time for the selected row
+/// }
+/// ```
+///
+/// The percentile is a `Float64` in the range `[0, 100]`. If there are
+/// multiple rows with the same value, the row with the first
+/// (earliest/smallest) timestamp is chosen.
+pub fn struct_selector_percentile() -> AggregateUDF {
+    make_percentile_uda("selector_percentile", SelectorOutput::Struct, None)
+}
+
+/// Returns a DataFusion user defined aggregate function for computing
+/// one field of the percentile() selector function.
+///
+/// selector_percentile(data_column, timestamp_column, percentile) -> value and timestamp
+///
+/// value is the value of the data_column nearest to the specified
+/// percentile, using the same method as InfluxQL
+///
+/// timestamp is the value of the timestamp_column at the position of
+/// the selected value_column.
+pub fn selector_percentile(data_type: &DataType, output: SelectorOutput) -> AggregateUDF {
+    let name = match output {
+        SelectorOutput::Value => "selector_percentile_value",
+        SelectorOutput::Time => "selector_percentile_time",
+        SelectorOutput::Struct => "selector_percentile",
+    };
+
+    make_percentile_uda(name, output, Some(data_type.clone()))
+}
+
+/// Create the User Defined Aggregate Function (UDAF) for the percentile selector.
+///
+/// The percentile selector only supports numeric values and requires an
+/// additional percentile argument, so does not use the [`FactoryBuilder`].
+fn make_percentile_uda(
+    name: &str,
+    output: SelectorOutput,
+    value_type: Option<DataType>,
+) -> AggregateUDF {
+    let input_signature = Signature::one_of(
+        vec![
+            TypeSignature::Exact(vec![DataType::Float64, TIME_DATA_TYPE(), DataType::Float64]),
+            TypeSignature::Exact(vec![DataType::Int64, TIME_DATA_TYPE(), DataType::Float64]),
+            TypeSignature::Exact(vec![DataType::UInt64, TIME_DATA_TYPE(), DataType::Float64]),
+        ],
+        Volatility::Stable,
+    );
+
+    let captured_name = name.to_string();
+    let return_type_func: ReturnTypeFunction = Arc::new(move |arg_types| {
+        if arg_types.len() != 3 {
+            return Err(DataFusionError::Plan(format!(
+                "{} requires exactly 3 arguments, got {}",
+                captured_name,
+                arg_types.len()
+            )));
+        }
+
+        Ok(Arc::new(output.return_type(&arg_types[0])))
+    });
+
+    let state_value_type = value_type.clone();
+    let state_type_factory: StateTypeFactory = Arc::new(move |return_type| {
+        let value_type = match &state_value_type {
+            Some(t) => t,
+            None => value_data_type_from_return_data_type(return_type),
+        };
+
+        Ok(Arc::new(vec![
+            DataType::List(Arc::new(Field::new("item", value_type.clone(), true))),
+            DataType::List(Arc::new(Field::new("item", TIME_DATA_TYPE(), true))),
+            DataType::Float64,
+        ]))
+    });
+
+    let accumulator_factory: AccumulatorFunctionImplementation = Arc::new(move |return_type| {
+        let value_type = match &value_type {
+            Some(t) => t,
+            None => value_data_type_from_return_data_type(return_type),
+        };
+
+        Ok(Box::new(PercentileAccumulator::new(
+            value_type.clone(),
+            output,
+        )))
+    });
+
+    AggregateUDF::new(
+        name,
+        &input_signature,
+        &return_type_func,
+        &accumulator_factory,
+        &state_type_factory,
+    )
+}
+
 #[derive(Debug, Clone, Copy)]
 enum SelectorType {
     First,
@@ -1260,6 +1368,89 @@ mod test {
         .await;
     }
 
+    // Begin `percentile`
+
+    #[tokio::test]
+    async fn test_struct_selector_percentile_f64() {
+        run_case(
+            struct_selector_percentile()
+                .call(vec![col("f64_value"), col("time"), lit(50.0)])
+                .alias("p50"),
+            vec![
+                "+------------------------------------------------+",
+                "| p50                                            |",
+                "+------------------------------------------------+",
+                "| {value: 3.0, time: 1970-01-01T00:00:00.000006} |",
+                "+------------------------------------------------+",
+            ],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_struct_selector_percentile_i64() {
+        run_case(
+            struct_selector_percentile()
+                .call(vec![col("i64_value"), col("time"), lit(50.0)])
+                .alias("p50"),
+            vec![
+                "+-----------------------------------------------+",
+                "| p50                                           |",
+                "+-----------------------------------------------+",
+                "| {value: 30, time: 1970-01-01T00:00:00.000006} |",
+                "+-----------------------------------------------+",
+            ],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_struct_selector_percentile_u64() {
+        run_case(
+            struct_selector_percentile()
+                .call(vec![col("u64_value"), col("time"), lit(50.0)])
+                .alias("p50"),
+            vec![
+                "+-----------------------------------------------+",
+                "| p50                                           |",
+                "+-----------------------------------------------+",
+                "| {value: 30, time: 1970-01-01T00:00:00.000006} |",
+                "+-----------------------------------------------+",
+            ],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_selector_percentile() {
+        let value = selector_percentile(&DataType::Float64, SelectorOutput::Value);
+        let time = selector_percentile(&DataType::Float64, SelectorOutput::Time);
+        let aggs = [0.0, 10.0, 90.0, 100.0]
+            .into_iter()
+            .flat_map(|p| {
+                [
+                    value
+                        .call(vec![col("f64_value"), col("time"), lit(p)])
+                        .alias(format!("value_{p}")),
+                    time.call(vec![col("f64_value"), col("time"), lit(p)])
+                        .alias(format!("time_{p}")),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let actual = run_plan(aggs).await;
+        assert_eq!(
+            actual,
+            vec![
+                "+---------+--------+----------+----------------------------+----------+----------------------------+-----------+----------------------------+",
+                "| value_0 | time_0 | value_10 | time_10                    | value_90 | time_90                    | value_100 | time_100                   |",
+                "+---------+--------+----------+----------------------------+----------+----------------------------+-----------+----------------------------+",
+                "|         |        | 1.0      | 1970-01-01T00:00:00.000004 | 5.0      | 1970-01-01T00:00:00.000005 | 5.0       | 1970-01-01T00:00:00.000005 |",
+                "+---------+--------+----------+----------------------------+----------+----------------------------+-----------+----------------------------+",
+            ]
+        );
+    }
+
     // Begin utility functions
 
     /// Runs the expr using `run_plan` and compares the result to `expected`
@@ -107,7 +107,7 @@ impl ToState<String> for &str {
     }
 }
 
-fn make_scalar_struct(data_fields: Vec<ScalarValue>) -> ScalarValue {
+pub(super) fn make_scalar_struct(data_fields: Vec<ScalarValue>) -> ScalarValue {
     let fields = vec![
         Field::new("value", data_fields[0].get_datatype(), true),
         Field::new("time", data_fields[1].get_datatype(), true),
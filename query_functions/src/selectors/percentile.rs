@@ -0,0 +1,215 @@
+//! Implementation of the InfluxDB `percentile` selector function.
+//!
+//! Unlike the other selectors, which only need to track a single
+//! value, `percentile` must buffer every input value, as the selected
+//! row is not known until all values have been observed.
+
+use std::cmp::Ordering;
+
+use arrow::{
+    array::{Array, ArrayRef, ListArray},
+    datatypes::DataType,
+};
+use datafusion::{
+    error::{DataFusionError, Result as DataFusionResult},
+    physical_plan::Accumulator,
+    scalar::ScalarValue,
+};
+use schema::TIME_DATA_TYPE;
+
+use super::{internal::make_scalar_struct, SelectorOutput};
+
+/// Accumulator for the `percentile(value, time, percentile)` selector function.
+///
+/// The selected row is determined using the same method as InfluxQL, which
+/// sorts the values in ascending order and chooses the value at the index:
+///
+/// ```text
+/// floor(count * percentile / 100 + 0.5) - 1
+/// ```
+///
+/// If the index is out of range, the result is `NULL`.
+///
+/// See: <https://github.com/influxdata/influxdb/blob/98361e207349a3643bcc332d54b009818fe7585f/query/call_iterator.go#L1010-L1025>
+#[derive(Debug)]
+pub(super) struct PercentileAccumulator {
+    value_type: DataType,
+    output: SelectorOutput,
+    /// The requested percentile, which is known once the first batch is received.
+    percentile: Option<f64>,
+    /// All non-null values and their timestamps.
+    values: Vec<(ScalarValue, i64)>,
+}
+
+impl PercentileAccumulator {
+    pub(super) fn new(value_type: DataType, output: SelectorOutput) -> Self {
+        Self {
+            value_type,
+            output,
+            percentile: None,
+            values: vec![],
+        }
+    }
+
+    fn set_percentile(&mut self, arr: &ArrayRef) -> DataFusionResult<()> {
+        if self.percentile.is_some() {
+            return Ok(());
+        }
+
+        for i in 0..arr.len() {
+            match ScalarValue::try_from_array(arr, i)? {
+                ScalarValue::Float64(Some(v)) => {
+                    self.percentile = Some(v);
+                    break;
+                }
+                ScalarValue::Float64(None) => {}
+                v => {
+                    return Err(DataFusionError::Internal(format!(
+                        "Internal error: Expected Float64 percentile argument, got {}",
+                        v.get_datatype()
+                    )))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn push_values(&mut self, value_arr: &ArrayRef, time_arr: &ArrayRef) -> DataFusionResult<()> {
+        for i in 0..value_arr.len() {
+            let value = ScalarValue::try_from_array(value_arr, i)?;
+            if value.is_null() {
+                continue;
+            }
+
+            match ScalarValue::try_from_array(time_arr, i)? {
+                ScalarValue::TimestampNanosecond(Some(time), _) => self.values.push((value, time)),
+                ScalarValue::TimestampNanosecond(None, _) => {}
+                v => {
+                    return Err(DataFusionError::Internal(format!(
+                        "Internal error: Expected timestamp argument, got {}",
+                        v.get_datatype()
+                    )))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the selected value and timestamp, if any.
+    fn selected(&self) -> Option<(ScalarValue, i64)> {
+        let percentile = self.percentile?;
+
+        let mut values = self.values.clone();
+        values.sort_by(|(a, a_time), (b, b_time)| {
+            a.partial_cmp(b)
+                .unwrap_or(Ordering::Equal)
+                .then(a_time.cmp(b_time))
+        });
+
+        let index = (values.len() as f64 * percentile / 100.0 + 0.5).floor() as i64 - 1;
+        if index < 0 || index as usize >= values.len() {
+            return None;
+        }
+
+        Some(values.swap_remove(index as usize))
+    }
+}
+
+impl Accumulator for PercentileAccumulator {
+    fn state(&self) -> DataFusionResult<Vec<ScalarValue>> {
+        let (values, times): (Vec<_>, Vec<_>) = self
+            .values
+            .iter()
+            .map(|(value, time)| {
+                (
+                    value.clone(),
+                    ScalarValue::TimestampNanosecond(Some(*time), None),
+                )
+            })
+            .unzip();
+
+        Ok(vec![
+            ScalarValue::new_list(Some(values), self.value_type.clone()),
+            ScalarValue::new_list(Some(times), TIME_DATA_TYPE()),
+            ScalarValue::Float64(self.percentile),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DataFusionResult<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        if values.len() != 3 {
+            return Err(DataFusionError::Internal(format!(
+                "Internal error: Expected 3 arguments passed to percentile function but got {}",
+                values.len()
+            )));
+        }
+
+        self.set_percentile(&values[2])?;
+        self.push_values(&values[0], &values[1])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> DataFusionResult<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+
+        if states.len() != 3 {
+            return Err(DataFusionError::Internal(format!(
+                "Internal error: Expected 3 states passed to percentile function but got {}",
+                states.len()
+            )));
+        }
+
+        let value_lists = as_list(&states[0])?;
+        let time_lists = as_list(&states[1])?;
+
+        self.set_percentile(&states[2])?;
+        for i in 0..value_lists.len() {
+            if value_lists.is_null(i) {
+                continue;
+            }
+            self.push_values(&value_lists.value(i), &time_lists.value(i))?;
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> DataFusionResult<ScalarValue> {
+        let (value, time) = match self.selected() {
+            Some((value, time)) => (value, Some(time)),
+            None => (ScalarValue::try_from(&self.value_type)?, None),
+        };
+
+        Ok(match self.output {
+            SelectorOutput::Value => value,
+            SelectorOutput::Time => ScalarValue::TimestampNanosecond(time, None),
+            SelectorOutput::Struct => {
+                make_scalar_struct(vec![value, ScalarValue::TimestampNanosecond(time, None)])
+            }
+        })
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.values.capacity() * std::mem::size_of::<(ScalarValue, i64)>()
+            + self
+                .values
+                .iter()
+                .map(|(v, _)| v.size() - std::mem::size_of_val(v))
+                .sum::<usize>()
+    }
+}
+
+fn as_list(arr: &ArrayRef) -> DataFusionResult<&ListArray> {
+    arr.as_any().downcast_ref::<ListArray>().ok_or_else(|| {
+        DataFusionError::Internal(format!(
+            "Internal error: Expected list state for percentile, got {}",
+            arr.data_type()
+        ))
+    })
+}
@@ -0,0 +1,687 @@
+//! User defined aggregate functions that implement the InfluxQL
+//! [transformations], such as `derivative`, `difference`,
+//! `moving_average` and `cumulative_sum`.
+//!
+//! Unlike regular aggregate functions, which collapse a set of rows
+//! into a single row, transformations produce a value for every input
+//! row, which is computed from the current and preceding rows of the
+//! series. They are therefore planned as window functions, ordered by
+//! `time` and using a frame that starts at the first row of the
+//! partition and ends at the current row:
+//!
+//! ```sql
+//! SELECT
+//!   difference(usage_idle) OVER (
+//!     PARTITION BY cpu
+//!     ORDER BY time ASC
+//!     ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+//!   )
+//! FROM cpu
+//! ```
+//!
+//! For each row, the accumulator is updated with the row that entered the
+//! frame and evaluated, so the output is the result of the transformation
+//! for the current row. If the transformation has no result for the row,
+//! such as the first row of a `difference`, the output is `NULL`.
+//!
+//! `NULL` input values do not contribute to the state of the transformation
+//! and produce a `NULL` output.
+//!
+//! [transformations]: https://docs.influxdata.com/influxdb/v1.8/query_language/functions/#transformations
+use std::{collections::VecDeque, sync::Arc};
+
+use arrow::{array::ArrayRef, datatypes::DataType};
+use datafusion::{
+    error::{DataFusionError, Result as DataFusionResult},
+    logical_expr::{
+        AccumulatorFunctionImplementation, AggregateUDF, ReturnTypeFunction, Signature,
+        StateTypeFunction, TypeSignature, Volatility,
+    },
+    physical_plan::Accumulator,
+    scalar::ScalarValue,
+};
+use once_cell::sync::Lazy;
+use schema::TIME_DATA_TYPE;
+
+/// The name of the derivative UDAF given to DataFusion.
+pub const DERIVATIVE_UDAF_NAME: &str = "derivative";
+
+/// The name of the non_negative_derivative UDAF given to DataFusion.
+pub const NON_NEGATIVE_DERIVATIVE_UDAF_NAME: &str = "non_negative_derivative";
+
+/// The name of the difference UDAF given to DataFusion.
+pub const DIFFERENCE_UDAF_NAME: &str = "difference";
+
+/// The name of the non_negative_difference UDAF given to DataFusion.
+pub const NON_NEGATIVE_DIFFERENCE_UDAF_NAME: &str = "non_negative_difference";
+
+/// The name of the moving_average UDAF given to DataFusion.
+pub const MOVING_AVERAGE_UDAF_NAME: &str = "moving_average";
+
+/// The name of the cumulative_sum UDAF given to DataFusion.
+pub const CUMULATIVE_SUM_UDAF_NAME: &str = "cumulative_sum";
+
+/// The numeric types accepted as the value of a transformation.
+const NUMERIC_TYPES: [DataType; 3] = [DataType::Float64, DataType::Int64, DataType::UInt64];
+
+/// Implementation of `derivative(value, unit, time)`.
+///
+/// Computes the rate of change between the current and previous value,
+/// per `unit` nanoseconds.
+pub(crate) static DERIVATIVE: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| Arc::new(make_derivative_udaf(DERIVATIVE_UDAF_NAME, false)));
+
+/// Implementation of `non_negative_derivative(value, unit, time)`.
+///
+/// Computes the rate of change between the current and previous value,
+/// per `unit` nanoseconds, omitting negative results.
+pub(crate) static NON_NEGATIVE_DERIVATIVE: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    Arc::new(make_derivative_udaf(
+        NON_NEGATIVE_DERIVATIVE_UDAF_NAME,
+        true,
+    ))
+});
+
+/// Implementation of `difference(value)`.
+///
+/// Computes the difference between the current and previous value.
+pub(crate) static DIFFERENCE: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| Arc::new(make_difference_udaf(DIFFERENCE_UDAF_NAME, false)));
+
+/// Implementation of `non_negative_difference(value)`.
+///
+/// Computes the difference between the current and previous value,
+/// omitting negative results.
+pub(crate) static NON_NEGATIVE_DIFFERENCE: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    Arc::new(make_difference_udaf(
+        NON_NEGATIVE_DIFFERENCE_UDAF_NAME,
+        true,
+    ))
+});
+
+/// Implementation of `moving_average(value, n)`.
+///
+/// Computes the mean of the current and `n - 1` previous values. No
+/// value is produced until `n` values have been observed.
+pub(crate) static MOVING_AVERAGE: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    let signature = Signature::one_of(
+        NUMERIC_TYPES
+            .into_iter()
+            .map(|t| TypeSignature::Exact(vec![t, DataType::Int64]))
+            .collect(),
+        Volatility::Immutable,
+    );
+    let return_type: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    let accumulator: AccumulatorFunctionImplementation =
+        Arc::new(|_| Ok(Box::new(MovingAverageAccumulator::new())));
+
+    Arc::new(AggregateUDF::new(
+        MOVING_AVERAGE_UDAF_NAME,
+        &signature,
+        &return_type,
+        &accumulator,
+        &state_type(),
+    ))
+});
+
+/// Implementation of `cumulative_sum(value)`.
+///
+/// Computes the running total of the values.
+pub(crate) static CUMULATIVE_SUM: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    let signature = Signature::uniform(1, NUMERIC_TYPES.to_vec(), Volatility::Immutable);
+    let return_type: ReturnTypeFunction = Arc::new(|args| Ok(Arc::new(args[0].clone())));
+    let accumulator: AccumulatorFunctionImplementation =
+        Arc::new(|return_type| Ok(Box::new(CumulativeSumAccumulator::try_new(return_type)?)));
+
+    Arc::new(AggregateUDF::new(
+        CUMULATIVE_SUM_UDAF_NAME,
+        &signature,
+        &return_type,
+        &accumulator,
+        &state_type(),
+    ))
+});
+
+fn make_derivative_udaf(name: &str, non_negative: bool) -> AggregateUDF {
+    let signature = Signature::one_of(
+        NUMERIC_TYPES
+            .into_iter()
+            .map(|t| TypeSignature::Exact(vec![t, DataType::Int64, TIME_DATA_TYPE()]))
+            .collect(),
+        Volatility::Immutable,
+    );
+    let return_type: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    let accumulator: AccumulatorFunctionImplementation =
+        Arc::new(move |_| Ok(Box::new(DerivativeAccumulator::new(non_negative))));
+
+    AggregateUDF::new(name, &signature, &return_type, &accumulator, &state_type())
+}
+
+fn make_difference_udaf(name: &str, non_negative: bool) -> AggregateUDF {
+    let signature = Signature::uniform(1, NUMERIC_TYPES.to_vec(), Volatility::Immutable);
+    let return_type: ReturnTypeFunction = Arc::new(|args| Ok(Arc::new(args[0].clone())));
+    let accumulator: AccumulatorFunctionImplementation = Arc::new(move |return_type| {
+        Ok(Box::new(DifferenceAccumulator::try_new(
+            return_type,
+            non_negative,
+        )?))
+    });
+
+    AggregateUDF::new(name, &signature, &return_type, &accumulator, &state_type())
+}
+
+/// The state of all transformations is the output for the current row.
+fn state_type() -> StateTypeFunction {
+    Arc::new(|return_type| Ok(Arc::new(vec![return_type.clone()])))
+}
+
+/// Returns the value at `index` of `array` as an `f64`, or `None` if the value is `NULL`.
+fn f64_value(array: &ArrayRef, index: usize) -> DataFusionResult<Option<f64>> {
+    Ok(match ScalarValue::try_from_array(array, index)? {
+        ScalarValue::Float64(v) => v,
+        ScalarValue::Int64(v) => v.map(|v| v as f64),
+        ScalarValue::UInt64(v) => v.map(|v| v as f64),
+        v => {
+            return Err(DataFusionError::Internal(format!(
+                "unsupported value type for transformation: {}",
+                v.get_datatype()
+            )))
+        }
+    })
+}
+
+/// Returns the value at `index` of `array` as an `i64`, or `None` if the value is `NULL`.
+fn i64_value(array: &ArrayRef, index: usize) -> DataFusionResult<Option<i64>> {
+    Ok(match ScalarValue::try_from_array(array, index)? {
+        ScalarValue::Int64(v) | ScalarValue::TimestampNanosecond(v, _) => v,
+        v => {
+            return Err(DataFusionError::Internal(format!(
+                "expected integer or timestamp argument, got {}",
+                v.get_datatype()
+            )))
+        }
+    })
+}
+
+/// Verify the number of arguments passed to the accumulator of `name`.
+fn check_args(name: &str, values: &[ArrayRef], expected: usize) -> DataFusionResult<()> {
+    if values.len() != expected {
+        return Err(DataFusionError::Internal(format!(
+            "Internal error: Expected {expected} arguments passed to {name} but got {}",
+            values.len()
+        )));
+    }
+    Ok(())
+}
+
+fn merge_not_supported(name: &str) -> DataFusionResult<()> {
+    Err(DataFusionError::NotImplemented(format!(
+        "{name} must be evaluated as a window function"
+    )))
+}
+
+/// Accumulator for `derivative` and `non_negative_derivative`.
+#[derive(Debug)]
+struct DerivativeAccumulator {
+    non_negative: bool,
+    /// The previous value and timestamp of the series.
+    prev: Option<(f64, i64)>,
+    /// The result for the most recent row.
+    value: Option<f64>,
+}
+
+impl DerivativeAccumulator {
+    fn new(non_negative: bool) -> Self {
+        Self {
+            non_negative,
+            prev: None,
+            value: None,
+        }
+    }
+}
+
+impl Accumulator for DerivativeAccumulator {
+    fn state(&self) -> DataFusionResult<Vec<ScalarValue>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DataFusionResult<()> {
+        check_args(DERIVATIVE_UDAF_NAME, values, 3)?;
+
+        for i in 0..values[0].len() {
+            let (Some(v), Some(unit), Some(t)) = (
+                f64_value(&values[0], i)?,
+                i64_value(&values[1], i)?,
+                i64_value(&values[2], i)?,
+            ) else {
+                self.value = None;
+                continue;
+            };
+
+            match self.prev {
+                // Points with the same timestamp as the previous point are ignored.
+                Some((_, prev_t)) if prev_t == t => {
+                    self.value = None;
+                    continue;
+                }
+                Some((prev_v, prev_t)) => {
+                    let diff = v - prev_v;
+                    // The elapsed time is always positive, so the sign of the result
+                    // is the same when the series is ordered by descending time.
+                    let elapsed = (t - prev_t).abs() as f64 / unit as f64;
+                    self.value = (!self.non_negative || diff >= 0.0).then_some(diff / elapsed);
+                }
+                None => self.value = None,
+            }
+            self.prev = Some((v, t));
+        }
+
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, _states: &[ArrayRef]) -> DataFusionResult<()> {
+        merge_not_supported(DERIVATIVE_UDAF_NAME)
+    }
+
+    fn evaluate(&self) -> DataFusionResult<ScalarValue> {
+        Ok(ScalarValue::Float64(self.value))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+/// Accumulator for `difference` and `non_negative_difference`.
+#[derive(Debug)]
+struct DifferenceAccumulator {
+    non_negative: bool,
+    /// The previous value of the series.
+    prev: ScalarValue,
+    /// The result for the most recent row.
+    value: ScalarValue,
+}
+
+impl DifferenceAccumulator {
+    fn try_new(data_type: &DataType, non_negative: bool) -> DataFusionResult<Self> {
+        let null = ScalarValue::try_from(data_type)?;
+        Ok(Self {
+            non_negative,
+            prev: null.clone(),
+            value: null,
+        })
+    }
+}
+
+impl Accumulator for DifferenceAccumulator {
+    fn state(&self) -> DataFusionResult<Vec<ScalarValue>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DataFusionResult<()> {
+        check_args(DIFFERENCE_UDAF_NAME, values, 1)?;
+
+        for i in 0..values[0].len() {
+            let v = ScalarValue::try_from_array(&values[0], i)?;
+            if v.is_null() {
+                self.value = v;
+                continue;
+            }
+
+            self.value = match (&self.prev, &v) {
+                // The first value of the series
+                (prev, v) if prev.is_null() => ScalarValue::try_from(&v.get_datatype())?,
+                (prev, v) if self.non_negative && v < prev => {
+                    ScalarValue::try_from(&v.get_datatype())?
+                }
+                (ScalarValue::Float64(Some(prev)), ScalarValue::Float64(Some(v))) => {
+                    ScalarValue::Float64(Some(v - prev))
+                }
+                (ScalarValue::Int64(Some(prev)), ScalarValue::Int64(Some(v))) => {
+                    ScalarValue::Int64(Some(v.wrapping_sub(*prev)))
+                }
+                (ScalarValue::UInt64(Some(prev)), ScalarValue::UInt64(Some(v))) => {
+                    ScalarValue::UInt64(Some(v.wrapping_sub(*prev)))
+                }
+                (prev, v) => {
+                    return Err(DataFusionError::Internal(format!(
+                        "mismatched types for difference: {} and {}",
+                        prev.get_datatype(),
+                        v.get_datatype()
+                    )))
+                }
+            };
+            self.prev = v;
+        }
+
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, _states: &[ArrayRef]) -> DataFusionResult<()> {
+        merge_not_supported(DIFFERENCE_UDAF_NAME)
+    }
+
+    fn evaluate(&self) -> DataFusionResult<ScalarValue> {
+        Ok(self.value.clone())
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) - std::mem::size_of_val(&self.prev) + self.prev.size()
+            - std::mem::size_of_val(&self.value)
+            + self.value.size()
+    }
+}
+
+/// Accumulator for `moving_average`.
+#[derive(Debug)]
+struct MovingAverageAccumulator {
+    /// The most recent values of the series, up to the size of the window.
+    window: VecDeque<f64>,
+    /// The sum of the values in `window`.
+    sum: f64,
+    /// The result for the most recent row.
+    value: Option<f64>,
+}
+
+impl MovingAverageAccumulator {
+    fn new() -> Self {
+        Self {
+            window: VecDeque::new(),
+            sum: 0.0,
+            value: None,
+        }
+    }
+}
+
+impl Accumulator for MovingAverageAccumulator {
+    fn state(&self) -> DataFusionResult<Vec<ScalarValue>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DataFusionResult<()> {
+        check_args(MOVING_AVERAGE_UDAF_NAME, values, 2)?;
+
+        for i in 0..values[0].len() {
+            let (Some(v), Some(n)) = (
+                f64_value(&values[0], i)?,
+                i64_value(&values[1], i)?,
+            ) else {
+                self.value = None;
+                continue;
+            };
+            let n = usize::try_from(n).map_err(|_| {
+                DataFusionError::Execution(format!(
+                    "moving_average window must be greater than 1, got {n}"
+                ))
+            })?;
+
+            self.window.push_back(v);
+            self.sum += v;
+            while self.window.len() > n {
+                self.sum -= self.window.pop_front().unwrap_or_default();
+            }
+
+            self.value = (self.window.len() == n).then(|| self.sum / n as f64);
+        }
+
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, _states: &[ArrayRef]) -> DataFusionResult<()> {
+        merge_not_supported(MOVING_AVERAGE_UDAF_NAME)
+    }
+
+    fn evaluate(&self) -> DataFusionResult<ScalarValue> {
+        Ok(ScalarValue::Float64(self.value))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.window.capacity() * std::mem::size_of::<f64>()
+    }
+}
+
+/// Accumulator for `cumulative_sum`.
+#[derive(Debug)]
+struct CumulativeSumAccumulator {
+    /// The running total of the series.
+    sum: ScalarValue,
+    /// The result for the most recent row.
+    value: ScalarValue,
+}
+
+impl CumulativeSumAccumulator {
+    fn try_new(data_type: &DataType) -> DataFusionResult<Self> {
+        let null = ScalarValue::try_from(data_type)?;
+        Ok(Self {
+            sum: null.clone(),
+            value: null,
+        })
+    }
+}
+
+impl Accumulator for CumulativeSumAccumulator {
+    fn state(&self) -> DataFusionResult<Vec<ScalarValue>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DataFusionResult<()> {
+        check_args(CUMULATIVE_SUM_UDAF_NAME, values, 1)?;
+
+        for i in 0..values[0].len() {
+            let v = ScalarValue::try_from_array(&values[0], i)?;
+            if v.is_null() {
+                self.value = v;
+                continue;
+            }
+
+            self.sum = match (&self.sum, &v) {
+                (ScalarValue::Float64(Some(sum)), ScalarValue::Float64(Some(v))) => {
+                    ScalarValue::Float64(Some(sum + v))
+                }
+                (ScalarValue::Int64(Some(sum)), ScalarValue::Int64(Some(v))) => {
+                    ScalarValue::Int64(Some(sum.wrapping_add(*v)))
+                }
+                (ScalarValue::UInt64(Some(sum)), ScalarValue::UInt64(Some(v))) => {
+                    ScalarValue::UInt64(Some(sum.wrapping_add(*v)))
+                }
+                // The first value of the series
+                (sum, v) if sum.is_null() => v.clone(),
+                (sum, v) => {
+                    return Err(DataFusionError::Internal(format!(
+                        "mismatched types for cumulative_sum: {} and {}",
+                        sum.get_datatype(),
+                        v.get_datatype()
+                    )))
+                }
+            };
+            self.value = self.sum.clone();
+        }
+
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, _states: &[ArrayRef]) -> DataFusionResult<()> {
+        merge_not_supported(CUMULATIVE_SUM_UDAF_NAME)
+    }
+
+    fn evaluate(&self) -> DataFusionResult<ScalarValue> {
+        Ok(self.value.clone())
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) - std::mem::size_of_val(&self.sum) + self.sum.size()
+            - std::mem::size_of_val(&self.value)
+            + self.value.size()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use arrow::array::{Float64Array, Int64Array, TimestampNanosecondArray, UInt64Array};
+
+    use super::*;
+
+    /// Evaluate `udaf` for each row of `args`, as a window function with a frame of
+    /// `ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`.
+    fn eval_rows(udaf: &AggregateUDF, args: Vec<ArrayRef>) -> Vec<ScalarValue> {
+        let arg_types = args
+            .iter()
+            .map(|a| a.data_type().clone())
+            .collect::<Vec<_>>();
+        let return_type = (udaf.return_type)(&arg_types).unwrap();
+        let mut acc = (udaf.accumulator)(&return_type).unwrap();
+
+        (0..args[0].len())
+            .map(|i| {
+                let row = args.iter().map(|a| a.slice(i, 1)).collect::<Vec<_>>();
+                acc.update_batch(&row).unwrap();
+                acc.evaluate().unwrap()
+            })
+            .collect()
+    }
+
+    fn times(v: Vec<i64>) -> ArrayRef {
+        Arc::new(TimestampNanosecondArray::from(v))
+    }
+
+    fn units(unit: i64, len: usize) -> ArrayRef {
+        Arc::new(Int64Array::from(vec![unit; len]))
+    }
+
+    #[test]
+    fn test_derivative() {
+        let values: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(1.0),
+            Some(3.0),
+            None,
+            Some(2.0),
+            Some(2.0),
+            Some(8.0),
+        ]));
+        let time = times(vec![0, 1_000, 1_500, 2_000, 2_000, 5_000]);
+
+        let got = eval_rows(
+            &DERIVATIVE,
+            vec![Arc::clone(&values), units(1_000, 6), Arc::clone(&time)],
+        );
+        assert_eq!(
+            got,
+            vec![
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(Some(2.0)),
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(Some(-1.0)),
+                // same timestamp as the previous point
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(Some(2.0)),
+            ]
+        );
+
+        let got = eval_rows(
+            &NON_NEGATIVE_DERIVATIVE,
+            vec![values, units(2_000, 6), time],
+        );
+        assert_eq!(
+            got,
+            vec![
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(Some(4.0)),
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(Some(4.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_difference() {
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![
+            Some(10),
+            Some(12),
+            None,
+            Some(7),
+            Some(9),
+        ]));
+
+        let got = eval_rows(&DIFFERENCE, vec![Arc::clone(&values)]);
+        assert_eq!(
+            got,
+            vec![
+                ScalarValue::Int64(None),
+                ScalarValue::Int64(Some(2)),
+                ScalarValue::Int64(None),
+                ScalarValue::Int64(Some(-5)),
+                ScalarValue::Int64(Some(2)),
+            ]
+        );
+
+        let got = eval_rows(&NON_NEGATIVE_DIFFERENCE, vec![values]);
+        assert_eq!(
+            got,
+            vec![
+                ScalarValue::Int64(None),
+                ScalarValue::Int64(Some(2)),
+                ScalarValue::Int64(None),
+                ScalarValue::Int64(None),
+                ScalarValue::Int64(Some(2)),
+            ]
+        );
+
+        // unsigned values
+        let values: ArrayRef = Arc::new(UInt64Array::from(vec![3, 5, 4]));
+        let got = eval_rows(&NON_NEGATIVE_DIFFERENCE, vec![values]);
+        assert_eq!(
+            got,
+            vec![
+                ScalarValue::UInt64(None),
+                ScalarValue::UInt64(Some(2)),
+                ScalarValue::UInt64(None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_moving_average() {
+        let values: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(1.0),
+            Some(3.0),
+            None,
+            Some(5.0),
+            Some(10.0),
+        ]));
+
+        let got = eval_rows(&MOVING_AVERAGE, vec![values, units(2, 5)]);
+        assert_eq!(
+            got,
+            vec![
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(Some(2.0)),
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(Some(4.0)),
+                ScalarValue::Float64(Some(7.5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cumulative_sum() {
+        let values: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(1.5),
+            None,
+            Some(3.0),
+            Some(-2.0),
+        ]));
+
+        let got = eval_rows(&CUMULATIVE_SUM, vec![values]);
+        assert_eq!(
+            got,
+            vec![
+                ScalarValue::Float64(Some(1.5)),
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(Some(4.5)),
+                ScalarValue::Float64(Some(2.5)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! A trait to abstract the handling of predicate delete requests, and an
+//! implementation that records them as tombstones in the catalog.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use data_types::{DeletePredicate, NamespaceName, Timestamp, TRANSITION_SHARD_ID};
+use futures::future::join_all;
+use generated_types::influxdata::iox::{delete::v1::DeletePayload, ingester::v1::DeleteRequest};
+use iox_catalog::interface::{Catalog, SoftDeletedRows};
+use observability_deps::tracing::*;
+use thiserror::Error;
+
+use crate::dml_handlers::{
+    client::WriteClient, lazy_connector::LazyConnector, RpcWriteError, RPC_TIMEOUT,
+};
+
+pub mod mock;
+
+/// Errors emitted by a [`DeleteHandler`] implementation.
+#[derive(Debug, Error)]
+pub enum DeleteError {
+    /// The namespace specified by the caller does not exist.
+    #[error("namespace {0} does not exist")]
+    NamespaceNotFound(String),
+
+    /// An error occurred when reading from or writing to the catalog.
+    #[error("failed to record delete: {0}")]
+    Catalog(#[from] iox_catalog::interface::Error),
+
+    /// The delete was recorded, but an ingester failed to apply it to its
+    /// buffered data - the caller must retry the delete.
+    #[error("failed to apply delete to ingester {0}: {1}")]
+    Upstream(Arc<str>, RpcWriteError),
+}
+
+/// An abstract handler of predicate delete requests.
+#[async_trait]
+pub trait DeleteHandler: std::fmt::Debug + Send + Sync {
+    /// Delete all rows in `namespace` that match `predicate`.
+    ///
+    /// If `table_name` is specified, only rows in that table are deleted,
+    /// otherwise the delete applies to every table in the namespace.
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        table_name: Option<&str>,
+        predicate: &DeletePredicate,
+    ) -> Result<(), DeleteError>;
+}
+
+/// A [`DeleteHandler`] that records each delete as one tombstone per affected
+/// table in the [`Catalog`], and then sends it to every ingester.
+///
+/// Tombstones are applied by the querier when reading, and used by the
+/// compactor to physically remove the deleted rows. A tombstone applies to the
+/// rows of the table matching its predicate that were buffered by an ingester
+/// before it received the delete, as each parquet file records the most recent
+/// tombstone received before its data was buffered.
+///
+/// # Partial Failure
+///
+/// If an ingester cannot be reached, the tombstone remains recorded and an
+/// error is returned. Rows buffered by that ingester after the tombstone was
+/// recorded may then be deleted once persisted, and the caller must retry the
+/// delete to ensure the rows buffered before it are deleted.
+#[derive(Debug)]
+pub struct CatalogDeleteHandler<T = LazyConnector> {
+    catalog: Arc<dyn Catalog>,
+
+    /// The ingesters buffering writes, each of which must apply the delete.
+    upstreams: Vec<(T, Arc<str>)>,
+}
+
+impl<T> CatalogDeleteHandler<T> {
+    /// Initialise a [`CatalogDeleteHandler`] that records deletes in
+    /// `catalog` and applies them to the data buffered by `upstreams`.
+    pub fn new<N>(catalog: Arc<dyn Catalog>, upstreams: impl IntoIterator<Item = (T, N)>) -> Self
+    where
+        N: Into<Arc<str>>,
+    {
+        Self {
+            catalog,
+            upstreams: upstreams
+                .into_iter()
+                .map(|(client, name)| (client, name.into()))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl<T> DeleteHandler for CatalogDeleteHandler<T>
+where
+    T: WriteClient + 'static,
+{
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        table_name: Option<&str>,
+        predicate: &DeletePredicate,
+    ) -> Result<(), DeleteError> {
+        let mut txn = self.catalog.start_transaction().await?;
+
+        let namespace_id = txn
+            .namespaces()
+            .get_by_name(namespace.as_str(), SoftDeletedRows::ExcludeDeleted)
+            .await?
+            .ok_or_else(|| DeleteError::NamespaceNotFound(namespace.to_string()))?
+            .id;
+
+        let tables = match table_name {
+            Some(name) => txn
+                .tables()
+                .get_by_namespace_and_name(namespace_id, name)
+                .await?
+                .into_iter()
+                .collect(),
+            None => txn.tables().list_by_namespace_id(namespace_id).await?,
+        };
+
+        let serialized_predicate = predicate.expr_sql_string();
+        let mut payloads = Vec::with_capacity(tables.len());
+        for table in tables {
+            let tombstone = txn
+                .tombstones()
+                .create(
+                    table.id,
+                    TRANSITION_SHARD_ID,
+                    Timestamp::new(predicate.range.start()),
+                    Timestamp::new(predicate.range.end()),
+                    &serialized_predicate,
+                )
+                .await?;
+
+            debug!(
+                %namespace,
+                table_name=%table.name,
+                tombstone_id=%tombstone.id,
+                predicate=%serialized_predicate,
+                "recorded delete"
+            );
+
+            payloads.push(DeletePayload {
+                database_id: namespace_id.get(),
+                table_name: table.name,
+                predicate: Some(predicate.clone().into()),
+                table_id: table.id.get(),
+                tombstone_sequence_number: tombstone.sequence_number.get(),
+            });
+        }
+
+        txn.commit().await?;
+
+        // Apply the delete to the data buffered by every ingester, so that
+        // rows buffered from now on are not affected by it.
+        let requests = payloads.iter().flat_map(|payload| {
+            self.upstreams.iter().map(move |(client, name)| async move {
+                let req = DeleteRequest {
+                    payload: Some(payload.clone()),
+                };
+                tokio::time::timeout(RPC_TIMEOUT, client.delete(req))
+                    .await
+                    .map_err(RpcWriteError::Timeout)
+                    .and_then(|v| v)
+                    .map_err(|e| DeleteError::Upstream(Arc::clone(name), e))
+            })
+        });
+
+        join_all(requests).await.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::{DeleteExpr, Op, Scalar, TimestampRange};
+    use iox_catalog::mem::MemCatalog;
+
+    use super::*;
+    use crate::dml_handlers::client::mock::MockWriteClient;
+
+    const NAMESPACE: &str = "bananas";
+
+    async fn setup() -> (Arc<dyn Catalog>, Vec<data_types::Table>) {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(metrics));
+
+        let mut repos = catalog.repositories().await;
+        let topic = repos.topics().create_or_get("iox-shared").await.unwrap();
+        let query_pool = repos
+            .query_pools()
+            .create_or_get("iox-shared")
+            .await
+            .unwrap();
+        let namespace = repos
+            .namespaces()
+            .create(NAMESPACE, None, topic.id, query_pool.id)
+            .await
+            .unwrap();
+        let mut tables = vec![];
+        for name in ["platanos", "bananas"] {
+            tables.push(
+                repos
+                    .tables()
+                    .create_or_get(name, namespace.id)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        (catalog, tables)
+    }
+
+    fn predicate() -> DeletePredicate {
+        DeletePredicate {
+            range: TimestampRange::new(1, 10),
+            exprs: vec![DeleteExpr::new(
+                "tag1".to_string(),
+                Op::Eq,
+                Scalar::String("A".to_string()),
+            )],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_single_table() {
+        let (catalog, tables) = setup().await;
+        let client = Arc::new(MockWriteClient::default());
+        let handler =
+            CatalogDeleteHandler::new(Arc::clone(&catalog), [(Arc::clone(&client), "ingester-1")]);
+
+        handler
+            .delete(
+                &NamespaceName::try_from(NAMESPACE).unwrap(),
+                Some("platanos"),
+                &predicate(),
+            )
+            .await
+            .expect("delete should succeed");
+
+        let mut repos = catalog.repositories().await;
+        let tombstones = repos
+            .tombstones()
+            .list_by_table(tables[0].id)
+            .await
+            .unwrap();
+        let tombstone = assert_matches!(tombstones.as_slice(), [t] => {
+            assert_eq!(t.min_time, Timestamp::new(1));
+            assert_eq!(t.max_time, Timestamp::new(10));
+            assert_eq!(t.serialized_predicate, r#""tag1"='A'"#);
+            t.clone()
+        });
+
+        // The delete is applied to the data buffered by the ingester.
+        assert_matches!(client.delete_calls().as_slice(), [req] => {
+            let payload = req.payload.as_ref().unwrap();
+            assert_eq!(payload.table_id, tables[0].id.get());
+            assert_eq!(
+                payload.tombstone_sequence_number,
+                tombstone.sequence_number.get()
+            );
+            assert_eq!(payload.predicate, Some(predicate().into()));
+        });
+
+        let tombstones = repos
+            .tombstones()
+            .list_by_table(tables[1].id)
+            .await
+            .unwrap();
+        assert!(tombstones.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_all_tables() {
+        let (catalog, tables) = setup().await;
+        let clients = [
+            Arc::new(MockWriteClient::default()),
+            Arc::new(MockWriteClient::default()),
+        ];
+        let handler = CatalogDeleteHandler::new(
+            Arc::clone(&catalog),
+            clients
+                .iter()
+                .enumerate()
+                .map(|(i, c)| (Arc::clone(c), format!("ingester-{i}"))),
+        );
+
+        handler
+            .delete(
+                &NamespaceName::try_from(NAMESPACE).unwrap(),
+                None,
+                &predicate(),
+            )
+            .await
+            .expect("delete should succeed");
+
+        let mut repos = catalog.repositories().await;
+        for table in tables {
+            let tombstones = repos.tombstones().list_by_table(table.id).await.unwrap();
+            assert_eq!(tombstones.len(), 1);
+        }
+
+        // Every ingester is sent the delete of every table.
+        for client in clients {
+            assert_eq!(client.delete_calls().len(), 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_upstream_error() {
+        let (catalog, tables) = setup().await;
+        let client = Arc::new(
+            MockWriteClient::default().with_ret(
+                [Err(RpcWriteError::NoUpstreams)]
+                    .into_iter()
+                    .chain(std::iter::repeat_with(|| Ok(()))),
+            ),
+        );
+        let handler =
+            CatalogDeleteHandler::new(Arc::clone(&catalog), [(Arc::clone(&client), "ingester-1")]);
+
+        let err = handler
+            .delete(
+                &NamespaceName::try_from(NAMESPACE).unwrap(),
+                Some("platanos"),
+                &predicate(),
+            )
+            .await
+            .expect_err("delete should fail");
+        assert_matches!(err, DeleteError::Upstream(name, RpcWriteError::NoUpstreams) => {
+            assert_eq!(&*name, "ingester-1");
+        });
+
+        // The tombstone remains recorded, and is recorded again when the
+        // caller retries.
+        handler
+            .delete(
+                &NamespaceName::try_from(NAMESPACE).unwrap(),
+                Some("platanos"),
+                &predicate(),
+            )
+            .await
+            .expect("retried delete should succeed");
+
+        let tombstones = catalog
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_table(tables[0].id)
+            .await
+            .unwrap();
+        assert_eq!(tombstones.len(), 2);
+        assert_eq!(client.delete_calls().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_delete_unknown_table() {
+        let (catalog, tables) = setup().await;
+        let client = Arc::new(MockWriteClient::default());
+        let handler =
+            CatalogDeleteHandler::new(Arc::clone(&catalog), [(Arc::clone(&client), "ingester-1")]);
+
+        handler
+            .delete(
+                &NamespaceName::try_from(NAMESPACE).unwrap(),
+                Some("missing"),
+                &predicate(),
+            )
+            .await
+            .expect("delete of unknown table is a no-op");
+
+        let mut repos = catalog.repositories().await;
+        for table in tables {
+            let tombstones = repos.tombstones().list_by_table(table.id).await.unwrap();
+            assert!(tombstones.is_empty());
+        }
+        assert!(client.delete_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_unknown_namespace() {
+        let (catalog, _tables) = setup().await;
+        let handler = CatalogDeleteHandler::new(
+            catalog,
+            [(Arc::new(MockWriteClient::default()), "ingester-1")],
+        );
+
+        let err = handler
+            .delete(
+                &NamespaceName::try_from("missing").unwrap(),
+                None,
+                &predicate(),
+            )
+            .await
+            .expect_err("delete of unknown namespace should fail");
+        assert_matches!(err, DeleteError::NamespaceNotFound(name) => {
+            assert_eq!(name, "missing");
+        });
+    }
+}
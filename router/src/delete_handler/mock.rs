@@ -0,0 +1,60 @@
+//! A mock implementation of [`DeleteHandler`].
+
+#![allow(missing_docs)]
+
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+use data_types::{DeletePredicate, NamespaceName};
+use parking_lot::Mutex;
+
+use super::{DeleteError, DeleteHandler};
+
+/// A captured call to a [`MockDeleteHandler`].
+#[derive(Debug, Clone)]
+pub struct MockDeleteHandlerCall {
+    pub namespace: String,
+    pub table_name: Option<String>,
+    pub predicate: DeletePredicate,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    calls: Vec<MockDeleteHandlerCall>,
+    delete_return: VecDeque<Result<(), DeleteError>>,
+}
+
+#[derive(Debug, Default)]
+pub struct MockDeleteHandler(Mutex<Inner>);
+
+impl MockDeleteHandler {
+    pub fn with_delete_return(self, ret: impl Into<VecDeque<Result<(), DeleteError>>>) -> Self {
+        self.0.lock().delete_return = ret.into();
+        self
+    }
+
+    pub fn calls(&self) -> Vec<MockDeleteHandlerCall> {
+        self.0.lock().calls.clone()
+    }
+}
+
+#[async_trait]
+impl DeleteHandler for MockDeleteHandler {
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        table_name: Option<&str>,
+        predicate: &DeletePredicate,
+    ) -> Result<(), DeleteError> {
+        let mut guard = self.0.lock();
+        guard.calls.push(MockDeleteHandlerCall {
+            namespace: namespace.to_string(),
+            table_name: table_name.map(ToString::to_string),
+            predicate: predicate.clone(),
+        });
+        guard
+            .delete_return
+            .pop_front()
+            .expect("no mock value to return")
+    }
+}
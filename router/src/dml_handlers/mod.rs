@@ -53,6 +53,9 @@ pub mod nop;
 mod retention_validation;
 pub use retention_validation::*;
 
+mod rate_limit;
+pub use rate_limit::*;
+
 mod partitioner;
 pub use partitioner::*;
 
@@ -7,10 +7,15 @@ use thiserror::Error;
 use trace::ctx::SpanContext;
 
 use super::DmlHandler;
+use crate::namespace_cache::NamespaceCache;
 
 /// An error raised by the [`Partitioner`] handler.
 #[derive(Debug, Error)]
 pub enum PartitionError {
+    /// The requested namespace could not be found in the catalog.
+    #[error("failed to read namespace schema from catalog: {0}")]
+    NamespaceLookup(iox_catalog::interface::Error),
+
     /// Failed to write to the partitioned table batch.
     #[error("error batching into partitioned write: {0}")]
     BatchWrite(#[from] mutable_batch::Error),
@@ -41,26 +46,38 @@ impl<T> Partitioned<T> {
 }
 
 /// A [`DmlHandler`] implementation that splits per-table [`MutableBatch`] into
-/// partitioned per-table [`MutableBatch`] instances according to a configured
-/// [`PartitionTemplate`]. Deletes pass through unmodified.
+/// partitioned per-table [`MutableBatch`] instances according to the
+/// [`PartitionTemplate`] of each table. Deletes pass through unmodified.
+///
+/// The template used for a table is resolved from the [`NamespaceCache`],
+/// preferring the template of the table, then the template of the namespace,
+/// and falling back to the default template this handler is configured with.
 ///
 /// A vector of partitions are returned to the caller, or the first error that
 /// occurs during partitioning.
 #[derive(Debug)]
-pub struct Partitioner {
-    partition_template: PartitionTemplate,
+pub struct Partitioner<C> {
+    cache: C,
+    default_template: PartitionTemplate,
 }
 
-impl Partitioner {
+impl<C> Partitioner<C> {
     /// Initialise a new [`Partitioner`], splitting writes according to the
-    /// specified [`PartitionTemplate`].
-    pub fn new(partition_template: PartitionTemplate) -> Self {
-        Self { partition_template }
+    /// templates in `cache`, or the specified default [`PartitionTemplate`]
+    /// when neither the table nor its namespace have one.
+    pub fn new(cache: C, default_template: PartitionTemplate) -> Self {
+        Self {
+            cache,
+            default_template,
+        }
     }
 }
 
 #[async_trait]
-impl DmlHandler for Partitioner {
+impl<C> DmlHandler for Partitioner<C>
+where
+    C: NamespaceCache<ReadError = iox_catalog::interface::Error>, // The handler expects the cache to read from the catalog if necessary.
+{
     type WriteError = PartitionError;
 
     type WriteInput = HashMap<TableId, (String, MutableBatch)>;
@@ -69,20 +86,34 @@ impl DmlHandler for Partitioner {
     /// Partition the per-table [`MutableBatch`].
     async fn write(
         &self,
-        _namespace: &NamespaceName<'static>,
+        namespace: &NamespaceName<'static>,
         _namespace_id: NamespaceId,
         batch: Self::WriteInput,
         _span_ctx: Option<SpanContext>,
     ) -> Result<Self::WriteOutput, Self::WriteError> {
+        // Try to fetch the namespace schema through the cache.
+        let schema = self
+            .cache
+            .get_schema(namespace)
+            .await
+            .map_err(PartitionError::NamespaceLookup)?;
+
         // A collection of partition-keyed, per-table MutableBatch instances.
         let mut partitions: HashMap<PartitionKey, HashMap<_, (String, MutableBatch)>> =
             HashMap::default();
 
         for (table_id, (table_name, batch)) in batch {
-            // Partition the table batch according to the configured partition
+            let partition_template = schema
+                .tables
+                .get(&table_name)
+                .and_then(|t| t.partition_template.as_ref())
+                .or(schema.partition_template.as_ref())
+                .unwrap_or(&self.default_template);
+
+            // Partition the table batch according to the resolved partition
             // template and write it into the partition-keyed map.
             for (partition_key, partition_payload) in
-                PartitionWrite::partition(&table_name, &batch, &self.partition_template)
+                PartitionWrite::partition(&table_name, &batch, partition_template)?
             {
                 let partition = partitions.entry(partition_key).or_default();
                 let table_batch = partition
@@ -105,10 +136,72 @@ impl DmlHandler for Partitioner {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use assert_matches::assert_matches;
-    use data_types::TemplatePart;
+    use data_types::{NamespaceSchema, QueryPoolId, TableSchema, TemplatePart, TopicId};
+    use iox_catalog::mem::MemCatalog;
+    use once_cell::sync::Lazy;
 
     use super::*;
+    use crate::namespace_cache::{MemoryNamespaceCache, ReadThroughCache};
+
+    static NAMESPACE: Lazy<NamespaceName<'static>> = Lazy::new(|| "bananas".try_into().unwrap());
+
+    type TestCache = Arc<ReadThroughCache<Arc<MemoryNamespaceCache>>>;
+
+    fn daily_template() -> PartitionTemplate {
+        PartitionTemplate {
+            parts: vec![TemplatePart::TimeFormat("%Y-%m-%d".to_owned())],
+        }
+    }
+
+    fn new_schema() -> NamespaceSchema {
+        NamespaceSchema::new(
+            NamespaceId::new(42),
+            TopicId::new(1),
+            QueryPoolId::new(1),
+            100,
+            42,
+            None,
+        )
+    }
+
+    // Initialise a cache containing `schema` for [`NAMESPACE`].
+    fn new_cache(schema: Option<NamespaceSchema>) -> TestCache {
+        let catalog = Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let cache = Arc::new(ReadThroughCache::new(
+            Arc::new(MemoryNamespaceCache::default()),
+            catalog,
+        ));
+        if let Some(schema) = schema {
+            cache.put_schema(NAMESPACE.clone(), schema);
+        }
+        cache
+    }
+
+    // Partition `lp` with `partitioner`, returning the sorted partition keys
+    // of each table.
+    async fn partition_keys(
+        partitioner: &Partitioner<TestCache>,
+        lp: &str,
+    ) -> HashMap<String, Vec<String>> {
+        let partitions = partitioner
+            .write(&NAMESPACE, NamespaceId::new(42), lp_to_writes(lp), None)
+            .await
+            .expect("partitioning should succeed");
+
+        let mut keys: HashMap<String, Vec<String>> = HashMap::default();
+        for partition in partitions {
+            for (table_name, _) in partition.payload.values() {
+                keys.entry(table_name.clone())
+                    .or_default()
+                    .push(partition.key.to_string());
+            }
+        }
+        keys.values_mut().for_each(|v| v.sort());
+        keys
+    }
 
     // Parse `lp` into a table-keyed MutableBatch map.
     pub(crate) fn lp_to_writes(lp: &str) -> HashMap<TableId, (String, MutableBatch)> {
@@ -136,16 +229,11 @@ mod tests {
             paste::paste! {
                 #[tokio::test]
                 async fn [<test_write_ $name>]() {
-                    let partition_template = PartitionTemplate {
-                        parts: vec![TemplatePart::TimeFormat("%Y-%m-%d".to_owned())],
-                    };
-
-                    let partitioner = Partitioner::new(partition_template);
-                    let ns = NamespaceName::new("bananas").expect("valid db name");
+                    let partitioner = Partitioner::new(new_cache(Some(new_schema())), daily_template());
 
                     let writes = lp_to_writes($lp);
 
-                    let handler_ret = partitioner.write(&ns, NamespaceId::new(42), writes, None).await;
+                    let handler_ret = partitioner.write(&NAMESPACE, NamespaceId::new(42), writes, None).await;
                     assert_matches!(handler_ret, $($want_handler_ret)+);
 
                     // Check the partition -> table mapping.
@@ -285,4 +373,75 @@ mod tests {
         ],
         want_handler_ret = Ok(_)
     );
+
+    #[tokio::test]
+    async fn test_namespace_partition_template() {
+        let mut schema = new_schema();
+        schema.partition_template = Some(PartitionTemplate {
+            parts: vec![TemplatePart::TimeFormat("%Y-%m-%d %H".to_owned())],
+        });
+        let partitioner = Partitioner::new(new_cache(Some(schema)), daily_template());
+
+        let got = partition_keys(
+            &partitioner,
+            "\
+                bananas,region=west val=42i 1\n\
+                bananas,region=east val=42i 7200000000000\n\
+            ",
+        )
+        .await;
+
+        assert_eq!(
+            got["bananas"],
+            ["1970-01-01 00".to_string(), "1970-01-01 02".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_table_partition_template() {
+        let mut schema = new_schema();
+        schema.partition_template = Some(PartitionTemplate {
+            parts: vec![TemplatePart::TimeFormat("%Y-%m-%d %H".to_owned())],
+        });
+        let mut table = TableSchema::new(TableId::new(1));
+        table.partition_template = Some(PartitionTemplate {
+            parts: vec![TemplatePart::Column("region".to_owned())],
+        });
+        schema.tables.insert("bananas".to_string(), table);
+        let partitioner = Partitioner::new(new_cache(Some(schema)), daily_template());
+
+        let got = partition_keys(
+            &partitioner,
+            "\
+                bananas,region=west val=42i 1\n\
+                bananas,region=east val=42i 7200000000000\n\
+                platanos,region=west val=42i 1\n\
+            ",
+        )
+        .await;
+
+        // The table template takes precedence over the namespace template,
+        // which in turn is used for tables without a template.
+        assert_eq!(
+            got["bananas"],
+            ["region_east".to_string(), "region_west".to_string()]
+        );
+        assert_eq!(got["platanos"], ["1970-01-01 00".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_namespace_not_found() {
+        let partitioner = Partitioner::new(new_cache(None), daily_template());
+
+        let got = partitioner
+            .write(
+                &NAMESPACE,
+                NamespaceId::new(42),
+                lp_to_writes("bananas val=42i 1"),
+                None,
+            )
+            .await;
+
+        assert_matches!(got, Err(PartitionError::NamespaceLookup(_)));
+    }
 }
@@ -0,0 +1,504 @@
+use std::{fmt::Display, time::Duration};
+
+use async_trait::async_trait;
+use data_types::{NamespaceId, NamespaceName};
+use hashbrown::HashMap;
+use iox_time::{SystemProvider, Time, TimeProvider};
+use metric::U64Counter;
+use mutable_batch::MutableBatch;
+use observability_deps::tracing::*;
+use parking_lot::Mutex;
+use thiserror::Error;
+use trace::ctx::SpanContext;
+
+use super::DmlHandler;
+use crate::namespace_cache::NamespaceCache;
+
+/// The write rate limits that can be configured for a namespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitKind {
+    /// The number of lines (rows) written per second.
+    Lines,
+    /// The number of bytes of data written per second, measured as the
+    /// in-memory size of the decoded write (see [`MutableBatch::size_data()`]).
+    Bytes,
+}
+
+impl Display for RateLimitKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lines => write!(f, "lines"),
+            Self::Bytes => write!(f, "bytes"),
+        }
+    }
+}
+
+/// Errors emitted during write rate limiting.
+#[derive(Debug, Error)]
+pub enum RateLimitError {
+    /// The requested namespace could not be found in the catalog.
+    #[error("failed to read namespace schema from catalog: {0}")]
+    NamespaceLookup(iox_catalog::interface::Error),
+
+    /// The namespace has exceeded one of its write rate limits.
+    #[error(
+        "namespace {namespace} exceeded its {kind} per second write limit, \
+        retry after {}s",
+        retry_after.as_secs_f64().ceil()
+    )]
+    Exceeded {
+        /// The namespace that was written to.
+        namespace: String,
+        /// The limit that was exceeded.
+        kind: RateLimitKind,
+        /// The time after which the write is expected to be accepted.
+        retry_after: Duration,
+    },
+}
+
+impl RateLimitError {
+    /// Returns the duration the caller should wait before retrying the write,
+    /// if the write was rejected for exceeding a rate limit.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::NamespaceLookup(_) => None,
+            Self::Exceeded { retry_after, .. } => Some(*retry_after),
+        }
+    }
+}
+
+/// A token bucket refilled at `rate` tokens per second, holding at most one
+/// second worth of tokens.
+///
+/// A write is admitted whenever the bucket is not empty, which allows a single
+/// write to exceed the per-second limit; the bucket then goes into debt and
+/// subsequent writes are rejected until the debt has been repaid.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Time,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, now: Time) -> Self {
+        Self {
+            tokens: rate as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Refill the bucket for the time elapsed since the last refill.
+    fn refill(&mut self, rate: u64, now: Time) {
+        let elapsed = now
+            .checked_duration_since(self.last_refill)
+            .unwrap_or_default();
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * rate as f64).min(rate as f64);
+        self.last_refill = now;
+    }
+
+    /// Returns how long to wait before a write can be admitted, or [`None`]
+    /// if a write can be admitted immediately.
+    fn wait_time(&self, rate: u64) -> Option<Duration> {
+        if self.tokens > 0.0 {
+            return None;
+        }
+        Some(Duration::from_secs_f64(-self.tokens / rate as f64))
+    }
+}
+
+/// The token buckets of a single namespace.
+#[derive(Debug, Default)]
+struct NamespaceBuckets {
+    lines: Option<TokenBucket>,
+    bytes: Option<TokenBucket>,
+}
+
+/// A [`DmlHandler`] implementation that enforces the per-namespace write rate
+/// limits.
+///
+/// Each namespace may be configured with a maximum number of lines and / or
+/// bytes per second. Writes are rejected with [`RateLimitError::Exceeded`]
+/// (carrying the time after which a retry is expected to succeed) once a
+/// namespace has used up its budget for the current second.
+///
+/// The byte limit is charged with the in-memory size of the decoded write
+/// ([`MutableBatch::size_data()`]) rather than the size of the request body on
+/// the wire, so compressed or verbose line protocol is charged for the data it
+/// contains.
+///
+/// The rate limits are loaded from the provided [`NamespaceCache`]
+/// implementation and state is tracked locally, so each router instance
+/// enforces the configured limits independently.
+#[derive(Debug)]
+pub struct RateLimiter<C, P = SystemProvider> {
+    cache: C,
+    time_provider: P,
+
+    buckets: Mutex<HashMap<NamespaceName<'static>, NamespaceBuckets>>,
+
+    rejected_lines: U64Counter,
+    rejected_bytes: U64Counter,
+}
+
+impl<C> RateLimiter<C> {
+    /// Initialise a new [`RateLimiter`], loading the namespace rate limits from
+    /// `cache`.
+    pub fn new(cache: C, metrics: &metric::Registry) -> Self {
+        Self::new_with_time_provider(cache, SystemProvider::default(), metrics)
+    }
+}
+
+impl<C, P> RateLimiter<C, P> {
+    fn new_with_time_provider(cache: C, time_provider: P, metrics: &metric::Registry) -> Self {
+        let rejected = metrics.register_metric::<U64Counter>(
+            "rate_limit_rejected_writes",
+            "number of writes rejected for exceeding a namespace write rate limit",
+        );
+
+        Self {
+            cache,
+            time_provider,
+            buckets: Default::default(),
+            rejected_lines: rejected.recorder(&[("limit", "lines")]),
+            rejected_bytes: rejected.recorder(&[("limit", "bytes")]),
+        }
+    }
+}
+
+#[async_trait]
+impl<C, P> DmlHandler for RateLimiter<C, P>
+where
+    C: NamespaceCache<ReadError = iox_catalog::interface::Error>, // The handler expects the cache to read from the catalog if necessary.
+    P: TimeProvider,
+{
+    type WriteError = RateLimitError;
+
+    type WriteInput = HashMap<String, MutableBatch>;
+    type WriteOutput = Self::WriteInput;
+
+    /// Admit the write if the namespace is within its rate limits.
+    async fn write(
+        &self,
+        namespace: &NamespaceName<'static>,
+        _namespace_id: NamespaceId,
+        batch: Self::WriteInput,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<Self::WriteOutput, Self::WriteError> {
+        // Try to fetch the namespace schema through the cache.
+        let schema = self
+            .cache
+            .get_schema(namespace)
+            .await
+            .map_err(RateLimitError::NamespaceLookup)?;
+
+        let limits = [
+            (RateLimitKind::Lines, schema.max_lines_per_second),
+            (RateLimitKind::Bytes, schema.max_bytes_per_second),
+        ];
+        if limits.iter().all(|(_, rate)| rate.is_none()) {
+            // Drop any state left over from a limit that has since been
+            // removed.
+            self.buckets.lock().remove(namespace);
+            return Ok(batch);
+        }
+
+        let (lines, bytes) = batch.values().fold((0, 0), |(lines, bytes), b| {
+            (lines + b.rows(), bytes + b.size_data())
+        });
+
+        let now = self.time_provider.now();
+        let mut buckets = self.buckets.lock();
+        let ns_buckets = buckets.entry(namespace.clone()).or_default();
+
+        // Refill all the configured buckets, and ensure none of them are
+        // empty before consuming from any of them so a rejected write does not
+        // use up the budget of the other limit.
+        for (kind, rate) in limits {
+            let bucket = match kind {
+                RateLimitKind::Lines => &mut ns_buckets.lines,
+                RateLimitKind::Bytes => &mut ns_buckets.bytes,
+            };
+
+            let rate = match rate {
+                Some(v) => v,
+                None => {
+                    *bucket = None;
+                    continue;
+                }
+            };
+
+            let bucket = bucket.get_or_insert_with(|| TokenBucket::new(rate, now));
+            bucket.refill(rate, now);
+
+            if let Some(retry_after) = bucket.wait_time(rate) {
+                match kind {
+                    RateLimitKind::Lines => self.rejected_lines.inc(1),
+                    RateLimitKind::Bytes => self.rejected_bytes.inc(1),
+                }
+                debug!(
+                    %namespace,
+                    %kind,
+                    ?retry_after,
+                    "write rejected by namespace rate limit"
+                );
+                return Err(RateLimitError::Exceeded {
+                    namespace: namespace.to_string(),
+                    kind,
+                    retry_after,
+                });
+            }
+        }
+
+        if let Some(bucket) = ns_buckets.lines.as_mut() {
+            bucket.tokens -= lines as f64;
+        }
+        if let Some(bucket) = ns_buckets.bytes.as_mut() {
+            bucket.tokens -= bytes as f64;
+        }
+
+        Ok(batch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use assert_matches::assert_matches;
+    use iox_tests::{TestCatalog, TestNamespace};
+    use metric::{Attributes, Metric};
+    use once_cell::sync::Lazy;
+
+    use super::*;
+    use crate::namespace_cache::{MemoryNamespaceCache, ReadThroughCache};
+
+    static NAMESPACE: Lazy<NamespaceName<'static>> = Lazy::new(|| "bananas".try_into().unwrap());
+
+    fn setup_test_cache(
+        catalog: Arc<TestCatalog>,
+    ) -> Arc<ReadThroughCache<Arc<MemoryNamespaceCache>>> {
+        Arc::new(ReadThroughCache::new(
+            Arc::new(MemoryNamespaceCache::default()),
+            catalog.catalog(),
+        ))
+    }
+
+    fn assert_rejected_count(metrics: &metric::Registry, limit: &'static str, want: u64) {
+        let got = metrics
+            .get_instrument::<Metric<U64Counter>>("rate_limit_rejected_writes")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[("limit", limit)]))
+            .expect("failed to get observer")
+            .fetch();
+        assert_eq!(got, want);
+    }
+
+    #[tokio::test]
+    async fn test_no_limits() {
+        let (catalog, _namespace) = test_setup().await;
+        let metrics = metric::Registry::default();
+        let time = Arc::new(iox_time::MockProvider::new(Time::from_timestamp_nanos(0)));
+        let handler = RateLimiter::new_with_time_provider(
+            setup_test_cache(catalog),
+            Arc::clone(&time),
+            &metrics,
+        );
+
+        // Without any configured limits, all writes are admitted.
+        for _ in 0..10 {
+            let writes = lp_to_writes("bananas,tag1=A val=42i 1\nbananas,tag1=B val=42i 2");
+            handler
+                .write(&NAMESPACE, NamespaceId::new(42), writes, None)
+                .await
+                .expect("write should be admitted");
+        }
+
+        assert_rejected_count(&metrics, "lines", 0);
+        assert_rejected_count(&metrics, "bytes", 0);
+    }
+
+    #[tokio::test]
+    async fn test_lines_per_second_limit() {
+        let (catalog, _namespace) = test_setup().await;
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .namespaces()
+            .update_lines_per_second_limit(&NAMESPACE, Some(2))
+            .await
+            .unwrap();
+
+        let metrics = metric::Registry::default();
+        let time = Arc::new(iox_time::MockProvider::new(Time::from_timestamp_nanos(0)));
+        let handler = RateLimiter::new_with_time_provider(
+            setup_test_cache(catalog),
+            Arc::clone(&time),
+            &metrics,
+        );
+
+        // The first write of 3 lines is admitted, exceeding the limit of 2
+        // lines per second.
+        let writes = lp_to_writes("bananas val=1i 1\nbananas val=2i 2\nbananas val=3i 3");
+        handler
+            .write(&NAMESPACE, NamespaceId::new(42), writes, None)
+            .await
+            .expect("write should be admitted");
+
+        // The next write must wait for the excess line to be paid back, and
+        // for at least one more line of budget to accumulate.
+        let err = handler
+            .write(
+                &NAMESPACE,
+                NamespaceId::new(42),
+                lp_to_writes("bananas val=4i 4"),
+                None,
+            )
+            .await
+            .expect_err("write should be rejected");
+        assert_matches!(
+            &err,
+            RateLimitError::Exceeded {
+                kind: RateLimitKind::Lines,
+                retry_after,
+                ..
+            } => {
+                assert_eq!(*retry_after, Duration::from_millis(500));
+            }
+        );
+        assert_eq!(err.retry_after(), Some(Duration::from_millis(500)));
+        assert_rejected_count(&metrics, "lines", 1);
+        assert_rejected_count(&metrics, "bytes", 0);
+
+        // Once enough time has passed, writes are admitted again.
+        time.inc(Duration::from_secs(1));
+        handler
+            .write(
+                &NAMESPACE,
+                NamespaceId::new(42),
+                lp_to_writes("bananas val=4i 4"),
+                None,
+            )
+            .await
+            .expect("write should be admitted");
+    }
+
+    #[tokio::test]
+    async fn test_bytes_per_second_limit() {
+        let (catalog, _namespace) = test_setup().await;
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .namespaces()
+            .update_bytes_per_second_limit(&NAMESPACE, Some(1))
+            .await
+            .unwrap();
+
+        let metrics = metric::Registry::default();
+        let time = Arc::new(iox_time::MockProvider::new(Time::from_timestamp_nanos(0)));
+        let handler = RateLimiter::new_with_time_provider(
+            setup_test_cache(catalog),
+            Arc::clone(&time),
+            &metrics,
+        );
+
+        handler
+            .write(
+                &NAMESPACE,
+                NamespaceId::new(42),
+                lp_to_writes("bananas val=1i 1"),
+                None,
+            )
+            .await
+            .expect("write should be admitted");
+
+        let err = handler
+            .write(
+                &NAMESPACE,
+                NamespaceId::new(42),
+                lp_to_writes("bananas val=2i 2"),
+                None,
+            )
+            .await
+            .expect_err("write should be rejected");
+        assert_matches!(
+            err,
+            RateLimitError::Exceeded {
+                kind: RateLimitKind::Bytes,
+                ..
+            }
+        );
+        assert_rejected_count(&metrics, "lines", 0);
+        assert_rejected_count(&metrics, "bytes", 1);
+    }
+
+    #[tokio::test]
+    async fn test_limits_are_per_namespace() {
+        let (catalog, _namespace) = test_setup().await;
+        catalog.create_namespace_1hr_retention("platanos").await;
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .namespaces()
+            .update_lines_per_second_limit(&NAMESPACE, Some(1))
+            .await
+            .unwrap();
+
+        let metrics = metric::Registry::default();
+        let time = Arc::new(iox_time::MockProvider::new(Time::from_timestamp_nanos(0)));
+        let handler = RateLimiter::new_with_time_provider(
+            setup_test_cache(catalog),
+            Arc::clone(&time),
+            &metrics,
+        );
+
+        handler
+            .write(
+                &NAMESPACE,
+                NamespaceId::new(42),
+                lp_to_writes("bananas val=1i 1"),
+                None,
+            )
+            .await
+            .expect("write should be admitted");
+        handler
+            .write(
+                &NAMESPACE,
+                NamespaceId::new(42),
+                lp_to_writes("bananas val=1i 1"),
+                None,
+            )
+            .await
+            .expect_err("write should be rejected");
+
+        // Writes to another namespace without limits are unaffected.
+        let other = NamespaceName::try_from("platanos").unwrap();
+        handler
+            .write(
+                &other,
+                NamespaceId::new(24),
+                lp_to_writes("bananas val=1i 1"),
+                None,
+            )
+            .await
+            .expect("write should be admitted");
+    }
+
+    // Parse `lp` into a table-keyed MutableBatch map.
+    fn lp_to_writes(lp: &str) -> HashMap<String, MutableBatch> {
+        let (writes, _) = mutable_batch_lp::lines_to_batches_stats(lp, 42)
+            .expect("failed to build test writes from LP");
+        writes
+    }
+
+    /// Initialise an in-memory [`MemCatalog`] and create a single namespace
+    /// named [`NAMESPACE`].
+    async fn test_setup() -> (Arc<TestCatalog>, Arc<TestNamespace>) {
+        let catalog = TestCatalog::new();
+        let namespace = catalog.create_namespace_1hr_retention(&NAMESPACE).await;
+
+        (catalog, namespace)
+    }
+}
@@ -1,7 +1,9 @@
 use std::{fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
-use generated_types::influxdata::iox::ingester::v1::WriteRequest;
+use generated_types::influxdata::iox::ingester::v1::{
+    DeleteRequest, InvalidateColumnsRequest, WriteRequest,
+};
 
 use super::{circuit_breaker::CircuitBreaker, client::WriteClient, RpcWriteError};
 
@@ -100,6 +102,18 @@ where
         self.state.observe(&res);
         res
     }
+
+    async fn delete(&self, op: DeleteRequest) -> Result<(), RpcWriteError> {
+        let res = self.inner.delete(op).await;
+        self.state.observe(&res);
+        res
+    }
+
+    async fn invalidate_columns(&self, op: InvalidateColumnsRequest) -> Result<(), RpcWriteError> {
+        let res = self.inner.invalidate_columns(op).await;
+        self.state.observe(&res);
+        res
+    }
 }
 
 #[cfg(test)]
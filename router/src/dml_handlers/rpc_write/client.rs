@@ -2,16 +2,23 @@
 
 use async_trait::async_trait;
 use generated_types::influxdata::iox::ingester::v1::{
-    write_service_client::WriteServiceClient, WriteRequest,
+    write_service_client::WriteServiceClient, DeleteRequest, InvalidateColumnsRequest, WriteRequest,
 };
 
 use super::RpcWriteError;
 
 /// An abstract RPC client that pushes `op` to an opaque receiver.
 #[async_trait]
-pub(super) trait WriteClient: Send + Sync + std::fmt::Debug {
+pub(crate) trait WriteClient: Send + Sync + std::fmt::Debug {
     /// Write `op` and wait for a response.
     async fn write(&self, op: WriteRequest) -> Result<(), RpcWriteError>;
+
+    /// Apply the delete `op` to the buffered data and wait for a response.
+    async fn delete(&self, op: DeleteRequest) -> Result<(), RpcWriteError>;
+
+    /// Discard the cached column IDs of the table in `op` and wait for a
+    /// response.
+    async fn invalidate_columns(&self, op: InvalidateColumnsRequest) -> Result<(), RpcWriteError>;
 }
 
 /// An implementation of [`WriteClient`] for the tonic gRPC client.
@@ -21,6 +28,16 @@ impl WriteClient for WriteServiceClient<tonic::transport::Channel> {
         WriteServiceClient::write(&mut self.clone(), op).await?;
         Ok(())
     }
+
+    async fn delete(&self, op: DeleteRequest) -> Result<(), RpcWriteError> {
+        WriteServiceClient::delete(&mut self.clone(), op).await?;
+        Ok(())
+    }
+
+    async fn invalidate_columns(&self, op: InvalidateColumnsRequest) -> Result<(), RpcWriteError> {
+        WriteServiceClient::invalidate_columns(&mut self.clone(), op).await?;
+        Ok(())
+    }
 }
 
 /// Mocks for testing
@@ -31,6 +48,8 @@ pub mod mock {
 
     struct State {
         calls: Vec<WriteRequest>,
+        delete_calls: Vec<DeleteRequest>,
+        invalidate_columns_calls: Vec<InvalidateColumnsRequest>,
         ret: Box<dyn Iterator<Item = Result<(), RpcWriteError>> + Send + Sync>,
     }
 
@@ -53,6 +72,8 @@ pub mod mock {
             Self {
                 state: Mutex::new(State {
                     calls: Default::default(),
+                    delete_calls: Default::default(),
+                    invalidate_columns_calls: Default::default(),
                     ret: Box::new(iter::repeat_with(|| Ok(()))),
                 }),
             }
@@ -65,8 +86,19 @@ pub mod mock {
             self.state.lock().calls.clone()
         }
 
+        /// Retrieve the delete requests that this mock received.
+        pub fn delete_calls(&self) -> Vec<DeleteRequest> {
+            self.state.lock().delete_calls.clone()
+        }
+
+        /// Retrieve the column invalidation requests that this mock received.
+        pub fn invalidate_columns_calls(&self) -> Vec<InvalidateColumnsRequest> {
+            self.state.lock().invalidate_columns_calls.clone()
+        }
+
         /// Read values off of the provided iterator and return them for calls
-        /// to [`Self::write()`].
+        /// to [`Self::write()`], [`Self::delete()`] and
+        /// [`Self::invalidate_columns()`].
         #[cfg(test)]
         pub(crate) fn with_ret<T, U>(self, ret: T) -> Self
         where
@@ -85,5 +117,20 @@ pub mod mock {
             guard.calls.push(op);
             guard.ret.next().expect("no mock response")
         }
+
+        async fn delete(&self, op: DeleteRequest) -> Result<(), RpcWriteError> {
+            let mut guard = self.state.lock();
+            guard.delete_calls.push(op);
+            guard.ret.next().expect("no mock response")
+        }
+
+        async fn invalidate_columns(
+            &self,
+            op: InvalidateColumnsRequest,
+        ) -> Result<(), RpcWriteError> {
+            let mut guard = self.state.lock();
+            guard.invalidate_columns_calls.push(op);
+            guard.ret.next().expect("no mock response")
+        }
     }
 }
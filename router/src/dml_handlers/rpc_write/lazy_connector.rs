@@ -10,7 +10,7 @@ use std::{
 
 use async_trait::async_trait;
 use generated_types::influxdata::iox::ingester::v1::{
-    write_service_client::WriteServiceClient, WriteRequest,
+    write_service_client::WriteServiceClient, DeleteRequest, InvalidateColumnsRequest, WriteRequest,
 };
 use observability_deps::tracing::*;
 use parking_lot::Mutex;
@@ -82,23 +82,25 @@ impl LazyConnector {
     }
 }
 
-#[async_trait]
-impl WriteClient for LazyConnector {
-    async fn write(&self, op: WriteRequest) -> Result<(), RpcWriteError> {
+impl LazyConnector {
+    /// Return the connection to the upstream, if established.
+    fn connection(&self) -> Result<Channel, RpcWriteError> {
         let conn = self.connection.lock().clone();
-        let conn =
-            conn.ok_or_else(|| RpcWriteError::UpstreamNotConnected(self.addr.uri().to_string()))?;
+        conn.ok_or_else(|| RpcWriteError::UpstreamNotConnected(self.addr.uri().to_string()))
+    }
 
-        match WriteServiceClient::new(conn).write(op).await {
+    /// Record the outcome of a request to the upstream, returning it.
+    fn observe<T>(&self, res: Result<T, tonic::Status>) -> Result<(), RpcWriteError> {
+        match res.map_err(RpcWriteError::from) {
             Err(e) if is_envoy_unavailable_error(&e) => {
                 warn!(error=%e, "detected envoy proxy upstream network error translation, reconnecting");
                 self.consecutive_errors
                     .store(RECONNECT_ERROR_COUNT + 1, Ordering::Relaxed);
-                return Err(e);
+                Err(e)
             }
             Err(e) => {
                 self.consecutive_errors.fetch_add(1, Ordering::Relaxed);
-                return Err(e);
+                Err(e)
             }
             Ok(_) => {
                 self.consecutive_errors.store(0, Ordering::Relaxed);
@@ -108,6 +110,24 @@ impl WriteClient for LazyConnector {
     }
 }
 
+#[async_trait]
+impl WriteClient for LazyConnector {
+    async fn write(&self, op: WriteRequest) -> Result<(), RpcWriteError> {
+        let conn = self.connection()?;
+        self.observe(WriteServiceClient::new(conn).write(op).await)
+    }
+
+    async fn delete(&self, op: DeleteRequest) -> Result<(), RpcWriteError> {
+        let conn = self.connection()?;
+        self.observe(WriteServiceClient::new(conn).delete(op).await)
+    }
+
+    async fn invalidate_columns(&self, op: InvalidateColumnsRequest) -> Result<(), RpcWriteError> {
+        let conn = self.connection()?;
+        self.observe(WriteServiceClient::new(conn).invalidate_columns(op).await)
+    }
+}
+
 /// Returns `true` if `e` is a gRPC error with the status [`Code::Unavailable`],
 /// and a metadata entry indicating the response was generated by an envoy proxy
 /// instance.
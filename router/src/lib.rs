@@ -129,6 +129,7 @@
 )]
 #![allow(clippy::missing_docs_in_private_items)]
 
+pub mod delete_handler;
 pub mod dml_handlers;
 pub mod namespace_cache;
 pub mod namespace_resolver;
@@ -11,6 +11,9 @@ pub mod metrics;
 mod read_through_cache;
 pub use read_through_cache::*;
 
+mod ttl;
+pub use ttl::*;
+
 use std::{error::Error, fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
@@ -38,4 +41,10 @@ pub trait NamespaceCache: Debug + Send + Sync {
         namespace: NamespaceName<'static>,
         schema: impl Into<Arc<NamespaceSchema>>,
     ) -> Option<Arc<NamespaceSchema>>;
+
+    /// Remove the [`NamespaceSchema`] mapped to `namespace` from the cache,
+    /// returning it if it was present.
+    ///
+    /// Subsequent reads for `namespace` observe a cache miss.
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>>;
 }
@@ -46,6 +46,10 @@ impl NamespaceCache for Arc<MemoryNamespaceCache> {
     ) -> Option<Arc<NamespaceSchema>> {
         self.cache.write().insert(namespace, schema.into())
     }
+
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        self.cache.write().remove(namespace)
+    }
 }
 
 #[cfg(test)]
@@ -75,6 +79,9 @@ mod tests {
             max_columns_per_table: 50,
             max_tables: 24,
             retention_period_ns: Some(876),
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
         };
         assert!(cache.put_schema(ns.clone(), schema1.clone()).is_none());
         assert_eq!(
@@ -90,6 +97,9 @@ mod tests {
             max_columns_per_table: 10,
             max_tables: 42,
             retention_period_ns: Some(876),
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
         };
 
         assert_eq!(
@@ -102,5 +112,14 @@ mod tests {
             *cache.get_schema(&ns).await.expect("lookup failure"),
             schema2
         );
+
+        assert_eq!(
+            *cache
+                .remove_schema(&ns)
+                .expect("should have existing schema"),
+            schema2
+        );
+        assert_matches!(cache.get_schema(&ns).await, Err(CacheMissErr { .. }));
+        assert!(cache.remove_schema(&ns).is_none());
     }
 }
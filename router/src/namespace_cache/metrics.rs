@@ -139,6 +139,19 @@ where
             }
         }
     }
+
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        let res = self.inner.remove_schema(namespace);
+
+        // Remove the evicted namespace stats from the counts.
+        if let Some(v) = &res {
+            let stats = NamespaceStats::new(v);
+            self.table_count.dec(stats.table_count);
+            self.column_count.dec(stats.column_count);
+        }
+
+        res
+    }
 }
 
 #[derive(Debug)]
@@ -195,6 +208,7 @@ mod tests {
                     TableSchema {
                         id: TableId::new(i as _),
                         columns,
+                        partition_template: None,
                     },
                 )
             })
@@ -208,6 +222,9 @@ mod tests {
             max_columns_per_table: 100,
             max_tables: 42,
             retention_period_ns: None,
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
         }
     }
 
@@ -390,5 +407,15 @@ mod tests {
             ("result", "hit"),
             1,
         );
+
+        // Remove the new namespace
+        assert!(cache.remove_schema(&ns).is_some());
+        assert_eq!(cache.table_count.observe(), Observation::U64Gauge(2));
+        assert_eq!(cache.column_count.observe(), Observation::U64Gauge(11));
+
+        // Removing it again is a no-op
+        assert!(cache.remove_schema(&ns).is_none());
+        assert_eq!(cache.table_count.observe(), Observation::U64Gauge(2));
+        assert_eq!(cache.column_count.observe(), Observation::U64Gauge(11));
     }
 }
@@ -93,6 +93,10 @@ where
     ) -> Option<Arc<NamespaceSchema>> {
         self.inner_cache.put_schema(namespace, schema)
     }
+
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        self.inner_cache.remove_schema(namespace)
+    }
 }
 
 #[cfg(test)]
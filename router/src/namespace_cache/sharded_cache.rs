@@ -43,6 +43,10 @@ where
     ) -> Option<Arc<NamespaceSchema>> {
         self.shards.hash(&namespace).put_schema(namespace, schema)
     }
+
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        self.shards.hash(namespace).remove_schema(namespace)
+    }
 }
 
 #[cfg(test)]
@@ -76,6 +80,9 @@ mod tests {
             max_columns_per_table: 7,
             max_tables: 42,
             retention_period_ns: None,
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
         }
     }
 
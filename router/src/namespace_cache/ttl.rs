@@ -0,0 +1,177 @@
+//! Time-based expiry of [`NamespaceSchema`] cache entries.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use data_types::{NamespaceName, NamespaceSchema};
+use hashbrown::HashMap;
+use iox_time::{SystemProvider, Time, TimeProvider};
+use observability_deps::tracing::*;
+use parking_lot::Mutex;
+
+use super::{memory::CacheMissErr, NamespaceCache};
+
+/// A [`TtlCache`] decorates a [`NamespaceCache`], expiring each entry once
+/// `ttl` has elapsed since it was first inserted.
+///
+/// Overwriting an existing entry (such as when a write adds new columns to
+/// the schema) does NOT reset its age - changes made to the catalog by other
+/// routers, or through the management APIs of another router, are observed at
+/// most `ttl` after they are made.
+///
+/// An expired entry is removed from the inner cache and reported as a cache
+/// miss, causing a read-through cache to reload it from the catalog.
+#[derive(Debug)]
+pub struct TtlCache<T, P = SystemProvider> {
+    inner: T,
+    ttl: Duration,
+    time_provider: P,
+
+    /// The time each namespace currently in `inner` was first inserted.
+    inserted_at: Mutex<HashMap<NamespaceName<'static>, Time>>,
+}
+
+impl<T> TtlCache<T> {
+    /// Expire entries of `inner` once they are older than `ttl`.
+    pub fn new(inner: T, ttl: Duration) -> Self {
+        Self::new_with_time_provider(inner, ttl, SystemProvider::default())
+    }
+}
+
+impl<T, P> TtlCache<T, P> {
+    /// Expire entries of `inner` once they are older than `ttl`, as measured
+    /// by `time_provider`.
+    pub fn new_with_time_provider(inner: T, ttl: Duration, time_provider: P) -> Self {
+        Self {
+            inner,
+            ttl,
+            time_provider,
+            inserted_at: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<T, P> NamespaceCache for Arc<TtlCache<T, P>>
+where
+    T: NamespaceCache<ReadError = CacheMissErr>,
+    P: TimeProvider,
+{
+    type ReadError = CacheMissErr;
+
+    async fn get_schema(
+        &self,
+        namespace: &NamespaceName<'static>,
+    ) -> Result<Arc<NamespaceSchema>, Self::ReadError> {
+        let expired = self
+            .inserted_at
+            .lock()
+            .get(namespace)
+            .and_then(|t| self.time_provider.now().checked_duration_since(*t))
+            .map(|age| age >= self.ttl)
+            .unwrap_or_default();
+
+        if expired {
+            debug!(%namespace, "expiring cached namespace schema");
+            self.remove_schema(namespace);
+            return Err(CacheMissErr {
+                namespace: namespace.clone(),
+            });
+        }
+
+        self.inner.get_schema(namespace).await
+    }
+
+    fn put_schema(
+        &self,
+        namespace: NamespaceName<'static>,
+        schema: impl Into<Arc<NamespaceSchema>>,
+    ) -> Option<Arc<NamespaceSchema>> {
+        let now = self.time_provider.now();
+        self.inserted_at
+            .lock()
+            .entry(namespace.clone())
+            .or_insert(now);
+        self.inner.put_schema(namespace, schema)
+    }
+
+    fn remove_schema(&self, namespace: &NamespaceName<'static>) -> Option<Arc<NamespaceSchema>> {
+        self.inserted_at.lock().remove(namespace);
+        self.inner.remove_schema(namespace)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::{NamespaceId, QueryPoolId, TopicId};
+    use iox_time::MockProvider;
+
+    use super::*;
+    use crate::namespace_cache::MemoryNamespaceCache;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    fn schema_with_id(id: i64) -> NamespaceSchema {
+        NamespaceSchema {
+            id: NamespaceId::new(id),
+            topic_id: TopicId::new(1),
+            query_pool_id: QueryPoolId::new(1),
+            tables: Default::default(),
+            max_columns_per_table: 7,
+            max_tables: 42,
+            retention_period_ns: None,
+            partition_template: None,
+            max_lines_per_second: None,
+            max_bytes_per_second: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expiry() {
+        let ns = NamespaceName::new("test").expect("namespace name is valid");
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let cache = Arc::new(TtlCache::new_with_time_provider(
+            Arc::new(MemoryNamespaceCache::default()),
+            TTL,
+            Arc::clone(&time_provider),
+        ));
+
+        assert_matches!(cache.get_schema(&ns).await, Err(CacheMissErr { .. }));
+
+        assert!(cache.put_schema(ns.clone(), schema_with_id(1)).is_none());
+        time_provider.inc(TTL / 2);
+        assert_eq!(cache.get_schema(&ns).await.unwrap().id.get(), 1);
+
+        // Overwriting the entry does not reset its age.
+        assert!(cache.put_schema(ns.clone(), schema_with_id(2)).is_some());
+        time_provider.inc(TTL / 2);
+        assert_matches!(cache.get_schema(&ns).await, Err(CacheMissErr { namespace }) => {
+            assert_eq!(namespace, ns);
+        });
+
+        // The expired entry was removed, and a new entry starts a new TTL.
+        assert!(cache.put_schema(ns.clone(), schema_with_id(3)).is_none());
+        time_provider.inc(TTL / 2);
+        assert_eq!(cache.get_schema(&ns).await.unwrap().id.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_remove_resets_age() {
+        let ns = NamespaceName::new("test").expect("namespace name is valid");
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let cache = Arc::new(TtlCache::new_with_time_provider(
+            Arc::new(MemoryNamespaceCache::default()),
+            TTL,
+            Arc::clone(&time_provider),
+        ));
+
+        cache.put_schema(ns.clone(), schema_with_id(1));
+        time_provider.inc(TTL / 2);
+        assert!(cache.remove_schema(&ns).is_some());
+
+        cache.put_schema(ns.clone(), schema_with_id(2));
+        time_provider.inc(TTL / 2);
+        assert_eq!(cache.get_schema(&ns).await.unwrap().id.get(), 2);
+    }
+}
@@ -1,7 +1,9 @@
 //! An trait to abstract resolving a[`NamespaceName`] to [`NamespaceId`], and a
 //! collection of composable implementations.
+use std::sync::Arc;
+
 use async_trait::async_trait;
-use data_types::{NamespaceId, NamespaceName};
+use data_types::{NamespaceId, NamespaceName, NamespaceSchema};
 use observability_deps::tracing::*;
 use thiserror::Error;
 
@@ -26,11 +28,32 @@ pub enum Error {
 /// An abstract resolver of [`NamespaceName`] to [`NamespaceId`].
 #[async_trait]
 pub trait NamespaceResolver: std::fmt::Debug + Send + Sync {
+    /// Return the [`NamespaceSchema`] for the given [`NamespaceName`].
+    async fn get_namespace_schema(
+        &self,
+        namespace: &NamespaceName<'static>,
+    ) -> Result<Arc<NamespaceSchema>, Error>;
+
     /// Return the [`NamespaceId`] for the given [`NamespaceName`].
     async fn get_namespace_id(
         &self,
         namespace: &NamespaceName<'static>,
-    ) -> Result<NamespaceId, Error>;
+    ) -> Result<NamespaceId, Error> {
+        Ok(self.get_namespace_schema(namespace).await?.id)
+    }
+}
+
+#[async_trait]
+impl<T> NamespaceResolver for Arc<T>
+where
+    T: NamespaceResolver,
+{
+    async fn get_namespace_schema(
+        &self,
+        namespace: &NamespaceName<'static>,
+    ) -> Result<Arc<NamespaceSchema>, Error> {
+        (**self).get_namespace_schema(namespace).await
+    }
 }
 
 /// An implementation of [`NamespaceResolver`] that resolves the [`NamespaceId`]
@@ -53,16 +76,16 @@ impl<C> NamespaceResolver for NamespaceSchemaResolver<C>
 where
     C: NamespaceCache<ReadError = iox_catalog::interface::Error>,
 {
-    async fn get_namespace_id(
+    async fn get_namespace_schema(
         &self,
         namespace: &NamespaceName<'static>,
-    ) -> Result<NamespaceId, Error> {
+    ) -> Result<Arc<NamespaceSchema>, Error> {
         // Load the namespace schema from the cache, falling back to pulling it
         // from the global catalog (if it exists).
-        match self.cache.get_schema(namespace).await {
-            Ok(v) => Ok(v.id),
-            Err(e) => return Err(Error::Lookup(e)),
-        }
+        self.cache
+            .get_schema(namespace)
+            .await
+            .map_err(Error::Lookup)
     }
 }
 
@@ -102,6 +125,9 @@ mod tests {
                 max_columns_per_table: 4,
                 max_tables: 42,
                 retention_period_ns: None,
+                partition_template: None,
+                max_lines_per_second: None,
+                max_bytes_per_second: None,
             },
         );
 
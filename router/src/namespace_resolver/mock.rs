@@ -2,44 +2,63 @@
 
 #![allow(missing_docs)]
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
-use data_types::{NamespaceId, NamespaceName};
+use data_types::{NamespaceId, NamespaceName, NamespaceSchema, QueryPoolId, TopicId};
 use parking_lot::Mutex;
 
 use super::NamespaceResolver;
 
 #[derive(Debug, Default)]
 pub struct MockNamespaceResolver {
-    map: Mutex<HashMap<NamespaceName<'static>, NamespaceId>>,
+    map: Mutex<HashMap<NamespaceName<'static>, Arc<NamespaceSchema>>>,
 }
 
 impl MockNamespaceResolver {
     pub fn new(map: HashMap<NamespaceName<'static>, NamespaceId>) -> Self {
         Self {
-            map: Mutex::new(map),
+            map: Mutex::new(
+                map.into_iter()
+                    .map(|(name, id)| (name, Arc::new(empty_schema(id))))
+                    .collect(),
+            ),
         }
     }
 
     pub fn with_mapping(self, name: impl Into<String> + 'static, id: NamespaceId) -> Self {
+        self.with_schema(name, empty_schema(id))
+    }
+
+    pub fn with_schema(self, name: impl Into<String> + 'static, schema: NamespaceSchema) -> Self {
         let name = NamespaceName::try_from(name.into()).unwrap();
-        assert!(self.map.lock().insert(name, id).is_none());
+        assert!(self.map.lock().insert(name, Arc::new(schema)).is_none());
         self
     }
 }
 
+fn empty_schema(id: NamespaceId) -> NamespaceSchema {
+    NamespaceSchema::new(
+        id,
+        TopicId::new(1),
+        QueryPoolId::new(1),
+        i32::MAX,
+        i32::MAX,
+        None,
+    )
+}
+
 #[async_trait]
 impl NamespaceResolver for MockNamespaceResolver {
-    /// Return the [`NamespaceId`] for the given [`NamespaceName`].
-    async fn get_namespace_id(
+    /// Return the [`NamespaceSchema`] for the given [`NamespaceName`].
+    async fn get_namespace_schema(
         &self,
         namespace: &NamespaceName<'static>,
-    ) -> Result<NamespaceId, super::Error> {
-        Ok(*self.map.lock().get(namespace).ok_or(super::Error::Lookup(
-            iox_catalog::interface::Error::NamespaceNotFoundByName {
+    ) -> Result<Arc<NamespaceSchema>, super::Error> {
+        Ok(Arc::clone(self.map.lock().get(namespace).ok_or(
+            super::Error::Lookup(iox_catalog::interface::Error::NamespaceNotFoundByName {
                 name: namespace.to_string(),
-            },
-        ))?)
+            }),
+        )?))
     }
 }
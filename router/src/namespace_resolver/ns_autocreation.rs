@@ -1,7 +1,7 @@
 use std::{fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
-use data_types::{NamespaceId, NamespaceName, QueryPoolId, TopicId};
+use data_types::{NamespaceName, NamespaceSchema, QueryPoolId, TopicId};
 use iox_catalog::interface::Catalog;
 use observability_deps::tracing::*;
 use thiserror::Error;
@@ -84,10 +84,10 @@ where
 {
     /// Force the creation of `namespace` if it does not already exist in the
     /// cache, before passing the request through to the inner delegate.
-    async fn get_namespace_id(
+    async fn get_namespace_schema(
         &self,
         namespace: &NamespaceName<'static>,
-    ) -> Result<NamespaceId, super::Error> {
+    ) -> Result<Arc<NamespaceSchema>, super::Error> {
         if self.cache.get_schema(namespace).await.is_err() {
             trace!(%namespace, "namespace not found in cache");
 
@@ -96,7 +96,7 @@ where
                     // The namespace is not cached, but may exist in the
                     // catalog. Delegate discovery down to the inner handler,
                     // and map the lookup error to a reject error.
-                    match self.inner.get_namespace_id(namespace).await {
+                    match self.inner.get_namespace_schema(namespace).await {
                         Ok(v) => return Ok(v),
                         Err(super::Error::Lookup(
                             iox_catalog::interface::Error::NamespaceNotFoundByName { .. },
@@ -139,7 +139,7 @@ where
             }
         }
 
-        self.inner.get_namespace_id(namespace).await
+        self.inner.get_namespace_schema(namespace).await
     }
 }
 
@@ -184,6 +184,9 @@ mod tests {
                 max_columns_per_table: 4,
                 max_tables: 42,
                 retention_period_ns: None,
+                partition_template: None,
+                max_lines_per_second: None,
+                max_bytes_per_second: None,
             },
         );
 
@@ -265,6 +268,9 @@ mod tests {
                 max_columns_per_table: iox_catalog::DEFAULT_MAX_COLUMNS_PER_TABLE,
                 retention_period_ns: TEST_RETENTION_PERIOD_NS,
                 deleted_at: None,
+                partition_template: None,
+                max_lines_per_second: None,
+                max_bytes_per_second: None,
             }
         );
     }
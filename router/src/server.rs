@@ -1,6 +1,13 @@
 //! Router server entrypoint.
 
-use self::{grpc::RpcWriteGrpcDelegate, http::HttpDelegate};
+use self::{
+    grpc::{flight::FlightIngestService, RpcWriteGrpcDelegate},
+    http::HttpDelegate,
+};
+use crate::{dml_handlers::DmlHandler, namespace_resolver::NamespaceResolver};
+use arrow_flight::flight_service_server::FlightServiceServer;
+use hashbrown::HashMap;
+use mutable_batch::MutableBatch;
 use std::sync::Arc;
 use trace::TraceCollector;
 
@@ -10,20 +17,22 @@ pub mod http;
 /// The [`RpcWriteRouterServer`] manages the lifecycle and contains all state for a
 /// `router-rpc-write` server instance.
 #[derive(Debug)]
-pub struct RpcWriteRouterServer<D, N> {
+pub struct RpcWriteRouterServer<D, N, C> {
     metrics: Arc<metric::Registry>,
     trace_collector: Option<Arc<dyn TraceCollector>>,
 
     http: HttpDelegate<D, N>,
-    grpc: RpcWriteGrpcDelegate,
+    grpc: RpcWriteGrpcDelegate<C>,
+    flight: Arc<FlightIngestService<D, N>>,
 }
 
-impl<D, N> RpcWriteRouterServer<D, N> {
-    /// Initialise a new [`RpcWriteRouterServer`] using the provided HTTP and gRPC
-    /// handlers.
+impl<D, N, C> RpcWriteRouterServer<D, N, C> {
+    /// Initialise a new [`RpcWriteRouterServer`] using the provided HTTP, gRPC
+    /// and Flight handlers.
     pub fn new(
         http: HttpDelegate<D, N>,
-        grpc: RpcWriteGrpcDelegate,
+        grpc: RpcWriteGrpcDelegate<C>,
+        flight: FlightIngestService<D, N>,
         metrics: Arc<metric::Registry>,
         trace_collector: Option<Arc<dyn TraceCollector>>,
     ) -> Self {
@@ -32,6 +41,7 @@ impl<D, N> RpcWriteRouterServer<D, N> {
             trace_collector,
             http,
             grpc,
+            flight: Arc::new(flight),
         }
     }
 
@@ -51,7 +61,18 @@ impl<D, N> RpcWriteRouterServer<D, N> {
     }
 
     /// Get a reference to the router grpc delegate.
-    pub fn grpc(&self) -> &RpcWriteGrpcDelegate {
+    pub fn grpc(&self) -> &RpcWriteGrpcDelegate<C> {
         &self.grpc
     }
 }
+
+impl<D, N, C> RpcWriteRouterServer<D, N, C>
+where
+    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()> + 'static,
+    N: NamespaceResolver + 'static,
+{
+    /// Acquire the Arrow Flight bulk ingest service.
+    pub fn flight_service(&self) -> FlightServiceServer<FlightIngestService<D, N>> {
+        FlightServiceServer::from_arc(Arc::clone(&self.flight))
+    }
+}
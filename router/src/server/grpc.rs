@@ -1,37 +1,75 @@
 //! gRPC service implementations for `router`.
 
-use data_types::{QueryPoolId, TopicId};
-use generated_types::influxdata::iox::{catalog::v1::*, namespace::v1::*, object_store::v1::*};
+pub mod flight;
+pub mod namespace;
+pub mod schema;
+pub mod table;
+
+use data_types::{NamespaceName, QueryPoolId, TopicId};
+use generated_types::influxdata::iox::{
+    catalog::v1::*, namespace::v1::*, object_store::v1::*, schema::v1::*, table::v1::*,
+};
 use iox_catalog::interface::Catalog;
 use object_store::DynObjectStore;
+use observability_deps::tracing::*;
 use service_grpc_catalog::CatalogService;
 use service_grpc_namespace::NamespaceService;
 use service_grpc_object_store::ObjectStoreService;
 use service_grpc_schema::SchemaService;
+use service_grpc_table::TableService;
 use std::sync::Arc;
 
+use self::{
+    namespace::CacheInvalidatingNamespaceService, schema::CacheInvalidatingSchemaService,
+    table::CacheInvalidatingTableService,
+};
+use crate::{dml_handlers::lazy_connector::LazyConnector, namespace_cache::NamespaceCache};
+
 /// This type manages all gRPC services exposed by a `router` using the RPC write path.
 #[derive(Debug)]
-pub struct RpcWriteGrpcDelegate {
+pub struct RpcWriteGrpcDelegate<C, W = LazyConnector> {
     catalog: Arc<dyn Catalog>,
     object_store: Arc<DynObjectStore>,
+    namespace_cache: C,
+
+    /// The ingesters buffering writes, notified when a column is renamed.
+    ingesters: Arc<[(W, Arc<str>)]>,
 
     // Temporary values during kafka -> kafkaless transition.
     topic_id: TopicId,
     query_id: QueryPoolId,
 }
 
-impl RpcWriteGrpcDelegate {
+impl<C, W> RpcWriteGrpcDelegate<C, W>
+where
+    C: NamespaceCache + Clone + 'static,
+{
     /// Create a new gRPC handler
-    pub fn new(
+    ///
+    /// Namespaces are evicted from `namespace_cache` when they are deleted or
+    /// updated through the [`NamespaceService`], or when one of their tables or
+    /// columns is deleted through the [`TableService`], or renamed through the
+    /// [`SchemaService`]. The `ingesters` discard their cached column IDs of a
+    /// table when one of its columns is renamed.
+    pub fn new<N>(
         catalog: Arc<dyn Catalog>,
         object_store: Arc<DynObjectStore>,
+        namespace_cache: C,
+        ingesters: impl IntoIterator<Item = (W, N)>,
         topic_id: TopicId,
         query_id: QueryPoolId,
-    ) -> Self {
+    ) -> Self
+    where
+        N: Into<Arc<str>>,
+    {
         Self {
             catalog,
             object_store,
+            namespace_cache,
+            ingesters: ingesters
+                .into_iter()
+                .map(|(client, name)| (client, name.into()))
+                .collect(),
             topic_id,
             query_id,
         }
@@ -40,8 +78,12 @@ impl RpcWriteGrpcDelegate {
     /// Acquire a [`SchemaService`] gRPC service implementation.
     ///
     /// [`SchemaService`]: generated_types::influxdata::iox::schema::v1::schema_service_server::SchemaService.
-    pub fn schema_service(&self) -> SchemaService {
-        SchemaService::new(Arc::clone(&self.catalog))
+    pub fn schema_service(&self) -> CacheInvalidatingSchemaService<SchemaService, C, W> {
+        CacheInvalidatingSchemaService::new(
+            SchemaService::new(Arc::clone(&self.catalog)),
+            self.namespace_cache.clone(),
+            Arc::clone(&self.ingesters),
+        )
     }
 
     /// Acquire a [`CatalogService`] gRPC service implementation.
@@ -62,10 +104,40 @@ impl RpcWriteGrpcDelegate {
     ///
     /// [`NamespaceService`]: generated_types::influxdata::iox::namespace::v1::namespace_service_server::NamespaceService.
     pub fn namespace_service(&self) -> impl namespace_service_server::NamespaceService {
-        NamespaceService::new(
-            Arc::clone(&self.catalog),
-            Some(self.topic_id),
-            Some(self.query_id),
+        CacheInvalidatingNamespaceService::new(
+            NamespaceService::new(
+                Arc::clone(&self.catalog),
+                Some(self.topic_id),
+                Some(self.query_id),
+            ),
+            self.namespace_cache.clone(),
         )
     }
+
+    /// Acquire a [`TableService`] gRPC service implementation.
+    ///
+    /// [`TableService`]: generated_types::influxdata::iox::table::v1::table_service_server::TableService.
+    pub fn table_service(&self) -> impl table_service_server::TableService {
+        CacheInvalidatingTableService::new(
+            TableService::new(Arc::clone(&self.catalog)),
+            self.namespace_cache.clone(),
+        )
+    }
+}
+
+/// Remove the schema of the namespace named `namespace` from `cache`, if
+/// present.
+fn evict_namespace<C>(cache: &C, namespace: String)
+where
+    C: NamespaceCache,
+{
+    // A namespace with an invalid name cannot be present in the cache.
+    let namespace = match NamespaceName::try_from(namespace) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    if cache.remove_schema(&namespace).is_some() {
+        debug!(%namespace, "evicted namespace schema from cache");
+    }
 }
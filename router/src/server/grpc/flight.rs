@@ -0,0 +1,703 @@
+//! An Arrow Flight service accepting FlightSQL bulk ingest writes.
+
+mod convert;
+
+pub use convert::ConversionError;
+
+use std::pin::Pin;
+
+use arrow::record_batch::RecordBatch;
+use arrow_flight::{
+    decode::FlightRecordBatchStream,
+    error::FlightError,
+    flight_service_server::FlightService as Flight,
+    sql::{Any, DoPutUpdateResult, ProstMessageExt},
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PutResult, SchemaResult, Ticket,
+};
+use authz::{Action as AuthzAction, Authorizer, Permission, Resource};
+use bytes::Bytes;
+use data_types::{NamespaceName, NamespaceNameError};
+use futures::{Stream, StreamExt, TryStreamExt};
+use hashbrown::HashMap;
+use hyper::StatusCode;
+use mutable_batch::MutableBatch;
+use observability_deps::tracing::*;
+use prost::Message;
+use std::sync::Arc;
+use thiserror::Error;
+use tonic::{metadata::MetadataMap, Code, Request, Response, Streaming};
+use trace::ctx::SpanContext;
+
+use self::convert::append_record_batch;
+use crate::{
+    dml_handlers::{DmlError, DmlHandler},
+    namespace_resolver::{self, NamespaceCreationError, NamespaceResolver},
+};
+
+/// The request headers that may name the database (namespace) being written
+/// to, matching those accepted by the querier FlightSQL service.
+const DATABASE_HEADERS: [&str; 4] = ["database", "bucket", "bucket-name", "iox-namespace-name"];
+
+/// The FlightSQL `CommandStatementIngest` message, sent by clients (such as
+/// ADBC drivers) in the [`FlightDescriptor`] of a `DoPut` request to bulk load
+/// Arrow data into a table.
+///
+/// This command is not (yet) provided by `arrow-flight`, so it is defined
+/// here. The table definition and options fields are not used by IOx, and are
+/// ignored when decoding.
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandStatementIngest {
+    /// The table to write to.
+    #[prost(string, tag = "2")]
+    pub table: String,
+    /// The schema of the table - not supported by IOx.
+    #[prost(string, optional, tag = "3")]
+    pub schema: Option<String>,
+    /// The catalog of the table - not supported by IOx.
+    #[prost(string, optional, tag = "4")]
+    pub catalog: Option<String>,
+    /// Write to a temporary table - not supported by IOx.
+    #[prost(bool, tag = "5")]
+    pub temporary: bool,
+    /// Write as part of a transaction - not supported by IOx.
+    #[prost(bytes = "bytes", optional, tag = "6")]
+    pub transaction_id: Option<Bytes>,
+}
+
+impl ProstMessageExt for CommandStatementIngest {
+    fn type_url() -> &'static str {
+        "type.googleapis.com/arrow.flight.protocol.sql.CommandStatementIngest"
+    }
+
+    fn as_any(&self) -> Any {
+        Any {
+            type_url: Self::type_url().to_string(),
+            value: self.encode_to_vec().into(),
+        }
+    }
+}
+
+/// Errors returned by the [`FlightIngestService`].
+#[derive(Debug, Error)]
+pub enum Error {
+    /// No database header was provided.
+    #[error("no database specified, set the \"database\" request header")]
+    NoDatabase,
+
+    /// A database header was not valid ASCII.
+    #[error("invalid database header value: {0}")]
+    InvalidDatabaseHeader(tonic::metadata::errors::ToStrError),
+
+    /// More than one database was specified in the request headers.
+    #[error("conflicting database names specified in request headers")]
+    TooManyDatabases,
+
+    /// The database name is not a valid namespace name.
+    #[error(transparent)]
+    InvalidNamespaceName(#[from] NamespaceNameError),
+
+    /// The request contained no messages.
+    #[error("no messages in DoPut request")]
+    NoMessages,
+
+    /// The first message of the request had no [`FlightDescriptor`].
+    #[error("no FlightDescriptor in first DoPut message")]
+    NoDescriptor,
+
+    /// The [`FlightDescriptor`] command is not a [`CommandStatementIngest`],
+    /// or uses options not supported by IOx.
+    #[error("invalid ingest command: {0}")]
+    InvalidCommand(String),
+
+    /// The total size of the request exceeds the configured maximum.
+    #[error("max request size ({0} bytes) exceeded")]
+    RequestSizeExceeded(usize),
+
+    /// The request data could not be decoded into Arrow record batches.
+    #[error("failed to decode record batches: {0}")]
+    Decode(FlightError),
+
+    /// A record batch could not be converted into an IOx write.
+    #[error("failed to convert record batch: {0}")]
+    Conversion(#[from] ConversionError),
+
+    /// The request contained no rows.
+    #[error("no rows to write")]
+    NoRows,
+
+    /// An error resolving the [`NamespaceId`] of the database.
+    ///
+    /// [`NamespaceId`]: data_types::NamespaceId
+    #[error(transparent)]
+    NamespaceResolver(#[from] namespace_resolver::Error),
+
+    /// An error returned from the [`DmlHandler`].
+    #[error("dml handler error: {0}")]
+    DmlHandler(#[from] DmlError),
+
+    /// The request has no authentication, but authorization is configured.
+    #[error("authentication required")]
+    Unauthenticated,
+
+    /// The provided authorization is not sufficient to perform the request.
+    #[error("access denied")]
+    Forbidden,
+
+    /// An error occurred verifying the authorization token.
+    #[error(transparent)]
+    Authorizer(authz::Error),
+}
+
+impl From<authz::Error> for Error {
+    fn from(value: authz::Error) -> Self {
+        match value {
+            authz::Error::Forbidden => Self::Forbidden,
+            authz::Error::NoToken => Self::Unauthenticated,
+            e => Self::Authorizer(e),
+        }
+    }
+}
+
+/// Map a [`FlightIngestService`] error to a [`tonic::Status`].
+impl From<Error> for tonic::Status {
+    fn from(e: Error) -> Self {
+        let code = match &e {
+            Error::NoDatabase
+            | Error::InvalidDatabaseHeader(_)
+            | Error::TooManyDatabases
+            | Error::InvalidNamespaceName(_)
+            | Error::NoMessages
+            | Error::NoDescriptor
+            | Error::InvalidCommand(_)
+            | Error::Decode(_)
+            | Error::Conversion(_)
+            | Error::NoRows => Code::InvalidArgument,
+            Error::RequestSizeExceeded(_) => Code::ResourceExhausted,
+            // Error from the namespace resolver is 4xx if autocreation is
+            // disabled, 5xx otherwise
+            Error::NamespaceResolver(namespace_resolver::Error::Create(
+                NamespaceCreationError::Reject(_),
+            )) => Code::NotFound,
+            Error::NamespaceResolver(_) => Code::Internal,
+            Error::DmlHandler(e) => dml_error_code(e),
+            Error::Unauthenticated => Code::Unauthenticated,
+            Error::Forbidden => Code::PermissionDenied,
+            Error::Authorizer(_) => Code::Internal,
+        };
+
+        if code == Code::Internal {
+            warn!(error=%e, "flight ingest error");
+        } else {
+            debug!(error=%e, "flight ingest request rejected");
+        }
+
+        Self::new(code, e.to_string())
+    }
+}
+
+/// Map a [`DmlError`] to the gRPC [`Code`] equivalent of the HTTP status code
+/// returned by the write API for the same error.
+fn dml_error_code(e: &DmlError) -> Code {
+    match StatusCode::from(e) {
+        StatusCode::NOT_FOUND => Code::NotFound,
+        StatusCode::BAD_REQUEST => Code::InvalidArgument,
+        StatusCode::FORBIDDEN => Code::FailedPrecondition,
+        StatusCode::TOO_MANY_REQUESTS => Code::ResourceExhausted,
+        StatusCode::GATEWAY_TIMEOUT => Code::DeadlineExceeded,
+        StatusCode::SERVICE_UNAVAILABLE => Code::Unavailable,
+        _ => Code::Internal,
+    }
+}
+
+type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send + 'static>>;
+
+/// An Arrow Flight service implementing the FlightSQL bulk ingest `DoPut`
+/// command ([`CommandStatementIngest`]).
+///
+/// The record batches streamed by the client are converted into a single
+/// IOx write to the table named in the command - dictionary encoded string
+/// columns are tags, the `time` column is the timestamp, and all other
+/// columns are fields. The write is then passed through the same
+/// [`DmlHandler`] chain as writes to the HTTP API, which validates the schema
+/// and partitions the write before it is sent to the ingesters.
+///
+/// The database is named by the same request headers accepted by the querier
+/// FlightSQL service, and must exist (or be auto-created by the
+/// [`NamespaceResolver`]).
+///
+/// All other Flight RPCs are unimplemented.
+#[derive(Debug)]
+pub struct FlightIngestService<D, N> {
+    max_request_bytes: usize,
+    namespace_resolver: N,
+    dml_handler: D,
+    authz: Option<Arc<dyn Authorizer>>,
+}
+
+impl<D, N> FlightIngestService<D, N> {
+    /// Initialise a new [`FlightIngestService`] that writes to
+    /// `dml_handler`, rejecting requests that carry more than
+    /// `max_request_bytes` of encoded data.
+    pub fn new(
+        max_request_bytes: usize,
+        namespace_resolver: N,
+        dml_handler: D,
+        authz: Option<Arc<dyn Authorizer>>,
+    ) -> Self {
+        Self {
+            max_request_bytes,
+            namespace_resolver,
+            dml_handler,
+            authz,
+        }
+    }
+}
+
+impl<D, N> FlightIngestService<D, N>
+where
+    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
+    N: NamespaceResolver,
+{
+    /// Write the record batches in `stream` to the namespace `namespace`,
+    /// returning the number of rows written.
+    async fn ingest<S>(
+        &self,
+        namespace: String,
+        token: Option<Vec<u8>>,
+        mut stream: S,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<usize, Error>
+    where
+        S: Stream<Item = Result<FlightData, tonic::Status>> + Send + Unpin,
+    {
+        let namespace = NamespaceName::try_from(namespace)?;
+
+        let perms = [Permission::ResourceAction(
+            Resource::Database(namespace.to_string()),
+            AuthzAction::Write,
+        )];
+        self.authz
+            .require_any_permission(token.as_deref(), &perms)
+            .await?;
+
+        // The FlightDescriptor is sent in the first message.
+        let first = stream
+            .next()
+            .await
+            .transpose()
+            .map_err(|e| Error::Decode(FlightError::Tonic(e)))?
+            .ok_or(Error::NoMessages)?;
+        let descriptor = first
+            .flight_descriptor
+            .as_ref()
+            .ok_or(Error::NoDescriptor)?;
+        let table = table_from_descriptor(descriptor)?;
+
+        // Buffer the request, bounding the amount of data held in memory.
+        let mut data = vec![];
+        let mut size = 0;
+        let mut next = Some(first);
+        while let Some(v) = next {
+            size += v.data_header.len() + v.data_body.len();
+            if size > self.max_request_bytes {
+                return Err(Error::RequestSizeExceeded(self.max_request_bytes));
+            }
+            data.push(v);
+
+            next = stream
+                .try_next()
+                .await
+                .map_err(|e| Error::Decode(FlightError::Tonic(e)))?;
+        }
+
+        let batches: Vec<RecordBatch> = FlightRecordBatchStream::new_from_flight_data(
+            futures::stream::iter(data.into_iter().map(Ok)),
+        )
+        .try_collect()
+        .await
+        .map_err(Error::Decode)?;
+
+        let mut batch = MutableBatch::new();
+        for b in &batches {
+            append_record_batch(&mut batch, b)?;
+        }
+
+        let num_rows = batch.rows();
+        if num_rows == 0 {
+            return Err(Error::NoRows);
+        }
+
+        debug!(
+            num_rows,
+            num_batches = batches.len(),
+            %namespace,
+            %table,
+            "routing flight ingest write",
+        );
+
+        let namespace_id = self.namespace_resolver.get_namespace_id(&namespace).await?;
+
+        self.dml_handler
+            .write(
+                &namespace,
+                namespace_id,
+                HashMap::from([(table, batch)]),
+                span_ctx,
+            )
+            .await
+            .map_err(Into::into)?;
+
+        Ok(num_rows)
+    }
+}
+
+#[tonic::async_trait]
+impl<D, N> Flight for FlightIngestService<D, N>
+where
+    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()> + 'static,
+    N: NamespaceResolver + 'static,
+{
+    type HandshakeStream = TonicStream<HandshakeResponse>;
+    type ListFlightsStream = TonicStream<FlightInfo>;
+    type DoGetStream = TonicStream<FlightData>;
+    type DoPutStream = TonicStream<PutResult>;
+    type DoActionStream = TonicStream<arrow_flight::Result>;
+    type ListActionsStream = TonicStream<ActionType>;
+    type DoExchangeStream = TonicStream<FlightData>;
+
+    async fn get_schema(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, tonic::Status> {
+        Err(tonic::Status::unimplemented("Not yet implemented"))
+    }
+
+    async fn do_get(
+        &self,
+        _request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("Not yet implemented"))
+    }
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("Not yet implemented"))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("Not yet implemented"))
+    }
+
+    async fn get_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, tonic::Status> {
+        Err(tonic::Status::unimplemented("Not yet implemented"))
+    }
+
+    /// Handle a FlightSQL bulk ingest request. The [`FlightDescriptor`] of
+    /// the first message contains the [`CommandStatementIngest`], followed by
+    /// the Arrow IPC encoded record batches to write.
+    ///
+    /// The response contains a single `DoPutUpdateResult` with the number of
+    /// rows written.
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, tonic::Status> {
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+        let namespace = get_database(request.metadata())?;
+        let token = get_flight_authz(request.metadata());
+
+        let num_rows = self
+            .ingest(namespace, token, request.into_inner(), span_ctx)
+            .await?;
+
+        let result = DoPutUpdateResult {
+            record_count: num_rows as i64,
+        };
+        let result = PutResult {
+            app_metadata: result.encode_to_vec().into(),
+        };
+
+        Ok(Response::new(
+            futures::stream::iter([Ok(result)]).boxed() as Self::DoPutStream
+        ))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("Not yet implemented"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("Not yet implemented"))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("Not yet implemented"))
+    }
+}
+
+/// Decode the [`CommandStatementIngest`] in `descriptor`, returning the name
+/// of the table to write to.
+fn table_from_descriptor(descriptor: &FlightDescriptor) -> Result<String, Error> {
+    let msg = Any::decode(&*descriptor.cmd)
+        .map_err(|e| Error::InvalidCommand(format!("failed to decode command: {e}")))?;
+    let cmd = msg
+        .unpack::<CommandStatementIngest>()
+        .map_err(|e| Error::InvalidCommand(e.to_string()))?
+        .ok_or_else(|| {
+            Error::InvalidCommand(format!("unsupported command type {}", msg.type_url))
+        })?;
+
+    if cmd.temporary {
+        return Err(Error::InvalidCommand(
+            "temporary tables are not supported".to_string(),
+        ));
+    }
+    if cmd.transaction_id.is_some() {
+        return Err(Error::InvalidCommand(
+            "transactions are not supported".to_string(),
+        ));
+    }
+    if cmd.table.is_empty() {
+        return Err(Error::InvalidCommand("no table specified".to_string()));
+    }
+
+    Ok(cmd.table)
+}
+
+/// Return the database named by the [`DATABASE_HEADERS`] of the request.
+///
+/// More than one of the headers may be set, as long as they name the same
+/// database.
+fn get_database(metadata: &MetadataMap) -> Result<String, Error> {
+    let mut database: Option<&str> = None;
+    for key in DATABASE_HEADERS {
+        let v = match metadata.get(key) {
+            Some(v) => v.to_str().map_err(Error::InvalidDatabaseHeader)?,
+            None => continue,
+        };
+        match database {
+            Some(d) if d != v => return Err(Error::TooManyDatabases),
+            _ => database = Some(v),
+        }
+    }
+
+    database.map(ToString::to_string).ok_or(Error::NoDatabase)
+}
+
+/// Retrieve the bearer token associated with the request.
+fn get_flight_authz(metadata: &MetadataMap) -> Option<Vec<u8>> {
+    let val = metadata.get("authorization")?.as_ref();
+    if val.len() < b"Bearer ".len() {
+        return None;
+    }
+    match val.split_at(b"Bearer ".len()) {
+        (b"Bearer ", token) => Some(token.to_vec()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, DictionaryArray, Float64Array, TimestampNanosecondArray};
+    use arrow::datatypes::Int32Type;
+    use arrow_flight::encode::FlightDataEncoderBuilder;
+    use assert_matches::assert_matches;
+    use data_types::NamespaceId;
+
+    use super::*;
+    use crate::{
+        dml_handlers::{
+            mock::{MockDmlHandler, MockDmlHandlerCall},
+            RetentionError,
+        },
+        namespace_resolver::mock::MockNamespaceResolver,
+    };
+
+    const NAMESPACE_NAME: &str = "bananas_test";
+    const NAMESPACE_ID: NamespaceId = NamespaceId::new(42);
+
+    fn batch() -> RecordBatch {
+        RecordBatch::try_from_iter([
+            (
+                "region",
+                Arc::new(
+                    vec!["Hereford", "Worcester"]
+                        .into_iter()
+                        .collect::<DictionaryArray<Int32Type>>(),
+                ) as ArrayRef,
+            ),
+            ("temp", Arc::new(Float64Array::from(vec![12.5, 13.0]))),
+            ("time", Arc::new(TimestampNanosecondArray::from(vec![1, 2]))),
+        ])
+        .unwrap()
+    }
+
+    fn ingest_descriptor(table: &str) -> FlightDescriptor {
+        let cmd = CommandStatementIngest {
+            table: table.to_string(),
+            ..Default::default()
+        };
+        FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec())
+    }
+
+    /// Encode `batches` as a DoPut request stream, with `descriptor` in the
+    /// first message.
+    async fn put_stream(
+        descriptor: Option<FlightDescriptor>,
+        batches: Vec<RecordBatch>,
+    ) -> impl Stream<Item = Result<FlightData, tonic::Status>> + Send + Unpin {
+        let mut data: Vec<FlightData> = FlightDataEncoderBuilder::new()
+            .build(futures::stream::iter(batches.into_iter().map(Ok)))
+            .try_collect()
+            .await
+            .unwrap();
+        data[0].flight_descriptor = descriptor;
+        futures::stream::iter(data.into_iter().map(Ok))
+    }
+
+    fn new_service(
+        dml_handler: Arc<MockDmlHandler<HashMap<String, MutableBatch>>>,
+    ) -> FlightIngestService<
+        Arc<MockDmlHandler<HashMap<String, MutableBatch>>>,
+        MockNamespaceResolver,
+    > {
+        FlightIngestService::new(
+            1024 * 1024,
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID),
+            dml_handler,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_ingest() {
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let service = new_service(Arc::clone(&dml_handler));
+
+        let stream = put_stream(Some(ingest_descriptor("platanos")), vec![batch(), batch()]).await;
+        let num_rows = service
+            .ingest(NAMESPACE_NAME.to_string(), None, stream, None)
+            .await
+            .expect("ingest should succeed");
+        assert_eq!(num_rows, 4);
+
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write {
+                namespace,
+                namespace_id,
+                write_input,
+            }] => {
+                assert_eq!(namespace, NAMESPACE_NAME);
+                assert_eq!(*namespace_id, NAMESPACE_ID);
+                let table = write_input.get("platanos").expect("table not written");
+                assert_eq!(table.rows(), 4);
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ingest_no_descriptor() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let service = new_service(Arc::clone(&dml_handler));
+
+        let stream = put_stream(None, vec![batch()]).await;
+        let err = service
+            .ingest(NAMESPACE_NAME.to_string(), None, stream, None)
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::NoDescriptor);
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ingest_unsupported_command() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let service = new_service(Arc::clone(&dml_handler));
+
+        let descriptor = FlightDescriptor::new_cmd(
+            arrow_flight::sql::CommandStatementQuery {
+                query: "SELECT 1".to_string(),
+            }
+            .as_any()
+            .encode_to_vec(),
+        );
+        let stream = put_stream(Some(descriptor), vec![batch()]).await;
+        let err = service
+            .ingest(NAMESPACE_NAME.to_string(), None, stream, None)
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::InvalidCommand(_));
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ingest_request_size_exceeded() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let service = FlightIngestService::new(
+            1,
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID),
+            Arc::clone(&dml_handler),
+            None,
+        );
+
+        let stream = put_stream(Some(ingest_descriptor("platanos")), vec![batch()]).await;
+        let err = service
+            .ingest(NAMESPACE_NAME.to_string(), None, stream, None)
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::RequestSizeExceeded(1));
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ingest_dml_handler_error() {
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Err(
+            DmlError::Retention(RetentionError::OutsideRetention("platanos".to_string())),
+        )]));
+        let service = new_service(Arc::clone(&dml_handler));
+
+        let stream = put_stream(Some(ingest_descriptor("platanos")), vec![batch()]).await;
+        let err = service
+            .ingest(NAMESPACE_NAME.to_string(), None, stream, None)
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::DmlHandler(DmlError::Retention(_)));
+        assert_eq!(tonic::Status::from(err).code(), Code::FailedPrecondition);
+    }
+
+    #[test]
+    fn test_get_database() {
+        let mut metadata = MetadataMap::new();
+        assert_matches!(get_database(&metadata), Err(Error::NoDatabase));
+
+        metadata.insert("bucket", "bananas".parse().unwrap());
+        assert_eq!(get_database(&metadata).unwrap(), "bananas");
+
+        metadata.insert("database", "bananas".parse().unwrap());
+        assert_eq!(get_database(&metadata).unwrap(), "bananas");
+
+        metadata.insert("iox-namespace-name", "platanos".parse().unwrap());
+        assert_matches!(get_database(&metadata), Err(Error::TooManyDatabases));
+    }
+}
@@ -0,0 +1,436 @@
+//! Conversion of Arrow [`RecordBatch`] to IOx [`MutableBatch`].
+
+use arrow::{
+    array::{
+        as_boolean_array, as_dictionary_array, as_primitive_array, as_string_array, Array, ArrayRef,
+    },
+    compute::cast,
+    datatypes::{
+        DataType, Float64Type, Int32Type, Int64Type, TimeUnit, TimestampNanosecondType, UInt64Type,
+    },
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use arrow_util::bitset::BitSet;
+use hashbrown::HashSet;
+use mutable_batch::{writer::Writer, MutableBatch};
+use schema::TIME_COLUMN_NAME;
+use thiserror::Error;
+
+/// Errors converting a [`RecordBatch`] into a [`MutableBatch`].
+#[derive(Debug, Error)]
+pub enum ConversionError {
+    /// The batch has no `time` column.
+    #[error("record batch must contain a time column")]
+    MissingTime,
+
+    /// The `time` column contains null values.
+    #[error("time column must not contain nulls")]
+    NullTime,
+
+    /// The batch contains more than one column with the same name.
+    #[error("duplicate column name: {0}")]
+    DuplicateColumnName(String),
+
+    /// A column has an Arrow type with no IOx equivalent.
+    #[error("column {column} has unsupported type {data_type}")]
+    UnsupportedType {
+        /// The name of the column.
+        column: String,
+        /// The Arrow type of the column.
+        data_type: DataType,
+    },
+
+    /// Converting a column to the IOx representation failed.
+    #[error("error converting column {column}: {source}")]
+    Cast {
+        /// The name of the column.
+        column: String,
+        /// The underlying error.
+        source: ArrowError,
+    },
+
+    /// Writing a column to the [`MutableBatch`] failed, for example because
+    /// its type differs from an earlier batch.
+    #[error("error writing column {column}: {source}")]
+    Write {
+        /// The name of the column.
+        column: String,
+        /// The underlying error.
+        source: mutable_batch::writer::Error,
+    },
+}
+
+/// The IOx column type an Arrow column is mapped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Tag,
+    Time,
+    Float,
+    Integer,
+    UInteger,
+    Boolean,
+    String,
+}
+
+impl ColumnKind {
+    /// Map the Arrow type of the column `name` to an IOx column type.
+    ///
+    /// Dictionary-encoded string columns are tags, the column named `time`
+    /// is the timestamp, and all other columns are fields.
+    fn try_new(name: &str, data_type: &DataType) -> Result<Self, ConversionError> {
+        let kind = match data_type {
+            DataType::Timestamp(_, _) | DataType::Int64 if name == TIME_COLUMN_NAME => Self::Time,
+            DataType::Dictionary(_, value)
+                if matches!(value.as_ref(), DataType::Utf8 | DataType::LargeUtf8) =>
+            {
+                Self::Tag
+            }
+            DataType::Float16 | DataType::Float32 | DataType::Float64 => Self::Float,
+            DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => Self::Integer,
+            DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+                Self::UInteger
+            }
+            DataType::Boolean => Self::Boolean,
+            DataType::Utf8 | DataType::LargeUtf8 => Self::String,
+            _ => {
+                return Err(ConversionError::UnsupportedType {
+                    column: name.to_string(),
+                    data_type: data_type.clone(),
+                })
+            }
+        };
+
+        // Only the time column may have the timestamp type, and it must have
+        // it.
+        if name == TIME_COLUMN_NAME && kind != Self::Time {
+            return Err(ConversionError::UnsupportedType {
+                column: name.to_string(),
+                data_type: data_type.clone(),
+            });
+        }
+
+        Ok(kind)
+    }
+
+    /// The Arrow type the column is cast to before writing.
+    fn data_type(&self) -> DataType {
+        match self {
+            Self::Tag => DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            Self::Time => DataType::Timestamp(TimeUnit::Nanosecond, None),
+            Self::Float => DataType::Float64,
+            Self::Integer => DataType::Int64,
+            Self::UInteger => DataType::UInt64,
+            Self::Boolean => DataType::Boolean,
+            Self::String => DataType::Utf8,
+        }
+    }
+}
+
+/// Append the rows in `batch` to `dst`.
+///
+/// Columns are mapped to IOx column types by their Arrow type: dictionary
+/// encoded strings are tags, and the `time` column (a timestamp of any
+/// unit, or nanoseconds as an `Int64`) is the timestamp. All other columns
+/// are fields, with narrower numeric types widened to their 64-bit
+/// equivalent.
+///
+/// No rows are appended if an error is returned.
+pub(crate) fn append_record_batch(
+    dst: &mut MutableBatch,
+    batch: &RecordBatch,
+) -> Result<(), ConversionError> {
+    let schema = batch.schema();
+
+    if schema.column_with_name(TIME_COLUMN_NAME).is_none() {
+        return Err(ConversionError::MissingTime);
+    }
+
+    let mut names = HashSet::with_capacity(schema.fields().len());
+    for field in schema.fields() {
+        if !names.insert(field.name()) {
+            return Err(ConversionError::DuplicateColumnName(field.name().clone()));
+        }
+    }
+
+    let mut writer = Writer::new(dst, batch.num_rows());
+    for (field, array) in schema.fields().iter().zip(batch.columns()) {
+        let name = field.name();
+        let kind = ColumnKind::try_new(name, field.data_type())?;
+
+        let array = cast(array, &kind.data_type()).map_err(|source| ConversionError::Cast {
+            column: name.to_string(),
+            source,
+        })?;
+        if kind == ColumnKind::Time && array.null_count() > 0 {
+            return Err(ConversionError::NullTime);
+        }
+
+        write_column(&mut writer, name, kind, &array).map_err(|source| ConversionError::Write {
+            column: name.to_string(),
+            source,
+        })?;
+    }
+    writer.commit();
+
+    Ok(())
+}
+
+/// Write `array`, already cast to the type for `kind`, to the column `name`.
+fn write_column(
+    writer: &mut Writer<'_>,
+    name: &str,
+    kind: ColumnKind,
+    array: &ArrayRef,
+) -> Result<(), mutable_batch::writer::Error> {
+    let mask = valid_mask(array.as_ref());
+    let mask = mask.as_ref().map(|v| v.bytes());
+
+    match kind {
+        ColumnKind::Tag => {
+            let array = as_dictionary_array::<Int32Type>(array);
+            let values = as_string_array(array.values());
+            writer.write_tag_dict(
+                name,
+                mask,
+                array.keys().iter().flatten().map(|k| k as usize),
+                values.iter().map(|v| v.unwrap_or_default()),
+            )
+        }
+        ColumnKind::Time => {
+            let array = as_primitive_array::<TimestampNanosecondType>(array);
+            writer.write_time(name, array.values().iter().copied())
+        }
+        ColumnKind::Float => {
+            let array = as_primitive_array::<Float64Type>(array);
+            writer.write_f64(name, mask, array.iter().flatten())
+        }
+        ColumnKind::Integer => {
+            let array = as_primitive_array::<Int64Type>(array);
+            writer.write_i64(name, mask, array.iter().flatten())
+        }
+        ColumnKind::UInteger => {
+            let array = as_primitive_array::<UInt64Type>(array);
+            writer.write_u64(name, mask, array.iter().flatten())
+        }
+        ColumnKind::Boolean => {
+            let array = as_boolean_array(array);
+            writer.write_bool(name, mask, array.iter().flatten())
+        }
+        ColumnKind::String => {
+            let array = as_string_array(array);
+            writer.write_string(name, mask, array.iter().flatten())
+        }
+    }
+}
+
+/// Return the validity of each row in `array` as a bitmask, or [`None`] if
+/// there are no nulls.
+fn valid_mask(array: &dyn Array) -> Option<BitSet> {
+    if array.null_count() == 0 {
+        return None;
+    }
+
+    let mut mask = BitSet::with_size(array.len());
+    (0..array.len())
+        .filter(|idx| array.is_valid(*idx))
+        .for_each(|idx| mask.set(idx));
+    Some(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{
+            BooleanArray, DictionaryArray, Float32Array, Int64Array, StringArray,
+            TimestampMillisecondArray, UInt8Array,
+        },
+        datatypes::Int8Type,
+    };
+    use arrow_util::assert_batches_eq;
+    use assert_matches::assert_matches;
+    use schema::Projection;
+
+    use super::*;
+
+    fn to_batch(columns: Vec<(&str, ArrayRef)>) -> RecordBatch {
+        RecordBatch::try_from_iter(columns).expect("invalid record batch")
+    }
+
+    #[test]
+    fn test_append_record_batch() {
+        let batch = to_batch(vec![
+            (
+                "region",
+                Arc::new(
+                    vec![Some("Hereford"), None, Some("Hereford")]
+                        .into_iter()
+                        .collect::<DictionaryArray<Int8Type>>(),
+                ) as ArrayRef,
+            ),
+            (
+                "temp",
+                Arc::new(Float32Array::from(vec![Some(1.5), Some(2.5), None])),
+            ),
+            ("count", Arc::new(UInt8Array::from(vec![1, 2, 3]))),
+            (
+                "ok",
+                Arc::new(BooleanArray::from(vec![Some(true), None, Some(false)])),
+            ),
+            (
+                "note",
+                Arc::new(StringArray::from(vec![None, Some("bananas"), None])),
+            ),
+            (
+                "time",
+                Arc::new(TimestampMillisecondArray::from(vec![1, 2, 3])),
+            ),
+        ]);
+
+        let mut mb = MutableBatch::new();
+        append_record_batch(&mut mb, &batch).expect("conversion should succeed");
+        append_record_batch(&mut mb, &batch).expect("conversion should succeed");
+        assert_eq!(mb.rows(), 6);
+
+        let schema = mb.schema(Projection::All).unwrap();
+        assert_matches!(
+            schema.field_by_name("region"),
+            Some((schema::InfluxColumnType::Tag, _))
+        );
+        assert_matches!(
+            schema.field_by_name("temp"),
+            Some((
+                schema::InfluxColumnType::Field(schema::InfluxFieldType::Float),
+                _
+            ))
+        );
+        assert_matches!(
+            schema.field_by_name("count"),
+            Some((
+                schema::InfluxColumnType::Field(schema::InfluxFieldType::UInteger),
+                _
+            ))
+        );
+
+        let got = mb.to_arrow(Projection::All).unwrap();
+        let expected = [
+            "+-------+---------+-------+----------+------+-------------------------+",
+            "| count | note    | ok    | region   | temp | time                    |",
+            "+-------+---------+-------+----------+------+-------------------------+",
+            "| 1     |         | true  | Hereford | 1.5  | 1970-01-01T00:00:00.001 |",
+            "| 2     | bananas |       |          | 2.5  | 1970-01-01T00:00:00.002 |",
+            "| 3     |         | false | Hereford |      | 1970-01-01T00:00:00.003 |",
+            "| 1     |         | true  | Hereford | 1.5  | 1970-01-01T00:00:00.001 |",
+            "| 2     | bananas |       |          | 2.5  | 1970-01-01T00:00:00.002 |",
+            "| 3     |         | false | Hereford |      | 1970-01-01T00:00:00.003 |",
+            "+-------+---------+-------+----------+------+-------------------------+",
+        ];
+        assert_batches_eq!(expected, &[got]);
+    }
+
+    #[test]
+    fn test_int64_time() {
+        let batch = to_batch(vec![
+            ("v", Arc::new(Int64Array::from(vec![42])) as ArrayRef),
+            ("time", Arc::new(Int64Array::from(vec![1_000]))),
+        ]);
+
+        let mut mb = MutableBatch::new();
+        append_record_batch(&mut mb, &batch).expect("conversion should succeed");
+
+        let got = mb.to_arrow(Projection::All).unwrap();
+        let expected = [
+            "+----------------------------+----+",
+            "| time                       | v  |",
+            "+----------------------------+----+",
+            "| 1970-01-01T00:00:00.000001 | 42 |",
+            "+----------------------------+----+",
+        ];
+        assert_batches_eq!(expected, &[got]);
+    }
+
+    #[test]
+    fn test_missing_time() {
+        let batch = to_batch(vec![(
+            "v",
+            Arc::new(Int64Array::from(vec![42])) as ArrayRef,
+        )]);
+
+        let mut mb = MutableBatch::new();
+        let err = append_record_batch(&mut mb, &batch).unwrap_err();
+        assert_matches!(err, ConversionError::MissingTime);
+    }
+
+    #[test]
+    fn test_null_time() {
+        let batch = to_batch(vec![(
+            "time",
+            Arc::new(TimestampMillisecondArray::from(vec![Some(1), None])) as ArrayRef,
+        )]);
+
+        let mut mb = MutableBatch::new();
+        let err = append_record_batch(&mut mb, &batch).unwrap_err();
+        assert_matches!(err, ConversionError::NullTime);
+        assert_eq!(mb.rows(), 0);
+    }
+
+    #[test]
+    fn test_invalid_time_type() {
+        let batch = to_batch(vec![(
+            "time",
+            Arc::new(StringArray::from(vec!["now"])) as ArrayRef,
+        )]);
+
+        let mut mb = MutableBatch::new();
+        let err = append_record_batch(&mut mb, &batch).unwrap_err();
+        assert_matches!(err, ConversionError::UnsupportedType { column, .. } => {
+            assert_eq!(column, "time");
+        });
+    }
+
+    #[test]
+    fn test_unsupported_type() {
+        let batch = to_batch(vec![
+            (
+                "v",
+                Arc::new(arrow::array::BinaryArray::from(vec![&b"bananas"[..]])) as ArrayRef,
+            ),
+            ("time", Arc::new(Int64Array::from(vec![1]))),
+        ]);
+
+        let mut mb = MutableBatch::new();
+        let err = append_record_batch(&mut mb, &batch).unwrap_err();
+        assert_matches!(err, ConversionError::UnsupportedType { column, .. } => {
+            assert_eq!(column, "v");
+        });
+        assert_eq!(mb.rows(), 0);
+    }
+
+    #[test]
+    fn test_type_conflict() {
+        let mut mb = MutableBatch::new();
+
+        let batch = to_batch(vec![
+            ("v", Arc::new(Int64Array::from(vec![1])) as ArrayRef),
+            ("time", Arc::new(Int64Array::from(vec![1]))),
+        ]);
+        append_record_batch(&mut mb, &batch).expect("conversion should succeed");
+
+        let batch = to_batch(vec![
+            (
+                "v",
+                Arc::new(StringArray::from(vec!["bananas"])) as ArrayRef,
+            ),
+            ("time", Arc::new(Int64Array::from(vec![2]))),
+        ]);
+        let err = append_record_batch(&mut mb, &batch).unwrap_err();
+        assert_matches!(err, ConversionError::Write { column, .. } => {
+            assert_eq!(column, "v");
+        });
+
+        // The rows of the failed batch are not appended.
+        assert_eq!(mb.rows(), 1);
+    }
+}
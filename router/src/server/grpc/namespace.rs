@@ -0,0 +1,94 @@
+//! A [`NamespaceService`] decorator evicting modified namespaces from the
+//! router's schema cache.
+
+use generated_types::influxdata::iox::namespace::v1::{
+    namespace_service_server::NamespaceService, *,
+};
+use tonic::{Request, Response, Status};
+
+use super::evict_namespace;
+use crate::namespace_cache::NamespaceCache;
+
+/// A [`NamespaceService`] implementation that delegates all calls to `T`,
+/// removing the [`NamespaceSchema`] of the affected namespace from the cache
+/// `C` after it is successfully deleted, or its retention period or service
+/// protection limits are updated.
+///
+/// Without this eviction, the router would continue to enforce the retention
+/// period and limits of the stale cached schema, such as the write rate limits
+/// applied by the [`RateLimiter`].
+///
+/// Only the cache of the router that served the request is updated - other
+/// routers observe the change once their cached schema expires
+/// (see [`TtlCache`]).
+///
+/// [`NamespaceSchema`]: data_types::NamespaceSchema
+/// [`TtlCache`]: crate::namespace_cache::TtlCache
+/// [`RateLimiter`]: crate::dml_handlers::RateLimiter
+#[derive(Debug)]
+pub struct CacheInvalidatingNamespaceService<T, C> {
+    inner: T,
+    cache: C,
+}
+
+impl<T, C> CacheInvalidatingNamespaceService<T, C> {
+    /// Decorate `inner`, evicting namespaces from `cache` when they are
+    /// deleted or updated.
+    pub fn new(inner: T, cache: C) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[tonic::async_trait]
+impl<T, C> NamespaceService for CacheInvalidatingNamespaceService<T, C>
+where
+    T: NamespaceService,
+    C: NamespaceCache + 'static,
+{
+    async fn get_namespaces(
+        &self,
+        request: Request<GetNamespacesRequest>,
+    ) -> Result<Response<GetNamespacesResponse>, Status> {
+        self.inner.get_namespaces(request).await
+    }
+
+    async fn create_namespace(
+        &self,
+        request: Request<CreateNamespaceRequest>,
+    ) -> Result<Response<CreateNamespaceResponse>, Status> {
+        self.inner.create_namespace(request).await
+    }
+
+    async fn delete_namespace(
+        &self,
+        request: Request<DeleteNamespaceRequest>,
+    ) -> Result<Response<DeleteNamespaceResponse>, Status> {
+        let namespace = request.get_ref().name.clone();
+        let resp = self.inner.delete_namespace(request).await?;
+        evict_namespace(&self.cache, namespace);
+        Ok(resp)
+    }
+
+    async fn update_namespace_retention(
+        &self,
+        request: Request<UpdateNamespaceRetentionRequest>,
+    ) -> Result<Response<UpdateNamespaceRetentionResponse>, Status> {
+        let namespace = request.get_ref().name.clone();
+        let resp = self.inner.update_namespace_retention(request).await?;
+        evict_namespace(&self.cache, namespace);
+        Ok(resp)
+    }
+
+    async fn update_namespace_service_protection_limit(
+        &self,
+        request: Request<UpdateNamespaceServiceProtectionLimitRequest>,
+    ) -> Result<Response<UpdateNamespaceServiceProtectionLimitResponse>, Status> {
+        let namespace = request.get_ref().name.clone();
+        let resp = self
+            .inner
+            .update_namespace_service_protection_limit(request)
+            .await?;
+        evict_namespace(&self.cache, namespace);
+        Ok(resp)
+    }
+}
@@ -0,0 +1,122 @@
+//! A [`SchemaService`] decorator evicting renamed namespaces from the router's
+//! schema cache, and the column IDs of renamed columns from the ingesters.
+
+use std::sync::Arc;
+
+use futures::future::join_all;
+use generated_types::influxdata::iox::{
+    ingester::v1::InvalidateColumnsRequest,
+    schema::v1::{schema_service_server::SchemaService, *},
+};
+use observability_deps::tracing::*;
+use tonic::{Request, Response, Status};
+
+use super::evict_namespace;
+use crate::{
+    dml_handlers::{
+        client::WriteClient, lazy_connector::LazyConnector, RpcWriteError, RPC_TIMEOUT,
+    },
+    namespace_cache::NamespaceCache,
+};
+
+/// A [`SchemaService`] implementation that delegates all calls to `T`,
+/// removing the [`NamespaceSchema`] of the affected namespace from the cache
+/// `C` after a table or column is successfully renamed.
+///
+/// Without this eviction, the router would continue to resolve writes using
+/// the previous name against the stale cached schema, rather than treating
+/// the previous name as free and the new name as taken.
+///
+/// Only the cache of the router that served the rename request is updated -
+/// other routers observe the change once their cached schema expires
+/// (see [`TtlCache`]).
+///
+/// After a column is renamed, each ingester is also told to discard the column
+/// IDs it cached for the table, so that a new column created with the previous
+/// name is not mistaken for the renamed one. This happens before the namespace
+/// is evicted from the cache, so that this router does not create such a
+/// column until the ingesters have been notified. An ingester that cannot be
+/// reached is logged and skipped - it discards its cache when restarted.
+///
+/// [`NamespaceSchema`]: data_types::NamespaceSchema
+/// [`TtlCache`]: crate::namespace_cache::TtlCache
+#[derive(Debug)]
+pub struct CacheInvalidatingSchemaService<T, C, W = LazyConnector> {
+    inner: T,
+    cache: C,
+    ingesters: Arc<[(W, Arc<str>)]>,
+}
+
+impl<T, C, W> CacheInvalidatingSchemaService<T, C, W> {
+    /// Decorate `inner`, evicting namespaces from `cache` when one of their
+    /// tables or columns is renamed, and invalidating the column IDs cached by
+    /// `ingesters` when a column is renamed.
+    pub fn new(inner: T, cache: C, ingesters: Arc<[(W, Arc<str>)]>) -> Self {
+        Self {
+            inner,
+            cache,
+            ingesters,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<T, C, W> SchemaService for CacheInvalidatingSchemaService<T, C, W>
+where
+    T: SchemaService,
+    C: NamespaceCache + 'static,
+    W: WriteClient + 'static,
+{
+    async fn get_schema(
+        &self,
+        request: Request<GetSchemaRequest>,
+    ) -> Result<Response<GetSchemaResponse>, Status> {
+        self.inner.get_schema(request).await
+    }
+
+    async fn rename_table(
+        &self,
+        request: Request<RenameTableRequest>,
+    ) -> Result<Response<RenameTableResponse>, Status> {
+        let namespace = request.get_ref().namespace.clone();
+        let resp = self.inner.rename_table(request).await?;
+        evict_namespace(&self.cache, namespace);
+        Ok(resp)
+    }
+
+    async fn rename_column(
+        &self,
+        request: Request<RenameColumnRequest>,
+    ) -> Result<Response<RenameColumnResponse>, Status> {
+        let namespace = request.get_ref().namespace.clone();
+        let resp = self.inner.rename_column(request).await?;
+
+        let namespace_id = resp.get_ref().namespace_id;
+        let table_id = resp.get_ref().table_id;
+        let requests = self.ingesters.iter().map(|(client, name)| async move {
+            let req = InvalidateColumnsRequest {
+                namespace_id,
+                table_id,
+            };
+            let res = tokio::time::timeout(RPC_TIMEOUT, client.invalidate_columns(req))
+                .await
+                .map_err(RpcWriteError::Timeout)
+                .and_then(|v| v);
+            (name, res)
+        });
+        for (name, res) in join_all(requests).await {
+            if let Err(e) = res {
+                warn!(
+                    error=%e,
+                    ingester=%name,
+                    %namespace,
+                    table_id,
+                    "failed to invalidate ingester column ids"
+                );
+            }
+        }
+
+        evict_namespace(&self.cache, namespace);
+        Ok(resp)
+    }
+}
@@ -0,0 +1,70 @@
+//! A [`TableService`] decorator evicting modified namespaces from the router's
+//! schema cache.
+
+use generated_types::influxdata::iox::table::v1::{table_service_server::TableService, *};
+use tonic::{Request, Response, Status};
+
+use super::evict_namespace;
+use crate::namespace_cache::NamespaceCache;
+
+/// A [`TableService`] implementation that delegates all calls to `T`, removing
+/// the [`NamespaceSchema`] of the affected namespace from the cache `C` after
+/// a table or column is successfully deleted.
+///
+/// Without this eviction, the router would continue to accept writes for a
+/// deleted table or column using the stale cached schema, rather than
+/// re-creating (and so reviving) it in the catalog.
+///
+/// Only the cache of the router that served the delete request is updated -
+/// other routers observe the change once their cached schema expires
+/// (see [`TtlCache`]).
+///
+/// [`NamespaceSchema`]: data_types::NamespaceSchema
+/// [`TtlCache`]: crate::namespace_cache::TtlCache
+#[derive(Debug)]
+pub struct CacheInvalidatingTableService<T, C> {
+    inner: T,
+    cache: C,
+}
+
+impl<T, C> CacheInvalidatingTableService<T, C> {
+    /// Decorate `inner`, evicting namespaces from `cache` when one of their
+    /// tables or columns is deleted.
+    pub fn new(inner: T, cache: C) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[tonic::async_trait]
+impl<T, C> TableService for CacheInvalidatingTableService<T, C>
+where
+    T: TableService,
+    C: NamespaceCache + 'static,
+{
+    async fn create_table(
+        &self,
+        request: Request<CreateTableRequest>,
+    ) -> Result<Response<CreateTableResponse>, Status> {
+        self.inner.create_table(request).await
+    }
+
+    async fn delete_table(
+        &self,
+        request: Request<DeleteTableRequest>,
+    ) -> Result<Response<DeleteTableResponse>, Status> {
+        let namespace = request.get_ref().namespace.clone();
+        let resp = self.inner.delete_table(request).await?;
+        evict_namespace(&self.cache, namespace);
+        Ok(resp)
+    }
+
+    async fn delete_column(
+        &self,
+        request: Request<DeleteColumnRequest>,
+    ) -> Result<Response<DeleteColumnResponse>, Status> {
+        let namespace = request.get_ref().namespace.clone();
+        let resp = self.inner.delete_column(request).await?;
+        evict_namespace(&self.cache, namespace);
+        Ok(resp)
+    }
+}
@@ -1,29 +1,44 @@
 //! HTTP service implementations for `router`.
 
+pub mod delete;
 pub mod write;
 
 use authz::{http::AuthorizationHeaderExtension, Action, Authorizer, Permission, Resource};
 use bytes::{Bytes, BytesMut};
+use data_types::{ColumnType, NamespaceName, NamespaceSchema};
+use flate2::write::GzDecoder;
 use futures::StreamExt;
 use hashbrown::HashMap;
 use hyper::{header::CONTENT_ENCODING, Body, Method, Request, Response, StatusCode};
+use influxdb_line_protocol::{FieldValue, ParsedLine};
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{DurationHistogram, U64Counter};
 use mutable_batch::MutableBatch;
-use mutable_batch_lp::LinesConverter;
+use mutable_batch_lp::{LineBuffer, LinesConverter, SkippedLine};
 use observability_deps::tracing::*;
-use std::{str::Utf8Error, sync::Arc, time::Instant};
+use std::{
+    fmt::Display,
+    io::Write,
+    str::Utf8Error,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use thiserror::Error;
 use tokio::sync::{Semaphore, TryAcquireError};
 use trace::ctx::SpanContext;
 
-use self::write::{
-    multi_tenant::MultiTenantExtractError, single_tenant::SingleTenantExtractError,
-    WriteParamExtractor, WriteParams,
+use self::{
+    delete::{DeleteRequest, DeleteRequestError},
+    write::{
+        multi_tenant::MultiTenantExtractError, single_tenant::SingleTenantExtractError,
+        WriteParamExtractor, WriteParams,
+    },
 };
 use crate::{
+    delete_handler::{DeleteError, DeleteHandler},
     dml_handlers::{
-        DmlError, DmlHandler, PartitionError, RetentionError, RpcWriteError, SchemaError,
+        DmlError, DmlHandler, PartitionError, RateLimitError, RetentionError, RpcWriteError,
+        SchemaError,
     },
     namespace_resolver::NamespaceResolver,
 };
@@ -39,6 +54,14 @@ pub enum Error {
     #[error("deletes are not supported")]
     DeletesUnsupported,
 
+    /// The delete request body could not be parsed.
+    #[error("failed to parse delete request: {0}")]
+    ParseDelete(#[from] DeleteRequestError),
+
+    /// An error returned from the [`DeleteHandler`].
+    #[error("delete handler error: {0}")]
+    DeleteHandler(#[from] DeleteError),
+
     /// An error parsing a single-tenant HTTP request.
     #[error(transparent)]
     SingleTenantError(#[from] SingleTenantExtractError),
@@ -79,6 +102,16 @@ pub enum Error {
     #[error("dml handler error: {0}")]
     DmlHandler(#[from] DmlError),
 
+    /// Some lines of a write request were rejected, and the remaining lines
+    /// were written.
+    ///
+    /// Only returned when partial writes are enabled.
+    #[error(
+        "partial write has occurred, errors encountered on line(s): {}",
+        join_rejected(.0)
+    )]
+    PartialWrite(Vec<RejectedLine>),
+
     /// An error that occurs when attempting to map the user-provided namespace
     /// name into a [`NamespaceId`].
     ///
@@ -116,12 +149,17 @@ impl Error {
             Error::NonUtf8ContentHeader(_) => StatusCode::BAD_REQUEST,
             Error::NonUtf8Body(_) => StatusCode::BAD_REQUEST,
             Error::ParseLineProtocol(_) => StatusCode::BAD_REQUEST,
+            Error::PartialWrite(_) => StatusCode::BAD_REQUEST,
             Error::RequestSizeExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
             Error::InvalidContentEncoding(_) => {
                 // https://www.rfc-editor.org/rfc/rfc7231#section-6.5.13
                 StatusCode::UNSUPPORTED_MEDIA_TYPE
             }
             Error::DmlHandler(err) => StatusCode::from(err),
+            Error::ParseDelete(_) => StatusCode::BAD_REQUEST,
+            Error::DeleteHandler(DeleteError::NamespaceNotFound(_)) => StatusCode::NOT_FOUND,
+            Error::DeleteHandler(DeleteError::Catalog(_)) => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::DeleteHandler(DeleteError::Upstream(..)) => StatusCode::SERVICE_UNAVAILABLE,
             // Error from the namespace resolver is 4xx if autocreation is disabled, 5xx otherwise
             Error::NamespaceResolver(crate::namespace_resolver::Error::Create(
                 crate::namespace_resolver::ns_autocreation::NamespaceCreationError::Reject(_),
@@ -135,6 +173,111 @@ impl Error {
             Error::MultiTenantError(e) => StatusCode::from(e),
         }
     }
+
+    /// Returns the duration the client should wait before retrying the
+    /// request, if the request was rejected for exceeding a rate limit.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::DmlHandler(DmlError::RateLimit(e)) => e.retry_after(),
+            _ => None,
+        }
+    }
+}
+
+/// Validate `line`, with the timestamp `time`, against the column types of the
+/// namespace `schema` and the minimum time allowed by its retention period.
+///
+/// Returns the reason the line would be rejected by the schema or retention
+/// validation of the [`DmlHandler`], if any.
+fn validate_line(
+    schema: &NamespaceSchema,
+    min_time: Option<i64>,
+    line: &ParsedLine<'_>,
+    time: i64,
+) -> Result<(), String> {
+    let table = line.series.measurement.as_str();
+    if min_time.map_or(false, |min_time| time < min_time) {
+        return Err(format!(
+            "data in table {table} is outside of the retention period"
+        ));
+    }
+
+    let table_schema = match schema.tables.get(table) {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    let tags = line
+        .series
+        .tag_set
+        .iter()
+        .flatten()
+        .map(|(name, _)| (name.as_str(), ColumnType::Tag));
+    let fields = line.field_set.iter().map(|(name, value)| {
+        let column_type = match value {
+            FieldValue::I64(_) => ColumnType::I64,
+            FieldValue::U64(_) => ColumnType::U64,
+            FieldValue::F64(_) => ColumnType::F64,
+            FieldValue::String(_) => ColumnType::String,
+            FieldValue::Boolean(_) => ColumnType::Bool,
+        };
+        (name.as_str(), column_type)
+    });
+
+    for (name, column_type) in tags.chain(fields) {
+        match table_schema.columns.get(name) {
+            Some(existing) if existing.column_type != column_type => {
+                return Err(format!(
+                    "table {table}, column {name} is type {} but write has type {column_type}",
+                    existing.column_type
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// A line of a write request that was rejected by a partial write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedLine {
+    /// The 1-based line number of the rejected line.
+    pub line: usize,
+    /// A description of why the line was rejected.
+    pub reason: String,
+}
+
+impl Display for RejectedLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl From<SkippedLine> for RejectedLine {
+    fn from(v: SkippedLine) -> Self {
+        // The line number is reported separately, so use the underlying error
+        // as the reason where possible.
+        let reason = match v.error {
+            mutable_batch_lp::Error::LineProtocol { source, .. } => source.to_string(),
+            mutable_batch_lp::Error::Write { source, .. } => source.to_string(),
+            mutable_batch_lp::Error::Validation { reason, .. } => reason,
+            e => e.to_string(),
+        };
+
+        Self {
+            line: v.line,
+            reason,
+        }
+    }
+}
+
+fn join_rejected(lines: &[RejectedLine]) -> String {
+    lines
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 impl From<authz::Error> for Error {
@@ -169,10 +312,17 @@ impl From<&DmlError> for StatusCode {
 
             DmlError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
             DmlError::Partition(PartitionError::BatchWrite(_)) => StatusCode::INTERNAL_SERVER_ERROR,
+            DmlError::Partition(PartitionError::NamespaceLookup(_)) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
             DmlError::Retention(RetentionError::NamespaceLookup(_)) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
             DmlError::Retention(RetentionError::OutsideRetention(_)) => StatusCode::FORBIDDEN,
+            DmlError::RateLimit(RateLimitError::NamespaceLookup(_)) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            DmlError::RateLimit(RateLimitError::Exceeded { .. }) => StatusCode::TOO_MANY_REQUESTS,
             DmlError::RpcWrite(RpcWriteError::Upstream(_)) => StatusCode::INTERNAL_SERVER_ERROR,
             DmlError::RpcWrite(RpcWriteError::Timeout(_)) => StatusCode::GATEWAY_TIMEOUT,
             DmlError::RpcWrite(
@@ -197,9 +347,14 @@ pub struct HttpDelegate<D, N, T = SystemProvider> {
     time_provider: T,
     namespace_resolver: N,
     dml_handler: D,
+    delete_handler: Option<Arc<dyn DeleteHandler>>,
     authz: Option<Arc<dyn Authorizer>>,
     write_param_extractor: Box<dyn WriteParamExtractor>,
 
+    // When true, lines that fail to parse or validate are rejected
+    // individually instead of failing the whole write request.
+    partial_writes: bool,
+
     // A request limiter to restrict the number of simultaneous requests this
     // router services.
     //
@@ -214,6 +369,8 @@ pub struct HttpDelegate<D, N, T = SystemProvider> {
     write_metric_fields: U64Counter,
     write_metric_tables: U64Counter,
     write_metric_body_size: U64Counter,
+    write_metric_rejected_lines: U64Counter,
+    delete_metric_body_size: U64Counter,
     request_limit_rejected: U64Counter,
 }
 
@@ -256,6 +413,18 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
                 "cumulative byte size of successfully routed (decompressed) line protocol write requests",
             )
             .recorder(&[]);
+        let write_metric_rejected_lines = metrics
+            .register_metric::<U64Counter>(
+                "http_write_rejected_lines",
+                "cumulative number of line protocol lines rejected by partial writes",
+            )
+            .recorder(&[]);
+        let delete_metric_body_size = metrics
+            .register_metric::<U64Counter>(
+                "http_delete_body_bytes",
+                "cumulative byte size of successfully routed (decompressed) delete requests",
+            )
+            .recorder(&[]);
         let request_limit_rejected = metrics
             .register_metric::<U64Counter>(
                 "http_request_limit_rejected",
@@ -275,18 +444,48 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
             namespace_resolver,
             write_param_extractor,
             dml_handler,
+            delete_handler: None,
             authz,
+            partial_writes: false,
             request_sem: Semaphore::new(max_requests),
             write_metric_lines,
             http_line_protocol_parse_duration,
             write_metric_fields,
             write_metric_tables,
             write_metric_body_size,
+            write_metric_rejected_lines,
+            delete_metric_body_size,
             request_limit_rejected,
         }
     }
 }
 
+impl<D, N, T> HttpDelegate<D, N, T> {
+    /// Handle requests to the delete endpoint with `delete_handler`.
+    ///
+    /// Without a delete handler, delete requests are rejected with
+    /// [`Error::DeletesUnsupported`].
+    pub fn with_delete_handler(mut self, delete_handler: Arc<dyn DeleteHandler>) -> Self {
+        self.delete_handler = Some(delete_handler);
+        self
+    }
+
+    /// Enable or disable partial writes.
+    ///
+    /// When enabled, lines that fail to parse, conflict with the column types
+    /// of the namespace schema or fall outside of its retention period are
+    /// skipped while the rest of the write is applied. The skipped lines are
+    /// then reported in an [`Error::PartialWrite`].
+    ///
+    /// Lines are validated against the cached namespace schema as they are
+    /// converted, and the remaining lines are passed to the [`DmlHandler`] in
+    /// a single write.
+    pub fn with_partial_writes(mut self, enabled: bool) -> Self {
+        self.partial_writes = enabled;
+        self
+    }
+}
+
 impl<D, N, T> HttpDelegate<D, N, T>
 where
     D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
@@ -323,7 +522,10 @@ where
                 let dml_info = self.write_param_extractor.parse_v2(&req)?;
                 self.write_handler(req, dml_info).await
             }
-            (&Method::POST, "/api/v2/delete") => return Err(Error::DeletesUnsupported),
+            (&Method::POST, "/api/v2/delete") => {
+                let dml_info = self.write_param_extractor.parse_v2(&req)?;
+                self.delete_handler(req, dml_info).await
+            }
             _ => return Err(Error::NoHandler),
         }
         .map(|_summary| {
@@ -341,71 +543,100 @@ where
     ) -> Result<(), Error> {
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
-        let token = req
-            .extensions()
-            .get::<AuthorizationHeaderExtension>()
-            .and_then(|v| v.as_ref())
-            .and_then(|v| {
-                let s = v.as_ref();
-                if s.len() < b"Token ".len() {
-                    None
-                } else {
-                    match s.split_at(b"Token ".len()) {
-                        (b"Token ", token) => Some(token),
-                        _ => None,
-                    }
-                }
-            });
-        let perms = [Permission::ResourceAction(
-            Resource::Database(write_info.namespace.to_string()),
-            Action::Write,
-        )];
-        self.authz.require_any_permission(token, &perms).await?;
+        self.authorize_write(&req, &write_info).await?;
 
         trace!(
             namespace=%write_info.namespace,
             "processing write request"
         );
 
-        // Read the HTTP body and convert it to a str.
-        let body = self.read_body(req).await?;
-        let body = std::str::from_utf8(&body).map_err(Error::NonUtf8Body)?;
-
         // The time, in nanoseconds since the epoch, to assign to any points that don't
         // contain a timestamp
         let default_time = self.time_provider.now().timestamp_nanos();
-        let start_instant = Instant::now();
+
+        // Partial writes validate each line against the namespace schema as it
+        // is converted, so the namespace is resolved before the body is read.
+        let schema = match self.partial_writes {
+            true => Some(
+                self.namespace_resolver
+                    .get_namespace_schema(&write_info.namespace)
+                    .await?,
+            ),
+            false => None,
+        };
+        let min_time = schema
+            .as_ref()
+            .and_then(|s| s.retention_period_ns)
+            .map(|retention_period_ns| default_time - retention_period_ns);
 
         let mut converter = LinesConverter::new(default_time);
         converter.set_timestamp_base(write_info.precision.timestamp_base());
-        let (batches, stats) = match converter.write_lp(body).and_then(|_| converter.finish()) {
+
+        // Convert the line protocol as the HTTP body is read, so that only the
+        // resulting batches (and not the whole, decoded body) are held in
+        // memory.
+        let mut lines = LineBuffer::default();
+        let mut rejected = Vec::new();
+        let mut duration = Duration::ZERO;
+        let mut convert = |text: &str| {
+            let start_instant = Instant::now();
+            let res = match &schema {
+                Some(schema) => {
+                    rejected.extend(
+                        converter
+                            .write_lp_partial(text, |line, time| {
+                                validate_line(schema, min_time, line, time)
+                            })
+                            .into_iter()
+                            .map(RejectedLine::from),
+                    );
+                    Ok(())
+                }
+                None => converter.write_lp(text).map_err(Error::ParseLineProtocol),
+            };
+            duration += start_instant.elapsed();
+            res
+        };
+        let body_size = self
+            .stream_body(req, |chunk| {
+                let text = lines.push(chunk).map_err(Error::NonUtf8Body)?;
+                convert(&text)
+            })
+            .await?;
+        convert(&lines.finish().map_err(Error::NonUtf8Body)?)?;
+
+        let (batches, stats) = match converter.finish() {
             Ok(v) => v,
             Err(mutable_batch_lp::Error::EmptyPayload) => {
                 debug!("nothing to write");
-                return Ok(());
+                return self.finish_partial_write(rejected);
             }
             Err(e) => return Err(Error::ParseLineProtocol(e)),
         };
 
         let num_tables = batches.len();
-        let duration = start_instant.elapsed();
         self.http_line_protocol_parse_duration.record(duration);
         debug!(
             num_lines=stats.num_lines,
             num_fields=stats.num_fields,
             num_tables,
+            num_rejected_lines=rejected.len(),
             precision=?write_info.precision,
-            body_size=body.len(),
+            body_size,
             namespace=%write_info.namespace,
             duration=?duration,
             "routing write",
         );
 
         // Retrieve the namespace ID for this namespace.
-        let namespace_id = self
-            .namespace_resolver
-            .get_namespace_id(&write_info.namespace)
-            .await?;
+        let namespace_id = match &schema {
+            Some(schema) => schema.id,
+            None => {
+                self.namespace_resolver
+                    .get_namespace_id(&write_info.namespace)
+                    .await?
+            }
+        };
 
         self.dml_handler
             .write(&write_info.namespace, namespace_id, batches, span_ctx)
@@ -415,14 +646,118 @@ where
         self.write_metric_lines.inc(stats.num_lines as _);
         self.write_metric_fields.inc(stats.num_fields as _);
         self.write_metric_tables.inc(num_tables as _);
-        self.write_metric_body_size.inc(body.len() as _);
+        self.write_metric_body_size.inc(body_size as _);
+
+        self.finish_partial_write(rejected)
+    }
+
+    /// Return an [`Error::PartialWrite`] if any lines were `rejected`.
+    fn finish_partial_write(&self, rejected: Vec<RejectedLine>) -> Result<(), Error> {
+        if rejected.is_empty() {
+            return Ok(());
+        }
+
+        self.write_metric_rejected_lines.inc(rejected.len() as _);
+        Err(Error::PartialWrite(rejected))
+    }
+
+    /// Parse a predicate delete from the request body and record it with the
+    /// configured [`DeleteHandler`], rejecting the request if deletes are not
+    /// supported or the token does not permit writes to the namespace.
+    async fn delete_handler(
+        &self,
+        req: Request<Body>,
+        delete_info: WriteParams,
+    ) -> Result<(), Error> {
+        let delete_handler = self
+            .delete_handler
+            .as_ref()
+            .ok_or(Error::DeletesUnsupported)?;
+
+        self.authorize_write(&req, &delete_info).await?;
+
+        trace!(
+            namespace=%delete_info.namespace,
+            "processing delete request"
+        );
+
+        // Read the HTTP body and parse the delete request from it.
+        let body = self.read_body(req).await?;
+        let delete = DeleteRequest::try_from(body.as_ref())?;
+
+        debug!(
+            table_name=?delete.table_name,
+            predicate=?delete.predicate,
+            namespace=%delete_info.namespace,
+            "routing delete",
+        );
+
+        delete_handler
+            .delete(
+                &delete_info.namespace,
+                delete.table_name.as_deref(),
+                &delete.predicate,
+            )
+            .await?;
+
+        self.delete_metric_body_size.inc(body.len() as _);
+
+        Ok(())
+    }
+
+    /// Require the request to carry a token that permits writes to the
+    /// namespace in `params`.
+    async fn authorize_write(
+        &self,
+        req: &Request<Body>,
+        params: &WriteParams,
+    ) -> Result<(), Error> {
+        let token = req
+            .extensions()
+            .get::<AuthorizationHeaderExtension>()
+            .and_then(|v| v.as_ref())
+            .and_then(|v| {
+                let s = v.as_ref();
+                if s.len() < b"Token ".len() {
+                    None
+                } else {
+                    match s.split_at(b"Token ".len()) {
+                        (b"Token ", token) => Some(token),
+                        _ => None,
+                    }
+                }
+            });
+        let perms = [Permission::ResourceAction(
+            Resource::Database(params.namespace.to_string()),
+            Action::Write,
+        )];
+        self.authz.require_any_permission(token, &perms).await?;
 
         Ok(())
     }
 
-    /// Parse the request's body into raw bytes, applying the configured size
+    /// Read the request's body into raw bytes, applying the configured size
     /// limits and decoding any content encoding.
     async fn read_body(&self, req: hyper::Request<Body>) -> Result<Bytes, Error> {
+        let mut body = BytesMut::new();
+        self.stream_body(req, |chunk| {
+            body.extend_from_slice(chunk);
+            Ok(())
+        })
+        .await?;
+
+        Ok(body.freeze())
+    }
+
+    /// Read the request's body, applying the configured size limits and
+    /// decoding any content encoding, passing the decoded data to `f` as it
+    /// arrives.
+    ///
+    /// Returns the total (decoded) size of the body.
+    async fn stream_body<F>(&self, req: hyper::Request<Body>, mut f: F) -> Result<usize, Error>
+    where
+        F: FnMut(&[u8]) -> Result<(), Error>,
+    {
         let encoding = req
             .headers()
             .get(&CONTENT_ENCODING)
@@ -434,47 +769,102 @@ where
             Some(v) => return Err(Error::InvalidContentEncoding(v.to_string())),
         };
 
+        // Decode gzip-encoded content as it is read, writing at most
+        // max_request_bytes of decoded data to prevent a decompression bomb
+        // based DoS.
+        let mut decoder =
+            ungzip.then(|| GzDecoder::new(LimitedBuffer::new(self.max_request_bytes)));
+
         let mut payload = req.into_body();
 
-        let mut body = BytesMut::new();
+        let mut read = 0;
+        let mut size = 0;
         while let Some(chunk) = payload.next().await {
             let chunk = chunk.map_err(Error::ClientHangup)?;
-            // limit max size of in-memory payload
-            if (body.len() + chunk.len()) > self.max_request_bytes {
+            // limit max size of the received payload
+            read += chunk.len();
+            if read > self.max_request_bytes {
                 return Err(Error::RequestSizeExceeded(self.max_request_bytes));
             }
-            body.extend_from_slice(&chunk);
+
+            match decoder.as_mut() {
+                None => {
+                    size += chunk.len();
+                    f(&chunk)?;
+                }
+                Some(decoder) => {
+                    if let Err(e) = decoder.write_all(&chunk) {
+                        return Err(decoder.get_ref().decode_error(e));
+                    }
+                    let decoded = decoder.get_mut().take();
+                    size += decoded.len();
+                    f(&decoded)?;
+                }
+            }
         }
-        let body = body.freeze();
 
-        // If the body is not compressed, return early.
-        if !ungzip {
-            return Ok(body);
+        // Flush the remaining decoded data, and validate the gzip trailer.
+        if let Some(mut decoder) = decoder {
+            if let Err(e) = decoder.try_finish() {
+                return Err(decoder.get_ref().decode_error(e));
+            }
+            let decoded = decoder.get_mut().take();
+            size += decoded.len();
+            f(&decoded)?;
         }
 
-        // Unzip the gzip-encoded content
-        use std::io::Read;
-        let decoder = flate2::read::GzDecoder::new(&body[..]);
+        Ok(size)
+    }
+}
+
+/// A [`Write`] implementation buffering decoded data, that fails once more
+/// than `limit` bytes have been written to it in total.
+#[derive(Debug)]
+struct LimitedBuffer {
+    buf: Vec<u8>,
+    written: usize,
+    limit: usize,
+}
 
-        // Read at most max_request_bytes bytes to prevent a decompression bomb
-        // based DoS.
-        //
-        // In order to detect if the entire stream ahs been read, or truncated,
-        // read an extra byte beyond the limit and check the resulting data
-        // length - see the max_request_size_truncation test.
-        let mut decoder = decoder.take(self.max_request_bytes as u64 + 1);
-        let mut decoded_data = Vec::new();
-        decoder
-            .read_to_end(&mut decoded_data)
-            .map_err(Error::InvalidGzip)?;
-
-        // If the length is max_size+1, the body is at least max_size+1 bytes in
-        // length, and possibly longer, but truncated.
-        if decoded_data.len() > self.max_request_bytes {
-            return Err(Error::RequestSizeExceeded(self.max_request_bytes));
+impl LimitedBuffer {
+    fn new(limit: usize) -> Self {
+        Self {
+            buf: Vec::new(),
+            written: 0,
+            limit,
+        }
+    }
+
+    /// Take the data buffered so far.
+    fn take(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buf)
+    }
+
+    /// Map an error decoding data into this buffer to the [`Error`] returned
+    /// to the user.
+    fn decode_error(&self, e: std::io::Error) -> Error {
+        if self.written > self.limit {
+            return Error::RequestSizeExceeded(self.limit);
+        }
+        Error::InvalidGzip(e)
+    }
+}
+
+impl Write for LimitedBuffer {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.written += data.len();
+        if self.written > self.limit {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "decoded size limit exceeded",
+            ));
         }
+        self.buf.extend_from_slice(data);
+        Ok(data.len())
+    }
 
-        Ok(decoded_data.into())
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
     }
 }
 
@@ -482,6 +872,7 @@ where
 mod tests {
     use super::*;
     use crate::{
+        delete_handler::mock::MockDeleteHandler,
         dml_handlers::{
             mock::{MockDmlHandler, MockDmlHandlerCall},
             CachedServiceProtectionLimit,
@@ -498,7 +889,8 @@ mod tests {
     use assert_matches::assert_matches;
     use async_trait::async_trait;
     use data_types::{
-        NamespaceId, NamespaceName, NamespaceNameError, OrgBucketMappingError, TableId,
+        ColumnId, ColumnSchema, NamespaceId, NamespaceName, NamespaceNameError,
+        OrgBucketMappingError, QueryPoolId, TableId, TableSchema, TimestampRange, TopicId,
     };
     use flate2::{write::GzEncoder, Compression};
     use hyper::header::HeaderValue;
@@ -619,6 +1011,9 @@ mod tests {
                     let dml_handler = Arc::new(MockDmlHandler::default()
                         .with_write_return($dml_write_handler)
                     );
+                    let delete_handler = Arc::new(MockDeleteHandler::default()
+                        .with_delete_return($dml_delete_handler)
+                    );
                     let metrics = Arc::new(metric::Registry::default());
                     let delegate = HttpDelegate::new(
                         MAX_BYTES,
@@ -628,7 +1023,8 @@ mod tests {
                         None,
                         &metrics,
                         Box::<crate::server::http::write::multi_tenant::MultiTenantRequestParser>::default(),
-                    );
+                    )
+                    .with_delete_handler(delete_handler);
 
                     let got = delegate.route(request).await;
                     assert_matches!(got, $want_result);
@@ -703,6 +1099,29 @@ mod tests {
         };
     }
 
+    // Wrapper over test_http_handler specifically for delete requests.
+    macro_rules! test_delete_handler {
+        (
+            $name:ident,
+            query_string = $query_string:expr,   // Request URI query string
+            body = $body:expr,                   // Request body content
+            delete_handler = $delete_handler:expr, // Delete handler response (if called)
+            want_result = $want_result:pat
+        ) => {
+            paste::paste! {
+                test_http_handler!(
+                    [<delete_ $name>],
+                    uri = format!("https://bananas.example/api/v2/delete{}", $query_string),
+                    body = $body,
+                    dml_write_handler = [],
+                    dml_delete_handler = $delete_handler,
+                    want_result = $want_result,
+                    want_dml_calls = []
+                );
+            }
+        };
+    }
+
     test_write_handler!(
         ok,
         query_string = "?org=bananas&bucket=test",
@@ -935,6 +1354,50 @@ mod tests {
         want_dml_calls = []
     );
 
+    test_delete_handler!(
+        ok,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"1","stop":"2","predicate":"_measurement=platanos"}"#.as_bytes(),
+        delete_handler = [Ok(())],
+        want_result = Ok(_)
+    );
+
+    test_delete_handler!(
+        no_query_params,
+        query_string = "",
+        body = r#"{"start":"1","stop":"2"}"#.as_bytes(),
+        delete_handler = [],
+        want_result = Err(Error::MultiTenantError(
+            MultiTenantExtractError::ParseV2Request(V2WriteParseError::NoQueryParams)
+        ))
+    );
+
+    test_delete_handler!(
+        invalid_body,
+        query_string = "?org=bananas&bucket=test",
+        body = "platanos,tag1=A,tag2=B val=42i 123456".as_bytes(),
+        delete_handler = [],
+        want_result = Err(Error::ParseDelete(DeleteRequestError::DecodeFail(_)))
+    );
+
+    test_delete_handler!(
+        invalid_predicate,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"1","stop":"2","predicate":"tag1 > 42"}"#.as_bytes(),
+        delete_handler = [],
+        want_result = Err(Error::ParseDelete(DeleteRequestError::InvalidPredicate(_)))
+    );
+
+    test_delete_handler!(
+        namespace_not_found,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"1","stop":"2"}"#.as_bytes(),
+        delete_handler = [Err(DeleteError::NamespaceNotFound(
+            NAMESPACE_NAME.to_string()
+        ))],
+        want_result = Err(Error::DeleteHandler(DeleteError::NamespaceNotFound(_)))
+    );
+
     test_http_handler!(
         not_found,
         uri = "https://bananas.example/wat",
@@ -1326,6 +1789,227 @@ mod tests {
         assert_matches!(got, Err(Error::NoHandler));
     }
 
+    #[tokio::test]
+    async fn test_delete_request() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let delete_handler = Arc::new(MockDeleteHandler::default().with_delete_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            MockNamespaceResolver::default(),
+            Arc::clone(&dml_handler),
+            None,
+            &metrics,
+            Box::<MultiTenantRequestParser>::default(),
+        )
+        .with_delete_handler(Arc::clone(&delete_handler) as _);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/delete?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from(
+                r#"{"start":"1","stop":"2","predicate":"_measurement=platanos and tag1='A'"}"#,
+            ))
+            .unwrap();
+
+        let got = delegate.route(request).await;
+        assert_matches!(got, Ok(r) => {
+            assert_eq!(r.status(), StatusCode::NO_CONTENT);
+        });
+
+        assert_matches!(delete_handler.calls().as_slice(), [call] => {
+            assert_eq!(call.namespace, NAMESPACE_NAME);
+            assert_eq!(call.table_name.as_deref(), Some("platanos"));
+            assert_eq!(call.predicate.range, TimestampRange::new(1, 2));
+            assert_eq!(call.predicate.expr_sql_string(), r#""tag1"='A'"#);
+        });
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_unsupported() {
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            MockNamespaceResolver::default(),
+            Arc::new(MockDmlHandler::default()),
+            None,
+            &metrics,
+            Box::<MultiTenantRequestParser>::default(),
+        );
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/delete?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from(r#"{"start":"1","stop":"2"}"#))
+            .unwrap();
+
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(Error::DeletesUnsupported));
+    }
+
+    #[tokio::test]
+    async fn test_partial_write() {
+        let body = "platanos,tag1=A val=42i\n\
+                    bananas\n\
+                    platanos,tag1=B val=4.2\n\
+                    ananas,tag1=A val=1i 1\n\
+                    platanos,tag1=C val=24i\n\
+                    ananas,tag1=B val=2i";
+
+        // A namespace with a one hour retention period, in which the "val"
+        // column of "platanos" is an integer.
+        let mut schema = NamespaceSchema::new(
+            NAMESPACE_ID,
+            TopicId::new(1),
+            QueryPoolId::new(1),
+            100,
+            100,
+            Some(3_600_000_000_000),
+        );
+        let mut table = TableSchema::new(TableId::new(1));
+        table.columns.insert(
+            "val".to_string(),
+            ColumnSchema {
+                id: ColumnId::new(1),
+                column_type: ColumnType::I64,
+            },
+        );
+        schema.tables.insert("platanos".to_string(), table);
+
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_schema(NAMESPACE_NAME, schema);
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            None,
+            &metrics,
+            Box::<MultiTenantRequestParser>::default(),
+        )
+        .with_partial_writes(true);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from(body))
+            .unwrap();
+
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(Error::PartialWrite(rejected)) => {
+            let lines = rejected.iter().map(|v| v.line).collect::<Vec<_>>();
+            assert_eq!(lines, [2, 3, 4]);
+            assert_eq!(
+                rejected[1].reason,
+                "table platanos, column val is type i64 but write has type f64"
+            );
+            assert_eq!(
+                rejected[2].reason,
+                "data in table ananas is outside of the retention period"
+            );
+        });
+
+        // The remaining lines are written in a single call.
+        assert_matches!(dml_handler.calls().as_slice(), [
+            MockDmlHandlerCall::Write { write_input, .. },
+        ] => {
+            assert_eq!(write_input.len(), 2);
+            assert_eq!(write_input["platanos"].rows(), 2);
+            assert_eq!(write_input["ananas"].rows(), 1);
+        });
+
+        assert_metric_hit(&metrics, "http_write_lines", Some(3));
+        assert_metric_hit(&metrics, "http_write_tables", Some(2));
+        assert_metric_hit(&metrics, "http_write_rejected_lines", Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_partial_write_disabled() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            MockNamespaceResolver::default(),
+            Arc::clone(&dml_handler),
+            None,
+            &metrics,
+            Box::<MultiTenantRequestParser>::default(),
+        );
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from("platanos,tag1=A val=42i 1\nbananas"))
+            .unwrap();
+
+        let got = delegate.route(request).await;
+        assert_matches!(
+            got,
+            Err(Error::ParseLineProtocol(
+                mutable_batch_lp::Error::LineProtocol { line: 2, .. }
+            ))
+        );
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    /// Assert line protocol split across body chunks at arbitrary offsets is
+    /// converted as it is read, for both plain and gzip-encoded bodies.
+    #[tokio::test]
+    async fn test_write_streamed_body() {
+        let lp = "platanos,tag1=A val=42i 1\n\
+                  platanos,tag1=B desc=\"multi\nline\" 2\n\
+                  platanos,tag1=C val=24i 3";
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(lp.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        for (body, encoding) in [(lp.as_bytes().to_vec(), None), (compressed, Some("gzip"))] {
+            let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+            let metrics = Arc::new(metric::Registry::default());
+            let delegate = HttpDelegate::new(
+                MAX_BYTES,
+                1,
+                MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID),
+                Arc::clone(&dml_handler),
+                None,
+                &metrics,
+                Box::<MultiTenantRequestParser>::default(),
+            );
+
+            // Deliver the body in small chunks, splitting lines, quoted
+            // strings and the gzip stream.
+            let chunks = body
+                .chunks(7)
+                .map(|v| Ok::<_, std::io::Error>(v.to_vec()))
+                .collect::<Vec<_>>();
+            let mut request = Request::builder()
+                .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+                .method("POST");
+            if let Some(encoding) = encoding {
+                request = request.header(CONTENT_ENCODING, encoding);
+            }
+            let request = request
+                .body(Body::wrap_stream(futures::stream::iter(chunks)))
+                .unwrap();
+
+            let got = delegate.route(request).await;
+            assert_matches!(got, Ok(_));
+
+            assert_matches!(dml_handler.calls().as_slice(), [MockDmlHandlerCall::Write { write_input, .. }] => {
+                assert_eq!(write_input["platanos"].rows(), 3);
+            });
+            assert_metric_hit(&metrics, "http_write_lines", Some(3));
+            assert_metric_hit(&metrics, "http_write_body_bytes", Some(lp.len() as _));
+        }
+    }
+
     /// Assert the router delegates request parsing to the
     /// [`WriteParamExtractor`] implementation.
     ///
@@ -1452,6 +2136,21 @@ mod tests {
             "deletes are not supported",
         ),
 
+        (
+            ParseDelete(DeleteRequestError::InvalidMeasurement),
+            "failed to parse delete request: delete predicate must select at most one measurement with _measurement=<name>",
+        ),
+
+        (
+            DeleteHandler(DeleteError::NamespaceNotFound("bananas".to_string())),
+            "delete handler error: namespace bananas does not exist",
+        ),
+
+        (
+            DeleteHandler(DeleteError::Upstream("ingester-1".into(), RpcWriteError::NoUpstreams)),
+            "delete handler error: failed to apply delete to ingester ingester-1: no healthy upstream ingesters available",
+        ),
+
         (
             NonUtf8Body(std::str::from_utf8(&[0, 159]).unwrap_err()),
             "body content is not valid utf8: invalid utf-8 sequence of 1 bytes from index 1",
@@ -1530,6 +2229,14 @@ mod tests {
             "failed to parse line protocol: timestamp overflows i64",
         ),
 
+        (
+            PartialWrite(vec![
+                RejectedLine { line: 2, reason: "bad line".to_string() },
+                RejectedLine { line: 4, reason: "bad table".to_string() },
+            ]),
+            "partial write has occurred, errors encountered on line(s): line 2: bad line; line 4: bad table",
+        ),
+
         (
             DmlHandler(DmlError::NamespaceNotFound("[namespace name]".into())),
             "dml handler error: namespace [namespace name] does not exist",
@@ -0,0 +1,158 @@
+//! Parsing of HTTP request bodies that conform to the [V2 Delete API].
+//!
+//! [V2 Delete API]:
+//!     https://docs.influxdata.com/influxdb/v2.6/api/#operation/PostDelete
+
+use data_types::{DeletePredicate, Op, Scalar};
+use predicate::delete_predicate::parse_delete_predicate;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The name of the pseudo-column used in a delete predicate to select the
+/// measurement (table) the delete applies to.
+const MEASUREMENT_COLUMN_NAME: &str = "_measurement";
+
+/// Errors returned when parsing a delete request body.
+#[derive(Debug, Error)]
+pub enum DeleteRequestError {
+    /// The request body is not a valid delete request.
+    #[error("failed to deserialize delete request body: {0}")]
+    DecodeFail(#[from] serde_json::Error),
+
+    /// The time range or predicate of the delete request is invalid.
+    #[error(transparent)]
+    InvalidPredicate(#[from] predicate::delete_predicate::Error),
+
+    /// The predicate selects the measurement with something other than a
+    /// single `_measurement = <name>` expression.
+    #[error("delete predicate must select at most one measurement with _measurement=<name>")]
+    InvalidMeasurement,
+}
+
+/// The JSON body of a v2 delete request.
+#[derive(Debug, Deserialize)]
+struct DeleteRequestBody {
+    start: String,
+    stop: String,
+    #[serde(default)]
+    predicate: String,
+}
+
+/// A parsed delete request.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct DeleteRequest {
+    /// The table the delete applies to, or [`None`] for all tables.
+    pub(crate) table_name: Option<String>,
+
+    /// The predicate selecting the rows to delete, excluding the measurement
+    /// selection.
+    pub(crate) predicate: DeletePredicate,
+}
+
+impl TryFrom<&[u8]> for DeleteRequest {
+    type Error = DeleteRequestError;
+
+    fn try_from(body: &[u8]) -> Result<Self, Self::Error> {
+        let body: DeleteRequestBody = serde_json::from_slice(body)?;
+
+        let mut predicate = parse_delete_predicate(&body.start, &body.stop, &body.predicate)?;
+
+        let (measurement, exprs): (Vec<_>, Vec<_>) = predicate
+            .exprs
+            .into_iter()
+            .partition(|expr| expr.column == MEASUREMENT_COLUMN_NAME);
+        predicate.exprs = exprs;
+
+        let table_name = match measurement.as_slice() {
+            [] => None,
+            [expr] => match (&expr.op, &expr.scalar) {
+                (Op::Eq, Scalar::String(name)) => Some(name.clone()),
+                _ => return Err(DeleteRequestError::InvalidMeasurement),
+            },
+            _ => return Err(DeleteRequestError::InvalidMeasurement),
+        };
+
+        Ok(Self {
+            table_name,
+            predicate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::{DeleteExpr, TimestampRange};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_measurement_and_tags() {
+        let body = r#"{
+            "start": "1970-01-01T00:00:00Z",
+            "stop": "1970-01-01T00:00:00.000000100Z",
+            "predicate": "_measurement=\"cpu\" and host=\"a\""
+        }"#;
+
+        let got = DeleteRequest::try_from(body.as_bytes()).expect("valid delete request");
+        assert_eq!(
+            got,
+            DeleteRequest {
+                table_name: Some("cpu".to_string()),
+                predicate: DeletePredicate {
+                    range: TimestampRange::new(0, 100),
+                    exprs: vec![DeleteExpr::new(
+                        "host".to_string(),
+                        Op::Eq,
+                        Scalar::String("a".to_string())
+                    )],
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_no_predicate() {
+        let body = r#"{"start": "1", "stop": "2"}"#;
+
+        let got = DeleteRequest::try_from(body.as_bytes()).expect("valid delete request");
+        assert_eq!(
+            got,
+            DeleteRequest {
+                table_name: None,
+                predicate: DeletePredicate {
+                    range: TimestampRange::new(1, 2),
+                    exprs: vec![],
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let got = DeleteRequest::try_from("bananas".as_bytes());
+        assert_matches!(got, Err(DeleteRequestError::DecodeFail(_)));
+
+        let got = DeleteRequest::try_from(r#"{"predicate": "host='a'"}"#.as_bytes());
+        assert_matches!(got, Err(DeleteRequestError::DecodeFail(_)));
+
+        let got = DeleteRequest::try_from(r#"{"start": "2", "stop": "1"}"#.as_bytes());
+        assert_matches!(got, Err(DeleteRequestError::InvalidPredicate(_)));
+
+        let got = DeleteRequest::try_from(
+            r#"{"start": "1", "stop": "2", "predicate": "host > 'a'"}"#.as_bytes(),
+        );
+        assert_matches!(got, Err(DeleteRequestError::InvalidPredicate(_)));
+
+        let got = DeleteRequest::try_from(
+            r#"{"start": "1", "stop": "2", "predicate": "_measurement!='cpu'"}"#.as_bytes(),
+        );
+        assert_matches!(got, Err(DeleteRequestError::InvalidMeasurement));
+
+        let got = DeleteRequest::try_from(
+            r#"{"start": "1", "stop": "2", "predicate": "_measurement='cpu' and _measurement='mem'"}"#
+                .as_bytes(),
+        );
+        assert_matches!(got, Err(DeleteRequestError::InvalidMeasurement));
+    }
+}
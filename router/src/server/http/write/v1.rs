@@ -13,10 +13,7 @@ use crate::server::http::{write::Precision, Error};
 
 // When a retention policy is provided, it is appended to the db field,
 // separated by a single `/`.
-//
-//      See https://github.com/influxdata/idpe/issues/17265
-//
-pub(crate) const V1_NAMESPACE_RP_SEPARATOR: char = '/';
+pub(crate) use data_types::V1_NAMESPACE_RP_SEPARATOR;
 
 /// v1 DmlErrors returned when decoding the database / rp information from a
 /// HTTP request and deriving the namespace name from it.
@@ -1,5 +1,5 @@
 use data_types::{PartitionTemplate, QueryPoolId, TableId, TemplatePart, TopicId};
-use generated_types::influxdata::iox::ingester::v1::WriteRequest;
+use generated_types::influxdata::iox::ingester::v1::{InvalidateColumnsRequest, WriteRequest};
 use hashbrown::HashMap;
 use hyper::{Body, Request, Response};
 use iox_catalog::{
@@ -11,8 +11,8 @@ use object_store::memory::InMemory;
 use router::{
     dml_handlers::{
         client::mock::MockWriteClient, Chain, DmlHandlerChainExt, FanOutAdaptor,
-        InstrumentationDecorator, Partitioned, Partitioner, RetentionValidator, RpcWrite,
-        SchemaValidator,
+        InstrumentationDecorator, Partitioned, Partitioner, RateLimiter, RetentionValidator,
+        RpcWrite, SchemaValidator,
     },
     namespace_cache::{MemoryNamespaceCache, ReadThroughCache, ShardedCache},
     namespace_resolver::{MissingNamespaceAction, NamespaceAutocreation, NamespaceSchemaResolver},
@@ -101,7 +101,7 @@ impl TestContextBuilder {
 pub struct TestContext {
     client: Arc<MockWriteClient>,
     http_delegate: HttpDelegateStack,
-    grpc_delegate: RpcWriteGrpcDelegate,
+    grpc_delegate: RpcWriteGrpcDelegate<NamespaceCacheStack, Arc<MockWriteClient>>,
     catalog: Arc<dyn Catalog>,
     metrics: Arc<metric::Registry>,
 
@@ -109,6 +109,9 @@ pub struct TestContext {
     single_tenancy: bool,
 }
 
+/// The namespace schema cache shared by the handlers and gRPC services.
+type NamespaceCacheStack = Arc<ReadThroughCache<Arc<ShardedCache<Arc<MemoryNamespaceCache>>>>>;
+
 // This mass of words is certainly a downside of chained handlers.
 //
 // Fortunately the compiler errors are very descriptive and updating this is
@@ -118,14 +121,19 @@ type HttpDelegateStack = HttpDelegate<
         Chain<
             Chain<
                 Chain<
-                    RetentionValidator<
-                        Arc<ReadThroughCache<Arc<ShardedCache<Arc<MemoryNamespaceCache>>>>>,
+                    Chain<
+                        RateLimiter<
+                            Arc<ReadThroughCache<Arc<ShardedCache<Arc<MemoryNamespaceCache>>>>>,
+                        >,
+                        RetentionValidator<
+                            Arc<ReadThroughCache<Arc<ShardedCache<Arc<MemoryNamespaceCache>>>>>,
+                        >,
                     >,
                     SchemaValidator<
                         Arc<ReadThroughCache<Arc<ShardedCache<Arc<MemoryNamespaceCache>>>>>,
                     >,
                 >,
-                Partitioner,
+                Partitioner<Arc<ReadThroughCache<Arc<ShardedCache<Arc<MemoryNamespaceCache>>>>>>,
             >,
             FanOutAdaptor<
                 RpcWrite<Arc<MockWriteClient>>,
@@ -162,11 +170,16 @@ impl TestContext {
         let schema_validator =
             SchemaValidator::new(Arc::clone(&catalog), Arc::clone(&ns_cache), &metrics);
 
+        let rate_limiter = RateLimiter::new(Arc::clone(&ns_cache), &metrics);
+
         let retention_validator = RetentionValidator::new(Arc::clone(&ns_cache));
 
-        let partitioner = Partitioner::new(PartitionTemplate {
-            parts: vec![TemplatePart::TimeFormat("%Y-%m-%d".to_owned())],
-        });
+        let partitioner = Partitioner::new(
+            Arc::clone(&ns_cache),
+            PartitionTemplate {
+                parts: vec![TemplatePart::TimeFormat("%Y-%m-%d".to_owned())],
+            },
+        );
 
         let namespace_resolver = NamespaceSchemaResolver::new(Arc::clone(&ns_cache));
         let namespace_resolver = NamespaceAutocreation::new(
@@ -180,7 +193,8 @@ impl TestContext {
 
         let parallel_write = FanOutAdaptor::new(rpc_writer);
 
-        let handler_stack = retention_validator
+        let handler_stack = rate_limiter
+            .and_then(retention_validator)
             .and_then(schema_validator)
             .and_then(partitioner)
             .and_then(parallel_write);
@@ -205,6 +219,8 @@ impl TestContext {
         let grpc_delegate = RpcWriteGrpcDelegate::new(
             Arc::clone(&catalog),
             Arc::new(InMemory::default()),
+            Arc::clone(&ns_cache),
+            [(Arc::clone(&client), "mock client")],
             TopicId::new(TEST_TOPIC_ID),
             QueryPoolId::new(TEST_QUERY_POOL_ID),
         );
@@ -241,7 +257,9 @@ impl TestContext {
     }
 
     /// Get a reference to the test context's grpc delegate.
-    pub fn grpc_delegate(&self) -> &RpcWriteGrpcDelegate {
+    pub fn grpc_delegate(
+        &self,
+    ) -> &RpcWriteGrpcDelegate<NamespaceCacheStack, Arc<MockWriteClient>> {
         &self.grpc_delegate
     }
 
@@ -254,6 +272,10 @@ impl TestContext {
         self.client.calls()
     }
 
+    pub fn invalidate_columns_calls(&self) -> Vec<InvalidateColumnsRequest> {
+        self.client.invalidate_columns_calls()
+    }
+
     /// Get a reference to the test context's metrics.
     pub fn metrics(&self) -> &metric::Registry {
         self.metrics.as_ref()
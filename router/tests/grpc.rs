@@ -8,7 +8,9 @@ use hyper::StatusCode;
 use iox_catalog::interface::{Error as CatalogError, SoftDeletedRows};
 use iox_time::{SystemProvider, TimeProvider};
 use router::{
-    dml_handlers::{CachedServiceProtectionLimit, DmlError, RetentionError, SchemaError},
+    dml_handlers::{
+        CachedServiceProtectionLimit, DmlError, RateLimitError, RetentionError, SchemaError,
+    },
     namespace_resolver::{self, NamespaceCreationError},
     server::http::Error,
 };
@@ -79,6 +81,7 @@ async fn test_namespace_create() {
     let req = CreateNamespaceRequest {
         name: "bananas_test".to_string(),
         retention_period_ns: Some(RETENTION),
+        partition_template: None,
     };
     let got = ctx
         .grpc_delegate()
@@ -151,6 +154,7 @@ async fn test_namespace_delete() {
     let req = CreateNamespaceRequest {
         name: "bananas_test".to_string(),
         retention_period_ns: Some(RETENTION),
+        partition_template: None,
     };
     let got = ctx
         .grpc_delegate()
@@ -280,6 +284,7 @@ async fn test_create_namespace_0_retention_period() {
     let req = CreateNamespaceRequest {
         name: "bananas_test".to_string(),
         retention_period_ns: Some(0), // A zero!
+        partition_template: None,
     };
     let got = ctx
         .grpc_delegate()
@@ -344,6 +349,7 @@ async fn test_create_namespace_negative_retention_period() {
     let req = CreateNamespaceRequest {
         name: "bananas_test".to_string(),
         retention_period_ns: Some(-42),
+        partition_template: None,
     };
     let err = ctx
         .grpc_delegate()
@@ -407,6 +413,7 @@ async fn test_update_namespace_0_retention_period() {
         .create_namespace(Request::new(CreateNamespaceRequest {
             name: "bananas_test".to_string(),
             retention_period_ns: Some(42),
+            partition_template: None,
         }))
         .await
         .expect("failed to create namespace")
@@ -511,6 +518,7 @@ async fn test_update_namespace_negative_retention_period() {
         .create_namespace(Request::new(CreateNamespaceRequest {
             name: "bananas_test".to_string(),
             retention_period_ns: Some(42),
+            partition_template: None,
         }))
         .await
         .expect("failed to create namespace")
@@ -656,6 +664,53 @@ async fn test_update_namespace_limit_max_tables() {
         });
     });
 }
+/// Ensure updating a write rate limit through the gRPC NamespaceService
+/// evicts the namespace from the schema cache, so that the new limit is
+/// enforced without restarting the router.
+#[tokio::test]
+async fn test_update_namespace_rate_limit_evicts_cache() {
+    let ctx = TestContextBuilder::default()
+        .with_autocreate_namespace(None)
+        .build()
+        .await;
+
+    let lp = "platanos,tag1=A val=42i 42424242\nplatanos,tag1=B val=24i 42424243";
+
+    // Populate the cache with the unlimited namespace.
+    ctx.write_lp("bananas", "test", lp)
+        .await
+        .expect("write should succeed");
+
+    ctx.grpc_delegate()
+        .namespace_service()
+        .update_namespace_service_protection_limit(Request::new(
+            UpdateNamespaceServiceProtectionLimitRequest {
+                name: "bananas_test".to_string(),
+                limit_update: Some(
+                    update_namespace_service_protection_limit_request::LimitUpdate::MaxLinesPerSecond(1),
+                ),
+            },
+        ))
+        .await
+        .expect("failed to update namespace rate limit");
+
+    // The first write is admitted and uses up more than the budget of the
+    // current second, which causes the next write to be rejected.
+    ctx.write_lp("bananas", "test", lp)
+        .await
+        .expect("write should succeed");
+    let err = ctx
+        .write_lp("bananas", "test", lp)
+        .await
+        .expect_err("write should exceed the rate limit");
+    assert_matches!(
+        err,
+        router::server::http::Error::DmlHandler(DmlError::RateLimit(
+            RateLimitError::Exceeded { .. }
+        ))
+    );
+}
+
 #[tokio::test]
 async fn test_update_namespace_limit_max_columns_per_table() {
     // Initialise a TestContext with namespace autocreation.
@@ -780,6 +835,7 @@ async fn test_update_namespace_limit_0_max_tables_max_columns() {
         .create_namespace(Request::new(CreateNamespaceRequest {
             name: "bananas_test".to_string(),
             retention_period_ns: Some(0),
+            partition_template: None,
         }))
         .await
         .expect("failed to create namespace")
@@ -839,3 +895,257 @@ async fn test_update_namespace_limit_0_max_tables_max_columns() {
         });
     }
 }
+
+/// Ensure the partition templates set through the gRPC NamespaceService and
+/// TableService are used to partition writes.
+#[tokio::test]
+async fn test_partition_templates() {
+    use generated_types::influxdata::iox::{
+        partition_template::v1::{template_part::Part, PartitionTemplate, TemplatePart},
+        table::v1::{table_service_server::TableService, CreateTableRequest},
+    };
+
+    // Initialise a TestContext without a namespace autocreation policy.
+    let ctx = TestContextBuilder::default().build().await;
+
+    // Create a namespace that partitions by the "region" tag.
+    let namespace = ctx
+        .grpc_delegate()
+        .namespace_service()
+        .create_namespace(Request::new(CreateNamespaceRequest {
+            name: "bananas_test".to_string(),
+            retention_period_ns: None,
+            partition_template: Some(PartitionTemplate {
+                parts: vec![TemplatePart {
+                    part: Some(Part::Column("region".to_string())),
+                }],
+            }),
+        }))
+        .await
+        .expect("failed to create namespace")
+        .into_inner()
+        .namespace
+        .expect("no namespace in response");
+
+    // And a table within it that partitions by its name.
+    let table = ctx
+        .grpc_delegate()
+        .table_service()
+        .create_table(Request::new(CreateTableRequest {
+            namespace: "bananas_test".to_string(),
+            name: "platanos".to_string(),
+            partition_template: Some(PartitionTemplate {
+                parts: vec![TemplatePart {
+                    part: Some(Part::Table(Default::default())),
+                }],
+            }),
+        }))
+        .await
+        .expect("failed to create table")
+        .into_inner()
+        .table
+        .expect("no table in response");
+    assert_eq!(table.namespace_id, namespace.id);
+
+    let now = SystemProvider::default()
+        .now()
+        .timestamp_nanos()
+        .to_string();
+    let lp = format!(
+        "platanos,region=west val=42i {now}\n\
+         bananas,region=west val=42i {now}\n\
+         bananas,region=east val=42i {now}"
+    );
+    let response = ctx
+        .write_lp("bananas", "test", lp)
+        .await
+        .expect("write failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let bananas_id = ctx.table_id("bananas_test", "bananas").await.get();
+    let mut got = ctx
+        .write_calls()
+        .into_iter()
+        .map(|w| {
+            let batch = w.payload.expect("no payload in write");
+            let mut tables = batch
+                .table_batches
+                .iter()
+                .map(|t| t.table_id)
+                .collect::<Vec<_>>();
+            tables.sort();
+            (batch.partition_key, tables)
+        })
+        .collect::<Vec<_>>();
+    got.sort();
+
+    assert_eq!(
+        got,
+        [
+            ("platanos".to_string(), vec![table.id]),
+            ("region_east".to_string(), vec![bananas_id]),
+            ("region_west".to_string(), vec![bananas_id]),
+        ]
+    );
+}
+
+/// Ensure deleting a table through the gRPC TableService evicts the namespace
+/// from the schema cache, so that subsequent writes re-create the table.
+#[tokio::test]
+async fn test_delete_table_evicts_cache() {
+    use generated_types::influxdata::iox::table::v1::{
+        table_service_server::TableService, DeleteTableRequest,
+    };
+
+    let ctx = TestContextBuilder::default()
+        .with_autocreate_namespace(None)
+        .build()
+        .await;
+
+    let now = SystemProvider::default()
+        .now()
+        .timestamp_nanos()
+        .to_string();
+    let lp = format!("platanos,tag1=A val=42i {now}");
+
+    let response = ctx
+        .write_lp("bananas", "test", &lp)
+        .await
+        .expect("write failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let table_id = ctx.table_id("bananas_test", "platanos").await;
+
+    ctx.grpc_delegate()
+        .table_service()
+        .delete_table(Request::new(DeleteTableRequest {
+            namespace: "bananas_test".to_string(),
+            name: "platanos".to_string(),
+        }))
+        .await
+        .expect("failed to delete table");
+
+    // The table is hidden in the catalog.
+    {
+        let mut repos = ctx.catalog().repositories().await;
+        let namespace = repos
+            .namespaces()
+            .get_by_name("bananas_test", SoftDeletedRows::ExcludeDeleted)
+            .await
+            .expect("query failed")
+            .expect("namespace should exist");
+        assert!(repos
+            .tables()
+            .get_by_namespace_and_name(namespace.id, "platanos")
+            .await
+            .expect("query failed")
+            .is_none());
+    }
+
+    // Writing to the table again does not use the stale cached schema, and
+    // revives the table in the catalog.
+    let response = ctx
+        .write_lp("bananas", "test", &lp)
+        .await
+        .expect("write failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(ctx.table_id("bananas_test", "platanos").await, table_id);
+}
+
+#[tokio::test]
+async fn test_rename_table_evicts_cache() {
+    use generated_types::influxdata::iox::schema::v1::{
+        schema_service_server::SchemaService, RenameTableRequest,
+    };
+
+    let ctx = TestContextBuilder::default()
+        .with_autocreate_namespace(None)
+        .build()
+        .await;
+
+    let now = SystemProvider::default()
+        .now()
+        .timestamp_nanos()
+        .to_string();
+    let lp = format!("platanos,tag1=A val=42i {now}");
+
+    let response = ctx
+        .write_lp("bananas", "test", &lp)
+        .await
+        .expect("write failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let table_id = ctx.table_id("bananas_test", "platanos").await;
+
+    ctx.grpc_delegate()
+        .schema_service()
+        .rename_table(Request::new(RenameTableRequest {
+            namespace: "bananas_test".to_string(),
+            table: "platanos".to_string(),
+            new_name: "plantains".to_string(),
+        }))
+        .await
+        .expect("failed to rename table");
+
+    // The existing table is now known by its new name.
+    assert_eq!(ctx.table_id("bananas_test", "plantains").await, table_id);
+
+    // Writing using the previous name does not use the stale cached schema,
+    // and creates a new table.
+    let response = ctx
+        .write_lp("bananas", "test", &lp)
+        .await
+        .expect("write failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_ne!(ctx.table_id("bananas_test", "platanos").await, table_id);
+}
+
+/// Ensure renaming a column tells the ingesters to discard the column IDs they
+/// cached for the table, as a new column may be created with the previous
+/// name.
+#[tokio::test]
+async fn test_rename_column_invalidates_ingesters() {
+    use generated_types::influxdata::iox::{
+        ingester::v1::InvalidateColumnsRequest,
+        schema::v1::{schema_service_server::SchemaService, RenameColumnRequest},
+    };
+
+    let ctx = TestContextBuilder::default()
+        .with_autocreate_namespace(None)
+        .build()
+        .await;
+
+    let now = SystemProvider::default()
+        .now()
+        .timestamp_nanos()
+        .to_string();
+    let lp = format!("platanos,tag1=A val=42i {now}");
+
+    let response = ctx
+        .write_lp("bananas", "test", &lp)
+        .await
+        .expect("write failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let table_id = ctx.table_id("bananas_test", "platanos").await;
+    assert!(ctx.invalidate_columns_calls().is_empty());
+
+    let resp = ctx
+        .grpc_delegate()
+        .schema_service()
+        .rename_column(Request::new(RenameColumnRequest {
+            namespace: "bananas_test".to_string(),
+            table: "platanos".to_string(),
+            column: "tag1".to_string(),
+            new_name: "tag2".to_string(),
+        }))
+        .await
+        .expect("failed to rename column")
+        .into_inner();
+    assert_eq!(resp.table_id, table_id.get());
+
+    assert_eq!(
+        ctx.invalidate_columns_calls(),
+        [InvalidateColumnsRequest {
+            namespace_id: resp.namespace_id,
+            table_id: table_id.get(),
+        }]
+    );
+}
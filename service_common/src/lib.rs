@@ -1,14 +1,18 @@
 //! Common methods for RPC service implementations
 
 mod error;
+mod namespace_filter;
 pub mod planner;
 pub mod test_util;
 
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use iox_query::{exec::ExecutionContextProvider, QueryNamespace};
-use trace::span::Span;
+use iox_query::{
+    exec::{ExecutionContextProvider, IOxSessionContext},
+    QueryNamespace, QueryNamespaceFilter,
+};
+use trace::{ctx::SpanContext, span::Span};
 use tracker::InstrumentedAsyncOwnedSemaphorePermit;
 
 /// Trait that allows the query engine (which includes flight and storage/InfluxRPC) to access a
@@ -23,8 +27,20 @@ pub trait QueryNamespaceProvider: std::fmt::Debug + Send + Sync + 'static {
     /// Get namespace if it exists.
     async fn db(&self, name: &str, span: Option<Span>) -> Option<Arc<Self::Db>>;
 
+    /// Returns a new execution context for queries that are not run against a
+    /// namespace, such as the InfluxQL `SHOW DATABASES`.
+    ///
+    /// The context has no tables other than the system table listing the
+    /// namespaces accepted by `filter`.
+    fn new_query_context_without_namespace(
+        &self,
+        span_ctx: Option<SpanContext>,
+        filter: Arc<dyn QueryNamespaceFilter>,
+    ) -> IOxSessionContext;
+
     /// Acquire concurrency-limiting sempahore
     async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit;
 }
 
 pub use error::datafusion_error_to_tonic_code;
+pub use namespace_filter::AuthzNamespaceFilter;
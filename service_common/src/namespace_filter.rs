@@ -0,0 +1,52 @@
+//! Restrict the namespaces a query may discover to those a client is
+//! authorized to access.
+use std::{collections::HashSet, sync::Arc};
+
+use async_trait::async_trait;
+use authz::{Action, Authorizer, Permission, Resource};
+use datafusion::error::DataFusionError;
+use iox_query::QueryNamespaceFilter;
+
+/// A [`QueryNamespaceFilter`] that accepts the namespaces the request token
+/// grants read or read-schema permission for.
+///
+/// All namespaces are accepted if no authorizer is configured.
+#[derive(Debug)]
+pub struct AuthzNamespaceFilter {
+    authz: Option<Arc<dyn Authorizer>>,
+    token: Option<Vec<u8>>,
+}
+
+impl AuthzNamespaceFilter {
+    /// Create a filter for the request carrying `token`.
+    pub fn new(authz: Option<Arc<dyn Authorizer>>, token: Option<Vec<u8>>) -> Self {
+        Self { authz, token }
+    }
+}
+
+#[async_trait]
+impl QueryNamespaceFilter for AuthzNamespaceFilter {
+    async fn filter(&self, namespaces: Vec<String>) -> Result<Vec<String>, DataFusionError> {
+        let perms = namespaces
+            .iter()
+            .flat_map(|name| {
+                [Action::Read, Action::ReadSchema].map(|action| {
+                    Permission::ResourceAction(Resource::Database(name.clone()), action)
+                })
+            })
+            .collect::<Vec<_>>();
+        let granted = self
+            .authz
+            .permissions(self.token.as_deref(), &perms)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?
+            .into_iter()
+            .map(|Permission::ResourceAction(Resource::Database(name), _)| name)
+            .collect::<HashSet<_>>();
+
+        Ok(namespaces
+            .into_iter()
+            .filter(|name| granted.contains(name))
+            .collect())
+    }
+}
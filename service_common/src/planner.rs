@@ -2,8 +2,8 @@
 use std::sync::Arc;
 
 use bytes::Bytes;
-use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
-use flightsql::{FlightSQLCommand, FlightSQLPlanner};
+use datafusion::{error::DataFusionError, logical_expr::LogicalPlan, physical_plan::ExecutionPlan};
+use flightsql::{FlightSQLCommand, FlightSQLPlanner, QueryLanguage};
 use iox_query::{
     exec::IOxSessionContext,
     frontend::sql::SqlQueryPlanner,
@@ -16,6 +16,20 @@ pub use datafusion::error::{DataFusionError as Error, Result};
 use iox_query_influxql::frontend::planner::InfluxQLQueryPlanner;
 use predicate::rpc_predicate::InfluxRpcPredicate;
 
+/// Returns `true` if the InfluxQL `query` deletes data, as described on
+/// [`InfluxQLQueryPlanner::query_deletes_data`].
+///
+/// Such queries require write permission to the namespace.
+pub fn influxql_deletes_data(query: &str) -> bool {
+    InfluxQLQueryPlanner::query_deletes_data(query)
+}
+
+/// Returns `true` if the InfluxQL `query` must be run against a namespace, as
+/// described on [`InfluxQLQueryPlanner::query_requires_database`].
+pub fn influxql_requires_namespace(query: &str) -> bool {
+    InfluxQLQueryPlanner::query_requires_database(query)
+}
+
 /// Query planner that plans queries on a separate threadpool.
 ///
 /// Query planning was, at time of writing, a single threaded
@@ -62,6 +76,22 @@ impl Planner {
             .await
     }
 
+    /// Plan each statement of a, possibly multi-statement, InfluxQL query
+    /// against the data in `database`, as described on
+    /// [`InfluxQLQueryPlanner::query_statements`].
+    pub async fn influxql_statements(
+        &self,
+        query: impl Into<String> + Send,
+    ) -> Result<Vec<Result<Arc<dyn ExecutionPlan>>>> {
+        let planner = InfluxQLQueryPlanner::new();
+        let query = query.into();
+        let ctx = self.ctx.child_ctx("planner influxql");
+
+        self.ctx
+            .run(async move { planner.query_statements(&query, &ctx).await })
+            .await
+    }
+
     /// Creates a plan for a `DoGet` FlightSQL message,
     /// as described on [`FlightSQLPlanner::do_get`], on a
     /// separate threadpool
@@ -94,6 +124,7 @@ impl Planner {
         namespace_name: impl Into<String>,
         namespace: Arc<N>,
         cmd: FlightSQLCommand,
+        language: QueryLanguage,
     ) -> Result<Bytes>
     where
         N: QueryNamespace + 'static,
@@ -103,7 +134,7 @@ impl Planner {
 
         self.ctx
             .run(async move {
-                FlightSQLPlanner::do_action(namespace_name, namespace, cmd, &ctx)
+                FlightSQLPlanner::do_action(namespace_name, namespace, cmd, language, &ctx)
                     .await
                     .map_err(DataFusionError::from)
             })
@@ -117,13 +148,14 @@ impl Planner {
         &self,
         namespace_name: impl Into<String>,
         cmd: FlightSQLCommand,
+        language: QueryLanguage,
     ) -> Result<Bytes> {
         let namespace_name = namespace_name.into();
         let ctx = self.ctx.child_ctx("planner flight_sql_get_flight_info");
 
         self.ctx
             .run(async move {
-                FlightSQLPlanner::get_flight_info(namespace_name, cmd, &ctx)
+                FlightSQLPlanner::get_flight_info(namespace_name, cmd, language, &ctx)
                     .await
                     .map_err(DataFusionError::from)
             })
@@ -242,6 +274,28 @@ impl Planner {
             .await
     }
 
+    /// Creates plans as described on
+    /// [`InfluxRpcPlanner::read_series_cardinality`], on a separate threadpool
+    pub async fn read_series_cardinality<N>(
+        &self,
+        namespace: Arc<N>,
+        predicate: InfluxRpcPredicate,
+    ) -> Result<Vec<LogicalPlan>>
+    where
+        N: QueryNamespace + 'static,
+    {
+        let planner = InfluxRpcPlanner::new(self.ctx.child_ctx("planner read_series_cardinality"));
+
+        self.ctx
+            .run(async move {
+                planner
+                    .read_series_cardinality(namespace, predicate)
+                    .await
+                    .map_err(|e| e.to_df_error("read_series_cardinality"))
+            })
+            .await
+    }
+
     /// Creates a plan as described on
     /// [`InfluxRpcPlanner::read_group`], on a separate threadpool
     pub async fn read_group<N>(
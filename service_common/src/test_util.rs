@@ -1,9 +1,13 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use async_trait::async_trait;
-use iox_query::{exec::Executor, test::TestDatabase};
+use iox_query::{
+    exec::{Executor, ExecutorType, IOxSessionContext},
+    test::TestDatabase,
+    QueryNamespaceFilter,
+};
 use parking_lot::Mutex;
-use trace::span::Span;
+use trace::{ctx::SpanContext, span::Span};
 use tracker::{
     AsyncSemaphoreMetrics, InstrumentedAsyncOwnedSemaphorePermit, InstrumentedAsyncSemaphore,
 };
@@ -67,6 +71,18 @@ impl QueryNamespaceProvider for TestDatabaseStore {
         databases.get(name).cloned()
     }
 
+    fn new_query_context_without_namespace(
+        &self,
+        span_ctx: Option<SpanContext>,
+        filter: Arc<dyn QueryNamespaceFilter>,
+    ) -> IOxSessionContext {
+        self.executor
+            .new_execution_config(ExecutorType::Query)
+            .with_namespace_filter(filter)
+            .with_span_context(span_ctx)
+            .build()
+    }
+
     async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit {
         Arc::clone(&self.query_semaphore)
             .acquire_owned(span)
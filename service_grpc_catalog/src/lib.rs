@@ -257,6 +257,7 @@ mod tests {
                 created_at: Timestamp::new(2343),
                 column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
                 max_l0_created_at: Timestamp::new(2343),
+                max_tombstone_sequence_number: SequenceNumber::new(0),
             };
             let p2params = ParquetFileParams {
                 object_store_id: Uuid::new_v4(),
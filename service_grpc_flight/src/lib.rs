@@ -4,13 +4,19 @@
 mod request;
 
 use arrow::{
-    datatypes::SchemaRef, error::ArrowError, ipc::writer::IpcWriteOptions,
+    array::{ArrayRef, StringArray},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    error::ArrowError,
+    ipc::writer::IpcWriteOptions,
     record_batch::RecordBatch,
 };
 use arrow_flight::{
+    decode::FlightRecordBatchStream,
     encode::{FlightDataEncoder, FlightDataEncoderBuilder},
+    error::FlightError,
     flight_descriptor::DescriptorType,
     flight_service_server::{FlightService as Flight, FlightServiceServer as FlightServer},
+    sql::CommandStatementQuery,
     Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
     HandshakeRequest, HandshakeResponse, PutResult, SchemaAsIpc, SchemaResult, Ticket,
 };
@@ -19,8 +25,8 @@ use authz::Authorizer;
 use bytes::Bytes;
 use data_types::NamespaceNameError;
 use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
-use flightsql::FlightSQLCommand;
-use futures::{ready, Stream, StreamExt, TryStreamExt};
+use flightsql::{FlightSQLCommand, QueryLanguage};
+use futures::{ready, stream::BoxStream, Stream, StreamExt, TryStreamExt};
 use generated_types::influxdata::iox::querier::v1 as proto;
 use iox_query::{
     exec::{ExecutionContextProvider, IOxSessionContext},
@@ -29,9 +35,21 @@ use iox_query::{
 use observability_deps::tracing::{debug, info, warn};
 use prost::Message;
 use request::{IoxGetRequest, RunQuery};
-use service_common::{datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider};
+use schema::INFLUXQL_METADATA_KEY;
+use service_common::{
+    datafusion_error_to_tonic_code,
+    planner::{influxql_deletes_data, Planner},
+    AuthzNamespaceFilter, QueryNamespaceProvider,
+};
 use snafu::{OptionExt, ResultExt, Snafu};
-use std::{fmt::Debug, pin::Pin, sync::Arc, task::Poll, time::Instant};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::Poll,
+    time::Instant,
+};
 use tonic::{metadata::MetadataMap, Request, Response, Streaming};
 use trace::{ctx::SpanContext, span::SpanExt};
 use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
@@ -49,6 +67,14 @@ const IOX_FLIGHT_SQL_DATABASE_HEADERS: [&str; 4] = [
     "iox-namespace-name", // deprecated
 ];
 
+/// The name of the grpc header that selects the query language of
+/// FlightSQL statements, either "sql" (the default) or "influxql".
+const IOX_FLIGHT_SQL_QUERY_LANGUAGE_HEADER: &str = "query-language";
+
+/// The maximum total size of the parameter values bound to a
+/// prepared statement by a single `DoPut` request.
+const MAX_DO_PUT_PARAMETER_BYTES: usize = 1024 * 1024;
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -61,6 +87,9 @@ pub enum Error {
     #[snafu(display("Invalid handshake. No payload provided"))]
     InvalidHandshake {},
 
+    #[snafu(display("Invalid DoPut request: {}", description))]
+    InvalidDoPut { description: String },
+
     #[snafu(display("Namespace '{}' not found", namespace_name))]
     NamespaceNotFound { namespace_name: String },
 
@@ -89,6 +118,11 @@ pub enum Error {
         source: tonic::metadata::errors::ToStrError,
     },
 
+    #[snafu(display("Invalid 'query-language' header in request: {}", source))]
+    InvalidQueryLanguageHeader {
+        source: tonic::metadata::errors::ToStrError,
+    },
+
     #[snafu(display("Invalid namespace name: {}", source))]
     InvalidNamespaceName { source: NamespaceNameError },
 
@@ -131,6 +165,7 @@ impl From<Error> for tonic::Status {
             Error::NamespaceNotFound { .. }
             | Error::InvalidTicket { .. }
             | Error::InvalidHandshake { .. }
+            | Error::InvalidDoPut { .. }
             | Error::Unauthenticated { .. }
             | Error::PermissionDenied { .. }
             // TODO(edd): this should be `debug`. Keeping at info while IOx in early development
@@ -140,6 +175,7 @@ impl From<Error> for tonic::Status {
             | Error::TooManyFlightSQLDatabases { .. }
             | Error::NoFlightSQLDatabase
             | Error::InvalidDatabaseHeader { .. }
+            | Error::InvalidQueryLanguageHeader { .. }
             | Error::Planning { .. }
             | Error::Deserialization { .. }
             | Error::InternalCreatingTicket { .. }
@@ -164,10 +200,12 @@ impl Error {
             Self::NamespaceNotFound { .. } => tonic::Code::NotFound,
             Self::InvalidTicket { .. }
             | Self::InvalidHandshake { .. }
+            | Self::InvalidDoPut { .. }
             | Self::Deserialization { .. }
             | Self::TooManyFlightSQLDatabases { .. }
             | Self::NoFlightSQLDatabase
             | Self::InvalidDatabaseHeader { .. }
+            | Self::InvalidQueryLanguageHeader { .. }
             | Self::InvalidNamespaceName { .. } => tonic::Code::InvalidArgument,
             Self::Planning { source, .. } | Self::Query { source, .. } => {
                 datafusion_error_to_tonic_code(&source)
@@ -175,6 +213,8 @@ impl Error {
             Self::UnsupportedMessageType { .. } => tonic::Code::Unimplemented,
             Self::FlightSQL { source } => match source {
                 flightsql::Error::InvalidHandle { .. }
+                | flightsql::Error::InvalidParameters { .. }
+                | flightsql::Error::InvalidQueryLanguage { .. }
                 | flightsql::Error::Decode { .. }
                 | flightsql::Error::Protocol { .. }
                 | flightsql::Error::UnsupportedMessageType { .. } => tonic::Code::InvalidArgument,
@@ -201,6 +241,12 @@ impl Error {
             description: description.into(),
         }
     }
+
+    fn invalid_do_put(description: impl Into<String>) -> Self {
+        Self::InvalidDoPut {
+            description: description.into(),
+        }
+    }
 }
 
 impl From<flightsql::Error> for Error {
@@ -314,7 +360,7 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///       ┃                                                  ┃
 /// ```
 ///
-/// ## FlightSQL Prepared Statement (no bind parameters like $1, etc)
+/// ## FlightSQL Prepared Statement
 ///
 /// To run a prepared query, via FlightSQL, the client undertakes a
 /// few more steps:
@@ -333,6 +379,21 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///
 /// 5. Steps 5,6,7 proceed the same as for a FlightSQL ad-hoc query
 ///
+/// ### Bind parameters
+///
+/// The query may contain placeholders, either numbered (`$1`, `$2`,
+/// ...) or positional (`?`). The `ActionCreatePreparedStatementResponse`
+/// contains the parameter schema, with one field per placeholder and
+/// the type inferred from the query (or `Null` if unknown).
+///
+/// Before step 4, the client binds values to the placeholders by
+/// calling `DoPut` with a [`FlightDescriptor`] containing the
+/// `CommandPreparedStatementQuery` and a single row of parameter
+/// values (at most [`MAX_DO_PUT_PARAMETER_BYTES`]). IOx does not
+/// store the values: the `PutResult` contains a
+/// `DoPutPreparedStatementResult` with a new handle that includes
+/// them, which the client uses in place of the original handle.
+///
 /// ```text
 ///                                                      .───────.
 /// ╔═══════════╗                                       (         )
@@ -364,6 +425,19 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///     7 ┃◀ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ┃
 /// ```
 ///
+/// ## FlightSQL query language
+///
+/// FlightSQL statements are SQL by default. To run InfluxQL instead,
+/// set the `query-language` header to `influxql` on the
+/// `GetFlightInfo` (ad-hoc query) or `DoAction` (prepared statement)
+/// request.
+///
+/// The `Ticket` returned for an ad-hoc InfluxQL query is the same as
+/// a native IOx API InfluxQL query, while prepared statement handles
+/// record the language they were created with. InfluxQL prepared
+/// statements do not support bind parameters, and, like native
+/// InfluxQL queries, require write permission if they delete data.
+///
 /// [Arrow Flight]: https://arrow.apache.org/docs/format/Flight.html
 /// [Arrow FlightSQL]: https://arrow.apache.org/docs/format/FlightSql.html
 #[derive(Debug)]
@@ -390,9 +464,13 @@ where
     S: QueryNamespaceProvider,
 {
     /// Implementation of the `DoGet` method
+    ///
+    /// Queries that list namespaces, such as the InfluxQL `SHOW DATABASES`,
+    /// only list those `authz_token` grants access to.
     async fn run_do_get(
         &self,
         span_ctx: Option<SpanContext>,
+        authz_token: Option<Vec<u8>>,
         permit: InstrumentedAsyncOwnedSemaphorePermit,
         query: &RunQuery,
         namespace: String,
@@ -405,23 +483,33 @@ where
                 namespace_name: &namespace,
             })?;
 
-        let ctx = db.new_query_context(span_ctx);
-        let (query_completed_token, physical_plan) = match query {
+        let filter = AuthzNamespaceFilter::new(self.authz.clone(), authz_token);
+        let ctx = db.new_query_context_with_filter(span_ctx, Arc::new(filter));
+        let output = match query {
             RunQuery::Sql(sql_query) => {
                 let token = db.record_query(&ctx, "sql", Box::new(sql_query.clone()));
                 let plan = Planner::new(&ctx)
                     .sql(sql_query)
                     .await
                     .context(PlanningSnafu)?;
-                (token, plan)
+                GetStream::new(ctx, plan, namespace, token, permit).await?
             }
             RunQuery::InfluxQL(sql_query) => {
                 let token = db.record_query(&ctx, "influxql", Box::new(sql_query.clone()));
-                let plan = Planner::new(&ctx)
-                    .influxql(sql_query)
+                let plans = Planner::new(&ctx)
+                    .influxql_statements(sql_query)
                     .await
                     .context(PlanningSnafu)?;
-                (token, plan)
+                // A query with a single statement fails the request if the
+                // statement fails, so that the error is reported with the
+                // appropriate status code.
+                match <[_; 1]>::try_from(plans) {
+                    Ok([plan]) => {
+                        let plan = plan.context(PlanningSnafu)?;
+                        GetStream::new(ctx, plan, namespace, token, permit).await?
+                    }
+                    Err(plans) => GetStream::new_influxql(ctx, plans, token, permit),
+                }
             }
             RunQuery::FlightSQL(msg) => {
                 let token = db.record_query(&ctx, "flightsql", Box::new(msg.to_string()));
@@ -429,13 +517,10 @@ where
                     .flight_sql_do_get(&namespace, db, msg.clone())
                     .await
                     .context(PlanningSnafu)?;
-                (token, plan)
+                GetStream::new(ctx, plan, namespace, token, permit).await?
             }
         };
 
-        let output =
-            GetStream::new(ctx, physical_plan, namespace, query_completed_token, permit).await?;
-
         Ok(Response::new(Box::pin(output) as TonicStream<FlightData>))
     }
 }
@@ -485,10 +570,11 @@ where
 
         let perms = match query {
             RunQuery::FlightSQL(cmd) => flightsql_permissions(namespace_name, cmd),
-            RunQuery::Sql(_) | RunQuery::InfluxQL(_) => vec![authz::Permission::ResourceAction(
+            RunQuery::Sql(_) => vec![authz::Permission::ResourceAction(
                 authz::Resource::Database(namespace_name.to_string()),
                 authz::Action::Read,
             )],
+            RunQuery::InfluxQL(query) => influxql_permissions(namespace_name, query),
         };
         self.authz
             .require_any_permission(authz_token.as_deref(), &perms)
@@ -511,7 +597,13 @@ where
         );
 
         let response = self
-            .run_do_get(span_ctx, permit, query, namespace_name.to_string())
+            .run_do_get(
+                span_ctx,
+                authz_token,
+                permit,
+                query,
+                namespace_name.to_string(),
+            )
             .await;
 
         if let Err(e) = &response {
@@ -564,12 +656,13 @@ where
         let trace = external_span_ctx.format_jaeger();
 
         let namespace_name = get_flightsql_namespace(request.metadata())?;
+        let language = get_flightsql_query_language(request.metadata())?;
         let authz_token = get_flight_authz(request.metadata());
         let flight_descriptor = request.into_inner();
 
         // extract the FlightSQL message
         let cmd = cmd_from_descriptor(flight_descriptor.clone())?;
-        info!(%namespace_name, %cmd, %trace, "GetFlightInfo request");
+        info!(%namespace_name, %cmd, %language, %trace, "GetFlightInfo request");
 
         let perms = flightsql_permissions(&namespace_name, &cmd);
         self.authz
@@ -587,7 +680,7 @@ where
 
         let ctx = db.new_query_context(span_ctx);
         let schema = Planner::new(&ctx)
-            .flight_sql_get_flight_info(&namespace_name, cmd.clone())
+            .flight_sql_get_flight_info(&namespace_name, cmd.clone(), language)
             .await
             .context(PlanningSnafu);
 
@@ -598,8 +691,18 @@ where
         };
         let schema = schema?;
 
-        // Form the response ticket (that the client will pass back to DoGet)
-        let ticket = IoxGetRequest::new(&namespace_name, RunQuery::FlightSQL(cmd))
+        // Form the response ticket (that the client will pass back to
+        // DoGet). InfluxQL statements are run the same as native IOx
+        // API InfluxQL queries.
+        let query = match cmd {
+            FlightSQLCommand::CommandStatementQuery(CommandStatementQuery { query })
+                if language == QueryLanguage::InfluxQL =>
+            {
+                RunQuery::InfluxQL(query)
+            }
+            cmd => RunQuery::FlightSQL(cmd),
+        };
+        let ticket = IoxGetRequest::new(&namespace_name, query)
             .try_encode()
             .context(InternalCreatingTicketSnafu)?;
 
@@ -631,13 +734,87 @@ where
         Ok(tonic::Response::new(flight_info))
     }
 
+    /// Handles `DoPut` RPC requests, which bind parameter values to a
+    /// FlightSQL prepared statement. The [`FlightDescriptor`] of the
+    /// first message contains the `CommandPreparedStatementQuery`.
+    ///
+    /// see [`FlightService`] for more details.
     async fn do_put(
         &self,
-        _request: Request<Streaming<FlightData>>,
+        request: Request<Streaming<FlightData>>,
     ) -> Result<Response<Self::DoPutStream>, tonic::Status> {
-        info!("Handling flightsql do_put body");
+        let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
+        let trace = external_span_ctx.format_jaeger();
+
+        let namespace_name = get_flightsql_namespace(request.metadata())?;
+        let authz_token = get_flight_authz(request.metadata());
+        let mut stream = request.into_inner();
+
+        // The FlightDescriptor is sent in the first message
+        let first = stream
+            .message()
+            .await?
+            .ok_or_else(|| Error::invalid_do_put("no messages"))?;
+        let flight_descriptor = first
+            .flight_descriptor
+            .clone()
+            .ok_or_else(|| Error::invalid_do_put("no FlightDescriptor in first message"))?;
+
+        // extract the FlightSQL message
+        let cmd = cmd_from_descriptor(flight_descriptor)?;
+        info!(%namespace_name, %cmd, %trace, "DoPut request");
+
+        let perms = flightsql_permissions(&namespace_name, &cmd);
+        self.authz
+            .require_any_permission(authz_token.as_deref(), &perms)
+            .await
+            .map_err(Error::from)?;
+
+        let handle = match cmd {
+            FlightSQLCommand::CommandPreparedStatementQuery(handle) => handle,
+            cmd => return Err(Error::unsupported_message_type(format!("DoPut with {cmd}")).into()),
+        };
+
+        // decode the parameter values, rejecting oversized requests
+        // before buffering them
+        let mut total_bytes = 0;
+        let data = futures::stream::once(async { Ok(first) })
+            .chain(stream)
+            .map_err(FlightError::Tonic)
+            .and_then(move |data: FlightData| {
+                total_bytes += data.data_header.len() + data.data_body.len();
+                let result = if total_bytes > MAX_DO_PUT_PARAMETER_BYTES {
+                    let err = Error::invalid_do_put(format!(
+                        "parameter values exceed the maximum of {MAX_DO_PUT_PARAMETER_BYTES} bytes"
+                    ));
+                    Err(FlightError::Tonic(err.into()))
+                } else {
+                    Ok(data)
+                };
+                futures::future::ready(result)
+            });
+        let batches: Vec<RecordBatch> = FlightRecordBatchStream::new_from_flight_data(data)
+            .try_collect()
+            .await?;
+        let schema = batches
+            .first()
+            .map(|batch| batch.schema())
+            .ok_or_else(|| Error::invalid_do_put("no parameter values"))?;
+        let parameters = arrow::compute::concat_batches(&schema, &batches)
+            .map_err(flightsql::Error::from)
+            .map_err(Error::from)?;
 
-        Err(tonic::Status::unimplemented("Not yet implemented: do_put"))
+        // validate the parameters, then return them to the client
+        // in the handle, so any querier can run the statement
+        let handle = handle.with_parameters(parameters).map_err(Error::from)?;
+        debug!(%namespace_name, %handle, %trace, "Completed DoPut request");
+
+        let result = PutResult {
+            app_metadata: handle.try_encode_put_result().map_err(Error::from)?,
+        };
+        let stream = futures::stream::iter([Ok(result)]);
+
+        Ok(Response::new(stream.boxed()))
     }
 
     async fn do_action(
@@ -649,6 +826,7 @@ where
         let trace = external_span_ctx.format_jaeger();
 
         let namespace_name = get_flightsql_namespace(request.metadata())?;
+        let language = get_flightsql_query_language(request.metadata())?;
         let authz_token = get_flight_authz(request.metadata());
         let Action {
             r#type: action_type,
@@ -658,7 +836,7 @@ where
         // extract the FlightSQL message
         let cmd = FlightSQLCommand::try_decode(body).context(FlightSQLSnafu)?;
 
-        info!(%namespace_name, %action_type, %cmd, %trace, "DoAction request");
+        info!(%namespace_name, %action_type, %cmd, %language, %trace, "DoAction request");
 
         let perms = flightsql_permissions(&namespace_name, &cmd);
         self.authz
@@ -676,7 +854,7 @@ where
 
         let ctx = db.new_query_context(span_ctx);
         let body = Planner::new(&ctx)
-            .flight_sql_do_action(&namespace_name, db, cmd.clone())
+            .flight_sql_do_action(&namespace_name, db, cmd.clone(), language)
             .await
             .context(PlanningSnafu);
 
@@ -764,6 +942,18 @@ fn get_flightsql_namespace(metadata: &MetadataMap) -> Result<String> {
     Ok(database_name.context(NoFlightSQLDatabaseSnafu)?.to_string())
 }
 
+/// Retrieve the query language of FlightSQL statements from the
+/// `query-language` header, defaulting to SQL if not specified.
+fn get_flightsql_query_language(metadata: &MetadataMap) -> Result<QueryLanguage> {
+    match metadata.get(IOX_FLIGHT_SQL_QUERY_LANGUAGE_HEADER) {
+        Some(v) => {
+            let v = v.to_str().context(InvalidQueryLanguageHeaderSnafu)?;
+            v.parse().context(FlightSQLSnafu)
+        }
+        None => Ok(QueryLanguage::default()),
+    }
+}
+
 /// Retrieve the authorization token associated with the request.
 fn get_flight_authz(metadata: &MetadataMap) -> Option<Vec<u8>> {
     let val = metadata.get("authorization")?.as_ref();
@@ -776,11 +966,28 @@ fn get_flight_authz(metadata: &MetadataMap) -> Option<Vec<u8>> {
     }
 }
 
+/// InfluxQL queries that delete data, such as `DELETE` and `DROP MEASUREMENT`,
+/// require write permission, all other queries require read permission.
+fn influxql_permissions(namespace_name: &str, query: &str) -> Vec<authz::Permission> {
+    let resource = authz::Resource::Database(namespace_name.to_string());
+    let action = if influxql_deletes_data(query) {
+        authz::Action::Write
+    } else {
+        authz::Action::Read
+    };
+    vec![authz::Permission::ResourceAction(resource, action)]
+}
+
+/// InfluxQL prepared statements that delete data require write
+/// permission, as described on [`influxql_permissions`].
 fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<authz::Permission> {
     let resource = authz::Resource::Database(namespace_name.to_string());
     let action = match cmd {
         FlightSQLCommand::CommandStatementQuery(_) => authz::Action::Read,
-        FlightSQLCommand::CommandPreparedStatementQuery(_) => authz::Action::Read,
+        FlightSQLCommand::CommandPreparedStatementQuery(handle) => match handle.language() {
+            QueryLanguage::InfluxQL => return influxql_permissions(namespace_name, handle.query()),
+            QueryLanguage::Sql => authz::Action::Read,
+        },
         FlightSQLCommand::CommandGetSqlInfo(_) => authz::Action::ReadSchema,
         FlightSQLCommand::CommandGetCatalogs(_) => authz::Action::ReadSchema,
         FlightSQLCommand::CommandGetCrossReference(_) => authz::Action::ReadSchema,
@@ -790,6 +997,7 @@ fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<au
         FlightSQLCommand::CommandGetPrimaryKeys(_) => authz::Action::ReadSchema,
         FlightSQLCommand::CommandGetTables(_) => authz::Action::ReadSchema,
         FlightSQLCommand::CommandGetTableTypes(_) => authz::Action::ReadSchema,
+        FlightSQLCommand::CommandGetXdbcTypeInfo(_) => authz::Action::ReadSchema,
         FlightSQLCommand::ActionCreatePreparedStatementRequest(_) => authz::Action::Read,
         FlightSQLCommand::ActionClosePreparedStatementRequest(_) => authz::Action::Read,
     };
@@ -799,7 +1007,7 @@ fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<au
 /// Wrapper over a FlightDataEncodeStream that adds IOx specfic
 /// metadata and records completion
 struct GetStream {
-    inner: IOxFlightDataEncoder,
+    inner: BoxStream<'static, arrow_flight::error::Result<FlightData>>,
     #[allow(dead_code)]
     permit: InstrumentedAsyncOwnedSemaphorePermit,
     query_completed_token: QueryCompletedToken,
@@ -832,7 +1040,8 @@ impl GetStream {
         // setup inner stream
         let inner = IOxFlightDataEncoderBuilder::new(schema)
             .with_metadata(app_metadata.encode_to_vec().into())
-            .build(query_results);
+            .build(query_results)
+            .boxed();
 
         Ok(Self {
             inner,
@@ -841,6 +1050,130 @@ impl GetStream {
             done: false,
         })
     }
+
+    /// Create a stream that executes each statement of an InfluxQL query in
+    /// turn, where `plans` holds the plan of each statement, indexed by
+    /// `statement_id`.
+    ///
+    /// The results of each statement are encoded as a separate sequence of
+    /// [`FlightData`], starting with the schema of the statement. A statement
+    /// that fails to plan or execute is reported by an error batch, as
+    /// described by [`influxql_error_batch`], and does not fail the statements
+    /// that follow it.
+    fn new_influxql(
+        ctx: IOxSessionContext,
+        plans: Vec<Result<Arc<dyn ExecutionPlan>, DataFusionError>>,
+        query_completed_token: QueryCompletedToken,
+        permit: InstrumentedAsyncOwnedSemaphorePermit,
+    ) -> Self {
+        let inner = futures::stream::iter(plans.into_iter().enumerate())
+            .then(move |(statement_id, plan)| {
+                influxql_statement_stream(ctx.child_ctx("influxql statement"), statement_id, plan)
+            })
+            .flatten()
+            .boxed();
+
+        Self {
+            inner,
+            permit,
+            query_completed_token,
+            done: false,
+        }
+    }
+}
+
+/// Execute the `plan` of the InfluxQL statement identified by `statement_id`,
+/// returning the encoded results.
+///
+/// Should the statement fail, the results that have already been produced are
+/// followed by an error batch for the statement.
+async fn influxql_statement_stream(
+    ctx: IOxSessionContext,
+    statement_id: usize,
+    plan: Result<Arc<dyn ExecutionPlan>, DataFusionError>,
+) -> BoxStream<'static, arrow_flight::error::Result<FlightData>> {
+    let plan = match plan {
+        Ok(plan) => plan,
+        Err(e) => return influxql_error_stream(statement_id, &e),
+    };
+
+    let query_results = match ctx.execute_stream(Arc::clone(&plan)).await {
+        Ok(query_results) => query_results,
+        Err(e) => return influxql_error_stream(statement_id, &e),
+    };
+
+    // End the results of the statement at the first error, which is saved so
+    // that it can be reported once the preceding results have been sent.
+    let error = Arc::new(Mutex::new(None));
+    let query_results = {
+        let error = Arc::clone(&error);
+        query_results.scan((), move |_, res| {
+            futures::future::ready(match res {
+                Ok(batch) => Some(Ok(batch)),
+                Err(e) => {
+                    *error.lock().expect("mutex poisoned") = Some(e);
+                    None
+                }
+            })
+        })
+    };
+
+    IOxFlightDataEncoderBuilder::new(plan.schema())
+        .with_metadata(proto::AppMetadata {}.encode_to_vec().into())
+        .build(query_results)
+        .chain(
+            futures::stream::once(async move { error.lock().expect("mutex poisoned").take() })
+                .flat_map(move |e| match e {
+                    Some(e) => influxql_error_stream(statement_id, &e),
+                    None => futures::stream::empty().boxed(),
+                }),
+        )
+        .boxed()
+}
+
+/// Encode the error batch for the InfluxQL statement identified by
+/// `statement_id`.
+fn influxql_error_stream(
+    statement_id: usize,
+    error: &DataFusionError,
+) -> BoxStream<'static, arrow_flight::error::Result<FlightData>> {
+    match influxql_error_batch(statement_id, error) {
+        Ok(batch) => IOxFlightDataEncoderBuilder::new(batch.schema())
+            .with_metadata(proto::AppMetadata {}.encode_to_vec().into())
+            .build(futures::stream::once(async move { Ok(batch) }))
+            .boxed(),
+        Err(e) => futures::stream::once(async move { Err(e) }).boxed(),
+    }
+}
+
+/// Returns a [`RecordBatch`] that reports the `error` of the InfluxQL
+/// statement identified by `statement_id`.
+///
+/// The batch has a single `error` column and row, holding the error message,
+/// and the [`proto::InfluxQlMetadata`] of its schema sets the `statement_id`
+/// and `error` fields.
+fn influxql_error_batch(
+    statement_id: usize,
+    error: &DataFusionError,
+) -> arrow_flight::error::Result<RecordBatch> {
+    let message = error.to_string();
+    let metadata = proto::InfluxQlMetadata {
+        statement_id: statement_id as u32,
+        error: Some(message.clone()),
+        ..Default::default()
+    };
+    let metadata =
+        serde_json::to_string(&metadata).map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+
+    let schema = Arc::new(Schema::new_with_metadata(
+        vec![Field::new("error", DataType::Utf8, false)],
+        HashMap::from([(INFLUXQL_METADATA_KEY.to_owned(), metadata)]),
+    ));
+
+    Ok(RecordBatch::try_new(
+        schema,
+        vec![Arc::new(StringArray::from(vec![message])) as ArrayRef],
+    )?)
 }
 
 /// workaround for <https://github.com/apache/arrow-rs/issues/3591>
@@ -967,6 +1300,7 @@ mod tests {
     use arrow_flight::sql::ProstMessageExt;
     use async_trait::async_trait;
     use authz::Permission;
+    use flightsql::PreparedStatementHandle;
     use futures::Future;
     use metric::{Attributes, Metric, U64Gauge};
     use service_common::test_util::TestDatabaseStore;
@@ -975,6 +1309,28 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_influxql_error_batch() {
+        let batch =
+            influxql_error_batch(2, &DataFusionError::Plan("database not found: foo".into()))
+                .unwrap();
+        assert_eq!(batch.num_rows(), 1);
+
+        let message = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .value(0);
+        assert_eq!(message, "Error during planning: database not found: foo");
+
+        let schema = batch.schema();
+        let md: proto::InfluxQlMetadata =
+            serde_json::from_str(&schema.metadata()[INFLUXQL_METADATA_KEY]).unwrap();
+        assert_eq!(md.statement_id, 2);
+        assert_eq!(md.error.as_deref(), Some(message));
+    }
+
     #[tokio::test]
     async fn test_query_semaphore() {
         let semaphore_size = 2;
@@ -1147,6 +1503,11 @@ mod tests {
             match token {
                 Some(b"GOOD") => Ok(perms.to_vec()),
                 Some(b"BAD") => Ok(vec![]),
+                Some(b"READ") => Ok(perms
+                    .iter()
+                    .filter(|p| matches!(p, Permission::ResourceAction(_, authz::Action::Read)))
+                    .cloned()
+                    .collect()),
                 Some(b"UGLY") => Err(authz::Error::verification("test", "test error")),
                 Some(_) => panic!("unexpected token"),
                 None => Err(authz::Error::NoToken),
@@ -1205,6 +1566,28 @@ mod tests {
             )
         }
 
+        fn influxql_delete_request(
+            authorization: &'static str,
+        ) -> tonic::Request<arrow_flight::Ticket> {
+            request(
+                RunQuery::InfluxQL("DROP MEASUREMENT cpu".to_string()),
+                authorization,
+            )
+        }
+
+        fn flightsql_influxql_delete_request(
+            authorization: &'static str,
+        ) -> tonic::Request<arrow_flight::Ticket> {
+            let handle = PreparedStatementHandle::new(
+                "DROP MEASUREMENT cpu".to_string(),
+                QueryLanguage::InfluxQL,
+            );
+            request(
+                RunQuery::FlightSQL(FlightSQLCommand::CommandPreparedStatementQuery(handle)),
+                authorization,
+            )
+        }
+
         fn flightsql_request(authorization: &'static str) -> tonic::Request<arrow_flight::Ticket> {
             request(
                 RunQuery::FlightSQL(FlightSQLCommand::CommandGetCatalogs(
@@ -1240,6 +1623,34 @@ mod tests {
         .await;
         assert_code(&svc, tonic::Code::Internal, influxql_request("Bearer UGLY")).await;
 
+        // Deleting data requires write permission
+        assert_code(
+            &svc,
+            tonic::Code::PermissionDenied,
+            influxql_delete_request("Bearer READ"),
+        )
+        .await;
+        assert_code(
+            &svc,
+            tonic::Code::PermissionDenied,
+            influxql_delete_request("Bearer BAD"),
+        )
+        .await;
+
+        // Including through an InfluxQL FlightSQL prepared statement
+        assert_code(
+            &svc,
+            tonic::Code::PermissionDenied,
+            flightsql_influxql_delete_request("Bearer READ"),
+        )
+        .await;
+        assert_code(
+            &svc,
+            tonic::Code::PermissionDenied,
+            flightsql_influxql_delete_request("Bearer BAD"),
+        )
+        .await;
+
         assert_code(&svc, tonic::Code::Unauthenticated, flightsql_request("")).await;
         assert_code(&svc, tonic::Code::Ok, flightsql_request("Bearer GOOD")).await;
         assert_code(
@@ -13,6 +13,7 @@ use crate::{
     response_chunking::ChunkReadResponses,
     StorageService,
 };
+use arrow::array::Int64Array;
 use data_types::NamespaceName;
 use datafusion::error::DataFusionError;
 use futures::{stream::BoxStream, Stream, StreamExt, TryStreamExt};
@@ -38,6 +39,7 @@ use iox_query::{
     },
     QueryCompletedToken, QueryNamespace, QueryText,
 };
+use iox_query_influxrpc::SERIES_CARDINALITY_COLUMN_NAME;
 use observability_deps::tracing::{error, info, trace};
 use prost::{bytes::BytesMut, Message};
 use service_common::{datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider};
@@ -47,7 +49,6 @@ use std::{
     fmt::{Display, Formatter, Result as FmtResult},
     sync::Arc,
 };
-use tokio_stream::wrappers::ReceiverStream;
 use tonic::{metadata::MetadataMap, Response, Status};
 use trace::{ctx::SpanContext, span::SpanExt};
 use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
@@ -782,13 +783,65 @@ where
         )
     }
 
-    type ReadSeriesCardinalityStream = ReceiverStream<Result<Int64ValuesResponse, Status>>;
+    type ReadSeriesCardinalityStream = StreamWithPermit<
+        QueryCompletedTokenStream<
+            BoxStream<'static, Result<Int64ValuesResponse, Status>>,
+            Int64ValuesResponse,
+            Status,
+        >,
+    >;
 
     async fn read_series_cardinality(
         &self,
-        _req: tonic::Request<ReadSeriesCardinalityRequest>,
+        req: tonic::Request<ReadSeriesCardinalityRequest>,
     ) -> Result<Response<Self::ReadSeriesCardinalityStream>, Status> {
-        unimplemented!("read_series_cardinality not yet implemented. https://github.com/influxdata/influxdb_iox/issues/447");
+        let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+
+        let req = req.into_inner();
+        let permit = self
+            .db_store
+            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .await;
+
+        let db_name = get_namespace_name(&req)?;
+        info!(
+            %db_name,
+            ?req.range,
+            predicate=%req.predicate.loggable(),
+            trace=%external_span_ctx.format_jaeger(),
+            "read_series_cardinality",
+        );
+
+        let db = self
+            .db_store
+            .db(&db_name, span_ctx.child_span("get namespace"))
+            .await
+            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+
+        let ctx = db.new_query_context(span_ctx);
+        let query_completed_token =
+            db.record_query(&ctx, "read_series_cardinality", defer_json(&req));
+
+        let ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: _source,
+            range,
+            predicate,
+        } = req;
+
+        let response =
+            read_series_cardinality_impl(Arc::clone(&db), db_name, range, predicate, &ctx)
+                .await
+                .map(|count| Int64ValuesResponse {
+                    values: vec![count],
+                })
+                .map_err(|e| e.into_status());
+
+        make_response(
+            futures::stream::once(async move { response }).boxed(),
+            query_completed_token,
+            permit,
+        )
     }
 
     async fn capabilities(
@@ -1389,6 +1442,67 @@ where
     ))
 }
 
+/// Counts the series that have data matching the specified range and
+/// predicate.
+///
+/// Consistent with the InfluxDB storage engine, a series is a unique
+/// combination of measurement, tag set and field key.
+async fn read_series_cardinality_impl<N>(
+    db: Arc<N>,
+    db_name: NamespaceName<'static>,
+    range: Option<TimestampRange>,
+    rpc_predicate: Option<Predicate>,
+    ctx: &IOxSessionContext,
+) -> Result<i64, Error>
+where
+    N: QueryNamespace + ExecutionContextProvider + 'static,
+{
+    let db_name = db_name.as_str();
+
+    let rpc_predicate_string = format!("{rpc_predicate:?}");
+
+    let predicate = InfluxRpcPredicateBuilder::default()
+        .set_range(range)
+        .rpc_predicate(rpc_predicate)
+        .context(ConvertingPredicateSnafu {
+            rpc_predicate_string,
+        })?
+        .build();
+
+    let plans = Planner::new(ctx)
+        .read_series_cardinality(db, predicate)
+        .await
+        .context(PlanningFilteringSeriesSnafu { db_name })?;
+
+    let mut count = 0;
+    for plan in plans {
+        let physical_plan = ctx
+            .create_physical_plan(&plan)
+            .await
+            .context(FilteringSeriesSnafu { db_name })?;
+        let batches = ctx
+            .collect(physical_plan)
+            .await
+            .context(FilteringSeriesSnafu { db_name })
+            .log_if_error("Counting series")?;
+
+        for batch in batches {
+            let series = batch
+                .column_by_name(SERIES_CARDINALITY_COLUMN_NAME)
+                .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+                .ok_or_else(|| {
+                    DataFusionError::Internal(format!(
+                        "expected Int64 {SERIES_CARDINALITY_COLUMN_NAME} column"
+                    ))
+                })
+                .context(FilteringSeriesSnafu { db_name })?;
+            count += series.iter().flatten().sum::<i64>();
+        }
+    }
+
+    Ok(count)
+}
+
 /// Launch async tasks that send the result of executing read_group to `tx`
 async fn query_group_impl<N>(
     db: Arc<N>,
@@ -2763,6 +2877,74 @@ mod tests {
         grpc_request_metric_has_count(&fixture, "ReadFilter", "ok", 1);
     }
 
+    #[tokio::test]
+    async fn test_read_series_cardinality() {
+        test_helpers::maybe_start_logging();
+        // Start a test gRPC server on a randomally allocated port
+        let mut fixture = Fixture::new().await.expect("Connecting to test server");
+
+        let db_info = org_and_bucket();
+
+        // Three tag sets, each with two fields
+        let chunk = TestChunk::new("TheMeasurement")
+            .with_time_column()
+            .with_tag_column("tag1")
+            .with_i64_field_column("field1")
+            .with_i64_field_column("field2")
+            .with_three_rows_of_data();
+
+        fixture
+            .test_storage
+            .db_or_create(db_info.db_name())
+            .await
+            .add_chunk("my_partition_key", Arc::new(chunk));
+
+        let source = Some(StorageClient::read_source(&db_info, 1));
+
+        let request = ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: source.clone(),
+            range: Some(make_timestamp_range(0, 30000)),
+            predicate: None,
+        };
+
+        let counts = fixture
+            .storage_client
+            .read_series_cardinality(request)
+            .await
+            .unwrap();
+        assert_eq!(counts, vec![6]);
+
+        // the range excludes the row with tag1 = UT
+        let request = ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: source.clone(),
+            range: Some(make_timestamp_range(0, 15000)),
+            predicate: None,
+        };
+
+        let counts = fixture
+            .storage_client
+            .read_series_cardinality(request)
+            .await
+            .unwrap();
+        assert_eq!(counts, vec![4]);
+
+        // only the series of a single tag set match the predicate
+        let request = ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: source,
+            range: Some(make_timestamp_range(0, 30000)),
+            predicate: Some(make_tag_predicate("tag1", "VT", node::Comparison::Equal)),
+        };
+
+        let counts = fixture
+            .storage_client
+            .read_series_cardinality(request)
+            .await
+            .unwrap();
+        assert_eq!(counts, vec![2]);
+
+        grpc_request_metric_has_count(&fixture, "ReadSeriesCardinality", "ok", 3);
+    }
+
     #[tokio::test]
     async fn test_read_filter_empty_string() {
         test_helpers::maybe_start_logging();
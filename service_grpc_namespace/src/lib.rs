@@ -1,9 +1,14 @@
 //! Implementation of the namespace gRPC service
 use std::sync::Arc;
 
-use data_types::{Namespace as CatalogNamespace, NamespaceName, QueryPoolId, TopicId};
-use generated_types::influxdata::iox::namespace::v1::{
-    update_namespace_service_protection_limit_request::LimitUpdate, *,
+use data_types::{
+    Namespace as CatalogNamespace, NamespaceName, PartitionTemplate, QueryPoolId, TopicId,
+};
+use generated_types::{
+    google::FromOptionalField,
+    influxdata::iox::namespace::v1::{
+        update_namespace_service_protection_limit_request::LimitUpdate, *,
+    },
 };
 use iox_catalog::interface::{Catalog, SoftDeletedRows};
 use observability_deps::tracing::{debug, info, warn};
@@ -62,11 +67,10 @@ impl namespace_service_server::NamespaceService for NamespaceService {
             return Err(Status::invalid_argument("topic_id or query_id not set"));
         }
 
-        let mut repos = self.catalog.repositories().await;
-
         let CreateNamespaceRequest {
             name: namespace_name,
             retention_period_ns,
+            partition_template,
         } = request.into_inner();
 
         // Ensure the namespace name is consistently processed within IOx - this
@@ -75,10 +79,26 @@ impl namespace_service_server::NamespaceService for NamespaceService {
             .map_err(|v| Status::invalid_argument(v.to_string()))?;
 
         let retention_period_ns = map_retention_period(retention_period_ns)?;
+        let partition_template: Option<PartitionTemplate> =
+            partition_template.optional("partition_template")?;
+
+        debug!(
+            %namespace_name,
+            ?retention_period_ns,
+            ?partition_template,
+            "Creating namespace"
+        );
 
-        debug!(%namespace_name, ?retention_period_ns, "Creating namespace");
+        // The namespace and its partition template are created in a single
+        // transaction, so the namespace is never observable without its
+        // template.
+        let mut txn = self
+            .catalog
+            .start_transaction()
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
 
-        let namespace = repos
+        let mut namespace = txn
             .namespaces()
             .create(
                 &namespace_name,
@@ -92,6 +112,21 @@ impl namespace_service_server::NamespaceService for NamespaceService {
                 Status::internal(e.to_string())
             })?;
 
+        if partition_template.is_some() {
+            namespace = txn
+                .namespaces()
+                .update_partition_template(&namespace_name, partition_template)
+                .await
+                .map_err(|e| {
+                    warn!(error=%e, %namespace_name, "failed to set namespace partition template");
+                    Status::internal(e.to_string())
+                })?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
         info!(
             %namespace_name,
             namespace_id = %namespace.id,
@@ -221,6 +256,38 @@ impl namespace_service_server::NamespaceService for NamespaceService {
                         status_from_catalog_namespace_error(e)
                     })
             }
+            Some(LimitUpdate::MaxLinesPerSecond(n)) => {
+                let new_max = rate_limit_from_proto(n, "max lines per second")?;
+                repos
+                    .namespaces()
+                    .update_lines_per_second_limit(&namespace_name, new_max)
+                    .await
+                    .map_err(|e| {
+                        warn!(
+                            error = %e,
+                            %namespace_name,
+                            max_lines_per_second = ?new_max,
+                            "failed to update lines per second limit for namespace",
+                        );
+                        status_from_catalog_namespace_error(e)
+                    })
+            }
+            Some(LimitUpdate::MaxBytesPerSecond(n)) => {
+                let new_max = rate_limit_from_proto(n, "max bytes per second")?;
+                repos
+                    .namespaces()
+                    .update_bytes_per_second_limit(&namespace_name, new_max)
+                    .await
+                    .map_err(|e| {
+                        warn!(
+                            error = %e,
+                            %namespace_name,
+                            max_bytes_per_second = ?new_max,
+                            "failed to update bytes per second limit for namespace",
+                        );
+                        status_from_catalog_namespace_error(e)
+                    })
+            }
             None => Err(Status::invalid_argument(
                 "unsupported service protection limit change requested",
             )),
@@ -231,6 +298,8 @@ impl namespace_service_server::NamespaceService for NamespaceService {
             namespace_id = %namespace.id,
             max_tables = %namespace.max_tables,
             max_columns_per_table = %namespace.max_columns_per_table,
+            max_lines_per_second = ?namespace.max_lines_per_second,
+            max_bytes_per_second = ?namespace.max_bytes_per_second,
             "updated namespace service protection limits",
         );
 
@@ -242,6 +311,18 @@ impl namespace_service_server::NamespaceService for NamespaceService {
     }
 }
 
+/// Map a write rate limit in a limit update request to the value stored in
+/// the catalog, where zero removes the limit.
+fn rate_limit_from_proto(n: i64, limit_name: &str) -> Result<Option<i64>, Status> {
+    match n {
+        0 => Ok(None),
+        n if n < 0 => Err(Status::invalid_argument(format!(
+            "{limit_name} limit for namespace must not be negative"
+        ))),
+        n => Ok(Some(n)),
+    }
+}
+
 fn namespace_to_proto(namespace: CatalogNamespace) -> Namespace {
     Namespace {
         id: namespace.id.get(),
@@ -249,6 +330,9 @@ fn namespace_to_proto(namespace: CatalogNamespace) -> Namespace {
         retention_period_ns: namespace.retention_period_ns,
         max_tables: namespace.max_tables,
         max_columns_per_table: namespace.max_columns_per_table,
+        partition_template: namespace.partition_template.map(Into::into),
+        max_lines_per_second: namespace.max_lines_per_second,
+        max_bytes_per_second: namespace.max_bytes_per_second,
     }
 }
 
@@ -260,6 +344,9 @@ fn namespace_to_create_response_proto(namespace: CatalogNamespace) -> CreateName
             retention_period_ns: namespace.retention_period_ns,
             max_tables: namespace.max_tables,
             max_columns_per_table: namespace.max_columns_per_table,
+            partition_template: namespace.partition_template.map(Into::into),
+            max_lines_per_second: namespace.max_lines_per_second,
+            max_bytes_per_second: namespace.max_bytes_per_second,
         }),
     }
 }
@@ -380,6 +467,7 @@ mod tests {
         let req = CreateNamespaceRequest {
             name: NS_NAME.to_string(),
             retention_period_ns: Some(RETENTION),
+            partition_template: None,
         };
         let created_ns = handler
             .create_namespace(Request::new(req))
@@ -474,6 +562,53 @@ mod tests {
         assert_eq!(updated_ns.max_tables, want_max_tables);
         assert_eq!(updated_ns.max_columns_per_table, want_max_columns_per_table);
 
+        // Namespaces have no write rate limits by default.
+        assert_eq!(updated_ns.max_lines_per_second, None);
+        assert_eq!(updated_ns.max_bytes_per_second, None);
+
+        // Set the write rate limits
+        for limit_update in [
+            LimitUpdate::MaxLinesPerSecond(1_000),
+            LimitUpdate::MaxBytesPerSecond(4_096),
+        ] {
+            handler
+                .update_namespace_service_protection_limit(Request::new(
+                    UpdateNamespaceServiceProtectionLimitRequest {
+                        name: NS_NAME.to_string(),
+                        limit_update: Some(limit_update),
+                    },
+                ))
+                .await
+                .expect("failed to update namespace");
+        }
+        let current = handler
+            .get_namespaces(Request::new(Default::default()))
+            .await
+            .expect("must return namespaces")
+            .into_inner()
+            .namespaces;
+        assert_matches!(current.as_slice(), [ns] => {
+            assert_eq!(ns.max_lines_per_second, Some(1_000));
+            assert_eq!(ns.max_bytes_per_second, Some(4_096));
+            assert_eq!(ns.max_tables, want_max_tables);
+        });
+
+        // Setting a rate limit to zero removes it
+        let updated_ns = handler
+            .update_namespace_service_protection_limit(Request::new(
+                UpdateNamespaceServiceProtectionLimitRequest {
+                    name: NS_NAME.to_string(),
+                    limit_update: Some(LimitUpdate::MaxLinesPerSecond(0)),
+                },
+            ))
+            .await
+            .expect("failed to update namespace")
+            .into_inner()
+            .namespace
+            .expect("no namespace in response");
+        assert_eq!(updated_ns.max_lines_per_second, None);
+        assert_eq!(updated_ns.max_bytes_per_second, Some(4_096));
+
         // Deleting the namespace should cause it to disappear
         handler
             .delete_namespace(Request::new(DeleteNamespaceRequest {
@@ -518,6 +653,7 @@ mod tests {
         let req = CreateNamespaceRequest {
             name: NS_NAME.to_string(),
             retention_period_ns: Some(RETENTION),
+            partition_template: None,
         };
         let created_ns = handler
             .create_namespace(Request::new(req))
@@ -556,6 +692,114 @@ mod tests {
                 "invalid namespace update request for max columns per table limit should fail",
             );
         assert_eq!(status.code(), Code::InvalidArgument);
+
+        // Negative write rate limits are rejected.
+        for limit_update in [
+            LimitUpdate::MaxLinesPerSecond(-1),
+            LimitUpdate::MaxBytesPerSecond(-1),
+        ] {
+            let status = handler
+                .update_namespace_service_protection_limit(Request::new(
+                    UpdateNamespaceServiceProtectionLimitRequest {
+                        name: NS_NAME.to_string(),
+                        limit_update: Some(limit_update),
+                    },
+                ))
+                .await
+                .expect_err("negative write rate limit should fail");
+            assert_eq!(status.code(), Code::InvalidArgument);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_namespace_with_partition_template() {
+        use generated_types::influxdata::iox::partition_template::v1 as template_proto;
+
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+
+        let topic = catalog
+            .repositories()
+            .await
+            .topics()
+            .create_or_get("kafka-topic")
+            .await
+            .unwrap();
+        let query_pool = catalog
+            .repositories()
+            .await
+            .query_pools()
+            .create_or_get("query-pool")
+            .await
+            .unwrap();
+
+        let handler =
+            NamespaceService::new(Arc::clone(&catalog), Some(topic.id), Some(query_pool.id));
+
+        let template = template_proto::PartitionTemplate {
+            parts: vec![
+                template_proto::TemplatePart {
+                    part: Some(template_proto::template_part::Part::TimeFormat(
+                        "%Y-%m-%d %H".to_string(),
+                    )),
+                },
+                template_proto::TemplatePart {
+                    part: Some(template_proto::template_part::Part::Column(
+                        "region".to_string(),
+                    )),
+                },
+            ],
+        };
+        let created_ns = handler
+            .create_namespace(Request::new(CreateNamespaceRequest {
+                name: NS_NAME.to_string(),
+                retention_period_ns: None,
+                partition_template: Some(template.clone()),
+            }))
+            .await
+            .expect("failed to create namespace")
+            .into_inner()
+            .namespace
+            .expect("no namespace in response");
+        assert_eq!(created_ns.partition_template, Some(template));
+
+        // The template is stored in the catalog.
+        let ns = catalog
+            .repositories()
+            .await
+            .namespaces()
+            .get_by_name(NS_NAME, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .unwrap()
+            .expect("namespace must exist");
+        assert_eq!(
+            ns.partition_template,
+            Some(PartitionTemplate {
+                parts: vec![
+                    data_types::TemplatePart::TimeFormat("%Y-%m-%d %H".to_string()),
+                    data_types::TemplatePart::Column("region".to_string()),
+                ],
+            })
+        );
+
+        // An invalid template is rejected and no namespace is created.
+        let status = handler
+            .create_namespace(Request::new(CreateNamespaceRequest {
+                name: "platanos".to_string(),
+                retention_period_ns: None,
+                partition_template: Some(template_proto::PartitionTemplate { parts: vec![] }),
+            }))
+            .await
+            .expect_err("empty partition template should be rejected");
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let current = handler
+            .get_namespaces(Request::new(Default::default()))
+            .await
+            .expect("must return namespaces")
+            .into_inner()
+            .namespaces;
+        assert_eq!(current.len(), 1);
     }
 
     macro_rules! test_create_namespace_name {
@@ -592,6 +836,7 @@ mod tests {
                     let req = CreateNamespaceRequest {
                         name: String::from($name),
                         retention_period_ns: Some(RETENTION),
+                        partition_template: None,
                     };
 
                     let got = handler.create_namespace(Request::new(req)).await;
@@ -2,9 +2,10 @@
 
 use std::{ops::DerefMut, sync::Arc};
 
-use generated_types::influxdata::iox::schema::v1::*;
-use iox_catalog::interface::{get_schema_by_name, Catalog, SoftDeletedRows};
-use observability_deps::tracing::warn;
+use data_types::{Column, ColumnType, Table};
+use generated_types::{google::NonEmptyString, influxdata::iox::schema::v1::*};
+use iox_catalog::interface::{get_schema_by_name, Catalog, RepoCollection, SoftDeletedRows};
+use observability_deps::tracing::{debug, info, warn};
 use tonic::{Request, Response, Status};
 
 /// Implementation of the gRPC schema service
@@ -42,6 +43,286 @@ impl schema_service_server::SchemaService for SchemaService {
         .map(Arc::new)?;
         Ok(Response::new(schema_to_proto(schema)))
     }
+
+    async fn rename_table(
+        &self,
+        request: Request<RenameTableRequest>,
+    ) -> Result<Response<RenameTableResponse>, Status> {
+        let RenameTableRequest {
+            namespace: namespace_name,
+            table: table_name,
+            new_name,
+        } = request.into_inner();
+
+        let table_name = table_name.non_empty("table")?;
+        let new_name = new_name.non_empty("new_name")?;
+
+        debug!(%namespace_name, %table_name, %new_name, "Renaming table");
+
+        let mut repos = self.catalog.repositories().await;
+
+        let table = get_table(repos.as_mut(), &namespace_name, &table_name).await?;
+
+        // Parquet files and ingester buffers are addressed by table ID, so
+        // changing the name in the catalog is all that is needed.
+        let table = repos
+            .tables()
+            .rename(table.id, &new_name)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %namespace_name, %table_name, %new_name, "failed to rename table");
+                status_from_catalog_rename_error(e)
+            })?;
+
+        info!(
+            %namespace_name,
+            %table_name,
+            %new_name,
+            table_id = %table.id,
+            "renamed table"
+        );
+
+        Ok(Response::new(RenameTableResponse { id: table.id.get() }))
+    }
+
+    async fn rename_column(
+        &self,
+        request: Request<RenameColumnRequest>,
+    ) -> Result<Response<RenameColumnResponse>, Status> {
+        let RenameColumnRequest {
+            namespace: namespace_name,
+            table: table_name,
+            column: column_name,
+            new_name,
+        } = request.into_inner();
+
+        let table_name = table_name.non_empty("table")?;
+        let column_name = column_name.non_empty("column")?;
+        let new_name = new_name.non_empty("new_name")?;
+
+        debug!(%namespace_name, %table_name, %column_name, %new_name, "Renaming column");
+
+        // The rename and the persisted check run in one transaction so that
+        // an ingester cannot commit a parquet file referring to the column by
+        // its old name in between, see `check_column_not_persisted()`.
+        let mut txn = self.catalog.start_transaction().await.map_err(|e| {
+            warn!(error=%e, "failed to start catalog transaction");
+            Status::internal(e.to_string())
+        })?;
+
+        let (table, column) = match rename_unpersisted_column(
+            txn.as_mut(),
+            &namespace_name,
+            &table_name,
+            &column_name,
+            &new_name,
+        )
+        .await
+        {
+            Ok(v) => v,
+            Err(e) => {
+                if let Err(abort_err) = txn.abort().await {
+                    warn!(error=%abort_err, "failed to abort catalog transaction");
+                }
+                return Err(e);
+            }
+        };
+
+        txn.commit().await.map_err(|e| {
+            warn!(
+                error=%e,
+                %namespace_name,
+                %table_name,
+                %column_name,
+                %new_name,
+                "failed to commit column rename"
+            );
+            Status::internal(e.to_string())
+        })?;
+
+        info!(
+            %namespace_name,
+            %table_name,
+            %column_name,
+            %new_name,
+            column_id = column.id.get(),
+            "renamed column"
+        );
+
+        Ok(Response::new(RenameColumnResponse {
+            id: column.id.get(),
+            namespace_id: table.namespace_id.get(),
+            table_id: table.id.get(),
+        }))
+    }
+}
+
+/// Rename the column named `column_name` in the table named `table_name` to
+/// `new_name`, as long as it is not the time column and has not been
+/// persisted, returning the table and the renamed column.
+async fn rename_unpersisted_column<R>(
+    repos: &mut R,
+    namespace_name: &str,
+    table_name: &str,
+    column_name: &str,
+    new_name: &str,
+) -> Result<(Table, Column), Status>
+where
+    R: RepoCollection + ?Sized,
+{
+    let table = get_table(repos, namespace_name, table_name).await?;
+    let column = get_column(repos, &table, column_name).await?;
+
+    // The time column is referred to by name throughout IOx.
+    if column.column_type == ColumnType::Time {
+        return Err(Status::invalid_argument(format!(
+            "cannot rename the time column {column_name}"
+        )));
+    }
+
+    // Rename before checking for persisted data, as the rename takes the lock
+    // on the column row that persisting ingesters wait on.
+    let renamed = repos
+        .columns()
+        .rename(column.id, new_name)
+        .await
+        .map_err(|e| {
+            warn!(
+                error=%e,
+                %namespace_name,
+                %table_name,
+                %column_name,
+                %new_name,
+                "failed to rename column"
+            );
+            status_from_catalog_rename_error(e)
+        })?;
+
+    check_column_not_persisted(repos, &table, &column).await?;
+
+    Ok((table, renamed))
+}
+
+/// Resolve the live table named `table_name` in the namespace named
+/// `namespace_name`, returning a not found status if either does not exist.
+async fn get_table<R>(
+    repos: &mut R,
+    namespace_name: &str,
+    table_name: &str,
+) -> Result<Table, Status>
+where
+    R: RepoCollection + ?Sized,
+{
+    let namespace = repos
+        .namespaces()
+        .get_by_name(namespace_name, SoftDeletedRows::ExcludeDeleted)
+        .await
+        .map_err(|e| {
+            warn!(error=%e, %namespace_name, "failed to retrieve namespace from catalog");
+            Status::internal(e.to_string())
+        })?
+        .ok_or_else(|| Status::not_found(format!("namespace {namespace_name} not found")))?;
+
+    repos
+        .tables()
+        .get_by_namespace_and_name(namespace.id, table_name)
+        .await
+        .map_err(|e| {
+            warn!(error=%e, %namespace_name, %table_name, "failed to retrieve table from catalog");
+            Status::internal(e.to_string())
+        })?
+        .ok_or_else(|| {
+            Status::not_found(format!(
+                "table {table_name} not found in namespace {namespace_name}"
+            ))
+        })
+}
+
+/// Resolve the live column named `column_name` in `table`, returning a not
+/// found status if it does not exist.
+async fn get_column<R>(repos: &mut R, table: &Table, column_name: &str) -> Result<Column, Status>
+where
+    R: RepoCollection + ?Sized,
+{
+    repos
+        .columns()
+        .list_by_table_id(table.id)
+        .await
+        .map_err(|e| {
+            warn!(error=%e, table_id=%table.id, "failed to list columns");
+            Status::internal(e.to_string())
+        })?
+        .into_iter()
+        .find(|c| c.name == column_name)
+        .ok_or_else(|| {
+            Status::not_found(format!(
+                "column {column_name} not found in table {}",
+                table.name
+            ))
+        })
+}
+
+/// Parquet files and partition sort keys refer to columns by name, not ID.
+///
+/// Renaming a column that is referenced by either would make the existing
+/// data for it unreadable under the new name, so such renames are rejected
+/// with a failed precondition status.
+///
+/// Writes still buffered in an ingester at the time of the rename are
+/// persisted under the new name, as the ingester records the ID of each
+/// buffered column. An ingester commits a parquet file in a transaction that
+/// locks the column rows of its table and checks they still have the names
+/// the file was written with, so `column` must already have been renamed in
+/// the transaction of `repos` to make this check exclusive with persisting.
+async fn check_column_not_persisted<R>(
+    repos: &mut R,
+    table: &Table,
+    column: &Column,
+) -> Result<(), Status>
+where
+    R: RepoCollection + ?Sized,
+{
+    let in_sort_key = repos
+        .partitions()
+        .list_by_table_id(table.id)
+        .await
+        .map_err(|e| {
+            warn!(error=%e, table_id=%table.id, "failed to list partitions");
+            Status::internal(e.to_string())
+        })?
+        .iter()
+        .any(|p| p.sort_key.iter().any(|c| *c == column.name));
+
+    let in_files = repos
+        .parquet_files()
+        .list_by_table_not_to_delete(table.id)
+        .await
+        .map_err(|e| {
+            warn!(error=%e, table_id=%table.id, "failed to list parquet files");
+            Status::internal(e.to_string())
+        })?
+        .iter()
+        .any(|f| f.column_set.contains(&column.id));
+
+    if in_sort_key || in_files {
+        return Err(Status::failed_precondition(format!(
+            "cannot rename column {} as it has been persisted in table {}",
+            column.name, table.name
+        )));
+    }
+
+    Ok(())
+}
+
+fn status_from_catalog_rename_error(err: iox_catalog::interface::Error) -> Status {
+    match err {
+        iox_catalog::interface::Error::NameExists { .. } => Status::already_exists(err.to_string()),
+        iox_catalog::interface::Error::TableNotFound { .. }
+        | iox_catalog::interface::Error::ColumnNotFound { .. } => {
+            Status::not_found(err.to_string())
+        }
+        _ => Status::internal(err.to_string()),
+    }
 }
 
 fn schema_to_proto(schema: Arc<data_types::NamespaceSchema>) -> GetSchemaResponse {
@@ -83,10 +364,11 @@ fn schema_to_proto(schema: Arc<data_types::NamespaceSchema>) -> GetSchemaRespons
 #[cfg(test)]
 mod tests {
     use super::*;
-    use data_types::ColumnType;
+    use data_types::{ColumnType, TRANSITION_SHARD_ID};
     use generated_types::influxdata::iox::schema::v1::schema_service_server::SchemaService;
     use iox_catalog::mem::MemCatalog;
     use std::sync::Arc;
+    use tonic::Code;
 
     #[tokio::test]
     async fn test_schema() {
@@ -142,4 +424,179 @@ mod tests {
             vec![&"schema_test_column".to_string()]
         );
     }
+
+    const NS_NAME: &str = "bananas";
+
+    async fn setup() -> (Arc<dyn Catalog>, data_types::Table) {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+
+        let mut repos = catalog.repositories().await;
+        let topic = repos.topics().create_or_get("franz").await.unwrap();
+        let pool = repos.query_pools().create_or_get("franz").await.unwrap();
+        let namespace = repos
+            .namespaces()
+            .create(NS_NAME, None, topic.id, pool.id)
+            .await
+            .unwrap();
+        let table = repos
+            .tables()
+            .create_or_get("platanos", namespace.id)
+            .await
+            .unwrap();
+        repos
+            .tables()
+            .create_or_get("plantains", namespace.id)
+            .await
+            .unwrap();
+        drop(repos);
+
+        (catalog, table)
+    }
+
+    #[tokio::test]
+    async fn test_rename_table() {
+        let (catalog, table) = setup().await;
+        let grpc = super::SchemaService::new(Arc::clone(&catalog));
+
+        let rename = |table: &str, new_name: &str| {
+            grpc.rename_table(Request::new(RenameTableRequest {
+                namespace: NS_NAME.to_string(),
+                table: table.to_string(),
+                new_name: new_name.to_string(),
+            }))
+        };
+
+        let status = rename("platanos", "plantains")
+            .await
+            .expect_err("renaming over an existing table should fail");
+        assert_eq!(status.code(), Code::AlreadyExists);
+
+        let status = rename("bananas", "platanos")
+            .await
+            .expect_err("renaming an unknown table should fail");
+        assert_eq!(status.code(), Code::NotFound);
+
+        let status = rename("platanos", "")
+            .await
+            .expect_err("renaming to an empty name should fail");
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let id = rename("platanos", "bananas")
+            .await
+            .expect("rename should succeed")
+            .into_inner()
+            .id;
+        assert_eq!(id, table.id.get());
+
+        let schema = grpc
+            .get_schema(Request::new(GetSchemaRequest {
+                namespace: NS_NAME.to_string(),
+            }))
+            .await
+            .expect("rpc request should succeed")
+            .into_inner()
+            .schema
+            .expect("schema should be Some()");
+        let mut tables = schema.tables.keys().collect::<Vec<_>>();
+        tables.sort();
+        assert_eq!(tables, ["bananas", "plantains"]);
+        assert_eq!(schema.tables["bananas"].id, table.id.get());
+    }
+
+    #[tokio::test]
+    async fn test_rename_column() {
+        let (catalog, table) = setup().await;
+        let grpc = super::SchemaService::new(Arc::clone(&catalog));
+
+        let (tag, sorted) = {
+            let mut repos = catalog.repositories().await;
+            repos
+                .columns()
+                .create_or_get("time", table.id, ColumnType::Time)
+                .await
+                .unwrap();
+            repos
+                .columns()
+                .create_or_get("value", table.id, ColumnType::F64)
+                .await
+                .unwrap();
+            let tag = repos
+                .columns()
+                .create_or_get("reigon", table.id, ColumnType::Tag)
+                .await
+                .unwrap();
+            let sorted = repos
+                .columns()
+                .create_or_get("city", table.id, ColumnType::Tag)
+                .await
+                .unwrap();
+
+            // Persisted data for the "city" column is sorted by it.
+            let partition = repos
+                .partitions()
+                .create_or_get("arán".into(), TRANSITION_SHARD_ID, table.id)
+                .await
+                .unwrap();
+            repos
+                .partitions()
+                .cas_sort_key(partition.id, None, &["city", "time"])
+                .await
+                .unwrap();
+
+            (tag, sorted)
+        };
+
+        let rename = |column: &str, new_name: &str| {
+            grpc.rename_column(Request::new(RenameColumnRequest {
+                namespace: NS_NAME.to_string(),
+                table: "platanos".to_string(),
+                column: column.to_string(),
+                new_name: new_name.to_string(),
+            }))
+        };
+
+        let status = rename("reigon", "value")
+            .await
+            .expect_err("renaming over an existing column should fail");
+        assert_eq!(status.code(), Code::AlreadyExists);
+
+        let status = rename("region", "reigon")
+            .await
+            .expect_err("renaming an unknown column should fail");
+        assert_eq!(status.code(), Code::NotFound);
+
+        let status = rename("time", "timestamp")
+            .await
+            .expect_err("renaming the time column should fail");
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let status = rename("city", "town")
+            .await
+            .expect_err("renaming a persisted column should fail");
+        assert_eq!(status.code(), Code::FailedPrecondition);
+
+        let resp = rename("reigon", "region")
+            .await
+            .expect("rename should succeed")
+            .into_inner();
+        assert_eq!(resp.id, tag.id.get());
+        assert_eq!(resp.namespace_id, table.namespace_id.get());
+        assert_eq!(resp.table_id, table.id.get());
+
+        let columns = catalog
+            .repositories()
+            .await
+            .columns()
+            .list_by_table_id(table.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.name, c.id))
+            .collect::<Vec<_>>();
+        assert_eq!(columns.len(), 4);
+        assert!(columns.contains(&("region".to_string(), tag.id)));
+        // The rejected rename of the persisted column was rolled back.
+        assert!(columns.contains(&("city".to_string(), sorted.id)));
+    }
 }
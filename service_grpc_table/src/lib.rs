@@ -0,0 +1,503 @@
+//! Implementation of the table gRPC service
+use std::sync::Arc;
+
+use data_types::{ColumnType, PartitionTemplate, Table as CatalogTable};
+use generated_types::{
+    google::{FromOptionalField, NonEmptyString},
+    influxdata::iox::table::v1::*,
+};
+use iox_catalog::interface::{Catalog, RepoCollection, SoftDeletedRows};
+use observability_deps::tracing::{debug, info, warn};
+use tonic::{Request, Response, Status};
+
+/// Implementation of the gRPC table service
+#[derive(Debug)]
+pub struct TableService {
+    /// Catalog.
+    catalog: Arc<dyn Catalog>,
+}
+
+impl TableService {
+    pub fn new(catalog: Arc<dyn Catalog>) -> Self {
+        Self { catalog }
+    }
+}
+
+#[tonic::async_trait]
+impl table_service_server::TableService for TableService {
+    // create a table
+    async fn create_table(
+        &self,
+        request: Request<CreateTableRequest>,
+    ) -> Result<Response<CreateTableResponse>, Status> {
+        let CreateTableRequest {
+            namespace: namespace_name,
+            name: table_name,
+            partition_template,
+        } = request.into_inner();
+
+        let table_name = table_name.non_empty("name")?;
+        let partition_template: Option<PartitionTemplate> =
+            partition_template.optional("partition_template")?;
+
+        debug!(%namespace_name, %table_name, ?partition_template, "Creating table");
+
+        let mut repos = self.catalog.repositories().await;
+
+        let namespace = repos
+            .namespaces()
+            .get_by_name(&namespace_name, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %namespace_name, "failed to retrieve namespace from catalog");
+                Status::internal(e.to_string())
+            })?
+            .ok_or_else(|| Status::not_found(format!("namespace {namespace_name} not found")))?;
+
+        let table = repos
+            .tables()
+            .create(&table_name, partition_template, namespace.id)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %namespace_name, %table_name, "failed to create table");
+                status_from_catalog_table_error(e)
+            })?;
+
+        info!(
+            %namespace_name,
+            %table_name,
+            table_id = %table.id,
+            "created table"
+        );
+
+        Ok(Response::new(CreateTableResponse {
+            table: Some(table_to_proto(table)),
+        }))
+    }
+
+    // soft-delete a table
+    async fn delete_table(
+        &self,
+        request: Request<DeleteTableRequest>,
+    ) -> Result<Response<DeleteTableResponse>, Status> {
+        let DeleteTableRequest {
+            namespace: namespace_name,
+            name: table_name,
+        } = request.into_inner();
+
+        let table_name = table_name.non_empty("name")?;
+
+        debug!(%namespace_name, %table_name, "Deleting table");
+
+        let mut repos = self.catalog.repositories().await;
+
+        let table = get_table(repos.as_mut(), &namespace_name, &table_name).await?;
+
+        repos.tables().soft_delete(table.id).await.map_err(|e| {
+            warn!(error=%e, %namespace_name, %table_name, "failed to soft-delete table");
+            Status::internal(e.to_string())
+        })?;
+
+        info!(
+            %namespace_name,
+            %table_name,
+            table_id = %table.id,
+            "soft-deleted table"
+        );
+
+        Ok(Response::new(DeleteTableResponse {}))
+    }
+
+    // soft-delete a column
+    async fn delete_column(
+        &self,
+        request: Request<DeleteColumnRequest>,
+    ) -> Result<Response<DeleteColumnResponse>, Status> {
+        let DeleteColumnRequest {
+            namespace: namespace_name,
+            table: table_name,
+            name: column_name,
+        } = request.into_inner();
+
+        let table_name = table_name.non_empty("table")?;
+        let column_name = column_name.non_empty("name")?;
+
+        debug!(%namespace_name, %table_name, %column_name, "Deleting column");
+
+        let mut repos = self.catalog.repositories().await;
+
+        let table = get_table(repos.as_mut(), &namespace_name, &table_name).await?;
+
+        let column = repos
+            .columns()
+            .list_by_table_id(table.id)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %namespace_name, %table_name, "failed to list columns");
+                Status::internal(e.to_string())
+            })?
+            .into_iter()
+            .find(|c| c.name == column_name)
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "column {column_name} not found in table {table_name}"
+                ))
+            })?;
+
+        // Every row has a timestamp, so the time column cannot be removed.
+        if column.column_type == ColumnType::Time {
+            return Err(Status::invalid_argument(format!(
+                "cannot delete the time column {column_name}"
+            )));
+        }
+
+        repos.columns().soft_delete(column.id).await.map_err(|e| {
+            warn!(
+                error=%e,
+                %namespace_name,
+                %table_name,
+                %column_name,
+                "failed to soft-delete column"
+            );
+            Status::internal(e.to_string())
+        })?;
+
+        info!(
+            %namespace_name,
+            %table_name,
+            %column_name,
+            column_id = column.id.get(),
+            "soft-deleted column"
+        );
+
+        Ok(Response::new(DeleteColumnResponse {}))
+    }
+}
+
+/// Resolve the live table named `table_name` in the namespace named
+/// `namespace_name`, returning a not found status if either does not exist.
+async fn get_table(
+    repos: &mut dyn RepoCollection,
+    namespace_name: &str,
+    table_name: &str,
+) -> Result<CatalogTable, Status> {
+    let namespace = repos
+        .namespaces()
+        .get_by_name(namespace_name, SoftDeletedRows::ExcludeDeleted)
+        .await
+        .map_err(|e| {
+            warn!(error=%e, %namespace_name, "failed to retrieve namespace from catalog");
+            Status::internal(e.to_string())
+        })?
+        .ok_or_else(|| Status::not_found(format!("namespace {namespace_name} not found")))?;
+
+    repos
+        .tables()
+        .get_by_namespace_and_name(namespace.id, table_name)
+        .await
+        .map_err(|e| {
+            warn!(error=%e, %namespace_name, %table_name, "failed to retrieve table from catalog");
+            Status::internal(e.to_string())
+        })?
+        .ok_or_else(|| {
+            Status::not_found(format!(
+                "table {table_name} not found in namespace {namespace_name}"
+            ))
+        })
+}
+
+fn table_to_proto(table: CatalogTable) -> Table {
+    Table {
+        id: table.id.get(),
+        name: table.name,
+        namespace_id: table.namespace_id.get(),
+        partition_template: table.partition_template.map(Into::into),
+    }
+}
+
+fn status_from_catalog_table_error(err: iox_catalog::interface::Error) -> Status {
+    match err {
+        iox_catalog::interface::Error::NameExists { .. } => Status::already_exists(err.to_string()),
+        iox_catalog::interface::Error::TableCreateLimitError { .. } => {
+            Status::resource_exhausted(err.to_string())
+        }
+        _ => Status::internal(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use generated_types::influxdata::iox::{
+        partition_template::v1 as template_proto,
+        table::v1::table_service_server::TableService as _,
+    };
+    use iox_catalog::mem::MemCatalog;
+    use tonic::Code;
+
+    use super::*;
+
+    const NS_NAME: &str = "bananas";
+
+    async fn setup() -> (Arc<dyn Catalog>, TableService) {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+
+        let mut repos = catalog.repositories().await;
+        let topic = repos.topics().create_or_get("kafka-topic").await.unwrap();
+        let query_pool = repos
+            .query_pools()
+            .create_or_get("query-pool")
+            .await
+            .unwrap();
+        repos
+            .namespaces()
+            .create(NS_NAME, None, topic.id, query_pool.id)
+            .await
+            .unwrap();
+
+        let handler = TableService::new(Arc::clone(&catalog));
+        (catalog, handler)
+    }
+
+    fn tag_template() -> template_proto::PartitionTemplate {
+        template_proto::PartitionTemplate {
+            parts: vec![template_proto::TemplatePart {
+                part: Some(template_proto::template_part::Part::Column(
+                    "region".to_string(),
+                )),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_table() {
+        let (catalog, handler) = setup().await;
+
+        let created = handler
+            .create_table(Request::new(CreateTableRequest {
+                namespace: NS_NAME.to_string(),
+                name: "platanos".to_string(),
+                partition_template: Some(tag_template()),
+            }))
+            .await
+            .expect("failed to create table")
+            .into_inner()
+            .table
+            .expect("no table in response");
+        assert_eq!(created.name, "platanos");
+        assert_eq!(created.partition_template, Some(tag_template()));
+
+        // The template is stored in the catalog.
+        let table = catalog
+            .repositories()
+            .await
+            .tables()
+            .get_by_id(data_types::TableId::new(created.id))
+            .await
+            .unwrap()
+            .expect("table must exist");
+        assert_eq!(
+            table.partition_template,
+            Some(PartitionTemplate {
+                parts: vec![data_types::TemplatePart::Column("region".to_string())],
+            })
+        );
+
+        // A table without a template can be created too.
+        let created = handler
+            .create_table(Request::new(CreateTableRequest {
+                namespace: NS_NAME.to_string(),
+                name: "bananas".to_string(),
+                partition_template: None,
+            }))
+            .await
+            .expect("failed to create table")
+            .into_inner()
+            .table
+            .expect("no table in response");
+        assert_eq!(created.partition_template, None);
+
+        // Creating the same table again is rejected.
+        let status = handler
+            .create_table(Request::new(CreateTableRequest {
+                namespace: NS_NAME.to_string(),
+                name: "platanos".to_string(),
+                partition_template: None,
+            }))
+            .await
+            .expect_err("duplicate table should be rejected");
+        assert_eq!(status.code(), Code::AlreadyExists);
+    }
+
+    #[tokio::test]
+    async fn test_create_table_errors() {
+        let (_catalog, handler) = setup().await;
+
+        let status = handler
+            .create_table(Request::new(CreateTableRequest {
+                namespace: "unknown".to_string(),
+                name: "platanos".to_string(),
+                partition_template: None,
+            }))
+            .await
+            .expect_err("unknown namespace should be rejected");
+        assert_eq!(status.code(), Code::NotFound);
+
+        let status = handler
+            .create_table(Request::new(CreateTableRequest {
+                namespace: NS_NAME.to_string(),
+                name: String::new(),
+                partition_template: None,
+            }))
+            .await
+            .expect_err("empty table name should be rejected");
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let status = handler
+            .create_table(Request::new(CreateTableRequest {
+                namespace: NS_NAME.to_string(),
+                name: "platanos".to_string(),
+                partition_template: Some(template_proto::PartitionTemplate { parts: vec![] }),
+            }))
+            .await
+            .expect_err("empty partition template should be rejected");
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_delete_table() {
+        let (catalog, handler) = setup().await;
+
+        let created = handler
+            .create_table(Request::new(CreateTableRequest {
+                namespace: NS_NAME.to_string(),
+                name: "platanos".to_string(),
+                partition_template: None,
+            }))
+            .await
+            .expect("failed to create table")
+            .into_inner()
+            .table
+            .expect("no table in response");
+
+        handler
+            .delete_table(Request::new(DeleteTableRequest {
+                namespace: NS_NAME.to_string(),
+                name: "platanos".to_string(),
+            }))
+            .await
+            .expect("failed to delete table");
+
+        // The table is soft-deleted in the catalog.
+        let table = catalog
+            .repositories()
+            .await
+            .tables()
+            .get_by_id(data_types::TableId::new(created.id))
+            .await
+            .unwrap()
+            .expect("table must exist");
+        assert!(table.deleted_at.is_some());
+
+        // Deleting it again reports it as not found.
+        let status = handler
+            .delete_table(Request::new(DeleteTableRequest {
+                namespace: NS_NAME.to_string(),
+                name: "platanos".to_string(),
+            }))
+            .await
+            .expect_err("deleted table should not be found");
+        assert_eq!(status.code(), Code::NotFound);
+
+        let status = handler
+            .delete_table(Request::new(DeleteTableRequest {
+                namespace: "unknown".to_string(),
+                name: "platanos".to_string(),
+            }))
+            .await
+            .expect_err("unknown namespace should be rejected");
+        assert_eq!(status.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_delete_column() {
+        let (catalog, handler) = setup().await;
+
+        let table = handler
+            .create_table(Request::new(CreateTableRequest {
+                namespace: NS_NAME.to_string(),
+                name: "platanos".to_string(),
+                partition_template: None,
+            }))
+            .await
+            .expect("failed to create table")
+            .into_inner()
+            .table
+            .expect("no table in response");
+        let table_id = data_types::TableId::new(table.id);
+
+        {
+            let mut repos = catalog.repositories().await;
+            repos
+                .columns()
+                .create_or_get("time", table_id, ColumnType::Time)
+                .await
+                .unwrap();
+            repos
+                .columns()
+                .create_or_get("typo", table_id, ColumnType::Tag)
+                .await
+                .unwrap();
+        }
+
+        handler
+            .delete_column(Request::new(DeleteColumnRequest {
+                namespace: NS_NAME.to_string(),
+                table: "platanos".to_string(),
+                name: "typo".to_string(),
+            }))
+            .await
+            .expect("failed to delete column");
+
+        // Only the time column remains.
+        let columns = catalog
+            .repositories()
+            .await
+            .columns()
+            .list_by_table_id(table_id)
+            .await
+            .unwrap();
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0].name, "time");
+
+        let status = handler
+            .delete_column(Request::new(DeleteColumnRequest {
+                namespace: NS_NAME.to_string(),
+                table: "platanos".to_string(),
+                name: "typo".to_string(),
+            }))
+            .await
+            .expect_err("deleted column should not be found");
+        assert_eq!(status.code(), Code::NotFound);
+
+        let status = handler
+            .delete_column(Request::new(DeleteColumnRequest {
+                namespace: NS_NAME.to_string(),
+                table: "platanos".to_string(),
+                name: "time".to_string(),
+            }))
+            .await
+            .expect_err("time column should not be deleted");
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let status = handler
+            .delete_column(Request::new(DeleteColumnRequest {
+                namespace: NS_NAME.to_string(),
+                table: "bananas".to_string(),
+                name: "typo".to_string(),
+            }))
+            .await
+            .expect_err("unknown table should be rejected");
+        assert_eq!(status.code(), Code::NotFound);
+    }
+}
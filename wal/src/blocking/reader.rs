@@ -12,7 +12,13 @@ use std::{
 };
 
 #[derive(Debug)]
-pub struct ClosedSegmentFileReader<R>(R);
+pub struct ClosedSegmentFileReader<R> {
+    inner: R,
+
+    /// The number of bytes making up the header and the entries that have
+    /// been read successfully.
+    bytes_read: u64,
+}
 
 impl ClosedSegmentFileReader<BufReader<File>> {
     pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
@@ -28,34 +34,47 @@ where
     R: Read,
 {
     pub fn new(f: R) -> Self {
-        Self(f)
+        Self {
+            inner: f,
+            bytes_read: 0,
+        }
+    }
+
+    /// The number of bytes of the header and valid entries read so far.
+    ///
+    /// If reading stops at the first error, this is the offset of the first
+    /// byte that could not be read.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
     }
 
     fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
         let mut data = [0u8; N];
-        self.0
+        self.inner
             .read_exact(&mut data)
             .context(UnableToReadArraySnafu { length: N })?;
         Ok(data)
     }
 
     pub fn read_header(&mut self) -> Result<(FileTypeIdentifier, SegmentIdBytes)> {
-        Ok((self.read_array()?, self.read_array()?))
+        let header: (FileTypeIdentifier, SegmentIdBytes) = (self.read_array()?, self.read_array()?);
+        self.bytes_read += (header.0.len() + header.1.len()) as u64;
+        Ok(header)
     }
 
     fn one_entry(&mut self) -> Result<Option<SegmentEntry>> {
-        let expected_checksum = match self.0.read_u32::<BigEndian>() {
+        let expected_checksum = match self.inner.read_u32::<BigEndian>() {
             Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
             other => other.context(UnableToReadChecksumSnafu)?,
         };
 
-        let expected_len = self
-            .0
+        let expected_len: u64 = self
+            .inner
             .read_u32::<BigEndian>()
             .context(UnableToReadLengthSnafu)?
             .into();
 
-        let compressed_read = self.0.by_ref().take(expected_len);
+        let compressed_read = self.inner.by_ref().take(expected_len);
         let hashing_read = CrcReader::new(compressed_read);
         let mut decompressing_read = FrameDecoder::new(hashing_read);
 
@@ -82,6 +101,9 @@ where
             }
         );
 
+        // The checksum and length prefix, followed by the compressed data.
+        self.bytes_read += 8 + expected_len;
+
         Ok(Some(SegmentEntry { data }))
     }
 
@@ -322,6 +344,28 @@ mod tests {
         assert!(entry.is_none());
     }
 
+    #[test]
+    fn bytes_read_stops_at_truncated_entry() {
+        let mut segment_file = FakeSegmentFile::new();
+        segment_file.add_entry(FakeSegmentEntry::new(b"hello"));
+        let complete = segment_file.data();
+        segment_file.add_entry(FakeSegmentEntry::new(b"goodbye"));
+
+        // Simulate a torn write of the last entry.
+        let mut data = segment_file.data();
+        data.truncate(data.len() - 3);
+
+        let mut reader = ClosedSegmentFileReader::new(data.as_slice());
+        reader.read_header().unwrap();
+        assert_eq!(reader.bytes_read(), 16);
+
+        reader.one_entry().unwrap().unwrap();
+        assert_eq!(reader.bytes_read(), complete.len() as u64);
+
+        assert_error!(reader.one_entry(), Error::UnableToReadData { .. });
+        assert_eq!(reader.bytes_read(), complete.len() as u64);
+    }
+
     #[derive(Debug)]
     struct FakeSegmentFile {
         id: SegmentId,
@@ -123,6 +123,11 @@ pub enum Error {
         path: PathBuf,
     },
 
+    QuarantineClosedSegment {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
     UnableToWrite {
         source: blocking::WriterError,
     },
@@ -180,6 +185,9 @@ type FileTypeIdentifier = [u8; 8];
 const FILE_TYPE_IDENTIFIER: &FileTypeIdentifier = b"INFLUXV3";
 /// File extension for segment files.
 const SEGMENT_FILE_EXTENSION: &str = "dat";
+/// Name of the directory within the WAL root that holds quarantined segment
+/// files.
+const QUARANTINE_DIRECTORY: &str = "quarantine";
 
 /// The main type representing one WAL for one ingester instance.
 ///
@@ -399,6 +407,32 @@ impl Wal {
             .context(SegmentNotFoundSnafu { id })?;
         std::fs::remove_file(&closed.path).context(DeleteClosedSegmentSnafu { path: closed.path })
     }
+
+    /// Moves the specified segment out of the WAL and into the quarantine
+    /// directory, returning the new path of the segment file.
+    ///
+    /// Quarantined segments are no longer part of the WAL and will not be
+    /// replayed, but are retained for inspection.
+    pub async fn quarantine(&self, id: SegmentId) -> Result<PathBuf> {
+        let closed = self
+            .segments
+            .lock()
+            .closed_segments
+            .remove(&id)
+            .context(SegmentNotFoundSnafu { id })?;
+
+        let dir = self.root.join(QUARANTINE_DIRECTORY);
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .context(QuarantineClosedSegmentSnafu { path: &dir })?;
+
+        let path = build_segment_path(dir, id);
+        tokio::fs::rename(&closed.path, &path)
+            .await
+            .context(QuarantineClosedSegmentSnafu { path: &path })?;
+
+        Ok(path)
+    }
 }
 
 impl Drop for Wal {
@@ -522,6 +556,12 @@ impl ClosedSegmentFileReader {
         self.file.next_batch().context(UnableToReadNextOpsSnafu)
     }
 
+    /// The number of bytes of the segment file that have been read without
+    /// error, including the file header.
+    pub fn bytes_read(&self) -> u64 {
+        self.file.bytes_read()
+    }
+
     /// Return the segment file id
     pub fn id(&self) -> SegmentId {
         self.id
@@ -643,7 +683,65 @@ mod tests {
 
     // open wal with files that aren't segments (should log and skip)
 
-    // read segment works even if last entry is truncated
+    #[tokio::test]
+    async fn read_truncated_segment() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let wal = Wal::new(&dir.path()).await.unwrap();
+
+        let op1 = SequencedWalOp {
+            sequence_number: 0,
+            op: WalOp::Write(test_data("m1,t=foo v=1i 1")),
+        };
+        let op2 = SequencedWalOp {
+            sequence_number: 1,
+            op: WalOp::Write(test_data("m1,t=foo v=2i 2")),
+        };
+
+        wal.write_op(op1.clone()).changed().await.unwrap();
+        let (segment, _) = wal.rotate().unwrap();
+        let valid_len = segment.size();
+
+        // Append a second entry to the same segment by writing it into a new
+        // segment and copying its entry bytes, then tear the final write.
+        wal.write_op(op2).changed().await.unwrap();
+        let (other, _) = wal.rotate().unwrap();
+        let other_data = std::fs::read(&other.path).unwrap();
+        let mut data = std::fs::read(&segment.path).unwrap();
+        data.extend_from_slice(&other_data[16..other_data.len() - 3]);
+        std::fs::write(&segment.path, data).unwrap();
+
+        // The complete entry is readable, the torn one is not.
+        let mut reader = wal.reader_for_segment(segment.id()).unwrap();
+        assert_eq!(reader.next_batch().unwrap(), Some(vec![op1]));
+        assert_eq!(reader.bytes_read(), valid_len);
+        assert!(reader.next_batch().is_err());
+        assert_eq!(reader.bytes_read(), valid_len);
+    }
+
+    #[tokio::test]
+    async fn quarantine_segment() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let wal = Wal::new(&dir.path()).await.unwrap();
+
+        let (closed, _) = wal.rotate().unwrap();
+        assert_eq!(wal.closed_segments().len(), 1);
+
+        let path = wal.quarantine(closed.id()).await.unwrap();
+        assert!(path.exists());
+        assert!(!closed.path.exists());
+        assert!(wal.closed_segments().is_empty());
+
+        // Quarantining a segment that is not part of the WAL fails.
+        assert!(wal.quarantine(closed.id()).await.is_err());
+        drop(wal);
+
+        // The quarantined segment is not loaded when the WAL is reopened,
+        // while the segment that was open is.
+        let wal = Wal::new(&dir.path()).await.unwrap();
+        let ids: Vec<_> = wal.closed_segments().iter().map(|c| c.id()).collect();
+        assert!(!ids.contains(&closed.id()));
+        assert_eq!(ids.len(), 1);
+    }
 
     #[tokio::test]
     async fn rotate_without_writes() {
@@ -704,6 +802,8 @@ mod tests {
             database_id: 42,
             predicate: None,
             table_name: "bananas".into(),
+            table_id: 44,
+            tombstone_sequence_number: 1,
         }
     }
 